@@ -1,6 +1,16 @@
 #pragma once
 
 #include <stdint.h>
+#ifndef __cplusplus
+#include <stdbool.h>
+#endif
+
+// Version of the C API. The major version changes whenever a struct layout, an enum value or a
+// function signature changes incompatibly, and with it the version node of the exported symbols;
+// the minor version changes when functions, structs or enum values are added.
+#define WIFI_API_VERSION_MAJOR 2
+#define WIFI_API_VERSION_MINOR 0
+#define WIFI_API_VERSION ((WIFI_API_VERSION_MAJOR << 16) | WIFI_API_VERSION_MINOR)
 
 #ifdef __cplusplus
 extern "C" {
@@ -9,6 +19,18 @@ extern "C" {
 // Opaque pointer to WifiManager
 typedef struct WifiManager WifiManager;
 
+// Log level enum for C API
+typedef enum {
+    WIFI_LOG_DEBUG = 0,
+    WIFI_LOG_INFO = 1,
+    WIFI_LOG_WARNING = 2,
+    WIFI_LOG_ERROR = 3
+} WifiLogLevel;
+
+// Receives a log message of the library; may be called from any thread
+// message is only valid during the call
+typedef void (*WifiLogCallback)(WifiLogLevel level, const char* message, void* user_data);
+
 // Network information struct for C API
 typedef struct {
     const char* ssid;
@@ -17,8 +39,22 @@ typedef struct {
     int32_t security_type;
     int32_t channel;
     int32_t frequency;
+    int32_t pmf;          // WifiPmfMode
+    int32_t wps_enabled;  // non-zero if the AP advertises WPS
+    int32_t eht;          // non-zero if the AP supports Wi-Fi 7 (802.11be)
+    int32_t mlo;          // non-zero if the AP is part of a multi-link device
+    const uint8_t* ie_data;  // information elements from the beacon or probe response, NULL if not reported
+    int32_t ie_len;          // length of ie_data in bytes
 } WifiNetworkInfo;
 
+// Protected Management Frames enum for C API
+typedef enum {
+    WIFI_PMF_UNKNOWN = 0,
+    WIFI_PMF_DISABLED = 1,
+    WIFI_PMF_CAPABLE = 2,
+    WIFI_PMF_REQUIRED = 3
+} WifiPmfMode;
+
 // Connection status enum for C API
 typedef enum {
     WIFI_STATUS_CONNECTED = 0,
@@ -27,9 +63,500 @@ typedef enum {
     WIFI_STATUS_ERROR = 3
 } WifiConnectionStatus;
 
-// Create a new WifiManager instance
+// Reason the last connect, disconnect or hotspot call failed, for C API
+typedef enum {
+    WIFI_ERROR_NONE = 0,
+    WIFI_ERROR_OPERATION_FAILED = 1,
+    WIFI_ERROR_UNSUPPORTED = 2,
+    WIFI_ERROR_INVALID_CREDENTIALS = 3,
+    WIFI_ERROR_ADAPTER_NOT_FOUND = 4,
+    WIFI_ERROR_PERMISSION_DENIED = 5,
+    WIFI_ERROR_TIMEOUT = 6,
+    WIFI_ERROR_DRIVER_ERROR = 7,
+    WIFI_ERROR_CANCELLED = 8
+} WifiErrorCode;
+
+// EAP method enum for C API
+typedef enum {
+    WIFI_EAP_PEAP = 0,
+    WIFI_EAP_TLS = 1,
+    WIFI_EAP_TTLS = 2,
+    WIFI_EAP_SIM = 3,
+    WIFI_EAP_AKA = 4,
+    WIFI_EAP_AKA_PRIME = 5
+} WifiEapMethod;
+
+// Phase 2 (inner) authentication enum for C API
+typedef enum {
+    WIFI_PHASE2_MSCHAPV2 = 0,
+    WIFI_PHASE2_PAP = 1
+} WifiPhase2Method;
+
+// Enterprise (802.1X) credentials for C API
+// Optional string fields may be NULL
+typedef struct {
+    int32_t method;                  // WifiEapMethod
+    int32_t phase2;                  // WifiPhase2Method, used by TTLS
+    const char* identity;            // optional for SIM/AKA (derived from the IMSI)
+    const char* anonymous_identity;  // optional outer identity
+    const char* password;            // SIM PIN for SIM/AKA
+    const char* ca_cert;             // path to the CA certificate; connecting with validation needs it
+                                     // or server_cert_hash, Passpoint uses the system store without it
+    
+    // RADIUS server certificate validation
+    int32_t disable_server_validation; // non-zero skips validation (insecure)
+    const char* server_cert_hash;    // optional SHA-256 pin of the server certificate (hex)
+    const char* domain_match;        // optional expected server name
+    const char* domain_suffix_match; // optional expected server name suffix
+    
+    // EAP-TLS client credentials: certificate/key files, a PKCS#12 blob,
+    // or a reference into the OS certificate store
+    const char* client_cert;
+    const char* private_key;
+    const char* private_key_password;
+    const uint8_t* pkcs12_data;
+    int32_t pkcs12_len;
+    const char* cert_store_ref;
+} WifiEapConfig;
+
+// Additional connection settings for C API
+typedef struct {
+    int32_t ephemeral;               // non-zero never stores a profile and forgets the network on disconnect
+    const int32_t* frequencies;      // restrict association to these frequencies in MHz, a hint where unsupported
+    int32_t frequency_count;         // 0 allows any frequency
+    int32_t hidden;                  // non-zero for a network that does not broadcast its SSID
+    const char* bssid;               // optional access point to join, NULL for any
+    int32_t timeout_seconds;         // association timeout, 0 for the platform default
+    const char* ipv4_address;        // optional static address in CIDR notation, NULL for DHCP
+    const char* ipv4_gateway;        // optional default route for the static address
+    const char* ipv6_address;        // optional static address in CIDR notation
+    const char* ipv6_gateway;        // optional default route for the static IPv6 address
+    const char* dns_servers;         // optional comma separated DNS servers, NULL keeps the DHCP-provided ones
+} WifiConnectOptions;
+
+// Directed scan settings for C API
+typedef struct {
+    const char* const* ssids;        // probe for these networks only
+    int32_t ssid_count;              // 0 for any network
+    const int32_t* frequencies;      // scan only these frequencies in MHz
+    int32_t frequency_count;         // 0 for all supported frequencies
+    int32_t passive;                 // non-zero listens for beacons without sending probe requests
+} WifiScanOptions;
+
+// Target Wake Time agreement parameters for C API
+typedef struct {
+    uint64_t wake_interval_us;
+    uint32_t wake_duration_us;
+    int32_t trigger_enabled;
+    int32_t announced;
+} WifiTwtConfig;
+
+// Target Wake Time status for C API
+typedef struct {
+    int32_t ap_responder;
+    int32_t local_requester;
+    int32_t agreement_requested;
+    WifiTwtConfig config;
+} WifiTwtStatus;
+
+// Wake-on-WLAN packet pattern for C API
+typedef struct {
+    const uint8_t* pattern;
+    const uint8_t* mask;             // (length + 7) / 8 bytes, one bit per pattern byte, LSB first
+    int32_t length;
+    int32_t offset;                  // position in the frame where matching starts
+} WifiWowlanPattern;
+
+// Wake-on-WLAN triggers for C API
+typedef struct {
+    int32_t magic_packet;
+    int32_t disconnect;
+    const WifiWowlanPattern* patterns;
+    int32_t pattern_count;
+} WifiWowlanConfig;
+
+// Wake-on-WLAN triggers currently configured for C API
+typedef struct {
+    int32_t magic_packet;
+    int32_t disconnect;
+    int32_t pattern_count;
+} WifiWowlanStatus;
+
+// Wake-on-WLAN support of the adapter for C API
+typedef struct {
+    int32_t magic_packet;
+    int32_t disconnect;
+    int32_t max_patterns;
+    int32_t min_pattern_length;
+    int32_t max_pattern_length;
+    int32_t max_pattern_offset;
+} WifiWowlanCapabilities;
+
+// 802.11k/v/r support of the current association for C API
+typedef struct {
+    int32_t ap_neighbor_report;
+    int32_t ap_bss_transition;
+    int32_t ap_fast_transition;
+    int32_t local_neighbor_report;
+    int32_t local_bss_transition;
+    int32_t local_fast_transition;
+} WifiRoamingFeatures;
+
+// Roaming policy for C API
+typedef struct {
+    int32_t rssi_threshold;          // scan for a better access point below this signal, in dBm
+    int32_t scan_interval_seconds;   // how often to scan while below the threshold
+} WifiRoamingPolicy;
+
+// Fine Timing Measurement result for C API
+typedef struct {
+    int64_t distance_mm;
+    int64_t distance_variance_mm2;
+    int64_t rtt_ps;
+    int32_t attempts;
+    int32_t successes;
+} WifiRangingResult;
+
+// TDLS peer for C API
+typedef struct {
+    char address[18];
+    char ip_address[46];
+    int32_t linked;
+} WifiTdlsPeer;
+
+// Ad-hoc (IBSS) network settings for C API
+typedef struct {
+    const char* ssid;
+    int32_t channel;
+    const char* password;       // WPA-None passphrase, NULL for an open network
+} WifiAdHocConfig;
+
+// 802.11s mesh settings for C API
+typedef struct {
+    const char* mesh_id;
+    int32_t channel;            // 0 lets the driver pick
+    const char* password;       // SAE password, NULL for an open mesh
+    const char* interface_name; // NULL for the default name
+} WifiMeshConfig;
+
+typedef enum {
+    WIFI_MESH_PLINK_LISTEN = 0,
+    WIFI_MESH_PLINK_OPEN_SENT = 1,
+    WIFI_MESH_PLINK_OPEN_RECEIVED = 2,
+    WIFI_MESH_PLINK_CONFIRM_RECEIVED = 3,
+    WIFI_MESH_PLINK_ESTABLISHED = 4,
+    WIFI_MESH_PLINK_HOLDING = 5,
+    WIFI_MESH_PLINK_BLOCKED = 6,
+    WIFI_MESH_PLINK_UNKNOWN = 7
+} WifiMeshPeerLinkState;
+
+// Mesh neighbor for C API
+typedef struct {
+    char address[18];
+    int32_t link_state;         // WifiMeshPeerLinkState
+    int32_t signal_strength;
+    int32_t inactive_ms;
+} WifiMeshPeer;
+
+// Wi-Fi Aware publication for C API
+typedef struct {
+    const char* service_name;
+    const uint8_t* service_info;
+    int32_t service_info_len;
+    int32_t unsolicited;
+    int32_t solicited;
+    int32_t ttl_seconds;        // 0 until cancelled
+} WifiNanPublishConfig;
+
+// Wi-Fi Aware subscription for C API
+typedef struct {
+    const char* service_name;
+    const uint8_t* service_info;
+    int32_t service_info_len;
+    int32_t active;
+    int32_t ttl_seconds;        // 0 until cancelled
+} WifiNanSubscribeConfig;
+
+typedef enum {
+    WIFI_NAN_EVENT_DISCOVERED = 0,
+    WIFI_NAN_EVENT_RECEIVED = 1,
+    WIFI_NAN_EVENT_TERMINATED = 2
+} WifiNanEventType;
+
+// Wi-Fi Aware event for C API
+typedef struct {
+    int32_t type;               // WifiNanEventType
+    int32_t handle;
+    int32_t peer_instance_id;
+    char peer_address[18];
+    uint8_t service_info[255];
+    int32_t service_info_len;
+} WifiNanEvent;
+
+// WPS method for Wi-Fi Direct connections
+typedef enum {
+    WIFI_P2P_WPS_PBC = 0,
+    WIFI_P2P_WPS_PIN_DISPLAY = 1,
+    WIFI_P2P_WPS_PIN_KEYPAD = 2
+} WifiP2pWpsMethod;
+
+// Wi-Fi Direct peer for C API
+typedef struct {
+    char device_address[18];
+    char device_name[64];
+    char device_type[32];
+    int32_t group_owner;
+} WifiP2pPeer;
+
+// Wi-Fi Direct connection settings for C API
+typedef struct {
+    const char* peer_address;
+    int32_t wps_method;     // WifiP2pWpsMethod
+    const char* pin;        // 8-digit PIN, required for the PIN methods
+    int32_t go_intent;      // 0-15
+} WifiP2pConnectConfig;
+
+// Wi-Fi Direct group for C API
+typedef struct {
+    char interface_name[32];
+    char ssid[33];
+    int32_t group_owner;
+    char go_device_address[18];
+    int32_t frequency;
+} WifiP2pGroup;
+
+// Current association for C API
+typedef struct {
+    char ssid[33];
+    char bssid[18];
+    int32_t frequency;
+    int32_t signal_strength;
+    int32_t fast_transition;
+    char ap_mld_address[18];    // empty unless the association is multi-link
+    int32_t noise;              // dBm, 0 when not reported
+    int32_t link_speed_mbps;    // transmit rate, 0 when not reported
+} WifiConnectionInfo;
+
+// Rates and frame counters of the current association for C API, -1 where not reported
+typedef struct {
+    uint32_t tx_bitrate_kbps;   // 0 when not reported
+    uint32_t rx_bitrate_kbps;   // 0 when not reported
+    int32_t tx_mcs;
+    int32_t rx_mcs;
+    int64_t tx_packets;
+    int64_t rx_packets;
+    int64_t tx_retries;
+    int64_t tx_failed;
+    int64_t rx_dropped;
+} WifiLinkStats;
+
+// Affiliated link of a multi-link association for C API
+typedef struct {
+    int32_t link_id;
+    char ap_link_address[18];
+    int32_t frequency;
+    int32_t signal_strength;
+} WifiMloLink;
+
+// Channel permitted by the regulatory domain for C API
+typedef struct {
+    int32_t frequency;
+    int32_t max_power_mbm;      // 1/100 dBm
+    int32_t dfs;
+    int32_t no_ir;
+} WifiChannelInfo;
+
+// Link state for connection diagnostics, strings are empty when unknown
+typedef struct {
+    int32_t adapter_present;
+    int32_t radio_enabled;
+    int32_t associated;
+    int32_t authenticated;
+    char ssid[33];
+    char bssid[18];
+    char ip_address[46];
+    char gateway[46];
+    char dns_servers[4][46];
+    int32_t dns_server_count;
+} WifiLinkDiagnostics;
+
+// IP configuration of the wireless interface for C API, strings are empty when unknown
+typedef struct {
+    char ipv4_addresses[4][50];      // CIDR notation
+    int32_t ipv4_address_count;
+    char ipv6_addresses[8][50];      // CIDR notation
+    int32_t ipv6_address_count;
+    char gateway[46];
+    char ipv6_gateway[46];
+    char dns_servers[4][46];
+    int32_t dns_server_count;
+    int32_t dhcp;                    // non-zero if the IPv4 address was leased over DHCP
+    char dhcp_server[46];
+    int64_t lease_obtained;          // Unix time, 0 if unknown
+    int64_t lease_expires;           // Unix time, 0 if unknown
+} WifiIpInfo;
+
+// Hotspot settings for C API
+typedef struct {
+    const char* ssid;
+    const char* password;            // optional, NULL for an open hotspot
+    int32_t channel;                 // 0 lets the platform choose
+    const char* dhcp_range_start;    // optional first client address, NULL for the platform's addressing
+    const char* dhcp_range_end;      // last client address
+    const char* gateway;             // address of the hotspot, NULL for the platform's addressing
+    int32_t prefix_length;           // subnet prefix of the gateway and the range
+    const char* dns_servers;         // optional comma separated DNS servers, NULL announces the gateway
+} WifiHotspotConfig;
+
+// Device associated with the hotspot for C API
+typedef struct {
+    char mac_address[18];
+    char ip_address[46];             // empty until a DHCP lease is handed out
+    char hostname[64];               // empty if the client sent none
+    int32_t signal_strength;         // dBm, 0 if unknown
+    int32_t connected_seconds;       // -1 if unknown
+} WifiHotspotClient;
+
+// Channel survey entry for C API, times are -1 when not reported
+typedef struct {
+    int32_t frequency;
+    int32_t channel;
+    int32_t in_use;
+    int32_t noise;                   // dBm, 0 when not reported
+    int64_t active_time_ms;
+    int64_t busy_time_ms;
+    int64_t receive_time_ms;
+    int64_t transmit_time_ms;
+} WifiChannelSurvey;
+
+// Saved network profile for C API
+typedef struct {
+    char name[256];
+    char ssid[33];
+    int32_t security_type;           // WifiSecurityType
+    int32_t auto_connect;
+} WifiSavedProfile;
+
+// Wireless adapter for C API
+typedef struct {
+    char name[64];
+    char mac_address[18];
+    char driver[64];
+    int32_t selected;                // the interface the manager operates on
+    int32_t supports_ap;
+    int32_t supports_monitor;
+    int32_t supports_mesh;
+    int32_t supports_p2p;
+    int32_t band_2ghz;
+    int32_t band_5ghz;
+    int32_t band_6ghz;
+} WifiInterfaceInfo;
+
+// Monitor mode capture settings for C API
+typedef struct {
+    int32_t channel;                 // channel to capture on, 0 keeps the current one
+    int32_t include_management;      // non-zero to deliver management frames
+    int32_t include_control;         // non-zero to deliver control frames
+    int32_t include_data;            // non-zero to deliver data frames
+    const char* monitor_interface;   // optional monitor interface name
+    const int32_t* hop_channels;     // optional channels to cycle through
+    int32_t hop_channel_count;
+    int32_t dwell_ms;                // time spent on each hop channel
+} WifiCaptureConfig;
+
+// Receives a captured frame, starting with its radiotap header
+// Called from the capture thread; data is only valid during the call
+typedef void (*WifiFrameCallback)(const uint8_t* data, int32_t length, uint64_t timestamp_us, void* user_data);
+
+// Connectivity-change callback for C API, called on a platform thread
+typedef void (*WifiNetworkChangeCallback)(void* user_data);
+
+// Signal monitor callback for C API, called from the monitor thread while associated
+// The reading is only valid during the call
+typedef void (*WifiSignalCallback)(const WifiConnectionInfo* info, void* user_data);
+
+// Stage of a WPS enrollment for C API
+typedef enum {
+    WIFI_WPS_SEARCHING = 0,
+    WIFI_WPS_EXCHANGING = 1,
+    WIFI_WPS_CREDENTIALS_RECEIVED = 2,
+    WIFI_WPS_CONNECTED = 3,
+    WIFI_WPS_FAILED = 4
+} WifiWpsStage;
+
+// Reason a WPS enrollment failed for C API
+typedef enum {
+    WIFI_WPS_FAILURE_NONE = 0,
+    WIFI_WPS_FAILURE_TIMEOUT = 1,
+    WIFI_WPS_FAILURE_SESSION_OVERLAP = 2,
+    WIFI_WPS_FAILURE_WRONG_PIN = 3,
+    WIFI_WPS_FAILURE_SETUP_LOCKED = 4,
+    WIFI_WPS_FAILURE_REJECTED = 5
+} WifiWpsFailure;
+
+typedef struct {
+    int32_t stage;                   // WifiWpsStage
+    int32_t failure;                 // WifiWpsFailure, set with WIFI_WPS_FAILED
+    int32_t config_error;            // WPS configuration error from the registrar, 0 if none
+} WifiWpsProgress;
+
+// WPS progress callback for C API, called on the thread running the connection
+// The progress is only valid during the call
+typedef void (*WifiWpsProgressCallback)(const WifiWpsProgress* progress, void* user_data);
+
+// Connection event types for C API
+typedef enum {
+    WIFI_EVENT_CONNECTED = 0,
+    WIFI_EVENT_CONNECTION_LOST = 1,
+    WIFI_EVENT_ROAMED = 2,
+    WIFI_EVENT_SCAN_COMPLETE = 3,
+    WIFI_EVENT_SIGNAL_CHANGED = 4,
+    WIFI_EVENT_HOTSPOT_CLIENT_JOINED = 5,
+    WIFI_EVENT_HOTSPOT_CLIENT_LEFT = 6
+} WifiEventType;
+
+// Connection event for C API, fields not used by the event type are empty
+typedef struct {
+    int32_t type;                 // WifiEventType
+    char ssid[33];
+    char bssid[18];
+    int32_t signal_strength;      // dBm, 0 if unknown
+    int32_t signal_low;           // non-zero when the signal fell below the threshold
+    char client_address[18];
+} WifiEventData;
+
+// Connection event callback for C API, called on a platform thread
+// The event is only valid during the call
+typedef void (*WifiEventCallback)(const WifiEventData* event, void* user_data);
+
+// Passpoint (Hotspot 2.0) credential for C API
+typedef struct {
+    const char* domain;              // home service provider FQDN
+    const char* realm;               // NAI realm
+    const char* home_ois;            // optional comma separated roaming consortium OIs (hex)
+    const char* friendly_name;       // optional display name
+    WifiEapConfig credentials;       // TTLS username/password or TLS certificate
+} WifiPasspointProfile;
+
+// Version of the C API the library was built with, as WIFI_API_VERSION
+// Bindings that load the library at runtime should refuse a different major version
+uint32_t wifi_api_version(void);
+
+// Send the library's log messages to callback instead of standard output, for every manager
+// of the process; NULL restores standard output
+void wifi_set_log_callback(WifiLogCallback callback, void* user_data);
+
+// Drop log messages below level, WIFI_LOG_INFO by default
+void wifi_set_log_level(WifiLogLevel level);
+
+// Create a new WifiManager instance. A manager may be shared between
+// threads; calls on it are serialized.
 WifiManager* wifi_manager_new();
 
+// Create a WifiManager operating on the named adapter, e.g. "wlan1"
+// Returns NULL if no usable wireless interface has that name
+WifiManager* wifi_manager_new_with_interface(const char* interface_name);
+
 // Delete a WifiManager instance
 void wifi_manager_delete(WifiManager* manager);
 
@@ -38,11 +565,347 @@ void wifi_manager_delete(WifiManager* manager);
 // The caller must free the returned array using wifi_free_network_info
 WifiNetworkInfo* wifi_manager_scan(WifiManager* manager, int* count);
 
+// Scan only the given networks or frequencies, which is much faster than a full scan
+// Platforms that cannot direct a scan run a full one and filter the results
+// The caller must free the returned array using wifi_free_network_info
+WifiNetworkInfo* wifi_manager_scan_with_options(WifiManager* manager, const WifiScanOptions* options, int* count);
+
+// As wifi_manager_scan_with_options, with options NULL for a full scan, waiting at most timeout_ms
+// for the scan to complete (0 for the manager's scan timeout). A scan still running then is aborted
+// where the platform allows it, and the results gathered so far are returned
+WifiNetworkInfo* wifi_manager_scan_with_timeout(WifiManager* manager, const WifiScanOptions* options, int32_t timeout_ms,
+                                                int* count);
+
+// Abort a scan or connect that another thread is running on the manager; it fails with
+// WIFI_ERROR_CANCELLED after stopping the platform operation (aborting the scan, dropping the
+// association attempt). Unlike other calls this does not wait for the call in progress.
+// Returns false if no scan or connect was in progress. On macOS the calls cannot be interrupted
+bool wifi_manager_cancel(WifiManager* manager);
+
+// Longest waits in milliseconds for a scan to complete and for a hotspot's access point to start,
+// for calls that do not set their own; 0 restores the defaults of 10 and 90 seconds.
+// A hotspot that does not start in time fails with WIFI_ERROR_TIMEOUT
+bool wifi_manager_set_timeouts(WifiManager* manager, int32_t scan_timeout_ms, int32_t hotspot_timeout_ms);
+
+// Scan and wait for the platform to report the scan's completion, so no cached results are returned
+// Returns false if the scan failed or did not complete; see wifi_manager_get_last_error
+// On success networks holds count entries (NULL if none), to be freed using wifi_free_network_info
+bool wifi_manager_scan_fresh(WifiManager* manager, WifiNetworkInfo** networks, int* count);
+
+// Information elements of an access point in the most recent scan, as received in its beacon or probe response
+// Scan results already carry them in ie_data; this looks them up again by BSSID
+// Copies up to size bytes into buffer, which may be NULL to query the length, and returns the full length,
+// or -1 if the access point was not in the most recent scan or the platform does not report its elements
+int wifi_manager_get_information_elements(WifiManager* manager, const char* bssid, uint8_t* buffer, int size);
+
 // Connect to a network
 // If password is NULL, it will attempt to connect to an open network
 // Returns true if the connection was initiated successfully
 bool wifi_manager_connect(WifiManager* manager, const char* ssid, const char* password);
 
+// Connect to a network with additional options
+// Fails if the platform cannot honour one of the options
+bool wifi_manager_connect_with_options(WifiManager* manager, const char* ssid, const char* password, const WifiConnectOptions* options);
+
+// Connect to a WPA2/WPA3-Enterprise network using 802.1X credentials
+// Only supported on Linux; elsewhere fails with WIFI_ERROR_UNSUPPORTED
+// Returns true if the connection was initiated successfully
+bool wifi_manager_connect_enterprise(WifiManager* manager, const char* ssid, const WifiEapConfig* eap);
+
+// Connect using WPS push-button configuration
+// Blocks until the credentials are received and the connection is established,
+// or until timeout_seconds have elapsed
+bool wifi_manager_connect_wps_pbc(WifiManager* manager, int32_t timeout_seconds);
+
+// Connect using a WPS PIN
+// bssid may be NULL to accept any access point running a WPS PIN session
+bool wifi_manager_connect_wps_pin(WifiManager* manager, const char* bssid, const char* pin, int32_t timeout_seconds);
+
+// Same as the functions above, reporting each stage of the exchange to callback
+// wifi_manager_get_last_error tells a wrong PIN (WIFI_ERROR_INVALID_CREDENTIALS) and a timeout apart
+bool wifi_manager_connect_wps_pbc_with_progress(WifiManager* manager, int32_t timeout_seconds,
+                                                WifiWpsProgressCallback callback, void* user_data);
+bool wifi_manager_connect_wps_pin_with_progress(WifiManager* manager, const char* bssid, const char* pin, int32_t timeout_seconds,
+                                                WifiWpsProgressCallback callback, void* user_data);
+
+// Check whether the platform can authenticate with the given WifiEapMethod
+bool wifi_manager_is_eap_method_supported(WifiManager* manager, int32_t method);
+
+// Install a Passpoint credential so matching hotspots are joined automatically
+// Returns true if the credential was stored; see wifi_manager_get_last_error otherwise
+bool wifi_manager_install_passpoint(WifiManager* manager, const WifiPasspointProfile* profile);
+
+// Remove a previously installed Passpoint credential by its home domain
+bool wifi_manager_remove_passpoint(WifiManager* manager, const char* domain);
+
+// Enable or disable probe-request privacy (randomized MAC addresses, no directed probes)
+// Returns false if the platform cannot provide it
+bool wifi_manager_set_probe_privacy(WifiManager* manager, bool enabled);
+
+// Check whether probe-request privacy is enabled
+bool wifi_manager_is_probe_privacy_enabled(WifiManager* manager);
+
+// Put a monitor interface into capture mode and deliver raw 802.11 frames to callback
+// Requires root (CAP_NET_ADMIN and CAP_NET_RAW); only one capture can run at a time
+bool wifi_manager_start_capture(WifiManager* manager, const WifiCaptureConfig* config,
+                                WifiFrameCallback callback, void* user_data);
+
+// Stop the running capture and remove the monitor interface
+// No callbacks are made once this returns
+bool wifi_manager_stop_capture(WifiManager* manager);
+
+// Check whether a capture is running
+bool wifi_manager_is_capturing(WifiManager* manager);
+
+// Get per-channel survey data (airtime utilization and noise floor)
+// Returns an array with the length stored in count
+// The caller must free the returned array using wifi_free_channel_survey
+WifiChannelSurvey* wifi_manager_channel_survey(WifiManager* manager, int* count);
+
+// Free the array returned by wifi_manager_channel_survey
+void wifi_free_channel_survey(WifiChannelSurvey* surveys);
+
+// Fill in adapter, radio, association and IP state for connection diagnostics
+// Returns false if the platform cannot report link state
+bool wifi_manager_get_link_diagnostics(WifiManager* manager, WifiLinkDiagnostics* diagnostics);
+
+// Get details of the current association
+// Returns false if not connected or the platform cannot report them
+bool wifi_manager_get_connection_info(WifiManager* manager, WifiConnectionInfo* info);
+
+// Get transmit and receive rates, retries and dropped frames of the current association
+// Returns false if not connected or the platform cannot report them
+bool wifi_manager_get_link_stats(WifiManager* manager, WifiLinkStats* stats);
+
+// How connections are made on Linux
+typedef enum {
+    WIFI_LINUX_BACKEND_AUTO = 0,             // NetworkManager when it manages the interface, wpa_supplicant otherwise
+    WIFI_LINUX_BACKEND_NL80211 = 1,          // the driver associates on its own, open networks only
+    WIFI_LINUX_BACKEND_NETWORK_MANAGER = 2,  // nmcli, which leaves a saved connection profile behind
+    WIFI_LINUX_BACKEND_WPA_CLI = 3           // a wpa_supplicant started by the library
+} WifiLinuxBackend;
+
+// Select how connections are made on Linux
+// Returns false with WIFI_ERROR_UNSUPPORTED on other platforms, or for NetworkManager when it does not manage the interface
+bool wifi_manager_set_linux_backend(WifiManager* manager, WifiLinuxBackend backend);
+
+// The backend connections are made with, never AUTO on Linux; WIFI_LINUX_BACKEND_AUTO on other platforms
+WifiLinuxBackend wifi_manager_get_linux_backend(WifiManager* manager);
+
+// Check whether the process may see network names and BSSIDs
+// macOS hides them from scans and the current connection without Location Services authorization;
+// always true on other platforms
+bool wifi_manager_is_location_authorized(WifiManager* manager);
+
+// Ask the user for Location Services authorization, blocking until they answer (at most 30 seconds)
+// Returns true once it is granted, or if the platform does not require it
+bool wifi_manager_request_location_authorization(WifiManager* manager);
+
+// Check whether the process has the privileges to manage the adapter
+bool wifi_manager_has_admin_privileges(WifiManager* manager);
+
+// Check whether the radio is on, i.e. neither switched off in software nor by a hardware switch
+bool wifi_manager_is_radio_enabled(WifiManager* manager);
+
+// Switch the radio on or off in software
+bool wifi_manager_set_radio_enabled(WifiManager* manager, bool enabled);
+
+// Check whether Wi-Fi Direct (P2P) is available
+bool wifi_manager_is_p2p_supported(WifiManager* manager);
+
+// Search for Wi-Fi Direct peers for the given number of seconds
+// Returns an array of peers that must be freed with wifi_free_p2p_peers
+WifiP2pPeer* wifi_manager_p2p_discover(WifiManager* manager, int32_t timeout_seconds, int* count);
+
+// Free the array returned by wifi_manager_p2p_discover
+void wifi_free_p2p_peers(WifiP2pPeer* peers);
+
+// Negotiate a group with a peer and join it, filling in the resulting group
+bool wifi_manager_p2p_connect(WifiManager* manager, const WifiP2pConnectConfig* config, int32_t timeout_seconds, WifiP2pGroup* group);
+
+// Leave the current Wi-Fi Direct group
+bool wifi_manager_p2p_disconnect(WifiManager* manager);
+
+// Check whether Wi-Fi Aware (NAN) service discovery is available
+bool wifi_manager_is_nan_supported(WifiManager* manager);
+
+// Start advertising a service, returns the publish id or 0 on failure
+int32_t wifi_manager_nan_publish(WifiManager* manager, const WifiNanPublishConfig* config);
+
+// Start looking for a service, returns the subscribe id or 0 on failure
+int32_t wifi_manager_nan_subscribe(WifiManager* manager, const WifiNanSubscribeConfig* config);
+
+// Stop a publication or subscription
+bool wifi_manager_nan_cancel(WifiManager* manager, int32_t handle);
+
+// Send a follow-up message to a discovered peer
+bool wifi_manager_nan_transmit(WifiManager* manager, int32_t handle, int32_t peer_instance_id, const char* peer_address, const uint8_t* data, int32_t data_len);
+
+// Wait up to timeout_ms for the next Wi-Fi Aware event, false on timeout
+bool wifi_manager_nan_next_event(WifiManager* manager, int32_t timeout_ms, WifiNanEvent* event);
+
+// Check whether the adapter can operate as an 802.11s mesh point
+bool wifi_manager_is_mesh_supported(WifiManager* manager);
+
+// Create a mesh interface and join (or start) the mesh with the given ID
+bool wifi_manager_mesh_join(WifiManager* manager, const WifiMeshConfig* config);
+
+// Leave the mesh and remove the mesh interface
+bool wifi_manager_mesh_leave(WifiManager* manager);
+
+// Get the mesh neighbors and their peer link state
+// Returns an array that must be freed with wifi_free_mesh_peers
+WifiMeshPeer* wifi_manager_mesh_peers(WifiManager* manager, int* count);
+
+// Free the array returned by wifi_manager_mesh_peers
+void wifi_free_mesh_peers(WifiMeshPeer* peers);
+
+// Check whether the adapter can operate in ad-hoc (IBSS) mode
+bool wifi_manager_is_adhoc_supported(WifiManager* manager);
+
+// Create or join an ad-hoc network; leave it with wifi_manager_disconnect
+bool wifi_manager_join_adhoc(WifiManager* manager, const WifiAdHocConfig* config);
+
+// Check whether the adapter supports TDLS direct links
+bool wifi_manager_is_tdls_supported(WifiManager* manager);
+
+// Probe the stations of the current BSS for TDLS and list them
+// Returns an array that must be freed with wifi_free_tdls_peers
+WifiTdlsPeer* wifi_manager_tdls_discover(WifiManager* manager, int timeout_seconds, int* count);
+
+// Free the array returned by wifi_manager_tdls_discover
+void wifi_free_tdls_peers(WifiTdlsPeer* peers);
+
+// Set up a direct link to a station of the current BSS
+bool wifi_manager_tdls_setup(WifiManager* manager, const char* peer_address);
+
+// Tear down the direct link to a station
+bool wifi_manager_tdls_teardown(WifiManager* manager, const char* peer_address);
+
+// Check whether the adapter can range with 802.11mc Fine Timing Measurement
+bool wifi_manager_is_rtt_supported(WifiManager* manager);
+
+// Measure the distance to an FTM responder; frequency 0 looks the access point up in a scan
+bool wifi_manager_measure_distance(WifiManager* manager, const char* bssid, int frequency, WifiRangingResult* result);
+
+// Report 802.11k/v/r support of the connected access point and the local stack
+bool wifi_manager_get_roaming_features(WifiManager* manager, WifiRoamingFeatures* features);
+
+// Re-associate with another access point of the connected network
+bool wifi_manager_roam_to(WifiManager* manager, const char* bssid);
+
+// Set when the station scans for a stronger access point of the connected network
+bool wifi_manager_set_roaming_policy(WifiManager* manager, const WifiRoamingPolicy* policy);
+
+// Report TWT support of the connected access point and the adapter
+bool wifi_manager_get_twt_status(WifiManager* manager, WifiTwtStatus* status);
+
+// Request an individual TWT agreement with the connected access point
+bool wifi_manager_twt_setup(WifiManager* manager, const WifiTwtConfig* config);
+
+// Tear down the TWT agreement
+bool wifi_manager_twt_teardown(WifiManager* manager);
+
+// Get the links of a Wi-Fi 7 multi-link association, empty for single-link associations
+// Returns an array that must be freed with wifi_free_mlo_links
+WifiMloLink* wifi_manager_get_mlo_links(WifiManager* manager, int* count);
+
+// Free the array returned by wifi_manager_get_mlo_links
+void wifi_free_mlo_links(WifiMloLink* links);
+
+// Get the regulatory domain as an ISO 3166-1 alpha-2 country code, "00" for the world domain
+// Returns false if the domain is unknown
+bool wifi_manager_get_regulatory_domain(WifiManager* manager, char* country, int country_size);
+
+// Check whether the application may change the regulatory domain
+bool wifi_manager_can_set_regulatory_domain(WifiManager* manager);
+
+// Set the regulatory domain to an ISO 3166-1 alpha-2 country code
+bool wifi_manager_set_regulatory_domain(WifiManager* manager, const char* country);
+
+// Get the channels the regulatory domain permits on the adapter, all bands
+// Returns an array that must be freed with wifi_free_channel_info
+WifiChannelInfo* wifi_manager_get_allowed_channels(WifiManager* manager, int* count);
+
+// Free the array returned by wifi_manager_get_allowed_channels
+void wifi_free_channel_info(WifiChannelInfo* channels);
+
+// Get the name of the station interface, e.g. "wlan0"
+// Returns false if the platform does not name its interfaces
+bool wifi_manager_get_interface_name(WifiManager* manager, char* name, int name_size);
+
+// Get the Wake-on-WLAN triggers the adapter supports
+// Returns false if the adapter or platform has no WoWLAN support
+bool wifi_manager_get_wowlan_capabilities(WifiManager* manager, WifiWowlanCapabilities* capabilities);
+
+// Get the configured Wake-on-WLAN triggers
+bool wifi_manager_get_wowlan(WifiManager* manager, WifiWowlanStatus* status);
+
+// Configure Wake-on-WLAN, a config without triggers disables it
+bool wifi_manager_set_wowlan(WifiManager* manager, const WifiWowlanConfig* config);
+
+// Watch for connectivity changes reported by the OS, including those made by other software
+// Only one observer can be active at a time
+bool wifi_manager_start_network_observer(WifiManager* manager, WifiNetworkChangeCallback callback, void* user_data);
+
+// Stop watching for connectivity changes
+// No callbacks are made once this returns
+bool wifi_manager_stop_network_observer(WifiManager* manager);
+
+// Check whether the platform has a profile store this library can use
+bool wifi_manager_is_profile_store_supported(WifiManager* manager);
+
+// Check whether a stored WiFi profile with the given name exists
+bool wifi_manager_has_profile(WifiManager* manager, const char* name);
+
+// Connect with the credentials and settings of a stored profile
+bool wifi_manager_connect_profile(WifiManager* manager, const char* name);
+
+// Receive connection events (connected, lost, roamed, scan complete, signal threshold
+// crossings at signal_threshold dBm, hotspot clients) without polling
+// Only one callback can be registered at a time
+bool wifi_manager_register_callback(WifiManager* manager, WifiEventCallback callback, void* user_data, int32_t signal_threshold);
+
+// Remove the event callback
+// No callbacks are made once this returns
+bool wifi_manager_unregister_callback(WifiManager* manager);
+
+// List the wireless adapters present on the system
+// Returns an array of WifiInterfaceInfo and sets count to the number of entries
+// The caller must free the returned array using wifi_free_interface_list
+WifiInterfaceInfo* wifi_manager_list_interfaces(WifiManager* manager, int* count);
+
+// Free the array returned by wifi_manager_list_interfaces
+void wifi_free_interface_list(WifiInterfaceInfo* interfaces);
+
+// List the WiFi profiles in the platform profile store
+// Returns an array of WifiSavedProfile and sets count to the number of entries
+// The caller must free the returned array using wifi_free_profile_list
+WifiSavedProfile* wifi_manager_list_profiles(WifiManager* manager, int* count);
+
+// Free the array returned by wifi_manager_list_profiles
+void wifi_free_profile_list(WifiSavedProfile* profiles);
+
+// Save credentials for a network in the platform profile store, named after the SSID
+// password may be NULL for an open network; an existing profile of the same name is replaced
+bool wifi_manager_save_profile(WifiManager* manager, const char* ssid, const char* password, bool auto_connect);
+
+// Remove a profile from the platform profile store
+bool wifi_manager_forget_profile(WifiManager* manager, const char* name);
+
+// Enable or disable automatic connection for a saved profile
+bool wifi_manager_set_profile_auto_connect(WifiManager* manager, const char* name, bool enabled);
+
+// Read the current association every interval_ms milliseconds on a background thread
+// and pass each reading to callback; nothing is reported while not associated
+bool wifi_manager_start_signal_monitor(WifiManager* manager, int32_t interval_ms, WifiSignalCallback callback, void* user_data);
+
+// Stop the signal monitor; no callbacks are made once this returns
+bool wifi_manager_stop_signal_monitor(WifiManager* manager);
+
+// Get the addresses, gateways, DNS servers and DHCP lease of the wireless interface
+bool wifi_manager_get_ip_info(WifiManager* manager, WifiIpInfo* info);
+
 // Disconnect from the current network
 // Returns true if the disconnection was successful
 bool wifi_manager_disconnect(WifiManager* manager);
@@ -50,6 +913,12 @@ bool wifi_manager_disconnect(WifiManager* manager);
 // Get the current connection status
 WifiConnectionStatus wifi_manager_get_status(WifiManager* manager);
 
+// Get why the last call on the manager that returns false on failure, such as a connect,
+// hotspot, P2P, mesh, TDLS or regulatory domain call, failed on the calling thread, so threads
+// sharing a manager each see their own errors
+// Returns WIFI_ERROR_NONE after a successful call
+WifiErrorCode wifi_manager_get_last_error(WifiManager* manager);
+
 /**
  * Create an unsecured WiFi hotspot with the given SSID.
  * 
@@ -60,6 +929,57 @@ WifiConnectionStatus wifi_manager_get_status(WifiManager* manager);
  */
 bool wifi_manager_create_hotspot(WifiManager* manager, const char* ssid);
 
+/**
+ * Create a WiFi hotspot with explicit settings.
+ * 
+ * @param manager The WifiManager instance
+ * @param config SSID, optional WPA2 passphrase and channel
+ * @return true if the hotspot was created successfully, false otherwise
+ * @note This operation typically requires administrative privileges
+ */
+bool wifi_manager_create_hotspot_with_config(WifiManager* manager, const WifiHotspotConfig* config);
+
+/**
+ * Create a WiFi hotspot whose clients share the connection of another interface.
+ * 
+ * @param manager The WifiManager instance
+ * @param config SSID, optional WPA2 passphrase and channel
+ * @param share_from Upstream interface, e.g. "eth0", whose connection is shared through NAT;
+ *                   NULL for none. The NAT rules are removed when the hotspot stops.
+ * @return true if the hotspot was created and sharing set up, false otherwise
+ * @note Supported on Linux, with iptables or nftables
+ */
+bool wifi_manager_create_hotspot_with_sharing(WifiManager* manager, const WifiHotspotConfig* config,
+                                              const char* share_from);
+
+/**
+ * Create a WiFi hotspot for a captive portal: the hotspot's DNS server answers every query with
+ * the gateway address, so clients open the portal served there.
+ * 
+ * @param manager The WifiManager instance
+ * @param config SSID, optional WPA2 passphrase, channel and addressing; configured DNS servers
+ *               are not announced
+ * @param share_from Upstream interface whose connection is shared through NAT, NULL for none
+ * @return true if the hotspot was created, false otherwise
+ * @note Supported on Linux
+ */
+bool wifi_manager_create_captive_hotspot(WifiManager* manager, const WifiHotspotConfig* config,
+                                         const char* share_from);
+
+/**
+ * Create a WiFi hotspot, giving up if its access point has not started in time.
+ * 
+ * @param manager The WifiManager instance
+ * @param config SSID, optional WPA2 passphrase, channel and addressing
+ * @param share_from Upstream interface whose connection is shared through NAT, NULL for none
+ * @param captive_portal Answer every DNS query with the gateway address, as wifi_manager_create_captive_hotspot
+ * @param timeout_ms Longest wait for the access point to start, 0 for the manager's hotspot timeout
+ * @return true if the hotspot was created, false otherwise; WIFI_ERROR_TIMEOUT if it did not start in time
+ * @note Supported on Linux
+ */
+bool wifi_manager_create_hotspot_with_timeout(WifiManager* manager, const WifiHotspotConfig* config,
+                                              const char* share_from, bool captive_portal, int32_t timeout_ms);
+
 /**
  * Stop the active hotspot.
  * 
@@ -84,6 +1004,22 @@ bool wifi_manager_is_hotspot_active(WifiManager* manager);
  */
 bool wifi_manager_is_hotspot_supported(WifiManager* manager);
 
+/**
+ * List the devices associated with the active hotspot.
+ * 
+ * @param manager The WifiManager instance
+ * @param count Receives the number of clients
+ * @return An array that must be freed with wifi_free_hotspot_clients, NULL if there are none
+ */
+WifiHotspotClient* wifi_manager_hotspot_clients(WifiManager* manager, int* count);
+
+/**
+ * Free the array returned by wifi_manager_hotspot_clients.
+ * 
+ * @param clients The array to free
+ */
+void wifi_free_hotspot_clients(WifiHotspotClient* clients);
+
 // Free the network info array returned by wifi_manager_scan
 void wifi_free_network_info(WifiNetworkInfo* networks, int count);
 
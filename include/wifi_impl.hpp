@@ -2,8 +2,15 @@
 
 #include "wifi_types.hpp"
 #include "wifi_platform.hpp"
+#include "wifi_logger.hpp"
+#include <algorithm>
+#include <atomic>
+#include <chrono>
 #include <memory>
+#include <mutex>
 #include <string>
+#include <thread>
+#include <unordered_map>
 #include <vector>
 
 namespace wificpp {
@@ -15,14 +22,603 @@ public:
     
     // WiFi operations
     virtual std::vector<NetworkInfo> scan() = 0;
+    // Platforms that cannot direct a scan run a full one and filter the results
+    virtual std::vector<NetworkInfo> scanWithOptions(const ScanOptions& options) {
+        std::vector<NetworkInfo> networks = scan();
+        dropUnrequested(networks, options);
+        return networks;
+    }
+    // A scan whose results are known to come from this call, failing with TIMEOUT or DRIVER_ERROR
+    // instead of returning cached results when the platform did not report the scan's completion
+    virtual bool scanFresh(std::vector<NetworkInfo>& networks) {
+        (void)networks;
+        Logger::getInstance().warning("Fresh scans are not supported on ", platform::getPlatformName());
+        return fail(ErrorCode::UNSUPPORTED);
+    }
     virtual bool connect(const std::string& ssid, const std::string& password) = 0;
     virtual bool disconnect() = 0;
     virtual ConnectionStatus getStatus() const = 0;
+
+    // Connection with additional options, unsupported options fail rather than being ignored
+    // Platforms without an override probe for the SSID directly, so hidden networks need no special handling
+    virtual bool connectWithOptions(const std::string& ssid, const std::string& password, const ConnectOptions& options) {
+        if (options.ephemeral) {
+            Logger::getInstance().warning("Ephemeral connections are not supported on ", platform::getPlatformName());
+            return fail(ErrorCode::UNSUPPORTED);
+        }
+        if (!options.bssid.empty()) {
+            Logger::getInstance().warning("Connecting to a specific access point is not supported on ", platform::getPlatformName());
+            return fail(ErrorCode::UNSUPPORTED);
+        }
+        if (!options.staticIp.empty()) {
+            Logger::getInstance().warning("Static IP configuration is not supported on ", platform::getPlatformName());
+            return fail(ErrorCode::UNSUPPORTED);
+        }
+        return connect(ssid, password);
+    }
+
+    // Enterprise (802.1X) connection, only implemented on Linux through wpa_supplicant
+    virtual bool connectEnterprise(const std::string& ssid, const EapConfig& eap) {
+        (void)ssid;
+        (void)eap;
+        Logger::getInstance().warning("Enterprise connections are not supported on ", platform::getPlatformName());
+        return fail(ErrorCode::UNSUPPORTED);
+    }
+
+    // WPS push-button connection, blocks until connected or the timeout expires
+    // progress may be empty and is only called by platforms that can follow the exchange
+    virtual bool connectWpsPbc(int timeoutSeconds, WpsProgressCallback progress) {
+        (void)timeoutSeconds;
+        (void)progress;
+        Logger::getInstance().warning("WPS is not supported on ", platform::getPlatformName());
+        return false;
+    }
+
+    // WPS PIN connection against an access point (empty bssid means any AP)
+    virtual bool connectWpsPin(const std::string& bssid, const std::string& pin, int timeoutSeconds,
+                               WpsProgressCallback progress) {
+        (void)bssid;
+        (void)pin;
+        (void)timeoutSeconds;
+        (void)progress;
+        Logger::getInstance().warning("WPS is not supported on ", platform::getPlatformName());
+        return false;
+    }
+
+    virtual bool isEapMethodSupported(EapMethod method) const {
+        (void)method;
+        return false;
+    }
+
+    // Passpoint (Hotspot 2.0) credential provisioning
+    virtual bool installPasspointProfile(const PasspointProfile& profile) {
+        (void)profile;
+        Logger::getInstance().warning("Passpoint is not supported on ", platform::getPlatformName());
+        return fail(ErrorCode::UNSUPPORTED);
+    }
+    virtual bool removePasspointProfile(const std::string& domain) {
+        (void)domain;
+        Logger::getInstance().warning("Passpoint is not supported on ", platform::getPlatformName());
+        return fail(ErrorCode::UNSUPPORTED);
+    }
+
+    // Probe-request privacy: randomized MAC addresses and no directed probes for saved SSIDs
+    virtual bool setProbePrivacy(bool enabled) {
+        (void)enabled;
+        Logger::getInstance().warning("Probe-request privacy is not supported on ", platform::getPlatformName());
+        return fail(ErrorCode::UNSUPPORTED);
+    }
+    virtual bool isProbePrivacyEnabled() const {
+        return false;
+    }
+
+    // Monitor mode capture of raw 802.11 frames
+    virtual bool startCapture(const CaptureConfig& config, FrameCallback callback) {
+        (void)config;
+        (void)callback;
+        Logger::getInstance().warning("Monitor mode capture is not supported on ", platform::getPlatformName());
+        return fail(ErrorCode::UNSUPPORTED);
+    }
+    virtual bool stopCapture() {
+        return false;
+    }
+    virtual bool isCapturing() const {
+        return false;
+    }
+
+    // Connectivity-change notifications from the OS, including changes made by other software
+    virtual bool startNetworkObserver(NetworkChangeCallback callback) {
+        (void)callback;
+        Logger::getInstance().warning("Network change notifications are not supported on ", platform::getPlatformName());
+        return false;
+    }
+    virtual bool stopNetworkObserver() {
+        return true;
+    }
+
+    // Wireless adapters present on the system
+    virtual std::vector<InterfaceInfo> listInterfaces() const {
+        Logger::getInstance().warning("Interface enumeration is not supported on ", platform::getPlatformName());
+        return {};
+    }
+    // Operate on the named adapter instead of the default one
+    virtual bool selectInterface(const std::string& name) {
+        if (name == getInterfaceName()) {
+            return true;
+        }
+        Logger::getInstance().warning("Interface selection is not supported on ", platform::getPlatformName());
+        return fail(ErrorCode::UNSUPPORTED);
+    }
+
+    // Push notifications of connection events; signalThreshold is in dBm
+    virtual bool registerEventCallback(WifiEventCallback callback, int signalThreshold) {
+        (void)callback;
+        (void)signalThreshold;
+        Logger::getInstance().warning("Event callbacks are not supported on ", platform::getPlatformName());
+        return false;
+    }
+    virtual bool unregisterEventCallback() {
+        return true;
+    }
+
+    // Per-channel survey data (airtime utilization and noise)
+    virtual std::vector<ChannelSurvey> channelSurvey() {
+        Logger::getInstance().warning("Channel survey is not supported on ", platform::getPlatformName());
+        return {};
+    }
+
+    // Snapshot of adapter, radio, association and IP state for diagnostics
+    virtual bool getLinkDiagnostics(LinkDiagnostics& diagnostics) const {
+        (void)diagnostics;
+        Logger::getInstance().warning("Link diagnostics are not supported on ", platform::getPlatformName());
+        return false;
+    }
+
+    // Details of the current association, false when not connected
+    virtual bool getConnectionInfo(ConnectionInfo& info) const {
+        (void)info;
+        Logger::getInstance().warning("Connection info is not supported on ", platform::getPlatformName());
+        return false;
+    }
+
+    // Rates and counters of the current association, false when not connected
+    virtual bool getLinkStats(LinkStats& stats) const {
+        (void)stats;
+        Logger::getInstance().warning("Link statistics are not supported on ", platform::getPlatformName());
+        return false;
+    }
+
+    // Select how connections are made, only Linux has more than one way
+    virtual bool setLinuxBackend(LinuxBackend backend) {
+        if (backend == LinuxBackend::AUTO) {
+            return true;
+        }
+        Logger::getInstance().warning("Connection backends cannot be selected on ", platform::getPlatformName());
+        return fail(ErrorCode::UNSUPPORTED);
+    }
+    // The backend connections are made with, AUTO resolved; AUTO on other platforms
+    virtual LinuxBackend getLinuxBackend() const {
+        return LinuxBackend::AUTO;
+    }
+
+    // Whether the process may see network names and BSSIDs, which macOS hides from scans and the
+    // current connection without Location Services authorization
+    virtual bool isLocationAuthorized() const {
+        return true;
+    }
+    // Ask the user for the authorization isLocationAuthorized reports, true once it is granted
+    virtual bool requestLocationAuthorization() {
+        return isLocationAuthorized();
+    }
+
+    // Whether the process may manage the adapter: root or CAP_NET_ADMIN on Linux, an elevated token on Windows
+    virtual bool hasAdminPrivileges() const {
+        return !platform::requiresElevatedPrivileges();
+    }
+
+    // Software radio switch; the radio is reported on where its state cannot be read
+    virtual bool isRadioEnabled() const {
+        return true;
+    }
+    virtual bool setRadioEnabled(bool enabled) {
+        (void)enabled;
+        Logger::getInstance().warning("Switching the radio is not supported on ", platform::getPlatformName());
+        return fail(ErrorCode::UNSUPPORTED);
+    }
+
+    // Wi-Fi Direct (P2P) operations
+    virtual bool isP2pSupported() const {
+        return false;
+    }
+    
+    virtual std::vector<P2pPeer> p2pDiscover(int timeoutSeconds) {
+        (void)timeoutSeconds;
+        Logger::getInstance().warning("Wi-Fi Direct is not supported on ", platform::getPlatformName());
+        return {};
+    }
+    
+    virtual bool p2pConnect(const P2pConnectConfig& config, int timeoutSeconds, P2pGroup& group) {
+        (void)config;
+        (void)timeoutSeconds;
+        (void)group;
+        Logger::getInstance().warning("Wi-Fi Direct is not supported on ", platform::getPlatformName());
+        return fail(ErrorCode::UNSUPPORTED);
+    }
+    
+    virtual bool p2pDisconnect() {
+        Logger::getInstance().warning("Wi-Fi Direct is not supported on ", platform::getPlatformName());
+        return fail(ErrorCode::UNSUPPORTED);
+    }
+
+    // Wi-Fi Aware (NAN) service discovery
+    virtual bool isNanSupported() const {
+        return false;
+    }
+    
+    // Returns the publish id, or 0 on failure
+    virtual int nanPublish(const NanPublishConfig& config) {
+        (void)config;
+        Logger::getInstance().warning("Wi-Fi Aware is not supported on ", platform::getPlatformName());
+        return 0;
+    }
+    
+    // Returns the subscribe id, or 0 on failure
+    virtual int nanSubscribe(const NanSubscribeConfig& config) {
+        (void)config;
+        Logger::getInstance().warning("Wi-Fi Aware is not supported on ", platform::getPlatformName());
+        return 0;
+    }
+    
+    virtual bool nanCancel(int handle) {
+        (void)handle;
+        return fail(ErrorCode::UNSUPPORTED);
+    }
+    
+    // Send a follow-up message to a peer discovered through a publication or subscription
+    virtual bool nanTransmit(int handle, int peerInstanceId, const std::string& peerAddress, const std::vector<uint8_t>& data) {
+        (void)handle;
+        (void)peerInstanceId;
+        (void)peerAddress;
+        (void)data;
+        return fail(ErrorCode::UNSUPPORTED);
+    }
+    
+    // Wait up to timeoutMs for the next event, false on timeout
+    virtual bool nanNextEvent(int timeoutMs, NanEvent& event) {
+        (void)timeoutMs;
+        (void)event;
+        return false;
+    }
+
+    // 802.11s mesh operations
+    virtual bool isMeshSupported() const {
+        return false;
+    }
+    
+    virtual bool meshJoin(const MeshConfig& config) {
+        (void)config;
+        Logger::getInstance().warning("Mesh networking is not supported on ", platform::getPlatformName());
+        return fail(ErrorCode::UNSUPPORTED);
+    }
+    
+    virtual bool meshLeave() {
+        return fail(ErrorCode::UNSUPPORTED);
+    }
+    
+    virtual std::vector<MeshPeer> meshPeers() {
+        return {};
+    }
+
+    // Ad-hoc (IBSS) operations
+    virtual bool isAdHocSupported() const {
+        return false;
+    }
+    
+    virtual bool joinAdHoc(const AdHocConfig& config) {
+        (void)config;
+        Logger::getInstance().warning("Ad-hoc networks are not supported on ", platform::getPlatformName());
+        return false;
+    }
+
+    // TDLS (direct links between stations of the same BSS)
+    virtual bool isTdlsSupported() const {
+        return false;
+    }
+    
+    virtual std::vector<TdlsPeer> tdlsDiscover(int timeoutSeconds) {
+        (void)timeoutSeconds;
+        Logger::getInstance().warning("TDLS is not supported on ", platform::getPlatformName());
+        return {};
+    }
+    
+    virtual bool tdlsSetup(const std::string& peerAddress) {
+        (void)peerAddress;
+        Logger::getInstance().warning("TDLS is not supported on ", platform::getPlatformName());
+        return fail(ErrorCode::UNSUPPORTED);
+    }
+    
+    virtual bool tdlsTeardown(const std::string& peerAddress) {
+        (void)peerAddress;
+        return fail(ErrorCode::UNSUPPORTED);
+    }
+
+    // Fine Timing Measurement (802.11mc round trip time ranging)
+    virtual bool isRttSupported() const {
+        return false;
+    }
+    
+    virtual bool measureDistance(const std::string& bssid, int frequency, RangingResult& result) {
+        (void)bssid;
+        (void)frequency;
+        (void)result;
+        Logger::getInstance().warning("RTT ranging is not supported on ", platform::getPlatformName());
+        return fail(ErrorCode::UNSUPPORTED);
+    }
+
+    // 802.11k/v/r support of the access point and the local stack
+    virtual bool getRoamingFeatures(RoamingFeatures& features) const {
+        (void)features;
+        return false;
+    }
+
+    // Move the current association to another access point of the same network
+    virtual bool roamTo(const std::string& bssid) {
+        (void)bssid;
+        Logger::getInstance().warning("Roaming to a chosen access point is not supported on ", platform::getPlatformName());
+        return fail(ErrorCode::UNSUPPORTED);
+    }
+
+    virtual bool setRoamingPolicy(const RoamingPolicy& policy) {
+        (void)policy;
+        Logger::getInstance().warning("Roaming policies are not supported on ", platform::getPlatformName());
+        return fail(ErrorCode::UNSUPPORTED);
+    }
+
+    // Target Wake Time (802.11ax power save)
+    virtual bool getTwtStatus(TwtStatus& status) const {
+        (void)status;
+        return false;
+    }
+    
+    virtual bool twtSetup(const TwtConfig& config) {
+        (void)config;
+        Logger::getInstance().warning("Target Wake Time is not supported on ", platform::getPlatformName());
+        return fail(ErrorCode::UNSUPPORTED);
+    }
+    
+    virtual bool twtTeardown() {
+        return fail(ErrorCode::UNSUPPORTED);
+    }
+
+    // Affiliated links of a Wi-Fi 7 multi-link association
+    virtual std::vector<MloLink> getMloLinks() const {
+        return {};
+    }
+
+    // Regulatory domain (ISO 3166-1 alpha-2 country code, "00" for world)
+    virtual std::string getRegulatoryDomain() const {
+        return "";
+    }
+    
+    virtual bool canSetRegulatoryDomain() const {
+        return false;
+    }
+    
+    virtual bool setRegulatoryDomain(const std::string& country) {
+        (void)country;
+        Logger::getInstance().warning("Setting the regulatory domain is not supported on ", platform::getPlatformName());
+        return fail(ErrorCode::UNSUPPORTED);
+    }
+
+    // Channels the regulatory domain permits, disabled channels excluded
+    virtual std::vector<ChannelInfo> getAllowedChannels() const {
+        return {};
+    }
+    // Name of the station interface, empty if the platform has none
+    virtual std::string getInterfaceName() const {
+        return "";
+    }
+    // Wake-on-WLAN triggers applied while the system sleeps
+    virtual bool getWowlanCapabilities(WowlanCapabilities& capabilities) const {
+        (void)capabilities;
+        return false;
+    }
+    
+    virtual bool getWowlanConfig(WowlanConfig& config) const {
+        (void)config;
+        return false;
+    }
+    
+    virtual bool setWowlanConfig(const WowlanConfig& config) {
+        (void)config;
+        Logger::getInstance().warning("Wake-on-WLAN is not supported on ", platform::getPlatformName());
+        return fail(ErrorCode::UNSUPPORTED);
+    }
+    // Stored network profiles: WLAN profiles on Windows, NetworkManager connections on Linux
+    virtual bool isProfileStoreSupported() const {
+        return false;
+    }
+    
+    virtual bool hasProfile(const std::string& name) const {
+        (void)name;
+        return false;
+    }
+    
+    virtual bool connectProfile(const std::string& name) {
+        (void)name;
+        Logger::getInstance().warning("Connecting by profile is not supported on ", platform::getPlatformName());
+        return false;
+    }
+    
+    virtual std::vector<SavedProfile> listProfiles() const {
+        Logger::getInstance().warning("Listing profiles is not supported on ", platform::getPlatformName());
+        return {};
+    }
+    
+    // Store credentials for an open or WPA2-Personal network, replacing a profile of the same name
+    virtual bool saveProfile(const std::string& ssid, const std::string& password, bool autoConnect) {
+        (void)ssid;
+        (void)password;
+        (void)autoConnect;
+        Logger::getInstance().warning("Saving profiles is not supported on ", platform::getPlatformName());
+        return false;
+    }
+    
+    virtual bool forgetProfile(const std::string& name) {
+        (void)name;
+        Logger::getInstance().warning("Removing profiles is not supported on ", platform::getPlatformName());
+        return false;
+    }
+    
+    virtual bool setProfileAutoConnect(const std::string& name, bool enabled) {
+        (void)name;
+        (void)enabled;
+        Logger::getInstance().warning("Changing profiles is not supported on ", platform::getPlatformName());
+        return false;
+    }
+    // Addresses, routes and DHCP lease of the wireless interface
+    virtual bool getIpInfo(IpInfo& info) const {
+        (void)info;
+        Logger::getInstance().warning("Reading the IP configuration is not supported on ", platform::getPlatformName());
+        return false;
+    }
       // Hotspot operations
     virtual bool createHotspot(const std::string& ssid, const std::string& password) = 0;
+
+    // Hotspot with explicit settings, fails rather than ignoring a requested channel
+    virtual bool createHotspotWithConfig(const HotspotConfig& config) {
+        if (!config.dhcp.gateway.empty()) {
+            Logger::getInstance().warning("Configuring the hotspot DHCP server is not supported on ", platform::getPlatformName());
+            return false;
+        }
+        if (!config.shareFrom.empty()) {
+            Logger::getInstance().warning("Sharing a connection with the hotspot is not supported on ", platform::getPlatformName());
+            return fail(ErrorCode::UNSUPPORTED);
+        }
+        if (config.redirectDns) {
+            Logger::getInstance().warning("Captive portal hotspots are not supported on ", platform::getPlatformName());
+            return fail(ErrorCode::UNSUPPORTED);
+        }
+        if (config.channel == 0) {
+            return createHotspot(config.ssid, config.password);
+        }
+        Logger::getInstance().warning("Choosing the hotspot channel is not supported on ", platform::getPlatformName());
+        return false;
+    }
     virtual bool stopHotspot() = 0;
     virtual bool isHotspotActive() const = 0;
     virtual bool isHotspotSupported() const = 0;
+
+    // Devices associated with the active hotspot
+    virtual std::vector<HotspotClient> hotspotClients() const {
+        Logger::getInstance().warning("Listing hotspot clients is not supported on ", platform::getPlatformName());
+        return {};
+    }
+
+    // Reason for the last failed operation on the calling thread, reset before each connect,
+    // disconnect and hotspot operation. Kept per thread so concurrent callers only see their own
+    ErrorCode getLastError() const {
+        std::lock_guard<std::mutex> lock(errorMutex);
+        auto found = lastErrors.find(std::this_thread::get_id());
+        return found == lastErrors.end() ? ErrorCode::NONE : found->second;
+    }
+    void clearLastError() {
+        std::lock_guard<std::mutex> lock(errorMutex);
+        lastErrors.erase(std::this_thread::get_id());
+    }
+
+    static constexpr int DEFAULT_SCAN_TIMEOUT_MS = 10000;
+    // Long enough for the channel availability check on DFS channels
+    static constexpr int DEFAULT_HOTSPOT_TIMEOUT_MS = 90000;
+
+    // Longest waits for a scan to complete and for a hotspot to start, values <= 0 restore the
+    // defaults. A scan still running at its deadline is aborted and a hotspot torn down; platforms
+    // whose calls cannot be bounded ignore them
+    void setOperationTimeouts(int scanMs, int hotspotMs) {
+        scanTimeout = scanMs > 0 ? scanMs : DEFAULT_SCAN_TIMEOUT_MS;
+        hotspotTimeout = hotspotMs > 0 ? hotspotMs : DEFAULT_HOTSPOT_TIMEOUT_MS;
+    }
+    int scanTimeoutMs() const {
+        return scanTimeout;
+    }
+    int hotspotTimeoutMs() const {
+        return hotspotTimeout;
+    }
+
+    // Abort the scan or connect in progress, which then fails with CANCELLED; may be called from
+    // any thread. Returns false if none was in progress
+    bool requestCancel() {
+        CallState running = CallState::RUNNING;
+        return callState.compare_exchange_strong(running, CallState::CANCELLED);
+    }
+    // Bracket an operation that requestCancel may abort
+    void beginCancellable() {
+        callState.store(CallState::RUNNING);
+    }
+    void endCancellable() {
+        callState.store(CallState::IDLE);
+    }
+
+protected:
+    // Remove results for networks or frequencies a directed scan did not ask for
+    static void dropUnrequested(std::vector<NetworkInfo>& networks, const ScanOptions& options) {
+        auto requested = [](const auto& wanted, const auto& value) {
+            return wanted.empty() || std::find(wanted.begin(), wanted.end(), value) != wanted.end();
+        };
+        networks.erase(std::remove_if(networks.begin(), networks.end(), [&](const NetworkInfo& network) {
+            return !requested(options.ssids, network.ssid) || !requested(options.frequencies, network.frequency);
+        }), networks.end());
+    }
+
+    // Static addressing is handed to platform tools, so only address characters are accepted
+    static bool isWellFormed(const StaticIpConfig& addressing) {
+        auto address = [](const std::string& text, bool cidr) {
+            return text.find_first_not_of(cidr ? "0123456789abcdefABCDEF.:/" : "0123456789abcdefABCDEF.:") == std::string::npos &&
+                   (!cidr || text.empty() || text.find('/') != std::string::npos);
+        };
+        bool valid = address(addressing.ipv4Address, true) && address(addressing.ipv4Gateway, false) &&
+                     address(addressing.ipv6Address, true) && address(addressing.ipv6Gateway, false);
+        for (const auto& server : addressing.dnsServers) {
+            valid = valid && !server.empty() && address(server, false);
+        }
+        if (!valid) {
+            Logger::getInstance().error("Invalid static IP configuration");
+        }
+        return valid;
+    }
+
+    bool cancelled() const {
+        return callState.load() == CallState::CANCELLED;
+    }
+
+    // Sleep in short steps, returning false as soon as the operation is cancelled
+    bool sleepUnlessCancelled(int ms) const {
+        auto deadline = std::chrono::steady_clock::now() + std::chrono::milliseconds(ms);
+        while (!cancelled()) {
+            auto left = deadline - std::chrono::steady_clock::now();
+            if (left <= std::chrono::steady_clock::duration::zero()) {
+                return true;
+            }
+            std::this_thread::sleep_for(std::min<std::chrono::steady_clock::duration>(left, std::chrono::milliseconds(100)));
+        }
+        return false;
+    }
+
+    // Record why the current operation failed, returns false for use in return statements
+    bool fail(ErrorCode code) {
+        std::lock_guard<std::mutex> lock(errorMutex);
+        lastErrors[std::this_thread::get_id()] = code;
+        return false;
+    }
+
+private:
+    enum class CallState { IDLE, RUNNING, CANCELLED };
+
+    mutable std::mutex errorMutex;
+    std::unordered_map<std::thread::id, ErrorCode> lastErrors;
+    std::atomic<CallState> callState{CallState::IDLE};
+    int scanTimeout = DEFAULT_SCAN_TIMEOUT_MS;
+    int hotspotTimeout = DEFAULT_HOTSPOT_TIMEOUT_MS;
 };
 
 // Factory function to create platform-specific implementation
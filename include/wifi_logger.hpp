@@ -3,6 +3,8 @@
 #include <string>
 #include <sstream>
 #include <chrono>
+#include <functional>
+#include <mutex>
 
 namespace wificpp {
 
@@ -13,12 +15,17 @@ enum class LogLevel {
     ERROR
 };
 
+// Receives log messages instead of standard output; may be called from any thread
+using LogSink = std::function<void(LogLevel level, const std::string& message)>;
+
 class Logger {
 public:
     static Logger& getInstance();
 
     void log(LogLevel level, const std::string& message);
     void setLogLevel(LogLevel level);
+    // Send messages to sink instead of standard output, an empty sink restores standard output
+    void setSink(LogSink sink);
 
     template<typename... Args>
     void debug(Args&&... args) {
@@ -53,6 +60,8 @@ private:
     }
 
     LogLevel currentLevel = LogLevel::INFO;
+    std::mutex sinkMutex;
+    LogSink sink;
 };
 
 } // namespace wificpp
@@ -9,9 +9,13 @@ namespace wificpp {
 
 class WifiImpl;  // Forward declaration
 
+// Calls may be made from any thread; they are serialized internally, so a
+// slow operation such as a scan or connect delays concurrent calls.
 class WifiManager {
 public:
     WifiManager();
+    // Operate on the named adapter; throws if it is not a usable wireless interface
+    explicit WifiManager(const std::string& interfaceName);
     ~WifiManager();
 
     // Prevent copy and assignment
@@ -20,14 +24,102 @@ public:
 
     // WiFi operations
     std::vector<NetworkInfo> scan();
+    std::vector<NetworkInfo> scanWithOptions(const ScanOptions& options);
+    bool scanFresh(std::vector<NetworkInfo>& networks);
+    // Abort a scan or connect running on another thread, which fails with CANCELLED; false if none was
+    bool cancel();
+    // Longest waits for scans and hotspot starts that do not set their own, values <= 0 restore the defaults
+    void setOperationTimeouts(int scanTimeoutMs, int hotspotTimeoutMs);
+    // Information elements of an access point in the latest scan, false if it was not seen or sent none
+    bool getInformationElements(const std::string& bssid, std::vector<uint8_t>& elements) const;
     bool connect(const std::string& ssid, const std::string& password = "");
+    bool connectWithOptions(const std::string& ssid, const std::string& password, const ConnectOptions& options);
+    bool connectEnterprise(const std::string& ssid, const EapConfig& eap);
+    bool isEapMethodSupported(EapMethod method) const;
+    bool connectWpsPbc(int timeoutSeconds = 120, WpsProgressCallback progress = nullptr);
+    bool connectWpsPin(const std::string& bssid, const std::string& pin, int timeoutSeconds = 120,
+                       WpsProgressCallback progress = nullptr);
+    bool installPasspointProfile(const PasspointProfile& profile);
+    bool removePasspointProfile(const std::string& domain);
+    bool setProbePrivacy(bool enabled);
+    bool isProbePrivacyEnabled() const;
+    bool startCapture(const CaptureConfig& config, FrameCallback callback);
+    bool stopCapture();
+    bool isCapturing() const;
+    std::vector<ChannelSurvey> channelSurvey();
+    bool getLinkDiagnostics(LinkDiagnostics& diagnostics) const;
+    bool getConnectionInfo(ConnectionInfo& info) const;
+    bool getLinkStats(LinkStats& stats) const;
+    bool setLinuxBackend(LinuxBackend backend);
+    LinuxBackend getLinuxBackend() const;
+    bool isLocationAuthorized() const;
+    bool requestLocationAuthorization();
+    bool hasAdminPrivileges() const;
+    bool isRadioEnabled() const;
+    bool setRadioEnabled(bool enabled);
+    bool isP2pSupported() const;
+    std::vector<P2pPeer> p2pDiscover(int timeoutSeconds);
+    bool p2pConnect(const P2pConnectConfig& config, int timeoutSeconds, P2pGroup& group);
+    bool p2pDisconnect();
+    bool isNanSupported() const;
+    int nanPublish(const NanPublishConfig& config);
+    int nanSubscribe(const NanSubscribeConfig& config);
+    bool nanCancel(int handle);
+    bool nanTransmit(int handle, int peerInstanceId, const std::string& peerAddress, const std::vector<uint8_t>& data);
+    bool nanNextEvent(int timeoutMs, NanEvent& event);
+    bool isMeshSupported() const;
+    bool meshJoin(const MeshConfig& config);
+    bool meshLeave();
+    std::vector<MeshPeer> meshPeers();
+    bool isAdHocSupported() const;
+    bool joinAdHoc(const AdHocConfig& config);
+    bool isTdlsSupported() const;
+    std::vector<TdlsPeer> tdlsDiscover(int timeoutSeconds);
+    bool tdlsSetup(const std::string& peerAddress);
+    bool tdlsTeardown(const std::string& peerAddress);
+    bool isRttSupported() const;
+    bool measureDistance(const std::string& bssid, int frequency, RangingResult& result);
+    bool getRoamingFeatures(RoamingFeatures& features) const;
+    bool roamTo(const std::string& bssid);
+    bool setRoamingPolicy(const RoamingPolicy& policy);
+    bool getTwtStatus(TwtStatus& status) const;
+    bool twtSetup(const TwtConfig& config);
+    bool twtTeardown();
+    std::vector<MloLink> getMloLinks() const;
+    std::string getRegulatoryDomain() const;
+    bool canSetRegulatoryDomain() const;
+    bool setRegulatoryDomain(const std::string& country);
+    std::vector<ChannelInfo> getAllowedChannels() const;
+    std::string getInterfaceName() const;
+    bool getWowlanCapabilities(WowlanCapabilities& capabilities) const;
+    bool getWowlanConfig(WowlanConfig& config) const;
+    bool setWowlanConfig(const WowlanConfig& config);
+    bool startNetworkObserver(NetworkChangeCallback callback);
+    bool stopNetworkObserver();
+    bool isProfileStoreSupported() const;
+    bool hasProfile(const std::string& name) const;
+    bool connectProfile(const std::string& name);
+    ErrorCode getLastError() const;
+    bool registerEventCallback(WifiEventCallback callback, int signalThreshold);
+    bool unregisterEventCallback();
+    std::vector<InterfaceInfo> listInterfaces() const;
+    std::vector<SavedProfile> listProfiles() const;
+    bool saveProfile(const std::string& ssid, const std::string& password, bool autoConnect);
+    bool forgetProfile(const std::string& name);
+    bool setProfileAutoConnect(const std::string& name, bool enabled);
+    // Poll the current association every intervalMs on a background thread
+    bool startSignalMonitor(int intervalMs, SignalCallback callback);
+    bool stopSignalMonitor();
+    bool getIpInfo(IpInfo& info) const;
     bool disconnect();
     ConnectionStatus getStatus() const;
       // Hotspot management
     bool createHotspot(const std::string& ssid, const std::string& password = "");
+    bool createHotspotWithConfig(const HotspotConfig& config);
     bool stopHotspot();
     bool isHotspotActive() const;
     bool isHotspotSupported() const;
+    std::vector<HotspotClient> hotspotClients() const;
 
 private:
     class Impl;
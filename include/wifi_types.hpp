@@ -1,5 +1,8 @@
 #pragma once
 
+#include <cstddef>
+#include <cstdint>
+#include <functional>
 #include <string>
 #include <vector>
 
@@ -21,6 +24,509 @@ enum class ConnectionStatus {
     CONNECTION_ERROR // Renamed from ERROR to avoid conflict with Windows macro
 };
 
+// Why the last connect, disconnect or hotspot operation failed
+enum class ErrorCode {
+    NONE,
+    OPERATION_FAILED,     // no more specific reason known
+    UNSUPPORTED,
+    INVALID_CREDENTIALS,  // the access point rejected the password or certificate
+    ADAPTER_NOT_FOUND,
+    PERMISSION_DENIED,    // the process lacks the privileges to manage the adapter
+    TIMEOUT,
+    DRIVER_ERROR,         // the driver or a system service reported an error
+    CANCELLED             // aborted by WifiManager::cancel
+};
+
+// Protected Management Frames (802.11w) advertised in the RSN element
+enum class PmfMode {
+    UNKNOWN,
+    DISABLED,
+    CAPABLE,
+    REQUIRED
+};
+
+// EAP methods supported for WPA2/WPA3-Enterprise connections
+enum class EapMethod {
+    PEAP,
+    TLS,
+    TTLS,
+    SIM,
+    AKA,
+    AKA_PRIME
+};
+
+// Inner (phase 2) authentication for tunneled EAP methods
+enum class Phase2Method {
+    MSCHAPV2,
+    PAP
+};
+
+// Credentials and settings for an 802.1X (enterprise) connection
+struct EapConfig {
+    EapMethod method = EapMethod::PEAP;
+    Phase2Method phase2 = Phase2Method::MSCHAPV2;  // used by TTLS, PEAP always uses MSCHAPv2
+    std::string identity;
+    std::string anonymousIdentity;  // outer identity, empty to use identity
+    std::string password;           // SIM PIN for SIM/AKA methods
+    std::string caCert;             // path to CA certificate; required to connect unless serverCertHash is
+                                    // set, Passpoint uses the system store when empty
+
+    // RADIUS server certificate validation
+    bool validateServer = true;     // false disables validation entirely (insecure)
+    std::string serverCertHash;     // SHA-256 of the server certificate, hex encoded
+    std::string domainMatch;        // expected full server name
+    std::string domainSuffixMatch;  // expected server name suffix
+
+    // EAP-TLS client credentials, exactly one source is used:
+    // certificate/key files, a PKCS#12 blob or an OS certificate store reference
+    std::string clientCert;
+    std::string privateKey;
+    std::string privateKeyPassword;  // also used to decrypt the PKCS#12 blob
+    std::vector<uint8_t> pkcs12;
+    std::string certStoreRef;
+};
+
+// Manual addressing applied after joining a network, instead of or next to DHCP
+struct StaticIpConfig {
+    std::string ipv4Address;              // CIDR notation, e.g. 192.168.1.20/24, empty to use DHCP
+    std::string ipv4Gateway;              // optional default route
+    std::string ipv6Address;              // CIDR notation, added next to autoconfigured addresses
+    std::string ipv6Gateway;              // optional default route
+    std::vector<std::string> dnsServers;  // replace the DHCP-provided servers when not empty
+    
+    bool empty() const {
+        return ipv4Address.empty() && ipv6Address.empty() && dnsServers.empty();
+    }
+};
+
+// Additional settings for a personal or open network connection
+struct ConnectOptions {
+    bool ephemeral = false;  // never store a profile, forget the network on disconnect
+    std::vector<int> frequencies;  // restrict association to these frequencies (MHz), empty for any
+    bool hidden = false;     // the network does not broadcast its SSID, probe for it directly
+    std::string bssid;       // join only this access point, empty for any
+    int timeoutSeconds = 0;  // how long to wait for the association, 0 for the platform default
+    StaticIpConfig staticIp; // manual addressing, empty for DHCP
+};
+
+// How connections are made on Linux
+enum class LinuxBackend {
+    AUTO,             // NetworkManager when it manages the interface, wpa_supplicant otherwise
+    NL80211,          // the driver associates on its own, open networks only
+    NETWORK_MANAGER,  // nmcli, which leaves a saved connection profile behind
+    WPA_CLI           // a wpa_supplicant started by the library, controlled with wpa_cli
+};
+
+// Stage of a WPS enrollment started by connectWpsPbc or connectWpsPin
+enum class WpsStage {
+    SEARCHING,              // looking for an access point with an active WPS session
+    EXCHANGING,             // registration protocol running with the access point
+    CREDENTIALS_RECEIVED,   // network credentials received, joining the network
+    CONNECTED,
+    FAILED
+};
+
+// Why a WPS enrollment failed
+enum class WpsFailure {
+    NONE,
+    TIMEOUT,            // no access point completed the exchange within the walk time
+    SESSION_OVERLAP,    // more than one access point is in push-button mode
+    WRONG_PIN,          // the registrar rejected the PIN
+    SETUP_LOCKED,       // the access point locked WPS after repeated failures
+    REJECTED            // the exchange failed for another reason, see configError
+};
+
+struct WpsProgress {
+    WpsStage stage = WpsStage::SEARCHING;
+    WpsFailure failure = WpsFailure::NONE;  // FAILED only
+    int configError = 0;                    // last WPS configuration error from the registrar, 0 if none
+};
+
+// Receives each stage of a WPS enrollment on the calling thread, which must not call back into the manager
+using WpsProgressCallback = std::function<void(const WpsProgress& progress)>;
+
+// Limits a scan to some networks or channels, which finishes much faster than a full scan
+struct ScanOptions {
+    std::vector<std::string> ssids;  // probe for these networks only, empty for any
+    std::vector<int> frequencies;    // scan only these frequencies (MHz), empty for all
+    bool passive = false;            // listen for beacons only, never transmit probe requests
+    int timeoutMs = 0;               // longest wait for the scan to complete, 0 for the manager's scan timeout
+};
+
+// The access point the adapter is currently associated with
+struct ConnectionInfo {
+    std::string ssid;
+    std::string bssid;
+    int frequency = 0;              // MHz
+    int signalStrength = 0;         // dBm
+    bool fastTransition = false;    // 802.11r FT key management negotiated
+    std::string apMldAddress;       // Wi-Fi 7 multi-link association, empty otherwise
+    int noise = 0;                  // noise floor in dBm, 0 when not reported
+    int linkSpeedMbps = 0;          // current transmit rate, 0 when not reported
+};
+
+// Rates and frame counters of the current association, -1 where the platform does not report a counter
+struct LinkStats {
+    uint32_t txBitrateKbps = 0;     // rate of the last transmitted frame, 0 when not reported
+    uint32_t rxBitrateKbps = 0;     // rate of the last received frame, 0 when not reported
+    int txMcs = -1;                 // HT/VHT/HE/EHT MCS index of the transmit rate
+    int rxMcs = -1;
+    int64_t txPackets = -1;
+    int64_t rxPackets = -1;
+    int64_t txRetries = -1;         // retransmissions
+    int64_t txFailed = -1;          // frames given up on after all retries
+    int64_t rxDropped = -1;         // received frames dropped by the driver or stack
+};
+
+// One affiliated link of a Wi-Fi 7 multi-link (MLO) association
+struct MloLink {
+    int linkId = 0;
+    std::string apLinkAddress;      // BSSID of the AP on this link
+    int frequency = 0;              // MHz
+    int signalStrength = 0;         // dBm, 0 if unknown
+};
+
+// A channel the regulatory domain permits on this adapter
+struct ChannelInfo {
+    int frequency = 0;              // MHz
+    int maxPowerMbm = 0;            // maximum transmit power in mBm (1/100 dBm)
+    bool dfs = false;               // radar detection required before transmitting
+    bool noIr = false;              // no initiating radiation: no beaconing or active probing
+};
+
+// Link state used by connection diagnostics
+struct LinkDiagnostics {
+    bool adapterPresent = false;
+    bool radioEnabled = false;      // not blocked by rfkill or airplane mode
+    bool associated = false;
+    bool authenticated = false;     // key handshake / 802.1X completed
+    std::string ssid;
+    std::string bssid;
+    std::string ipAddress;          // IPv4 address, empty without a lease
+    std::string gateway;            // default gateway through the WiFi interface
+    std::vector<std::string> dnsServers;
+};
+
+// Layer 3 configuration of the wireless interface
+struct IpInfo {
+    std::vector<std::string> ipv4Addresses;  // CIDR notation, e.g. "192.168.1.20/24"
+    std::vector<std::string> ipv6Addresses;  // CIDR notation, link-local addresses included
+    std::string gateway;            // IPv4 default gateway through the interface, empty if none
+    std::string ipv6Gateway;
+    std::vector<std::string> dnsServers;
+    bool dhcp = false;              // the IPv4 address was leased over DHCP
+    std::string dhcpServer;         // empty if unknown
+    int64_t leaseObtained = 0;      // Unix time, 0 if unknown
+    int64_t leaseExpires = 0;       // Unix time, 0 if unknown
+};
+
+// Addressing handed out to hotspot clients
+struct HotspotDhcpConfig {
+    std::string rangeStart;         // first client address, e.g. "192.168.4.2"
+    std::string rangeEnd;           // last client address
+    std::string gateway;            // address of the hotspot itself, announced as the router
+    int prefixLength = 24;          // subnet of the gateway and the range
+    std::vector<std::string> dnsServers;  // empty announces the gateway
+};
+
+// Access point settings for a hotspot
+struct HotspotConfig {
+    std::string ssid;
+    std::string password;           // empty for an open hotspot
+    int channel = 0;                // 0 lets the platform choose
+    HotspotDhcpConfig dhcp;         // empty gateway keeps the platform's addressing
+    std::string shareFrom;          // upstream interface whose connection clients share through NAT, empty for none
+    bool redirectDns = false;       // answer every DNS query with the gateway address, for captive portals
+    int timeoutMs = 0;              // longest wait for the access point to start, 0 for the manager's hotspot timeout
+};
+
+// A device associated with the hotspot
+struct HotspotClient {
+    std::string macAddress;
+    std::string ipAddress;          // from the DHCP lease, empty until one is handed out
+    std::string hostname;           // as sent by the client with its DHCP request, may be empty
+    int signalStrength = 0;         // dBm, 0 if unknown
+    int connectedSeconds = -1;      // time since association, -1 if unknown
+};
+
+// Channel occupancy reported by the radio, times are -1 when not reported
+struct ChannelSurvey {
+    int frequency = 0;              // in MHz
+    int channel = 0;
+    bool inUse = false;             // the channel the interface is currently on
+    int noise = 0;                  // noise floor in dBm, 0 when not reported
+    int64_t activeTimeMs = -1;      // time the radio spent on the channel
+    int64_t busyTimeMs = -1;        // time the medium was sensed busy
+    int64_t receiveTimeMs = -1;     // time spent receiving
+    int64_t transmitTimeMs = -1;    // time spent transmitting
+};
+
+// A network profile saved in the platform's profile store
+struct SavedProfile {
+    std::string name;               // profile name, often but not necessarily the SSID
+    std::string ssid;
+    SecurityType security = SecurityType::UNKNOWN;
+    bool autoConnect = true;        // joined automatically when in range
+};
+
+// A wireless adapter present on the system
+struct InterfaceInfo {
+    std::string name;               // e.g. "wlan0", or the adapter description where unnamed
+    std::string macAddress;
+    std::string driver;             // kernel driver or adapter description
+    bool selected = false;          // the interface the manager operates on
+    bool supportsAp = false;
+    bool supportsMonitor = false;
+    bool supportsMesh = false;
+    bool supportsP2p = false;
+    bool band2Ghz = false;
+    bool band5Ghz = false;
+    bool band6Ghz = false;
+};
+
+// Monitor mode capture settings
+struct CaptureConfig {
+    int channel = 0;                 // channel to capture on, 0 keeps the current one
+    bool includeManagement = true;
+    bool includeControl = true;
+    bool includeData = true;
+    std::string monitorInterface;    // name of the monitor interface, empty for a default
+    
+    // Channel hopping, the capture cycles through hopChannels staying dwellMs on each
+    std::vector<int> hopChannels;
+    int dwellMs = 250;
+};
+
+// Individual Target Wake Time agreement to request (802.11ax)
+struct TwtConfig {
+    uint64_t wakeIntervalUs = 0;    // time between service periods
+    uint32_t wakeDurationUs = 0;    // minimum awake time per service period, up to 65280
+    bool triggerEnabled = false;    // service periods start with a trigger frame from the AP
+    bool announced = false;         // the station announces itself before the AP sends
+};
+
+// TWT support and the agreement requested on the current association
+struct TwtStatus {
+    bool apResponder = false;       // from the AP's HE Capabilities element
+    bool localRequester = false;    // from the adapter's HE MAC capabilities
+    bool agreementRequested = false;
+    TwtConfig config;               // parameters of the requested agreement
+};
+
+// Wake-on-WLAN packet pattern, matched against received frames from offset on
+struct WowlanPattern {
+    std::vector<uint8_t> pattern;
+    std::vector<uint8_t> mask;      // one bit per pattern byte, least significant bit first
+    int offset = 0;
+};
+
+// Wake-on-WLAN triggers, none set disables WoWLAN
+struct WowlanConfig {
+    bool magicPacket = false;
+    bool disconnect = false;        // wake when the link to the access point is lost
+    std::vector<WowlanPattern> patterns;
+};
+
+// Wake-on-WLAN triggers the adapter supports
+struct WowlanCapabilities {
+    bool magicPacket = false;
+    bool disconnect = false;
+    int maxPatterns = 0;
+    int minPatternLength = 0;
+    int maxPatternLength = 0;
+    int maxPatternOffset = 0;
+};
+
+// Roaming assistance available on the current association, per side
+struct RoamingFeatures {
+    bool apNeighborReport = false;      // 802.11k, from the RM Enabled Capabilities element
+    bool apBssTransition = false;       // 802.11v, from the Extended Capabilities element
+    bool apFastTransition = false;      // 802.11r, from the Mobility Domain element
+    bool localNeighborReport = false;
+    bool localBssTransition = false;
+    bool localFastTransition = false;
+};
+
+// When the station looks for a stronger access point of the network it is connected to
+struct RoamingPolicy {
+    int rssiThreshold = -70;        // scan for a better access point below this signal, in dBm
+    int scanIntervalSeconds = 30;   // how often to scan while below the threshold
+};
+
+// Outcome of a Fine Timing Measurement (802.11mc) exchange with an access point
+struct RangingResult {
+    std::string bssid;
+    int64_t distanceMm = 0;
+    int64_t distanceVarianceMm2 = 0;
+    int64_t rttPs = 0;              // average round trip time in picoseconds
+    int attempts = 0;               // FTM requests sent
+    int successes = 0;              // FTM requests answered
+};
+
+// A station on the current BSS that a direct TDLS link can be set up with
+struct TdlsPeer {
+    std::string address;
+    std::string ipAddress;          // empty if unknown
+    bool linked = false;            // a TDLS link to the peer is up
+};
+
+// Ad-hoc (IBSS) network to create or join
+struct AdHocConfig {
+    std::string ssid;
+    int channel = 1;
+    std::string password;           // WPA-None passphrase, empty for an open network
+};
+
+// 802.11s mesh network to join
+struct MeshConfig {
+    std::string meshId;
+    int channel = 0;                // 0 lets the driver pick
+    std::string password;           // SAE password, empty for an open mesh
+    std::string interfaceName;      // mesh interface to create, empty for "<iface>mesh"
+};
+
+// Peer link state of a mesh neighbor (802.11s Mesh Peering Management)
+enum class MeshPeerLinkState {
+    LISTEN,
+    OPEN_SENT,
+    OPEN_RECEIVED,
+    CONFIRM_RECEIVED,
+    ESTABLISHED,
+    HOLDING,
+    BLOCKED,
+    UNKNOWN
+};
+
+// A neighbor in the mesh
+struct MeshPeer {
+    std::string address;
+    MeshPeerLinkState linkState = MeshPeerLinkState::UNKNOWN;
+    int signalStrength = 0;         // dBm, 0 if unknown
+    int inactiveMs = -1;            // time since the last frame, -1 if unknown
+};
+
+// Wi-Fi Aware (NAN) service to advertise
+struct NanPublishConfig {
+    std::string serviceName;
+    std::vector<uint8_t> serviceInfo;   // service specific info sent with the announcement
+    bool unsolicited = true;            // announce without being asked
+    bool solicited = true;              // answer active subscribers
+    int ttlSeconds = 0;                 // 0 publishes until cancelled
+};
+
+// Wi-Fi Aware (NAN) service to look for
+struct NanSubscribeConfig {
+    std::string serviceName;
+    std::vector<uint8_t> serviceInfo;
+    bool active = true;                 // send queries instead of only listening
+    int ttlSeconds = 0;                 // 0 subscribes until cancelled
+};
+
+enum class NanEventType {
+    DISCOVERED,     // a subscription matched a publisher, or a subscriber answered a publication
+    RECEIVED,       // a follow-up message arrived
+    TERMINATED      // a publication or subscription ended
+};
+
+// Notification from an active publication or subscription
+struct NanEvent {
+    NanEventType type = NanEventType::DISCOVERED;
+    int handle = 0;                     // our publish or subscribe id
+    int peerInstanceId = 0;             // the peer's publish or subscribe id
+    std::string peerAddress;
+    std::vector<uint8_t> serviceInfo;   // service info or follow-up payload
+};
+
+// WPS method used to authorize a Wi-Fi Direct connection
+enum class P2pWpsMethod {
+    PBC,            // push button on both devices
+    PIN_DISPLAY,    // we display the PIN, the peer enters it
+    PIN_KEYPAD      // the peer displays the PIN, we enter it
+};
+
+// A Wi-Fi Direct device found during discovery
+struct P2pPeer {
+    std::string deviceAddress;
+    std::string deviceName;
+    std::string deviceType;     // primary device type, e.g. "10-0050F204-5"
+    bool groupOwner = false;    // currently owns a group
+};
+
+// Settings for connecting to a Wi-Fi Direct peer
+struct P2pConnectConfig {
+    std::string peerAddress;
+    P2pWpsMethod method = P2pWpsMethod::PBC;
+    std::string pin;            // 8-digit PIN, required for the PIN methods
+    int goIntent = 7;           // 0-15, willingness to become group owner
+};
+
+// A Wi-Fi Direct group this device has joined or formed
+struct P2pGroup {
+    std::string interfaceName;  // group network interface
+    std::string ssid;
+    bool groupOwner = false;
+    std::string goDeviceAddress;
+    int frequency = 0;          // MHz
+};
+
+// Receives each captured frame including its radiotap header, called from the capture thread
+using FrameCallback = std::function<void(const uint8_t* data, size_t length, uint64_t timestampUs)>;
+
+// Called on a platform thread when the OS reports a connectivity change
+using NetworkChangeCallback = std::function<void()>;
+
+// Receives each reading of the signal monitor while associated, called from the monitor thread
+using SignalCallback = std::function<void(const ConnectionInfo& info)>;
+
+// Connection state change pushed by the driver or WLAN service
+enum class WifiEventType {
+    CONNECTED,
+    CONNECTION_LOST,
+    ROAMED,                 // moved to another access point of the same network
+    SCAN_COMPLETE,
+    SIGNAL_CHANGED,         // signal crossed the configured threshold
+    HOTSPOT_CLIENT_JOINED,
+    HOTSPOT_CLIENT_LEFT
+};
+
+struct WifiEventInfo {
+    WifiEventType type = WifiEventType::CONNECTED;
+    std::string ssid;           // CONNECTED, where reported
+    std::string bssid;          // CONNECTED and ROAMED
+    int signalStrength = 0;     // SIGNAL_CHANGED, dBm, 0 if only the threshold crossing is known
+    bool signalLow = false;     // SIGNAL_CHANGED, true when the signal fell below the threshold
+    std::string clientAddress;  // HOTSPOT_CLIENT_JOINED and HOTSPOT_CLIENT_LEFT
+};
+
+// Called on a platform thread for each connection event
+using WifiEventCallback = std::function<void(const WifiEventInfo& event)>;
+
+// Passpoint (Hotspot 2.0) credential
+struct PasspointProfile {
+    std::string domain;              // home service provider FQDN
+    std::string realm;               // NAI realm
+    std::vector<std::string> homeOis; // roaming consortium OIs, hex encoded
+    std::string friendlyName;
+    EapConfig eap;                   // TTLS or TLS credentials
+};
+
+// Channel number of a center frequency in MHz, 0 if unknown. 6 GHz channel numbers repeat those of
+// 2.4 and 5 GHz, so a channel only identifies a frequency together with its band.
+inline int frequencyToChannel(int frequency) {
+    if (frequency == 2484) {
+        return 14;
+    } else if (frequency >= 2412 && frequency <= 2472) {
+        return (frequency - 2407) / 5;
+    } else if (frequency >= 5000 && frequency <= 5900) {
+        return (frequency - 5000) / 5;
+    } else if (frequency == 5935) {
+        return 2;
+    } else if (frequency >= 5955 && frequency <= 7115) {
+        return (frequency - 5950) / 5;
+    }
+    return 0;
+}
+
 struct NetworkInfo {
     std::string ssid;
     std::string bssid;
@@ -28,6 +534,11 @@ struct NetworkInfo {
     SecurityType security;
     int channel;
     int frequency;          // in MHz
+    PmfMode pmf = PmfMode::UNKNOWN;
+    bool wpsEnabled = false;
+    bool ehtSupported = false;      // Wi-Fi 7 (802.11be) EHT capabilities advertised
+    bool mloCapable = false;        // AP is affiliated with a multi-link device
+    std::vector<uint8_t> informationElements;  // as received in the beacon or probe response, empty if not reported
     
     // Additional fields that might be useful
     bool isSecure() const { return security != SecurityType::NONE; }
@@ -19,7 +19,24 @@ if %ERRORLEVEL% neq 0 (
 cd ..
 
 echo.
-echo Step 2: Running Rust example...
+echo Step 2: Checking the C API binary interface...
+if not exist build\test_abi.exe (
+  echo Error: build\test_abi.exe not found.
+  echo Please run build_all.bat first to build the project.
+  exit /b 1
+)
+
+cd build
+.\test_abi.exe
+if %ERRORLEVEL% neq 0 (
+  echo C API binary interface check failed with error code %ERRORLEVEL%.
+  cd ..
+  exit /b %ERRORLEVEL%
+)
+cd ..
+
+echo.
+echo Step 3: Running Rust example...
 if not exist wifi-rs\target (
   echo Error: Rust project hasn't been built.
   echo Please run build_all.bat first to build the project.
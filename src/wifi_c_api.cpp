@@ -1,90 +1,1962 @@
 #include "wifi_c_api.h"
 #include "wifi_manager.hpp"
 #include "wifi_logger.hpp"
+#include <algorithm>
+#include <sstream>
 #include <string>
 #include <vector>
 #include <cstring>
 
 extern "C" {
 
+// Version of the C API
+uint32_t wifi_api_version(void) {
+    return WIFI_API_VERSION;
+}
+
+// Route log messages to a callback
+void wifi_set_log_callback(WifiLogCallback callback, void* user_data) {
+    if (!callback) {
+        wificpp::Logger::getInstance().setSink(nullptr);
+        return;
+    }
+    wificpp::Logger::getInstance().setSink([callback, user_data](wificpp::LogLevel level, const std::string& message) {
+        callback(static_cast<WifiLogLevel>(level), message.c_str(), user_data);
+    });
+}
+
+// Set the minimum level of logged messages
+void wifi_set_log_level(WifiLogLevel level) {
+    wificpp::Logger::getInstance().setLogLevel(static_cast<wificpp::LogLevel>(level));
+}
+
 // Create a new WifiManager instance
 WifiManager* wifi_manager_new() {
     try {
-        return reinterpret_cast<WifiManager*>(new wificpp::WifiManager());
+        return reinterpret_cast<WifiManager*>(new wificpp::WifiManager());
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to create WifiManager: ", e.what());
+        return nullptr;
+    }
+}
+
+// Create a WifiManager instance for a specific adapter
+WifiManager* wifi_manager_new_with_interface(const char* interface_name) {
+    if (!interface_name) {
+        return nullptr;
+    }
+    
+    try {
+        return reinterpret_cast<WifiManager*>(new wificpp::WifiManager(std::string(interface_name)));
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to create WifiManager: ", e.what());
+        return nullptr;
+    }
+}
+
+// Delete a WifiManager instance
+void wifi_manager_delete(WifiManager* manager) {
+    if (manager) {
+        delete reinterpret_cast<wificpp::WifiManager*>(manager);
+    }
+}
+
+// Helper function to convert wificpp::NetworkInfo to WifiNetworkInfo
+static WifiNetworkInfo convert_network_info(const wificpp::NetworkInfo& info) {
+    WifiNetworkInfo result;
+    
+    // Allocate memory for the C-style strings
+    char* ssid = new char[info.ssid.length() + 1];
+    std::strcpy(ssid, info.ssid.c_str());
+    
+    char* bssid = new char[info.bssid.length() + 1];
+    std::strcpy(bssid, info.bssid.c_str());
+
+    result.ssid = ssid;
+    result.bssid = bssid;
+    result.signal_strength = info.signalStrength;
+    result.security_type = static_cast<int32_t>(info.security);
+    result.channel = info.channel;
+    result.frequency = info.frequency;
+    result.pmf = static_cast<int32_t>(info.pmf);
+    result.wps_enabled = info.wpsEnabled ? 1 : 0;
+    result.eht = info.ehtSupported ? 1 : 0;
+    result.mlo = info.mloCapable ? 1 : 0;
+
+    // Copied with the rest of the record, so they match the scan that returned it
+    uint8_t* elements = nullptr;
+    if (!info.informationElements.empty()) {
+        elements = new uint8_t[info.informationElements.size()];
+        std::memcpy(elements, info.informationElements.data(), info.informationElements.size());
+    }
+    result.ie_data = elements;
+    result.ie_len = static_cast<int32_t>(info.informationElements.size());
+    
+    return result;
+}
+
+// Scan for available networks
+WifiNetworkInfo* wifi_manager_scan(WifiManager* manager, int* count) {
+    if (!manager || !count) {
+        return nullptr;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        auto networks = wifiManager->scan();
+        
+        *count = static_cast<int>(networks.size());
+        if (networks.empty()) {
+            return nullptr;
+        }
+        
+        // Allocate the array of network infos
+        WifiNetworkInfo* result = new WifiNetworkInfo[*count];
+        for (int i = 0; i < *count; i++) {
+            result[i] = convert_network_info(networks[i]);
+        }
+        
+        return result;
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to scan for networks: ", e.what());
+        *count = 0;
+        return nullptr;
+    }
+}
+
+// Scan and wait for the scan's completion
+bool wifi_manager_scan_fresh(WifiManager* manager, WifiNetworkInfo** networks, int* count) {
+    if (!manager || !networks || !count) {
+        return false;
+    }
+    *networks = nullptr;
+    *count = 0;
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        std::vector<wificpp::NetworkInfo> results;
+        if (!wifiManager->scanFresh(results)) {
+            return false;
+        }
+        
+        *count = static_cast<int>(results.size());
+        if (!results.empty()) {
+            *networks = new WifiNetworkInfo[*count];
+            for (int i = 0; i < *count; i++) {
+                (*networks)[i] = convert_network_info(results[i]);
+            }
+        }
+        return true;
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to scan for networks: ", e.what());
+        *count = 0;
+        return false;
+    }
+}
+
+// Scan only the given networks or frequencies
+// Shared by the directed scans, options may be NULL for a scan of everything
+static WifiNetworkInfo* scan_with_options(WifiManager* manager, const WifiScanOptions* options, int32_t timeout_ms,
+                                          int* count) {
+    if (!manager || !count) {
+        return nullptr;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        wificpp::ScanOptions scanOptions;
+        if (options) {
+            for (int32_t i = 0; options->ssids && i < options->ssid_count; i++) {
+                if (options->ssids[i]) {
+                    scanOptions.ssids.push_back(options->ssids[i]);
+                }
+            }
+            if (options->frequencies && options->frequency_count > 0) {
+                scanOptions.frequencies.assign(options->frequencies, options->frequencies + options->frequency_count);
+            }
+            scanOptions.passive = options->passive != 0;
+        }
+        scanOptions.timeoutMs = timeout_ms;
+        auto networks = wifiManager->scanWithOptions(scanOptions);
+        
+        *count = static_cast<int>(networks.size());
+        if (networks.empty()) {
+            return nullptr;
+        }
+        
+        WifiNetworkInfo* result = new WifiNetworkInfo[*count];
+        for (int i = 0; i < *count; i++) {
+            result[i] = convert_network_info(networks[i]);
+        }
+        return result;
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to scan for networks: ", e.what());
+        *count = 0;
+        return nullptr;
+    }
+}
+
+WifiNetworkInfo* wifi_manager_scan_with_options(WifiManager* manager, const WifiScanOptions* options, int* count) {
+    if (!options) {
+        return nullptr;
+    }
+    return scan_with_options(manager, options, 0, count);
+}
+
+// Scan with its own limit on the wait for the scan to complete
+WifiNetworkInfo* wifi_manager_scan_with_timeout(WifiManager* manager, const WifiScanOptions* options, int32_t timeout_ms,
+                                                int* count) {
+    return scan_with_options(manager, options, timeout_ms, count);
+}
+
+// Abort the scan or connect in progress on another thread
+bool wifi_manager_cancel(WifiManager* manager) {
+    if (!manager) {
+        return false;
+    }
+    
+    try {
+        return reinterpret_cast<wificpp::WifiManager*>(manager)->cancel();
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to cancel: ", e.what());
+        return false;
+    }
+}
+
+// Longest waits for scans and hotspot starts that do not set their own
+bool wifi_manager_set_timeouts(WifiManager* manager, int32_t scan_timeout_ms, int32_t hotspot_timeout_ms) {
+    if (!manager) {
+        return false;
+    }
+    
+    try {
+        reinterpret_cast<wificpp::WifiManager*>(manager)->setOperationTimeouts(scan_timeout_ms, hotspot_timeout_ms);
+        return true;
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to set timeouts: ", e.what());
+        return false;
+    }
+}
+
+// Information elements of an access point in the most recent scan
+int wifi_manager_get_information_elements(WifiManager* manager, const char* bssid, uint8_t* buffer, int size) {
+    if (!manager || !bssid || (buffer && size < 0)) {
+        return -1;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        std::vector<uint8_t> elements;
+        if (!wifiManager->getInformationElements(bssid, elements)) {
+            return -1;
+        }
+        if (buffer) {
+            std::memcpy(buffer, elements.data(), std::min(elements.size(), static_cast<size_t>(size)));
+        }
+        return static_cast<int>(elements.size());
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to read information elements: ", e.what());
+        return -1;
+    }
+}
+
+// Connect to a network
+bool wifi_manager_connect(WifiManager* manager, const char* ssid, const char* password) {
+    if (!manager || !ssid) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        return wifiManager->connect(ssid, password ? password : "");
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to connect to network: ", e.what());
+        return false;
+    }
+}
+
+// Connect to a network with additional options
+bool wifi_manager_connect_with_options(WifiManager* manager, const char* ssid, const char* password, const WifiConnectOptions* options) {
+    if (!manager || !ssid || !options) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        wificpp::ConnectOptions connectOptions;
+        connectOptions.ephemeral = options->ephemeral != 0;
+        if (options->frequencies && options->frequency_count > 0) {
+            connectOptions.frequencies.assign(options->frequencies, options->frequencies + options->frequency_count);
+        }
+        connectOptions.hidden = options->hidden != 0;
+        if (options->bssid) {
+            connectOptions.bssid = options->bssid;
+        }
+        connectOptions.timeoutSeconds = options->timeout_seconds;
+        connectOptions.staticIp.ipv4Address = options->ipv4_address ? options->ipv4_address : "";
+        connectOptions.staticIp.ipv4Gateway = options->ipv4_gateway ? options->ipv4_gateway : "";
+        connectOptions.staticIp.ipv6Address = options->ipv6_address ? options->ipv6_address : "";
+        connectOptions.staticIp.ipv6Gateway = options->ipv6_gateway ? options->ipv6_gateway : "";
+        std::stringstream servers(options->dns_servers ? options->dns_servers : "");
+        std::string server;
+        while (std::getline(servers, server, ',')) {
+            if (!server.empty()) {
+                connectOptions.staticIp.dnsServers.push_back(server);
+            }
+        }
+        return wifiManager->connectWithOptions(ssid, password ? password : "", connectOptions);
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to connect to network: ", e.what());
+        return false;
+    }
+}
+
+// Helper function to convert WifiEapConfig to wificpp::EapConfig
+static bool convert_eap_config(const WifiEapConfig* eap, wificpp::EapConfig& config) {
+    switch (eap->method) {
+        case WIFI_EAP_PEAP:
+            config.method = wificpp::EapMethod::PEAP;
+            break;
+        case WIFI_EAP_TLS:
+            config.method = wificpp::EapMethod::TLS;
+            break;
+        case WIFI_EAP_TTLS:
+            config.method = wificpp::EapMethod::TTLS;
+            break;
+        case WIFI_EAP_SIM:
+            config.method = wificpp::EapMethod::SIM;
+            break;
+        case WIFI_EAP_AKA:
+            config.method = wificpp::EapMethod::AKA;
+            break;
+        case WIFI_EAP_AKA_PRIME:
+            config.method = wificpp::EapMethod::AKA_PRIME;
+            break;
+        default:
+            wificpp::Logger::getInstance().error("Unknown EAP method: ", eap->method);
+            return false;
+    }
+    config.phase2 = eap->phase2 == WIFI_PHASE2_PAP ?
+        wificpp::Phase2Method::PAP : wificpp::Phase2Method::MSCHAPV2;
+    config.identity = eap->identity ? eap->identity : "";
+    config.anonymousIdentity = eap->anonymous_identity ? eap->anonymous_identity : "";
+    config.password = eap->password ? eap->password : "";
+    config.caCert = eap->ca_cert ? eap->ca_cert : "";
+    config.validateServer = eap->disable_server_validation == 0;
+    config.serverCertHash = eap->server_cert_hash ? eap->server_cert_hash : "";
+    config.domainMatch = eap->domain_match ? eap->domain_match : "";
+    config.domainSuffixMatch = eap->domain_suffix_match ? eap->domain_suffix_match : "";
+    config.clientCert = eap->client_cert ? eap->client_cert : "";
+    config.privateKey = eap->private_key ? eap->private_key : "";
+    config.privateKeyPassword = eap->private_key_password ? eap->private_key_password : "";
+    if (eap->pkcs12_data && eap->pkcs12_len > 0) {
+        config.pkcs12.assign(eap->pkcs12_data, eap->pkcs12_data + eap->pkcs12_len);
+    }
+    config.certStoreRef = eap->cert_store_ref ? eap->cert_store_ref : "";
+    return true;
+}
+
+// Connect to an enterprise network
+bool wifi_manager_connect_enterprise(WifiManager* manager, const char* ssid, const WifiEapConfig* eap) {
+    if (!manager || !ssid || !eap) {
+        return false;
+    }
+    
+    try {
+        wificpp::EapConfig config;
+        if (!convert_eap_config(eap, config)) {
+            return false;
+        }
+        bool sim_based = config.method == wificpp::EapMethod::SIM ||
+                         config.method == wificpp::EapMethod::AKA ||
+                         config.method == wificpp::EapMethod::AKA_PRIME;
+        if (config.identity.empty() && !sim_based) {
+            return false;
+        }
+        
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        return wifiManager->connectEnterprise(ssid, config);
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to connect to enterprise network: ", e.what());
+        return false;
+    }
+}
+
+// Connect using WPS push-button configuration
+bool wifi_manager_connect_wps_pbc(WifiManager* manager, int32_t timeout_seconds) {
+    return wifi_manager_connect_wps_pbc_with_progress(manager, timeout_seconds, nullptr, nullptr);
+}
+
+// Connect using a WPS PIN
+bool wifi_manager_connect_wps_pin(WifiManager* manager, const char* bssid, const char* pin, int32_t timeout_seconds) {
+    return wifi_manager_connect_wps_pin_with_progress(manager, bssid, pin, timeout_seconds, nullptr, nullptr);
+}
+
+// Helper function to forward WPS progress to a C callback, empty without one
+static wificpp::WpsProgressCallback wps_progress_callback(WifiWpsProgressCallback callback, void* user_data) {
+    if (!callback) {
+        return nullptr;
+    }
+    return [callback, user_data](const wificpp::WpsProgress& progress) {
+        WifiWpsProgress result;
+        result.stage = static_cast<int32_t>(progress.stage);
+        result.failure = static_cast<int32_t>(progress.failure);
+        result.config_error = progress.configError;
+        callback(&result, user_data);
+    };
+}
+
+// Connect using WPS push-button configuration, reporting progress
+bool wifi_manager_connect_wps_pbc_with_progress(WifiManager* manager, int32_t timeout_seconds,
+                                                WifiWpsProgressCallback callback, void* user_data) {
+    if (!manager || timeout_seconds <= 0) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        return wifiManager->connectWpsPbc(timeout_seconds, wps_progress_callback(callback, user_data));
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to connect with WPS: ", e.what());
+        return false;
+    }
+}
+
+// Connect using a WPS PIN, reporting progress
+bool wifi_manager_connect_wps_pin_with_progress(WifiManager* manager, const char* bssid, const char* pin, int32_t timeout_seconds,
+                                                WifiWpsProgressCallback callback, void* user_data) {
+    if (!manager || !pin || timeout_seconds <= 0) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        return wifiManager->connectWpsPin(bssid ? bssid : "", pin, timeout_seconds, wps_progress_callback(callback, user_data));
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to connect with WPS PIN: ", e.what());
+        return false;
+    }
+}
+
+// Check whether an EAP method is supported
+bool wifi_manager_is_eap_method_supported(WifiManager* manager, int32_t method) {
+    if (!manager) {
+        return false;
+    }
+    
+    try {
+        WifiEapConfig probe = {};
+        probe.method = method;
+        wificpp::EapConfig config;
+        if (!convert_eap_config(&probe, config)) {
+            return false;
+        }
+        
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        return wifiManager->isEapMethodSupported(config.method);
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to check EAP method support: ", e.what());
+        return false;
+    }
+}
+
+// Install a Passpoint (Hotspot 2.0) credential
+bool wifi_manager_install_passpoint(WifiManager* manager, const WifiPasspointProfile* profile) {
+    if (!manager || !profile || !profile->domain || !profile->realm) {
+        return false;
+    }
+    
+    try {
+        wificpp::PasspointProfile config;
+        config.domain = profile->domain;
+        config.realm = profile->realm;
+        config.friendlyName = profile->friendly_name ? profile->friendly_name : "";
+        if (profile->home_ois) {
+            std::string ois = profile->home_ois;
+            size_t start = 0;
+            while (start <= ois.size()) {
+                size_t end = ois.find(',', start);
+                if (end == std::string::npos) {
+                    end = ois.size();
+                }
+                if (end > start) {
+                    config.homeOis.push_back(ois.substr(start, end - start));
+                }
+                start = end + 1;
+            }
+        }
+        if (!convert_eap_config(&profile->credentials, config.eap)) {
+            return false;
+        }
+        
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        return wifiManager->installPasspointProfile(config);
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to install Passpoint profile: ", e.what());
+        return false;
+    }
+}
+
+// Remove a Passpoint (Hotspot 2.0) credential
+bool wifi_manager_remove_passpoint(WifiManager* manager, const char* domain) {
+    if (!manager || !domain) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        return wifiManager->removePasspointProfile(domain);
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to remove Passpoint profile: ", e.what());
+        return false;
+    }
+}
+
+// Enable or disable probe-request privacy
+bool wifi_manager_set_probe_privacy(WifiManager* manager, bool enabled) {
+    if (!manager) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        return wifiManager->setProbePrivacy(enabled);
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to set probe privacy: ", e.what());
+        return false;
+    }
+}
+
+// Check whether probe-request privacy is enabled
+bool wifi_manager_is_probe_privacy_enabled(WifiManager* manager) {
+    if (!manager) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        return wifiManager->isProbePrivacyEnabled();
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to query probe privacy: ", e.what());
+        return false;
+    }
+}
+
+// Start monitor mode capture
+bool wifi_manager_start_capture(WifiManager* manager, const WifiCaptureConfig* config,
+                                WifiFrameCallback callback, void* user_data) {
+    if (!manager || !config || !callback) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        wificpp::CaptureConfig captureConfig;
+        captureConfig.channel = config->channel;
+        captureConfig.includeManagement = config->include_management != 0;
+        captureConfig.includeControl = config->include_control != 0;
+        captureConfig.includeData = config->include_data != 0;
+        if (config->monitor_interface) {
+            captureConfig.monitorInterface = config->monitor_interface;
+        }
+        if (config->hop_channels && config->hop_channel_count > 0) {
+            captureConfig.hopChannels.assign(config->hop_channels,
+                                             config->hop_channels + config->hop_channel_count);
+            captureConfig.dwellMs = config->dwell_ms > 0 ? config->dwell_ms : captureConfig.dwellMs;
+        }
+        return wifiManager->startCapture(captureConfig,
+            [callback, user_data](const uint8_t* data, size_t length, uint64_t timestampUs) {
+                callback(data, static_cast<int32_t>(length), timestampUs, user_data);
+            });
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to start capture: ", e.what());
+        return false;
+    }
+}
+
+// Stop monitor mode capture
+bool wifi_manager_stop_capture(WifiManager* manager) {
+    if (!manager) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        return wifiManager->stopCapture();
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to stop capture: ", e.what());
+        return false;
+    }
+}
+
+// Check whether a capture is running
+bool wifi_manager_is_capturing(WifiManager* manager) {
+    if (!manager) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        return wifiManager->isCapturing();
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to query capture state: ", e.what());
+        return false;
+    }
+}
+
+// Get per-channel survey data
+WifiChannelSurvey* wifi_manager_channel_survey(WifiManager* manager, int* count) {
+    if (!manager || !count) {
+        return nullptr;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        auto surveys = wifiManager->channelSurvey();
+        
+        *count = static_cast<int>(surveys.size());
+        if (surveys.empty()) {
+            return nullptr;
+        }
+        
+        WifiChannelSurvey* result = new WifiChannelSurvey[*count];
+        for (int i = 0; i < *count; i++) {
+            const auto& survey = surveys[i];
+            result[i].frequency = survey.frequency;
+            result[i].channel = survey.channel;
+            result[i].in_use = survey.inUse ? 1 : 0;
+            result[i].noise = survey.noise;
+            result[i].active_time_ms = survey.activeTimeMs;
+            result[i].busy_time_ms = survey.busyTimeMs;
+            result[i].receive_time_ms = survey.receiveTimeMs;
+            result[i].transmit_time_ms = survey.transmitTimeMs;
+        }
+        return result;
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to get channel survey: ", e.what());
+        *count = 0;
+        return nullptr;
+    }
+}
+
+// Free the array returned by wifi_manager_channel_survey
+void wifi_free_channel_survey(WifiChannelSurvey* surveys) {
+    delete[] surveys;
+}
+
+// Copy a string into a fixed-size, NUL-terminated buffer
+static void copy_string(char* dest, size_t size, const std::string& src) {
+    size_t len = std::min(src.size(), size - 1);
+    std::memcpy(dest, src.data(), len);
+    dest[len] = '\0';
+}
+
+// Helper function to convert wificpp::ConnectionInfo to WifiConnectionInfo
+static void convert_connection_info(const wificpp::ConnectionInfo& connection, WifiConnectionInfo* info) {
+    std::memset(info, 0, sizeof(*info));
+    copy_string(info->ssid, sizeof(info->ssid), connection.ssid);
+    copy_string(info->bssid, sizeof(info->bssid), connection.bssid);
+    info->frequency = connection.frequency;
+    info->signal_strength = connection.signalStrength;
+    info->fast_transition = connection.fastTransition ? 1 : 0;
+    copy_string(info->ap_mld_address, sizeof(info->ap_mld_address), connection.apMldAddress);
+    info->noise = connection.noise;
+    info->link_speed_mbps = connection.linkSpeedMbps;
+}
+
+// Get link state for connection diagnostics
+bool wifi_manager_get_link_diagnostics(WifiManager* manager, WifiLinkDiagnostics* diagnostics) {
+    if (!manager || !diagnostics) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        wificpp::LinkDiagnostics link;
+        if (!wifiManager->getLinkDiagnostics(link)) {
+            return false;
+        }
+        
+        std::memset(diagnostics, 0, sizeof(*diagnostics));
+        diagnostics->adapter_present = link.adapterPresent ? 1 : 0;
+        diagnostics->radio_enabled = link.radioEnabled ? 1 : 0;
+        diagnostics->associated = link.associated ? 1 : 0;
+        diagnostics->authenticated = link.authenticated ? 1 : 0;
+        copy_string(diagnostics->ssid, sizeof(diagnostics->ssid), link.ssid);
+        copy_string(diagnostics->bssid, sizeof(diagnostics->bssid), link.bssid);
+        copy_string(diagnostics->ip_address, sizeof(diagnostics->ip_address), link.ipAddress);
+        copy_string(diagnostics->gateway, sizeof(diagnostics->gateway), link.gateway);
+        
+        size_t count = std::min<size_t>(link.dnsServers.size(), 4);
+        for (size_t i = 0; i < count; i++) {
+            copy_string(diagnostics->dns_servers[i], sizeof(diagnostics->dns_servers[i]), link.dnsServers[i]);
+        }
+        diagnostics->dns_server_count = static_cast<int32_t>(count);
+        return true;
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to get link diagnostics: ", e.what());
+        return false;
+    }
+}
+
+// Get details of the current association
+bool wifi_manager_get_connection_info(WifiManager* manager, WifiConnectionInfo* info) {
+    if (!manager || !info) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        wificpp::ConnectionInfo connection;
+        if (!wifiManager->getConnectionInfo(connection)) {
+            return false;
+        }
+        
+        convert_connection_info(connection, info);
+        return true;
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to get connection info: ", e.what());
+        return false;
+    }
+}
+
+// Get rates and counters of the current association
+bool wifi_manager_get_link_stats(WifiManager* manager, WifiLinkStats* stats) {
+    if (!manager || !stats) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        wificpp::LinkStats cppStats;
+        if (!wifiManager->getLinkStats(cppStats)) {
+            return false;
+        }
+        stats->tx_bitrate_kbps = cppStats.txBitrateKbps;
+        stats->rx_bitrate_kbps = cppStats.rxBitrateKbps;
+        stats->tx_mcs = cppStats.txMcs;
+        stats->rx_mcs = cppStats.rxMcs;
+        stats->tx_packets = cppStats.txPackets;
+        stats->rx_packets = cppStats.rxPackets;
+        stats->tx_retries = cppStats.txRetries;
+        stats->tx_failed = cppStats.txFailed;
+        stats->rx_dropped = cppStats.rxDropped;
+        return true;
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to get link statistics: ", e.what());
+        return false;
+    }
+}
+
+// Select how connections are made on Linux
+bool wifi_manager_set_linux_backend(WifiManager* manager, WifiLinuxBackend backend) {
+    if (!manager || backend < WIFI_LINUX_BACKEND_AUTO || backend > WIFI_LINUX_BACKEND_WPA_CLI) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        return wifiManager->setLinuxBackend(static_cast<wificpp::LinuxBackend>(backend));
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to select the connection backend: ", e.what());
+        return false;
+    }
+}
+
+// The backend connections are made with
+WifiLinuxBackend wifi_manager_get_linux_backend(WifiManager* manager) {
+    if (!manager) {
+        return WIFI_LINUX_BACKEND_AUTO;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        return static_cast<WifiLinuxBackend>(wifiManager->getLinuxBackend());
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to read the connection backend: ", e.what());
+        return WIFI_LINUX_BACKEND_AUTO;
+    }
+}
+
+// Check whether the process may see network names and BSSIDs
+bool wifi_manager_is_location_authorized(WifiManager* manager) {
+    if (!manager) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        return wifiManager->isLocationAuthorized();
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to check location authorization: ", e.what());
+        return false;
+    }
+}
+
+// Ask the user for Location Services authorization
+bool wifi_manager_request_location_authorization(WifiManager* manager) {
+    if (!manager) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        return wifiManager->requestLocationAuthorization();
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to request location authorization: ", e.what());
+        return false;
+    }
+}
+
+// Check whether the process may manage the adapter
+bool wifi_manager_has_admin_privileges(WifiManager* manager) {
+    if (!manager) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        return wifiManager->hasAdminPrivileges();
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to check privileges: ", e.what());
+        return false;
+    }
+}
+
+// Check whether the radio is on
+bool wifi_manager_is_radio_enabled(WifiManager* manager) {
+    if (!manager) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        return wifiManager->isRadioEnabled();
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to get radio state: ", e.what());
+        return false;
+    }
+}
+
+// Switch the radio on or off
+bool wifi_manager_set_radio_enabled(WifiManager* manager, bool enabled) {
+    if (!manager) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        return wifiManager->setRadioEnabled(enabled);
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to switch the radio: ", e.what());
+        return false;
+    }
+}
+
+// Check whether Wi-Fi Direct is available
+bool wifi_manager_is_p2p_supported(WifiManager* manager) {
+    if (!manager) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        return wifiManager->isP2pSupported();
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to check Wi-Fi Direct support: ", e.what());
+        return false;
+    }
+}
+
+// Search for Wi-Fi Direct peers
+WifiP2pPeer* wifi_manager_p2p_discover(WifiManager* manager, int32_t timeout_seconds, int* count) {
+    if (!manager || !count) {
+        return nullptr;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        auto peers = wifiManager->p2pDiscover(timeout_seconds);
+        
+        *count = static_cast<int>(peers.size());
+        if (peers.empty()) {
+            return nullptr;
+        }
+        
+        WifiP2pPeer* result = new WifiP2pPeer[*count];
+        for (int i = 0; i < *count; i++) {
+            const auto& peer = peers[i];
+            copy_string(result[i].device_address, sizeof(result[i].device_address), peer.deviceAddress);
+            copy_string(result[i].device_name, sizeof(result[i].device_name), peer.deviceName);
+            copy_string(result[i].device_type, sizeof(result[i].device_type), peer.deviceType);
+            result[i].group_owner = peer.groupOwner ? 1 : 0;
+        }
+        return result;
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to discover Wi-Fi Direct peers: ", e.what());
+        *count = 0;
+        return nullptr;
+    }
+}
+
+// Free the array returned by wifi_manager_p2p_discover
+void wifi_free_p2p_peers(WifiP2pPeer* peers) {
+    delete[] peers;
+}
+
+// Negotiate a Wi-Fi Direct group with a peer
+bool wifi_manager_p2p_connect(WifiManager* manager, const WifiP2pConnectConfig* config, int32_t timeout_seconds, WifiP2pGroup* group) {
+    if (!manager || !config || !config->peer_address || !group) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        wificpp::P2pConnectConfig cppConfig;
+        cppConfig.peerAddress = config->peer_address;
+        cppConfig.method = static_cast<wificpp::P2pWpsMethod>(config->wps_method);
+        cppConfig.pin = config->pin ? config->pin : "";
+        cppConfig.goIntent = config->go_intent;
+        
+        wificpp::P2pGroup cppGroup;
+        if (!wifiManager->p2pConnect(cppConfig, timeout_seconds, cppGroup)) {
+            return false;
+        }
+        
+        std::memset(group, 0, sizeof(*group));
+        copy_string(group->interface_name, sizeof(group->interface_name), cppGroup.interfaceName);
+        copy_string(group->ssid, sizeof(group->ssid), cppGroup.ssid);
+        group->group_owner = cppGroup.groupOwner ? 1 : 0;
+        copy_string(group->go_device_address, sizeof(group->go_device_address), cppGroup.goDeviceAddress);
+        group->frequency = cppGroup.frequency;
+        return true;
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to connect to Wi-Fi Direct peer: ", e.what());
+        return false;
+    }
+}
+
+// Leave the current Wi-Fi Direct group
+bool wifi_manager_p2p_disconnect(WifiManager* manager) {
+    if (!manager) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        return wifiManager->p2pDisconnect();
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to leave Wi-Fi Direct group: ", e.what());
+        return false;
+    }
+}
+
+// Check whether Wi-Fi Aware is available
+bool wifi_manager_is_nan_supported(WifiManager* manager) {
+    if (!manager) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        return wifiManager->isNanSupported();
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to check Wi-Fi Aware support: ", e.what());
+        return false;
+    }
+}
+
+// Start advertising a Wi-Fi Aware service
+int32_t wifi_manager_nan_publish(WifiManager* manager, const WifiNanPublishConfig* config) {
+    if (!manager || !config || !config->service_name) {
+        return 0;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        wificpp::NanPublishConfig cppConfig;
+        cppConfig.serviceName = config->service_name;
+        if (config->service_info && config->service_info_len > 0) {
+            cppConfig.serviceInfo.assign(config->service_info, config->service_info + config->service_info_len);
+        }
+        cppConfig.unsolicited = config->unsolicited != 0;
+        cppConfig.solicited = config->solicited != 0;
+        cppConfig.ttlSeconds = config->ttl_seconds;
+        return wifiManager->nanPublish(cppConfig);
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to publish Wi-Fi Aware service: ", e.what());
+        return 0;
+    }
+}
+
+// Start looking for a Wi-Fi Aware service
+int32_t wifi_manager_nan_subscribe(WifiManager* manager, const WifiNanSubscribeConfig* config) {
+    if (!manager || !config || !config->service_name) {
+        return 0;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        wificpp::NanSubscribeConfig cppConfig;
+        cppConfig.serviceName = config->service_name;
+        if (config->service_info && config->service_info_len > 0) {
+            cppConfig.serviceInfo.assign(config->service_info, config->service_info + config->service_info_len);
+        }
+        cppConfig.active = config->active != 0;
+        cppConfig.ttlSeconds = config->ttl_seconds;
+        return wifiManager->nanSubscribe(cppConfig);
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to subscribe to Wi-Fi Aware service: ", e.what());
+        return 0;
+    }
+}
+
+// Stop a Wi-Fi Aware publication or subscription
+bool wifi_manager_nan_cancel(WifiManager* manager, int32_t handle) {
+    if (!manager) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        return wifiManager->nanCancel(handle);
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to cancel Wi-Fi Aware session: ", e.what());
+        return false;
+    }
+}
+
+// Send a Wi-Fi Aware follow-up message
+bool wifi_manager_nan_transmit(WifiManager* manager, int32_t handle, int32_t peer_instance_id, const char* peer_address, const uint8_t* data, int32_t data_len) {
+    if (!manager || !peer_address || (!data && data_len > 0)) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        std::vector<uint8_t> payload;
+        if (data_len > 0) {
+            payload.assign(data, data + data_len);
+        }
+        return wifiManager->nanTransmit(handle, peer_instance_id, peer_address, payload);
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to send Wi-Fi Aware message: ", e.what());
+        return false;
+    }
+}
+
+// Wait for the next Wi-Fi Aware event
+bool wifi_manager_nan_next_event(WifiManager* manager, int32_t timeout_ms, WifiNanEvent* event) {
+    if (!manager || !event) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        wificpp::NanEvent cppEvent;
+        if (!wifiManager->nanNextEvent(timeout_ms, cppEvent)) {
+            return false;
+        }
+        
+        std::memset(event, 0, sizeof(*event));
+        event->type = static_cast<int32_t>(cppEvent.type);
+        event->handle = cppEvent.handle;
+        event->peer_instance_id = cppEvent.peerInstanceId;
+        copy_string(event->peer_address, sizeof(event->peer_address), cppEvent.peerAddress);
+        size_t len = std::min(cppEvent.serviceInfo.size(), sizeof(event->service_info));
+        std::memcpy(event->service_info, cppEvent.serviceInfo.data(), len);
+        event->service_info_len = static_cast<int32_t>(len);
+        return true;
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to read Wi-Fi Aware event: ", e.what());
+        return false;
+    }
+}
+
+// Check whether 802.11s mesh is available
+bool wifi_manager_is_mesh_supported(WifiManager* manager) {
+    if (!manager) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        return wifiManager->isMeshSupported();
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to check mesh support: ", e.what());
+        return false;
+    }
+}
+
+// Join an 802.11s mesh
+bool wifi_manager_mesh_join(WifiManager* manager, const WifiMeshConfig* config) {
+    if (!manager || !config || !config->mesh_id) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        wificpp::MeshConfig cppConfig;
+        cppConfig.meshId = config->mesh_id;
+        cppConfig.channel = config->channel;
+        cppConfig.password = config->password ? config->password : "";
+        cppConfig.interfaceName = config->interface_name ? config->interface_name : "";
+        return wifiManager->meshJoin(cppConfig);
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to join mesh: ", e.what());
+        return false;
+    }
+}
+
+// Leave the 802.11s mesh
+bool wifi_manager_mesh_leave(WifiManager* manager) {
+    if (!manager) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        return wifiManager->meshLeave();
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to leave mesh: ", e.what());
+        return false;
+    }
+}
+
+// Get the mesh neighbors
+WifiMeshPeer* wifi_manager_mesh_peers(WifiManager* manager, int* count) {
+    if (!manager || !count) {
+        return nullptr;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        auto peers = wifiManager->meshPeers();
+        
+        *count = static_cast<int>(peers.size());
+        if (peers.empty()) {
+            return nullptr;
+        }
+        
+        WifiMeshPeer* result = new WifiMeshPeer[*count];
+        for (int i = 0; i < *count; i++) {
+            copy_string(result[i].address, sizeof(result[i].address), peers[i].address);
+            result[i].link_state = static_cast<int32_t>(peers[i].linkState);
+            result[i].signal_strength = peers[i].signalStrength;
+            result[i].inactive_ms = peers[i].inactiveMs;
+        }
+        return result;
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to get mesh peers: ", e.what());
+        *count = 0;
+        return nullptr;
+    }
+}
+
+// Free the array returned by wifi_manager_mesh_peers
+void wifi_free_mesh_peers(WifiMeshPeer* peers) {
+    delete[] peers;
+}
+
+// Check whether ad-hoc mode is available
+bool wifi_manager_is_adhoc_supported(WifiManager* manager) {
+    if (!manager) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        return wifiManager->isAdHocSupported();
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to check ad-hoc support: ", e.what());
+        return false;
+    }
+}
+
+// Create or join an ad-hoc network
+bool wifi_manager_join_adhoc(WifiManager* manager, const WifiAdHocConfig* config) {
+    if (!manager || !config || !config->ssid) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        wificpp::AdHocConfig cppConfig;
+        cppConfig.ssid = config->ssid;
+        cppConfig.channel = config->channel;
+        cppConfig.password = config->password ? config->password : "";
+        return wifiManager->joinAdHoc(cppConfig);
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to join ad-hoc network: ", e.what());
+        return false;
+    }
+}
+
+// Check whether TDLS is available
+bool wifi_manager_is_tdls_supported(WifiManager* manager) {
+    if (!manager) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        return wifiManager->isTdlsSupported();
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to check TDLS support: ", e.what());
+        return false;
+    }
+}
+
+// Probe the current BSS for TDLS peers
+WifiTdlsPeer* wifi_manager_tdls_discover(WifiManager* manager, int timeout_seconds, int* count) {
+    if (!manager || !count) {
+        return nullptr;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        auto peers = wifiManager->tdlsDiscover(timeout_seconds);
+        
+        *count = static_cast<int>(peers.size());
+        if (peers.empty()) {
+            return nullptr;
+        }
+        
+        WifiTdlsPeer* result = new WifiTdlsPeer[*count];
+        for (int i = 0; i < *count; i++) {
+            copy_string(result[i].address, sizeof(result[i].address), peers[i].address);
+            copy_string(result[i].ip_address, sizeof(result[i].ip_address), peers[i].ipAddress);
+            result[i].linked = peers[i].linked ? 1 : 0;
+        }
+        return result;
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to discover TDLS peers: ", e.what());
+        *count = 0;
+        return nullptr;
+    }
+}
+
+// Free the array returned by wifi_manager_tdls_discover
+void wifi_free_tdls_peers(WifiTdlsPeer* peers) {
+    delete[] peers;
+}
+
+// Set up a TDLS link
+bool wifi_manager_tdls_setup(WifiManager* manager, const char* peer_address) {
+    if (!manager || !peer_address) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        return wifiManager->tdlsSetup(peer_address);
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to set up TDLS link: ", e.what());
+        return false;
+    }
+}
+
+// Tear down a TDLS link
+bool wifi_manager_tdls_teardown(WifiManager* manager, const char* peer_address) {
+    if (!manager || !peer_address) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        return wifiManager->tdlsTeardown(peer_address);
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to tear down TDLS link: ", e.what());
+        return false;
+    }
+}
+
+// Check whether RTT ranging is available
+bool wifi_manager_is_rtt_supported(WifiManager* manager) {
+    if (!manager) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        return wifiManager->isRttSupported();
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to check RTT support: ", e.what());
+        return false;
+    }
+}
+
+// Range against an access point with Fine Timing Measurement
+bool wifi_manager_measure_distance(WifiManager* manager, const char* bssid, int frequency, WifiRangingResult* result) {
+    if (!manager || !bssid || !result) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        wificpp::RangingResult ranging;
+        if (!wifiManager->measureDistance(bssid, frequency, ranging)) {
+            return false;
+        }
+        result->distance_mm = ranging.distanceMm;
+        result->distance_variance_mm2 = ranging.distanceVarianceMm2;
+        result->rtt_ps = ranging.rttPs;
+        result->attempts = ranging.attempts;
+        result->successes = ranging.successes;
+        return true;
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to measure distance: ", e.what());
+        return false;
+    }
+}
+
+// Get the roaming features of the current association
+bool wifi_manager_get_roaming_features(WifiManager* manager, WifiRoamingFeatures* features) {
+    if (!manager || !features) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        wificpp::RoamingFeatures cppFeatures;
+        if (!wifiManager->getRoamingFeatures(cppFeatures)) {
+            return false;
+        }
+        features->ap_neighbor_report = cppFeatures.apNeighborReport ? 1 : 0;
+        features->ap_bss_transition = cppFeatures.apBssTransition ? 1 : 0;
+        features->ap_fast_transition = cppFeatures.apFastTransition ? 1 : 0;
+        features->local_neighbor_report = cppFeatures.localNeighborReport ? 1 : 0;
+        features->local_bss_transition = cppFeatures.localBssTransition ? 1 : 0;
+        features->local_fast_transition = cppFeatures.localFastTransition ? 1 : 0;
+        return true;
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to get roaming features: ", e.what());
+        return false;
+    }
+}
+
+// Move the association to another access point of the same network
+bool wifi_manager_roam_to(WifiManager* manager, const char* bssid) {
+    if (!manager || !bssid) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        return wifiManager->roamTo(bssid);
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to roam: ", e.what());
+        return false;
+    }
+}
+
+// Set when the station scans for a stronger access point
+bool wifi_manager_set_roaming_policy(WifiManager* manager, const WifiRoamingPolicy* policy) {
+    if (!manager || !policy) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        wificpp::RoamingPolicy cppPolicy;
+        cppPolicy.rssiThreshold = policy->rssi_threshold;
+        cppPolicy.scanIntervalSeconds = policy->scan_interval_seconds;
+        return wifiManager->setRoamingPolicy(cppPolicy);
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to set roaming policy: ", e.what());
+        return false;
+    }
+}
+
+// Get the TWT status of the current association
+bool wifi_manager_get_twt_status(WifiManager* manager, WifiTwtStatus* status) {
+    if (!manager || !status) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        wificpp::TwtStatus cppStatus;
+        if (!wifiManager->getTwtStatus(cppStatus)) {
+            return false;
+        }
+        status->ap_responder = cppStatus.apResponder ? 1 : 0;
+        status->local_requester = cppStatus.localRequester ? 1 : 0;
+        status->agreement_requested = cppStatus.agreementRequested ? 1 : 0;
+        status->config.wake_interval_us = cppStatus.config.wakeIntervalUs;
+        status->config.wake_duration_us = cppStatus.config.wakeDurationUs;
+        status->config.trigger_enabled = cppStatus.config.triggerEnabled ? 1 : 0;
+        status->config.announced = cppStatus.config.announced ? 1 : 0;
+        return true;
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to get TWT status: ", e.what());
+        return false;
+    }
+}
+
+// Request a TWT agreement
+bool wifi_manager_twt_setup(WifiManager* manager, const WifiTwtConfig* config) {
+    if (!manager || !config) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        wificpp::TwtConfig cppConfig;
+        cppConfig.wakeIntervalUs = config->wake_interval_us;
+        cppConfig.wakeDurationUs = config->wake_duration_us;
+        cppConfig.triggerEnabled = config->trigger_enabled != 0;
+        cppConfig.announced = config->announced != 0;
+        return wifiManager->twtSetup(cppConfig);
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to set up TWT: ", e.what());
+        return false;
+    }
+}
+
+// Tear down the TWT agreement
+bool wifi_manager_twt_teardown(WifiManager* manager) {
+    if (!manager) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        return wifiManager->twtTeardown();
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to tear down TWT: ", e.what());
+        return false;
+    }
+}
+
+// Get the affiliated links of the current association
+WifiMloLink* wifi_manager_get_mlo_links(WifiManager* manager, int* count) {
+    if (!manager || !count) {
+        return nullptr;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        auto links = wifiManager->getMloLinks();
+        
+        *count = static_cast<int>(links.size());
+        if (links.empty()) {
+            return nullptr;
+        }
+        
+        WifiMloLink* result = new WifiMloLink[*count];
+        for (int i = 0; i < *count; i++) {
+            result[i].link_id = links[i].linkId;
+            copy_string(result[i].ap_link_address, sizeof(result[i].ap_link_address), links[i].apLinkAddress);
+            result[i].frequency = links[i].frequency;
+            result[i].signal_strength = links[i].signalStrength;
+        }
+        return result;
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to get MLO links: ", e.what());
+        *count = 0;
+        return nullptr;
+    }
+}
+
+// Free the array returned by wifi_manager_get_mlo_links
+void wifi_free_mlo_links(WifiMloLink* links) {
+    delete[] links;
+}
+
+// Get the regulatory domain
+bool wifi_manager_get_regulatory_domain(WifiManager* manager, char* country, int country_size) {
+    if (!manager || !country || country_size <= 0) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        std::string domain = wifiManager->getRegulatoryDomain();
+        if (domain.empty()) {
+            return false;
+        }
+        copy_string(country, static_cast<size_t>(country_size), domain);
+        return true;
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to get regulatory domain: ", e.what());
+        return false;
+    }
+}
+
+// Check whether the regulatory domain can be changed
+bool wifi_manager_can_set_regulatory_domain(WifiManager* manager) {
+    if (!manager) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        return wifiManager->canSetRegulatoryDomain();
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to check regulatory domain support: ", e.what());
+        return false;
+    }
+}
+
+// Set the regulatory domain
+bool wifi_manager_set_regulatory_domain(WifiManager* manager, const char* country) {
+    if (!manager || !country) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        return wifiManager->setRegulatoryDomain(country);
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to set regulatory domain: ", e.what());
+        return false;
+    }
+}
+
+// Get the channels permitted by the regulatory domain
+WifiChannelInfo* wifi_manager_get_allowed_channels(WifiManager* manager, int* count) {
+    if (!manager || !count) {
+        return nullptr;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        auto channels = wifiManager->getAllowedChannels();
+        
+        *count = static_cast<int>(channels.size());
+        if (channels.empty()) {
+            return nullptr;
+        }
+        
+        WifiChannelInfo* result = new WifiChannelInfo[*count];
+        for (int i = 0; i < *count; i++) {
+            result[i].frequency = channels[i].frequency;
+            result[i].max_power_mbm = channels[i].maxPowerMbm;
+            result[i].dfs = channels[i].dfs ? 1 : 0;
+            result[i].no_ir = channels[i].noIr ? 1 : 0;
+        }
+        return result;
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to get allowed channels: ", e.what());
+        *count = 0;
+        return nullptr;
+    }
+}
+
+// Free the array returned by wifi_manager_get_allowed_channels
+void wifi_free_channel_info(WifiChannelInfo* channels) {
+    delete[] channels;
+}
+
+// Get the station interface name
+bool wifi_manager_get_interface_name(WifiManager* manager, char* name, int name_size) {
+    if (!manager || !name || name_size <= 0) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        std::string iface = wifiManager->getInterfaceName();
+        if (iface.empty()) {
+            return false;
+        }
+        copy_string(name, static_cast<size_t>(name_size), iface);
+        return true;
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to get interface name: ", e.what());
+        return false;
+    }
+}
+
+// Get the Wake-on-WLAN capabilities
+bool wifi_manager_get_wowlan_capabilities(WifiManager* manager, WifiWowlanCapabilities* capabilities) {
+    if (!manager || !capabilities) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        wificpp::WowlanCapabilities cppCapabilities;
+        if (!wifiManager->getWowlanCapabilities(cppCapabilities)) {
+            return false;
+        }
+        capabilities->magic_packet = cppCapabilities.magicPacket ? 1 : 0;
+        capabilities->disconnect = cppCapabilities.disconnect ? 1 : 0;
+        capabilities->max_patterns = cppCapabilities.maxPatterns;
+        capabilities->min_pattern_length = cppCapabilities.minPatternLength;
+        capabilities->max_pattern_length = cppCapabilities.maxPatternLength;
+        capabilities->max_pattern_offset = cppCapabilities.maxPatternOffset;
+        return true;
     } catch (const std::exception& e) {
-        wificpp::Logger::getInstance().error("Failed to create WifiManager: ", e.what());
-        return nullptr;
+        wificpp::Logger::getInstance().error("Failed to get WoWLAN capabilities: ", e.what());
+        return false;
     }
 }
 
-// Delete a WifiManager instance
-void wifi_manager_delete(WifiManager* manager) {
-    if (manager) {
-        delete reinterpret_cast<wificpp::WifiManager*>(manager);
+// Get the configured Wake-on-WLAN triggers
+bool wifi_manager_get_wowlan(WifiManager* manager, WifiWowlanStatus* status) {
+    if (!manager || !status) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        wificpp::WowlanConfig cppConfig;
+        if (!wifiManager->getWowlanConfig(cppConfig)) {
+            return false;
+        }
+        status->magic_packet = cppConfig.magicPacket ? 1 : 0;
+        status->disconnect = cppConfig.disconnect ? 1 : 0;
+        status->pattern_count = static_cast<int32_t>(cppConfig.patterns.size());
+        return true;
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to get WoWLAN configuration: ", e.what());
+        return false;
     }
 }
 
-// Helper function to convert wificpp::NetworkInfo to WifiNetworkInfo
-static WifiNetworkInfo convert_network_info(const wificpp::NetworkInfo& info) {
-    WifiNetworkInfo result;
+// Configure Wake-on-WLAN
+bool wifi_manager_set_wowlan(WifiManager* manager, const WifiWowlanConfig* config) {
+    if (!manager || !config || config->pattern_count < 0 || (config->pattern_count > 0 && !config->patterns)) {
+        return false;
+    }
     
-    // Allocate memory for the C-style strings
-    char* ssid = new char[info.ssid.length() + 1];
-    std::strcpy(ssid, info.ssid.c_str());
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        wificpp::WowlanConfig cppConfig;
+        cppConfig.magicPacket = config->magic_packet != 0;
+        cppConfig.disconnect = config->disconnect != 0;
+        for (int32_t i = 0; i < config->pattern_count; i++) {
+            const WifiWowlanPattern& pattern = config->patterns[i];
+            if (!pattern.pattern || !pattern.mask || pattern.length <= 0) {
+                return false;
+            }
+            wificpp::WowlanPattern cppPattern;
+            cppPattern.pattern.assign(pattern.pattern, pattern.pattern + pattern.length);
+            cppPattern.mask.assign(pattern.mask, pattern.mask + (pattern.length + 7) / 8);
+            cppPattern.offset = pattern.offset;
+            cppConfig.patterns.push_back(std::move(cppPattern));
+        }
+        return wifiManager->setWowlanConfig(cppConfig);
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to configure WoWLAN: ", e.what());
+        return false;
+    }
+}
+
+// Start the network change observer
+bool wifi_manager_start_network_observer(WifiManager* manager, WifiNetworkChangeCallback callback, void* user_data) {
+    if (!manager || !callback) {
+        return false;
+    }
     
-    char* bssid = new char[info.bssid.length() + 1];
-    std::strcpy(bssid, info.bssid.c_str());
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        return wifiManager->startNetworkObserver([callback, user_data]() {
+            callback(user_data);
+        });
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to start network observer: ", e.what());
+        return false;
+    }
+}
 
-    result.ssid = ssid;
-    result.bssid = bssid;
-    result.signal_strength = info.signalStrength;
-    result.security_type = static_cast<int32_t>(info.security);
-    result.channel = info.channel;
-    result.frequency = info.frequency;
+// Stop the network change observer
+bool wifi_manager_stop_network_observer(WifiManager* manager) {
+    if (!manager) {
+        return false;
+    }
     
-    return result;
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        return wifiManager->stopNetworkObserver();
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to stop network observer: ", e.what());
+        return false;
+    }
 }
 
-// Scan for available networks
-WifiNetworkInfo* wifi_manager_scan(WifiManager* manager, int* count) {
+// Check for profile store support
+bool wifi_manager_is_profile_store_supported(WifiManager* manager) {
+    if (!manager) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        return wifiManager->isProfileStoreSupported();
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to check profile store support: ", e.what());
+        return false;
+    }
+}
+
+// Look up a stored profile
+bool wifi_manager_has_profile(WifiManager* manager, const char* name) {
+    if (!manager || !name) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        return wifiManager->hasProfile(name);
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to look up profile: ", e.what());
+        return false;
+    }
+}
+
+// Connect using a stored profile
+bool wifi_manager_connect_profile(WifiManager* manager, const char* name) {
+    if (!manager || !name) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        return wifiManager->connectProfile(name);
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to connect with profile: ", e.what());
+        return false;
+    }
+}
+
+// Register the connection event callback
+bool wifi_manager_register_callback(WifiManager* manager, WifiEventCallback callback, void* user_data, int32_t signal_threshold) {
+    if (!manager || !callback) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        return wifiManager->registerEventCallback([callback, user_data](const wificpp::WifiEventInfo& info) {
+            WifiEventData event = {};
+            event.type = static_cast<int32_t>(info.type);
+            copy_string(event.ssid, sizeof(event.ssid), info.ssid);
+            copy_string(event.bssid, sizeof(event.bssid), info.bssid);
+            event.signal_strength = info.signalStrength;
+            event.signal_low = info.signalLow ? 1 : 0;
+            copy_string(event.client_address, sizeof(event.client_address), info.clientAddress);
+            callback(&event, user_data);
+        }, signal_threshold);
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to register event callback: ", e.what());
+        return false;
+    }
+}
+
+// Remove the connection event callback
+bool wifi_manager_unregister_callback(WifiManager* manager) {
+    if (!manager) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        return wifiManager->unregisterEventCallback();
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to unregister event callback: ", e.what());
+        return false;
+    }
+}
+
+// List the wireless adapters
+WifiInterfaceInfo* wifi_manager_list_interfaces(WifiManager* manager, int* count) {
     if (!manager || !count) {
         return nullptr;
     }
     
     try {
         auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
-        auto networks = wifiManager->scan();
+        auto interfaces = wifiManager->listInterfaces();
         
-        *count = static_cast<int>(networks.size());
-        if (networks.empty()) {
+        *count = static_cast<int>(interfaces.size());
+        if (interfaces.empty()) {
             return nullptr;
         }
         
-        // Allocate the array of network infos
-        WifiNetworkInfo* result = new WifiNetworkInfo[*count];
+        WifiInterfaceInfo* result = new WifiInterfaceInfo[*count];
         for (int i = 0; i < *count; i++) {
-            result[i] = convert_network_info(networks[i]);
+            const auto& info = interfaces[i];
+            copy_string(result[i].name, sizeof(result[i].name), info.name);
+            copy_string(result[i].mac_address, sizeof(result[i].mac_address), info.macAddress);
+            copy_string(result[i].driver, sizeof(result[i].driver), info.driver);
+            result[i].selected = info.selected ? 1 : 0;
+            result[i].supports_ap = info.supportsAp ? 1 : 0;
+            result[i].supports_monitor = info.supportsMonitor ? 1 : 0;
+            result[i].supports_mesh = info.supportsMesh ? 1 : 0;
+            result[i].supports_p2p = info.supportsP2p ? 1 : 0;
+            result[i].band_2ghz = info.band2Ghz ? 1 : 0;
+            result[i].band_5ghz = info.band5Ghz ? 1 : 0;
+            result[i].band_6ghz = info.band6Ghz ? 1 : 0;
+        }
+        return result;
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to list interfaces: ", e.what());
+        *count = 0;
+        return nullptr;
+    }
+}
+
+// Free the array returned by wifi_manager_list_interfaces
+void wifi_free_interface_list(WifiInterfaceInfo* interfaces) {
+    delete[] interfaces;
+}
+
+// List the saved WiFi profiles
+WifiSavedProfile* wifi_manager_list_profiles(WifiManager* manager, int* count) {
+    if (!manager || !count) {
+        return nullptr;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        auto profiles = wifiManager->listProfiles();
+        
+        *count = static_cast<int>(profiles.size());
+        if (profiles.empty()) {
+            return nullptr;
         }
         
+        WifiSavedProfile* result = new WifiSavedProfile[*count];
+        for (int i = 0; i < *count; i++) {
+            const auto& profile = profiles[i];
+            copy_string(result[i].name, sizeof(result[i].name), profile.name);
+            copy_string(result[i].ssid, sizeof(result[i].ssid), profile.ssid);
+            result[i].security_type = static_cast<int32_t>(profile.security);
+            result[i].auto_connect = profile.autoConnect ? 1 : 0;
+        }
         return result;
     } catch (const std::exception& e) {
-        wificpp::Logger::getInstance().error("Failed to scan for networks: ", e.what());
+        wificpp::Logger::getInstance().error("Failed to list profiles: ", e.what());
         *count = 0;
         return nullptr;
     }
 }
 
-// Connect to a network
-bool wifi_manager_connect(WifiManager* manager, const char* ssid, const char* password) {
+// Free the array returned by wifi_manager_list_profiles
+void wifi_free_profile_list(WifiSavedProfile* profiles) {
+    delete[] profiles;
+}
+
+// Save a network profile
+bool wifi_manager_save_profile(WifiManager* manager, const char* ssid, const char* password, bool auto_connect) {
     if (!manager || !ssid) {
         return false;
     }
     
     try {
         auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
-        return wifiManager->connect(ssid, password ? password : "");
+        return wifiManager->saveProfile(ssid, password ? password : "", auto_connect);
     } catch (const std::exception& e) {
-        wificpp::Logger::getInstance().error("Failed to connect to network: ", e.what());
+        wificpp::Logger::getInstance().error("Failed to save profile: ", e.what());
+        return false;
+    }
+}
+
+// Remove a saved profile
+bool wifi_manager_forget_profile(WifiManager* manager, const char* name) {
+    if (!manager || !name) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        return wifiManager->forgetProfile(name);
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to remove profile: ", e.what());
+        return false;
+    }
+}
+
+// Change automatic connection of a saved profile
+bool wifi_manager_set_profile_auto_connect(WifiManager* manager, const char* name, bool enabled) {
+    if (!manager || !name) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        return wifiManager->setProfileAutoConnect(name, enabled);
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to change profile: ", e.what());
+        return false;
+    }
+}
+
+// Start polling the current association
+bool wifi_manager_start_signal_monitor(WifiManager* manager, int32_t interval_ms, WifiSignalCallback callback, void* user_data) {
+    if (!manager || !callback || interval_ms <= 0) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        return wifiManager->startSignalMonitor(interval_ms, [callback, user_data](const wificpp::ConnectionInfo& connection) {
+            WifiConnectionInfo info;
+            convert_connection_info(connection, &info);
+            callback(&info, user_data);
+        });
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to start signal monitor: ", e.what());
+        return false;
+    }
+}
+
+// Stop polling the current association
+bool wifi_manager_stop_signal_monitor(WifiManager* manager) {
+    if (!manager) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        return wifiManager->stopSignalMonitor();
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to stop signal monitor: ", e.what());
+        return false;
+    }
+}
+
+// Get the IP configuration of the wireless interface
+bool wifi_manager_get_ip_info(WifiManager* manager, WifiIpInfo* info) {
+    if (!manager || !info) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        wificpp::IpInfo ip;
+        if (!wifiManager->getIpInfo(ip)) {
+            return false;
+        }
+        
+        std::memset(info, 0, sizeof(*info));
+        size_t count = std::min<size_t>(ip.ipv4Addresses.size(), 4);
+        for (size_t i = 0; i < count; i++) {
+            copy_string(info->ipv4_addresses[i], sizeof(info->ipv4_addresses[i]), ip.ipv4Addresses[i]);
+        }
+        info->ipv4_address_count = static_cast<int32_t>(count);
+        count = std::min<size_t>(ip.ipv6Addresses.size(), 8);
+        for (size_t i = 0; i < count; i++) {
+            copy_string(info->ipv6_addresses[i], sizeof(info->ipv6_addresses[i]), ip.ipv6Addresses[i]);
+        }
+        info->ipv6_address_count = static_cast<int32_t>(count);
+        copy_string(info->gateway, sizeof(info->gateway), ip.gateway);
+        copy_string(info->ipv6_gateway, sizeof(info->ipv6_gateway), ip.ipv6Gateway);
+        count = std::min<size_t>(ip.dnsServers.size(), 4);
+        for (size_t i = 0; i < count; i++) {
+            copy_string(info->dns_servers[i], sizeof(info->dns_servers[i]), ip.dnsServers[i]);
+        }
+        info->dns_server_count = static_cast<int32_t>(count);
+        info->dhcp = ip.dhcp ? 1 : 0;
+        copy_string(info->dhcp_server, sizeof(info->dhcp_server), ip.dhcpServer);
+        info->lease_obtained = ip.leaseObtained;
+        info->lease_expires = ip.leaseExpires;
+        return true;
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to get IP configuration: ", e.what());
         return false;
     }
 }
@@ -130,6 +2002,41 @@ WifiConnectionStatus wifi_manager_get_status(WifiManager* manager) {
     }
 }
 
+// Get why the last connect, disconnect or hotspot call failed
+WifiErrorCode wifi_manager_get_last_error(WifiManager* manager) {
+    if (!manager) {
+        return WIFI_ERROR_OPERATION_FAILED;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        switch (wifiManager->getLastError()) {
+            case wificpp::ErrorCode::NONE:
+                return WIFI_ERROR_NONE;
+            case wificpp::ErrorCode::UNSUPPORTED:
+                return WIFI_ERROR_UNSUPPORTED;
+            case wificpp::ErrorCode::INVALID_CREDENTIALS:
+                return WIFI_ERROR_INVALID_CREDENTIALS;
+            case wificpp::ErrorCode::ADAPTER_NOT_FOUND:
+                return WIFI_ERROR_ADAPTER_NOT_FOUND;
+            case wificpp::ErrorCode::PERMISSION_DENIED:
+                return WIFI_ERROR_PERMISSION_DENIED;
+            case wificpp::ErrorCode::TIMEOUT:
+                return WIFI_ERROR_TIMEOUT;
+            case wificpp::ErrorCode::DRIVER_ERROR:
+                return WIFI_ERROR_DRIVER_ERROR;
+            case wificpp::ErrorCode::CANCELLED:
+                return WIFI_ERROR_CANCELLED;
+            case wificpp::ErrorCode::OPERATION_FAILED:
+            default:
+                return WIFI_ERROR_OPERATION_FAILED;
+        }
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to get last error: ", e.what());
+        return WIFI_ERROR_OPERATION_FAILED;
+    }
+}
+
 // Free the network info array returned by wifi_manager_scan
 void wifi_free_network_info(WifiNetworkInfo* networks, int count) {
     if (!networks || count <= 0) {
@@ -139,6 +2046,7 @@ void wifi_free_network_info(WifiNetworkInfo* networks, int count) {
     for (int i = 0; i < count; i++) {
         delete[] networks[i].ssid;
         delete[] networks[i].bssid;
+        delete[] networks[i].ie_data;
     }
     
     delete[] networks;
@@ -159,6 +2067,65 @@ bool wifi_manager_create_hotspot(WifiManager* manager, const char* ssid) {
     }
 }
 
+// Create a hotspot from C settings
+static bool create_hotspot(WifiManager* manager, const WifiHotspotConfig* config, const char* share_from,
+                           bool redirect_dns, int32_t timeout_ms) {
+    if (!manager || !config || !config->ssid) {
+        return false;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        wificpp::HotspotConfig hotspotConfig;
+        hotspotConfig.ssid = config->ssid;
+        hotspotConfig.password = config->password ? config->password : "";
+        hotspotConfig.channel = config->channel;
+        if (config->gateway && config->dhcp_range_start && config->dhcp_range_end) {
+            hotspotConfig.dhcp.gateway = config->gateway;
+            hotspotConfig.dhcp.rangeStart = config->dhcp_range_start;
+            hotspotConfig.dhcp.rangeEnd = config->dhcp_range_end;
+            hotspotConfig.dhcp.prefixLength = config->prefix_length;
+            std::stringstream servers(config->dns_servers ? config->dns_servers : "");
+            std::string server;
+            while (std::getline(servers, server, ',')) {
+                if (!server.empty()) {
+                    hotspotConfig.dhcp.dnsServers.push_back(server);
+                }
+            }
+        }
+        hotspotConfig.shareFrom = share_from ? share_from : "";
+        hotspotConfig.redirectDns = redirect_dns;
+        hotspotConfig.timeoutMs = timeout_ms;
+        return wifiManager->createHotspotWithConfig(hotspotConfig);
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to create hotspot: ", e.what());
+        return false;
+    }
+}
+
+// Create a hotspot with explicit settings
+bool wifi_manager_create_hotspot_with_config(WifiManager* manager, const WifiHotspotConfig* config) {
+    return wifi_manager_create_hotspot_with_sharing(manager, config, nullptr);
+}
+
+// Create a WiFi hotspot that shares the connection of another interface
+bool wifi_manager_create_hotspot_with_sharing(WifiManager* manager, const WifiHotspotConfig* config,
+                                              const char* share_from) {
+    return create_hotspot(manager, config, share_from, false, 0);
+}
+
+// Create a WiFi hotspot whose DNS sends every client to a captive portal
+bool wifi_manager_create_captive_hotspot(WifiManager* manager, const WifiHotspotConfig* config,
+                                         const char* share_from) {
+    return create_hotspot(manager, config, share_from, true, 0);
+}
+
+// Create a WiFi hotspot with its own limit on the wait for the access point to start
+bool wifi_manager_create_hotspot_with_timeout(WifiManager* manager, const WifiHotspotConfig* config,
+                                              const char* share_from, bool captive_portal, int32_t timeout_ms) {
+    return create_hotspot(manager, config, share_from, captive_portal, timeout_ms);
+}
+
 // Stop the active hotspot
 bool wifi_manager_stop_hotspot(WifiManager* manager) {
     if (!manager) {
@@ -204,4 +2171,40 @@ bool wifi_manager_is_hotspot_supported(WifiManager* manager) {
     }
 }
 
+// List the devices associated with the active hotspot
+WifiHotspotClient* wifi_manager_hotspot_clients(WifiManager* manager, int* count) {
+    if (!manager || !count) {
+        return nullptr;
+    }
+    
+    try {
+        auto* wifiManager = reinterpret_cast<wificpp::WifiManager*>(manager);
+        auto clients = wifiManager->hotspotClients();
+        
+        *count = static_cast<int>(clients.size());
+        if (clients.empty()) {
+            return nullptr;
+        }
+        
+        WifiHotspotClient* result = new WifiHotspotClient[*count];
+        for (int i = 0; i < *count; i++) {
+            copy_string(result[i].mac_address, sizeof(result[i].mac_address), clients[i].macAddress);
+            copy_string(result[i].ip_address, sizeof(result[i].ip_address), clients[i].ipAddress);
+            copy_string(result[i].hostname, sizeof(result[i].hostname), clients[i].hostname);
+            result[i].signal_strength = clients[i].signalStrength;
+            result[i].connected_seconds = clients[i].connectedSeconds;
+        }
+        return result;
+    } catch (const std::exception& e) {
+        wificpp::Logger::getInstance().error("Failed to list hotspot clients: ", e.what());
+        *count = 0;
+        return nullptr;
+    }
+}
+
+// Free the array returned by wifi_manager_hotspot_clients
+void wifi_free_hotspot_clients(WifiHotspotClient* clients) {
+    delete[] clients;
+}
+
 }
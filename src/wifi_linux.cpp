@@ -7,6 +7,7 @@
 #include <fstream>
 #include <sstream>
 #include <cstring>
+#include <strings.h>
 #include <cstdlib>
 #include <unistd.h>
 #include <sys/socket.h>
@@ -17,12 +18,38 @@
 #include <netlink/genl/genl.h>
 #include <netlink/genl/ctrl.h>
 #include <linux/nl80211.h>
+#include <linux/rtnetlink.h>
+#include <linux/rfkill.h>
+#include <linux/capability.h>
 #include <dirent.h>
+#include <ifaddrs.h>
+#include <fcntl.h>
+#include <sys/stat.h>
 #include <signal.h>
 #include <wait.h>
+#include <poll.h>
+#include <sys/un.h>
+#include <arpa/inet.h>
+#include <linux/if_ether.h>
+#include <linux/if_packet.h>
+#include <climits>
+#include <algorithm>
+#include <cctype>
+#include <atomic>
+#include <chrono>
+#include <map>
+#include <thread>
 
 namespace wificpp {
 
+// How a scan triggered through nl80211 ended
+enum class ScanOutcome {
+    COMPLETED,
+    ABORTED,
+    TIMED_OUT,
+    CANCELLED
+};
+
 class LinuxWifiImpl : public WifiImpl {
 public:
     LinuxWifiImpl() {
@@ -55,6 +82,18 @@ public:
     }
 
     ~LinuxWifiImpl() {
+        // Never leave an ephemeral network joined beyond the lifetime of the manager
+        if (ephemeral_connection) {
+            disconnect();
+        }
+        stopCapture();
+        stopNetworkObserver();
+        unregisterEventCallback();
+        while (!nan_sessions.empty()) {
+            nanCancel(nan_sessions.begin()->first);
+        }
+        closeSupplicantMonitor();
+        meshLeave();
         if (socket) {
             nl_socket_free(socket);
             socket = nullptr;
@@ -64,31 +103,132 @@ public:
     std::vector<NetworkInfo> scan() override {
         std::vector<NetworkInfo> networks;
         Logger::getInstance().info("Scanning for networks on Linux interface " + interface_name);
+        runScan(ScanOptions(), networks, false);
+        return networks;
+    }
+
+    std::vector<NetworkInfo> scanWithOptions(const ScanOptions& options) override {
+        Logger::getInstance().info("Running a directed scan on Linux interface " + interface_name);
+        std::vector<NetworkInfo> networks;
+        runScan(options, networks, false);
+        return networks;
+    }
+
+    bool scanFresh(std::vector<NetworkInfo>& networks) override {
+        Logger::getInstance().info("Running a fresh scan on Linux interface " + interface_name);
+        return runScan(ScanOptions(), networks, true);
+    }
+
+    // Trigger a scan and read its results. With requireFresh the kernel must report that the scan
+    // completed, and entries it cached from earlier scans are left out.
+    bool runScan(const ScanOptions& options, std::vector<NetworkInfo>& networks, bool requireFresh) {
+        // Listen before triggering, a scan of a few channels can finish within milliseconds
+        struct nl_sock* scan_events = nl_socket_alloc();
+        int scan_group = -1;
+        if (scan_events && genl_connect(scan_events) >= 0) {
+            nl_socket_disable_seq_check(scan_events);
+            scan_group = genl_ctrl_resolve_grp(scan_events, "nl80211", "scan");
+        }
+        if (scan_group < 0 || nl_socket_add_membership(scan_events, scan_group) < 0) {
+            if (scan_events) {
+                nl_socket_free(scan_events);
+            }
+            scan_events = nullptr;
+        }
+        
+        if (requireFresh && !scan_events) {
+            Logger::getInstance().error("Cannot subscribe to nl80211 scan events");
+            return fail(ErrorCode::OPERATION_FAILED);
+        }
         
-        // Trigger scan
         struct nl_msg* msg = nlmsg_alloc();
         if (!msg) {
             Logger::getInstance().error("Failed to allocate netlink message");
-            return networks;
+            if (scan_events) {
+                nl_socket_free(scan_events);
+            }
+            return fail(ErrorCode::OPERATION_FAILED);
         }
-
-        // Setup scan request
         genlmsg_put(msg, 0, 0, nl80211_id, 0, 0, NL80211_CMD_TRIGGER_SCAN, 0);
         nla_put_u32(msg, NL80211_ATTR_IFINDEX, interface_index);
+        if (!options.passive) {
+            // Without SSIDs the kernel scans passively, an empty SSID probes for any network
+            struct nlattr* ssids = nla_nest_start(msg, NL80211_ATTR_SCAN_SSIDS);
+            if (options.ssids.empty()) {
+                nla_put(msg, 1, 0, "");
+            }
+            for (size_t i = 0; i < options.ssids.size(); i++) {
+                nla_put(msg, static_cast<int>(i + 1), static_cast<int>(options.ssids[i].size()), options.ssids[i].data());
+            }
+            nla_nest_end(msg, ssids);
+        }
+        if (!options.frequencies.empty()) {
+            struct nlattr* frequencies = nla_nest_start(msg, NL80211_ATTR_SCAN_FREQUENCIES);
+            for (size_t i = 0; i < options.frequencies.size(); i++) {
+                nla_put_u32(msg, static_cast<int>(i + 1), options.frequencies[i]);
+            }
+            nla_nest_end(msg, frequencies);
+        }
+        if (!sendAndWaitForAck(msg)) {
+            Logger::getInstance().error("The driver rejected the scan request on " + interface_name);
+            if (scan_events) {
+                nl_socket_free(scan_events);
+            }
+            return fail(ErrorCode::DRIVER_ERROR);
+        }
+        auto started = std::chrono::steady_clock::now();
+        
+        ScanOutcome outcome = ScanOutcome::TIMED_OUT;
+        if (scan_events) {
+            outcome = waitForScanDone(scan_events, scanTimeoutMs());
+            nl_socket_free(scan_events);
+        } else {
+            // Without scan events, about as long as a full scan takes
+            if (!sleepUnlessCancelled(std::min(scanTimeoutMs(), 5000))) {
+                outcome = ScanOutcome::CANCELLED;
+            }
+        }
+        if (outcome == ScanOutcome::TIMED_OUT || outcome == ScanOutcome::CANCELLED) {
+            // Free the radio for the next operation, the results so far stay in the kernel's cache
+            abortScan();
+        }
+        if (outcome == ScanOutcome::CANCELLED) {
+            Logger::getInstance().info("The scan was cancelled on " + interface_name);
+            return fail(ErrorCode::CANCELLED);
+        }
+        if (requireFresh && outcome != ScanOutcome::COMPLETED) {
+            Logger::getInstance().error(outcome == ScanOutcome::ABORTED ? "The scan was aborted on " + interface_name
+                                                                         : "The scan did not complete on " + interface_name);
+            return fail(outcome == ScanOutcome::ABORTED ? ErrorCode::DRIVER_ERROR : ErrorCode::TIMEOUT);
+        }
+        
+        // The dump also holds older entries cached by the kernel
+        int maxAgeMs = -1;
+        if (requireFresh) {
+            maxAgeMs = static_cast<int>(std::chrono::duration_cast<std::chrono::milliseconds>(
+                std::chrono::steady_clock::now() - started).count());
+        }
+        networks = scanResults(maxAgeMs);
+        dropUnrequested(networks, options);
+        return true;
+    }
 
-        // Send scan request
-        int ret = nl_send_auto(socket, msg);
-        nlmsg_free(msg);
-        if (ret < 0) {
-            Logger::getInstance().error("Failed to send scan request");
-            return networks;
+    void abortScan() {
+        struct nl_msg* msg = nlmsg_alloc();
+        if (!msg) {
+            return;
         }
+        genlmsg_put(msg, 0, 0, nl80211_id, 0, 0, NL80211_CMD_ABORT_SCAN, 0);
+        nla_put_u32(msg, NL80211_ATTR_IFINDEX, interface_index);
+        sendAndWaitForAck(msg);
+    }
 
-        // Wait for scan to complete (5 seconds max)
-        sleep(5);
+    // Entries of the kernel's scan cache, only those seen within maxAgeMs unless it is negative
+    std::vector<NetworkInfo> scanResults(int maxAgeMs = -1) {
+        std::vector<NetworkInfo> networks;
         
         // Get scan results
-        msg = nlmsg_alloc();
+        struct nl_msg* msg = nlmsg_alloc();
         if (!msg) {
             Logger::getInstance().error("Failed to allocate netlink message for scan results");
             return networks;
@@ -100,8 +240,10 @@ public:
         // Define callback data
         struct callback_data {
             std::vector<NetworkInfo>* networks;
+            int maxAgeMs;
         } cb_data;
         cb_data.networks = &networks;
+        cb_data.maxAgeMs = maxAgeMs;
 
         // Define callback function to process scan results
         auto callback = [](struct nl_msg* msg, void* arg) -> int {
@@ -122,6 +264,10 @@ public:
             if (!bss[NL80211_BSS_BSSID] || !bss[NL80211_BSS_INFORMATION_ELEMENTS]) {
                 return NL_SKIP;
             }
+            if (data->maxAgeMs >= 0 && bss[NL80211_BSS_SEEN_MS_AGO] &&
+                nla_get_u32(bss[NL80211_BSS_SEEN_MS_AGO]) > static_cast<uint32_t>(data->maxAgeMs)) {
+                return NL_SKIP;
+            }
             
             NetworkInfo network;
             
@@ -135,11 +281,40 @@ public:
             // Extract information elements to get SSID and other data
             uint8_t* ie = static_cast<uint8_t*>(nla_data(bss[NL80211_BSS_INFORMATION_ELEMENTS]));
             int ie_len = nla_len(bss[NL80211_BSS_INFORMATION_ELEMENTS]);
+            network.informationElements.assign(ie, ie + ie_len);
             
             for (int i = 0; i < ie_len; i += ie[i + 1] + 2) {
                 if (ie[i] == 0) { // SSID element
                     network.ssid = std::string(reinterpret_cast<char*>(&ie[i + 2]), ie[i + 1]);
                 }
+                if (ie[i] == 48) { // RSN element, RSN capabilities follow the cipher and AKM suite lists
+                    const uint8_t* rsn = &ie[i + 2];
+                    int len = ie[i + 1];
+                    int pos = 2 + 4; // version, group cipher
+                    if (pos + 2 <= len) {
+                        pos += 2 + 4 * (rsn[pos] | (rsn[pos + 1] << 8)); // pairwise ciphers
+                    }
+                    if (pos + 2 <= len) {
+                        pos += 2 + 4 * (rsn[pos] | (rsn[pos + 1] << 8)); // AKM suites
+                    }
+                    if (pos + 2 <= len) {
+                        uint16_t caps = rsn[pos] | (rsn[pos + 1] << 8);
+                        if (caps & (1 << 6)) network.pmf = PmfMode::REQUIRED;
+                        else if (caps & (1 << 7)) network.pmf = PmfMode::CAPABLE;
+                        else network.pmf = PmfMode::DISABLED;
+                    } else {
+                        network.pmf = PmfMode::DISABLED;
+                    }
+                }
+                if (ie[i] == 221 && ie[i + 1] >= 4 &&
+                    ie[i + 2] == 0x00 && ie[i + 3] == 0x50 &&
+                    ie[i + 4] == 0xf2 && ie[i + 5] == 0x04) {
+                    network.wpsEnabled = true; // WPS element
+                }
+                if (ie[i] == 255 && ie[i + 1] >= 1) { // Element ID extension
+                    if (ie[i + 2] == 108) network.ehtSupported = true; // EHT Capabilities
+                    if (ie[i + 2] == 107) network.mloCapable = true;   // Basic Multi-Link
+                }
             }
             
             // Extract signal strength
@@ -179,11 +354,11 @@ public:
                 network.security = SecurityType::UNKNOWN;
             }
             
-            // Add network if it's not already in the list
+            // One entry per access point, hidden networks have no name to list them under
             if (!network.ssid.empty()) {
                 bool exists = false;
                 for (const auto& existing : *(data->networks)) {
-                    if (existing.ssid == network.ssid) {
+                    if (existing.bssid == network.bssid) {
                         exists = true;
                         break;
                     }
@@ -202,7 +377,7 @@ public:
         nl_cb_set(cb, NL_CB_VALID, NL_CB_CUSTOM, callback, &cb_data);
         
         // Send message and receive response
-        ret = nl_send_auto(socket, msg);
+        int ret = nl_send_auto(socket, msg);
         nlmsg_free(msg);
         if (ret < 0) {
             Logger::getInstance().error("Failed to send scan results request");
@@ -219,7 +394,31 @@ public:
     }
 
     bool connect(const std::string& ssid, const std::string& password) override {
+        return connectNetwork(ssid, password, ConnectOptions{});
+    }
+
+    bool connectWithOptions(const std::string& ssid, const std::string& password, const ConnectOptions& options) override {
+        if (!isWellFormed(options.staticIp)) {
+            return fail(ErrorCode::OPERATION_FAILED);
+        }
+        if (options.ephemeral) {
+            // wpa_supplicant only holds the network in memory: the configuration file is
+            // deleted once the connection is up and the supplicant stops on disconnect
+            ephemeral_connection = true;
+        }
+        return connectNetwork(ssid, password, options);
+    }
+
+    bool connectNetwork(const std::string& ssid, const std::string& password, const ConnectOptions& options) {
         Logger::getInstance().info("Connecting to network: " + ssid);
+        switch (activeBackend()) {
+            case LinuxBackend::NETWORK_MANAGER:
+                return connectWithNetworkManager(ssid, password, options);
+            case LinuxBackend::NL80211:
+                return connectWithNl80211(ssid, password, options);
+            default:
+                break;
+        }
         
         // Generate wpa_supplicant configuration
         std::string config_path = "/tmp/wificpp_" + ssid + ".conf";
@@ -239,249 +438,3566 @@ public:
             config_file << "    psk=\"" << password << "\"\n";
             config_file << "    key_mgmt=WPA-PSK\n";
         }
+        if (!options.frequencies.empty()) {
+            // Also keeps wpa_supplicant's own roaming on the selected frequencies
+            config_file << "    freq_list=";
+            for (size_t i = 0; i < options.frequencies.size(); i++) {
+                config_file << (i ? " " : "") << options.frequencies[i];
+            }
+            config_file << "\n";
+        }
+        if (options.hidden) {
+            // Probe for the SSID, hidden networks never show up in passive scans
+            config_file << "    scan_ssid=1\n";
+        }
+        if (!options.bssid.empty()) {
+            config_file << "    bssid=" << options.bssid << "\n";
+        }
         
         config_file << "}\n";
         config_file.close();
         
-        // Stop existing wpa_supplicant using native process termination
-        terminateProcess("wpa_supplicant");
-        
-        // Start wpa_supplicant with our configuration using fork/exec
-        pid_t pid = fork();
-        if (pid == 0) {
-            // Child process
-            execl("/sbin/wpa_supplicant", "wpa_supplicant", "-B", "-i", 
-                  interface_name.c_str(), "-c", config_path.c_str(), NULL);
-            exit(1); // Exit if exec fails
-        } else if (pid < 0) {
-            Logger::getInstance().error("Failed to fork process for wpa_supplicant");
-            std::remove(config_path.c_str());
+        return startSupplicant(config_path,
+                               options.timeoutSeconds > 0 ? options.timeoutSeconds : association_timeout_seconds,
+                               options.staticIp);
+    }
+
+    bool setLinuxBackend(LinuxBackend backend) override {
+        if (backend == LinuxBackend::NETWORK_MANAGER && !managedByNetworkManager()) {
+            Logger::getInstance().warning("NetworkManager does not manage " + interface_name);
+            return fail(ErrorCode::UNSUPPORTED);
+        }
+        linux_backend = backend;
+        return true;
+    }
+
+    LinuxBackend getLinuxBackend() const override {
+        return activeBackend();
+    }
+
+    bool isProfileStoreSupported() const override {
+        std::string output;
+        return nmcli({"-t", "-f", "RUNNING", "general"}, &output) && output.find("running") == 0;
+    }
+
+    bool hasProfile(const std::string& name) const override {
+        auto profiles = wifiProfiles();
+        return std::find(profiles.begin(), profiles.end(), name) != profiles.end();
+    }
+
+    bool connectProfile(const std::string& name) override {
+        Logger::getInstance().info("Connecting with NetworkManager profile: " + name);
+        if (!nmcli({"--wait", "30", "connection", "up", "id", name, "ifname", interface_name})) {
+            Logger::getInstance().error("NetworkManager could not activate profile " + name);
             return false;
         }
-        
-        // Wait for connection to initialize
-        sleep(2);
-        
-        // Start DHCP client using native implementation
-        bool dhcp_success = configureDhcpClient(interface_name);
-        if (!dhcp_success) {
-            Logger::getInstance().warning("DHCP configuration failed, but connection might still be established");
+        return true;
+    }
+
+    std::vector<SavedProfile> listProfiles() const override {
+        std::vector<SavedProfile> profiles;
+        for (const auto& fields : wifiConnections("NAME,TYPE,AUTOCONNECT")) {
+            SavedProfile profile;
+            profile.name = fields[0];
+            profile.autoConnect = fields.size() > 2 && fields[2] == "yes";
+            
+            // Getter output has one unescaped value per line
+            std::string output;
+            if (nmcli({"-g", "802-11-wireless.ssid,802-11-wireless-security.key-mgmt",
+                       "connection", "show", "id", profile.name}, &output)) {
+                std::istringstream values(output);
+                std::string keyMgmt;
+                std::getline(values, profile.ssid);
+                std::getline(values, keyMgmt);
+                if (keyMgmt.empty()) {
+                    profile.security = SecurityType::NONE;
+                } else if (keyMgmt == "none" || keyMgmt == "ieee8021x") {
+                    profile.security = SecurityType::WEP;
+                } else if (keyMgmt == "sae" || keyMgmt == "wpa-eap-suite-b-192") {
+                    profile.security = SecurityType::WPA3;
+                } else if (keyMgmt == "wpa-psk" || keyMgmt == "wpa-eap") {
+                    profile.security = SecurityType::WPA2;
+                }
+            }
+            profiles.push_back(profile);
         }
-        
-        // Clean up temporary file
-        std::remove(config_path.c_str());
-        
-        // Verify connection by checking for IP address
-        return hasIpAddress(interface_name);
+        return profiles;
     }
 
-    bool disconnect() override {
-        Logger::getInstance().info("Disconnecting from network on " + interface_name);
-        
-        // Stop wpa_supplicant using native method
-        terminateProcess("wpa_supplicant");
-        
-        // Release DHCP lease
-        releaseDhcpLease(interface_name);
-        
-        // Set interface down/up using direct ioctl calls
-        setInterfaceState(interface_name, false);
-        sleep(1);
-        setInterfaceState(interface_name, true);
-        
+    bool saveProfile(const std::string& ssid, const std::string& password, bool autoConnect) override {
+        if (hasProfile(ssid) && !forgetProfile(ssid)) {
+            return false;
+        }
+        std::vector<std::string> args = {"connection", "add", "type", "wifi", "con-name", ssid,
+                                         "ifname", interface_name, "ssid", ssid,
+                                         "connection.autoconnect", autoConnect ? "yes" : "no"};
+        if (!password.empty()) {
+            args.insert(args.end(), {"wifi-sec.key-mgmt", "wpa-psk", "wifi-sec.psk", password});
+        }
+        if (!nmcli(args)) {
+            Logger::getInstance().error("NetworkManager could not save a profile for " + ssid);
+            return false;
+        }
         return true;
     }
 
-    ConnectionStatus getStatus() const override {
-        // Check if interface is up using ioctl
-        if (!isInterfaceUp(interface_name)) {
-            return ConnectionStatus::DISCONNECTED;
-        }
-        
-        // Check if we have an IP address
-        if (!hasIpAddress(interface_name)) {
-            return ConnectionStatus::CONNECTING;
+    bool forgetProfile(const std::string& name) override {
+        if (!nmcli({"connection", "delete", "id", name})) {
+            Logger::getInstance().error("NetworkManager could not remove profile " + name);
+            return false;
         }
-        
-        // Check if wpa_supplicant is running
-        if (!isProcessRunning("wpa_supplicant")) {
-            return ConnectionStatus::CONNECTION_ERROR;
+        return true;
+    }
+
+    bool setProfileAutoConnect(const std::string& name, bool enabled) override {
+        if (!nmcli({"connection", "modify", "id", name, "connection.autoconnect", enabled ? "yes" : "no"})) {
+            Logger::getInstance().error("NetworkManager could not change profile " + name);
+            return false;
         }
-        
-        return ConnectionStatus::CONNECTED;
+        return true;
     }
 
-    bool createHotspot(const std::string& ssid, const std::string& password = "") override {
-        Logger::getInstance().info("Creating hotspot: " + ssid);
-        
-        // Stop any existing hotspot or connection
-        stopHotspot();
-        disconnect();
+    bool connectEnterprise(const std::string& ssid, const EapConfig& eap) override {
+        Logger::getInstance().info("Connecting to enterprise network: " + ssid);
         
-        // Create hostapd configuration
-        std::string config_path = "/tmp/hostapd_" + ssid + ".conf";
+        // The configuration holds the credentials, so it is only readable by us and removed once
+        // wpa_supplicant has started or failed
+        std::string runtime_dir = privateRuntimeDir();
+        std::string config_path = runtime_dir + "/enterprise.conf";
+        if (runtime_dir.empty() || !createPrivateFile(config_path)) {
+            Logger::getInstance().error("Failed to create temporary configuration file");
+            return false;
+        }
         std::ofstream config_file(config_path);
         if (!config_file) {
-            Logger::getInstance().error("Failed to create hostapd configuration file");
+            Logger::getInstance().error("Failed to create temporary configuration file");
             return false;
         }
         
-        config_file << "interface=" << interface_name << "\n";
-        config_file << "driver=nl80211\n";
-        config_file << "ssid=" << ssid << "\n";
-        config_file << "hw_mode=g\n";
-        config_file << "channel=6\n";
-        config_file << "ieee80211n=1\n";
+        bool sim_based = eap.method == EapMethod::SIM || eap.method == EapMethod::AKA ||
+                         eap.method == EapMethod::AKA_PRIME;
         
-        if (!password.empty()) {
-            config_file << "wpa=2\n";
-            config_file << "wpa_passphrase=" << password << "\n";
-            config_file << "wpa_key_mgmt=WPA-PSK\n";
-            config_file << "wpa_pairwise=TKIP CCMP\n";
-            config_file << "rsn_pairwise=CCMP\n";
+        config_file << "ctrl_interface=/var/run/wpa_supplicant\n";
+        if (sim_based) {
+            config_file << "pcsc=\"\"\n";  // use the first PC/SC reader holding the SIM
         }
+        config_file << "network={\n";
+        config_file << "    ssid=" << configString(ssid) << "\n";
+        config_file << "    key_mgmt=WPA-EAP\n";
         
-        config_file.close();
+        switch (eap.method) {
+            case EapMethod::PEAP:
+                config_file << "    eap=PEAP\n";
+                config_file << "    phase2=\"auth=MSCHAPV2\"\n";
+                break;
+            case EapMethod::TLS:
+                config_file << "    eap=TLS\n";
+                break;
+            case EapMethod::TTLS:
+                config_file << "    eap=TTLS\n";
+                config_file << "    phase2=\"auth="
+                            << (eap.phase2 == Phase2Method::PAP ? "PAP" : "MSCHAPV2") << "\"\n";
+                break;
+            case EapMethod::SIM:
+                config_file << "    eap=SIM\n";
+                break;
+            case EapMethod::AKA:
+                config_file << "    eap=AKA\n";
+                break;
+            case EapMethod::AKA_PRIME:
+                config_file << "    eap=AKA'\n";
+                break;
+        }
         
-        // Configure interface for AP mode
-        std::string cmd = "ip link set " + interface_name + " down";
-        system(cmd.c_str());
-        sleep(1);
+        if (sim_based) {
+            // Identity is derived from the IMSI unless given, the password field carries the SIM PIN
+            if (!eap.identity.empty()) {
+                config_file << "    identity=" << configString(eap.identity) << "\n";
+            }
+            if (!eap.password.empty()) {
+                config_file << "    pin=" << configString(eap.password) << "\n";
+            }
+            config_file << "}\n";
+            config_file.close();
+            return startSupplicant(config_path);
+        }
         
-        // Set static IP for AP interface
-        cmd = "ip addr flush dev " + interface_name;
-        system(cmd.c_str());
-        cmd = "ip addr add 192.168.4.1/24 dev " + interface_name;
-        system(cmd.c_str());
-        cmd = "ip link set " + interface_name + " up";
-        system(cmd.c_str());
+        if (!eap.identity.empty()) {
+            config_file << "    identity=" << configString(eap.identity) << "\n";
+        }
+        if (!eap.anonymousIdentity.empty()) {
+            config_file << "    anonymous_identity=" << configString(eap.anonymousIdentity) << "\n";
+        }
+        if (!eap.password.empty()) {
+            config_file << "    password=" << configString(eap.password) << "\n";
+        }
+        if (!eap.validateServer) {
+            Logger::getInstance().warning("Server certificate validation is DISABLED for ", ssid,
+                                          ", credentials can be captured by a rogue access point");
+        } else {
+            if (!eap.serverCertHash.empty()) {
+                config_file << "    ca_cert=" << configString("hash://server/sha256/" + eap.serverCertHash) << "\n";
+            } else if (!eap.caCert.empty()) {
+                config_file << "    ca_cert=" << configString(eap.caCert) << "\n";
+            } else {
+                // Any CA in the system store could vouch for a rogue server
+                Logger::getInstance().error("A CA certificate or pinned server certificate is required for ", ssid);
+                config_file.close();
+                std::remove(config_path.c_str());
+                return fail(ErrorCode::OPERATION_FAILED);
+            }
+            if (!eap.domainMatch.empty()) {
+                config_file << "    domain_match=" << configString(eap.domainMatch) << "\n";
+            }
+            if (!eap.domainSuffixMatch.empty()) {
+                config_file << "    domain_suffix_match=" << configString(eap.domainSuffixMatch) << "\n";
+            }
+        }
+        if (eap.method == EapMethod::TLS) {
+            std::string key_path = runtime_dir + "/enterprise.p12";
+            if (!writeTlsCredentials(config_file, key_path, eap)) {
+                config_file.close();
+                std::remove(config_path.c_str());
+                return false;
+            }
+            if (!eap.pkcs12.empty()) {
+                eap_key_path = key_path;
+            }
+        }
         
-        // Start hostapd
-        cmd = "hostapd -B " + config_path;
-        int result = system(cmd.c_str());
-        if (result != 0) {
-            Logger::getInstance().error("Failed to start hostapd");
-            std::remove(config_path.c_str());
+        config_file << "}\n";
+        config_file.close();
+        
+        return startSupplicant(config_path);
+    }
+
+    bool connectWpsPbc(int timeout_seconds, WpsProgressCallback progress) override {
+        Logger::getInstance().info("Starting WPS push-button connection on " + interface_name);
+        return runWps("wps_pbc", timeout_seconds, progress);
+    }
+
+    bool connectWpsPin(const std::string& bssid, const std::string& pin, int timeout_seconds,
+                       WpsProgressCallback progress) override {
+        Logger::getInstance().info("Starting WPS PIN connection on " + interface_name);
+        
+        for (char c : pin) {
+            if (c < '0' || c > '9') {
+                Logger::getInstance().error("WPS PIN must contain only digits");
+                return false;
+            }
+        }
+        return runWps("wps_pin " + (bssid.empty() ? std::string("any") : bssid) + " " + pin, timeout_seconds, progress);
+    }
+
+    bool setProbePrivacy(bool enabled) override {
+        // Scans triggered over nl80211 are passive, so only wpa_supplicant needs configuring.
+        // It never sends directed probes unless a network sets scan_ssid=1.
+        probe_privacy = enabled;
+        Logger::getInstance().info("Probe-request privacy ", enabled ? "enabled" : "disabled");
+        return true;
+    }
+
+    bool isProbePrivacyEnabled() const override {
+        return probe_privacy;
+    }
+
+    bool startCapture(const CaptureConfig& config, FrameCallback callback) override {
+        if (capture_running) {
+            Logger::getInstance().error("A capture is already running on " + monitor_interface);
             return false;
         }
         
-        // Configure DHCP server (dnsmasq)
-        std::string dnsmasq_conf = "/tmp/dnsmasq_" + ssid + ".conf";
-        std::ofstream dnsmasq_file(dnsmasq_conf);
-        if (!dnsmasq_file) {
-            Logger::getInstance().error("Failed to create dnsmasq configuration");
-            system("killall hostapd");
-            std::remove(config_path.c_str());
-            return false;
+        if (!supportsInterfaceType(NL80211_IFTYPE_MONITOR)) {
+            Logger::getInstance().error("The driver of " + interface_name + " does not support monitor mode");
+            return fail(ErrorCode::UNSUPPORTED);
         }
         
-        dnsmasq_file << "interface=" << interface_name << "\n";
-        dnsmasq_file << "dhcp-range=192.168.4.2,192.168.4.20,255.255.255.0,24h\n";
-        dnsmasq_file << "bind-interfaces\n";
-        dnsmasq_file.close();
+        monitor_interface = config.monitorInterface.empty() ? interface_name + "mon" : config.monitorInterface;
+        Logger::getInstance().info("Starting monitor mode capture on " + monitor_interface);
         
-        // Start DHCP server
-        cmd = "dnsmasq --conf-file=" + dnsmasq_conf;
-        result = system(cmd.c_str());
-        if (result != 0) {
-            Logger::getInstance().error("Failed to start DHCP server");
-            system("killall hostapd");
-            std::remove(config_path.c_str());
-            std::remove(dnsmasq_conf.c_str());
-            return false;
+        // Add a monitor-mode virtual interface on the same radio as the managed interface
+        monitor_index = addVirtualInterface(monitor_interface, NL80211_IFTYPE_MONITOR);
+        if (monitor_index == 0 || !setInterfaceState(monitor_interface, true)) {
+            Logger::getInstance().error("Failed to bring up monitor interface " + monitor_interface);
+            deleteMonitorInterface();
+            return fail(serviceFailure());
         }
         
-        // Enable IP forwarding and NAT if there's another active interface for internet sharing
-        cmd = "sysctl -w net.ipv4.ip_forward=1";
-        system(cmd.c_str());
-        
-        // Find default gateway interface
-        FILE* pipe = popen("ip route | grep default | awk '{print $5}'", "r");
-        if (pipe) {
-            char buffer[128];
-            if (fgets(buffer, sizeof(buffer), pipe) != nullptr) {
-                std::string ext_iface = buffer;
-                // Remove newline character
-                ext_iface.erase(std::remove(ext_iface.begin(), ext_iface.end(), '\n'), ext_iface.end());
-                
-                if (!ext_iface.empty() && ext_iface != interface_name) {
-                    // Setup NAT
-                    cmd = "iptables -t nat -A POSTROUTING -o " + ext_iface + " -j MASQUERADE";
-                    system(cmd.c_str());
-                    cmd = "iptables -A FORWARD -i " + interface_name + " -o " + ext_iface + " -j ACCEPT";
-                    system(cmd.c_str());
-                    cmd = "iptables -A FORWARD -i " + ext_iface + " -o " + interface_name + " -m state --state RELATED,ESTABLISHED -j ACCEPT";
-                    system(cmd.c_str());
+        if (!config.hopChannels.empty()) {
+            // Hopping runs on the capture thread, which needs its own netlink socket
+            hop_socket = nl_socket_alloc();
+            if (!hop_socket || genl_connect(hop_socket) < 0) {
+                Logger::getInstance().error("Failed to open netlink socket for channel hopping");
+                if (hop_socket) {
+                    nl_socket_free(hop_socket);
+                    hop_socket = nullptr;
                 }
+                deleteMonitorInterface();
+                return false;
             }
-            pclose(pipe);
+        } else if (config.channel > 0 && !setMonitorChannel(config.channel)) {
+            Logger::getInstance().warning("Failed to tune monitor interface to channel ", config.channel);
         }
         
-        hostapd_conf_path = config_path;
-        dnsmasq_conf_path = dnsmasq_conf;
-        hotspot_active = true;
+        capture_fd = ::socket(AF_PACKET, SOCK_RAW, htons(ETH_P_ALL));
+        if (capture_fd < 0) {
+            Logger::getInstance().error("Failed to open packet socket: ", strerror(errno));
+            deleteMonitorInterface();
+            return fail(errno == EPERM ? ErrorCode::PERMISSION_DENIED : ErrorCode::OPERATION_FAILED);
+        }
         
+        struct sockaddr_ll addr;
+        memset(&addr, 0, sizeof(addr));
+        addr.sll_family = AF_PACKET;
+        addr.sll_protocol = htons(ETH_P_ALL);
+        addr.sll_ifindex = monitor_index;
+        if (bind(capture_fd, reinterpret_cast<struct sockaddr*>(&addr), sizeof(addr)) < 0) {
+            Logger::getInstance().error("Failed to bind packet socket: ", strerror(errno));
+            close(capture_fd);
+            capture_fd = -1;
+            deleteMonitorInterface();
+            return false;
+        }
+        
+        capture_running = true;
+        capture_thread = std::thread([this, config, callback]() {
+            captureLoop(config, callback);
+        });
+        return true;
+    }
+
+    bool stopCapture() override {
+        if (!capture_thread.joinable()) {
+            return true;
+        }
+        
+        Logger::getInstance().info("Stopping monitor mode capture on " + monitor_interface);
+        capture_running = false;
+        capture_thread.join();
+        
+        close(capture_fd);
+        capture_fd = -1;
+        if (hop_socket) {
+            nl_socket_free(hop_socket);
+            hop_socket = nullptr;
+        }
+        return deleteMonitorInterface();
+    }
+
+    bool isCapturing() const override {
+        return capture_running;
+    }
+
+    bool startNetworkObserver(NetworkChangeCallback callback) override {
+        if (observer_running) {
+            Logger::getInstance().error("A network observer is already running on " + interface_name);
+            return false;
+        }
+        
+        // Link state and address changes cover association, carrier and DHCP, whoever caused them
+        observer_fd = ::socket(AF_NETLINK, SOCK_RAW | SOCK_CLOEXEC, NETLINK_ROUTE);
+        if (observer_fd < 0) {
+            Logger::getInstance().error("Failed to open rtnetlink socket: ", strerror(errno));
+            return false;
+        }
+        struct sockaddr_nl addr;
+        memset(&addr, 0, sizeof(addr));
+        addr.nl_family = AF_NETLINK;
+        addr.nl_groups = RTMGRP_LINK | RTMGRP_IPV4_IFADDR | RTMGRP_IPV6_IFADDR;
+        if (bind(observer_fd, reinterpret_cast<struct sockaddr*>(&addr), sizeof(addr)) < 0) {
+            Logger::getInstance().error("Failed to subscribe to rtnetlink groups: ", strerror(errno));
+            close(observer_fd);
+            observer_fd = -1;
+            return false;
+        }
+        
+        observer_running = true;
+        observer_thread = std::thread([this, callback]() {
+            observerLoop(callback);
+        });
+        return true;
+    }
+
+    bool stopNetworkObserver() override {
+        if (!observer_thread.joinable()) {
+            return true;
+        }
+        observer_running = false;
+        observer_thread.join();
+        close(observer_fd);
+        observer_fd = -1;
+        return true;
+    }
+
+    std::vector<InterfaceInfo> listInterfaces() const override {
+        std::vector<InterfaceInfo> interfaces;
+        std::vector<uint32_t> wiphys;
+        queryNl80211(NL80211_CMD_GET_INTERFACE, NLM_F_DUMP, static_cast<enum nl80211_attrs>(0), 0,
+                     [&](struct nlattr** tb) {
+            // P2P devices have no network interface
+            if (!tb[NL80211_ATTR_IFNAME] || !tb[NL80211_ATTR_WIPHY]) {
+                return;
+            }
+            InterfaceInfo info;
+            info.name = nla_get_string(tb[NL80211_ATTR_IFNAME]);
+            info.macAddress = macAttribute(tb[NL80211_ATTR_MAC]);
+            info.selected = info.name == interface_name;
+            interfaces.push_back(info);
+            wiphys.push_back(nla_get_u32(tb[NL80211_ATTR_WIPHY]));
+        });
+        
+        for (size_t i = 0; i < interfaces.size(); i++) {
+            InterfaceInfo& info = interfaces[i];
+            char driver[PATH_MAX];
+            std::string link = "/sys/class/net/" + info.name + "/device/driver";
+            ssize_t len = readlink(link.c_str(), driver, sizeof(driver) - 1);
+            if (len > 0) {
+                driver[len] = '\0';
+                const char* slash = strrchr(driver, '/');
+                info.driver = slash ? slash + 1 : driver;
+            }
+            
+            queryNl80211(NL80211_CMD_GET_WIPHY, 0, NL80211_ATTR_WIPHY, wiphys[i], [&](struct nlattr** tb) {
+                if (tb[NL80211_ATTR_SUPPORTED_IFTYPES]) {
+                    struct nlattr* mode;
+                    int rem;
+                    nla_for_each_nested(mode, tb[NL80211_ATTR_SUPPORTED_IFTYPES], rem) {
+                        switch (nla_type(mode)) {
+                            case NL80211_IFTYPE_AP: info.supportsAp = true; break;
+                            case NL80211_IFTYPE_MONITOR: info.supportsMonitor = true; break;
+                            case NL80211_IFTYPE_MESH_POINT: info.supportsMesh = true; break;
+                            case NL80211_IFTYPE_P2P_GO: info.supportsP2p = true; break;
+                            default: break;
+                        }
+                    }
+                }
+                if (tb[NL80211_ATTR_WIPHY_BANDS]) {
+                    struct nlattr* band;
+                    int rem;
+                    nla_for_each_nested(band, tb[NL80211_ATTR_WIPHY_BANDS], rem) {
+                        switch (nla_type(band)) {
+                            case NL80211_BAND_2GHZ: info.band2Ghz = true; break;
+                            case NL80211_BAND_5GHZ: info.band5Ghz = true; break;
+                            case NL80211_BAND_6GHZ: info.band6Ghz = true; break;
+                            default: break;
+                        }
+                    }
+                }
+            });
+        }
+        return interfaces;
+    }
+
+    bool selectInterface(const std::string& name) override {
+        for (const auto& info : listInterfaces()) {
+            if (info.name != name) {
+                continue;
+            }
+            int index = interfaceIndex(name);
+            if (index <= 0) {
+                break;
+            }
+            interface_name = name;
+            interface_index = index;
+            Logger::getInstance().info("Using WiFi interface " + interface_name);
+            return true;
+        }
+        Logger::getInstance().error(name + " is not a wireless interface");
+        return fail(ErrorCode::ADAPTER_NOT_FOUND);
+    }
+
+    bool registerEventCallback(WifiEventCallback callback, int signalThreshold) override {
+        if (events_running) {
+            Logger::getInstance().error("An event callback is already registered on " + interface_name);
+            return false;
+        }
+        
+        // A separate socket so multicast events never interleave with request replies
+        event_socket = nl_socket_alloc();
+        if (!event_socket || genl_connect(event_socket) < 0) {
+            Logger::getInstance().error("Failed to open nl80211 event socket");
+            if (event_socket) {
+                nl_socket_free(event_socket);
+                event_socket = nullptr;
+            }
+            return false;
+        }
+        nl_socket_disable_seq_check(event_socket);
+        nl_socket_set_nonblocking(event_socket);
+        for (const char* group : {"mlme", "scan"}) {
+            int id = genl_ctrl_resolve_grp(event_socket, "nl80211", group);
+            if (id < 0 || nl_socket_add_membership(event_socket, id) < 0) {
+                Logger::getInstance().error(std::string("Failed to join nl80211 multicast group ") + group);
+                nl_socket_free(event_socket);
+                event_socket = nullptr;
+                return false;
+            }
+        }
+        
+        // Signal events need a connection quality monitor threshold on the interface
+        if (!setSignalThreshold(signalThreshold)) {
+            Logger::getInstance().warning("The driver does not report signal threshold crossings on " + interface_name);
+        }
+        
+        event_callback = std::move(callback);
+        events_running = true;
+        event_thread = std::thread([this]() {
+            eventLoop();
+        });
+        return true;
+    }
+
+    bool unregisterEventCallback() override {
+        if (!event_thread.joinable()) {
+            return true;
+        }
+        events_running = false;
+        event_thread.join();
+        nl_socket_free(event_socket);
+        event_socket = nullptr;
+        event_callback = nullptr;
+        return true;
+    }
+
+    std::vector<ChannelSurvey> channelSurvey() override {
+        std::vector<ChannelSurvey> surveys;
+        
+        struct nl_msg* msg = nlmsg_alloc();
+        if (!msg) {
+            Logger::getInstance().error("Failed to allocate netlink message");
+            return surveys;
+        }
+        genlmsg_put(msg, 0, 0, nl80211_id, 0, NLM_F_DUMP, NL80211_CMD_GET_SURVEY, 0);
+        nla_put_u32(msg, NL80211_ATTR_IFINDEX, interface_index);
+        
+        struct survey_data {
+            std::vector<ChannelSurvey>* surveys;
+            bool done;
+        } cb_data = {&surveys, false};
+        
+        auto callback = [](struct nl_msg* msg, void* arg) -> int {
+            auto* data = static_cast<survey_data*>(arg);
+            struct nlattr* tb[NL80211_ATTR_MAX + 1];
+            struct genlmsghdr* gnlh = static_cast<genlmsghdr*>(nlmsg_data(nlmsg_hdr(msg)));
+            
+            nla_parse(tb, NL80211_ATTR_MAX, genlmsg_attrdata(gnlh, 0), genlmsg_attrlen(gnlh, 0), nullptr);
+            if (!tb[NL80211_ATTR_SURVEY_INFO]) {
+                return NL_SKIP;
+            }
+            
+            struct nlattr* info[NL80211_SURVEY_INFO_MAX + 1];
+            static struct nla_policy survey_policy[NL80211_SURVEY_INFO_MAX + 1] = {};
+            nla_parse_nested(info, NL80211_SURVEY_INFO_MAX, tb[NL80211_ATTR_SURVEY_INFO], survey_policy);
+            if (!info[NL80211_SURVEY_INFO_FREQUENCY]) {
+                return NL_SKIP;
+            }
+            
+            ChannelSurvey survey;
+            survey.frequency = nla_get_u32(info[NL80211_SURVEY_INFO_FREQUENCY]);
+            survey.inUse = info[NL80211_SURVEY_INFO_IN_USE] != nullptr;
+            if (info[NL80211_SURVEY_INFO_NOISE]) {
+                survey.noise = nla_get_s8(info[NL80211_SURVEY_INFO_NOISE]);
+            }
+            if (info[NL80211_SURVEY_INFO_TIME]) {
+                survey.activeTimeMs = nla_get_u64(info[NL80211_SURVEY_INFO_TIME]);
+            }
+            if (info[NL80211_SURVEY_INFO_TIME_BUSY]) {
+                survey.busyTimeMs = nla_get_u64(info[NL80211_SURVEY_INFO_TIME_BUSY]);
+            }
+            if (info[NL80211_SURVEY_INFO_TIME_RX]) {
+                survey.receiveTimeMs = nla_get_u64(info[NL80211_SURVEY_INFO_TIME_RX]);
+            }
+            if (info[NL80211_SURVEY_INFO_TIME_TX]) {
+                survey.transmitTimeMs = nla_get_u64(info[NL80211_SURVEY_INFO_TIME_TX]);
+            }
+            data->surveys->push_back(survey);
+            return NL_SKIP;
+        };
+        auto finish = [](struct nl_msg*, void* arg) -> int {
+            static_cast<survey_data*>(arg)->done = true;
+            return NL_STOP;
+        };
+        
+        struct nl_cb* cb = nl_cb_alloc(NL_CB_DEFAULT);
+        nl_cb_set(cb, NL_CB_VALID, NL_CB_CUSTOM, callback, &cb_data);
+        nl_cb_set(cb, NL_CB_FINISH, NL_CB_CUSTOM, finish, &cb_data);
+        
+        int ret = nl_send_auto(socket, msg);
+        nlmsg_free(msg);
+        if (ret < 0) {
+            Logger::getInstance().error("Failed to send survey request");
+            nl_cb_put(cb);
+            return surveys;
+        }
+        
+        // Survey dumps can span several netlink messages
+        while (!cb_data.done && nl_recvmsgs(socket, cb) >= 0) {
+        }
+        nl_cb_put(cb);
+        
+        for (auto& survey : surveys) {
+            survey.channel = frequencyToChannel(survey.frequency);
+        }
+        return surveys;
+    }
+
+    bool getLinkDiagnostics(LinkDiagnostics& diagnostics) const override {
+        diagnostics.adapterPresent = interface_index > 0;
+        diagnostics.radioEnabled = !isRadioBlocked();
+        
+        // wpa_supplicant knows how far the association got
+        auto status = wpaCliValues("status");
+        const std::string& state = status["wpa_state"];
+        diagnostics.ssid = status["ssid"];
+        diagnostics.bssid = status["bssid"];
+        diagnostics.associated = state == "ASSOCIATED" || state == "4WAY_HANDSHAKE" ||
+                                 state == "GROUP_HANDSHAKE" || state == "COMPLETED";
+        diagnostics.authenticated = state == "COMPLETED";
+        
+        diagnostics.ipAddress = ipv4Address(interface_name);
+        diagnostics.gateway = defaultGateway(interface_name);
+        diagnostics.dnsServers = nameservers();
+        return true;
+    }
+
+    bool getIpInfo(IpInfo& info) const override {
+        if (interface_index <= 0) {
+            return false;
+        }
+        
+        struct ifaddrs* addresses = nullptr;
+        if (getifaddrs(&addresses) < 0) {
+            Logger::getInstance().error("Failed to read interface addresses");
+            return false;
+        }
+        for (struct ifaddrs* entry = addresses; entry; entry = entry->ifa_next) {
+            if (!entry->ifa_addr || interface_name != entry->ifa_name) {
+                continue;
+            }
+            char address[INET6_ADDRSTRLEN] = {};
+            int prefix = 0;
+            if (entry->ifa_addr->sa_family == AF_INET) {
+                inet_ntop(AF_INET, &reinterpret_cast<struct sockaddr_in*>(entry->ifa_addr)->sin_addr, address, sizeof(address));
+                if (entry->ifa_netmask) {
+                    prefix = __builtin_popcount(reinterpret_cast<struct sockaddr_in*>(entry->ifa_netmask)->sin_addr.s_addr);
+                }
+                info.ipv4Addresses.push_back(std::string(address) + "/" + std::to_string(prefix));
+            } else if (entry->ifa_addr->sa_family == AF_INET6) {
+                inet_ntop(AF_INET6, &reinterpret_cast<struct sockaddr_in6*>(entry->ifa_addr)->sin6_addr, address, sizeof(address));
+                if (entry->ifa_netmask) {
+                    const auto& mask = reinterpret_cast<struct sockaddr_in6*>(entry->ifa_netmask)->sin6_addr;
+                    for (uint8_t byte : mask.s6_addr) {
+                        prefix += __builtin_popcount(byte);
+                    }
+                }
+                info.ipv6Addresses.push_back(std::string(address) + "/" + std::to_string(prefix));
+            }
+        }
+        freeifaddrs(addresses);
+        
+        info.gateway = defaultGateway(interface_name);
+        info.ipv6Gateway = defaultIpv6Gateway(interface_name);
+        info.dnsServers = nameservers();
+        readDhcpLease(info);
+        return true;
+    }
+
+    bool hasAdminPrivileges() const override {
+        if (geteuid() == 0) {
+            return true;
+        }
+        // Managing interfaces needs CAP_NET_ADMIN in the effective capability set
+        std::ifstream status("/proc/self/status");
+        std::string line;
+        while (std::getline(status, line)) {
+            if (line.rfind("CapEff:", 0) == 0) {
+                unsigned long long capabilities = std::strtoull(line.c_str() + 7, nullptr, 16);
+                return (capabilities >> CAP_NET_ADMIN) & 1;
+            }
+        }
+        return false;
+    }
+
+    bool isRadioEnabled() const override {
+        return !isRadioBlocked();
+    }
+
+    bool setRadioEnabled(bool enabled) override {
+        int fd = open("/dev/rfkill", O_WRONLY | O_CLOEXEC);
+        if (fd < 0) {
+            int error = errno;
+            Logger::getInstance().error("Failed to open /dev/rfkill: ", strerror(error));
+            return fail(error == EACCES || error == EPERM ? ErrorCode::PERMISSION_DENIED : ErrorCode::DRIVER_ERROR);
+        }
+        
+        // Switch only our adapter's radio when its rfkill switch is known, otherwise all WLAN radios
+        struct rfkill_event event;
+        memset(&event, 0, sizeof(event));
+        int index = rfkillIndex();
+        event.idx = index >= 0 ? static_cast<uint32_t>(index) : 0;
+        event.type = RFKILL_TYPE_WLAN;
+        event.op = index >= 0 ? RFKILL_OP_CHANGE : RFKILL_OP_CHANGE_ALL;
+        event.soft = enabled ? 0 : 1;
+        bool written = write(fd, &event, sizeof(event)) == static_cast<ssize_t>(sizeof(event));
+        int error = errno;
+        close(fd);
+        if (!written) {
+            Logger::getInstance().error("Failed to switch the radio: ", strerror(error));
+            return fail(error == EPERM ? ErrorCode::PERMISSION_DENIED : ErrorCode::DRIVER_ERROR);
+        }
+        
+        if (enabled) {
+            if (isRadioBlocked()) {
+                Logger::getInstance().error("The radio is switched off by a hardware switch");
+                return fail(ErrorCode::OPERATION_FAILED);
+            }
+            // Blocking takes the interface down, and unblocking does not bring it back up
+            setInterfaceState(interface_name, true);
+        }
+        Logger::getInstance().info(enabled ? "Radio switched on" : "Radio switched off");
+        return true;
+    }
+
+    bool getConnectionInfo(ConnectionInfo& info) const override {
+        auto status = wpaCliValues("status");
+        if (status["wpa_state"] != "COMPLETED") {
+            return false;
+        }
+        
+        info.ssid = status["ssid"];
+        info.bssid = status["bssid"];
+        info.frequency = std::atoi(status["freq"].c_str());
+        info.fastTransition = status["key_mgmt"].rfind("FT-", 0) == 0;
+        
+        auto signal = wpaCliValues("signal_poll");
+        info.signalStrength = std::atoi(signal["RSSI"].c_str());
+        if (signal.count("FREQUENCY")) {
+            info.frequency = std::atoi(signal["FREQUENCY"].c_str());
+        }
+        info.apMldAddress = status["ap_mld_addr"];
+        // wpa_supplicant reports 9999 when the driver has no noise reading
+        int noise = std::atoi(signal["NOISE"].c_str());
+        info.noise = noise < 0 ? noise : 0;
+        info.linkSpeedMbps = std::atoi(signal["LINKSPEED"].c_str());
+        return true;
+    }
+
+    bool getLinkStats(LinkStats& stats) const override {
+        // A station interface has exactly one station entry: its access point
+        bool associated = false;
+        queryNl80211(NL80211_CMD_GET_STATION, NLM_F_DUMP, NL80211_ATTR_IFINDEX, interface_index,
+                     [&](struct nlattr** tb) {
+            if (associated || !tb[NL80211_ATTR_STA_INFO]) {
+                return;
+            }
+            associated = true;
+            struct nlattr* info[NL80211_STA_INFO_MAX + 1];
+            static struct nla_policy station_policy[NL80211_STA_INFO_MAX + 1] = {};
+            nla_parse_nested(info, NL80211_STA_INFO_MAX, tb[NL80211_ATTR_STA_INFO], station_policy);
+            
+            parseRateInfo(info[NL80211_STA_INFO_TX_BITRATE], stats.txBitrateKbps, stats.txMcs);
+            parseRateInfo(info[NL80211_STA_INFO_RX_BITRATE], stats.rxBitrateKbps, stats.rxMcs);
+            auto counter = [&](int attr, int64_t& value) {
+                if (info[attr]) {
+                    value = nla_get_u32(info[attr]);
+                }
+            };
+            counter(NL80211_STA_INFO_TX_PACKETS, stats.txPackets);
+            counter(NL80211_STA_INFO_RX_PACKETS, stats.rxPackets);
+            counter(NL80211_STA_INFO_TX_RETRIES, stats.txRetries);
+            counter(NL80211_STA_INFO_TX_FAILED, stats.txFailed);
+            if (info[NL80211_STA_INFO_RX_DROP_MISC]) {
+                stats.rxDropped = static_cast<int64_t>(nla_get_u64(info[NL80211_STA_INFO_RX_DROP_MISC]));
+            }
+        });
+        return associated;
+    }
+
+    // Bitrate in kbit/s and MCS index of an NL80211_STA_INFO_*_BITRATE attribute
+    static void parseRateInfo(struct nlattr* attr, uint32_t& bitrateKbps, int& mcs) {
+        if (!attr) {
+            return;
+        }
+        struct nlattr* rate[NL80211_RATE_INFO_MAX + 1];
+        static struct nla_policy rate_policy[NL80211_RATE_INFO_MAX + 1] = {};
+        nla_parse_nested(rate, NL80211_RATE_INFO_MAX, attr, rate_policy);
+        // Both bitrate attributes count in units of 100 kbit/s
+        if (rate[NL80211_RATE_INFO_BITRATE32]) {
+            bitrateKbps = nla_get_u32(rate[NL80211_RATE_INFO_BITRATE32]) * 100;
+        } else if (rate[NL80211_RATE_INFO_BITRATE]) {
+            bitrateKbps = nla_get_u16(rate[NL80211_RATE_INFO_BITRATE]) * 100u;
+        }
+        for (int attribute : {NL80211_RATE_INFO_MCS, NL80211_RATE_INFO_VHT_MCS, NL80211_RATE_INFO_HE_MCS,
+                              NL80211_RATE_INFO_EHT_MCS}) {
+            if (rate[attribute]) {
+                mcs = nla_get_u8(rate[attribute]);
+            }
+        }
+    }
+
+    std::vector<MloLink> getMloLinks() const override {
+        std::vector<MloLink> links;
+        if (wpaCliValues("status")["ap_mld_addr"].empty()) {
+            return links;
+        }
+        
+        // Both commands print one block of key=value lines per link, starting with the link ID
+        std::istringstream status(wpaCli("mlo_status"));
+        std::string line;
+        while (std::getline(status, line)) {
+            size_t eq = line.find('=');
+            if (eq == std::string::npos) {
+                continue;
+            }
+            std::string key = line.substr(0, eq);
+            std::string value = line.substr(eq + 1);
+            if (key == "link_id") {
+                links.emplace_back();
+                links.back().linkId = std::atoi(value.c_str());
+            } else if (links.empty()) {
+                continue;
+            } else if (key == "freq") {
+                links.back().frequency = std::atoi(value.c_str());
+            } else if (key == "ap_link_addr") {
+                links.back().apLinkAddress = value;
+            }
+        }
+        
+        std::istringstream signal(wpaCli("mlo_signal_poll"));
+        MloLink* current = nullptr;
+        while (std::getline(signal, line)) {
+            size_t eq = line.find('=');
+            if (eq == std::string::npos) {
+                continue;
+            }
+            std::string key = line.substr(0, eq);
+            int value = std::atoi(line.substr(eq + 1).c_str());
+            if (key == "LINK_ID") {
+                current = nullptr;
+                for (auto& link : links) {
+                    if (link.linkId == value) {
+                        current = &link;
+                    }
+                }
+            } else if (key == "RSSI" && current) {
+                current->signalStrength = value;
+            }
+        }
+        return links;
+    }
+
+    std::string getInterfaceName() const override {
+        return interface_name;
+    }
+
+    std::string getRegulatoryDomain() const override {
+        std::string country;
+        queryNl80211(NL80211_CMD_GET_REG, [&](struct nlattr** tb) {
+            if (tb[NL80211_ATTR_REG_ALPHA2]) {
+                country = nla_get_string(tb[NL80211_ATTR_REG_ALPHA2]);
+            }
+        });
+        return country;
+    }
+
+    std::vector<ChannelInfo> getAllowedChannels() const override {
+        std::vector<ChannelInfo> channels;
+        queryWiphy([&](struct nlattr** tb) {
+            if (!tb[NL80211_ATTR_WIPHY_BANDS]) {
+                return;
+            }
+            struct nlattr* band;
+            int band_rem;
+            nla_for_each_nested(band, tb[NL80211_ATTR_WIPHY_BANDS], band_rem) {
+                struct nlattr* band_tb[NL80211_BAND_ATTR_MAX + 1];
+                nla_parse_nested(band_tb, NL80211_BAND_ATTR_MAX, band, nullptr);
+                if (!band_tb[NL80211_BAND_ATTR_FREQS]) {
+                    continue;
+                }
+                struct nlattr* freq;
+                int freq_rem;
+                nla_for_each_nested(freq, band_tb[NL80211_BAND_ATTR_FREQS], freq_rem) {
+                    struct nlattr* freq_tb[NL80211_FREQUENCY_ATTR_MAX + 1];
+                    nla_parse_nested(freq_tb, NL80211_FREQUENCY_ATTR_MAX, freq, nullptr);
+                    if (!freq_tb[NL80211_FREQUENCY_ATTR_FREQ] || freq_tb[NL80211_FREQUENCY_ATTR_DISABLED]) {
+                        continue;
+                    }
+                    ChannelInfo channel;
+                    channel.frequency = static_cast<int>(nla_get_u32(freq_tb[NL80211_FREQUENCY_ATTR_FREQ]));
+                    if (freq_tb[NL80211_FREQUENCY_ATTR_MAX_TX_POWER]) {
+                        channel.maxPowerMbm = static_cast<int>(nla_get_u32(freq_tb[NL80211_FREQUENCY_ATTR_MAX_TX_POWER]));
+                    }
+                    channel.dfs = freq_tb[NL80211_FREQUENCY_ATTR_RADAR] != nullptr;
+                    channel.noIr = freq_tb[NL80211_FREQUENCY_ATTR_NO_IR] != nullptr;
+                    channels.push_back(channel);
+                }
+            }
+        });
+        return channels;
+    }
+
+    bool canSetRegulatoryDomain() const override {
+        // Self-managed drivers take the domain from firmware and ignore hints
+        return !wiphyHasFlag(NL80211_ATTR_WIPHY_SELF_MANAGED_REG);
+    }
+
+    bool setRegulatoryDomain(const std::string& country) override {
+        bool valid = country.size() == 2 &&
+            ((std::isupper(static_cast<unsigned char>(country[0])) && std::isupper(static_cast<unsigned char>(country[1]))) ||
+             country == "00");
+        if (!valid) {
+            Logger::getInstance().error("Invalid regulatory domain: " + country);
+            return fail(ErrorCode::OPERATION_FAILED);
+        }
+        if (!canSetRegulatoryDomain()) {
+            Logger::getInstance().warning("The driver of " + interface_name + " manages its own regulatory domain");
+            return fail(ErrorCode::UNSUPPORTED);
+        }
+        
+        struct nl_msg* msg = nlmsg_alloc();
+        if (!msg) {
+            return false;
+        }
+        genlmsg_put(msg, 0, 0, nl80211_id, 0, 0, NL80211_CMD_REQ_SET_REG, 0);
+        nla_put_string(msg, NL80211_ATTR_REG_ALPHA2, country.c_str());
+        if (!sendAndWaitForAck(msg)) {
+            Logger::getInstance().error("Regulatory domain change to " + country + " was rejected");
+            return fail(serviceFailure());
+        }
+        
+        // The kernel applies the request asynchronously once the rules are loaded
+        for (int i = 0; i < 20; i++) {
+            if (getRegulatoryDomain() == country) {
+                return true;
+            }
+            std::this_thread::sleep_for(std::chrono::milliseconds(100));
+        }
+        Logger::getInstance().warning("Regulatory domain is still " + getRegulatoryDomain() + " after requesting " + country);
+        return false;
+    }
+
+    bool getRoamingFeatures(RoamingFeatures& features) const override {
+        bool associated = forEachApElement([&](uint8_t id, const uint8_t* body, uint8_t len) {
+            if (id == 70 && len >= 1) {
+                features.apNeighborReport = (body[0] & 0x02) != 0;
+            } else if (id == 127 && len >= 3) {
+                features.apBssTransition = (body[2] & 0x08) != 0;
+            } else if (id == 54) {
+                features.apFastTransition = true;
+            }
+        });
+        if (!associated) {
+            return false;
+        }
+        
+        // wpa_supplicant only sends radio measurement requests if the driver can
+        // include the DS parameter set in probes and honor quiet periods
+        uint32_t flags = wiphyFeatureFlags();
+        features.localNeighborReport = (flags & NL80211_FEATURE_DS_PARAM_SET_IE_IN_PROBES) &&
+                                       (flags & NL80211_FEATURE_QUIET);
+        // wpa_cli is built with the same configuration as wpa_supplicant, it only
+        // knows the WNM commands when BSS transition management is compiled in
+        features.localBssTransition = wpaCli("help").find("wnm_bss_query") != std::string::npos;
+        features.localFastTransition = wpaCli("get_capability key_mgmt").find("FT-") != std::string::npos;
+        return true;
+    }
+
+    bool roamTo(const std::string& bssid) override {
+        if (!isCommandToken(bssid)) {
+            Logger::getInstance().error("Invalid BSSID: ", bssid);
+            return fail(ErrorCode::OPERATION_FAILED);
+        }
+        auto status = wpaCliValues("status");
+        if (status["wpa_state"] != "COMPLETED") {
+            Logger::getInstance().error("Cannot roam without a connection");
+            return fail(ErrorCode::OPERATION_FAILED);
+        }
+        if (strcasecmp(status["bssid"].c_str(), bssid.c_str()) == 0) {
+            return true;
+        }
+        
+        // wpa_supplicant only roams to access points of the current network that are in its scan results
+        if (wpaCli("roam " + bssid).find("OK") == std::string::npos) {
+            Logger::getInstance().error("wpa_supplicant refused to roam to ", bssid, ", it was not seen on ", status["ssid"]);
+            return fail(ErrorCode::OPERATION_FAILED);
+        }
+        for (int elapsed = 0; elapsed < 10; elapsed++) {
+            sleep(1);
+            auto current = wpaCliValues("status");
+            if (current["wpa_state"] == "COMPLETED" && strcasecmp(current["bssid"].c_str(), bssid.c_str()) == 0) {
+                Logger::getInstance().info("Roamed to ", bssid);
+                return true;
+            }
+        }
+        Logger::getInstance().error("Timed out roaming to ", bssid);
+        return fail(ErrorCode::TIMEOUT);
+    }
+
+    bool setRoamingPolicy(const RoamingPolicy& policy) override {
+        if (policy.rssiThreshold < -100 || policy.rssiThreshold > 0 || policy.scanIntervalSeconds <= 0) {
+            Logger::getInstance().error("Invalid roaming policy");
+            return fail(ErrorCode::OPERATION_FAILED);
+        }
+        std::string id = wpaCliValues("status")["id"];
+        if (id.empty()) {
+            Logger::getInstance().error("Cannot set a roaming policy without a connection");
+            return fail(ErrorCode::OPERATION_FAILED);
+        }
+        
+        // The simple background scan module scans every short interval while the signal is below
+        // the threshold and every long interval otherwise, then roams to a stronger access point;
+        // changing it on the current network restarts background scanning right away
+        int longInterval = std::max(policy.scanIntervalSeconds, 300);
+        std::string bgscan = "simple:" + std::to_string(policy.scanIntervalSeconds) + ":" +
+                             std::to_string(policy.rssiThreshold) + ":" + std::to_string(longInterval);
+        if (wpaCli("set_network " + id + " bgscan \"" + bgscan + "\"").find("OK") == std::string::npos) {
+            Logger::getInstance().error("wpa_supplicant rejected the roaming policy");
+            return fail(serviceFailure());
+        }
+        Logger::getInstance().info("Roaming below ", policy.rssiThreshold, " dBm");
+        return true;
+    }
+
+    bool getTwtStatus(TwtStatus& status) const override {
+        bool associated = forEachApElement([&](uint8_t id, const uint8_t* body, uint8_t len) {
+            // HE Capabilities extension element, TWT Responder Support in the first MAC capability byte
+            if (id == 255 && len >= 2 && body[0] == 35) {
+                status.apResponder = (body[1] & 0x04) != 0;
+            }
+        });
+        if (!associated) {
+            return false;
+        }
+        status.localRequester = heMacCapability(0, 0x02);
+        status.agreementRequested = twt_requested;
+        if (twt_requested) {
+            status.config = twt_config;
+        }
+        return true;
+    }
+
+    bool twtSetup(const TwtConfig& config) override {
+        if (config.wakeIntervalUs == 0 || config.wakeDurationUs == 0 || config.wakeDurationUs > 255 * 256 ||
+            config.wakeDurationUs >= config.wakeIntervalUs) {
+            Logger::getInstance().error("Invalid TWT parameters");
+            return false;
+        }
+        
+        // The wake interval is mantissa * 2^exponent microseconds
+        uint64_t mantissa = config.wakeIntervalUs;
+        int exponent = 0;
+        while (mantissa > 0xFFFF) {
+            mantissa >>= 1;
+            exponent++;
+        }
+        // The minimum wake duration is counted in units of 256 microseconds
+        uint32_t min_twt = (config.wakeDurationUs + 255) / 256;
+        
+        std::string command = "twt_setup setup_cmd=0 requestor=1 implicit=1";
+        command += " exponent=" + std::to_string(exponent);
+        command += " mantissa=" + std::to_string(mantissa);
+        command += " min_twt=" + std::to_string(min_twt);
+        command += " trigger=" + std::string(config.triggerEnabled ? "1" : "0");
+        command += " flow_type=" + std::string(config.announced ? "0" : "1");
+        
+        Logger::getInstance().info("Requesting TWT agreement with a " + std::to_string(config.wakeIntervalUs) +
+                                   " us wake interval");
+        if (wpaCli(command).find("OK") == std::string::npos) {
+            Logger::getInstance().error("wpa_supplicant rejected the TWT setup request");
+            return false;
+        }
+        twt_requested = true;
+        twt_config = config;
+        twt_config.wakeIntervalUs = mantissa << exponent;
+        twt_config.wakeDurationUs = min_twt * 256;
+        return true;
+    }
+
+    bool twtTeardown() override {
+        if (!twt_requested) {
+            return true;
+        }
+        twt_requested = false;
+        return wpaCli("twt_teardown").find("OK") != std::string::npos;
+    }
+
+    bool getWowlanCapabilities(WowlanCapabilities& capabilities) const override {
+        bool supported = false;
+        queryWiphy([&](struct nlattr** tb) {
+            if (!tb[NL80211_ATTR_WOWLAN_TRIGGERS_SUPPORTED]) {
+                return;
+            }
+            supported = true;
+            struct nlattr* triggers[NUM_NL80211_WOWLAN_TRIG];
+            nla_parse_nested(triggers, MAX_NL80211_WOWLAN_TRIG, tb[NL80211_ATTR_WOWLAN_TRIGGERS_SUPPORTED], nullptr);
+            capabilities.magicPacket = triggers[NL80211_WOWLAN_TRIG_MAGIC_PKT] != nullptr;
+            capabilities.disconnect = triggers[NL80211_WOWLAN_TRIG_DISCONNECT] != nullptr;
+            if (triggers[NL80211_WOWLAN_TRIG_PKT_PATTERN] &&
+                nla_len(triggers[NL80211_WOWLAN_TRIG_PKT_PATTERN]) >= static_cast<int>(sizeof(nl80211_pattern_support))) {
+                auto* patterns = static_cast<const nl80211_pattern_support*>(nla_data(triggers[NL80211_WOWLAN_TRIG_PKT_PATTERN]));
+                capabilities.maxPatterns = static_cast<int>(patterns->max_patterns);
+                capabilities.minPatternLength = static_cast<int>(patterns->min_pattern_len);
+                capabilities.maxPatternLength = static_cast<int>(patterns->max_pattern_len);
+                capabilities.maxPatternOffset = static_cast<int>(patterns->max_pkt_offset);
+            }
+        });
+        return supported;
+    }
+
+    bool getWowlanConfig(WowlanConfig& config) const override {
+        WowlanCapabilities capabilities;
+        if (!getWowlanCapabilities(capabilities)) {
+            return false;
+        }
+        queryNl80211(NL80211_CMD_GET_WOWLAN, [&](struct nlattr** tb) {
+            // No triggers attribute means WoWLAN is disabled
+            if (!tb[NL80211_ATTR_WOWLAN_TRIGGERS]) {
+                return;
+            }
+            struct nlattr* triggers[NUM_NL80211_WOWLAN_TRIG];
+            nla_parse_nested(triggers, MAX_NL80211_WOWLAN_TRIG, tb[NL80211_ATTR_WOWLAN_TRIGGERS], nullptr);
+            config.magicPacket = triggers[NL80211_WOWLAN_TRIG_MAGIC_PKT] != nullptr;
+            config.disconnect = triggers[NL80211_WOWLAN_TRIG_DISCONNECT] != nullptr;
+            if (!triggers[NL80211_WOWLAN_TRIG_PKT_PATTERN]) {
+                return;
+            }
+            struct nlattr* pattern;
+            int rem;
+            nla_for_each_nested(pattern, triggers[NL80211_WOWLAN_TRIG_PKT_PATTERN], rem) {
+                struct nlattr* pattern_tb[NUM_NL80211_PKTPAT];
+                nla_parse_nested(pattern_tb, MAX_NL80211_PKTPAT, pattern, nullptr);
+                if (!pattern_tb[NL80211_PKTPAT_PATTERN] || !pattern_tb[NL80211_PKTPAT_MASK]) {
+                    continue;
+                }
+                WowlanPattern entry;
+                auto* bytes = static_cast<const uint8_t*>(nla_data(pattern_tb[NL80211_PKTPAT_PATTERN]));
+                entry.pattern.assign(bytes, bytes + nla_len(pattern_tb[NL80211_PKTPAT_PATTERN]));
+                auto* mask = static_cast<const uint8_t*>(nla_data(pattern_tb[NL80211_PKTPAT_MASK]));
+                entry.mask.assign(mask, mask + nla_len(pattern_tb[NL80211_PKTPAT_MASK]));
+                if (pattern_tb[NL80211_PKTPAT_OFFSET]) {
+                    entry.offset = static_cast<int>(nla_get_u32(pattern_tb[NL80211_PKTPAT_OFFSET]));
+                }
+                config.patterns.push_back(std::move(entry));
+            }
+        });
+        return true;
+    }
+
+    bool setWowlanConfig(const WowlanConfig& config) override {
+        WowlanCapabilities capabilities;
+        if (!getWowlanCapabilities(capabilities)) {
+            Logger::getInstance().warning("The driver of " + interface_name + " does not support Wake-on-WLAN");
+            return false;
+        }
+        if ((config.magicPacket && !capabilities.magicPacket) || (config.disconnect && !capabilities.disconnect) ||
+            static_cast<int>(config.patterns.size()) > capabilities.maxPatterns) {
+            Logger::getInstance().error("Wake-on-WLAN trigger not supported by the driver of " + interface_name);
+            return false;
+        }
+        for (const auto& pattern : config.patterns) {
+            int length = static_cast<int>(pattern.pattern.size());
+            if (length < capabilities.minPatternLength || length > capabilities.maxPatternLength ||
+                pattern.offset < 0 || pattern.offset > capabilities.maxPatternOffset ||
+                pattern.mask.size() != pattern.pattern.size() / 8 + (pattern.pattern.size() % 8 ? 1 : 0)) {
+                Logger::getInstance().error("Invalid Wake-on-WLAN pattern");
+                return false;
+            }
+        }
+        
+        struct nl_msg* msg = nlmsg_alloc();
+        if (!msg) {
+            return false;
+        }
+        genlmsg_put(msg, 0, 0, nl80211_id, 0, 0, NL80211_CMD_SET_WOWLAN, 0);
+        nla_put_u32(msg, NL80211_ATTR_IFINDEX, interface_index);
+        bool enable = config.magicPacket || config.disconnect || !config.patterns.empty();
+        if (enable) {
+            struct nlattr* triggers = nla_nest_start(msg, NL80211_ATTR_WOWLAN_TRIGGERS);
+            if (config.magicPacket) {
+                nla_put_flag(msg, NL80211_WOWLAN_TRIG_MAGIC_PKT);
+            }
+            if (config.disconnect) {
+                nla_put_flag(msg, NL80211_WOWLAN_TRIG_DISCONNECT);
+            }
+            if (!config.patterns.empty()) {
+                struct nlattr* patterns = nla_nest_start(msg, NL80211_WOWLAN_TRIG_PKT_PATTERN);
+                int index = 1;
+                for (const auto& pattern : config.patterns) {
+                    struct nlattr* entry = nla_nest_start(msg, index++);
+                    nla_put(msg, NL80211_PKTPAT_MASK, static_cast<int>(pattern.mask.size()), pattern.mask.data());
+                    nla_put(msg, NL80211_PKTPAT_PATTERN, static_cast<int>(pattern.pattern.size()), pattern.pattern.data());
+                    nla_put_u32(msg, NL80211_PKTPAT_OFFSET, static_cast<uint32_t>(pattern.offset));
+                    nla_nest_end(msg, entry);
+                }
+                nla_nest_end(msg, patterns);
+            }
+            nla_nest_end(msg, triggers);
+        }
+        if (!sendAndWaitForAck(msg)) {
+            Logger::getInstance().error("Failed to configure Wake-on-WLAN on " + interface_name);
+            return false;
+        }
+        Logger::getInstance().info(std::string("Wake-on-WLAN ") + (enable ? "enabled" : "disabled") + " on " + interface_name);
+        return true;
+    }
+
+    bool isEapMethodSupported(EapMethod method) const override {
+        switch (method) {
+            case EapMethod::PEAP:
+            case EapMethod::TLS:
+            case EapMethod::TTLS:
+                return true;
+            case EapMethod::SIM:
+            case EapMethod::AKA:
+            case EapMethod::AKA_PRIME:
+                // SIM methods need pcscd with a reader holding the SIM card
+                return access("/run/pcscd/pcscd.comm", F_OK) == 0;
+        }
+        return false;
+    }
+
+    bool installPasspointProfile(const PasspointProfile& profile) override {
+        Logger::getInstance().info("Installing Passpoint profile for " + profile.domain);
+        
+        if (profile.domain.empty() || profile.realm.empty()) {
+            Logger::getInstance().error("Passpoint profile requires a domain and realm");
+            return false;
+        }
+        if (profile.eap.method == EapMethod::PEAP) {
+            Logger::getInstance().error("Passpoint credentials must use EAP-TTLS or EAP-TLS");
+            return false;
+        }
+        
+        mkdir("/var/lib/wificpp", 0700);
+        mkdir(passpoint_dir, 0700);
+        
+        std::string cred_path = std::string(passpoint_dir) + "/" + profile.domain + ".cred";
+        int fd = open(cred_path.c_str(), O_WRONLY | O_CREAT | O_TRUNC, 0600);
+        if (fd < 0) {
+            Logger::getInstance().error("Failed to create Passpoint credential file");
+            return fail(errno == EACCES || errno == EPERM ? ErrorCode::PERMISSION_DENIED : ErrorCode::OPERATION_FAILED);
+        }
+        close(fd);
+        
+        std::ofstream cred_file(cred_path, std::ios::trunc);
+        cred_file << "cred={\n";
+        cred_file << "    realm=\"" << profile.realm << "\"\n";
+        cred_file << "    domain=\"" << profile.domain << "\"\n";
+        if (!profile.homeOis.empty()) {
+            cred_file << "    roaming_consortiums=\"";
+            for (size_t i = 0; i < profile.homeOis.size(); i++) {
+                cred_file << (i ? "," : "") << profile.homeOis[i];
+            }
+            cred_file << "\"\n";
+        }
+        
+        const EapConfig& eap = profile.eap;
+        if (!eap.caCert.empty()) {
+            cred_file << "    ca_cert=\"" << eap.caCert << "\"\n";
+        } else {
+            cred_file << "    ca_cert=\"/etc/ssl/certs/ca-certificates.crt\"\n";
+        }
+        if (!eap.domainSuffixMatch.empty()) {
+            cred_file << "    domain_suffix_match=\"" << eap.domainSuffixMatch << "\"\n";
+        }
+        
+        bool written = true;
+        if (eap.method == EapMethod::TTLS) {
+            cred_file << "    eap=TTLS\n";
+            cred_file << "    username=\"" << eap.identity << "\"\n";
+            cred_file << "    password=\"" << eap.password << "\"\n";
+        } else {
+            cred_file << "    eap=TLS\n";
+            std::string key_path = std::string(passpoint_dir) + "/" + profile.domain + ".p12";
+            written = writeTlsCredentials(cred_file, key_path, eap);
+        }
+        cred_file << "}\n";
+        cred_file.close();
+        
+        if (!written) {
+            std::remove(cred_path.c_str());
+            return false;
+        }
+        
+        // Start interworking right away when idle so matching hotspots are joined automatically
+        if (getStatus() == ConnectionStatus::DISCONNECTED) {
+            std::string config_path = "/tmp/wificpp_passpoint.conf";
+            std::ofstream config_file(config_path);
+            config_file << "ctrl_interface=/var/run/wpa_supplicant\n";
+            config_file.close();
+            startSupplicant(config_path);
+        }
+        return true;
+    }
+
+    bool removePasspointProfile(const std::string& domain) override {
+        Logger::getInstance().info("Removing Passpoint profile for " + domain);
+        
+        std::string base = std::string(passpoint_dir) + "/" + domain;
+        std::remove((base + ".p12").c_str());
+        if (std::remove((base + ".cred").c_str()) != 0) {
+            Logger::getInstance().warning("No Passpoint profile installed for " + domain);
+            return false;
+        }
+        return true;
+    }
+
+    bool disconnect() override {
+        Logger::getInstance().info("Disconnecting from network on " + interface_name);
+        ephemeral_connection = false;
+        twt_requested = false;
+        
+        switch (activeBackend()) {
+            case LinuxBackend::NETWORK_MANAGER:
+                // NetworkManager releases the lease itself and fails when the device is not connected
+                if (!nmcli({"device", "disconnect", interface_name}) && isAssociated()) {
+                    Logger::getInstance().error("NetworkManager could not disconnect " + interface_name);
+                    return fail(ErrorCode::OPERATION_FAILED);
+                }
+                return true;
+            case LinuxBackend::NL80211:
+                disconnectWithNl80211();
+                releaseDhcpLease(interface_name);
+                return true;
+            default:
+                break;
+        }
+        
+        // Stop wpa_supplicant using native method
+        terminateProcess("wpa_supplicant");
+        
+        // Remove any EAP-TLS key material installed for the connection
+        if (!eap_key_path.empty()) {
+            std::remove(eap_key_path.c_str());
+            eap_key_path.clear();
+        }
+        
+        // Release DHCP lease
+        releaseDhcpLease(interface_name);
+        
+        // Set interface down/up using direct ioctl calls
+        setInterfaceState(interface_name, false);
+        sleep(1);
+        setInterfaceState(interface_name, true);
+        
+        return true;
+    }
+
+    ConnectionStatus getStatus() const override {
+        // Check if interface is up using ioctl
+        if (!isInterfaceUp(interface_name)) {
+            return ConnectionStatus::DISCONNECTED;
+        }
+        
+        // Check if we have an IP address
+        if (!hasIpAddress(interface_name)) {
+            return ConnectionStatus::CONNECTING;
+        }
+        
+        // An address without an association is left over from a lost connection
+        if (!isAssociated()) {
+            return ConnectionStatus::CONNECTION_ERROR;
+        }
+        
+        return ConnectionStatus::CONNECTED;
+    }
+
+    bool createHotspot(const std::string& ssid, const std::string& password = "") override {
+        HotspotConfig config;
+        config.ssid = ssid;
+        config.password = password;
+        // The simple form has always shared the connection of the default route
+        config.shareFrom = defaultRouteInterface();
+        if (config.shareFrom == interface_name) {
+            config.shareFrom.clear();
+        }
+        return createHotspotWithConfig(config);
+    }
+
+    bool createHotspotWithConfig(const HotspotConfig& hotspot) override {
+        const std::string& ssid = hotspot.ssid;
+        const std::string& password = hotspot.password;
+        int channel = hotspot.channel > 0 ? hotspot.channel : 6;
+        Logger::getInstance().info("Creating hotspot: " + ssid + " on channel " + std::to_string(channel));
+        
+        HotspotDhcpConfig dhcp = hotspot.dhcp;
+        if (dhcp.gateway.empty()) {
+            dhcp.gateway = "192.168.4.1";
+            dhcp.rangeStart = "192.168.4.2";
+            dhcp.rangeEnd = "192.168.4.20";
+            dhcp.prefixLength = 24;
+        }
+        struct in_addr gateway_address;
+        if (inet_pton(AF_INET, dhcp.gateway.c_str(), &gateway_address) != 1 ||
+            dhcp.prefixLength < 8 || dhcp.prefixLength > 30) {
+            Logger::getInstance().error("Invalid hotspot addressing: " + dhcp.gateway + "/" + std::to_string(dhcp.prefixLength));
+            return false;
+        }
+        if (!hotspot.shareFrom.empty() && (hotspot.shareFrom == interface_name || interfaceIndex(hotspot.shareFrom) == 0)) {
+            Logger::getInstance().error("Cannot share the connection of " + hotspot.shareFrom + " with the hotspot");
+            return fail(ErrorCode::OPERATION_FAILED);
+        }
+        
+        // Stop any existing hotspot or connection
+        stopHotspot();
+        disconnect();
+        
+        // Create hostapd configuration
+        std::string config_path = "/tmp/hostapd_" + ssid + ".conf";
+        std::ofstream config_file(config_path);
+        if (!config_file) {
+            Logger::getInstance().error("Failed to create hostapd configuration file");
+            return false;
+        }
+        
+        config_file << "interface=" << interface_name << "\n";
+        config_file << "driver=nl80211\n";
+        config_file << "ssid=" << ssid << "\n";
+        config_file << "hw_mode=" << (channel > 14 ? "a" : "g") << "\n";
+        config_file << "channel=" << channel << "\n";
+        config_file << "ieee80211n=1\n";
+        
+        if (!password.empty()) {
+            config_file << "wpa=2\n";
+            config_file << "wpa_passphrase=" << password << "\n";
+            config_file << "wpa_key_mgmt=WPA-PSK\n";
+            config_file << "wpa_pairwise=TKIP CCMP\n";
+            config_file << "rsn_pairwise=CCMP\n";
+        }
+        
+        config_file.close();
+        
+        // Configure interface for AP mode
+        std::string cmd = "ip link set " + interface_name + " down";
+        system(cmd.c_str());
+        sleep(1);
+        
+        // Set static IP for AP interface
+        cmd = "ip addr flush dev " + interface_name;
+        system(cmd.c_str());
+        cmd = "ip addr add " + dhcp.gateway + "/" + std::to_string(dhcp.prefixLength) + " dev " + interface_name;
+        system(cmd.c_str());
+        cmd = "ip link set " + interface_name + " up";
+        system(cmd.c_str());
+        
+        // Start hostapd, which only detaches once the access point is up; a channel availability
+        // check or a stuck driver would otherwise block here indefinitely
+        int timeout_seconds = std::max(1, (hotspotTimeoutMs() + 999) / 1000);
+        cmd = "timeout " + std::to_string(timeout_seconds) + " hostapd -B " + config_path;
+        int result = system(cmd.c_str());
+        if (result != 0) {
+            bool timed_out = WIFEXITED(result) && WEXITSTATUS(result) == 124;
+            Logger::getInstance().error(timed_out ? "hostapd did not start within " + std::to_string(timeout_seconds) + " seconds"
+                                                  : "Failed to start hostapd");
+            std::remove(config_path.c_str());
+            return fail(timed_out ? ErrorCode::TIMEOUT : serviceFailure());
+        }
+        
+        // Configure DHCP server (dnsmasq)
+        std::string dnsmasq_conf = "/tmp/dnsmasq_" + ssid + ".conf";
+        std::ofstream dnsmasq_file(dnsmasq_conf);
+        if (!dnsmasq_file) {
+            Logger::getInstance().error("Failed to create dnsmasq configuration");
+            system("killall hostapd");
+            std::remove(config_path.c_str());
+            return false;
+        }
+        
+        std::string lease_path = "/tmp/dnsmasq_" + ssid + ".leases";
+        dnsmasq_file << "interface=" << interface_name << "\n";
+        struct in_addr netmask;
+        netmask.s_addr = htonl(~((1u << (32 - dhcp.prefixLength)) - 1));
+        char netmask_text[INET_ADDRSTRLEN];
+        inet_ntop(AF_INET, &netmask, netmask_text, sizeof(netmask_text));
+        dnsmasq_file << "dhcp-range=" << dhcp.rangeStart << "," << dhcp.rangeEnd << "," << netmask_text << ",24h\n";
+        dnsmasq_file << "dhcp-option=option:router," << dhcp.gateway << "\n";
+        if (hotspot.redirectDns) {
+            // Clients must resolve through dnsmasq, which sends every name to the portal
+            dnsmasq_file << "address=/#/" << dhcp.gateway << "\n";
+        } else if (!dhcp.dnsServers.empty()) {
+            dnsmasq_file << "dhcp-option=option:dns-server";
+            for (const auto& server : dhcp.dnsServers) {
+                dnsmasq_file << "," << server;
+            }
+            dnsmasq_file << "\n";
+        }
+        dnsmasq_file << "dhcp-leasefile=" << lease_path << "\n";
+        dnsmasq_file << "bind-interfaces\n";
+        dnsmasq_file.close();
+        
+        // Start DHCP server
+        cmd = "dnsmasq --conf-file=" + dnsmasq_conf;
+        result = system(cmd.c_str());
+        if (result != 0) {
+            Logger::getInstance().error("Failed to start DHCP server");
+            system("killall hostapd");
+            std::remove(config_path.c_str());
+            std::remove(dnsmasq_conf.c_str());
+            return fail(serviceFailure());
+        }
+        
+        hostapd_conf_path = config_path;
+        dnsmasq_conf_path = dnsmasq_conf;
+        dnsmasq_lease_path = lease_path;
+        hotspot_active = true;
+        
+        if (!hotspot.shareFrom.empty() && !enableInternetSharing(hotspot.shareFrom)) {
+            Logger::getInstance().error("Failed to share the connection of " + hotspot.shareFrom);
+            stopHotspot();
+            return fail(serviceFailure());
+        }
+        
+        return true;
+    }
+
+    // Route hotspot clients to the internet through uplink: forwarding plus a masquerade rule,
+    // with iptables or, where it is missing, an nftables table of our own
+    bool enableInternetSharing(const std::string& uplink) {
+        std::ifstream forwarding_in("/proc/sys/net/ipv4/ip_forward");
+        forwarding_in >> ip_forward_before;
+        std::ofstream forwarding_out("/proc/sys/net/ipv4/ip_forward");
+        if (!(forwarding_out << "1\n") || !forwarding_out.flush()) {
+            return false;
+        }
+        
+        bool added = true;
+        for (const auto& rule : sharingRules(uplink)) {
+            added = added && system(("iptables " + rule.first + " -A " + rule.second + " > /dev/null 2>&1").c_str()) == 0;
+        }
+        if (added) {
+            shared_uplink = uplink;
+            shared_with_nft = false;
+            return true;
+        }
+        for (const auto& rule : sharingRules(uplink)) {
+            system(("iptables " + rule.first + " -D " + rule.second + " > /dev/null 2>&1").c_str());
+        }
+        
+        std::string ruleset_path = "/tmp/wificpp_nat_" + interface_name + ".nft";
+        std::ofstream ruleset(ruleset_path);
+        ruleset << "table ip wificpp {\n"
+                << "    chain postrouting {\n"
+                << "        type nat hook postrouting priority 100;\n"
+                << "        oifname \"" << uplink << "\" masquerade\n"
+                << "    }\n"
+                << "    chain forward {\n"
+                << "        type filter hook forward priority 0;\n"
+                << "        iifname \"" << interface_name << "\" oifname \"" << uplink << "\" accept\n"
+                << "        iifname \"" << uplink << "\" oifname \"" << interface_name
+                << "\" ct state related,established accept\n"
+                << "    }\n"
+                << "}\n";
+        ruleset.close();
+        added = system(("nft -f " + ruleset_path + " > /dev/null 2>&1").c_str()) == 0;
+        std::remove(ruleset_path.c_str());
+        if (!added) {
+            std::ofstream("/proc/sys/net/ipv4/ip_forward") << ip_forward_before << "\n";
+            return false;
+        }
+        shared_uplink = uplink;
+        shared_with_nft = true;
+        return true;
+    }
+
+    // Remove exactly the rules enableInternetSharing added and restore forwarding
+    void disableInternetSharing() {
+        if (shared_uplink.empty()) {
+            return;
+        }
+        if (shared_with_nft) {
+            system("nft delete table ip wificpp > /dev/null 2>&1");
+        } else {
+            for (const auto& rule : sharingRules(shared_uplink)) {
+                system(("iptables " + rule.first + " -D " + rule.second + " > /dev/null 2>&1").c_str());
+            }
+        }
+        std::ofstream forwarding("/proc/sys/net/ipv4/ip_forward");
+        forwarding << ip_forward_before << "\n";
+        shared_uplink.clear();
+    }
+
+    // Table option and chain with rule specification of each iptables rule used for sharing
+    std::vector<std::pair<std::string, std::string>> sharingRules(const std::string& uplink) const {
+        return {
+            {"-t nat", "POSTROUTING -o " + uplink + " -j MASQUERADE"},
+            {"-t filter", "FORWARD -i " + interface_name + " -o " + uplink + " -j ACCEPT"},
+            {"-t filter", "FORWARD -i " + uplink + " -o " + interface_name +
+                          " -m state --state RELATED,ESTABLISHED -j ACCEPT"},
+        };
+    }
+
+    // Interface of the IPv4 default route, empty if there is none
+    static std::string defaultRouteInterface() {
+        std::ifstream routes("/proc/net/route");
+        std::string line;
+        std::getline(routes, line);  // header
+        while (std::getline(routes, line)) {
+            std::istringstream fields(line);
+            std::string iface, destination;
+            if (fields >> iface >> destination && destination == "00000000") {
+                return iface;
+            }
+        }
+        return "";
+    }
+
+    bool stopHotspot() override {
+        Logger::getInstance().info("Stopping hotspot");
+        
+        if (!hotspot_active) {
+            return true;  // Nothing to do
+        }
+        
+        // Stop hostapd and dnsmasq
+        system("killall -q hostapd");
+        system("killall -q dnsmasq");
+        
+        // Clean up configuration files
+        if (!hostapd_conf_path.empty()) {
+            std::remove(hostapd_conf_path.c_str());
+            hostapd_conf_path.clear();
+        }
+        
+        if (!dnsmasq_conf_path.empty()) {
+            std::remove(dnsmasq_conf_path.c_str());
+            dnsmasq_conf_path.clear();
+        }
+        
+        if (!dnsmasq_lease_path.empty()) {
+            std::remove(dnsmasq_lease_path.c_str());
+            dnsmasq_lease_path.clear();
+        }
+        
+        // Remove the NAT rules, leaving those of other applications alone
+        disableInternetSharing();
+        
+        // Reset interface
+        std::string cmd = "ip addr flush dev " + interface_name;
+        system(cmd.c_str());
+        cmd = "ip link set " + interface_name + " down";
+        system(cmd.c_str());
+        sleep(1);
+        cmd = "ip link set " + interface_name + " up";
+        system(cmd.c_str());
+        
+        hotspot_active = false;
+        return true;
+    }
+
+    bool isHotspotActive() const override {
+        // Check if hostapd is running
+        std::string cmd = "pgrep -x hostapd";
+        int result = system((cmd + " > /dev/null 2>&1").c_str());
+        return result == 0;
+    }
+
+    bool isHotspotSupported() const override {
+        // Check if we have hostapd installed
+        std::string cmd = "which hostapd";
+        int result = system((cmd + " > /dev/null 2>&1").c_str());
+        if (result != 0) {
+            return false;
+        }
+        
+        // Check if interface supports AP mode
+        return supportsInterfaceType(NL80211_IFTYPE_AP);
+    }
+
+    std::vector<HotspotClient> hotspotClients() const override {
+        std::vector<HotspotClient> clients;
+        if (!hotspot_active) {
+            return clients;
+        }
+        
+        // The kernel's station table is authoritative for who is associated
+        queryNl80211(NL80211_CMD_GET_STATION, NLM_F_DUMP, NL80211_ATTR_IFINDEX, interface_index,
+                     [&](struct nlattr** tb) {
+            if (!tb[NL80211_ATTR_MAC]) {
+                return;
+            }
+            HotspotClient client;
+            client.macAddress = macAttribute(tb[NL80211_ATTR_MAC]);
+            if (tb[NL80211_ATTR_STA_INFO]) {
+                struct nlattr* info[NL80211_STA_INFO_MAX + 1];
+                static struct nla_policy station_policy[NL80211_STA_INFO_MAX + 1] = {};
+                nla_parse_nested(info, NL80211_STA_INFO_MAX, tb[NL80211_ATTR_STA_INFO], station_policy);
+                if (info[NL80211_STA_INFO_SIGNAL]) {
+                    client.signalStrength = static_cast<int8_t>(nla_get_u8(info[NL80211_STA_INFO_SIGNAL]));
+                }
+                if (info[NL80211_STA_INFO_CONNECTED_TIME]) {
+                    client.connectedSeconds = static_cast<int>(nla_get_u32(info[NL80211_STA_INFO_CONNECTED_TIME]));
+                }
+            }
+            clients.push_back(client);
+        });
+        
+        // dnsmasq leases: expiry, MAC, IP, hostname ("*" if none) and client id
+        std::ifstream leases(dnsmasq_lease_path);
+        std::string line;
+        while (std::getline(leases, line)) {
+            std::istringstream fields(line);
+            std::string expiry, mac, ip, hostname;
+            if (!(fields >> expiry >> mac >> ip >> hostname)) {
+                continue;
+            }
+            for (auto& client : clients) {
+                if (strcasecmp(client.macAddress.c_str(), mac.c_str()) == 0) {
+                    client.ipAddress = ip;
+                    client.hostname = hostname == "*" ? "" : hostname;
+                }
+            }
+        }
+        
+        // Clients with a static address only show up in the neighbor table
+        std::ifstream arp("/proc/net/arp");
+        std::getline(arp, line);
+        while (std::getline(arp, line)) {
+            std::istringstream fields(line);
+            std::string ip, hw_type, flags, mac, mask, device;
+            if (!(fields >> ip >> hw_type >> flags >> mac >> mask >> device) || device != interface_name) {
+                continue;
+            }
+            for (auto& client : clients) {
+                if (client.ipAddress.empty() && strcasecmp(client.macAddress.c_str(), mac.c_str()) == 0) {
+                    client.ipAddress = ip;
+                }
+            }
+        }
+        return clients;
+    }
+
+    bool isP2pSupported() const override {
+        return supportsInterfaceType(NL80211_IFTYPE_P2P_CLIENT) &&
+               supportsInterfaceType(NL80211_IFTYPE_P2P_GO);
+    }
+
+    std::vector<P2pPeer> p2pDiscover(int timeoutSeconds) override {
+        std::vector<P2pPeer> peers;
+        if (!ensureP2pSupplicant()) {
+            return peers;
+        }
+        
+        if (wpaCli("p2p_find " + std::to_string(timeoutSeconds)).find("OK") == std::string::npos) {
+            Logger::getInstance().error("wpa_supplicant rejected the P2P search");
+            return peers;
+        }
+        sleep(timeoutSeconds);
+        wpaCli("p2p_stop_find");
+        
+        std::istringstream addresses(wpaCli("p2p_peers"));
+        std::string address;
+        while (std::getline(addresses, address)) {
+            if (address.size() != 17) {
+                continue;
+            }
+            
+            auto info = wpaCliValues("p2p_peer " + address);
+            P2pPeer peer;
+            peer.deviceAddress = address;
+            peer.deviceName = info["device_name"];
+            peer.deviceType = info["pri_dev_type"];
+            // Group capability bit 0: the device is currently a group owner
+            peer.groupOwner = (std::strtol(info["group_capab"].c_str(), nullptr, 16) & 0x01) != 0;
+            peers.push_back(peer);
+        }
+        return peers;
+    }
+
+    bool p2pConnect(const P2pConnectConfig& config, int timeoutSeconds, P2pGroup& group) override {
+        if (!isMacAddress(config.peerAddress)) {
+            Logger::getInstance().error("Invalid P2P peer address: ", config.peerAddress);
+            return fail(ErrorCode::OPERATION_FAILED);
+        }
+        if (config.method != P2pWpsMethod::PBC &&
+            (config.pin.empty() || !std::all_of(config.pin.begin(), config.pin.end(), [](char c) {
+                return std::isdigit(static_cast<unsigned char>(c));
+            }))) {
+            Logger::getInstance().error("A numeric PIN is required for PIN based Wi-Fi Direct connections");
+            return fail(ErrorCode::OPERATION_FAILED);
+        }
+        if (!ensureP2pSupplicant()) {
+            return false;
+        }
+        
+        std::string command = "p2p_connect " + config.peerAddress;
+        switch (config.method) {
+            case P2pWpsMethod::PBC:
+                command += " pbc";
+                break;
+            case P2pWpsMethod::PIN_DISPLAY:
+                command += " " + config.pin + " display";
+                break;
+            case P2pWpsMethod::PIN_KEYPAD:
+                command += " " + config.pin + " keypad";
+                break;
+        }
+        command += " go_intent=" + std::to_string(std::max(0, std::min(15, config.goIntent)));
+        
+        if (wpaCli(command).find("FAIL") != std::string::npos) {
+            Logger::getInstance().error("wpa_supplicant rejected the P2P connection to ", config.peerAddress);
+            return false;
+        }
+        
+        // The group comes up on a new p2p-<iface>-N interface, or on the
+        // main interface when the driver has no separate group interfaces
+        for (int elapsed = 0; elapsed < timeoutSeconds; elapsed++) {
+            sleep(1);
+            for (const auto& iface : supplicantInterfaces()) {
+                auto status = wpaCliValues(iface, "status");
+                if (status["mode"].find("P2P") == std::string::npos || status["wpa_state"] != "COMPLETED") {
+                    continue;
+                }
+                
+                group.interfaceName = iface;
+                group.ssid = status["ssid"];
+                group.frequency = std::atoi(status["freq"].c_str());
+                group.groupOwner = status["mode"].find("GO") != std::string::npos;
+                group.goDeviceAddress = group.groupOwner ? status["p2p_device_address"] : config.peerAddress;
+                p2p_group_interface = iface;
+                
+                if (group.groupOwner) {
+                    Logger::getInstance().info("Formed Wi-Fi Direct group ", group.ssid, " as group owner on ", iface);
+                } else if (!configureDhcpClient(iface)) {
+                    Logger::getInstance().warning("DHCP on the Wi-Fi Direct group interface failed");
+                }
+                return true;
+            }
+        }
+        
+        Logger::getInstance().error("Wi-Fi Direct group formation did not complete within ", timeoutSeconds, " seconds");
+        wpaCli("p2p_cancel");
+        return false;
+    }
+
+    bool isRttSupported() const override {
+        return wiphyHasFlag(NL80211_ATTR_PEER_MEASUREMENTS);
+    }
+
+    bool measureDistance(const std::string& bssid, int frequency, RangingResult& result) override {
+        unsigned char mac[6];
+        if (sscanf(bssid.c_str(), "%hhx:%hhx:%hhx:%hhx:%hhx:%hhx",
+                   &mac[0], &mac[1], &mac[2], &mac[3], &mac[4], &mac[5]) != 6) {
+            Logger::getInstance().error("Invalid BSSID " + bssid);
+            return false;
+        }
+        if (frequency == 0) {
+            for (const auto& network : scan()) {
+                if (strcasecmp(network.bssid.c_str(), bssid.c_str()) == 0) {
+                    frequency = network.frequency;
+                    break;
+                }
+            }
+            if (frequency == 0) {
+                Logger::getInstance().error("Access point " + bssid + " was not found in a scan");
+                return false;
+            }
+        }
+        
+        // Results are delivered asynchronously to the requesting socket, use a private one
+        struct nl_sock* sock = nl_socket_alloc();
+        if (!sock || genl_connect(sock) < 0) {
+            Logger::getInstance().error("Failed to open netlink socket for ranging");
+            if (sock) {
+                nl_socket_free(sock);
+            }
+            return false;
+        }
+        nl_socket_disable_seq_check(sock);
+        struct timeval timeout = {10, 0};
+        setsockopt(nl_socket_get_fd(sock), SOL_SOCKET, SO_RCVTIMEO, &timeout, sizeof(timeout));
+        
+        struct nl_msg* msg = nlmsg_alloc();
+        if (!msg) {
+            nl_socket_free(sock);
+            return false;
+        }
+        genlmsg_put(msg, 0, 0, nl80211_id, 0, 0, NL80211_CMD_PEER_MEASUREMENT_START, 0);
+        nla_put_u32(msg, NL80211_ATTR_IFINDEX, interface_index);
+        struct nlattr* pmsr = nla_nest_start(msg, NL80211_ATTR_PEER_MEASUREMENTS);
+        struct nlattr* peers = nla_nest_start(msg, NL80211_PMSR_ATTR_PEERS);
+        struct nlattr* peer = nla_nest_start(msg, 1);
+        nla_put(msg, NL80211_PMSR_PEER_ATTR_ADDR, sizeof(mac), mac);
+        struct nlattr* chan = nla_nest_start(msg, NL80211_PMSR_PEER_ATTR_CHAN);
+        nla_put_u32(msg, NL80211_ATTR_WIPHY_FREQ, frequency);
+        nla_put_u32(msg, NL80211_ATTR_CHANNEL_WIDTH, NL80211_CHAN_WIDTH_20);
+        nla_put_u32(msg, NL80211_ATTR_CENTER_FREQ1, frequency);
+        nla_nest_end(msg, chan);
+        struct nlattr* req = nla_nest_start(msg, NL80211_PMSR_PEER_ATTR_REQ);
+        struct nlattr* data = nla_nest_start(msg, NL80211_PMSR_REQ_ATTR_DATA);
+        struct nlattr* ftm = nla_nest_start(msg, NL80211_PMSR_TYPE_FTM);
+        nla_put_flag(msg, NL80211_PMSR_FTM_REQ_ATTR_ASAP);
+        nla_put_u32(msg, NL80211_PMSR_FTM_REQ_ATTR_PREAMBLE, NL80211_PREAMBLE_HT);
+        nla_put_u8(msg, NL80211_PMSR_FTM_REQ_ATTR_FTMS_PER_BURST, 8);
+        nla_nest_end(msg, ftm);
+        nla_nest_end(msg, data);
+        nla_nest_end(msg, req);
+        nla_nest_end(msg, peer);
+        nla_nest_end(msg, peers);
+        nla_nest_end(msg, pmsr);
+        
+        struct ranging_data {
+            RangingResult* result;
+            bool measured;
+            bool done;
+        } cb_data = {&result, false, false};
+        
+        auto callback = [](struct nl_msg* msg, void* arg) -> int {
+            auto* data = static_cast<ranging_data*>(arg);
+            struct genlmsghdr* gnlh = static_cast<genlmsghdr*>(nlmsg_data(nlmsg_hdr(msg)));
+            if (gnlh->cmd == NL80211_CMD_PEER_MEASUREMENT_COMPLETE) {
+                data->done = true;
+                return NL_STOP;
+            }
+            if (gnlh->cmd != NL80211_CMD_PEER_MEASUREMENT_RESULT) {
+                return NL_SKIP;
+            }
+            
+            struct nlattr* tb[NL80211_ATTR_MAX + 1];
+            nla_parse(tb, NL80211_ATTR_MAX, genlmsg_attrdata(gnlh, 0), genlmsg_attrlen(gnlh, 0), nullptr);
+            if (!tb[NL80211_ATTR_PEER_MEASUREMENTS]) {
+                return NL_SKIP;
+            }
+            struct nlattr* pmsr[NL80211_PMSR_ATTR_MAX + 1];
+            nla_parse_nested(pmsr, NL80211_PMSR_ATTR_MAX, tb[NL80211_ATTR_PEER_MEASUREMENTS], nullptr);
+            if (!pmsr[NL80211_PMSR_ATTR_PEERS]) {
+                return NL_SKIP;
+            }
+            
+            struct nlattr* nl_peer;
+            int rem;
+            nla_for_each_nested(nl_peer, pmsr[NL80211_PMSR_ATTR_PEERS], rem) {
+                struct nlattr* peer[NL80211_PMSR_PEER_ATTR_MAX + 1];
+                nla_parse_nested(peer, NL80211_PMSR_PEER_ATTR_MAX, nl_peer, nullptr);
+                if (!peer[NL80211_PMSR_PEER_ATTR_RESP]) {
+                    continue;
+                }
+                struct nlattr* resp[NL80211_PMSR_RESP_ATTR_MAX + 1];
+                nla_parse_nested(resp, NL80211_PMSR_RESP_ATTR_MAX, peer[NL80211_PMSR_PEER_ATTR_RESP], nullptr);
+                if (!resp[NL80211_PMSR_RESP_ATTR_STATUS] || !resp[NL80211_PMSR_RESP_ATTR_DATA] ||
+                    nla_get_u32(resp[NL80211_PMSR_RESP_ATTR_STATUS]) != NL80211_PMSR_STATUS_SUCCESS) {
+                    continue;
+                }
+                struct nlattr* type[NL80211_PMSR_TYPE_MAX + 1];
+                nla_parse_nested(type, NL80211_PMSR_TYPE_MAX, resp[NL80211_PMSR_RESP_ATTR_DATA], nullptr);
+                if (!type[NL80211_PMSR_TYPE_FTM]) {
+                    continue;
+                }
+                struct nlattr* ftm[NL80211_PMSR_FTM_RESP_ATTR_MAX + 1];
+                nla_parse_nested(ftm, NL80211_PMSR_FTM_RESP_ATTR_MAX, type[NL80211_PMSR_TYPE_FTM], nullptr);
+                
+                RangingResult* result = data->result;
+                if (ftm[NL80211_PMSR_FTM_RESP_ATTR_RTT_AVG]) {
+                    result->rttPs = static_cast<int64_t>(nla_get_u64(ftm[NL80211_PMSR_FTM_RESP_ATTR_RTT_AVG]));
+                }
+                if (ftm[NL80211_PMSR_FTM_RESP_ATTR_DIST_AVG]) {
+                    result->distanceMm = static_cast<int64_t>(nla_get_u64(ftm[NL80211_PMSR_FTM_RESP_ATTR_DIST_AVG]));
+                } else {
+                    // Light travels about 0.3 mm per picosecond, the round trip covers the distance twice
+                    result->distanceMm = result->rttPs * 299792458 / 2000000000;
+                }
+                if (ftm[NL80211_PMSR_FTM_RESP_ATTR_DIST_VARIANCE]) {
+                    result->distanceVarianceMm2 = static_cast<int64_t>(
+                        nla_get_u64(ftm[NL80211_PMSR_FTM_RESP_ATTR_DIST_VARIANCE]));
+                } else if (ftm[NL80211_PMSR_FTM_RESP_ATTR_RTT_VARIANCE]) {
+                    double variance_ps2 = static_cast<double>(nla_get_u64(ftm[NL80211_PMSR_FTM_RESP_ATTR_RTT_VARIANCE]));
+                    result->distanceVarianceMm2 = static_cast<int64_t>(variance_ps2 * 0.1499 * 0.1499);
+                }
+                if (ftm[NL80211_PMSR_FTM_RESP_ATTR_NUM_FTMR_ATTEMPTS]) {
+                    result->attempts = static_cast<int>(nla_get_u32(ftm[NL80211_PMSR_FTM_RESP_ATTR_NUM_FTMR_ATTEMPTS]));
+                }
+                if (ftm[NL80211_PMSR_FTM_RESP_ATTR_NUM_FTMR_SUCCESSES]) {
+                    result->successes = static_cast<int>(nla_get_u32(ftm[NL80211_PMSR_FTM_RESP_ATTR_NUM_FTMR_SUCCESSES]));
+                }
+                data->measured = result->rttPs > 0 || result->distanceMm > 0;
+            }
+            return NL_SKIP;
+        };
+        
+        struct nl_cb* cb = nl_cb_alloc(NL_CB_DEFAULT);
+        nl_cb_set(cb, NL_CB_VALID, NL_CB_CUSTOM, callback, &cb_data);
+        
+        Logger::getInstance().info("Ranging with " + bssid + " at " + std::to_string(frequency) + " MHz");
+        int ret = nl_send_auto(sock, msg);
+        nlmsg_free(msg);
+        if (ret >= 0) {
+            // The request is acknowledged first, results and completion follow
+            while (!cb_data.done && nl_recvmsgs(sock, cb) >= 0) {
+            }
+        }
+        nl_cb_put(cb);
+        nl_socket_free(sock);
+        
+        if (!cb_data.measured) {
+            Logger::getInstance().error("No FTM response from " + bssid);
+            return false;
+        }
+        result.bssid = bssid;
+        return true;
+    }
+
+    bool isTdlsSupported() const override {
+        return wiphyHasFlag(NL80211_ATTR_TDLS_SUPPORT);
+    }
+
+    std::vector<TdlsPeer> tdlsDiscover(int timeoutSeconds) override {
+        std::vector<TdlsPeer> peers;
+        if (!hasIpAddress(interface_name)) {
+            Logger::getInstance().error("TDLS discovery requires a connection");
+            return peers;
+        }
+        
+        // Stations of the BSS we have talked to, from the neighbor table
+        std::ifstream arp("/proc/net/arp");
+        std::string line;
+        std::getline(arp, line);  // header
+        while (std::getline(arp, line)) {
+            std::istringstream fields(line);
+            std::string ip, hw_type, flags, mac, mask, device;
+            if (fields >> ip >> hw_type >> flags >> mac >> mask >> device &&
+                device == interface_name && mac != "00:00:00:00:00:00") {
+                TdlsPeer peer;
+                peer.address = mac;
+                peer.ipAddress = ip;
+                peers.push_back(peer);
+            }
+        }
+        
+        std::string gateway = defaultGateway(interface_name);
+        peers.erase(std::remove_if(peers.begin(), peers.end(),
+                                   [&](const TdlsPeer& peer) { return peer.ipAddress == gateway; }),
+                    peers.end());
+        
+        Logger::getInstance().info("Probing " + std::to_string(peers.size()) + " stations for TDLS");
+        for (const auto& peer : peers) {
+            wpaCli("tdls_discover " + peer.address);
+        }
+        sleep(static_cast<unsigned>(std::max(timeoutSeconds, 1)));
+        
+        for (auto& peer : peers) {
+            peer.linked = tdlsLinked(peer.address);
+        }
+        return peers;
+    }
+
+    bool tdlsSetup(const std::string& peerAddress) override {
+        if (!isMacAddress(peerAddress)) {
+            Logger::getInstance().error("Invalid TDLS peer address: ", peerAddress);
+            return fail(ErrorCode::OPERATION_FAILED);
+        }
+        Logger::getInstance().info("Setting up TDLS link to " + peerAddress);
+        if (wpaCli("tdls_setup " + peerAddress).find("OK") == std::string::npos) {
+            Logger::getInstance().error("wpa_supplicant rejected TDLS setup with " + peerAddress);
+            return false;
+        }
+        
+        for (int elapsed = 0; elapsed < 5; elapsed++) {
+            sleep(1);
+            if (tdlsLinked(peerAddress)) {
+                return true;
+            }
+        }
+        Logger::getInstance().error("TDLS peer " + peerAddress + " did not complete the setup");
+        return false;
+    }
+
+    bool tdlsTeardown(const std::string& peerAddress) override {
+        if (!isMacAddress(peerAddress)) {
+            Logger::getInstance().error("Invalid TDLS peer address: ", peerAddress);
+            return fail(ErrorCode::OPERATION_FAILED);
+        }
+        Logger::getInstance().info("Tearing down TDLS link to " + peerAddress);
+        return wpaCli("tdls_teardown " + peerAddress).find("OK") != std::string::npos;
+    }
+
+    bool isAdHocSupported() const override {
+        return supportsInterfaceType(NL80211_IFTYPE_ADHOC);
+    }
+
+    bool joinAdHoc(const AdHocConfig& config) override {
+        int frequency = channelToFrequency(config.channel);
+        if (frequency == 0) {
+            Logger::getInstance().error("Invalid ad-hoc channel ", config.channel);
+            return false;
+        }
+        if (!config.password.empty() && (config.password.size() < 8 || config.password.size() > 63)) {
+            Logger::getInstance().error("WPA-None passphrase must be 8 to 63 characters");
+            return false;
+        }
+        Logger::getInstance().info("Joining ad-hoc network " + config.ssid + " on channel " +
+                                   std::to_string(config.channel));
+        
+        std::string config_path = "/tmp/wificpp_" + config.ssid + ".conf";
+        std::ofstream config_file(config_path);
+        if (!config_file) {
+            Logger::getInstance().error("Failed to create temporary configuration file");
+            return false;
+        }
+        
+        // ap_scan=2 lets wpa_supplicant start the IBSS when no other station is around
+        config_file << "ctrl_interface=/var/run/wpa_supplicant\n";
+        config_file << "ap_scan=2\n";
+        config_file << "network={\n";
+        config_file << "    ssid=\"" << config.ssid << "\"\n";
+        config_file << "    mode=1\n";
+        config_file << "    frequency=" << frequency << "\n";
+        if (config.password.empty()) {
+            config_file << "    key_mgmt=NONE\n";
+        } else {
+            config_file << "    key_mgmt=WPA-NONE\n";
+            config_file << "    proto=WPA\n";
+            config_file << "    pairwise=NONE\n";
+            config_file << "    group=CCMP\n";
+            config_file << "    psk=\"" << config.password << "\"\n";
+        }
+        config_file << "}\n";
+        config_file.close();
+        
+        bool launched = launchSupplicant(config_path);
+        std::remove(config_path.c_str());
+        if (!launched) {
+            return false;
+        }
+        
+        // Ad-hoc peers rarely run a DHCP server, addressing is left to the application
+        if (!waitForSupplicantState("COMPLETED", 15)) {
+            Logger::getInstance().error("Failed to join ad-hoc network " + config.ssid);
+            return false;
+        }
+        return true;
+    }
+
+    bool isMeshSupported() const override {
+        return supportsInterfaceType(NL80211_IFTYPE_MESH_POINT);
+    }
+
+    bool meshJoin(const MeshConfig& config) override {
+        if (mesh_index != 0) {
+            Logger::getInstance().error("Already part of a mesh on " + mesh_interface);
+            return false;
+        }
+        if (config.meshId.empty() || config.meshId.size() > 32) {
+            Logger::getInstance().error("Mesh ID must be 1 to 32 bytes");
+            return false;
+        }
+        
+        int frequency = 0;
+        if (config.channel != 0) {
+            frequency = channelToFrequency(config.channel);
+            if (frequency == 0) {
+                Logger::getInstance().error("Invalid mesh channel ", config.channel);
+                return false;
+            }
+        }
+        
+        mesh_interface = config.interfaceName.empty() ? interface_name + "mesh" : config.interfaceName;
+        Logger::getInstance().info("Joining mesh " + config.meshId + " on " + mesh_interface);
+        mesh_index = addVirtualInterface(mesh_interface, NL80211_IFTYPE_MESH_POINT);
+        if (mesh_index == 0 || !setInterfaceState(mesh_interface, true)) {
+            Logger::getInstance().error("Failed to bring up mesh interface " + mesh_interface);
+            meshLeave();
+            return false;
+        }
+        
+        bool joined = config.password.empty()
+            ? joinOpenMesh(config.meshId, frequency)
+            : joinSecureMesh(config, frequency);
+        if (!joined) {
+            meshLeave();
+        }
+        return joined;
+    }
+
+    bool meshLeave() override {
+        if (mesh_index == 0) {
+            return true;
+        }
+        
+        Logger::getInstance().info("Leaving mesh on " + mesh_interface);
+        if (mesh_secured) {
+            // The SAE supplicant owns the mesh membership
+            std::ifstream pid_file(mesh_pid_path);
+            pid_t pid = 0;
+            if (pid_file >> pid && pid > 0) {
+                kill(pid, SIGTERM);
+            }
+            std::remove(mesh_pid_path);
+            mesh_secured = false;
+        } else {
+            struct nl_msg* msg = nlmsg_alloc();
+            if (msg) {
+                genlmsg_put(msg, 0, 0, nl80211_id, 0, 0, NL80211_CMD_LEAVE_MESH, 0);
+                nla_put_u32(msg, NL80211_ATTR_IFINDEX, mesh_index);
+                sendAndWaitForAck(msg);
+            }
+        }
+        
+        int index = mesh_index;
+        mesh_index = 0;
+        return deleteVirtualInterface(mesh_interface, index);
+    }
+
+    std::vector<MeshPeer> meshPeers() override {
+        std::vector<MeshPeer> peers;
+        if (mesh_index == 0) {
+            return peers;
+        }
+        
+        struct nl_msg* msg = nlmsg_alloc();
+        if (!msg) {
+            Logger::getInstance().error("Failed to allocate netlink message");
+            return peers;
+        }
+        genlmsg_put(msg, 0, 0, nl80211_id, 0, NLM_F_DUMP, NL80211_CMD_GET_STATION, 0);
+        nla_put_u32(msg, NL80211_ATTR_IFINDEX, mesh_index);
+        
+        struct station_data {
+            std::vector<MeshPeer>* peers;
+            bool done;
+        } cb_data = {&peers, false};
+        
+        auto callback = [](struct nl_msg* msg, void* arg) -> int {
+            auto* data = static_cast<station_data*>(arg);
+            struct nlattr* tb[NL80211_ATTR_MAX + 1];
+            struct genlmsghdr* gnlh = static_cast<genlmsghdr*>(nlmsg_data(nlmsg_hdr(msg)));
+            
+            nla_parse(tb, NL80211_ATTR_MAX, genlmsg_attrdata(gnlh, 0), genlmsg_attrlen(gnlh, 0), nullptr);
+            if (!tb[NL80211_ATTR_MAC] || !tb[NL80211_ATTR_STA_INFO]) {
+                return NL_SKIP;
+            }
+            
+            struct nlattr* info[NL80211_STA_INFO_MAX + 1];
+            static struct nla_policy station_policy[NL80211_STA_INFO_MAX + 1] = {};
+            nla_parse_nested(info, NL80211_STA_INFO_MAX, tb[NL80211_ATTR_STA_INFO], station_policy);
+            
+            MeshPeer peer;
+            const uint8_t* mac = static_cast<const uint8_t*>(nla_data(tb[NL80211_ATTR_MAC]));
+            char address[18];
+            snprintf(address, sizeof(address), "%02x:%02x:%02x:%02x:%02x:%02x",
+                     mac[0], mac[1], mac[2], mac[3], mac[4], mac[5]);
+            peer.address = address;
+            if (info[NL80211_STA_INFO_PLINK_STATE]) {
+                uint8_t state = nla_get_u8(info[NL80211_STA_INFO_PLINK_STATE]);
+                peer.linkState = state <= NL80211_PLINK_BLOCKED
+                    ? static_cast<MeshPeerLinkState>(state)
+                    : MeshPeerLinkState::UNKNOWN;
+            }
+            if (info[NL80211_STA_INFO_SIGNAL]) {
+                peer.signalStrength = static_cast<int8_t>(nla_get_u8(info[NL80211_STA_INFO_SIGNAL]));
+            }
+            if (info[NL80211_STA_INFO_INACTIVE_TIME]) {
+                peer.inactiveMs = static_cast<int>(nla_get_u32(info[NL80211_STA_INFO_INACTIVE_TIME]));
+            }
+            data->peers->push_back(peer);
+            return NL_SKIP;
+        };
+        auto finish = [](struct nl_msg*, void* arg) -> int {
+            static_cast<station_data*>(arg)->done = true;
+            return NL_STOP;
+        };
+        
+        struct nl_cb* cb = nl_cb_alloc(NL_CB_DEFAULT);
+        nl_cb_set(cb, NL_CB_VALID, NL_CB_CUSTOM, callback, &cb_data);
+        nl_cb_set(cb, NL_CB_FINISH, NL_CB_CUSTOM, finish, &cb_data);
+        
+        int ret = nl_send_auto(socket, msg);
+        nlmsg_free(msg);
+        if (ret < 0) {
+            Logger::getInstance().error("Failed to request mesh stations");
+            nl_cb_put(cb);
+            return peers;
+        }
+        
+        while (!cb_data.done && nl_recvmsgs(socket, cb) >= 0) {
+        }
+        nl_cb_put(cb);
+        return peers;
+    }
+
+    bool isNanSupported() const override {
+        // wpa_supplicant builds with NAN USD answer this instead of rejecting it as unknown
+        std::string reply = wpaCli("NAN_CANCEL_SUBSCRIBE subscribe_id=0");
+        return !reply.empty() && reply.find("UNKNOWN COMMAND") == std::string::npos;
+    }
+
+    int nanPublish(const NanPublishConfig& config) override {
+        if (!isCommandToken(config.serviceName) || !openSupplicantMonitor()) {
+            return 0;
+        }
+        
+        std::string command = "NAN_PUBLISH service_name=" + config.serviceName;
+        if (!config.serviceInfo.empty()) {
+            command += " ssi=" + toHex(config.serviceInfo);
+        }
+        if (!config.unsolicited) {
+            command += " unsolicited=0";
+        }
+        if (!config.solicited) {
+            command += " solicited=0";
+        }
+        if (config.ttlSeconds > 0) {
+            command += " ttl=" + std::to_string(config.ttlSeconds);
+        }
+        
+        int id = std::atoi(wpaCli(command).c_str());
+        if (id <= 0) {
+            Logger::getInstance().error("wpa_supplicant rejected the NAN publication of ", config.serviceName);
+            return 0;
+        }
+        nan_sessions[id] = true;
+        return id;
+    }
+
+    int nanSubscribe(const NanSubscribeConfig& config) override {
+        if (!isCommandToken(config.serviceName) || !openSupplicantMonitor()) {
+            return 0;
+        }
+        
+        std::string command = "NAN_SUBSCRIBE service_name=" + config.serviceName;
+        if (!config.serviceInfo.empty()) {
+            command += " ssi=" + toHex(config.serviceInfo);
+        }
+        if (config.active) {
+            command += " active=1";
+        }
+        if (config.ttlSeconds > 0) {
+            command += " ttl=" + std::to_string(config.ttlSeconds);
+        }
+        
+        int id = std::atoi(wpaCli(command).c_str());
+        if (id <= 0) {
+            Logger::getInstance().error("wpa_supplicant rejected the NAN subscription to ", config.serviceName);
+            return 0;
+        }
+        nan_sessions[id] = false;
+        return id;
+    }
+
+    bool nanCancel(int handle) override {
+        auto session = nan_sessions.find(handle);
+        if (session == nan_sessions.end()) {
+            return false;
+        }
+        
+        std::string command = session->second
+            ? "NAN_CANCEL_PUBLISH publish_id=" + std::to_string(handle)
+            : "NAN_CANCEL_SUBSCRIBE subscribe_id=" + std::to_string(handle);
+        bool cancelled = wpaCli(command).find("OK") != std::string::npos;
+        nan_sessions.erase(session);
+        if (nan_sessions.empty()) {
+            closeSupplicantMonitor();
+        }
+        return cancelled;
+    }
+
+    bool nanTransmit(int handle, int peerInstanceId, const std::string& peerAddress, const std::vector<uint8_t>& data) override {
+        if (nan_sessions.count(handle) == 0 || !isCommandToken(peerAddress)) {
+            return false;
+        }
+        
+        std::string command = "NAN_TRANSMIT handle=" + std::to_string(handle) +
+                              " req_instance_id=" + std::to_string(peerInstanceId) +
+                              " address=" + peerAddress;
+        if (!data.empty()) {
+            command += " ssi=" + toHex(data);
+        }
+        return wpaCli(command).find("OK") != std::string::npos;
+    }
+
+    bool nanNextEvent(int timeoutMs, NanEvent& event) override {
+        if (monitor_fd < 0) {
+            return false;
+        }
+        
+        auto deadline = std::chrono::steady_clock::now() + std::chrono::milliseconds(timeoutMs);
+        std::string message;
+        while (readSupplicantEvent(deadline, message)) {
+            std::istringstream fields(message);
+            std::string name, field;
+            fields >> name;
+            std::map<std::string, std::string> values;
+            while (fields >> field) {
+                size_t eq = field.find('=');
+                if (eq != std::string::npos) {
+                    values[field.substr(0, eq)] = field.substr(eq + 1);
+                }
+            }
+            
+            event = NanEvent();
+            event.peerAddress = values["address"];
+            event.serviceInfo = fromHex(values["ssi"]);
+            if (name == "NAN-DISCOVERY-RESULT") {
+                event.type = NanEventType::DISCOVERED;
+                event.handle = std::atoi(values["subscribe_id"].c_str());
+                event.peerInstanceId = std::atoi(values["publish_id"].c_str());
+            } else if (name == "NAN-REPLIED") {
+                event.type = NanEventType::DISCOVERED;
+                event.handle = std::atoi(values["publish_id"].c_str());
+                event.peerInstanceId = std::atoi(values["subscribe_id"].c_str());
+            } else if (name == "NAN-RECEIVE") {
+                event.type = NanEventType::RECEIVED;
+                event.handle = std::atoi(values["id"].c_str());
+                event.peerInstanceId = std::atoi(values["peer_instance_id"].c_str());
+            } else if (name == "NAN-PUBLISH-TERMINATED" || name == "NAN-SUBSCRIBE-TERMINATED") {
+                event.type = NanEventType::TERMINATED;
+                event.handle = std::atoi(values[name == "NAN-PUBLISH-TERMINATED" ? "publish_id" : "subscribe_id"].c_str());
+                nan_sessions.erase(event.handle);
+            } else {
+                continue;
+            }
+            return true;
+        }
+        return false;
+    }
+
+    bool p2pDisconnect() override {
+        if (p2p_group_interface.empty()) {
+            return true;
+        }
+        
+        bool removed = wpaCli("p2p_group_remove " + p2p_group_interface).find("OK") != std::string::npos;
+        p2p_group_interface.clear();
+        return removed;
+    }
+
+private:
+    struct nl_sock* socket = nullptr;
+    int nl80211_id = -1;
+    std::string interface_name;
+    int interface_index = -1;
+    std::string hostapd_conf_path;
+    std::string dnsmasq_conf_path;
+    std::string dnsmasq_lease_path;
+    std::atomic<bool> hotspot_active{false};
+    std::string shared_uplink;          // interface whose connection the hotspot shares, empty for none
+    bool shared_with_nft = false;
+    int ip_forward_before = 0;
+    std::string eap_key_path;
+    bool ephemeral_connection = false;
+    LinuxBackend linux_backend = LinuxBackend::AUTO;
+    
+    // Monitor mode capture state
+    std::string monitor_interface;
+    int monitor_index = 0;
+    int capture_fd = -1;
+    std::atomic<bool> capture_running{false};
+    struct nl_sock* hop_socket = nullptr;
+    std::thread capture_thread;
+    
+    // rtnetlink observer of link and address changes
+    int observer_fd = -1;
+    std::atomic<bool> observer_running{false};
+    std::thread observer_thread;
+    
+    // nl80211 multicast listener feeding the registered event callback
+    struct nl_sock* event_socket = nullptr;
+    std::atomic<bool> events_running{false};
+    std::thread event_thread;
+    WifiEventCallback event_callback;
+    bool probe_privacy = false;
+    std::string p2p_group_interface;
+    
+    // Target Wake Time agreement requested on the current association
+    bool twt_requested = false;
+    TwtConfig twt_config;
+    
+    // 802.11s mesh state
+    std::string mesh_interface;
+    int mesh_index = 0;
+    bool mesh_secured = false;
+    static constexpr const char* mesh_pid_path = "/tmp/wificpp_mesh.pid";
+    
+    // wpa_supplicant event monitor, attached while NAN sessions are active
+    int monitor_fd = -1;
+    std::string monitor_path;
+    std::map<int, bool> nan_sessions;   // id -> true for publications
+    static constexpr const char* passpoint_dir = "/var/lib/wificpp/passpoint";
+    
+    // How long wpa_supplicant may take to associate and authenticate
+    static constexpr int association_timeout_seconds = 15;
+    
+    bool findWifiInterface() {
+        // An explicitly chosen interface wins, e.g. a virtual radio in tests
+        const char* pinned = getenv("WIFICPP_INTERFACE");
+        bool found = pinned && *pinned;
+        if (found) {
+            interface_name = pinned;
+        }
+
+        // Otherwise take the first wireless interface, skipping monitor interfaces left over from captures
+        if (!found) {
+            queryNl80211(NL80211_CMD_GET_INTERFACE, NLM_F_DUMP, static_cast<enum nl80211_attrs>(0), 0,
+                         [&](struct nlattr** tb) {
+                if (found || !tb[NL80211_ATTR_IFNAME] ||
+                    (tb[NL80211_ATTR_IFTYPE] && nla_get_u32(tb[NL80211_ATTR_IFTYPE]) == NL80211_IFTYPE_MONITOR)) {
+                    return;
+                }
+                interface_name = nla_get_string(tb[NL80211_ATTR_IFNAME]);
+                found = true;
+            });
+        }
+        
+        if (found) {
+            interface_index = interfaceIndex(interface_name);
+            return interface_index > 0;
+        }
+        
+        return false;
+    }
+    
+    static int channelToFrequency(int channel) {
+        if (channel == 14) {
+            return 2484;
+        } else if (channel >= 1 && channel <= 13) {
+            return 2407 + channel * 5;
+        } else if (channel >= 32 && channel <= 177) {
+            return 5000 + channel * 5;
+        }
+        return 0;
+    }
+    
+    static int interfaceIndex(const std::string& iface) {
+        int sock = ::socket(AF_INET, SOCK_DGRAM, 0);
+        if (sock < 0) {
+            return 0;
+        }
+        
+        struct ifreq ifr;
+        memset(&ifr, 0, sizeof(ifr));
+        strncpy(ifr.ifr_name, iface.c_str(), IFNAMSIZ - 1);
+        int index = ioctl(sock, SIOCGIFINDEX, &ifr) >= 0 ? ifr.ifr_ifindex : 0;
+        close(sock);
+        return index;
+    }
+    
+    // Send an nl80211 request and wait for the kernel to acknowledge it
+    bool sendAndWaitForAck(struct nl_msg* msg, struct nl_sock* sock = nullptr) {
+        if (!sock) {
+            sock = socket;
+        }
+        int ret = nl_send_auto(sock, msg);
+        nlmsg_free(msg);
+        return ret >= 0 && nl_wait_for_ack(sock) >= 0;
+    }
+    
+    // Tune the monitor interface to a channel
+    bool setMonitorChannel(int channel, struct nl_sock* sock = nullptr) {
+        int frequency = channelToFrequency(channel);
+        if (frequency == 0) {
+            return false;
+        }
+        
+        struct nl_msg* msg = nlmsg_alloc();
+        if (!msg) {
+            return false;
+        }
+        genlmsg_put(msg, 0, 0, nl80211_id, 0, 0, NL80211_CMD_SET_WIPHY, 0);
+        nla_put_u32(msg, NL80211_ATTR_IFINDEX, monitor_index);
+        nla_put_u32(msg, NL80211_ATTR_WIPHY_FREQ, frequency);
+        return sendAndWaitForAck(msg, sock);
+    }
+    
+    bool deleteMonitorInterface() {
+        int index = monitor_index;
+        monitor_index = 0;
+        return deleteVirtualInterface(monitor_interface, index);
+    }
+    
+    // Add a virtual interface of the given type on our radio, returns its index or 0
+    int addVirtualInterface(const std::string& name, enum nl80211_iftype type) {
+        struct nl_msg* msg = nlmsg_alloc();
+        if (!msg) {
+            Logger::getInstance().error("Failed to allocate netlink message");
+            return 0;
+        }
+        genlmsg_put(msg, 0, 0, nl80211_id, 0, 0, NL80211_CMD_NEW_INTERFACE, 0);
+        nla_put_u32(msg, NL80211_ATTR_IFINDEX, interface_index);
+        nla_put_string(msg, NL80211_ATTR_IFNAME, name.c_str());
+        nla_put_u32(msg, NL80211_ATTR_IFTYPE, type);
+        if (!sendAndWaitForAck(msg)) {
+            Logger::getInstance().error("Failed to create interface " + name);
+            return 0;
+        }
+        return interfaceIndex(name);
+    }
+    
+    bool deleteVirtualInterface(const std::string& name, int index) {
+        if (index == 0) {
+            return true;
+        }
+        
+        struct nl_msg* msg = nlmsg_alloc();
+        if (!msg) {
+            return false;
+        }
+        genlmsg_put(msg, 0, 0, nl80211_id, 0, 0, NL80211_CMD_DEL_INTERFACE, 0);
+        nla_put_u32(msg, NL80211_ATTR_IFINDEX, index);
+        if (!sendAndWaitForAck(msg)) {
+            Logger::getInstance().warning("Failed to remove interface " + name);
+            return false;
+        }
+        return true;
+    }
+    
+    // Report rtnetlink link and address messages about our interface until the observer is stopped
+    void observerLoop(const NetworkChangeCallback& callback) {
+        std::vector<char> buffer(16384);
+        while (observer_running) {
+            struct pollfd pfd = {observer_fd, POLLIN, 0};
+            if (poll(&pfd, 1, 200) <= 0) {
+                continue;
+            }
+            ssize_t received = recv(observer_fd, buffer.data(), buffer.size(), 0);
+            if (received <= 0) {
+                continue;
+            }
+            
+            bool relevant = false;
+            int remaining = static_cast<int>(received);
+            for (auto* header = reinterpret_cast<struct nlmsghdr*>(buffer.data()); NLMSG_OK(header, remaining);
+                 header = NLMSG_NEXT(header, remaining)) {
+                if (header->nlmsg_type == RTM_NEWLINK || header->nlmsg_type == RTM_DELLINK) {
+                    auto* info = static_cast<struct ifinfomsg*>(NLMSG_DATA(header));
+                    relevant = relevant || info->ifi_index == interface_index;
+                } else if (header->nlmsg_type == RTM_NEWADDR || header->nlmsg_type == RTM_DELADDR) {
+                    auto* info = static_cast<struct ifaddrmsg*>(NLMSG_DATA(header));
+                    relevant = relevant || static_cast<int>(info->ifa_index) == interface_index;
+                }
+            }
+            // One notification per batch, the receiver re-reads the state anyway
+            if (relevant) {
+                callback();
+            }
+        }
+    }
+    
+    // Configure the connection quality monitor to report crossings of threshold dBm
+    bool setSignalThreshold(int threshold) {
+        struct nl_msg* msg = nlmsg_alloc();
+        if (!msg) {
+            return false;
+        }
+        genlmsg_put(msg, 0, 0, nl80211_id, 0, 0, NL80211_CMD_SET_CQM, 0);
+        nla_put_u32(msg, NL80211_ATTR_IFINDEX, interface_index);
+        struct nlattr* cqm = nla_nest_start(msg, NL80211_ATTR_CQM);
+        nla_put_u32(msg, NL80211_ATTR_CQM_RSSI_THOLD, static_cast<uint32_t>(threshold));
+        nla_put_u32(msg, NL80211_ATTR_CQM_RSSI_HYST, 4);
+        nla_nest_end(msg, cqm);
+        return sendAndWaitForAck(msg);
+    }
+    
+    // Dispatch nl80211 multicast events until the callback is unregistered
+    // Block until the interface reports the end of a scan on a socket in the "scan" group
+    ScanOutcome waitForScanDone(struct nl_sock* sock, int timeout_ms) {
+        struct scan_wait {
+            int ifindex;
+            bool done;
+            bool aborted;
+        } wait_data = {interface_index, false, false};
+        
+        struct nl_cb* cb = nl_cb_alloc(NL_CB_DEFAULT);
+        nl_cb_set(cb, NL_CB_VALID, NL_CB_CUSTOM, [](struct nl_msg* msg, void* arg) -> int {
+            auto* data = static_cast<scan_wait*>(arg);
+            struct genlmsghdr* gnlh = static_cast<genlmsghdr*>(nlmsg_data(nlmsg_hdr(msg)));
+            struct nlattr* tb[NL80211_ATTR_MAX + 1];
+            nla_parse(tb, NL80211_ATTR_MAX, genlmsg_attrdata(gnlh, 0), genlmsg_attrlen(gnlh, 0), nullptr);
+            bool ours = tb[NL80211_ATTR_IFINDEX] && static_cast<int>(nla_get_u32(tb[NL80211_ATTR_IFINDEX])) == data->ifindex;
+            if (ours && (gnlh->cmd == NL80211_CMD_NEW_SCAN_RESULTS || gnlh->cmd == NL80211_CMD_SCAN_ABORTED)) {
+                data->done = true;
+                data->aborted = gnlh->cmd == NL80211_CMD_SCAN_ABORTED;
+            }
+            return NL_OK;
+        }, &wait_data);
+        
+        int fd = nl_socket_get_fd(sock);
+        auto deadline = std::chrono::steady_clock::now() + std::chrono::milliseconds(timeout_ms);
+        while (!wait_data.done && !cancelled() && std::chrono::steady_clock::now() < deadline) {
+            struct pollfd pfd = {fd, POLLIN, 0};
+            if (poll(&pfd, 1, 200) > 0) {
+                nl_recvmsgs(sock, cb);
+            }
+        }
+        nl_cb_put(cb);
+        if (!wait_data.done && cancelled()) {
+            return ScanOutcome::CANCELLED;
+        }
+        if (!wait_data.done) {
+            return ScanOutcome::TIMED_OUT;
+        }
+        return wait_data.aborted ? ScanOutcome::ABORTED : ScanOutcome::COMPLETED;
+    }
+    
+    void eventLoop() {
+        struct nl_cb* cb = nl_cb_alloc(NL_CB_DEFAULT);
+        nl_cb_set(cb, NL_CB_VALID, NL_CB_CUSTOM, [](struct nl_msg* msg, void* arg) -> int {
+            static_cast<LinuxWifiImpl*>(arg)->handleEvent(msg);
+            return NL_OK;
+        }, this);
+        
+        int fd = nl_socket_get_fd(event_socket);
+        while (events_running) {
+            struct pollfd pfd = {fd, POLLIN, 0};
+            if (poll(&pfd, 1, 200) <= 0) {
+                continue;
+            }
+            nl_recvmsgs(event_socket, cb);
+        }
+        nl_cb_put(cb);
+    }
+    
+    void handleEvent(struct nl_msg* msg) {
+        struct genlmsghdr* gnlh = static_cast<genlmsghdr*>(nlmsg_data(nlmsg_hdr(msg)));
+        struct nlattr* tb[NL80211_ATTR_MAX + 1];
+        nla_parse(tb, NL80211_ATTR_MAX, genlmsg_attrdata(gnlh, 0), genlmsg_attrlen(gnlh, 0), nullptr);
+        if (!tb[NL80211_ATTR_IFINDEX] || static_cast<int>(nla_get_u32(tb[NL80211_ATTR_IFINDEX])) != interface_index) {
+            return;
+        }
+        
+        WifiEventInfo event;
+        switch (gnlh->cmd) {
+            case NL80211_CMD_CONNECT:
+                // Failed attempts are reported by the connect call itself
+                if (tb[NL80211_ATTR_STATUS_CODE] && nla_get_u16(tb[NL80211_ATTR_STATUS_CODE]) != 0) {
+                    return;
+                }
+                event.type = WifiEventType::CONNECTED;
+                event.bssid = macAttribute(tb[NL80211_ATTR_MAC]);
+                event.ssid = ssidFromIes(tb[NL80211_ATTR_REQ_IE]);
+                break;
+            case NL80211_CMD_ROAM:
+                event.type = WifiEventType::ROAMED;
+                event.bssid = macAttribute(tb[NL80211_ATTR_MAC]);
+                break;
+            case NL80211_CMD_DISCONNECT:
+                event.type = WifiEventType::CONNECTION_LOST;
+                break;
+            case NL80211_CMD_NEW_SCAN_RESULTS:
+                event.type = WifiEventType::SCAN_COMPLETE;
+                break;
+            case NL80211_CMD_NOTIFY_CQM: {
+                if (!tb[NL80211_ATTR_CQM]) {
+                    return;
+                }
+                struct nlattr* cqm[NL80211_ATTR_CQM_MAX + 1];
+                nla_parse_nested(cqm, NL80211_ATTR_CQM_MAX, tb[NL80211_ATTR_CQM], nullptr);
+                if (!cqm[NL80211_ATTR_CQM_RSSI_THRESHOLD_EVENT]) {
+                    return;
+                }
+                event.type = WifiEventType::SIGNAL_CHANGED;
+                event.signalLow = nla_get_u32(cqm[NL80211_ATTR_CQM_RSSI_THRESHOLD_EVENT]) ==
+                                  NL80211_CQM_RSSI_THRESHOLD_EVENT_LOW;
+                if (cqm[NL80211_ATTR_CQM_RSSI_LEVEL]) {
+                    event.signalStrength = static_cast<int32_t>(nla_get_u32(cqm[NL80211_ATTR_CQM_RSSI_LEVEL]));
+                }
+                break;
+            }
+            case NL80211_CMD_NEW_STATION:
+                // In station mode the access point itself is reported as a new station
+                if (!hotspot_active) {
+                    return;
+                }
+                event.type = WifiEventType::HOTSPOT_CLIENT_JOINED;
+                event.clientAddress = macAttribute(tb[NL80211_ATTR_MAC]);
+                break;
+            case NL80211_CMD_DEL_STATION:
+                if (!hotspot_active) {
+                    return;
+                }
+                event.type = WifiEventType::HOTSPOT_CLIENT_LEFT;
+                event.clientAddress = macAttribute(tb[NL80211_ATTR_MAC]);
+                break;
+            default:
+                return;
+        }
+        event_callback(event);
+    }
+    
+    static std::string macAttribute(struct nlattr* attr) {
+        if (!attr || nla_len(attr) < 6) {
+            return "";
+        }
+        const uint8_t* mac = static_cast<const uint8_t*>(nla_data(attr));
+        char address[18];
+        snprintf(address, sizeof(address), "%02x:%02x:%02x:%02x:%02x:%02x",
+                 mac[0], mac[1], mac[2], mac[3], mac[4], mac[5]);
+        return address;
+    }
+    
+    // SSID element of the association request
+    static std::string ssidFromIes(struct nlattr* attr) {
+        if (!attr) {
+            return "";
+        }
+        const uint8_t* ies = static_cast<const uint8_t*>(nla_data(attr));
+        int length = nla_len(attr);
+        for (int pos = 0; pos + 2 <= length && pos + 2 + ies[pos + 1] <= length; pos += 2 + ies[pos + 1]) {
+            if (ies[pos] == 0) {
+                return std::string(reinterpret_cast<const char*>(ies + pos + 2), ies[pos + 1]);
+            }
+        }
+        return "";
+    }
+    
+    // Read frames from the packet socket until the capture is stopped
+    void captureLoop(const CaptureConfig& config, const FrameCallback& callback) {
+        std::vector<uint8_t> buffer(65536);
+        size_t hop_index = 0;
+        auto next_hop = std::chrono::steady_clock::now();
+        
+        while (capture_running) {
+            int timeout_ms = 200;
+            if (!config.hopChannels.empty()) {
+                auto now = std::chrono::steady_clock::now();
+                if (now >= next_hop) {
+                    int channel = config.hopChannels[hop_index];
+                    if (!setMonitorChannel(channel, hop_socket)) {
+                        Logger::getInstance().debug("Failed to hop to channel ", channel);
+                    }
+                    hop_index = (hop_index + 1) % config.hopChannels.size();
+                    next_hop = now + std::chrono::milliseconds(config.dwellMs);
+                }
+                auto remaining = std::chrono::duration_cast<std::chrono::milliseconds>(next_hop - now).count();
+                timeout_ms = static_cast<int>(std::max<long long>(1, std::min<long long>(timeout_ms, remaining)));
+            }
+            
+            struct pollfd pfd = {capture_fd, POLLIN, 0};
+            if (poll(&pfd, 1, timeout_ms) <= 0) {
+                continue;
+            }
+            
+            ssize_t len = recv(capture_fd, buffer.data(), buffer.size(), 0);
+            if (len < 8) {
+                continue;
+            }
+            
+            // Frame control follows the radiotap header
+            size_t radiotap_len = buffer[2] | (buffer[3] << 8);
+            if (radiotap_len + 2 > static_cast<size_t>(len)) {
+                continue;
+            }
+            int type = (buffer[radiotap_len] >> 2) & 0x3;
+            if ((type == 0 && !config.includeManagement) ||
+                (type == 1 && !config.includeControl) ||
+                (type == 2 && !config.includeData)) {
+                continue;
+            }
+            
+            uint64_t timestamp = std::chrono::duration_cast<std::chrono::microseconds>(
+                std::chrono::system_clock::now().time_since_epoch()).count();
+            callback(buffer.data(), static_cast<size_t>(len), timestamp);
+        }
+    }
+    
+    // Helper methods for native implementation
+    
+    // Restart wpa_supplicant with the given configuration and run DHCP unless an IPv4 address is given
+    bool startSupplicant(const std::string& config_path, int timeout_seconds = association_timeout_seconds,
+                         const StaticIpConfig& addressing = StaticIpConfig()) {
+        // Let wpa_supplicant join Passpoint hotspots with the installed credentials
+        appendPasspointCredentials(config_path);
+        
+        if (!launchSupplicant(config_path)) {
+            return false;
+        }
+        
+        ErrorCode association = waitForAssociation(timeout_seconds);
+        if (association == ErrorCode::CANCELLED) {
+            // Stop the supplicant from continuing the attempt in the background
+            wpaCli("disconnect");
+            Logger::getInstance().info("The connection was cancelled");
+            std::remove(config_path.c_str());
+            return fail(association);
+        }
+        if (association != ErrorCode::NONE) {
+            Logger::getInstance().error(association == ErrorCode::INVALID_CREDENTIALS
+                                            ? "Authentication was rejected by the network"
+                                            : "Association did not complete in time");
+            std::remove(config_path.c_str());
+            return fail(association);
+        }
+        
+        // Clean up temporary file
+        std::remove(config_path.c_str());
+        return configureAddressing(addressing);
+    }
+
+    // Run DHCP unless an IPv4 address is given, apply any static addressing and check for an address
+    bool configureAddressing(const StaticIpConfig& addressing) {
+        // Start DHCP client using native implementation
+        if (addressing.ipv4Address.empty() && !configureDhcpClient(interface_name)) {
+            Logger::getInstance().warning("DHCP configuration failed, but connection might still be established");
+        }
+        
+        if (!addressing.empty() && !applyStaticIp(addressing)) {
+            return fail(ErrorCode::OPERATION_FAILED);
+        }
+        
+        // Verify connection by checking for IP address
+        if (!hasIpAddress(interface_name)) {
+            return fail(ErrorCode::TIMEOUT);
+        }
         return true;
     }
 
-    bool stopHotspot() override {
-        Logger::getInstance().info("Stopping hotspot");
+    // Wait for wpa_supplicant to complete the association and key exchange.
+    // Returns NONE on success, INVALID_CREDENTIALS if authentication failed,
+    // TIMEOUT if the network was never joined and CANCELLED if the wait was cancelled.
+    ErrorCode waitForAssociation(int timeout_seconds) const {
+        bool authenticating = false;
+        for (int elapsed = 0; elapsed < timeout_seconds; elapsed++) {
+            std::string state = wpaCliValues("status")["wpa_state"];
+            if (state == "COMPLETED") {
+                return ErrorCode::NONE;
+            }
+            authenticating |= state == "4WAY_HANDSHAKE" || state == "GROUP_HANDSHAKE";
+            // wpa_supplicant temporarily disables a network after a wrong key or EAP failure
+            if (wpaCli("list_networks").find("[TEMP-DISABLED]") != std::string::npos) {
+                return ErrorCode::INVALID_CREDENTIALS;
+            }
+            if (!sleepUnlessCancelled(1000)) {
+                return ErrorCode::CANCELLED;
+            }
+        }
+        return authenticating ? ErrorCode::INVALID_CREDENTIALS : ErrorCode::TIMEOUT;
+    }
+
+    // Error for a system service that could not be started
+    ErrorCode serviceFailure() const {
+        return geteuid() == 0 ? ErrorCode::DRIVER_ERROR : ErrorCode::PERMISSION_DENIED;
+    }
+
+    // The backend selected with setLinuxBackend, AUTO resolved
+    LinuxBackend activeBackend() const {
+        if (linux_backend != LinuxBackend::AUTO) {
+            return linux_backend;
+        }
+        // A supplicant of our own would fight NetworkManager's over the interface
+        return managedByNetworkManager() ? LinuxBackend::NETWORK_MANAGER : LinuxBackend::WPA_CLI;
+    }
+
+    // Whether NetworkManager runs and manages our interface
+    bool managedByNetworkManager() const {
+        std::string output;
+        if (!nmcli({"-t", "-f", "DEVICE,STATE", "device"}, &output)) {
+            return false;
+        }
+        std::istringstream lines(output);
+        std::string line;
+        std::string prefix = interface_name + ":";
+        while (std::getline(lines, line)) {
+            if (line.compare(0, prefix.size(), prefix) == 0) {
+                return line.substr(prefix.size()) != "unmanaged";
+            }
+        }
+        return false;
+    }
+
+    // A station interface has a station entry, its access point, only while associated
+    bool isAssociated() const {
+        bool associated = false;
+        queryNl80211(NL80211_CMD_GET_STATION, NLM_F_DUMP, NL80211_ATTR_IFINDEX, interface_index,
+                     [&](struct nlattr** tb) {
+            associated = associated || tb[NL80211_ATTR_STA_INFO] != nullptr;
+        });
+        return associated;
+    }
+
+    // Let NetworkManager join the network, which saves it as a connection profile named after the SSID
+    bool connectWithNetworkManager(const std::string& ssid, const std::string& password, const ConnectOptions& options) {
+        if (options.ephemeral || !options.frequencies.empty() || !options.staticIp.empty()) {
+            ephemeral_connection = false;
+            Logger::getInstance().warning("Ephemeral connections, frequency restrictions and static addressing "
+                                          "are not supported with the NetworkManager backend");
+            return fail(ErrorCode::UNSUPPORTED);
+        }
         
-        if (!hotspot_active) {
-            return true;  // Nothing to do
+        int timeout_seconds = options.timeoutSeconds > 0 ? options.timeoutSeconds : association_timeout_seconds;
+        std::vector<std::string> args = {"--wait", std::to_string(timeout_seconds), "device", "wifi", "connect", ssid};
+        if (!password.empty()) {
+            args.insert(args.end(), {"password", password});
+        }
+        args.insert(args.end(), {"ifname", interface_name});
+        if (!options.bssid.empty()) {
+            args.insert(args.end(), {"bssid", options.bssid});
+        }
+        if (options.hidden) {
+            args.insert(args.end(), {"hidden", "yes"});
         }
         
-        // Stop hostapd and dnsmasq
-        system("killall -q hostapd");
-        system("killall -q dnsmasq");
+        int exit_code = 0;
+        if (nmcli(args, nullptr, &exit_code)) {
+            return true;
+        }
+        if (cancelled()) {
+            // Interrupting nmcli leaves the activation running in NetworkManager; not through nmcli(),
+            // which would interrupt this call as well
+            system(("nmcli device disconnect " + interface_name + " > /dev/null 2>&1").c_str());
+            Logger::getInstance().info("The connection to " + ssid + " was cancelled");
+            return fail(ErrorCode::CANCELLED);
+        }
+        Logger::getInstance().error("NetworkManager could not connect to " + ssid + " (nmcli exit code " +
+                                    std::to_string(exit_code) + ")");
+        switch (exit_code) {
+            case 3:
+                return fail(ErrorCode::TIMEOUT);
+            case 4:
+                // Activation failed, for secured networks almost always because of the key
+                return fail(password.empty() ? ErrorCode::OPERATION_FAILED : ErrorCode::INVALID_CREDENTIALS);
+            default:
+                return fail(ErrorCode::OPERATION_FAILED);
+        }
+    }
+
+    // Join an open network with NL80211_CMD_CONNECT, letting the driver associate on its own
+    bool connectWithNl80211(const std::string& ssid, const std::string& password, const ConnectOptions& options) {
+        if (!password.empty()) {
+            Logger::getInstance().warning("The nl80211 backend only joins open networks, "
+                                          "the key exchange needs wpa_supplicant");
+            return fail(ErrorCode::UNSUPPORTED);
+        }
+        unsigned char mac[6];
+        if (!options.bssid.empty() &&
+            sscanf(options.bssid.c_str(), "%hhx:%hhx:%hhx:%hhx:%hhx:%hhx",
+                   &mac[0], &mac[1], &mac[2], &mac[3], &mac[4], &mac[5]) != 6) {
+            Logger::getInstance().error("Invalid BSSID " + options.bssid);
+            return fail(ErrorCode::OPERATION_FAILED);
+        }
         
-        // Clean up configuration files
-        if (!hostapd_conf_path.empty()) {
-            std::remove(hostapd_conf_path.c_str());
-            hostapd_conf_path.clear();
+        // The interface must not be driven by a supplicant at the same time
+        terminateProcess("wpa_supplicant");
+        setInterfaceState(interface_name, true);
+        
+        struct nl_msg* msg = nlmsg_alloc();
+        if (!msg) {
+            return fail(ErrorCode::OPERATION_FAILED);
+        }
+        genlmsg_put(msg, 0, 0, nl80211_id, 0, 0, NL80211_CMD_CONNECT, 0);
+        nla_put_u32(msg, NL80211_ATTR_IFINDEX, interface_index);
+        nla_put(msg, NL80211_ATTR_SSID, static_cast<int>(ssid.size()), ssid.data());
+        nla_put_u32(msg, NL80211_ATTR_AUTH_TYPE, NL80211_AUTHTYPE_OPEN_SYSTEM);
+        if (!options.bssid.empty()) {
+            nla_put(msg, NL80211_ATTR_MAC, sizeof(mac), mac);
+        }
+        // The driver accepts a single frequency hint; without one it picks the channel itself
+        if (options.frequencies.size() == 1) {
+            nla_put_u32(msg, NL80211_ATTR_WIPHY_FREQ, options.frequencies[0]);
+        }
+        if (!sendAndWaitForAck(msg)) {
+            Logger::getInstance().error("The driver rejected the connect request on " + interface_name);
+            return fail(ErrorCode::DRIVER_ERROR);
         }
         
-        if (!dnsmasq_conf_path.empty()) {
-            std::remove(dnsmasq_conf_path.c_str());
-            dnsmasq_conf_path.clear();
+        int timeout_seconds = options.timeoutSeconds > 0 ? options.timeoutSeconds : association_timeout_seconds;
+        for (int elapsed = 0; !isAssociated(); elapsed++) {
+            if (elapsed >= timeout_seconds) {
+                Logger::getInstance().error("Association did not complete in time");
+                disconnectWithNl80211();
+                return fail(ErrorCode::TIMEOUT);
+            }
+            if (!sleepUnlessCancelled(1000)) {
+                Logger::getInstance().info("The connection was cancelled");
+                disconnectWithNl80211();
+                return fail(ErrorCode::CANCELLED);
+            }
+        }
+        return configureAddressing(options.staticIp);
+    }
+
+    // Leave the current network with NL80211_CMD_DISCONNECT
+    void disconnectWithNl80211() {
+        struct nl_msg* msg = nlmsg_alloc();
+        if (!msg) {
+            return;
+        }
+        genlmsg_put(msg, 0, 0, nl80211_id, 0, 0, NL80211_CMD_DISCONNECT, 0);
+        nla_put_u32(msg, NL80211_ATTR_IFINDEX, interface_index);
+        nla_put_u16(msg, NL80211_ATTR_REASON_CODE, 3);  // deauthenticated because the station is leaving
+        // Rejected with ENOTCONN when not connected, which is fine
+        sendAndWaitForAck(msg);
+    }
+
+    // Restart wpa_supplicant in the background with the given configuration
+    bool launchSupplicant(const std::string& config_path) {
+        // Stop existing wpa_supplicant using native process termination
+        terminateProcess("wpa_supplicant");
+        twt_requested = false;
+        
+        if (probe_privacy) {
+            // Random MAC per network, while scanning and for ANQP queries
+            std::ofstream config_file(config_path, std::ios::app);
+            config_file << "mac_addr=1\n";
+            config_file << "preassoc_mac_addr=1\n";
+            config_file << "gas_rand_mac_addr=1\n";
+        }
+        
+        // Start wpa_supplicant with our configuration using fork/exec
+        pid_t pid = fork();
+        if (pid == 0) {
+            // Child process
+            execl("/sbin/wpa_supplicant", "wpa_supplicant", "-B", "-i", 
+                  interface_name.c_str(), "-c", config_path.c_str(), NULL);
+            exit(1); // Exit if exec fails
+        } else if (pid < 0) {
+            Logger::getInstance().error("Failed to fork process for wpa_supplicant");
+            std::remove(config_path.c_str());
+            return false;
+        }
+        
+        // With -B the process exits once the daemon is running, or with an error
+        int status = 0;
+        waitpid(pid, &status, 0);
+        if (!WIFEXITED(status) || WEXITSTATUS(status) != 0) {
+            Logger::getInstance().error("wpa_supplicant failed to start on " + interface_name);
+            std::remove(config_path.c_str());
+            return fail(serviceFailure());
+        }
+        
+        // Give wpa_supplicant time to open its control interface
+        sleep(1);
+        return true;
+    }
+    
+    // Run nmcli with the given arguments, without a shell, optionally collecting its output and exit code
+    bool nmcli(const std::vector<std::string>& args, std::string* output = nullptr, int* exit_code = nullptr) const {
+        int fds[2];
+        if (pipe(fds) < 0) {
+            return false;
+        }
+        pid_t pid = fork();
+        if (pid == 0) {
+            dup2(fds[1], STDOUT_FILENO);
+            close(fds[0]);
+            close(fds[1]);
+            int devnull = open("/dev/null", O_WRONLY);
+            if (devnull >= 0) {
+                dup2(devnull, STDERR_FILENO);
+            }
+            std::vector<char*> argv;
+            argv.push_back(const_cast<char*>("nmcli"));
+            for (const auto& arg : args) {
+                argv.push_back(const_cast<char*>(arg.c_str()));
+            }
+            argv.push_back(nullptr);
+            // Untranslated output, states and messages are parsed
+            setenv("LC_ALL", "C", 1);
+            execvp("nmcli", argv.data());
+            _exit(127);
+        } else if (pid < 0) {
+            close(fds[0]);
+            close(fds[1]);
+            return false;
+        }
+        
+        close(fds[1]);
+        char buffer[256];
+        bool interrupted = false;
+        while (true) {
+            struct pollfd pfd = {fds[0], POLLIN, 0};
+            if (poll(&pfd, 1, 200) == 0) {
+                // nmcli --wait blocks for the whole activation
+                if (cancelled() && !interrupted) {
+                    kill(pid, SIGTERM);
+                    interrupted = true;
+                }
+                continue;
+            }
+            ssize_t n = read(fds[0], buffer, sizeof(buffer));
+            if (n <= 0) {
+                break;
+            }
+            if (output) {
+                output->append(buffer, static_cast<size_t>(n));
+            }
+        }
+        close(fds[0]);
+        int status = 0;
+        waitpid(pid, &status, 0);
+        if (exit_code) {
+            *exit_code = WIFEXITED(status) ? WEXITSTATUS(status) : -1;
+        }
+        return WIFEXITED(status) && WEXITSTATUS(status) == 0;
+    }
+    
+    // Names of the NetworkManager WiFi connection profiles
+    std::vector<std::string> wifiProfiles() const {
+        std::vector<std::string> profiles;
+        for (const auto& fields : wifiConnections("NAME,TYPE")) {
+            profiles.push_back(fields[0]);
+        }
+        return profiles;
+    }
+    
+    // Terse fields of the NetworkManager WiFi connections, TYPE must be the second field
+    std::vector<std::vector<std::string>> wifiConnections(const std::string& columns) const {
+        std::vector<std::vector<std::string>> connections;
+        std::string output;
+        if (!nmcli({"-t", "-f", columns, "connection", "show"}, &output)) {
+            return connections;
+        }
+        std::istringstream lines(output);
+        std::string line;
+        while (std::getline(lines, line)) {
+            // Terse output escapes ':' and '\' in values with a backslash
+            std::vector<std::string> fields(1);
+            for (size_t i = 0; i < line.size(); i++) {
+                if (line[i] == '\\' && i + 1 < line.size()) {
+                    fields.back() += line[++i];
+                } else if (line[i] == ':') {
+                    fields.emplace_back();
+                } else {
+                    fields.back() += line[i];
+                }
+            }
+            if (fields.size() >= 2 && fields[1] == "802-11-wireless") {
+                connections.push_back(fields);
+            }
+        }
+        return connections;
+    }
+    
+    // Run a wpa_cli command against our interface and return its output
+    std::string wpaCli(const std::string& args) const {
+        return wpaCli(interface_name, args);
+    }
+    
+    // Run a wpa_cli command against the given interface and return its output; the command
+    // is split on spaces into arguments and run without a shell
+    std::string wpaCli(const std::string& iface, const std::string& args) const {
+        std::vector<std::string> words{"wpa_cli", "-i", iface};
+        std::istringstream command(args);
+        std::string word;
+        while (command >> word) {
+            words.push_back(word);
+        }
+        
+        std::string output;
+        int fds[2];
+        if (pipe(fds) < 0) {
+            return output;
+        }
+        pid_t pid = fork();
+        if (pid == 0) {
+            dup2(fds[1], STDOUT_FILENO);
+            close(fds[0]);
+            close(fds[1]);
+            int devnull = open("/dev/null", O_WRONLY);
+            if (devnull >= 0) {
+                dup2(devnull, STDERR_FILENO);
+            }
+            std::vector<char*> argv;
+            for (const auto& arg : words) {
+                argv.push_back(const_cast<char*>(arg.c_str()));
+            }
+            argv.push_back(nullptr);
+            execvp("wpa_cli", argv.data());
+            _exit(127);
+        } else if (pid < 0) {
+            close(fds[0]);
+            close(fds[1]);
+            return output;
+        }
+        
+        close(fds[1]);
+        char buffer[256];
+        ssize_t n;
+        while ((n = read(fds[0], buffer, sizeof(buffer))) > 0) {
+            output.append(buffer, static_cast<size_t>(n));
+        }
+        close(fds[0]);
+        waitpid(pid, nullptr, 0);
+        return output;
+    }
+    
+    // Run a wpa_cli command that prints key=value lines and collect them
+    std::map<std::string, std::string> wpaCliValues(const std::string& args) const {
+        return wpaCliValues(interface_name, args);
+    }
+    
+    std::map<std::string, std::string> wpaCliValues(const std::string& iface, const std::string& args) const {
+        std::map<std::string, std::string> values;
+        std::istringstream output(wpaCli(iface, args));
+        std::string line;
+        while (std::getline(output, line)) {
+            size_t eq = line.find('=');
+            if (eq != std::string::npos) {
+                values[line.substr(0, eq)] = line.substr(eq + 1);
+            }
+        }
+        return values;
+    }
+    
+    // Poll wpa_supplicant until it reports the given wpa_state or the timeout expires
+    bool waitForSupplicantState(const std::string& state, int timeout_seconds) const {
+        for (int elapsed = 0; elapsed < timeout_seconds; elapsed++) {
+            if (wpaCli("status").find("wpa_state=" + state) != std::string::npos) {
+                return true;
+            }
+            sleep(1);
+        }
+        return false;
+    }
+    
+    // Join an unsecured mesh directly through nl80211
+    bool joinOpenMesh(const std::string& mesh_id, int frequency) {
+        struct nl_msg* msg = nlmsg_alloc();
+        if (!msg) {
+            return false;
+        }
+        genlmsg_put(msg, 0, 0, nl80211_id, 0, 0, NL80211_CMD_JOIN_MESH, 0);
+        nla_put_u32(msg, NL80211_ATTR_IFINDEX, mesh_index);
+        nla_put(msg, NL80211_ATTR_MESH_ID, static_cast<int>(mesh_id.size()), mesh_id.data());
+        if (frequency != 0) {
+            nla_put_u32(msg, NL80211_ATTR_WIPHY_FREQ, frequency);
+        }
+        if (!sendAndWaitForAck(msg)) {
+            Logger::getInstance().error("The driver rejected joining mesh " + mesh_id);
+            return false;
+        }
+        return true;
+    }
+    
+    // Join an SAE secured mesh through a dedicated wpa_supplicant on the mesh interface
+    bool joinSecureMesh(const MeshConfig& config, int frequency) {
+        std::string config_path = "/tmp/wificpp_mesh.conf";
+        std::ofstream config_file(config_path);
+        if (!config_file) {
+            Logger::getInstance().error("Failed to create temporary configuration file");
+            return false;
+        }
+        config_file << "ctrl_interface=/var/run/wpa_supplicant\n";
+        config_file << "user_mpm=1\n";
+        config_file << "network={\n";
+        config_file << "    ssid=\"" << config.meshId << "\"\n";
+        config_file << "    mode=5\n";
+        config_file << "    frequency=" << (frequency != 0 ? frequency : 2412) << "\n";
+        config_file << "    key_mgmt=SAE\n";
+        config_file << "    sae_password=\"" << config.password << "\"\n";
+        config_file << "    ieee80211w=2\n";
+        config_file << "}\n";
+        config_file.close();
+        
+        pid_t pid = fork();
+        if (pid == 0) {
+            execl("/sbin/wpa_supplicant", "wpa_supplicant", "-B", "-i", mesh_interface.c_str(),
+                  "-c", config_path.c_str(), "-P", mesh_pid_path, NULL);
+            exit(1);
+        } else if (pid < 0) {
+            Logger::getInstance().error("Failed to fork process for the mesh supplicant");
+            std::remove(config_path.c_str());
+            return false;
+        }
+        
+        int status = 0;
+        waitpid(pid, &status, 0);
+        std::remove(config_path.c_str());
+        if (!WIFEXITED(status) || WEXITSTATUS(status) != 0) {
+            Logger::getInstance().error("wpa_supplicant failed to start on " + mesh_interface);
+            return false;
+        }
+        mesh_secured = true;
+        return true;
+    }
+    
+    // Attach to the wpa_supplicant control interface to receive events
+    bool openSupplicantMonitor() {
+        if (monitor_fd >= 0) {
+            return true;
+        }
+        
+        int fd = ::socket(AF_UNIX, SOCK_DGRAM, 0);
+        if (fd < 0) {
+            Logger::getInstance().error("Failed to create wpa_supplicant monitor socket");
+            return false;
+        }
+        
+        struct sockaddr_un local;
+        memset(&local, 0, sizeof(local));
+        local.sun_family = AF_UNIX;
+        std::string local_path = "/tmp/wificpp_monitor_" + std::to_string(getpid());
+        strncpy(local.sun_path, local_path.c_str(), sizeof(local.sun_path) - 1);
+        unlink(local_path.c_str());
+        
+        struct sockaddr_un remote;
+        memset(&remote, 0, sizeof(remote));
+        remote.sun_family = AF_UNIX;
+        std::string remote_path = "/var/run/wpa_supplicant/" + interface_name;
+        strncpy(remote.sun_path, remote_path.c_str(), sizeof(remote.sun_path) - 1);
+        
+        if (bind(fd, reinterpret_cast<struct sockaddr*>(&local), sizeof(local)) < 0 ||
+            ::connect(fd, reinterpret_cast<struct sockaddr*>(&remote), sizeof(remote)) < 0 ||
+            send(fd, "ATTACH", 6, 0) < 0) {
+            Logger::getInstance().error("Failed to attach to wpa_supplicant on ", interface_name);
+            close(fd);
+            unlink(local_path.c_str());
+            return false;
+        }
+        
+        monitor_fd = fd;
+        monitor_path = local_path;
+        
+        std::string reply;
+        auto deadline = std::chrono::steady_clock::now() + std::chrono::seconds(2);
+        if (!readSupplicantMessage(deadline, reply) || reply.compare(0, 2, "OK") != 0) {
+            Logger::getInstance().error("wpa_supplicant did not accept the event monitor");
+            closeSupplicantMonitor();
+            return false;
+        }
+        return true;
+    }
+    
+    void closeSupplicantMonitor() {
+        if (monitor_fd < 0) {
+            return;
+        }
+        send(monitor_fd, "DETACH", 6, 0);
+        close(monitor_fd);
+        unlink(monitor_path.c_str());
+        monitor_fd = -1;
+        monitor_path.clear();
+    }
+    
+    // Read one datagram from the monitor socket before the deadline
+    bool readSupplicantMessage(std::chrono::steady_clock::time_point deadline, std::string& message) {
+        auto remaining = std::chrono::duration_cast<std::chrono::milliseconds>(deadline - std::chrono::steady_clock::now());
+        struct pollfd pfd = {monitor_fd, POLLIN, 0};
+        if (remaining.count() < 0 || poll(&pfd, 1, static_cast<int>(remaining.count())) <= 0) {
+            return false;
+        }
+        
+        char buffer[4096];
+        ssize_t len = recv(monitor_fd, buffer, sizeof(buffer) - 1, 0);
+        if (len <= 0) {
+            return false;
+        }
+        message.assign(buffer, static_cast<size_t>(len));
+        return true;
+    }
+    
+    // Read the next unsolicited event, stripping its "<level>" prefix
+    bool readSupplicantEvent(std::chrono::steady_clock::time_point deadline, std::string& event) {
+        while (readSupplicantMessage(deadline, event)) {
+            if (!event.empty() && event[0] == '<') {
+                size_t end = event.find('>');
+                event.erase(0, end == std::string::npos ? 0 : end + 1);
+                return true;
+            }
+        }
+        return false;
+    }
+    
+    // Whether a value can be passed to wpa_cli as a single, shell-safe token
+    static bool isCommandToken(const std::string& value) {
+        return !value.empty() && std::all_of(value.begin(), value.end(), [](char c) {
+            return std::isalnum(static_cast<unsigned char>(c)) || c == '.' || c == '-' || c == '_' || c == ':';
+        });
+    }
+    
+    // Whether a value is a MAC address in the aa:bb:cc:dd:ee:ff form wpa_supplicant expects
+    static bool isMacAddress(const std::string& value) {
+        if (value.size() != 17) {
+            return false;
+        }
+        for (size_t i = 0; i < value.size(); i++) {
+            bool separator = i % 3 == 2;
+            if (separator ? value[i] != ':' : !std::isxdigit(static_cast<unsigned char>(value[i]))) {
+                return false;
+            }
+        }
+        return true;
+    }
+    
+    static std::string toHex(const std::vector<uint8_t>& data) {
+        static const char digits[] = "0123456789abcdef";
+        std::string hex;
+        for (uint8_t byte : data) {
+            hex += digits[byte >> 4];
+            hex += digits[byte & 0x0f];
+        }
+        return hex;
+    }
+    
+    // wpa_supplicant reads an unquoted value as hex, so quotes or line breaks in credentials
+    // cannot end the value and add settings of their own
+    static std::string configString(const std::string& value) {
+        return toHex(std::vector<uint8_t>(value.begin(), value.end()));
+    }
+    
+    // Directory for files holding credentials that only we can access, instead of the shared /tmp
+    static std::string privateRuntimeDir() {
+        const char* runtime = std::getenv("XDG_RUNTIME_DIR");
+        std::string dir = std::string(runtime && *runtime ? runtime : "/run") + "/wificpp";
+        if (mkdir(dir.c_str(), 0700) != 0 && errno != EEXIST) {
+            Logger::getInstance().error("Failed to create " + dir + ": ", strerror(errno));
+            return "";
+        }
+        struct stat info;
+        if (lstat(dir.c_str(), &info) != 0 || !S_ISDIR(info.st_mode) || info.st_uid != geteuid() ||
+            (info.st_mode & 077) != 0) {
+            Logger::getInstance().error(dir + " is not a private directory");
+            return "";
+        }
+        return dir;
+    }
+    
+    // Create or truncate a file only the owner can read
+    static bool createPrivateFile(const std::string& path) {
+        int fd = open(path.c_str(), O_WRONLY | O_CREAT | O_TRUNC | O_NOFOLLOW, 0600);
+        if (fd < 0) {
+            return false;
+        }
+        bool restricted = fchmod(fd, 0600) == 0;
+        close(fd);
+        return restricted;
+    }
+    
+    static std::vector<uint8_t> fromHex(const std::string& hex) {
+        std::vector<uint8_t> data;
+        for (size_t i = 0; i + 1 < hex.size(); i += 2) {
+            data.push_back(static_cast<uint8_t>(std::strtoul(hex.substr(i, 2).c_str(), nullptr, 16)));
         }
-        
-        // Remove NAT rules
-        system("iptables -t nat -F POSTROUTING");
-        system("iptables -F FORWARD");
-        
-        // Disable IP forwarding
-        system("sysctl -w net.ipv4.ip_forward=0");
-        
-        // Reset interface
-        std::string cmd = "ip addr flush dev " + interface_name;
-        system(cmd.c_str());
-        cmd = "ip link set " + interface_name + " down";
-        system(cmd.c_str());
-        sleep(1);
-        cmd = "ip link set " + interface_name + " up";
-        system(cmd.c_str());
-        
-        hotspot_active = false;
-        return true;
+        return data;
     }
-
-    bool isHotspotActive() const override {
-        // Check if hostapd is running
-        std::string cmd = "pgrep -x hostapd";
-        int result = system((cmd + " > /dev/null 2>&1").c_str());
-        return result == 0;
+    
+    // Interfaces controlled by wpa_supplicant, including P2P group interfaces
+    std::vector<std::string> supplicantInterfaces() const {
+        std::vector<std::string> interfaces;
+        std::istringstream output(wpaCli("interface"));
+        std::string line;
+        while (std::getline(output, line)) {
+            line.erase(std::remove(line.begin(), line.end(), '\r'), line.end());
+            if (!line.empty() && line.find(' ') == std::string::npos) {
+                interfaces.push_back(line);
+            }
+        }
+        if (std::find(interfaces.begin(), interfaces.end(), interface_name) == interfaces.end()) {
+            interfaces.push_back(interface_name);
+        }
+        return interfaces;
     }
-
-    bool isHotspotSupported() const override {
-        // Check if we have hostapd installed
-        std::string cmd = "which hostapd";
-        int result = system((cmd + " > /dev/null 2>&1").c_str());
-        if (result != 0) {
+    
+    // Make sure a wpa_supplicant with P2P enabled controls our interface
+    bool ensureP2pSupplicant() {
+        if (wpaCli("p2p_stop_find").find("OK") != std::string::npos) {
+            return true;
+        }
+        
+        std::string config_path = "/tmp/wificpp_p2p.conf";
+        std::ofstream config_file(config_path);
+        if (!config_file) {
+            Logger::getInstance().error("Failed to create temporary configuration file");
             return false;
         }
+        config_file << "ctrl_interface=/var/run/wpa_supplicant\n";
+        config_file << "update_config=1\n";
+        config_file << "device_name=wificpp\n";
+        config_file << "device_type=1-0050F204-1\n";
+        config_file << "config_methods=virtual_push_button display keypad\n";
+        config_file.close();
         
-        // Check if interface supports AP mode
+        return launchSupplicant(config_path);
+    }
+    
+    // Whether the wiphy of our interface supports the given interface type
+    bool supportsInterfaceType(enum nl80211_iftype type) const {
         struct nl_msg* msg = nlmsg_alloc();
         if (!msg) {
             return false;
@@ -490,12 +4006,14 @@ public:
         genlmsg_put(msg, 0, 0, nl80211_id, 0, 0, NL80211_CMD_GET_WIPHY, 0);
         nla_put_u32(msg, NL80211_ATTR_IFINDEX, interface_index);
         
-        bool supported = false;
+        struct IftypeQuery {
+            int type;
+            bool supported;
+        } query{type, false};
         struct nl_cb* cb = nl_cb_alloc(NL_CB_DEFAULT);
         
-        // Callback to check for AP mode support
         auto callback = [](struct nl_msg* msg, void* arg) -> int {
-            bool* supported = static_cast<bool*>(arg);
+            IftypeQuery* query = static_cast<IftypeQuery*>(arg);
             struct nlattr* tb[NL80211_ATTR_MAX + 1];
             struct genlmsghdr* gnlh = static_cast<genlmsghdr*>(nlmsg_data(nlmsg_hdr(msg)));
             
@@ -505,8 +4023,8 @@ public:
                 struct nlattr* nl_mode;
                 int rem_mode;
                 nla_for_each_nested(nl_mode, tb[NL80211_ATTR_SUPPORTED_IFTYPES], rem_mode) {
-                    if (nla_type(nl_mode) == NL80211_IFTYPE_AP) {
-                        *supported = true;
+                    if (nla_type(nl_mode) == query->type) {
+                        query->supported = true;
                         break;
                     }
                 }
@@ -515,7 +4033,7 @@ public:
             return NL_SKIP;
         };
         
-        nl_cb_set(cb, NL_CB_VALID, NL_CB_CUSTOM, callback, &supported);
+        nl_cb_set(cb, NL_CB_VALID, NL_CB_CUSTOM, callback, &query);
         
         int ret = nl_send_auto(socket, msg);
         nlmsg_free(msg);
@@ -525,77 +4043,323 @@ public:
         }
         
         nl_cb_put(cb);
-        return supported;
+        return query.supported;
     }
-
-private:
-    struct nl_sock* socket = nullptr;
-    int nl80211_id = -1;
-    std::string interface_name;
-    int interface_index = -1;
-    std::string hostapd_conf_path;
-    std::string dnsmasq_conf_path;
-    bool hotspot_active = false;
     
-    bool findWifiInterface() {
-        // Find first wireless interface
-        FILE* pipe = popen("iw dev | grep Interface | awk '{print $2}'", "r");
-        if (!pipe) {
+    // Pass the information elements advertised by the connected access point to a visitor,
+    // returns false when not associated
+    bool forEachApElement(const std::function<void(uint8_t id, const uint8_t* body, uint8_t len)>& visit) const {
+        auto status = wpaCliValues("status");
+        if (status["wpa_state"] != "COMPLETED" || status["bssid"].empty()) {
             return false;
         }
         
-        char buffer[128];
-        bool found = false;
+        std::vector<uint8_t> ies = fromHex(wpaCliValues("bss " + status["bssid"])["ie"]);
+        for (size_t pos = 0; pos + 2 <= ies.size(); pos += 2 + ies[pos + 1]) {
+            uint8_t len = ies[pos + 1];
+            if (pos + 2 + len > ies.size()) {
+                break;
+            }
+            visit(ies[pos], &ies[pos + 2], len);
+        }
+        return true;
+    }
+    
+    // Check a bit of the adapter's HE MAC capabilities in station mode on any band
+    bool heMacCapability(size_t byte, uint8_t mask) const {
+        bool supported = false;
+        queryWiphy([&](struct nlattr** tb) {
+            if (!tb[NL80211_ATTR_WIPHY_BANDS]) {
+                return;
+            }
+            struct nlattr* band;
+            int rem_band;
+            nla_for_each_nested(band, tb[NL80211_ATTR_WIPHY_BANDS], rem_band) {
+                struct nlattr* band_attrs[NL80211_BAND_ATTR_MAX + 1];
+                nla_parse_nested(band_attrs, NL80211_BAND_ATTR_MAX, band, nullptr);
+                if (!band_attrs[NL80211_BAND_ATTR_IFTYPE_DATA]) {
+                    continue;
+                }
+                struct nlattr* iftype_data;
+                int rem_data;
+                nla_for_each_nested(iftype_data, band_attrs[NL80211_BAND_ATTR_IFTYPE_DATA], rem_data) {
+                    struct nlattr* data[NL80211_BAND_IFTYPE_ATTR_MAX + 1];
+                    nla_parse_nested(data, NL80211_BAND_IFTYPE_ATTR_MAX, iftype_data, nullptr);
+                    if (!data[NL80211_BAND_IFTYPE_ATTR_IFTYPES] || !data[NL80211_BAND_IFTYPE_ATTR_HE_CAP_MAC] ||
+                        !nla_find(static_cast<struct nlattr*>(nla_data(data[NL80211_BAND_IFTYPE_ATTR_IFTYPES])),
+                                  nla_len(data[NL80211_BAND_IFTYPE_ATTR_IFTYPES]), NL80211_IFTYPE_STATION)) {
+                        continue;
+                    }
+                    if (static_cast<size_t>(nla_len(data[NL80211_BAND_IFTYPE_ATTR_HE_CAP_MAC])) > byte) {
+                        const uint8_t* mac = static_cast<const uint8_t*>(nla_data(data[NL80211_BAND_IFTYPE_ATTR_HE_CAP_MAC]));
+                        supported = supported || (mac[byte] & mask) != 0;
+                    }
+                }
+            }
+        });
+        return supported;
+    }
+    
+    // Pass the attributes of the wiphy description to a visitor
+    void queryWiphy(const std::function<void(struct nlattr** tb)>& visit) const {
+        queryNl80211(NL80211_CMD_GET_WIPHY, visit);
+    }
+    
+    // Send an nl80211 command for the interface and visit the attributes of each reply
+    void queryNl80211(uint8_t cmd, const std::function<void(struct nlattr** tb)>& visit) const {
+        queryNl80211(cmd, 0, NL80211_ATTR_IFINDEX, interface_index, visit);
+    }
+    
+    // Send an nl80211 command selecting its object by key, a dump when key is 0
+    void queryNl80211(uint8_t cmd, int flags, enum nl80211_attrs key, uint32_t value,
+                      const std::function<void(struct nlattr** tb)>& visit) const {
+        struct nl_msg* msg = nlmsg_alloc();
+        if (!msg) {
+            return;
+        }
+        
+        genlmsg_put(msg, 0, 0, nl80211_id, 0, flags, cmd, 0);
+        if (key != 0) {
+            nla_put_u32(msg, key, value);
+        }
         
-        while (fgets(buffer, sizeof(buffer), pipe) != nullptr) {
-            // Remove newline character
-            std::string iface(buffer);
-            iface.erase(std::remove(iface.begin(), iface.end(), '\n'), iface.end());
+        struct nl_cb* cb = nl_cb_alloc(NL_CB_DEFAULT);
+        auto callback = [](struct nl_msg* msg, void* arg) -> int {
+            auto* visit = static_cast<const std::function<void(struct nlattr**)>*>(arg);
+            struct nlattr* tb[NL80211_ATTR_MAX + 1];
+            struct genlmsghdr* gnlh = static_cast<genlmsghdr*>(nlmsg_data(nlmsg_hdr(msg)));
             
-            if (!iface.empty()) {
-                interface_name = iface;
-                found = true;
-                break;
+            nla_parse(tb, NL80211_ATTR_MAX, genlmsg_attrdata(gnlh, 0), genlmsg_attrlen(gnlh, 0), nullptr);
+            (*visit)(tb);
+            return NL_SKIP;
+        };
+        
+        nl_cb_set(cb, NL_CB_VALID, NL_CB_CUSTOM, callback, const_cast<void*>(static_cast<const void*>(&visit)));
+        
+        int ret = nl_send_auto(socket, msg);
+        nlmsg_free(msg);
+        
+        if (ret >= 0) {
+            nl_recvmsgs(socket, cb);
+        }
+        
+        nl_cb_put(cb);
+    }
+    
+    // Check for a flag attribute in the wiphy description
+    bool wiphyHasFlag(enum nl80211_attrs attr) const {
+        bool present = false;
+        queryWiphy([&](struct nlattr** tb) {
+            present = present || tb[attr] != nullptr;
+        });
+        return present;
+    }
+    
+    // NL80211_FEATURE_* flags of the wiphy
+    uint32_t wiphyFeatureFlags() const {
+        uint32_t flags = 0;
+        queryWiphy([&](struct nlattr** tb) {
+            if (tb[NL80211_ATTR_FEATURE_FLAGS]) {
+                flags = nla_get_u32(tb[NL80211_ATTR_FEATURE_FLAGS]);
             }
+        });
+        return flags;
+    }
+    
+    // Whether wpa_supplicant reports an established TDLS link to the peer
+    bool tdlsLinked(const std::string& peer_address) const {
+        if (!isMacAddress(peer_address)) {
+            return false;
         }
+        std::string status = wpaCli("tdls_link_status " + peer_address);
+        return status.find("connected") != std::string::npos && status.find("not connected") == std::string::npos;
+    }
+    
+    // Run a WPS exchange through a fresh wpa_supplicant instance and bring up DHCP
+    bool runWps(const std::string& command, int timeout_seconds, const WpsProgressCallback& progress) {
+        // wpa_supplicant stores the credentials received over WPS in this configuration
+        std::string config_path = "/tmp/wificpp_wps.conf";
+        std::ofstream config_file(config_path);
+        if (!config_file) {
+            Logger::getInstance().error("Failed to create temporary configuration file");
+            return false;
+        }
+        config_file << "ctrl_interface=/var/run/wpa_supplicant\n";
+        config_file << "update_config=1\n";
+        config_file.close();
         
-        pclose(pipe);
+        if (!launchSupplicant(config_path)) {
+            return false;
+        }
+        // The restarted supplicant has a new control socket, attach to it for the WPS events
+        closeSupplicantMonitor();
+        bool monitored = openSupplicantMonitor();
         
-        if (found) {
-            // Get interface index
-            struct ifreq ifr;
-            int sock = socket(AF_INET, SOCK_DGRAM, 0);
-            if (sock < 0) {
-                return false;
+        WpsProgress state;
+        auto report = [&](WpsStage stage) {
+            state.stage = stage;
+            if (progress) {
+                progress(state);
+            }
+        };
+        auto finish = [&](bool connected) {
+            if (monitored && nan_sessions.empty()) {
+                closeSupplicantMonitor();
+            }
+            std::remove(config_path.c_str());
+            return connected;
+        };
+        
+        if (wpaCli(command).find("OK") == std::string::npos) {
+            Logger::getInstance().error("wpa_supplicant rejected the WPS request");
+            return finish(false);
+        }
+        report(WpsStage::SEARCHING);
+        
+        state.failure = waitForWps(timeout_seconds, monitored, state, report);
+        if (state.failure != WpsFailure::NONE) {
+            Logger::getInstance().error("WPS enrollment failed, configuration error ", state.configError);
+            wpaCli("wps_cancel");
+            report(WpsStage::FAILED);
+            fail(state.failure == WpsFailure::TIMEOUT ? ErrorCode::TIMEOUT
+                 : state.failure == WpsFailure::WRONG_PIN ? ErrorCode::INVALID_CREDENTIALS
+                 : ErrorCode::OPERATION_FAILED);
+            return finish(false);
+        }
+        
+        if (!configureDhcpClient(interface_name)) {
+            Logger::getInstance().warning("DHCP configuration failed, but connection might still be established");
+        }
+        if (!hasIpAddress(interface_name)) {
+            state.failure = WpsFailure::TIMEOUT;
+            report(WpsStage::FAILED);
+            fail(ErrorCode::TIMEOUT);
+            return finish(false);
+        }
+        report(WpsStage::CONNECTED);
+        return finish(true);
+    }
+    
+    // Follow a WPS exchange through wpa_supplicant's events until the network is joined.
+    // Without the event monitor only completion and the timeout can be told apart.
+    WpsFailure waitForWps(int timeout_seconds, bool monitored, WpsProgress& state,
+                          const std::function<void(WpsStage)>& report) {
+        // WPS configuration errors reported by the registrar
+        const int setup_locked = 15;
+        const int wrong_pin = 18;
+        
+        auto deadline = std::chrono::steady_clock::now() + std::chrono::seconds(timeout_seconds);
+        bool failed_once = false;
+        while (std::chrono::steady_clock::now() < deadline) {
+            std::string event;
+            auto wake = std::min(deadline, std::chrono::steady_clock::now() + std::chrono::seconds(1));
+            if (!monitored || !readSupplicantEvent(wake, event)) {
+                if (!monitored) {
+                    sleep(1);
+                }
+                // Also catches a completion whose events were missed
+                if ((!monitored || state.stage != WpsStage::SEARCHING) &&
+                    wpaCliValues("status")["wpa_state"] == "COMPLETED") {
+                    return WpsFailure::NONE;
+                }
+                continue;
             }
             
-            memset(&ifr, 0, sizeof(ifr));
-            strncpy(ifr.ifr_name, interface_name.c_str(), IFNAMSIZ - 1);
-            
-            if (ioctl(sock, SIOCGIFINDEX, &ifr) >= 0) {
-                interface_index = ifr.ifr_ifindex;
-                close(sock);
-                return true;
+            if (event.compare(0, 22, "CTRL-EVENT-EAP-STARTED") == 0 && state.stage == WpsStage::SEARCHING) {
+                report(WpsStage::EXCHANGING);
+            } else if (event.compare(0, 17, "WPS-CRED-RECEIVED") == 0) {
+                report(WpsStage::CREDENTIALS_RECEIVED);
+            } else if (event.compare(0, 20, "CTRL-EVENT-CONNECTED") == 0 && state.stage == WpsStage::CREDENTIALS_RECEIVED) {
+                return WpsFailure::NONE;
+            } else if (event.compare(0, 20, "WPS-OVERLAP-DETECTED") == 0) {
+                return WpsFailure::SESSION_OVERLAP;
+            } else if (event.compare(0, 11, "WPS-TIMEOUT") == 0) {
+                break;
+            } else if (event.compare(0, 8, "WPS-FAIL") == 0) {
+                // "WPS-FAIL msg=8 config_error=18"; the supplicant retries other failures on its own
+                size_t field = event.find("config_error=");
+                state.configError = field == std::string::npos ? 0 : std::atoi(event.c_str() + field + 13);
+                failed_once = true;
+                if (state.configError == wrong_pin) {
+                    return WpsFailure::WRONG_PIN;
+                }
+                if (state.configError == setup_locked) {
+                    return WpsFailure::SETUP_LOCKED;
+                }
+            }
+        }
+        return failed_once ? WpsFailure::REJECTED : WpsFailure::TIMEOUT;
+    }
+    
+    // Append installed Passpoint credentials and interworking settings to a configuration
+    void appendPasspointCredentials(const std::string& config_path) const {
+        DIR* dir = opendir(passpoint_dir);
+        if (!dir) {
+            return;
+        }
+        
+        std::ofstream config_file(config_path, std::ios::app);
+        bool header_written = false;
+        struct dirent* entry;
+        while ((entry = readdir(dir)) != nullptr) {
+            std::string name = entry->d_name;
+            if (name.size() < 5 || name.compare(name.size() - 5, 5, ".cred") != 0) {
+                continue;
             }
             
-            close(sock);
+            std::ifstream cred_file(std::string(passpoint_dir) + "/" + name);
+            if (!cred_file) {
+                continue;
+            }
+            if (!header_written) {
+                config_file << "interworking=1\n";
+                config_file << "hs20=1\n";
+                config_file << "auto_interworking=1\n";
+                header_written = true;
+            }
+            config_file << cred_file.rdbuf();
         }
         
-        return false;
+        closedir(dir);
     }
     
-    int frequencyToChannel(int frequency) {
-        if (frequency >= 2412 && frequency <= 2484) {
-            return (frequency - 2412) / 5 + 1;
-        } else if (frequency >= 5170 && frequency <= 5825) {
-            return (frequency - 5170) / 5 + 34;
+    // Write EAP-TLS client credentials into a wpa_supplicant network block
+    bool writeTlsCredentials(std::ofstream& config_file, const std::string& key_path, const EapConfig& eap) {
+        if (!eap.certStoreRef.empty()) {
+            Logger::getInstance().error("Certificate store references are not supported on Linux, use certificate files or PKCS#12");
+            return false;
+        }
+        
+        if (!eap.pkcs12.empty()) {
+            // wpa_supplicant loads a PKCS#12 bundle through private_key when client_cert is omitted
+            int fd = open(key_path.c_str(), O_WRONLY | O_CREAT | O_TRUNC, 0600);
+            if (fd < 0) {
+                Logger::getInstance().error("Failed to install PKCS#12 credentials");
+                return false;
+            }
+            ssize_t written = write(fd, eap.pkcs12.data(), eap.pkcs12.size());
+            close(fd);
+            if (written != static_cast<ssize_t>(eap.pkcs12.size())) {
+                Logger::getInstance().error("Failed to write PKCS#12 credentials");
+                std::remove(key_path.c_str());
+                return false;
+            }
+            config_file << "    private_key=" << configString(key_path) << "\n";
         } else {
-            return 0;
+            if (eap.clientCert.empty() || eap.privateKey.empty()) {
+                Logger::getInstance().error("EAP-TLS requires a client certificate and private key");
+                return false;
+            }
+            config_file << "    client_cert=" << configString(eap.clientCert) << "\n";
+            config_file << "    private_key=" << configString(eap.privateKey) << "\n";
+        }
+        
+        if (!eap.privateKeyPassword.empty()) {
+            config_file << "    private_key_passwd=" << configString(eap.privateKeyPassword) << "\n";
         }
+        return true;
     }
     
-    // Helper methods for native implementation
-    
     bool terminateProcess(const std::string& process_name) {
         DIR* dir = opendir("/proc");
         if (!dir) {
@@ -729,6 +4493,200 @@ private:
         return (ifr.ifr_flags & IFF_UP) && (ifr.ifr_flags & IFF_RUNNING);
     }
     
+    // IPv4 address of an interface in dotted notation, empty if none is assigned
+    std::string ipv4Address(const std::string& iface) const {
+        int sock = ::socket(AF_INET, SOCK_DGRAM, 0);
+        if (sock < 0) {
+            return "";
+        }
+        
+        struct ifreq ifr;
+        memset(&ifr, 0, sizeof(ifr));
+        strncpy(ifr.ifr_name, iface.c_str(), IFNAMSIZ - 1);
+        ifr.ifr_addr.sa_family = AF_INET;
+        
+        bool ok = ioctl(sock, SIOCGIFADDR, &ifr) >= 0;
+        close(sock);
+        if (!ok) {
+            return "";
+        }
+        
+        char address[INET_ADDRSTRLEN] = {};
+        struct sockaddr_in* sin = reinterpret_cast<struct sockaddr_in*>(&ifr.ifr_addr);
+        inet_ntop(AF_INET, &sin->sin_addr, address, sizeof(address));
+        return address;
+    }
+    
+    // IPv6 default route through an interface: destination, prefix, source, prefix,
+    // next hop, metric, reference count, use count, flags and device
+    static std::string defaultIpv6Gateway(const std::string& iface) {
+        std::ifstream routes("/proc/net/ipv6_route");
+        std::string line;
+        while (std::getline(routes, line)) {
+            std::istringstream fields(line);
+            std::string destination, destination_prefix, source, source_prefix, next_hop, metric, refs, use, flags, device;
+            if (!(fields >> destination >> destination_prefix >> source >> source_prefix >> next_hop
+                         >> metric >> refs >> use >> flags >> device)) {
+                continue;
+            }
+            if (device != iface || destination_prefix != "00" || next_hop.size() != 32 ||
+                next_hop.find_first_not_of('0') == std::string::npos) {
+                continue;
+            }
+            struct in6_addr gateway;
+            for (int i = 0; i < 16; i++) {
+                gateway.s6_addr[i] = static_cast<uint8_t>(std::stoi(next_hop.substr(i * 2, 2), nullptr, 16));
+            }
+            char address[INET6_ADDRSTRLEN] = {};
+            inet_ntop(AF_INET6, &gateway, address, sizeof(address));
+            return address;
+        }
+        return "";
+    }
+    
+    static std::vector<std::string> nameservers() {
+        std::vector<std::string> servers;
+        std::ifstream resolv("/etc/resolv.conf");
+        std::string line;
+        while (std::getline(resolv, line)) {
+            std::istringstream fields(line);
+            std::string keyword, server;
+            if (fields >> keyword >> server && keyword == "nameserver") {
+                servers.push_back(server);
+            }
+        }
+        return servers;
+    }
+    
+    // Lease details from NetworkManager, or from systemd-networkd's lease file
+    void readDhcpLease(IpInfo& info) const {
+        std::string output;
+        if (nmcli({"-t", "-f", "DHCP4", "device", "show", interface_name}, &output)) {
+            // Lines look like "DHCP4.OPTION[3]:expiry = 1700000000"
+            std::istringstream lines(output);
+            std::string line;
+            int64_t lease_time = 0;
+            while (std::getline(lines, line)) {
+                size_t colon = line.find(':');
+                size_t equals = line.find(" = ");
+                if (colon == std::string::npos || equals == std::string::npos || equals < colon) {
+                    continue;
+                }
+                std::string key = line.substr(colon + 1, equals - colon - 1);
+                std::string value = line.substr(equals + 3);
+                info.dhcp = true;
+                if (key == "dhcp_server_identifier") {
+                    info.dhcpServer = value;
+                } else if (key == "expiry") {
+                    info.leaseExpires = std::atoll(value.c_str());
+                } else if (key == "dhcp_lease_time") {
+                    lease_time = std::atoll(value.c_str());
+                }
+            }
+            if (info.leaseExpires > 0 && lease_time > 0) {
+                info.leaseObtained = info.leaseExpires - lease_time;
+            }
+            if (info.dhcp) {
+                return;
+            }
+        }
+        
+        std::string lease_path = "/run/systemd/netif/leases/" + std::to_string(interface_index);
+        std::ifstream lease(lease_path);
+        std::string line;
+        int64_t lifetime = 0;
+        while (std::getline(lease, line)) {
+            size_t equals = line.find('=');
+            if (equals == std::string::npos) {
+                continue;
+            }
+            std::string key = line.substr(0, equals);
+            info.dhcp = true;
+            if (key == "SERVER_ADDRESS") {
+                info.dhcpServer = line.substr(equals + 1);
+            } else if (key == "LIFETIME") {
+                lifetime = std::atoll(line.c_str() + equals + 1);
+            }
+        }
+        struct stat lease_stat;
+        if (info.dhcp && stat(lease_path.c_str(), &lease_stat) == 0) {
+            // networkd rewrites the file whenever the lease is renewed
+            info.leaseObtained = lease_stat.st_mtime;
+            if (lifetime > 0) {
+                info.leaseExpires = info.leaseObtained + lifetime;
+            }
+        }
+    }
+    
+    // Default gateway routed through an interface, from the kernel routing table
+    std::string defaultGateway(const std::string& iface) const {
+        std::ifstream routes("/proc/net/route");
+        std::string line;
+        std::getline(routes, line); // header
+        while (std::getline(routes, line)) {
+            std::istringstream fields(line);
+            std::string name, destination, gateway;
+            if (!(fields >> name >> destination >> gateway) || name != iface || destination != "00000000") {
+                continue;
+            }
+            
+            struct in_addr addr;
+            addr.s_addr = static_cast<in_addr_t>(std::stoul(gateway, nullptr, 16));
+            char address[INET_ADDRSTRLEN] = {};
+            inet_ntop(AF_INET, &addr, address, sizeof(address));
+            return address;
+        }
+        return "";
+    }
+    
+    // rfkill index of the adapter's radio, -1 if it has none
+    int rfkillIndex() const {
+        std::string path = "/sys/class/net/" + interface_name + "/phy80211";
+        DIR* dir = opendir(path.c_str());
+        if (!dir) {
+            return -1;
+        }
+        
+        int index = -1;
+        struct dirent* entry;
+        while ((entry = readdir(dir)) != nullptr) {
+            if (strncmp(entry->d_name, "rfkill", 6) == 0 && std::isdigit(static_cast<unsigned char>(entry->d_name[6]))) {
+                index = std::atoi(entry->d_name + 6);
+                break;
+            }
+        }
+        closedir(dir);
+        return index;
+    }
+    
+    // Whether the WiFi radio is soft or hard blocked through rfkill
+    bool isRadioBlocked() const {
+        DIR* dir = opendir("/sys/class/rfkill");
+        if (!dir) {
+            return false;
+        }
+        
+        bool blocked = false;
+        struct dirent* entry;
+        while ((entry = readdir(dir)) != nullptr) {
+            std::string base = std::string("/sys/class/rfkill/") + entry->d_name;
+            std::ifstream type_file(base + "/type");
+            std::string type;
+            if (!(type_file >> type) || type != "wlan") {
+                continue;
+            }
+            
+            int soft = 0, hard = 0;
+            std::ifstream(base + "/soft") >> soft;
+            std::ifstream(base + "/hard") >> hard;
+            if (soft || hard) {
+                blocked = true;
+            }
+        }
+        closedir(dir);
+        return blocked;
+    }
+    
     bool hasIpAddress(const std::string& iface) const {
         int sock = socket(AF_INET, SOCK_DGRAM, 0);
         if (sock < 0) {
@@ -750,6 +4708,55 @@ private:
         return sin->sin_addr.s_addr != 0;
     }
     
+    // Assign manual addressing, replacing any IPv4 address and default route DHCP set up
+    bool applyStaticIp(const StaticIpConfig& addressing) {
+        std::string dev = " dev " + interface_name + " > /dev/null 2>&1";
+        if (!addressing.ipv4Address.empty()) {
+            system(("ip -4 addr flush" + dev).c_str());
+            if (system(("ip -4 addr add " + addressing.ipv4Address + dev).c_str()) != 0) {
+                Logger::getInstance().error("Failed to assign " + addressing.ipv4Address + " to " + interface_name);
+                return false;
+            }
+            if (!addressing.ipv4Gateway.empty() &&
+                system(("ip -4 route replace default via " + addressing.ipv4Gateway + dev).c_str()) != 0) {
+                Logger::getInstance().error("Failed to set the default route via " + addressing.ipv4Gateway);
+                return false;
+            }
+        }
+        if (!addressing.ipv6Address.empty()) {
+            if (system(("ip -6 addr replace " + addressing.ipv6Address + dev).c_str()) != 0) {
+                Logger::getInstance().error("Failed to assign " + addressing.ipv6Address + " to " + interface_name);
+                return false;
+            }
+            if (!addressing.ipv6Gateway.empty() &&
+                system(("ip -6 route replace default via " + addressing.ipv6Gateway + dev).c_str()) != 0) {
+                Logger::getInstance().error("Failed to set the default route via " + addressing.ipv6Gateway);
+                return false;
+            }
+        }
+        if (addressing.dnsServers.empty()) {
+            return true;
+        }
+        
+        // systemd-resolved keeps servers per link; otherwise rewrite resolv.conf as dhclient would
+        std::string servers;
+        for (const auto& server : addressing.dnsServers) {
+            servers += " " + server;
+        }
+        if (system(("resolvectl dns " + interface_name + servers + " > /dev/null 2>&1").c_str()) == 0) {
+            return true;
+        }
+        std::ofstream resolv("/etc/resolv.conf", std::ios::trunc);
+        for (const auto& server : addressing.dnsServers) {
+            resolv << "nameserver " << server << "\n";
+        }
+        if (!resolv) {
+            Logger::getInstance().error("Failed to write /etc/resolv.conf");
+            return false;
+        }
+        return true;
+    }
+    
     bool configureDhcpClient(const std::string& iface) {
         // For DHCP we still need to use an external client, but we'll use fork/exec
         pid_t pid = fork();
@@ -14,9 +14,24 @@ void Logger::setLogLevel(LogLevel level) {
     currentLevel = level;
 }
 
+void Logger::setSink(LogSink newSink) {
+    std::lock_guard<std::mutex> lock(sinkMutex);
+    sink = std::move(newSink);
+}
+
 void Logger::log(LogLevel level, const std::string& message) {
     if (level < currentLevel) return;
 
+    LogSink current;
+    {
+        std::lock_guard<std::mutex> lock(sinkMutex);
+        current = sink;
+    }
+    if (current) {
+        current(level, message);
+        return;
+    }
+
     auto now = std::chrono::system_clock::now();
     auto time = std::chrono::system_clock::to_time_t(now);
     
@@ -6,6 +6,8 @@
 #include <stdexcept>
 #include <vector>
 #include <string>
+#include <cstdio>
+#include <cstdlib>
 
 #ifdef WIFICPP_PLATFORM_MACOS
 
@@ -18,6 +20,7 @@
 #include <net/if.h>
 #include <ifaddrs.h>
 #include <dispatch/dispatch.h>
+#include <unistd.h>
 
 // Helper interface for Location Services delegate
 @interface LocationDelegate : NSObject <CLLocationManagerDelegate>
@@ -195,6 +198,20 @@ public:
         }
     }
     
+    bool isLocationAuthorized() const override {
+        return isLocationServicesAuthorized();
+    }
+    
+    bool requestLocationAuthorization() override {
+        return requestLocationServicesAuthorization();
+    }
+    
+    bool scanFresh(std::vector<NetworkInfo>& networks) override {
+        // CoreWLAN scans synchronously and returns only that scan's results; failures still come back empty
+        networks = scan();
+        return true;
+    }
+
     std::vector<NetworkInfo> scan() override {
         std::vector<NetworkInfo> networks;
         Logger::getInstance().info("Scanning for networks on macOS interface " + interfaceName);
@@ -325,6 +342,10 @@ public:
                         info.frequency = 2412 + ((info.channel - 1) * 5);
                     } else if ([channel channelBand] == kCWChannelBand5GHz) {
                         info.frequency = 5170 + ((info.channel - 34) * 5);
+                    } else if (@available(macOS 11.0, *)) {
+                        if ([channel channelBand] == kCWChannelBand6GHz) {
+                            info.frequency = info.channel == 2 ? 5935 : 5950 + info.channel * 5;
+                        }
                     }
                 }
                 
@@ -342,6 +363,12 @@ public:
                     info.security = SecurityType::WPA;
                 }
                 
+                NSData* elements = [network informationElementData];
+                if (elements) {
+                    const uint8_t* bytes = static_cast<const uint8_t*>([elements bytes]);
+                    info.informationElements.assign(bytes, bytes + [elements length]);
+                }
+                
                 networks.push_back(info);
             }
         }
@@ -378,6 +405,23 @@ public:
         return getStatus() == ConnectionStatus::CONNECTED;
     }
     
+    bool connectWithOptions(const std::string& ssid, const std::string& password, const ConnectOptions& options) override {
+        if (options.staticIp.empty()) {
+            return WifiImpl::connectWithOptions(ssid, password, options);
+        }
+        if (!isWellFormed(options.staticIp)) {
+            return fail(ErrorCode::OPERATION_FAILED);
+        }
+        
+        // Configure the service first so DHCP never runs for a manual address
+        if (!applyStaticIp(options.staticIp)) {
+            return fail(ErrorCode::OPERATION_FAILED);
+        }
+        ConnectOptions remaining = options;
+        remaining.staticIp = StaticIpConfig();
+        return WifiImpl::connectWithOptions(ssid, password, remaining);
+    }
+    
     bool disconnect() override {
         Logger::getInstance().info("Disconnecting from network");
         
@@ -404,6 +448,34 @@ public:
         return ConnectionStatus::CONNECTED;
     }
     
+    bool hasAdminPrivileges() const override {
+        return geteuid() == 0;
+    }
+    
+    bool isRadioEnabled() const override {
+        return [wifiInterface powerOn];
+    }
+    
+    bool getLinkStats(LinkStats& stats) const override {
+        if ([wifiInterface ssid] == nil) {
+            return false;
+        }
+        // CoreWLAN only reports the transmit rate, in Mbit/s; frame counters are not exposed
+        stats.txBitrateKbps = static_cast<uint32_t>([wifiInterface transmitRate] * 1000);
+        return true;
+    }
+    
+    bool setRadioEnabled(bool enabled) override {
+        NSError* error = nil;
+        if (![wifiInterface setPower:enabled error:&error]) {
+            Logger::getInstance().error("Failed to switch the radio: " +
+                                        NSStringToStdString([error localizedDescription]));
+            return fail(ErrorCode::OPERATION_FAILED);
+        }
+        Logger::getInstance().info(enabled ? "Radio switched on" : "Radio switched off");
+        return true;
+    }
+    
     bool createHotspot(const std::string& ssid, const std::string& password) override {
         Logger::getInstance().warning("Hotspot creation not yet implemented on macOS");
         return false;
@@ -429,6 +501,78 @@ private:
     CLLocationManager* locationManager = nullptr;
     LocationDelegate* locationDelegate = nullptr;
     
+    // Network service of the WiFi interface, e.g. "Wi-Fi", which networksetup configures
+    std::string networkService() const {
+        FILE* pipe = popen("networksetup -listallhardwareports", "r");
+        if (!pipe) {
+            return "";
+        }
+        // Entries are "Hardware Port: Wi-Fi" followed by "Device: en0"
+        std::string port;
+        std::string service;
+        char line[256];
+        while (fgets(line, sizeof(line), pipe)) {
+            std::string text(line);
+            text.erase(text.find_last_not_of("\r\n") + 1);
+            if (text.rfind("Hardware Port: ", 0) == 0) {
+                port = text.substr(15);
+            } else if (text == "Device: " + interfaceName) {
+                service = port;
+            }
+        }
+        pclose(pipe);
+        return service;
+    }
+    
+    bool applyStaticIp(const StaticIpConfig& addressing) {
+        std::string service = networkService();
+        if (service.empty()) {
+            Logger::getInstance().error("No network service for interface " + interfaceName);
+            return false;
+        }
+        auto networksetup = [&](const std::string& args) {
+            return system(("networksetup " + args + " > /dev/null 2>&1").c_str()) == 0;
+        };
+        std::string quoted = "\"" + service + "\"";
+        
+        if (!addressing.ipv4Address.empty()) {
+            // networksetup takes a dotted netmask rather than a prefix length
+            size_t slash = addressing.ipv4Address.find('/');
+            int prefix = std::atoi(addressing.ipv4Address.c_str() + slash + 1);
+            if (prefix < 0 || prefix > 32) {
+                return false;
+            }
+            uint32_t mask = prefix == 0 ? 0 : 0xFFFFFFFFu << (32 - prefix);
+            std::string netmask = std::to_string(mask >> 24) + "." + std::to_string((mask >> 16) & 0xFF) + "." +
+                                  std::to_string((mask >> 8) & 0xFF) + "." + std::to_string(mask & 0xFF);
+            if (!networksetup("-setmanual " + quoted + " " + addressing.ipv4Address.substr(0, slash) + " " + netmask +
+                              (addressing.ipv4Gateway.empty() ? "" : " " + addressing.ipv4Gateway))) {
+                Logger::getInstance().error("Failed to assign " + addressing.ipv4Address);
+                return false;
+            }
+        }
+        if (!addressing.ipv6Address.empty()) {
+            size_t slash = addressing.ipv6Address.find('/');
+            if (!networksetup("-setv6manual " + quoted + " " + addressing.ipv6Address.substr(0, slash) + " " +
+                              addressing.ipv6Address.substr(slash + 1) +
+                              (addressing.ipv6Gateway.empty() ? "" : " " + addressing.ipv6Gateway))) {
+                Logger::getInstance().error("Failed to assign " + addressing.ipv6Address);
+                return false;
+            }
+        }
+        if (!addressing.dnsServers.empty()) {
+            std::string servers;
+            for (const auto& server : addressing.dnsServers) {
+                servers += " " + server;
+            }
+            if (!networksetup("-setdnsservers " + quoted + servers)) {
+                Logger::getInstance().error("Failed to set DNS servers");
+                return false;
+            }
+        }
+        return true;
+    }
+    
     bool requestLocationServicesAuthorization() {
         // Must be called on main thread
         if (![NSThread isMainThread]) {
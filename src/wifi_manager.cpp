@@ -2,8 +2,16 @@
 #include "wifi_logger.hpp"
 #include "wifi_impl.hpp"
 #include "wifi_types.hpp"
+#include <algorithm>
+#include <cctype>
+#include <chrono>
+#include <map>
+#include <condition_variable>
 #include <memory>
+#include <mutex>
 #include <stdexcept>
+#include <thread>
+#include <utility>
 
 namespace wificpp {
 
@@ -20,54 +28,964 @@ public:
         Logger::getInstance().info("WifiManager initialized");
     }
 
-    ~Impl() = default;
+    explicit Impl(const std::string& interfaceName) : Impl() {
+        if (!platformImpl->selectInterface(interfaceName)) {
+            throw std::runtime_error("WiFi interface not found: " + interfaceName);
+        }
+    }
+
+    ~Impl() {
+        stopSignalMonitor();
+    }
 
     std::vector<NetworkInfo> scan() {
-        return platformImpl->scan();
+        std::lock_guard<std::mutex> lock(callMutex);
+        platformImpl->clearLastError();
+        CancellableCall call(*platformImpl);
+        std::vector<NetworkInfo> networks = platformImpl->scan();
+        rememberElements(networks);
+        return networks;
+    }
+
+    std::vector<NetworkInfo> scanWithOptions(const ScanOptions& options) {
+        std::lock_guard<std::mutex> lock(callMutex);
+        platformImpl->clearLastError();
+        CancellableCall call(*platformImpl);
+        TimeoutOverride timeouts(*platformImpl, options.timeoutMs, 0);
+        std::vector<NetworkInfo> networks = platformImpl->scanWithOptions(options);
+        rememberElements(networks);
+        return networks;
+    }
+
+    bool scanFresh(std::vector<NetworkInfo>& networks) {
+        std::lock_guard<std::mutex> lock(callMutex);
+        platformImpl->clearLastError();
+        CancellableCall call(*platformImpl);
+        bool scanned = platformImpl->scanFresh(networks);
+        if (scanned) {
+            rememberElements(networks);
+        }
+        return scanned;
+    }
+
+    // Deliberately without callMutex, which the operation to cancel holds
+    bool cancel() {
+        return platformImpl->requestCancel();
+    }
+
+    void setOperationTimeouts(int scanTimeoutMs, int hotspotTimeoutMs) {
+        std::lock_guard<std::mutex> lock(callMutex);
+        platformImpl->setOperationTimeouts(scanTimeoutMs, hotspotTimeoutMs);
+    }
+
+    bool getInformationElements(const std::string& bssid, std::vector<uint8_t>& elements) const {
+        std::lock_guard<std::mutex> lock(callMutex);
+        auto it = scanElements.find(normalizedBssid(bssid));
+        if (it == scanElements.end()) {
+            return false;
+        }
+        elements = it->second;
+        return true;
     }
 
     bool connect(const std::string& ssid, const std::string& password) {
+        std::lock_guard<std::mutex> lock(callMutex);
+        platformImpl->clearLastError();
+        CancellableCall call(*platformImpl);
         return platformImpl->connect(ssid, password);
     }
 
+    bool connectWithOptions(const std::string& ssid, const std::string& password, const ConnectOptions& options) {
+        std::lock_guard<std::mutex> lock(callMutex);
+        platformImpl->clearLastError();
+        CancellableCall call(*platformImpl);
+        return platformImpl->connectWithOptions(ssid, password, options);
+    }
+
+    bool connectEnterprise(const std::string& ssid, const EapConfig& eap) {
+        std::lock_guard<std::mutex> lock(callMutex);
+        platformImpl->clearLastError();
+        CancellableCall call(*platformImpl);
+        return platformImpl->connectEnterprise(ssid, eap);
+    }
+
+    bool connectWpsPbc(int timeoutSeconds, WpsProgressCallback progress) {
+        std::lock_guard<std::mutex> lock(callMutex);
+        platformImpl->clearLastError();
+        return platformImpl->connectWpsPbc(timeoutSeconds, std::move(progress));
+    }
+
+    bool connectWpsPin(const std::string& bssid, const std::string& pin, int timeoutSeconds, WpsProgressCallback progress) {
+        std::lock_guard<std::mutex> lock(callMutex);
+        platformImpl->clearLastError();
+        return platformImpl->connectWpsPin(bssid, pin, timeoutSeconds, std::move(progress));
+    }
+
+    bool isEapMethodSupported(EapMethod method) const {
+        std::lock_guard<std::mutex> lock(callMutex);
+        return platformImpl->isEapMethodSupported(method);
+    }
+
+    bool installPasspointProfile(const PasspointProfile& profile) {
+        std::lock_guard<std::mutex> lock(callMutex);
+        platformImpl->clearLastError();
+        return platformImpl->installPasspointProfile(profile);
+    }
+
+    bool removePasspointProfile(const std::string& domain) {
+        std::lock_guard<std::mutex> lock(callMutex);
+        platformImpl->clearLastError();
+        return platformImpl->removePasspointProfile(domain);
+    }
+
+    bool setProbePrivacy(bool enabled) {
+        std::lock_guard<std::mutex> lock(callMutex);
+        platformImpl->clearLastError();
+        return platformImpl->setProbePrivacy(enabled);
+    }
+
+    bool isProbePrivacyEnabled() const {
+        std::lock_guard<std::mutex> lock(callMutex);
+        return platformImpl->isProbePrivacyEnabled();
+    }
+
+    bool startCapture(const CaptureConfig& config, FrameCallback callback) {
+        std::lock_guard<std::mutex> lock(callMutex);
+        platformImpl->clearLastError();
+        return platformImpl->startCapture(config, std::move(callback));
+    }
+
+    bool stopCapture() {
+        return platformImpl->stopCapture();
+    }
+
+    bool isCapturing() const {
+        std::lock_guard<std::mutex> lock(callMutex);
+        return platformImpl->isCapturing();
+    }
+
+    std::vector<ChannelSurvey> channelSurvey() {
+        std::lock_guard<std::mutex> lock(callMutex);
+        return platformImpl->channelSurvey();
+    }
+
+    bool getLinkDiagnostics(LinkDiagnostics& diagnostics) const {
+        std::lock_guard<std::mutex> lock(callMutex);
+        return platformImpl->getLinkDiagnostics(diagnostics);
+    }
+
+    bool getConnectionInfo(ConnectionInfo& info) const {
+        std::lock_guard<std::mutex> lock(callMutex);
+        return platformImpl->getConnectionInfo(info);
+    }
+
+    bool getLinkStats(LinkStats& stats) const {
+        std::lock_guard<std::mutex> lock(callMutex);
+        return platformImpl->getLinkStats(stats);
+    }
+
+    bool setLinuxBackend(LinuxBackend backend) {
+        std::lock_guard<std::mutex> lock(callMutex);
+        platformImpl->clearLastError();
+        return platformImpl->setLinuxBackend(backend);
+    }
+
+    LinuxBackend getLinuxBackend() const {
+        std::lock_guard<std::mutex> lock(callMutex);
+        return platformImpl->getLinuxBackend();
+    }
+
+    bool isLocationAuthorized() const {
+        std::lock_guard<std::mutex> lock(callMutex);
+        return platformImpl->isLocationAuthorized();
+    }
+
+    bool requestLocationAuthorization() {
+        std::lock_guard<std::mutex> lock(callMutex);
+        return platformImpl->requestLocationAuthorization();
+    }
+
+    bool hasAdminPrivileges() const {
+        std::lock_guard<std::mutex> lock(callMutex);
+        return platformImpl->hasAdminPrivileges();
+    }
+
+    bool isRadioEnabled() const {
+        std::lock_guard<std::mutex> lock(callMutex);
+        return platformImpl->isRadioEnabled();
+    }
+
+    bool setRadioEnabled(bool enabled) {
+        std::lock_guard<std::mutex> lock(callMutex);
+        platformImpl->clearLastError();
+        return platformImpl->setRadioEnabled(enabled);
+    }
+
+    bool isP2pSupported() const {
+        std::lock_guard<std::mutex> lock(callMutex);
+        return platformImpl->isP2pSupported();
+    }
+
+    std::vector<P2pPeer> p2pDiscover(int timeoutSeconds) {
+        std::lock_guard<std::mutex> lock(callMutex);
+        return platformImpl->p2pDiscover(timeoutSeconds);
+    }
+
+    bool p2pConnect(const P2pConnectConfig& config, int timeoutSeconds, P2pGroup& group) {
+        std::lock_guard<std::mutex> lock(callMutex);
+        platformImpl->clearLastError();
+        return platformImpl->p2pConnect(config, timeoutSeconds, group);
+    }
+
+    bool p2pDisconnect() {
+        std::lock_guard<std::mutex> lock(callMutex);
+        platformImpl->clearLastError();
+        return platformImpl->p2pDisconnect();
+    }
+
+    bool isNanSupported() const {
+        std::lock_guard<std::mutex> lock(callMutex);
+        return platformImpl->isNanSupported();
+    }
+
+    int nanPublish(const NanPublishConfig& config) {
+        std::lock_guard<std::mutex> lock(callMutex);
+        return platformImpl->nanPublish(config);
+    }
+
+    int nanSubscribe(const NanSubscribeConfig& config) {
+        std::lock_guard<std::mutex> lock(callMutex);
+        return platformImpl->nanSubscribe(config);
+    }
+
+    bool nanCancel(int handle) {
+        std::lock_guard<std::mutex> lock(callMutex);
+        platformImpl->clearLastError();
+        return platformImpl->nanCancel(handle);
+    }
+
+    bool nanTransmit(int handle, int peerInstanceId, const std::string& peerAddress, const std::vector<uint8_t>& data) {
+        std::lock_guard<std::mutex> lock(callMutex);
+        platformImpl->clearLastError();
+        return platformImpl->nanTransmit(handle, peerInstanceId, peerAddress, data);
+    }
+
+    bool nanNextEvent(int timeoutMs, NanEvent& event) {
+        std::lock_guard<std::mutex> lock(callMutex);
+        return platformImpl->nanNextEvent(timeoutMs, event);
+    }
+
+    bool isMeshSupported() const {
+        std::lock_guard<std::mutex> lock(callMutex);
+        return platformImpl->isMeshSupported();
+    }
+
+    bool meshJoin(const MeshConfig& config) {
+        std::lock_guard<std::mutex> lock(callMutex);
+        platformImpl->clearLastError();
+        return platformImpl->meshJoin(config);
+    }
+
+    bool meshLeave() {
+        std::lock_guard<std::mutex> lock(callMutex);
+        platformImpl->clearLastError();
+        return platformImpl->meshLeave();
+    }
+
+    std::vector<MeshPeer> meshPeers() {
+        std::lock_guard<std::mutex> lock(callMutex);
+        return platformImpl->meshPeers();
+    }
+
+    bool isAdHocSupported() const {
+        std::lock_guard<std::mutex> lock(callMutex);
+        return platformImpl->isAdHocSupported();
+    }
+
+    bool joinAdHoc(const AdHocConfig& config) {
+        std::lock_guard<std::mutex> lock(callMutex);
+        platformImpl->clearLastError();
+        return platformImpl->joinAdHoc(config);
+    }
+
+    bool isTdlsSupported() const {
+        std::lock_guard<std::mutex> lock(callMutex);
+        return platformImpl->isTdlsSupported();
+    }
+
+    std::vector<TdlsPeer> tdlsDiscover(int timeoutSeconds) {
+        std::lock_guard<std::mutex> lock(callMutex);
+        return platformImpl->tdlsDiscover(timeoutSeconds);
+    }
+
+    bool tdlsSetup(const std::string& peerAddress) {
+        std::lock_guard<std::mutex> lock(callMutex);
+        platformImpl->clearLastError();
+        return platformImpl->tdlsSetup(peerAddress);
+    }
+
+    bool tdlsTeardown(const std::string& peerAddress) {
+        std::lock_guard<std::mutex> lock(callMutex);
+        platformImpl->clearLastError();
+        return platformImpl->tdlsTeardown(peerAddress);
+    }
+
+    bool isRttSupported() const {
+        std::lock_guard<std::mutex> lock(callMutex);
+        return platformImpl->isRttSupported();
+    }
+
+    bool measureDistance(const std::string& bssid, int frequency, RangingResult& result) {
+        std::lock_guard<std::mutex> lock(callMutex);
+        platformImpl->clearLastError();
+        return platformImpl->measureDistance(bssid, frequency, result);
+    }
+
+    bool getRoamingFeatures(RoamingFeatures& features) const {
+        std::lock_guard<std::mutex> lock(callMutex);
+        return platformImpl->getRoamingFeatures(features);
+    }
+
+    bool roamTo(const std::string& bssid) {
+        std::lock_guard<std::mutex> lock(callMutex);
+        platformImpl->clearLastError();
+        return platformImpl->roamTo(bssid);
+    }
+
+    bool setRoamingPolicy(const RoamingPolicy& policy) {
+        std::lock_guard<std::mutex> lock(callMutex);
+        platformImpl->clearLastError();
+        return platformImpl->setRoamingPolicy(policy);
+    }
+
+    bool getTwtStatus(TwtStatus& status) const {
+        std::lock_guard<std::mutex> lock(callMutex);
+        return platformImpl->getTwtStatus(status);
+    }
+
+    bool twtSetup(const TwtConfig& config) {
+        std::lock_guard<std::mutex> lock(callMutex);
+        platformImpl->clearLastError();
+        return platformImpl->twtSetup(config);
+    }
+
+    bool twtTeardown() {
+        std::lock_guard<std::mutex> lock(callMutex);
+        platformImpl->clearLastError();
+        return platformImpl->twtTeardown();
+    }
+
+    std::vector<MloLink> getMloLinks() const {
+        std::lock_guard<std::mutex> lock(callMutex);
+        return platformImpl->getMloLinks();
+    }
+
+    std::string getRegulatoryDomain() const {
+        std::lock_guard<std::mutex> lock(callMutex);
+        return platformImpl->getRegulatoryDomain();
+    }
+
+    bool canSetRegulatoryDomain() const {
+        std::lock_guard<std::mutex> lock(callMutex);
+        return platformImpl->canSetRegulatoryDomain();
+    }
+
+    bool setRegulatoryDomain(const std::string& country) {
+        std::lock_guard<std::mutex> lock(callMutex);
+        platformImpl->clearLastError();
+        return platformImpl->setRegulatoryDomain(country);
+    }
+
+    std::vector<ChannelInfo> getAllowedChannels() const {
+        std::lock_guard<std::mutex> lock(callMutex);
+        return platformImpl->getAllowedChannels();
+    }
+
+    std::string getInterfaceName() const {
+        std::lock_guard<std::mutex> lock(callMutex);
+        return platformImpl->getInterfaceName();
+    }
+
+    bool getWowlanCapabilities(WowlanCapabilities& capabilities) const {
+        std::lock_guard<std::mutex> lock(callMutex);
+        return platformImpl->getWowlanCapabilities(capabilities);
+    }
+
+    bool getWowlanConfig(WowlanConfig& config) const {
+        std::lock_guard<std::mutex> lock(callMutex);
+        return platformImpl->getWowlanConfig(config);
+    }
+
+    bool setWowlanConfig(const WowlanConfig& config) {
+        std::lock_guard<std::mutex> lock(callMutex);
+        platformImpl->clearLastError();
+        return platformImpl->setWowlanConfig(config);
+    }
+
+    bool startNetworkObserver(NetworkChangeCallback callback) {
+        std::lock_guard<std::mutex> lock(callMutex);
+        return platformImpl->startNetworkObserver(callback);
+    }
+
+    bool stopNetworkObserver() {
+        return platformImpl->stopNetworkObserver();
+    }
+
+    bool isProfileStoreSupported() const {
+        std::lock_guard<std::mutex> lock(callMutex);
+        return platformImpl->isProfileStoreSupported();
+    }
+
+    bool hasProfile(const std::string& name) const {
+        std::lock_guard<std::mutex> lock(callMutex);
+        return platformImpl->hasProfile(name);
+    }
+
+    bool connectProfile(const std::string& name) {
+        std::lock_guard<std::mutex> lock(callMutex);
+        platformImpl->clearLastError();
+        return platformImpl->connectProfile(name);
+    }
+
+    ErrorCode getLastError() const {
+        // Per calling thread, so no need to wait for another thread's call
+        return platformImpl->getLastError();
+    }
+
+    bool registerEventCallback(WifiEventCallback callback, int signalThreshold) {
+        std::lock_guard<std::mutex> lock(callMutex);
+        return platformImpl->registerEventCallback(std::move(callback), signalThreshold);
+    }
+
+    bool unregisterEventCallback() {
+        return platformImpl->unregisterEventCallback();
+    }
+
+    std::vector<InterfaceInfo> listInterfaces() const {
+        std::lock_guard<std::mutex> lock(callMutex);
+        return platformImpl->listInterfaces();
+    }
+
+    std::vector<SavedProfile> listProfiles() const {
+        std::lock_guard<std::mutex> lock(callMutex);
+        return platformImpl->listProfiles();
+    }
+
+    bool saveProfile(const std::string& ssid, const std::string& password, bool autoConnect) {
+        std::lock_guard<std::mutex> lock(callMutex);
+        platformImpl->clearLastError();
+        return platformImpl->saveProfile(ssid, password, autoConnect);
+    }
+
+    bool forgetProfile(const std::string& name) {
+        std::lock_guard<std::mutex> lock(callMutex);
+        platformImpl->clearLastError();
+        return platformImpl->forgetProfile(name);
+    }
+
+    bool setProfileAutoConnect(const std::string& name, bool enabled) {
+        std::lock_guard<std::mutex> lock(callMutex);
+        platformImpl->clearLastError();
+        return platformImpl->setProfileAutoConnect(name, enabled);
+    }
+
+    bool startSignalMonitor(int intervalMs, SignalCallback callback) {
+        if (signalThread.joinable()) {
+            Logger::getInstance().error("A signal monitor is already running");
+            return false;
+        }
+        if (intervalMs <= 0 || !callback) {
+            return false;
+        }
+        
+        signalRunning = true;
+        signalThread = std::thread([this, intervalMs, callback]() {
+            std::unique_lock<std::mutex> lock(signalMutex);
+            while (signalRunning) {
+                lock.unlock();
+                ConnectionInfo info;
+                if (getConnectionInfo(info)) {
+                    callback(info);
+                }
+                lock.lock();
+                signalWake.wait_for(lock, std::chrono::milliseconds(intervalMs), [this]() {
+                    return !signalRunning;
+                });
+            }
+        });
+        return true;
+    }
+    
+    bool stopSignalMonitor() {
+        if (!signalThread.joinable()) {
+            return true;
+        }
+        {
+            std::lock_guard<std::mutex> lock(signalMutex);
+            signalRunning = false;
+        }
+        signalWake.notify_all();
+        signalThread.join();
+        return true;
+    }
+
+    bool getIpInfo(IpInfo& info) const {
+        std::lock_guard<std::mutex> lock(callMutex);
+        return platformImpl->getIpInfo(info);
+    }
+
     bool disconnect() {
+        std::lock_guard<std::mutex> lock(callMutex);
+        platformImpl->clearLastError();
         return platformImpl->disconnect();
     }
 
     ConnectionStatus getStatus() const {
+        std::lock_guard<std::mutex> lock(callMutex);
         return platformImpl->getStatus();
-    }    bool createHotspot(const std::string& ssid, const std::string& password) {
+    }
+
+    bool createHotspot(const std::string& ssid, const std::string& password) {
+        std::lock_guard<std::mutex> lock(callMutex);
+        platformImpl->clearLastError();
         return platformImpl->createHotspot(ssid, password);
     }
+
+    bool createHotspotWithConfig(const HotspotConfig& config) {
+        std::lock_guard<std::mutex> lock(callMutex);
+        platformImpl->clearLastError();
+        TimeoutOverride timeouts(*platformImpl, 0, config.timeoutMs);
+        return platformImpl->createHotspotWithConfig(config);
+    }
     
     bool stopHotspot() {
+        std::lock_guard<std::mutex> lock(callMutex);
+        platformImpl->clearLastError();
         return platformImpl->stopHotspot();
     }
     
     bool isHotspotActive() const {
+        std::lock_guard<std::mutex> lock(callMutex);
         return platformImpl->isHotspotActive();
     }
     
     bool isHotspotSupported() const {
+        std::lock_guard<std::mutex> lock(callMutex);
         return platformImpl->isHotspotSupported();
     }
+    
+    std::vector<HotspotClient> hotspotClients() const {
+        std::lock_guard<std::mutex> lock(callMutex);
+        return platformImpl->hotspotClients();
+    }
 
 private:
+    // Replaces the platform's timeouts for one call made under callMutex, values <= 0 keep the
+    // configured ones
+    class TimeoutOverride {
+    public:
+        TimeoutOverride(WifiImpl& impl, int scanMs, int hotspotMs)
+            : impl(impl), scanBefore(impl.scanTimeoutMs()), hotspotBefore(impl.hotspotTimeoutMs()) {
+            impl.setOperationTimeouts(scanMs > 0 ? scanMs : scanBefore, hotspotMs > 0 ? hotspotMs : hotspotBefore);
+        }
+        ~TimeoutOverride() {
+            impl.setOperationTimeouts(scanBefore, hotspotBefore);
+        }
+        TimeoutOverride(const TimeoutOverride&) = delete;
+        TimeoutOverride& operator=(const TimeoutOverride&) = delete;
+
+    private:
+        WifiImpl& impl;
+        int scanBefore;
+        int hotspotBefore;
+    };
+
+    // Lets cancel abort the platform operation made for its lifetime
+    class CancellableCall {
+    public:
+        explicit CancellableCall(WifiImpl& impl) : impl(impl) {
+            impl.beginCancellable();
+        }
+        ~CancellableCall() {
+            impl.endCancellable();
+        }
+        CancellableCall(const CancellableCall&) = delete;
+        CancellableCall& operator=(const CancellableCall&) = delete;
+
+    private:
+        WifiImpl& impl;
+    };
+
+    // Keep the elements of the latest scan for lookups by BSSID after the results are freed
+    void rememberElements(const std::vector<NetworkInfo>& networks) {
+        scanElements.clear();
+        for (const auto& network : networks) {
+            if (!network.informationElements.empty()) {
+                scanElements[normalizedBssid(network.bssid)] = network.informationElements;
+            }
+        }
+    }
+
+    static std::string normalizedBssid(std::string bssid) {
+        std::transform(bssid.begin(), bssid.end(), bssid.begin(),
+                       [](unsigned char c) { return static_cast<char>(std::tolower(c)); });
+        return bssid;
+    }
+
     std::unique_ptr<WifiImpl> platformImpl;
+    std::map<std::string, std::vector<uint8_t>> scanElements;  // by lowercase BSSID
+    
+    // Serializes calls into the platform implementation, which keeps
+    // per-operation state (sockets, last error) that is not thread-safe.
+    // Stopping a background thread does not take it, as that thread may be
+    // waiting for it.
+    mutable std::mutex callMutex;
+    
+    // Signal monitor polling thread, woken early to stop
+    std::thread signalThread;
+    std::mutex signalMutex;
+    std::condition_variable signalWake;
+    bool signalRunning = false;
 };
 
 // Public interface implementation
 WifiManager::WifiManager() : pimpl(std::make_unique<Impl>()) {}
+WifiManager::WifiManager(const std::string& interfaceName) : pimpl(std::make_unique<Impl>(interfaceName)) {}
 WifiManager::~WifiManager() = default;
 
 std::vector<NetworkInfo> WifiManager::scan() {
     return pimpl->scan();
 }
 
+std::vector<NetworkInfo> WifiManager::scanWithOptions(const ScanOptions& options) {
+    return pimpl->scanWithOptions(options);
+}
+
+bool WifiManager::scanFresh(std::vector<NetworkInfo>& networks) {
+    return pimpl->scanFresh(networks);
+}
+
+bool WifiManager::cancel() {
+    return pimpl->cancel();
+}
+
+void WifiManager::setOperationTimeouts(int scanTimeoutMs, int hotspotTimeoutMs) {
+    pimpl->setOperationTimeouts(scanTimeoutMs, hotspotTimeoutMs);
+}
+
+bool WifiManager::getInformationElements(const std::string& bssid, std::vector<uint8_t>& elements) const {
+    return pimpl->getInformationElements(bssid, elements);
+}
+
 bool WifiManager::connect(const std::string& ssid, const std::string& password) {
     return pimpl->connect(ssid, password);
 }
 
+bool WifiManager::connectWithOptions(const std::string& ssid, const std::string& password, const ConnectOptions& options) {
+    return pimpl->connectWithOptions(ssid, password, options);
+}
+
+bool WifiManager::connectEnterprise(const std::string& ssid, const EapConfig& eap) {
+    return pimpl->connectEnterprise(ssid, eap);
+}
+
+bool WifiManager::connectWpsPbc(int timeoutSeconds, WpsProgressCallback progress) {
+    return pimpl->connectWpsPbc(timeoutSeconds, std::move(progress));
+}
+
+bool WifiManager::connectWpsPin(const std::string& bssid, const std::string& pin, int timeoutSeconds,
+                                WpsProgressCallback progress) {
+    return pimpl->connectWpsPin(bssid, pin, timeoutSeconds, std::move(progress));
+}
+
+bool WifiManager::isEapMethodSupported(EapMethod method) const {
+    return pimpl->isEapMethodSupported(method);
+}
+
+bool WifiManager::installPasspointProfile(const PasspointProfile& profile) {
+    return pimpl->installPasspointProfile(profile);
+}
+
+bool WifiManager::removePasspointProfile(const std::string& domain) {
+    return pimpl->removePasspointProfile(domain);
+}
+
+bool WifiManager::setProbePrivacy(bool enabled) {
+    return pimpl->setProbePrivacy(enabled);
+}
+
+bool WifiManager::isProbePrivacyEnabled() const {
+    return pimpl->isProbePrivacyEnabled();
+}
+
+bool WifiManager::startCapture(const CaptureConfig& config, FrameCallback callback) {
+    return pimpl->startCapture(config, std::move(callback));
+}
+
+bool WifiManager::stopCapture() {
+    return pimpl->stopCapture();
+}
+
+bool WifiManager::isCapturing() const {
+    return pimpl->isCapturing();
+}
+
+std::vector<ChannelSurvey> WifiManager::channelSurvey() {
+    return pimpl->channelSurvey();
+}
+
+bool WifiManager::getLinkDiagnostics(LinkDiagnostics& diagnostics) const {
+    return pimpl->getLinkDiagnostics(diagnostics);
+}
+
+bool WifiManager::getConnectionInfo(ConnectionInfo& info) const {
+    return pimpl->getConnectionInfo(info);
+}
+
+bool WifiManager::getLinkStats(LinkStats& stats) const {
+    return pimpl->getLinkStats(stats);
+}
+
+bool WifiManager::setLinuxBackend(LinuxBackend backend) {
+    return pimpl->setLinuxBackend(backend);
+}
+
+LinuxBackend WifiManager::getLinuxBackend() const {
+    return pimpl->getLinuxBackend();
+}
+
+bool WifiManager::isLocationAuthorized() const {
+    return pimpl->isLocationAuthorized();
+}
+
+bool WifiManager::requestLocationAuthorization() {
+    return pimpl->requestLocationAuthorization();
+}
+
+bool WifiManager::hasAdminPrivileges() const {
+    return pimpl->hasAdminPrivileges();
+}
+
+bool WifiManager::isRadioEnabled() const {
+    return pimpl->isRadioEnabled();
+}
+
+bool WifiManager::setRadioEnabled(bool enabled) {
+    return pimpl->setRadioEnabled(enabled);
+}
+
+bool WifiManager::isP2pSupported() const {
+    return pimpl->isP2pSupported();
+}
+
+std::vector<P2pPeer> WifiManager::p2pDiscover(int timeoutSeconds) {
+    return pimpl->p2pDiscover(timeoutSeconds);
+}
+
+bool WifiManager::p2pConnect(const P2pConnectConfig& config, int timeoutSeconds, P2pGroup& group) {
+    return pimpl->p2pConnect(config, timeoutSeconds, group);
+}
+
+bool WifiManager::p2pDisconnect() {
+    return pimpl->p2pDisconnect();
+}
+
+bool WifiManager::isNanSupported() const {
+    return pimpl->isNanSupported();
+}
+
+int WifiManager::nanPublish(const NanPublishConfig& config) {
+    return pimpl->nanPublish(config);
+}
+
+int WifiManager::nanSubscribe(const NanSubscribeConfig& config) {
+    return pimpl->nanSubscribe(config);
+}
+
+bool WifiManager::nanCancel(int handle) {
+    return pimpl->nanCancel(handle);
+}
+
+bool WifiManager::nanTransmit(int handle, int peerInstanceId, const std::string& peerAddress, const std::vector<uint8_t>& data) {
+    return pimpl->nanTransmit(handle, peerInstanceId, peerAddress, data);
+}
+
+bool WifiManager::nanNextEvent(int timeoutMs, NanEvent& event) {
+    return pimpl->nanNextEvent(timeoutMs, event);
+}
+
+bool WifiManager::isMeshSupported() const {
+    return pimpl->isMeshSupported();
+}
+
+bool WifiManager::meshJoin(const MeshConfig& config) {
+    return pimpl->meshJoin(config);
+}
+
+bool WifiManager::meshLeave() {
+    return pimpl->meshLeave();
+}
+
+std::vector<MeshPeer> WifiManager::meshPeers() {
+    return pimpl->meshPeers();
+}
+
+bool WifiManager::isAdHocSupported() const {
+    return pimpl->isAdHocSupported();
+}
+
+bool WifiManager::joinAdHoc(const AdHocConfig& config) {
+    return pimpl->joinAdHoc(config);
+}
+
+bool WifiManager::isTdlsSupported() const {
+    return pimpl->isTdlsSupported();
+}
+
+std::vector<TdlsPeer> WifiManager::tdlsDiscover(int timeoutSeconds) {
+    return pimpl->tdlsDiscover(timeoutSeconds);
+}
+
+bool WifiManager::tdlsSetup(const std::string& peerAddress) {
+    return pimpl->tdlsSetup(peerAddress);
+}
+
+bool WifiManager::tdlsTeardown(const std::string& peerAddress) {
+    return pimpl->tdlsTeardown(peerAddress);
+}
+
+bool WifiManager::isRttSupported() const {
+    return pimpl->isRttSupported();
+}
+
+bool WifiManager::measureDistance(const std::string& bssid, int frequency, RangingResult& result) {
+    return pimpl->measureDistance(bssid, frequency, result);
+}
+
+bool WifiManager::getRoamingFeatures(RoamingFeatures& features) const {
+    return pimpl->getRoamingFeatures(features);
+}
+
+bool WifiManager::roamTo(const std::string& bssid) {
+    return pimpl->roamTo(bssid);
+}
+
+bool WifiManager::setRoamingPolicy(const RoamingPolicy& policy) {
+    return pimpl->setRoamingPolicy(policy);
+}
+
+bool WifiManager::getTwtStatus(TwtStatus& status) const {
+    return pimpl->getTwtStatus(status);
+}
+
+bool WifiManager::twtSetup(const TwtConfig& config) {
+    return pimpl->twtSetup(config);
+}
+
+bool WifiManager::twtTeardown() {
+    return pimpl->twtTeardown();
+}
+
+std::vector<MloLink> WifiManager::getMloLinks() const {
+    return pimpl->getMloLinks();
+}
+
+std::string WifiManager::getRegulatoryDomain() const {
+    return pimpl->getRegulatoryDomain();
+}
+
+bool WifiManager::canSetRegulatoryDomain() const {
+    return pimpl->canSetRegulatoryDomain();
+}
+
+bool WifiManager::setRegulatoryDomain(const std::string& country) {
+    return pimpl->setRegulatoryDomain(country);
+}
+
+std::vector<ChannelInfo> WifiManager::getAllowedChannels() const {
+    return pimpl->getAllowedChannels();
+}
+
+std::string WifiManager::getInterfaceName() const {
+    return pimpl->getInterfaceName();
+}
+
+bool WifiManager::getWowlanCapabilities(WowlanCapabilities& capabilities) const {
+    return pimpl->getWowlanCapabilities(capabilities);
+}
+
+bool WifiManager::getWowlanConfig(WowlanConfig& config) const {
+    return pimpl->getWowlanConfig(config);
+}
+
+bool WifiManager::setWowlanConfig(const WowlanConfig& config) {
+    return pimpl->setWowlanConfig(config);
+}
+
+bool WifiManager::startNetworkObserver(NetworkChangeCallback callback) {
+    return pimpl->startNetworkObserver(callback);
+}
+
+bool WifiManager::stopNetworkObserver() {
+    return pimpl->stopNetworkObserver();
+}
+
+bool WifiManager::isProfileStoreSupported() const {
+    return pimpl->isProfileStoreSupported();
+}
+
+bool WifiManager::hasProfile(const std::string& name) const {
+    return pimpl->hasProfile(name);
+}
+
+bool WifiManager::connectProfile(const std::string& name) {
+    return pimpl->connectProfile(name);
+}
+
+ErrorCode WifiManager::getLastError() const {
+    return pimpl->getLastError();
+}
+
+bool WifiManager::registerEventCallback(WifiEventCallback callback, int signalThreshold) {
+    return pimpl->registerEventCallback(std::move(callback), signalThreshold);
+}
+
+bool WifiManager::unregisterEventCallback() {
+    return pimpl->unregisterEventCallback();
+}
+
+std::vector<InterfaceInfo> WifiManager::listInterfaces() const {
+    return pimpl->listInterfaces();
+}
+
+std::vector<SavedProfile> WifiManager::listProfiles() const {
+    return pimpl->listProfiles();
+}
+
+bool WifiManager::saveProfile(const std::string& ssid, const std::string& password, bool autoConnect) {
+    return pimpl->saveProfile(ssid, password, autoConnect);
+}
+
+bool WifiManager::forgetProfile(const std::string& name) {
+    return pimpl->forgetProfile(name);
+}
+
+bool WifiManager::setProfileAutoConnect(const std::string& name, bool enabled) {
+    return pimpl->setProfileAutoConnect(name, enabled);
+}
+
+bool WifiManager::startSignalMonitor(int intervalMs, SignalCallback callback) {
+    return pimpl->startSignalMonitor(intervalMs, std::move(callback));
+}
+
+bool WifiManager::stopSignalMonitor() {
+    return pimpl->stopSignalMonitor();
+}
+
+bool WifiManager::getIpInfo(IpInfo& info) const {
+    return pimpl->getIpInfo(info);
+}
+
 bool WifiManager::disconnect() {
     return pimpl->disconnect();
 }
@@ -80,6 +998,10 @@ bool WifiManager::createHotspot(const std::string& ssid, const std::string& pass
     return pimpl->createHotspot(ssid, password);
 }
 
+bool WifiManager::createHotspotWithConfig(const HotspotConfig& config) {
+    return pimpl->createHotspotWithConfig(config);
+}
+
 bool WifiManager::stopHotspot() {
     return pimpl->stopHotspot();
 }
@@ -92,4 +1014,8 @@ bool WifiManager::isHotspotSupported() const {
     return pimpl->isHotspotSupported();
 }
 
+std::vector<HotspotClient> WifiManager::hotspotClients() const {
+    return pimpl->hotspotClients();
+}
+
 } // namespace wificpp
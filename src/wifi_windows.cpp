@@ -7,15 +7,25 @@
 
 #ifdef WIFICPP_PLATFORM_WINDOWS
 
+#include <winsock2.h>
 #include <windows.h>
 #include <wlanapi.h>
+#include <iphlpapi.h>
 #include <objbase.h>
+#include <netlistmgr.h>
+#include <ocidl.h>
 #include <algorithm>
+#include <atomic>
+#include <chrono>
+#include <condition_variable>
+#include <mutex>
+#include <thread>
 #include <codecvt>
 #include <locale>
 
 #pragma comment(lib, "wlanapi.lib")
 #pragma comment(lib, "ole32.lib")
+#pragma comment(lib, "iphlpapi.lib")
 
 namespace {
 // Helper function to convert UTF-8 string to UTF-16 (wide) string
@@ -37,6 +47,61 @@ std::string wideToUtf8(const wchar_t* wideStr, size_t len = static_cast<size_t>(
         return std::string(wideStr, wideStr + (len == static_cast<size_t>(-1) ? wcslen(wideStr) : len));
     }
 }
+
+// Map a WLAN API error to the error reported through the C API
+wificpp::ErrorCode errorFromWin32(DWORD error) {
+    switch (error) {
+        case ERROR_ACCESS_DENIED:
+            return wificpp::ErrorCode::PERMISSION_DENIED;
+        case ERROR_NOT_SUPPORTED:
+            return wificpp::ErrorCode::UNSUPPORTED;
+        case ERROR_BAD_PROFILE:
+            return wificpp::ErrorCode::INVALID_CREDENTIALS;
+        case ERROR_NDIS_DOT11_POWER_STATE_INVALID:
+        case ERROR_INVALID_STATE:
+            return wificpp::ErrorCode::DRIVER_ERROR;
+        default:
+            return wificpp::ErrorCode::OPERATION_FAILED;
+    }
+}
+
+// Receives Network List Manager connectivity events and forwards them to a callback
+class NetworkEventSink : public INetworkListManagerEvents {
+public:
+    explicit NetworkEventSink(wificpp::NetworkChangeCallback callback) : callback(std::move(callback)) {}
+
+    HRESULT STDMETHODCALLTYPE QueryInterface(REFIID riid, void** object) override {
+        if (riid == __uuidof(IUnknown) || riid == __uuidof(INetworkListManagerEvents)) {
+            *object = static_cast<INetworkListManagerEvents*>(this);
+            AddRef();
+            return S_OK;
+        }
+        *object = nullptr;
+        return E_NOINTERFACE;
+    }
+
+    ULONG STDMETHODCALLTYPE AddRef() override {
+        return InterlockedIncrement(&refs);
+    }
+
+    ULONG STDMETHODCALLTYPE Release() override {
+        LONG remaining = InterlockedDecrement(&refs);
+        if (remaining == 0) {
+            delete this;
+        }
+        return remaining;
+    }
+
+    HRESULT STDMETHODCALLTYPE ConnectivityChanged(NLM_CONNECTIVITY connectivity) override {
+        (void)connectivity;
+        callback();
+        return S_OK;
+    }
+
+private:
+    LONG refs = 1;
+    wificpp::NetworkChangeCallback callback;
+};
 } // anonymous namespace
 
 namespace wificpp {
@@ -50,24 +115,52 @@ public:
         if (result != ERROR_SUCCESS) {
             throw std::runtime_error("Failed to open WLAN handle");
         }
+        
+        // Scan completion is reported through ACM notifications
+        scanNotifications = WlanRegisterNotification(clientHandle, WLAN_NOTIFICATION_SOURCE_ACM, TRUE, onNotification,
+                                                     this, nullptr, nullptr) == ERROR_SUCCESS;
+        if (!scanNotifications) {
+            Logger::getInstance().warning("Failed to register for WLAN notifications, scans will wait a fixed time");
+        }
 
         Logger::getInstance().info("WifiManager initialized on Windows platform");
     }
 
     ~WindowsWifiImpl() {
+        // Temporary profiles are discarded by the WLAN service on disconnect
+        if (ephemeralConnection) {
+            disconnect();
+        }
+        stopNetworkObserver();
         if (clientHandle != nullptr) {
+            // Closing the handle also waits for a notification callback in progress
             WlanCloseHandle(clientHandle, nullptr);
         }
     }
 
     std::vector<NetworkInfo> scan() override {
         std::vector<NetworkInfo> networks;
+        scanInterfaces(networks, false);
+        return networks;
+    }
+
+    bool scanFresh(std::vector<NetworkInfo>& networks) override {
+        return scanInterfaces(networks, true);
+    }
+
+    // Scan every interface and read its network list. A scan that did not complete falls back to the
+    // list the WLAN service has cached, unless requireFresh is set, which fails the call instead.
+    bool scanInterfaces(std::vector<NetworkInfo>& networks, bool requireFresh) {
+        if (requireFresh && !scanNotifications) {
+            Logger::getInstance().error("Scan completion cannot be detected without WLAN notifications");
+            return fail(ErrorCode::UNSUPPORTED);
+        }
         PWLAN_INTERFACE_INFO_LIST interfaceList = nullptr;
         
         DWORD result = WlanEnumInterfaces(clientHandle, nullptr, &interfaceList);
         if (result != ERROR_SUCCESS) {
             Logger::getInstance().error("Failed to enumerate WLAN interfaces");
-            return networks;
+            return fail(ErrorCode::ADAPTER_NOT_FOUND);
         }
 
         std::unique_ptr<WLAN_INTERFACE_INFO_LIST, decltype(&WlanFreeMemory)> 
@@ -75,16 +168,17 @@ public:
 
         for (DWORD i = 0; i < interfaceListPtr->dwNumberOfItems; i++) {
             PWLAN_AVAILABLE_NETWORK_LIST networkList = nullptr;
-            result = WlanScan(clientHandle, &interfaceListPtr->InterfaceInfo[i].InterfaceGuid, 
-                              nullptr, nullptr, nullptr);
-            
-            if (result != ERROR_SUCCESS) {
+            ErrorCode scanned = scanAndWait(interfaceListPtr->InterfaceInfo[i].InterfaceGuid);
+            if (scanned == ErrorCode::CANCELLED || (scanned != ErrorCode::NONE && requireFresh)) {
+                return fail(scanned);
+            }
+            if (scanned == ErrorCode::OPERATION_FAILED) {
                 Logger::getInstance().warning("Failed to initiate scan on interface ", i);
                 continue;
             }
-
-            // Wait for scan to complete (you might want to make this async in production)
-            Sleep(4000);
+            if (scanned != ErrorCode::NONE) {
+                Logger::getInstance().warning("Scan did not complete on interface ", i, ", results may be stale");
+            }
 
             result = WlanGetAvailableNetworkList(clientHandle, 
                                                 &interfaceListPtr->InterfaceInfo[i].InterfaceGuid,
@@ -92,6 +186,9 @@ public:
 
             if (result != ERROR_SUCCESS) {
                 Logger::getInstance().warning("Failed to get network list for interface ", i);
+                if (requireFresh) {
+                    return fail(ErrorCode::OPERATION_FAILED);
+                }
                 continue;
             }
 
@@ -130,7 +227,7 @@ public:
                         info.security = SecurityType::UNKNOWN;
                 }
 
-                // Get BSS list for additional information
+                // One entry per access point of the network
                 PWLAN_BSS_LIST bssList = nullptr;
                 result = WlanGetNetworkBssList(
                     clientHandle,
@@ -142,43 +239,114 @@ public:
                     &bssList
                 );
 
-                if (result == ERROR_SUCCESS && bssList && bssList->dwNumberOfItems > 0) {
+                if (result != ERROR_SUCCESS || !bssList || bssList->dwNumberOfItems == 0) {
+                    if (bssList) {
+                        WlanFreeMemory(bssList);
+                    }
+                    info.bssid = "";
+                    info.channel = 0;
+                    info.frequency = 0;
+                    
+                    // Without access points the network is listed once, by name
+                    auto it = std::find_if(networks.begin(), networks.end(),
+                                          [&info](const NetworkInfo& existingInfo) {
+                                              return existingInfo.ssid == info.ssid;
+                                          });
+                    if (it == networks.end()) {
+                        networks.push_back(info);
+                    }
+                    continue;
+                }
+                
+                for (DWORD k = 0; k < bssList->dwNumberOfItems; k++) {
+                    const WLAN_BSS_ENTRY& entry = bssList->wlanBssEntries[k];
+                    NetworkInfo ap = info;
                     char bssid[18];
                     snprintf(bssid, sizeof(bssid), "%02X:%02X:%02X:%02X:%02X:%02X",
-                            bssList->wlanBssEntries[0].dot11Bssid[0],
-                            bssList->wlanBssEntries[0].dot11Bssid[1],
-                            bssList->wlanBssEntries[0].dot11Bssid[2],
-                            bssList->wlanBssEntries[0].dot11Bssid[3],
-                            bssList->wlanBssEntries[0].dot11Bssid[4],
-                            bssList->wlanBssEntries[0].dot11Bssid[5]);
-                    info.bssid = bssid;
+                            entry.dot11Bssid[0], entry.dot11Bssid[1], entry.dot11Bssid[2],
+                            entry.dot11Bssid[3], entry.dot11Bssid[4], entry.dot11Bssid[5]);
+                    ap.bssid = bssid;
+                    // Same 0-100 scale as the network's signal quality
+                    ap.signalStrength = static_cast<int>(entry.uLinkQuality);
                     
                     // Get channel and frequency
-                    info.channel = bssList->wlanBssEntries[0].ulChCenterFrequency > 5000 ? 
-                                  (bssList->wlanBssEntries[0].ulChCenterFrequency - 5000) / 5 : 
-                                  (bssList->wlanBssEntries[0].ulChCenterFrequency - 2407) / 5;
-                    info.frequency = static_cast<int>(bssList->wlanBssEntries[0].ulChCenterFrequency);
+                    // Reported in kHz
+                    ap.frequency = static_cast<int>(entry.ulChCenterFrequency / 1000);
+                    ap.channel = frequencyToChannel(ap.frequency);
+                    if (entry.ulIeSize > 0) {
+                        const BYTE* elements = reinterpret_cast<const BYTE*>(&entry) + entry.ulIeOffset;
+                        ap.informationElements.assign(elements, elements + entry.ulIeSize);
+                    }
                     
-                    WlanFreeMemory(bssList);
-                } else {
-                    info.bssid = "";
-                    info.channel = 0;
-                    info.frequency = 0;
+                    // Networks with a profile are listed twice, with the same access points
+                    auto it = std::find_if(networks.begin(), networks.end(),
+                                          [&ap](const NetworkInfo& existingInfo) {
+                                              return existingInfo.bssid == ap.bssid;
+                                          });
+                    if (it == networks.end()) {
+                        networks.push_back(ap);
+                    }
                 }
+                WlanFreeMemory(bssList);
+            }
+        }
 
-                // Add to list only if not a duplicate
-                auto it = std::find_if(networks.begin(), networks.end(), 
-                                      [&info](const NetworkInfo& existingInfo) {
-                                          return existingInfo.ssid == info.ssid;
-                                      });
-                
-                if (it == networks.end()) {
-                    networks.push_back(info);
-                }
+        return true;
+    }
+
+    // Start a scan and wait for the WLAN service to report its end
+    ErrorCode scanAndWait(const GUID& interfaceGuid) {
+        {
+            std::lock_guard<std::mutex> lock(scanMutex);
+            scanInterface = interfaceGuid;
+            scanPending = true;
+            scanResult = ErrorCode::NONE;
+        }
+        if (WlanScan(clientHandle, &interfaceGuid, nullptr, nullptr, nullptr) != ERROR_SUCCESS) {
+            std::lock_guard<std::mutex> lock(scanMutex);
+            scanPending = false;
+            return ErrorCode::OPERATION_FAILED;
+        }
+        if (!scanNotifications) {
+            {
+                std::lock_guard<std::mutex> lock(scanMutex);
+                scanPending = false;
+            }
+            return sleepUnlessCancelled(std::min(scanTimeoutMs(), 4000)) ? ErrorCode::NONE : ErrorCode::CANCELLED;
+        }
+        
+        // Drivers must finish a scan within 4 seconds; the notification normally arrives well before.
+        // The WLAN service cannot abort a scan, a cancelled one only stops the wait
+        std::unique_lock<std::mutex> lock(scanMutex);
+        auto deadline = std::chrono::steady_clock::now() + std::chrono::milliseconds(scanTimeoutMs());
+        while (scanPending && !cancelled()) {
+            if (std::chrono::steady_clock::now() >= deadline) {
+                scanPending = false;
+                return ErrorCode::TIMEOUT;
             }
+            scanFinished.wait_for(lock, std::chrono::milliseconds(100));
         }
+        if (scanPending) {
+            scanPending = false;
+            return ErrorCode::CANCELLED;
+        }
+        return scanResult;
+    }
 
-        return networks;
+    static VOID WINAPI onNotification(PWLAN_NOTIFICATION_DATA data, PVOID context) {
+        if (data->NotificationSource != WLAN_NOTIFICATION_SOURCE_ACM ||
+            (data->NotificationCode != wlan_notification_acm_scan_complete &&
+             data->NotificationCode != wlan_notification_acm_scan_fail)) {
+            return;
+        }
+        auto* impl = static_cast<WindowsWifiImpl*>(context);
+        std::lock_guard<std::mutex> lock(impl->scanMutex);
+        if (impl->scanPending && IsEqualGUID(data->InterfaceGuid, impl->scanInterface)) {
+            impl->scanPending = false;
+            impl->scanResult = data->NotificationCode == wlan_notification_acm_scan_complete
+                ? ErrorCode::NONE : ErrorCode::DRIVER_ERROR;
+            impl->scanFinished.notify_all();
+        }
     }
 
     bool connect(const std::string& ssid, const std::string& password) override {
@@ -195,45 +363,12 @@ public:
 
         if (interfaceListPtr->dwNumberOfItems == 0) {
             Logger::getInstance().error("No WLAN interfaces found");
-            return false;
+            return fail(ErrorCode::ADAPTER_NOT_FOUND);
         }
 
         GUID interfaceGuid = interfaceListPtr->InterfaceInfo[0].InterfaceGuid;
 
-        // Create profile XML
-        std::string profileXmlStr =
-            "<?xml version=\"1.0\"?>"
-            "<WLANProfile xmlns=\"http://www.microsoft.com/networking/WLAN/profile/v1\">"
-            "<name>" + ssid + "</name>"
-            "<SSIDConfig>"
-            "<SSID>"
-            "<name>" + ssid + "</name>"
-            "</SSID>"
-            "</SSIDConfig>"
-            "<connectionType>ESS</connectionType>"
-            "<connectionMode>auto</connectionMode>"
-            "<MSM>"
-            "<security>"
-            "<authEncryption>" +
-            (password.empty() ?
-                std::string("<authentication>open</authentication><encryption>none</encryption>") :
-                std::string("<authentication>WPA2PSK</authentication><encryption>AES</encryption>")) +
-            "</authEncryption>";
-            
-        if (!password.empty()) {
-            profileXmlStr += "<sharedKey>"
-                            "<keyType>passPhrase</keyType>"
-                            "<protected>false</protected>"
-                            "<keyMaterial>" + password + "</keyMaterial>"
-                            "</sharedKey>";
-        }
-            
-        profileXmlStr += "<useOneX>false</useOneX>"
-                        "</security>"
-                        "</MSM>"
-                        "</WLANProfile>";
-                        
-        std::wstring profileXml = utf8ToWide(profileXmlStr);
+        std::wstring profileXml = utf8ToWide(buildProfileXml(ssid, password, true));
 
         // Set the profile
         DWORD reasonCode = 0;
@@ -250,7 +385,7 @@ public:
         
         if (result != ERROR_SUCCESS) {
             Logger::getInstance().error("Failed to set connection profile, reason code: ", reasonCode);
-            return false;
+            return fail(errorFromWin32(result));
         }
 
         // Connect using the profile
@@ -272,9 +407,95 @@ public:
         
         if (result != ERROR_SUCCESS) {
             Logger::getInstance().error("Failed to connect to network");
+            return fail(errorFromWin32(result));
+        }
+        
+        Logger::getInstance().info("Successfully connected to network: ", ssid);
+        return true;
+    }
+
+    bool connectWithOptions(const std::string& ssid, const std::string& password, const ConnectOptions& options) override {
+        if (!isWellFormed(options.staticIp)) {
+            return fail(ErrorCode::OPERATION_FAILED);
+        }
+        if (!options.ephemeral && !options.hidden && options.bssid.empty() && options.staticIp.empty()) {
+            return connect(ssid, password);
+        }
+        
+        PWLAN_INTERFACE_INFO_LIST interfaceList = nullptr;
+        DWORD result = WlanEnumInterfaces(clientHandle, nullptr, &interfaceList);
+        
+        if (result != ERROR_SUCCESS) {
+            Logger::getInstance().error("Failed to enumerate WLAN interfaces");
             return false;
         }
+
+        std::unique_ptr<WLAN_INTERFACE_INFO_LIST, decltype(&WlanFreeMemory)> 
+            interfaceListPtr(interfaceList, WlanFreeMemory);
+
+        if (interfaceListPtr->dwNumberOfItems == 0) {
+            Logger::getInstance().error("No WLAN interfaces found");
+            return fail(ErrorCode::ADAPTER_NOT_FOUND);
+        }
+
+        GUID interfaceGuid = interfaceListPtr->InterfaceInfo[0].InterfaceGuid;
+        std::wstring profileXml = utf8ToWide(buildProfileXml(ssid, password, !options.ephemeral, options.hidden));
+        std::wstring profileName = utf8ToWide(ssid);
+        WLAN_CONNECTION_PARAMETERS connectionParams = {};
+        if (options.ephemeral) {
+            // A temporary profile is passed inline and never stored by the WLAN service
+            connectionParams.wlanConnectionMode = wlan_connection_mode_temporary_profile;
+            connectionParams.strProfile = profileXml.c_str();
+        } else {
+            DWORD reasonCode = 0;
+            result = WlanSetProfile(clientHandle, &interfaceGuid, 0, profileXml.c_str(), nullptr, TRUE, nullptr, &reasonCode);
+            if (result != ERROR_SUCCESS) {
+                Logger::getInstance().error("Failed to set connection profile, reason code: ", reasonCode);
+                return fail(errorFromWin32(result));
+            }
+            connectionParams.wlanConnectionMode = wlan_connection_mode_profile;
+            connectionParams.strProfile = profileName.c_str();
+        }
+        connectionParams.pDot11Ssid = nullptr;
+        connectionParams.pDesiredBssidList = nullptr;
+        connectionParams.dot11BssType = dot11_BSS_type_infrastructure;
+        connectionParams.dwFlags = 0;
+        
+        DOT11_BSSID_LIST bssidList = {};
+        if (!options.bssid.empty()) {
+            unsigned int octets[6];
+            if (sscanf(options.bssid.c_str(), "%x:%x:%x:%x:%x:%x",
+                       &octets[0], &octets[1], &octets[2], &octets[3], &octets[4], &octets[5]) != 6) {
+                Logger::getInstance().error("Invalid BSSID: ", options.bssid);
+                return false;
+            }
+            bssidList.Header.Type = NDIS_OBJECT_TYPE_DEFAULT;
+            bssidList.Header.Revision = DOT11_BSSID_LIST_REVISION_1;
+            bssidList.Header.Size = sizeof(DOT11_BSSID_LIST);
+            bssidList.uNumOfEntries = 1;
+            bssidList.uTotalNumOfEntries = 1;
+            for (int i = 0; i < 6; i++) {
+                bssidList.BSSIDs[0][i] = static_cast<UCHAR>(octets[i]);
+            }
+            connectionParams.pDesiredBssidList = &bssidList;
+        }
         
+        result = WlanConnect(
+            clientHandle,
+            &interfaceGuid,
+            &connectionParams,
+            nullptr
+        );
+        
+        if (result != ERROR_SUCCESS) {
+            Logger::getInstance().error("Failed to connect to network");
+            return fail(errorFromWin32(result));
+        }
+        
+        ephemeralConnection = options.ephemeral;
+        if (!options.staticIp.empty() && !applyStaticIp(interfaceGuid, options.staticIp)) {
+            return fail(ErrorCode::OPERATION_FAILED);
+        }
         Logger::getInstance().info("Successfully connected to network: ", ssid);
         return true;
     }
@@ -293,7 +514,7 @@ public:
 
         if (interfaceListPtr->dwNumberOfItems == 0) {
             Logger::getInstance().error("No WLAN interfaces found");
-            return false;
+            return fail(ErrorCode::ADAPTER_NOT_FOUND);
         }
 
         result = WlanDisconnect(clientHandle, 
@@ -302,9 +523,10 @@ public:
         
         if (result != ERROR_SUCCESS) {
             Logger::getInstance().error("Failed to disconnect from network");
-            return false;
+            return fail(errorFromWin32(result));
         }
         
+        ephemeralConnection = false;
         Logger::getInstance().info("Successfully disconnected from network");
         return true;
     }
@@ -357,8 +579,568 @@ public:
         return false;  // Not supported yet
     }
 
+    bool getConnectionInfo(ConnectionInfo& info) const override {
+        GUID interfaceGuid;
+        if (!firstInterface(interfaceGuid)) {
+            return false;
+        }
+        
+        DWORD size = 0;
+        PWLAN_CONNECTION_ATTRIBUTES connection = nullptr;
+        if (WlanQueryInterface(clientHandle, &interfaceGuid, wlan_intf_opcode_current_connection, nullptr,
+                               &size, reinterpret_cast<PVOID*>(&connection), nullptr) != ERROR_SUCCESS) {
+            return false;
+        }
+        bool connected = connection->isState == wlan_interface_state_connected;
+        if (connected) {
+            const DOT11_SSID& ssid = connection->wlanAssociationAttributes.dot11Ssid;
+            info.ssid.assign(reinterpret_cast<const char*>(ssid.ucSSID), ssid.uSSIDLength);
+            const UCHAR* mac = connection->wlanAssociationAttributes.dot11Bssid;
+            char bssid[18];
+            snprintf(bssid, sizeof(bssid), "%02x:%02x:%02x:%02x:%02x:%02x",
+                     mac[0], mac[1], mac[2], mac[3], mac[4], mac[5]);
+            info.bssid = bssid;
+            // Rates are reported in kbit/s
+            info.linkSpeedMbps = static_cast<int>(connection->wlanAssociationAttributes.ulTxRate / 1000);
+            // Signal quality is linear between -100 dBm (0) and -50 dBm (100)
+            info.signalStrength = static_cast<int>(connection->wlanAssociationAttributes.wlanSignalQuality) / 2 - 100;
+        }
+        WlanFreeMemory(connection);
+        
+        LONG* rssi = nullptr;
+        if (connected && WlanQueryInterface(clientHandle, &interfaceGuid, wlan_intf_opcode_rssi, nullptr,
+                                            &size, reinterpret_cast<PVOID*>(&rssi), nullptr) == ERROR_SUCCESS) {
+            info.signalStrength = static_cast<int>(*rssi);
+            WlanFreeMemory(rssi);
+        }
+        return connected;
+    }
+
+    bool getLinkStats(LinkStats& stats) const override {
+        GUID interfaceGuid;
+        if (!firstInterface(interfaceGuid)) {
+            return false;
+        }
+        
+        DWORD size = 0;
+        PWLAN_CONNECTION_ATTRIBUTES connection = nullptr;
+        if (WlanQueryInterface(clientHandle, &interfaceGuid, wlan_intf_opcode_current_connection, nullptr,
+                               &size, reinterpret_cast<PVOID*>(&connection), nullptr) != ERROR_SUCCESS) {
+            return false;
+        }
+        bool connected = connection->isState == wlan_interface_state_connected;
+        if (connected) {
+            // Rates are reported in kbit/s
+            stats.txBitrateKbps = connection->wlanAssociationAttributes.ulTxRate;
+            stats.rxBitrateKbps = connection->wlanAssociationAttributes.ulRxRate;
+        }
+        WlanFreeMemory(connection);
+        
+        PWLAN_STATISTICS statistics = nullptr;
+        if (connected && WlanQueryInterface(clientHandle, &interfaceGuid, wlan_intf_opcode_statistics, nullptr,
+                                            &size, reinterpret_cast<PVOID*>(&statistics), nullptr) == ERROR_SUCCESS) {
+            stats.txPackets = static_cast<int64_t>(statistics->MacUcastCounters.ullTransmittedFrameCount);
+            stats.rxPackets = static_cast<int64_t>(statistics->MacUcastCounters.ullReceivedFrameCount);
+            stats.txRetries = 0;
+            stats.txFailed = 0;
+            for (DWORD i = 0; i < statistics->dwNumberOfPhys; i++) {
+                stats.txRetries += static_cast<int64_t>(statistics->PhyCounters[i].ullRetryCount);
+                stats.txFailed += static_cast<int64_t>(statistics->PhyCounters[i].ullFailedCount);
+            }
+            WlanFreeMemory(statistics);
+        }
+        return connected;
+    }
+
+    bool roamTo(const std::string& bssid) override {
+        GUID interfaceGuid;
+        if (!firstInterface(interfaceGuid)) {
+            Logger::getInstance().error("No WLAN interfaces found");
+            return fail(ErrorCode::ADAPTER_NOT_FOUND);
+        }
+        unsigned int octets[6];
+        if (sscanf(bssid.c_str(), "%x:%x:%x:%x:%x:%x",
+                   &octets[0], &octets[1], &octets[2], &octets[3], &octets[4], &octets[5]) != 6) {
+            Logger::getInstance().error("Invalid BSSID: ", bssid);
+            return fail(ErrorCode::OPERATION_FAILED);
+        }
+        
+        DWORD size = 0;
+        PWLAN_CONNECTION_ATTRIBUTES connection = nullptr;
+        if (WlanQueryInterface(clientHandle, &interfaceGuid, wlan_intf_opcode_current_connection, nullptr,
+                               &size, reinterpret_cast<PVOID*>(&connection), nullptr) != ERROR_SUCCESS) {
+            Logger::getInstance().error("Cannot roam without a connection");
+            return fail(ErrorCode::OPERATION_FAILED);
+        }
+        std::wstring profileName = connection->strProfileName;
+        WLAN_CONNECTION_MODE mode = connection->wlanConnectionMode;
+        WlanFreeMemory(connection);
+        if (mode != wlan_connection_mode_profile) {
+            Logger::getInstance().error("Roaming requires a connection made with a stored profile");
+            return fail(ErrorCode::UNSUPPORTED);
+        }
+        
+        // Connecting again with the same profile and a desired BSSID makes the
+        // WLAN service re-associate with that access point
+        DOT11_BSSID_LIST bssidList = {};
+        bssidList.Header.Type = NDIS_OBJECT_TYPE_DEFAULT;
+        bssidList.Header.Revision = DOT11_BSSID_LIST_REVISION_1;
+        bssidList.Header.Size = sizeof(DOT11_BSSID_LIST);
+        bssidList.uNumOfEntries = 1;
+        bssidList.uTotalNumOfEntries = 1;
+        for (int i = 0; i < 6; i++) {
+            bssidList.BSSIDs[0][i] = static_cast<UCHAR>(octets[i]);
+        }
+        WLAN_CONNECTION_PARAMETERS connectionParams = {};
+        connectionParams.wlanConnectionMode = wlan_connection_mode_profile;
+        connectionParams.strProfile = profileName.c_str();
+        connectionParams.pDesiredBssidList = &bssidList;
+        connectionParams.dot11BssType = dot11_BSS_type_infrastructure;
+        
+        DWORD result = WlanConnect(clientHandle, &interfaceGuid, &connectionParams, nullptr);
+        if (result != ERROR_SUCCESS) {
+            Logger::getInstance().error("Failed to roam to ", bssid, ", error: ", result);
+            return fail(errorFromWin32(result));
+        }
+        Logger::getInstance().info("Roaming to ", bssid);
+        return true;
+    }
+
+    bool hasAdminPrivileges() const override {
+        HANDLE token = nullptr;
+        if (!OpenProcessToken(GetCurrentProcess(), TOKEN_QUERY, &token)) {
+            return false;
+        }
+        TOKEN_ELEVATION elevation = {};
+        DWORD size = 0;
+        bool elevated = GetTokenInformation(token, TokenElevation, &elevation, sizeof(elevation), &size) &&
+                        elevation.TokenIsElevated != 0;
+        CloseHandle(token);
+        return elevated;
+    }
+
+    bool isRadioEnabled() const override {
+        GUID interfaceGuid;
+        DWORD size = 0;
+        PWLAN_RADIO_STATE state = nullptr;
+        if (!firstInterface(interfaceGuid) ||
+            WlanQueryInterface(clientHandle, &interfaceGuid, wlan_intf_opcode_radio_state, nullptr,
+                               &size, reinterpret_cast<PVOID*>(&state), nullptr) != ERROR_SUCCESS) {
+            return true;
+        }
+        bool enabled = false;
+        for (DWORD i = 0; i < state->dwNumberOfPhys; i++) {
+            if (state->PhyRadioState[i].dot11SoftwareRadioState == dot11_radio_state_on &&
+                state->PhyRadioState[i].dot11HardwareRadioState == dot11_radio_state_on) {
+                enabled = true;
+            }
+        }
+        WlanFreeMemory(state);
+        return enabled;
+    }
+
+    bool setRadioEnabled(bool enabled) override {
+        GUID interfaceGuid;
+        if (!firstInterface(interfaceGuid)) {
+            Logger::getInstance().error("No WLAN interfaces found");
+            return fail(ErrorCode::ADAPTER_NOT_FOUND);
+        }
+        DWORD size = 0;
+        PWLAN_RADIO_STATE state = nullptr;
+        DWORD result = WlanQueryInterface(clientHandle, &interfaceGuid, wlan_intf_opcode_radio_state, nullptr,
+                                          &size, reinterpret_cast<PVOID*>(&state), nullptr);
+        if (result != ERROR_SUCCESS) {
+            Logger::getInstance().error("Failed to query the radio state, error: ", result);
+            return fail(errorFromWin32(result));
+        }
+        DWORD phys = state->dwNumberOfPhys;
+        bool hardwareOff = false;
+        for (DWORD i = 0; i < phys; i++) {
+            hardwareOff = hardwareOff || state->PhyRadioState[i].dot11HardwareRadioState == dot11_radio_state_off;
+        }
+        WlanFreeMemory(state);
+        
+        // The software switch is set per PHY of the adapter
+        for (DWORD i = 0; i < phys; i++) {
+            WLAN_PHY_RADIO_STATE radio = {};
+            radio.dwPhyIndex = i;
+            radio.dot11SoftwareRadioState = enabled ? dot11_radio_state_on : dot11_radio_state_off;
+            result = WlanSetInterface(clientHandle, &interfaceGuid, wlan_intf_opcode_radio_state,
+                                      sizeof(radio), &radio, nullptr);
+            if (result != ERROR_SUCCESS) {
+                Logger::getInstance().error("Failed to switch the radio, error: ", result);
+                return fail(errorFromWin32(result));
+            }
+        }
+        if (enabled && hardwareOff) {
+            Logger::getInstance().error("The radio is switched off by a hardware switch");
+            return fail(ErrorCode::OPERATION_FAILED);
+        }
+        Logger::getInstance().info(enabled ? "Radio switched on" : "Radio switched off");
+        return true;
+    }
+
+    std::vector<InterfaceInfo> listInterfaces() const override {
+        std::vector<InterfaceInfo> interfaces;
+        PWLAN_INTERFACE_INFO_LIST interfaceList = nullptr;
+        if (WlanEnumInterfaces(clientHandle, nullptr, &interfaceList) != ERROR_SUCCESS) {
+            Logger::getInstance().error("Failed to enumerate WLAN interfaces");
+            return interfaces;
+        }
+        for (DWORD i = 0; i < interfaceList->dwNumberOfItems; i++) {
+            InterfaceInfo info;
+            // WLAN interfaces are identified by their adapter description
+            info.name = wideToUtf8(interfaceList->InterfaceInfo[i].strInterfaceDescription);
+            info.driver = info.name;
+            info.selected = i == 0;
+            interfaces.push_back(info);
+        }
+        WlanFreeMemory(interfaceList);
+        return interfaces;
+    }
+
+    bool isProfileStoreSupported() const override {
+        return true;
+    }
+
+    bool hasProfile(const std::string& name) const override {
+        GUID interfaceGuid;
+        if (!firstInterface(interfaceGuid)) {
+            return false;
+        }
+        std::wstring profileName = utf8ToWide(name);
+        LPWSTR profileXml = nullptr;
+        DWORD flags = 0;
+        DWORD access = 0;
+        DWORD result = WlanGetProfile(clientHandle, &interfaceGuid, profileName.c_str(), nullptr,
+                                      &profileXml, &flags, &access);
+        if (result != ERROR_SUCCESS) {
+            return false;
+        }
+        WlanFreeMemory(profileXml);
+        return true;
+    }
+
+    bool connectProfile(const std::string& name) override {
+        GUID interfaceGuid;
+        if (!firstInterface(interfaceGuid)) {
+            Logger::getInstance().error("No WLAN interfaces found");
+            return fail(ErrorCode::ADAPTER_NOT_FOUND);
+        }
+        
+        std::wstring profileName = utf8ToWide(name);
+        WLAN_CONNECTION_PARAMETERS connectionParams = {};
+        connectionParams.wlanConnectionMode = wlan_connection_mode_profile;
+        connectionParams.strProfile = profileName.c_str();
+        connectionParams.dot11BssType = dot11_BSS_type_infrastructure;
+        
+        DWORD result = WlanConnect(clientHandle, &interfaceGuid, &connectionParams, nullptr);
+        if (result != ERROR_SUCCESS) {
+            Logger::getInstance().error("Failed to connect with profile ", name, ", error: ", result);
+            return fail(errorFromWin32(result));
+        }
+        Logger::getInstance().info("Connecting with profile: ", name);
+        return true;
+    }
+
+    std::vector<SavedProfile> listProfiles() const override {
+        std::vector<SavedProfile> profiles;
+        GUID interfaceGuid;
+        PWLAN_PROFILE_INFO_LIST profileList = nullptr;
+        if (!firstInterface(interfaceGuid) ||
+            WlanGetProfileList(clientHandle, &interfaceGuid, nullptr, &profileList) != ERROR_SUCCESS) {
+            return profiles;
+        }
+        for (DWORD i = 0; i < profileList->dwNumberOfItems; i++) {
+            SavedProfile profile;
+            profile.name = wideToUtf8(profileList->ProfileInfo[i].strProfileName);
+            std::string xml = profileXml(interfaceGuid, profile.name);
+            profile.ssid = xmlElement(xmlElement(xml, "SSID"), "name");
+            profile.autoConnect = xmlElement(xml, "connectionMode") != "manual";
+            std::string authentication = xmlElement(xml, "authentication");
+            if (authentication == "open") {
+                profile.security = xmlElement(xml, "encryption") == "WEP" ? SecurityType::WEP : SecurityType::NONE;
+            } else if (authentication == "WPA3SAE" || authentication == "WPA3ENT192" || authentication == "WPA3ENT") {
+                profile.security = SecurityType::WPA3;
+            } else if (authentication == "WPA2PSK" || authentication == "WPA2") {
+                profile.security = SecurityType::WPA2;
+            } else if (authentication == "WPAPSK" || authentication == "WPA") {
+                profile.security = SecurityType::WPA;
+            }
+            profiles.push_back(profile);
+        }
+        WlanFreeMemory(profileList);
+        return profiles;
+    }
+
+    bool saveProfile(const std::string& ssid, const std::string& password, bool autoConnect) override {
+        GUID interfaceGuid;
+        if (!firstInterface(interfaceGuid)) {
+            Logger::getInstance().error("No WLAN interfaces found");
+            return fail(ErrorCode::ADAPTER_NOT_FOUND);
+        }
+        return setProfileXml(interfaceGuid, buildProfileXml(ssid, password, autoConnect));
+    }
+
+    bool forgetProfile(const std::string& name) override {
+        GUID interfaceGuid;
+        if (!firstInterface(interfaceGuid)) {
+            Logger::getInstance().error("No WLAN interfaces found");
+            return fail(ErrorCode::ADAPTER_NOT_FOUND);
+        }
+        DWORD result = WlanDeleteProfile(clientHandle, &interfaceGuid, utf8ToWide(name).c_str(), nullptr);
+        if (result != ERROR_SUCCESS) {
+            Logger::getInstance().error("Failed to delete profile ", name, ", error: ", result);
+            return fail(errorFromWin32(result));
+        }
+        return true;
+    }
+
+    bool setProfileAutoConnect(const std::string& name, bool enabled) override {
+        GUID interfaceGuid;
+        if (!firstInterface(interfaceGuid)) {
+            Logger::getInstance().error("No WLAN interfaces found");
+            return fail(ErrorCode::ADAPTER_NOT_FOUND);
+        }
+        std::string xml = profileXml(interfaceGuid, name);
+        size_t start = xml.find("<connectionMode>");
+        size_t end = xml.find("</connectionMode>");
+        if (start == std::string::npos || end == std::string::npos) {
+            Logger::getInstance().error("Profile not found: ", name);
+            return false;
+        }
+        start += strlen("<connectionMode>");
+        xml.replace(start, end - start, enabled ? "auto" : "manual");
+        return setProfileXml(interfaceGuid, xml);
+    }
+
+    bool startNetworkObserver(NetworkChangeCallback callback) override {
+        if (observerThread.joinable()) {
+            Logger::getInstance().error("A network observer is already running");
+            return false;
+        }
+        
+        observerStop = CreateEventW(nullptr, TRUE, FALSE, nullptr);
+        if (observerStop == nullptr) {
+            return false;
+        }
+        std::atomic<int> started{0};
+        
+        // The subscription lives in its own multithreaded apartment, so events arrive without a message loop
+        observerThread = std::thread([this, callback, &started]() {
+            if (FAILED(CoInitializeEx(nullptr, COINIT_MULTITHREADED))) {
+                started = -1;
+                return;
+            }
+            INetworkListManager* manager = nullptr;
+            IConnectionPointContainer* container = nullptr;
+            IConnectionPoint* point = nullptr;
+            NetworkEventSink* sink = new NetworkEventSink(callback);
+            DWORD cookie = 0;
+            bool subscribed =
+                SUCCEEDED(CoCreateInstance(__uuidof(NetworkListManager), nullptr, CLSCTX_ALL,
+                                           __uuidof(INetworkListManager), reinterpret_cast<void**>(&manager))) &&
+                SUCCEEDED(manager->QueryInterface(__uuidof(IConnectionPointContainer), reinterpret_cast<void**>(&container))) &&
+                SUCCEEDED(container->FindConnectionPoint(__uuidof(INetworkListManagerEvents), &point)) &&
+                SUCCEEDED(point->Advise(sink, &cookie));
+            started = subscribed ? 1 : -1;
+            
+            if (subscribed) {
+                WaitForSingleObject(observerStop, INFINITE);
+                point->Unadvise(cookie);
+            }
+            if (point) {
+                point->Release();
+            }
+            if (container) {
+                container->Release();
+            }
+            if (manager) {
+                manager->Release();
+            }
+            sink->Release();
+            CoUninitialize();
+        });
+        
+        while (started == 0) {
+            std::this_thread::sleep_for(std::chrono::milliseconds(10));
+        }
+        if (started < 0) {
+            Logger::getInstance().error("Failed to subscribe to Network List Manager events");
+            observerThread.join();
+            CloseHandle(observerStop);
+            observerStop = nullptr;
+            return false;
+        }
+        return true;
+    }
+
+    bool stopNetworkObserver() override {
+        if (!observerThread.joinable()) {
+            return true;
+        }
+        SetEvent(observerStop);
+        observerThread.join();
+        CloseHandle(observerStop);
+        observerStop = nullptr;
+        return true;
+    }
+
 private:
+    // Configure the adapter's addressing with netsh, which needs the interface alias rather than the GUID
+    bool applyStaticIp(const GUID& interfaceGuid, const StaticIpConfig& addressing) {
+        NET_LUID luid;
+        wchar_t alias[NDIS_IF_MAX_STRING_SIZE + 1] = {};
+        if (ConvertInterfaceGuidToLuid(&interfaceGuid, &luid) != NO_ERROR ||
+            ConvertInterfaceLuidToAlias(&luid, alias, NDIS_IF_MAX_STRING_SIZE + 1) != NO_ERROR) {
+            Logger::getInstance().error("Failed to look up the interface name");
+            return false;
+        }
+        std::string name = "\"" + wideToUtf8(alias) + "\"";
+        auto netsh = [](const std::string& command) {
+            return system(("netsh interface " + command + " > NUL 2>&1").c_str()) == 0;
+        };
+        
+        if (!addressing.ipv4Address.empty() &&
+            !netsh("ipv4 set address name=" + name + " source=static address=" + addressing.ipv4Address +
+                   " gateway=" + (addressing.ipv4Gateway.empty() ? "none" : addressing.ipv4Gateway))) {
+            Logger::getInstance().error("Failed to assign ", addressing.ipv4Address);
+            return false;
+        }
+        if (!addressing.ipv6Address.empty()) {
+            if (!netsh("ipv6 add address interface=" + name + " address=" + addressing.ipv6Address)) {
+                Logger::getInstance().error("Failed to assign ", addressing.ipv6Address);
+                return false;
+            }
+            if (!addressing.ipv6Gateway.empty() &&
+                !netsh("ipv6 add route prefix=::/0 interface=" + name + " nexthop=" + addressing.ipv6Gateway)) {
+                Logger::getInstance().error("Failed to set the default route via ", addressing.ipv6Gateway);
+                return false;
+            }
+        }
+        
+        // The first server of each family replaces the DHCP-provided ones, the rest are appended
+        bool firstV4 = true;
+        bool firstV6 = true;
+        for (const auto& server : addressing.dnsServers) {
+            bool v6 = server.find(':') != std::string::npos;
+            bool& first = v6 ? firstV6 : firstV4;
+            std::string command = std::string(v6 ? "ipv6" : "ipv4") +
+                                  (first ? " set dnsservers name=" + name + " source=static address=" + server
+                                         : " add dnsservers name=" + name + " address=" + server) +
+                                  " validate=no";
+            if (!netsh(command)) {
+                Logger::getInstance().error("Failed to set DNS server ", server);
+                return false;
+            }
+            first = false;
+        }
+        return true;
+    }
+
+    // XML of a stored profile, empty if it does not exist
+    std::string profileXml(const GUID& interfaceGuid, const std::string& name) const {
+        LPWSTR xml = nullptr;
+        DWORD flags = 0;
+        DWORD access = 0;
+        if (WlanGetProfile(clientHandle, &interfaceGuid, utf8ToWide(name).c_str(), nullptr,
+                           &xml, &flags, &access) != ERROR_SUCCESS) {
+            return "";
+        }
+        std::string result = wideToUtf8(xml);
+        WlanFreeMemory(xml);
+        return result;
+    }
+
+    // Store a profile, replacing one of the same name
+    bool setProfileXml(const GUID& interfaceGuid, const std::string& xml) {
+        DWORD reasonCode = 0;
+        DWORD result = WlanSetProfile(clientHandle, &interfaceGuid, 0, utf8ToWide(xml).c_str(),
+                                      nullptr, TRUE, nullptr, &reasonCode);
+        if (result != ERROR_SUCCESS) {
+            Logger::getInstance().error("Failed to store profile, error: ", result, ", reason: ", reasonCode);
+            return fail(errorFromWin32(result));
+        }
+        return true;
+    }
+
+    // Contents of the first element with the given tag
+    static std::string xmlElement(const std::string& xml, const std::string& tag) {
+        size_t start = xml.find("<" + tag + ">");
+        if (start == std::string::npos) {
+            return "";
+        }
+        start += tag.size() + 2;
+        size_t end = xml.find("</" + tag + ">", start);
+        return end == std::string::npos ? "" : xml.substr(start, end - start);
+    }
+
+    // GUID of the first WLAN interface
+    bool firstInterface(GUID& interfaceGuid) const {
+        PWLAN_INTERFACE_INFO_LIST interfaceList = nullptr;
+        if (WlanEnumInterfaces(clientHandle, nullptr, &interfaceList) != ERROR_SUCCESS) {
+            return false;
+        }
+        bool found = interfaceList->dwNumberOfItems > 0;
+        if (found) {
+            interfaceGuid = interfaceList->InterfaceInfo[0].InterfaceGuid;
+        }
+        WlanFreeMemory(interfaceList);
+        return found;
+    }
+
+    // Build a WLAN profile for an open or WPA2-Personal network
+    std::string buildProfileXml(const std::string& ssid, const std::string& password, bool autoConnect,
+                                bool hidden = false) const {
+        std::string profileXmlStr =
+            "<?xml version=\"1.0\"?>"
+            "<WLANProfile xmlns=\"http://www.microsoft.com/networking/WLAN/profile/v1\">"
+            "<name>" + ssid + "</name>"
+            "<SSIDConfig>"
+            "<SSID>"
+            "<name>" + ssid + "</name>"
+            "</SSID>"
+            "<nonBroadcast>" + std::string(hidden ? "true" : "false") + "</nonBroadcast>"
+            "</SSIDConfig>"
+            "<connectionType>ESS</connectionType>"
+            "<connectionMode>" + std::string(autoConnect ? "auto" : "manual") + "</connectionMode>"
+            "<MSM>"
+            "<security>"
+            "<authEncryption>" +
+            (password.empty() ?
+                std::string("<authentication>open</authentication><encryption>none</encryption>") :
+                std::string("<authentication>WPA2PSK</authentication><encryption>AES</encryption>")) +
+            "</authEncryption>";
+            
+        if (!password.empty()) {
+            profileXmlStr += "<sharedKey>"
+                            "<keyType>passPhrase</keyType>"
+                            "<protected>false</protected>"
+                            "<keyMaterial>" + password + "</keyMaterial>"
+                            "</sharedKey>";
+        }
+            
+        profileXmlStr += "<useOneX>false</useOneX>"
+                        "</security>"
+                        "</MSM>"
+                        "</WLANProfile>";
+        return profileXmlStr;
+    }
+
     HANDLE clientHandle = nullptr;
+    bool ephemeralConnection = false;
+    
+    // The scan waiting for its completion notification
+    bool scanNotifications = false;
+    std::mutex scanMutex;
+    std::condition_variable scanFinished;
+    GUID scanInterface = {};
+    bool scanPending = false;
+    ErrorCode scanResult = ErrorCode::NONE;
+    
+    // Network List Manager subscription thread and the event that ends it
+    std::thread observerThread;
+    HANDLE observerStop = nullptr;
 };
 
 // Factory function implementation for Windows
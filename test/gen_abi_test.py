@@ -0,0 +1,162 @@
+#!/usr/bin/env python3
+"""Generate test/test_abi.c from include/wifi_c_api.h.
+
+The expectations are taken from the header as it is now: enum values as written,
+struct layouts as measured by compiling a probe with $CC (default cc) on a 64-bit
+host, and every declared function and callback type.
+
+    python3 test/gen_abi_test.py          rewrite test/test_abi.c
+    python3 test/gen_abi_test.py --check  fail if test/test_abi.c is out of date
+
+Run --check after changing the header: it fails when something was added to the
+header without being pinned in test_abi.c. Regenerating after an incompatible
+change only succeeds once WIFI_API_VERSION_MAJOR matches the expectation below.
+"""
+
+import os
+import re
+import subprocess
+import sys
+import tempfile
+
+ROOT = os.path.dirname(os.path.dirname(os.path.abspath(__file__)))
+HEADER = os.path.join(ROOT, "include", "wifi_c_api.h")
+OUTPUT = os.path.join(ROOT, "test", "test_abi.c")
+
+# Keep in step with WIFI_API_VERSION_MAJOR; bumping it is a deliberate act
+API_VERSION_MAJOR = 2
+
+PREAMBLE = """\
+// Binary interface checks for the C API in wifi_c_api.h
+//
+// Bindings in other languages (C#, Python, ...) hard-code the layouts, values and
+// signatures below. This file only compiles and runs while the header still matches
+// them, so an accidental break fails the build. When a change is intended, bump
+// WIFI_API_VERSION_MAJOR, rename the version node in src/wifi_c_api.map and
+// regenerate this file with test/gen_abi_test.py.
+
+#include "wifi_c_api.h"
+#include <stddef.h>
+#include <stdio.h>
+
+#define EXPECT_SIZE(type, size) _Static_assert(sizeof(type) == (size), "size of " #type " changed")
+#define EXPECT_OFFSET(type, field, offset) \\
+    _Static_assert(offsetof(type, field) == (offset), "offset of " #type "." #field " changed")
+#define EXPECT_VALUE(name, value) _Static_assert((name) == (value), "value of " #name " changed")
+
+_Static_assert(WIFI_API_VERSION_MAJOR == {major}, "update the expectations below for the new major version");
+
+// Enum values
+"""
+
+MAIN = """\
+int main(void) {
+    if (wifi_api_version() != WIFI_API_VERSION) {
+        fprintf(stderr, "library reports C API version 0x%08x, header is 0x%08x\\n",
+                (unsigned)wifi_api_version(), (unsigned)WIFI_API_VERSION);
+        return 1;
+    }
+    for (size_t i = 0; i < sizeof(exported) / sizeof(exported[0]); i++) {
+        if (!exported[i]) {
+            fprintf(stderr, "function %zu is not exported\\n", i);
+            return 1;
+        }
+    }
+    printf("C API %d.%d: %zu functions checked\\n", WIFI_API_VERSION_MAJOR, WIFI_API_VERSION_MINOR,
+           sizeof(exported) / sizeof(exported[0]));
+    return 0;
+}
+"""
+
+
+def parse(header):
+    structs, enums = [], []
+    for kind, body, name in re.findall(r"typedef (struct|enum) \{(.*?)\n\} (\w+);", header, re.S):
+        if kind == "enum":
+            enums.append((name, re.findall(r"(WIFI_\w+)\s*=\s*(-?\d+)", body)))
+            continue
+        fields = []
+        for line in body.split("\n"):
+            line = re.sub(r"//.*", "", line).strip()
+            if not line:
+                continue
+            pointer = re.match(r".*\(\s*\*\s*(\w+)\)", line)
+            field = re.match(r".*?(\w+)\s*(\[[^\]]*\])*\s*;$", line)
+            if not field:
+                sys.exit(f"cannot parse field of {name}: {line}")
+            fields.append(pointer.group(1) if pointer else field.group(1))
+        structs.append((name, fields))
+    callbacks = re.findall(r"^typedef [^{;]*\(\*\w+\)[^;]*;", header, re.M)
+    functions = re.findall(r"^((?:const )?[A-Za-z]\w*\s*\**\s*(wifi_\w+)\([^;]*\);)", header, re.M)
+    return structs, enums, callbacks, functions
+
+
+def measure(structs):
+    """Sizes and field offsets of the structs, from a probe built with the C compiler."""
+    probe = '#include <stdio.h>\n#include <stddef.h>\n#include "wifi_c_api.h"\nint main(void) {\n'
+    for name, fields in structs:
+        probe += f'    printf("{name} %zu\\n", sizeof({name}));\n'
+        for field in fields:
+            probe += f'    printf("{name}.{field} %zu\\n", offsetof({name}, {field}));\n'
+    probe += "    return 0;\n}\n"
+    with tempfile.TemporaryDirectory() as tmp:
+        source = os.path.join(tmp, "probe.c")
+        binary = os.path.join(tmp, "probe")
+        with open(source, "w") as f:
+            f.write(probe)
+        compiler = os.environ.get("CC", "cc")
+        subprocess.check_call([compiler, "-std=c11", "-I" + os.path.join(ROOT, "include"), source, "-o", binary])
+        output = subprocess.check_output([binary]).decode()
+    return dict(line.split() for line in output.splitlines())
+
+
+def generate():
+    with open(HEADER) as f:
+        header = f.read()
+    major = re.search(r"#define WIFI_API_VERSION_MAJOR (\d+)", header)
+    if not major or int(major.group(1)) != API_VERSION_MAJOR:
+        sys.exit("WIFI_API_VERSION_MAJOR changed; update API_VERSION_MAJOR in " + os.path.basename(__file__))
+    structs, enums, callbacks, functions = parse(header)
+    layout = measure(structs)
+
+    out = [PREAMBLE.format(major=API_VERSION_MAJOR)]
+    for _, values in enums:
+        out += [f"EXPECT_VALUE({name}, {value});\n" for name, value in values]
+        out.append("\n")
+    out.append("// Struct layouts on 64-bit targets, where pointers are 8 bytes and int32_t is 4 bytes\n"
+               "// on every supported platform\n"
+               "#if UINTPTR_MAX == 0xFFFFFFFFFFFFFFFFu\n")
+    blocks = []
+    for name, fields in structs:
+        block = f"EXPECT_SIZE({name}, {layout[name]});\n"
+        block += "".join(f"EXPECT_OFFSET({name}, {field}, {layout[name + '.' + field]});\n" for field in fields)
+        blocks.append(block)
+    out.append("\n".join(blocks))
+    out.append("#endif\n\n// Callback signatures; redeclaring a typedef with a different type does not compile\n")
+    out += [callback + "\n" for callback in callbacks]
+    out.append("\n// Function signatures; a conflicting redeclaration does not compile, a removed\n"
+               "// function does not link\n")
+    out += [prototype + "\n" for prototype, _ in functions]
+    out.append("\nstatic void (*const exported[])(void) = {\n")
+    out += [f"    (void (*)(void)){name},\n" for _, name in functions]
+    out.append("};\n\n")
+    out.append(MAIN)
+    return "".join(out)
+
+
+def main():
+    generated = generate()
+    if "--check" in sys.argv[1:]:
+        with open(OUTPUT, newline="") as f:
+            current = f.read().replace("\r\n", "\n")
+        if current != generated:
+            sys.exit("test/test_abi.c does not match include/wifi_c_api.h; "
+                     "run python3 test/gen_abi_test.py and review the diff")
+        print("test/test_abi.c matches include/wifi_c_api.h")
+        return
+    with open(OUTPUT, "w", newline="\r\n") as f:
+        f.write(generated)
+
+
+if __name__ == "__main__":
+    main()
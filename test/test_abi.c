@@ -0,0 +1,672 @@
+// Binary interface checks for the C API in wifi_c_api.h
+//
+// Bindings in other languages (C#, Python, ...) hard-code the layouts, values and
+// signatures below. This file only compiles and runs while the header still matches
+// them, so an accidental break fails the build. When a change is intended, bump
+// WIFI_API_VERSION_MAJOR, rename the version node in src/wifi_c_api.map and
+// regenerate this file with test/gen_abi_test.py.
+
+#include "wifi_c_api.h"
+#include <stddef.h>
+#include <stdio.h>
+
+#define EXPECT_SIZE(type, size) _Static_assert(sizeof(type) == (size), "size of " #type " changed")
+#define EXPECT_OFFSET(type, field, offset) \
+    _Static_assert(offsetof(type, field) == (offset), "offset of " #type "." #field " changed")
+#define EXPECT_VALUE(name, value) _Static_assert((name) == (value), "value of " #name " changed")
+
+_Static_assert(WIFI_API_VERSION_MAJOR == 2, "update the expectations below for the new major version");
+
+// Enum values
+EXPECT_VALUE(WIFI_LOG_DEBUG, 0);
+EXPECT_VALUE(WIFI_LOG_INFO, 1);
+EXPECT_VALUE(WIFI_LOG_WARNING, 2);
+EXPECT_VALUE(WIFI_LOG_ERROR, 3);
+
+EXPECT_VALUE(WIFI_PMF_UNKNOWN, 0);
+EXPECT_VALUE(WIFI_PMF_DISABLED, 1);
+EXPECT_VALUE(WIFI_PMF_CAPABLE, 2);
+EXPECT_VALUE(WIFI_PMF_REQUIRED, 3);
+
+EXPECT_VALUE(WIFI_STATUS_CONNECTED, 0);
+EXPECT_VALUE(WIFI_STATUS_DISCONNECTED, 1);
+EXPECT_VALUE(WIFI_STATUS_CONNECTING, 2);
+EXPECT_VALUE(WIFI_STATUS_ERROR, 3);
+
+EXPECT_VALUE(WIFI_ERROR_NONE, 0);
+EXPECT_VALUE(WIFI_ERROR_OPERATION_FAILED, 1);
+EXPECT_VALUE(WIFI_ERROR_UNSUPPORTED, 2);
+EXPECT_VALUE(WIFI_ERROR_INVALID_CREDENTIALS, 3);
+EXPECT_VALUE(WIFI_ERROR_ADAPTER_NOT_FOUND, 4);
+EXPECT_VALUE(WIFI_ERROR_PERMISSION_DENIED, 5);
+EXPECT_VALUE(WIFI_ERROR_TIMEOUT, 6);
+EXPECT_VALUE(WIFI_ERROR_DRIVER_ERROR, 7);
+EXPECT_VALUE(WIFI_ERROR_CANCELLED, 8);
+
+EXPECT_VALUE(WIFI_EAP_PEAP, 0);
+EXPECT_VALUE(WIFI_EAP_TLS, 1);
+EXPECT_VALUE(WIFI_EAP_TTLS, 2);
+EXPECT_VALUE(WIFI_EAP_SIM, 3);
+EXPECT_VALUE(WIFI_EAP_AKA, 4);
+EXPECT_VALUE(WIFI_EAP_AKA_PRIME, 5);
+
+EXPECT_VALUE(WIFI_PHASE2_MSCHAPV2, 0);
+EXPECT_VALUE(WIFI_PHASE2_PAP, 1);
+
+EXPECT_VALUE(WIFI_MESH_PLINK_LISTEN, 0);
+EXPECT_VALUE(WIFI_MESH_PLINK_OPEN_SENT, 1);
+EXPECT_VALUE(WIFI_MESH_PLINK_OPEN_RECEIVED, 2);
+EXPECT_VALUE(WIFI_MESH_PLINK_CONFIRM_RECEIVED, 3);
+EXPECT_VALUE(WIFI_MESH_PLINK_ESTABLISHED, 4);
+EXPECT_VALUE(WIFI_MESH_PLINK_HOLDING, 5);
+EXPECT_VALUE(WIFI_MESH_PLINK_BLOCKED, 6);
+EXPECT_VALUE(WIFI_MESH_PLINK_UNKNOWN, 7);
+
+EXPECT_VALUE(WIFI_NAN_EVENT_DISCOVERED, 0);
+EXPECT_VALUE(WIFI_NAN_EVENT_RECEIVED, 1);
+EXPECT_VALUE(WIFI_NAN_EVENT_TERMINATED, 2);
+
+EXPECT_VALUE(WIFI_P2P_WPS_PBC, 0);
+EXPECT_VALUE(WIFI_P2P_WPS_PIN_DISPLAY, 1);
+EXPECT_VALUE(WIFI_P2P_WPS_PIN_KEYPAD, 2);
+
+EXPECT_VALUE(WIFI_WPS_SEARCHING, 0);
+EXPECT_VALUE(WIFI_WPS_EXCHANGING, 1);
+EXPECT_VALUE(WIFI_WPS_CREDENTIALS_RECEIVED, 2);
+EXPECT_VALUE(WIFI_WPS_CONNECTED, 3);
+EXPECT_VALUE(WIFI_WPS_FAILED, 4);
+
+EXPECT_VALUE(WIFI_WPS_FAILURE_NONE, 0);
+EXPECT_VALUE(WIFI_WPS_FAILURE_TIMEOUT, 1);
+EXPECT_VALUE(WIFI_WPS_FAILURE_SESSION_OVERLAP, 2);
+EXPECT_VALUE(WIFI_WPS_FAILURE_WRONG_PIN, 3);
+EXPECT_VALUE(WIFI_WPS_FAILURE_SETUP_LOCKED, 4);
+EXPECT_VALUE(WIFI_WPS_FAILURE_REJECTED, 5);
+
+EXPECT_VALUE(WIFI_EVENT_CONNECTED, 0);
+EXPECT_VALUE(WIFI_EVENT_CONNECTION_LOST, 1);
+EXPECT_VALUE(WIFI_EVENT_ROAMED, 2);
+EXPECT_VALUE(WIFI_EVENT_SCAN_COMPLETE, 3);
+EXPECT_VALUE(WIFI_EVENT_SIGNAL_CHANGED, 4);
+EXPECT_VALUE(WIFI_EVENT_HOTSPOT_CLIENT_JOINED, 5);
+EXPECT_VALUE(WIFI_EVENT_HOTSPOT_CLIENT_LEFT, 6);
+
+EXPECT_VALUE(WIFI_LINUX_BACKEND_AUTO, 0);
+EXPECT_VALUE(WIFI_LINUX_BACKEND_NL80211, 1);
+EXPECT_VALUE(WIFI_LINUX_BACKEND_NETWORK_MANAGER, 2);
+EXPECT_VALUE(WIFI_LINUX_BACKEND_WPA_CLI, 3);
+
+// Struct layouts on 64-bit targets, where pointers are 8 bytes and int32_t is 4 bytes
+// on every supported platform
+#if UINTPTR_MAX == 0xFFFFFFFFFFFFFFFFu
+EXPECT_SIZE(WifiNetworkInfo, 64);
+EXPECT_OFFSET(WifiNetworkInfo, ssid, 0);
+EXPECT_OFFSET(WifiNetworkInfo, bssid, 8);
+EXPECT_OFFSET(WifiNetworkInfo, signal_strength, 16);
+EXPECT_OFFSET(WifiNetworkInfo, security_type, 20);
+EXPECT_OFFSET(WifiNetworkInfo, channel, 24);
+EXPECT_OFFSET(WifiNetworkInfo, frequency, 28);
+EXPECT_OFFSET(WifiNetworkInfo, pmf, 32);
+EXPECT_OFFSET(WifiNetworkInfo, wps_enabled, 36);
+EXPECT_OFFSET(WifiNetworkInfo, eht, 40);
+EXPECT_OFFSET(WifiNetworkInfo, mlo, 44);
+EXPECT_OFFSET(WifiNetworkInfo, ie_data, 48);
+EXPECT_OFFSET(WifiNetworkInfo, ie_len, 56);
+
+EXPECT_SIZE(WifiEapConfig, 120);
+EXPECT_OFFSET(WifiEapConfig, method, 0);
+EXPECT_OFFSET(WifiEapConfig, phase2, 4);
+EXPECT_OFFSET(WifiEapConfig, identity, 8);
+EXPECT_OFFSET(WifiEapConfig, anonymous_identity, 16);
+EXPECT_OFFSET(WifiEapConfig, password, 24);
+EXPECT_OFFSET(WifiEapConfig, ca_cert, 32);
+EXPECT_OFFSET(WifiEapConfig, disable_server_validation, 40);
+EXPECT_OFFSET(WifiEapConfig, server_cert_hash, 48);
+EXPECT_OFFSET(WifiEapConfig, domain_match, 56);
+EXPECT_OFFSET(WifiEapConfig, domain_suffix_match, 64);
+EXPECT_OFFSET(WifiEapConfig, client_cert, 72);
+EXPECT_OFFSET(WifiEapConfig, private_key, 80);
+EXPECT_OFFSET(WifiEapConfig, private_key_password, 88);
+EXPECT_OFFSET(WifiEapConfig, pkcs12_data, 96);
+EXPECT_OFFSET(WifiEapConfig, pkcs12_len, 104);
+EXPECT_OFFSET(WifiEapConfig, cert_store_ref, 112);
+
+EXPECT_SIZE(WifiConnectOptions, 80);
+EXPECT_OFFSET(WifiConnectOptions, ephemeral, 0);
+EXPECT_OFFSET(WifiConnectOptions, frequencies, 8);
+EXPECT_OFFSET(WifiConnectOptions, frequency_count, 16);
+EXPECT_OFFSET(WifiConnectOptions, hidden, 20);
+EXPECT_OFFSET(WifiConnectOptions, bssid, 24);
+EXPECT_OFFSET(WifiConnectOptions, timeout_seconds, 32);
+EXPECT_OFFSET(WifiConnectOptions, ipv4_address, 40);
+EXPECT_OFFSET(WifiConnectOptions, ipv4_gateway, 48);
+EXPECT_OFFSET(WifiConnectOptions, ipv6_address, 56);
+EXPECT_OFFSET(WifiConnectOptions, ipv6_gateway, 64);
+EXPECT_OFFSET(WifiConnectOptions, dns_servers, 72);
+
+EXPECT_SIZE(WifiScanOptions, 32);
+EXPECT_OFFSET(WifiScanOptions, ssids, 0);
+EXPECT_OFFSET(WifiScanOptions, ssid_count, 8);
+EXPECT_OFFSET(WifiScanOptions, frequencies, 16);
+EXPECT_OFFSET(WifiScanOptions, frequency_count, 24);
+EXPECT_OFFSET(WifiScanOptions, passive, 28);
+
+EXPECT_SIZE(WifiTwtConfig, 24);
+EXPECT_OFFSET(WifiTwtConfig, wake_interval_us, 0);
+EXPECT_OFFSET(WifiTwtConfig, wake_duration_us, 8);
+EXPECT_OFFSET(WifiTwtConfig, trigger_enabled, 12);
+EXPECT_OFFSET(WifiTwtConfig, announced, 16);
+
+EXPECT_SIZE(WifiTwtStatus, 40);
+EXPECT_OFFSET(WifiTwtStatus, ap_responder, 0);
+EXPECT_OFFSET(WifiTwtStatus, local_requester, 4);
+EXPECT_OFFSET(WifiTwtStatus, agreement_requested, 8);
+EXPECT_OFFSET(WifiTwtStatus, config, 16);
+
+EXPECT_SIZE(WifiWowlanPattern, 24);
+EXPECT_OFFSET(WifiWowlanPattern, pattern, 0);
+EXPECT_OFFSET(WifiWowlanPattern, mask, 8);
+EXPECT_OFFSET(WifiWowlanPattern, length, 16);
+EXPECT_OFFSET(WifiWowlanPattern, offset, 20);
+
+EXPECT_SIZE(WifiWowlanConfig, 24);
+EXPECT_OFFSET(WifiWowlanConfig, magic_packet, 0);
+EXPECT_OFFSET(WifiWowlanConfig, disconnect, 4);
+EXPECT_OFFSET(WifiWowlanConfig, patterns, 8);
+EXPECT_OFFSET(WifiWowlanConfig, pattern_count, 16);
+
+EXPECT_SIZE(WifiWowlanStatus, 12);
+EXPECT_OFFSET(WifiWowlanStatus, magic_packet, 0);
+EXPECT_OFFSET(WifiWowlanStatus, disconnect, 4);
+EXPECT_OFFSET(WifiWowlanStatus, pattern_count, 8);
+
+EXPECT_SIZE(WifiWowlanCapabilities, 24);
+EXPECT_OFFSET(WifiWowlanCapabilities, magic_packet, 0);
+EXPECT_OFFSET(WifiWowlanCapabilities, disconnect, 4);
+EXPECT_OFFSET(WifiWowlanCapabilities, max_patterns, 8);
+EXPECT_OFFSET(WifiWowlanCapabilities, min_pattern_length, 12);
+EXPECT_OFFSET(WifiWowlanCapabilities, max_pattern_length, 16);
+EXPECT_OFFSET(WifiWowlanCapabilities, max_pattern_offset, 20);
+
+EXPECT_SIZE(WifiRoamingFeatures, 24);
+EXPECT_OFFSET(WifiRoamingFeatures, ap_neighbor_report, 0);
+EXPECT_OFFSET(WifiRoamingFeatures, ap_bss_transition, 4);
+EXPECT_OFFSET(WifiRoamingFeatures, ap_fast_transition, 8);
+EXPECT_OFFSET(WifiRoamingFeatures, local_neighbor_report, 12);
+EXPECT_OFFSET(WifiRoamingFeatures, local_bss_transition, 16);
+EXPECT_OFFSET(WifiRoamingFeatures, local_fast_transition, 20);
+
+EXPECT_SIZE(WifiRoamingPolicy, 8);
+EXPECT_OFFSET(WifiRoamingPolicy, rssi_threshold, 0);
+EXPECT_OFFSET(WifiRoamingPolicy, scan_interval_seconds, 4);
+
+EXPECT_SIZE(WifiRangingResult, 32);
+EXPECT_OFFSET(WifiRangingResult, distance_mm, 0);
+EXPECT_OFFSET(WifiRangingResult, distance_variance_mm2, 8);
+EXPECT_OFFSET(WifiRangingResult, rtt_ps, 16);
+EXPECT_OFFSET(WifiRangingResult, attempts, 24);
+EXPECT_OFFSET(WifiRangingResult, successes, 28);
+
+EXPECT_SIZE(WifiTdlsPeer, 68);
+EXPECT_OFFSET(WifiTdlsPeer, address, 0);
+EXPECT_OFFSET(WifiTdlsPeer, ip_address, 18);
+EXPECT_OFFSET(WifiTdlsPeer, linked, 64);
+
+EXPECT_SIZE(WifiAdHocConfig, 24);
+EXPECT_OFFSET(WifiAdHocConfig, ssid, 0);
+EXPECT_OFFSET(WifiAdHocConfig, channel, 8);
+EXPECT_OFFSET(WifiAdHocConfig, password, 16);
+
+EXPECT_SIZE(WifiMeshConfig, 32);
+EXPECT_OFFSET(WifiMeshConfig, mesh_id, 0);
+EXPECT_OFFSET(WifiMeshConfig, channel, 8);
+EXPECT_OFFSET(WifiMeshConfig, password, 16);
+EXPECT_OFFSET(WifiMeshConfig, interface_name, 24);
+
+EXPECT_SIZE(WifiMeshPeer, 32);
+EXPECT_OFFSET(WifiMeshPeer, address, 0);
+EXPECT_OFFSET(WifiMeshPeer, link_state, 20);
+EXPECT_OFFSET(WifiMeshPeer, signal_strength, 24);
+EXPECT_OFFSET(WifiMeshPeer, inactive_ms, 28);
+
+EXPECT_SIZE(WifiNanPublishConfig, 32);
+EXPECT_OFFSET(WifiNanPublishConfig, service_name, 0);
+EXPECT_OFFSET(WifiNanPublishConfig, service_info, 8);
+EXPECT_OFFSET(WifiNanPublishConfig, service_info_len, 16);
+EXPECT_OFFSET(WifiNanPublishConfig, unsolicited, 20);
+EXPECT_OFFSET(WifiNanPublishConfig, solicited, 24);
+EXPECT_OFFSET(WifiNanPublishConfig, ttl_seconds, 28);
+
+EXPECT_SIZE(WifiNanSubscribeConfig, 32);
+EXPECT_OFFSET(WifiNanSubscribeConfig, service_name, 0);
+EXPECT_OFFSET(WifiNanSubscribeConfig, service_info, 8);
+EXPECT_OFFSET(WifiNanSubscribeConfig, service_info_len, 16);
+EXPECT_OFFSET(WifiNanSubscribeConfig, active, 20);
+EXPECT_OFFSET(WifiNanSubscribeConfig, ttl_seconds, 24);
+
+EXPECT_SIZE(WifiNanEvent, 292);
+EXPECT_OFFSET(WifiNanEvent, type, 0);
+EXPECT_OFFSET(WifiNanEvent, handle, 4);
+EXPECT_OFFSET(WifiNanEvent, peer_instance_id, 8);
+EXPECT_OFFSET(WifiNanEvent, peer_address, 12);
+EXPECT_OFFSET(WifiNanEvent, service_info, 30);
+EXPECT_OFFSET(WifiNanEvent, service_info_len, 288);
+
+EXPECT_SIZE(WifiP2pPeer, 120);
+EXPECT_OFFSET(WifiP2pPeer, device_address, 0);
+EXPECT_OFFSET(WifiP2pPeer, device_name, 18);
+EXPECT_OFFSET(WifiP2pPeer, device_type, 82);
+EXPECT_OFFSET(WifiP2pPeer, group_owner, 116);
+
+EXPECT_SIZE(WifiP2pConnectConfig, 32);
+EXPECT_OFFSET(WifiP2pConnectConfig, peer_address, 0);
+EXPECT_OFFSET(WifiP2pConnectConfig, wps_method, 8);
+EXPECT_OFFSET(WifiP2pConnectConfig, pin, 16);
+EXPECT_OFFSET(WifiP2pConnectConfig, go_intent, 24);
+
+EXPECT_SIZE(WifiP2pGroup, 96);
+EXPECT_OFFSET(WifiP2pGroup, interface_name, 0);
+EXPECT_OFFSET(WifiP2pGroup, ssid, 32);
+EXPECT_OFFSET(WifiP2pGroup, group_owner, 68);
+EXPECT_OFFSET(WifiP2pGroup, go_device_address, 72);
+EXPECT_OFFSET(WifiP2pGroup, frequency, 92);
+
+EXPECT_SIZE(WifiConnectionInfo, 92);
+EXPECT_OFFSET(WifiConnectionInfo, ssid, 0);
+EXPECT_OFFSET(WifiConnectionInfo, bssid, 33);
+EXPECT_OFFSET(WifiConnectionInfo, frequency, 52);
+EXPECT_OFFSET(WifiConnectionInfo, signal_strength, 56);
+EXPECT_OFFSET(WifiConnectionInfo, fast_transition, 60);
+EXPECT_OFFSET(WifiConnectionInfo, ap_mld_address, 64);
+EXPECT_OFFSET(WifiConnectionInfo, noise, 84);
+EXPECT_OFFSET(WifiConnectionInfo, link_speed_mbps, 88);
+
+EXPECT_SIZE(WifiLinkStats, 56);
+EXPECT_OFFSET(WifiLinkStats, tx_bitrate_kbps, 0);
+EXPECT_OFFSET(WifiLinkStats, rx_bitrate_kbps, 4);
+EXPECT_OFFSET(WifiLinkStats, tx_mcs, 8);
+EXPECT_OFFSET(WifiLinkStats, rx_mcs, 12);
+EXPECT_OFFSET(WifiLinkStats, tx_packets, 16);
+EXPECT_OFFSET(WifiLinkStats, rx_packets, 24);
+EXPECT_OFFSET(WifiLinkStats, tx_retries, 32);
+EXPECT_OFFSET(WifiLinkStats, tx_failed, 40);
+EXPECT_OFFSET(WifiLinkStats, rx_dropped, 48);
+
+EXPECT_SIZE(WifiMloLink, 32);
+EXPECT_OFFSET(WifiMloLink, link_id, 0);
+EXPECT_OFFSET(WifiMloLink, ap_link_address, 4);
+EXPECT_OFFSET(WifiMloLink, frequency, 24);
+EXPECT_OFFSET(WifiMloLink, signal_strength, 28);
+
+EXPECT_SIZE(WifiChannelInfo, 16);
+EXPECT_OFFSET(WifiChannelInfo, frequency, 0);
+EXPECT_OFFSET(WifiChannelInfo, max_power_mbm, 4);
+EXPECT_OFFSET(WifiChannelInfo, dfs, 8);
+EXPECT_OFFSET(WifiChannelInfo, no_ir, 12);
+
+EXPECT_SIZE(WifiLinkDiagnostics, 348);
+EXPECT_OFFSET(WifiLinkDiagnostics, adapter_present, 0);
+EXPECT_OFFSET(WifiLinkDiagnostics, radio_enabled, 4);
+EXPECT_OFFSET(WifiLinkDiagnostics, associated, 8);
+EXPECT_OFFSET(WifiLinkDiagnostics, authenticated, 12);
+EXPECT_OFFSET(WifiLinkDiagnostics, ssid, 16);
+EXPECT_OFFSET(WifiLinkDiagnostics, bssid, 49);
+EXPECT_OFFSET(WifiLinkDiagnostics, ip_address, 67);
+EXPECT_OFFSET(WifiLinkDiagnostics, gateway, 113);
+EXPECT_OFFSET(WifiLinkDiagnostics, dns_servers, 159);
+EXPECT_OFFSET(WifiLinkDiagnostics, dns_server_count, 344);
+
+EXPECT_SIZE(WifiIpInfo, 960);
+EXPECT_OFFSET(WifiIpInfo, ipv4_addresses, 0);
+EXPECT_OFFSET(WifiIpInfo, ipv4_address_count, 200);
+EXPECT_OFFSET(WifiIpInfo, ipv6_addresses, 204);
+EXPECT_OFFSET(WifiIpInfo, ipv6_address_count, 604);
+EXPECT_OFFSET(WifiIpInfo, gateway, 608);
+EXPECT_OFFSET(WifiIpInfo, ipv6_gateway, 654);
+EXPECT_OFFSET(WifiIpInfo, dns_servers, 700);
+EXPECT_OFFSET(WifiIpInfo, dns_server_count, 884);
+EXPECT_OFFSET(WifiIpInfo, dhcp, 888);
+EXPECT_OFFSET(WifiIpInfo, dhcp_server, 892);
+EXPECT_OFFSET(WifiIpInfo, lease_obtained, 944);
+EXPECT_OFFSET(WifiIpInfo, lease_expires, 952);
+
+EXPECT_SIZE(WifiHotspotConfig, 64);
+EXPECT_OFFSET(WifiHotspotConfig, ssid, 0);
+EXPECT_OFFSET(WifiHotspotConfig, password, 8);
+EXPECT_OFFSET(WifiHotspotConfig, channel, 16);
+EXPECT_OFFSET(WifiHotspotConfig, dhcp_range_start, 24);
+EXPECT_OFFSET(WifiHotspotConfig, dhcp_range_end, 32);
+EXPECT_OFFSET(WifiHotspotConfig, gateway, 40);
+EXPECT_OFFSET(WifiHotspotConfig, prefix_length, 48);
+EXPECT_OFFSET(WifiHotspotConfig, dns_servers, 56);
+
+EXPECT_SIZE(WifiHotspotClient, 136);
+EXPECT_OFFSET(WifiHotspotClient, mac_address, 0);
+EXPECT_OFFSET(WifiHotspotClient, ip_address, 18);
+EXPECT_OFFSET(WifiHotspotClient, hostname, 64);
+EXPECT_OFFSET(WifiHotspotClient, signal_strength, 128);
+EXPECT_OFFSET(WifiHotspotClient, connected_seconds, 132);
+
+EXPECT_SIZE(WifiChannelSurvey, 48);
+EXPECT_OFFSET(WifiChannelSurvey, frequency, 0);
+EXPECT_OFFSET(WifiChannelSurvey, channel, 4);
+EXPECT_OFFSET(WifiChannelSurvey, in_use, 8);
+EXPECT_OFFSET(WifiChannelSurvey, noise, 12);
+EXPECT_OFFSET(WifiChannelSurvey, active_time_ms, 16);
+EXPECT_OFFSET(WifiChannelSurvey, busy_time_ms, 24);
+EXPECT_OFFSET(WifiChannelSurvey, receive_time_ms, 32);
+EXPECT_OFFSET(WifiChannelSurvey, transmit_time_ms, 40);
+
+EXPECT_SIZE(WifiSavedProfile, 300);
+EXPECT_OFFSET(WifiSavedProfile, name, 0);
+EXPECT_OFFSET(WifiSavedProfile, ssid, 256);
+EXPECT_OFFSET(WifiSavedProfile, security_type, 292);
+EXPECT_OFFSET(WifiSavedProfile, auto_connect, 296);
+
+EXPECT_SIZE(WifiInterfaceInfo, 180);
+EXPECT_OFFSET(WifiInterfaceInfo, name, 0);
+EXPECT_OFFSET(WifiInterfaceInfo, mac_address, 64);
+EXPECT_OFFSET(WifiInterfaceInfo, driver, 82);
+EXPECT_OFFSET(WifiInterfaceInfo, selected, 148);
+EXPECT_OFFSET(WifiInterfaceInfo, supports_ap, 152);
+EXPECT_OFFSET(WifiInterfaceInfo, supports_monitor, 156);
+EXPECT_OFFSET(WifiInterfaceInfo, supports_mesh, 160);
+EXPECT_OFFSET(WifiInterfaceInfo, supports_p2p, 164);
+EXPECT_OFFSET(WifiInterfaceInfo, band_2ghz, 168);
+EXPECT_OFFSET(WifiInterfaceInfo, band_5ghz, 172);
+EXPECT_OFFSET(WifiInterfaceInfo, band_6ghz, 176);
+
+EXPECT_SIZE(WifiCaptureConfig, 40);
+EXPECT_OFFSET(WifiCaptureConfig, channel, 0);
+EXPECT_OFFSET(WifiCaptureConfig, include_management, 4);
+EXPECT_OFFSET(WifiCaptureConfig, include_control, 8);
+EXPECT_OFFSET(WifiCaptureConfig, include_data, 12);
+EXPECT_OFFSET(WifiCaptureConfig, monitor_interface, 16);
+EXPECT_OFFSET(WifiCaptureConfig, hop_channels, 24);
+EXPECT_OFFSET(WifiCaptureConfig, hop_channel_count, 32);
+EXPECT_OFFSET(WifiCaptureConfig, dwell_ms, 36);
+
+EXPECT_SIZE(WifiWpsProgress, 12);
+EXPECT_OFFSET(WifiWpsProgress, stage, 0);
+EXPECT_OFFSET(WifiWpsProgress, failure, 4);
+EXPECT_OFFSET(WifiWpsProgress, config_error, 8);
+
+EXPECT_SIZE(WifiEventData, 84);
+EXPECT_OFFSET(WifiEventData, type, 0);
+EXPECT_OFFSET(WifiEventData, ssid, 4);
+EXPECT_OFFSET(WifiEventData, bssid, 37);
+EXPECT_OFFSET(WifiEventData, signal_strength, 56);
+EXPECT_OFFSET(WifiEventData, signal_low, 60);
+EXPECT_OFFSET(WifiEventData, client_address, 64);
+
+EXPECT_SIZE(WifiPasspointProfile, 152);
+EXPECT_OFFSET(WifiPasspointProfile, domain, 0);
+EXPECT_OFFSET(WifiPasspointProfile, realm, 8);
+EXPECT_OFFSET(WifiPasspointProfile, home_ois, 16);
+EXPECT_OFFSET(WifiPasspointProfile, friendly_name, 24);
+EXPECT_OFFSET(WifiPasspointProfile, credentials, 32);
+#endif
+
+// Callback signatures; redeclaring a typedef with a different type does not compile
+typedef void (*WifiLogCallback)(WifiLogLevel level, const char* message, void* user_data);
+typedef void (*WifiFrameCallback)(const uint8_t* data, int32_t length, uint64_t timestamp_us, void* user_data);
+typedef void (*WifiNetworkChangeCallback)(void* user_data);
+typedef void (*WifiSignalCallback)(const WifiConnectionInfo* info, void* user_data);
+typedef void (*WifiWpsProgressCallback)(const WifiWpsProgress* progress, void* user_data);
+typedef void (*WifiEventCallback)(const WifiEventData* event, void* user_data);
+
+// Function signatures; a conflicting redeclaration does not compile, a removed
+// function does not link
+uint32_t wifi_api_version(void);
+void wifi_set_log_callback(WifiLogCallback callback, void* user_data);
+void wifi_set_log_level(WifiLogLevel level);
+WifiManager* wifi_manager_new();
+WifiManager* wifi_manager_new_with_interface(const char* interface_name);
+void wifi_manager_delete(WifiManager* manager);
+WifiNetworkInfo* wifi_manager_scan(WifiManager* manager, int* count);
+WifiNetworkInfo* wifi_manager_scan_with_options(WifiManager* manager, const WifiScanOptions* options, int* count);
+WifiNetworkInfo* wifi_manager_scan_with_timeout(WifiManager* manager, const WifiScanOptions* options, int32_t timeout_ms,
+                                                int* count);
+bool wifi_manager_cancel(WifiManager* manager);
+bool wifi_manager_set_timeouts(WifiManager* manager, int32_t scan_timeout_ms, int32_t hotspot_timeout_ms);
+bool wifi_manager_scan_fresh(WifiManager* manager, WifiNetworkInfo** networks, int* count);
+int wifi_manager_get_information_elements(WifiManager* manager, const char* bssid, uint8_t* buffer, int size);
+bool wifi_manager_connect(WifiManager* manager, const char* ssid, const char* password);
+bool wifi_manager_connect_with_options(WifiManager* manager, const char* ssid, const char* password, const WifiConnectOptions* options);
+bool wifi_manager_connect_enterprise(WifiManager* manager, const char* ssid, const WifiEapConfig* eap);
+bool wifi_manager_connect_wps_pbc(WifiManager* manager, int32_t timeout_seconds);
+bool wifi_manager_connect_wps_pin(WifiManager* manager, const char* bssid, const char* pin, int32_t timeout_seconds);
+bool wifi_manager_connect_wps_pbc_with_progress(WifiManager* manager, int32_t timeout_seconds,
+                                                WifiWpsProgressCallback callback, void* user_data);
+bool wifi_manager_connect_wps_pin_with_progress(WifiManager* manager, const char* bssid, const char* pin, int32_t timeout_seconds,
+                                                WifiWpsProgressCallback callback, void* user_data);
+bool wifi_manager_is_eap_method_supported(WifiManager* manager, int32_t method);
+bool wifi_manager_install_passpoint(WifiManager* manager, const WifiPasspointProfile* profile);
+bool wifi_manager_remove_passpoint(WifiManager* manager, const char* domain);
+bool wifi_manager_set_probe_privacy(WifiManager* manager, bool enabled);
+bool wifi_manager_is_probe_privacy_enabled(WifiManager* manager);
+bool wifi_manager_start_capture(WifiManager* manager, const WifiCaptureConfig* config,
+                                WifiFrameCallback callback, void* user_data);
+bool wifi_manager_stop_capture(WifiManager* manager);
+bool wifi_manager_is_capturing(WifiManager* manager);
+WifiChannelSurvey* wifi_manager_channel_survey(WifiManager* manager, int* count);
+void wifi_free_channel_survey(WifiChannelSurvey* surveys);
+bool wifi_manager_get_link_diagnostics(WifiManager* manager, WifiLinkDiagnostics* diagnostics);
+bool wifi_manager_get_connection_info(WifiManager* manager, WifiConnectionInfo* info);
+bool wifi_manager_get_link_stats(WifiManager* manager, WifiLinkStats* stats);
+bool wifi_manager_set_linux_backend(WifiManager* manager, WifiLinuxBackend backend);
+WifiLinuxBackend wifi_manager_get_linux_backend(WifiManager* manager);
+bool wifi_manager_is_location_authorized(WifiManager* manager);
+bool wifi_manager_request_location_authorization(WifiManager* manager);
+bool wifi_manager_has_admin_privileges(WifiManager* manager);
+bool wifi_manager_is_radio_enabled(WifiManager* manager);
+bool wifi_manager_set_radio_enabled(WifiManager* manager, bool enabled);
+bool wifi_manager_is_p2p_supported(WifiManager* manager);
+WifiP2pPeer* wifi_manager_p2p_discover(WifiManager* manager, int32_t timeout_seconds, int* count);
+void wifi_free_p2p_peers(WifiP2pPeer* peers);
+bool wifi_manager_p2p_connect(WifiManager* manager, const WifiP2pConnectConfig* config, int32_t timeout_seconds, WifiP2pGroup* group);
+bool wifi_manager_p2p_disconnect(WifiManager* manager);
+bool wifi_manager_is_nan_supported(WifiManager* manager);
+int32_t wifi_manager_nan_publish(WifiManager* manager, const WifiNanPublishConfig* config);
+int32_t wifi_manager_nan_subscribe(WifiManager* manager, const WifiNanSubscribeConfig* config);
+bool wifi_manager_nan_cancel(WifiManager* manager, int32_t handle);
+bool wifi_manager_nan_transmit(WifiManager* manager, int32_t handle, int32_t peer_instance_id, const char* peer_address, const uint8_t* data, int32_t data_len);
+bool wifi_manager_nan_next_event(WifiManager* manager, int32_t timeout_ms, WifiNanEvent* event);
+bool wifi_manager_is_mesh_supported(WifiManager* manager);
+bool wifi_manager_mesh_join(WifiManager* manager, const WifiMeshConfig* config);
+bool wifi_manager_mesh_leave(WifiManager* manager);
+WifiMeshPeer* wifi_manager_mesh_peers(WifiManager* manager, int* count);
+void wifi_free_mesh_peers(WifiMeshPeer* peers);
+bool wifi_manager_is_adhoc_supported(WifiManager* manager);
+bool wifi_manager_join_adhoc(WifiManager* manager, const WifiAdHocConfig* config);
+bool wifi_manager_is_tdls_supported(WifiManager* manager);
+WifiTdlsPeer* wifi_manager_tdls_discover(WifiManager* manager, int timeout_seconds, int* count);
+void wifi_free_tdls_peers(WifiTdlsPeer* peers);
+bool wifi_manager_tdls_setup(WifiManager* manager, const char* peer_address);
+bool wifi_manager_tdls_teardown(WifiManager* manager, const char* peer_address);
+bool wifi_manager_is_rtt_supported(WifiManager* manager);
+bool wifi_manager_measure_distance(WifiManager* manager, const char* bssid, int frequency, WifiRangingResult* result);
+bool wifi_manager_get_roaming_features(WifiManager* manager, WifiRoamingFeatures* features);
+bool wifi_manager_roam_to(WifiManager* manager, const char* bssid);
+bool wifi_manager_set_roaming_policy(WifiManager* manager, const WifiRoamingPolicy* policy);
+bool wifi_manager_get_twt_status(WifiManager* manager, WifiTwtStatus* status);
+bool wifi_manager_twt_setup(WifiManager* manager, const WifiTwtConfig* config);
+bool wifi_manager_twt_teardown(WifiManager* manager);
+WifiMloLink* wifi_manager_get_mlo_links(WifiManager* manager, int* count);
+void wifi_free_mlo_links(WifiMloLink* links);
+bool wifi_manager_get_regulatory_domain(WifiManager* manager, char* country, int country_size);
+bool wifi_manager_can_set_regulatory_domain(WifiManager* manager);
+bool wifi_manager_set_regulatory_domain(WifiManager* manager, const char* country);
+WifiChannelInfo* wifi_manager_get_allowed_channels(WifiManager* manager, int* count);
+void wifi_free_channel_info(WifiChannelInfo* channels);
+bool wifi_manager_get_interface_name(WifiManager* manager, char* name, int name_size);
+bool wifi_manager_get_wowlan_capabilities(WifiManager* manager, WifiWowlanCapabilities* capabilities);
+bool wifi_manager_get_wowlan(WifiManager* manager, WifiWowlanStatus* status);
+bool wifi_manager_set_wowlan(WifiManager* manager, const WifiWowlanConfig* config);
+bool wifi_manager_start_network_observer(WifiManager* manager, WifiNetworkChangeCallback callback, void* user_data);
+bool wifi_manager_stop_network_observer(WifiManager* manager);
+bool wifi_manager_is_profile_store_supported(WifiManager* manager);
+bool wifi_manager_has_profile(WifiManager* manager, const char* name);
+bool wifi_manager_connect_profile(WifiManager* manager, const char* name);
+bool wifi_manager_register_callback(WifiManager* manager, WifiEventCallback callback, void* user_data, int32_t signal_threshold);
+bool wifi_manager_unregister_callback(WifiManager* manager);
+WifiInterfaceInfo* wifi_manager_list_interfaces(WifiManager* manager, int* count);
+void wifi_free_interface_list(WifiInterfaceInfo* interfaces);
+WifiSavedProfile* wifi_manager_list_profiles(WifiManager* manager, int* count);
+void wifi_free_profile_list(WifiSavedProfile* profiles);
+bool wifi_manager_save_profile(WifiManager* manager, const char* ssid, const char* password, bool auto_connect);
+bool wifi_manager_forget_profile(WifiManager* manager, const char* name);
+bool wifi_manager_set_profile_auto_connect(WifiManager* manager, const char* name, bool enabled);
+bool wifi_manager_start_signal_monitor(WifiManager* manager, int32_t interval_ms, WifiSignalCallback callback, void* user_data);
+bool wifi_manager_stop_signal_monitor(WifiManager* manager);
+bool wifi_manager_get_ip_info(WifiManager* manager, WifiIpInfo* info);
+bool wifi_manager_disconnect(WifiManager* manager);
+WifiConnectionStatus wifi_manager_get_status(WifiManager* manager);
+WifiErrorCode wifi_manager_get_last_error(WifiManager* manager);
+bool wifi_manager_create_hotspot(WifiManager* manager, const char* ssid);
+bool wifi_manager_create_hotspot_with_config(WifiManager* manager, const WifiHotspotConfig* config);
+bool wifi_manager_create_hotspot_with_sharing(WifiManager* manager, const WifiHotspotConfig* config,
+                                              const char* share_from);
+bool wifi_manager_create_captive_hotspot(WifiManager* manager, const WifiHotspotConfig* config,
+                                         const char* share_from);
+bool wifi_manager_create_hotspot_with_timeout(WifiManager* manager, const WifiHotspotConfig* config,
+                                              const char* share_from, bool captive_portal, int32_t timeout_ms);
+bool wifi_manager_stop_hotspot(WifiManager* manager);
+bool wifi_manager_is_hotspot_active(WifiManager* manager);
+bool wifi_manager_is_hotspot_supported(WifiManager* manager);
+WifiHotspotClient* wifi_manager_hotspot_clients(WifiManager* manager, int* count);
+void wifi_free_hotspot_clients(WifiHotspotClient* clients);
+void wifi_free_network_info(WifiNetworkInfo* networks, int count);
+
+static void (*const exported[])(void) = {
+    (void (*)(void))wifi_api_version,
+    (void (*)(void))wifi_set_log_callback,
+    (void (*)(void))wifi_set_log_level,
+    (void (*)(void))wifi_manager_new,
+    (void (*)(void))wifi_manager_new_with_interface,
+    (void (*)(void))wifi_manager_delete,
+    (void (*)(void))wifi_manager_scan,
+    (void (*)(void))wifi_manager_scan_with_options,
+    (void (*)(void))wifi_manager_scan_with_timeout,
+    (void (*)(void))wifi_manager_cancel,
+    (void (*)(void))wifi_manager_set_timeouts,
+    (void (*)(void))wifi_manager_scan_fresh,
+    (void (*)(void))wifi_manager_get_information_elements,
+    (void (*)(void))wifi_manager_connect,
+    (void (*)(void))wifi_manager_connect_with_options,
+    (void (*)(void))wifi_manager_connect_enterprise,
+    (void (*)(void))wifi_manager_connect_wps_pbc,
+    (void (*)(void))wifi_manager_connect_wps_pin,
+    (void (*)(void))wifi_manager_connect_wps_pbc_with_progress,
+    (void (*)(void))wifi_manager_connect_wps_pin_with_progress,
+    (void (*)(void))wifi_manager_is_eap_method_supported,
+    (void (*)(void))wifi_manager_install_passpoint,
+    (void (*)(void))wifi_manager_remove_passpoint,
+    (void (*)(void))wifi_manager_set_probe_privacy,
+    (void (*)(void))wifi_manager_is_probe_privacy_enabled,
+    (void (*)(void))wifi_manager_start_capture,
+    (void (*)(void))wifi_manager_stop_capture,
+    (void (*)(void))wifi_manager_is_capturing,
+    (void (*)(void))wifi_manager_channel_survey,
+    (void (*)(void))wifi_free_channel_survey,
+    (void (*)(void))wifi_manager_get_link_diagnostics,
+    (void (*)(void))wifi_manager_get_connection_info,
+    (void (*)(void))wifi_manager_get_link_stats,
+    (void (*)(void))wifi_manager_set_linux_backend,
+    (void (*)(void))wifi_manager_get_linux_backend,
+    (void (*)(void))wifi_manager_is_location_authorized,
+    (void (*)(void))wifi_manager_request_location_authorization,
+    (void (*)(void))wifi_manager_has_admin_privileges,
+    (void (*)(void))wifi_manager_is_radio_enabled,
+    (void (*)(void))wifi_manager_set_radio_enabled,
+    (void (*)(void))wifi_manager_is_p2p_supported,
+    (void (*)(void))wifi_manager_p2p_discover,
+    (void (*)(void))wifi_free_p2p_peers,
+    (void (*)(void))wifi_manager_p2p_connect,
+    (void (*)(void))wifi_manager_p2p_disconnect,
+    (void (*)(void))wifi_manager_is_nan_supported,
+    (void (*)(void))wifi_manager_nan_publish,
+    (void (*)(void))wifi_manager_nan_subscribe,
+    (void (*)(void))wifi_manager_nan_cancel,
+    (void (*)(void))wifi_manager_nan_transmit,
+    (void (*)(void))wifi_manager_nan_next_event,
+    (void (*)(void))wifi_manager_is_mesh_supported,
+    (void (*)(void))wifi_manager_mesh_join,
+    (void (*)(void))wifi_manager_mesh_leave,
+    (void (*)(void))wifi_manager_mesh_peers,
+    (void (*)(void))wifi_free_mesh_peers,
+    (void (*)(void))wifi_manager_is_adhoc_supported,
+    (void (*)(void))wifi_manager_join_adhoc,
+    (void (*)(void))wifi_manager_is_tdls_supported,
+    (void (*)(void))wifi_manager_tdls_discover,
+    (void (*)(void))wifi_free_tdls_peers,
+    (void (*)(void))wifi_manager_tdls_setup,
+    (void (*)(void))wifi_manager_tdls_teardown,
+    (void (*)(void))wifi_manager_is_rtt_supported,
+    (void (*)(void))wifi_manager_measure_distance,
+    (void (*)(void))wifi_manager_get_roaming_features,
+    (void (*)(void))wifi_manager_roam_to,
+    (void (*)(void))wifi_manager_set_roaming_policy,
+    (void (*)(void))wifi_manager_get_twt_status,
+    (void (*)(void))wifi_manager_twt_setup,
+    (void (*)(void))wifi_manager_twt_teardown,
+    (void (*)(void))wifi_manager_get_mlo_links,
+    (void (*)(void))wifi_free_mlo_links,
+    (void (*)(void))wifi_manager_get_regulatory_domain,
+    (void (*)(void))wifi_manager_can_set_regulatory_domain,
+    (void (*)(void))wifi_manager_set_regulatory_domain,
+    (void (*)(void))wifi_manager_get_allowed_channels,
+    (void (*)(void))wifi_free_channel_info,
+    (void (*)(void))wifi_manager_get_interface_name,
+    (void (*)(void))wifi_manager_get_wowlan_capabilities,
+    (void (*)(void))wifi_manager_get_wowlan,
+    (void (*)(void))wifi_manager_set_wowlan,
+    (void (*)(void))wifi_manager_start_network_observer,
+    (void (*)(void))wifi_manager_stop_network_observer,
+    (void (*)(void))wifi_manager_is_profile_store_supported,
+    (void (*)(void))wifi_manager_has_profile,
+    (void (*)(void))wifi_manager_connect_profile,
+    (void (*)(void))wifi_manager_register_callback,
+    (void (*)(void))wifi_manager_unregister_callback,
+    (void (*)(void))wifi_manager_list_interfaces,
+    (void (*)(void))wifi_free_interface_list,
+    (void (*)(void))wifi_manager_list_profiles,
+    (void (*)(void))wifi_free_profile_list,
+    (void (*)(void))wifi_manager_save_profile,
+    (void (*)(void))wifi_manager_forget_profile,
+    (void (*)(void))wifi_manager_set_profile_auto_connect,
+    (void (*)(void))wifi_manager_start_signal_monitor,
+    (void (*)(void))wifi_manager_stop_signal_monitor,
+    (void (*)(void))wifi_manager_get_ip_info,
+    (void (*)(void))wifi_manager_disconnect,
+    (void (*)(void))wifi_manager_get_status,
+    (void (*)(void))wifi_manager_get_last_error,
+    (void (*)(void))wifi_manager_create_hotspot,
+    (void (*)(void))wifi_manager_create_hotspot_with_config,
+    (void (*)(void))wifi_manager_create_hotspot_with_sharing,
+    (void (*)(void))wifi_manager_create_captive_hotspot,
+    (void (*)(void))wifi_manager_create_hotspot_with_timeout,
+    (void (*)(void))wifi_manager_stop_hotspot,
+    (void (*)(void))wifi_manager_is_hotspot_active,
+    (void (*)(void))wifi_manager_is_hotspot_supported,
+    (void (*)(void))wifi_manager_hotspot_clients,
+    (void (*)(void))wifi_free_hotspot_clients,
+    (void (*)(void))wifi_free_network_info,
+};
+
+int main(void) {
+    if (wifi_api_version() != WIFI_API_VERSION) {
+        fprintf(stderr, "library reports C API version 0x%08x, header is 0x%08x\n",
+                (unsigned)wifi_api_version(), (unsigned)WIFI_API_VERSION);
+        return 1;
+    }
+    for (size_t i = 0; i < sizeof(exported) / sizeof(exported[0]); i++) {
+        if (!exported[i]) {
+            fprintf(stderr, "function %zu is not exported\n", i);
+            return 1;
+        }
+    }
+    printf("C API %d.%d: %zu functions checked\n", WIFI_API_VERSION_MAJOR, WIFI_API_VERSION_MINOR,
+           sizeof(exported) / sizeof(exported[0]));
+    return 0;
+}
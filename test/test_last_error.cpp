@@ -0,0 +1,66 @@
+// Checks that threads sharing one platform implementation each see the error of
+// their own last call, as the bindings read it with a separate call afterwards
+
+#include "wifi_impl.hpp"
+#include <iostream>
+#include <string>
+#include <thread>
+
+namespace {
+
+using namespace wificpp;
+
+// Fails connects to "timeout" with TIMEOUT and everything else with INVALID_CREDENTIALS
+class StubImpl : public WifiImpl {
+public:
+    std::vector<NetworkInfo> scan() override { return {}; }
+    bool connect(const std::string& ssid, const std::string&) override {
+        // Give the other thread a chance to run between this call and the caller's read
+        std::this_thread::yield();
+        return fail(ssid == "timeout" ? ErrorCode::TIMEOUT : ErrorCode::INVALID_CREDENTIALS);
+    }
+    bool disconnect() override { return true; }
+    ConnectionStatus getStatus() const override { return ConnectionStatus::DISCONNECTED; }
+    bool createHotspot(const std::string&, const std::string&) override { return false; }
+    bool stopHotspot() override { return true; }
+    bool isHotspotActive() const override { return false; }
+    bool isHotspotSupported() const override { return false; }
+};
+
+// Connect the way WifiManager does and return how many reads saw another thread's error
+int connectRepeatedly(StubImpl& impl, const std::string& ssid, ErrorCode expected) {
+    int mismatches = 0;
+    for (int i = 0; i < 10000; ++i) {
+        impl.clearLastError();
+        impl.connect(ssid, "");
+        std::this_thread::yield();
+        if (impl.getLastError() != expected) {
+            ++mismatches;
+        }
+        impl.clearLastError();
+        impl.disconnect();
+        if (impl.getLastError() != ErrorCode::NONE) {
+            ++mismatches;
+        }
+    }
+    return mismatches;
+}
+
+} // namespace
+
+int main() {
+    StubImpl impl;
+    int timeouts = 0;
+    int rejected = 0;
+    std::thread first([&] { timeouts = connectRepeatedly(impl, "timeout", ErrorCode::TIMEOUT); });
+    std::thread second([&] { rejected = connectRepeatedly(impl, "wrong-password", ErrorCode::INVALID_CREDENTIALS); });
+    first.join();
+    second.join();
+
+    if (timeouts != 0 || rejected != 0) {
+        std::cerr << "last error leaked between threads: " << timeouts << " and " << rejected << " wrong reads\n";
+        return 1;
+    }
+    std::cout << "Last errors stayed with their threads\n";
+    return 0;
+}
@@ -20,6 +20,16 @@ fn main() {
         // Copy the DLL to the target directory for runtime linking
         copy_dll();
     }
+    
+    if cfg!(target_os = "macos") {
+        // The frameworks the CoreWLAN implementation is built on
+        for framework in ["CoreFoundation", "Foundation", "CoreWLAN", "CoreLocation", "SystemConfiguration", "AppKit"] {
+            println!("cargo:rustc-link-lib=framework={}", framework);
+        }
+        
+        // Let binaries find libwificpp.dylib in the cmake build directory
+        println!("cargo:rustc-link-arg=-Wl,-rpath,{}/build", dst.display());
+    }
 }
 
 fn copy_dll() {
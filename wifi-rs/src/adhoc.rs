@@ -0,0 +1,44 @@
+//! Ad-hoc (IBSS) network settings.
+
+use crate::secret::SecretString;
+
+/// Settings for [`WiFi::join_adhoc`](crate::WiFi::join_adhoc).
+///
+/// Ad-hoc networks connect stations directly without an access point. They
+/// are still found on legacy industrial equipment; new deployments should
+/// prefer a hotspot, Wi-Fi Direct or a mesh.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdHocConfig {
+    pub(crate) ssid: String,
+    pub(crate) channel: u32,
+    pub(crate) password: Option<SecretString>,
+}
+
+impl AdHocConfig {
+    /// An open ad-hoc network named `ssid` on `channel`. All stations must
+    /// use the same channel.
+    pub fn new(ssid: &str, channel: u32) -> Self {
+        AdHocConfig {
+            ssid: ssid.to_string(),
+            channel,
+            password: None,
+        }
+    }
+
+    /// Secure the network with WPA-None using a passphrase of 8 to 63
+    /// characters.
+    ///
+    /// WPA-None uses a static group key and offers little protection; it
+    /// exists for compatibility with equipment that supports nothing else.
+    pub fn password(mut self, password: impl Into<SecretString>) -> Self {
+        self.password = Some(password.into());
+        self
+    }
+}
+
+#[repr(C)]
+pub(crate) struct RawAdHocConfig {
+    pub(crate) ssid: *const libc::c_char,
+    pub(crate) channel: libc::c_int,
+    pub(crate) password: *const libc::c_char,
+}
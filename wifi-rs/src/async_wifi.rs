@@ -0,0 +1,176 @@
+//! Async access for applications running on an executor.
+//!
+//! Scanning and connecting block for seconds, which stalls an executor
+//! thread when called from async code. An [`AsyncWiFi`] owns a [`WiFi`] on
+//! a dedicated worker thread and runs each call there, so the caller only
+//! awaits the result. Calls are executed one at a time in the order they
+//! were made. The futures do not depend on a particular runtime.
+//!
+//! ```no_run
+//! use wifi_rs::{AsyncWiFi, WifiError};
+//!
+//! async fn join_strongest(wifi: &AsyncWiFi, password: &str) -> Result<(), WifiError> {
+//!     let networks = wifi.scan_async().await;
+//!     match networks.iter().max_by_key(|n| n.signal_strength) {
+//!         Some(network) => wifi.connect_async(&network.ssid, Some(password)).await,
+//!         None => Err(WifiError::OperationFailed),
+//!     }
+//! }
+//! ```
+
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc;
+use std::thread::{self, JoinHandle};
+
+use tokio::sync::oneshot;
+
+use crate::{
+    CancellationHandle, ConnectOptions, ConnectionStatus, HotspotConfig, NetworkInfo, SecretString, StatusDetail, WiFi,
+    WifiError,
+};
+
+type Job = Box<dyn FnOnce(&WiFi) + Send>;
+
+/// A [`WiFi`] driven from async code.
+pub struct AsyncWiFi {
+    jobs: Option<mpsc::Sender<Job>>,
+    worker: Option<JoinHandle<()>>,
+    cancel: CancellationHandle,
+}
+
+impl AsyncWiFi {
+    /// Start the worker thread and open the adapter on it.
+    pub fn new() -> Self {
+        let (jobs, queue) = mpsc::channel::<Job>();
+        let (cancel_sender, cancel) = mpsc::channel();
+        let worker = thread::Builder::new()
+            .name("wifi-rs".to_string())
+            .spawn(move || {
+                let wifi = WiFi::new();
+                let _ = cancel_sender.send(wifi.cancellation_handle());
+                for job in queue {
+                    // A panicking call fails only its own future
+                    let _ = panic::catch_unwind(AssertUnwindSafe(|| job(&wifi)));
+                }
+            })
+            .expect("failed to spawn the WiFi worker thread");
+        let cancel = cancel.recv().expect("failed to open the adapter on the WiFi worker thread");
+        AsyncWiFi { jobs: Some(jobs), worker: Some(worker), cancel }
+    }
+
+    /// Abort the call running on the worker thread, which then fails with
+    /// [`WifiError::Cancelled`]; calls queued behind it still run. Returns
+    /// whether a scan or connect was in progress. See [`WiFi::cancel`].
+    pub fn cancel(&self) -> bool {
+        self.cancel.cancel()
+    }
+
+    /// A handle that cancels the running call, e.g. from a UI event
+    /// handler while a [`connect_async`](Self::connect_async) is awaited.
+    pub fn cancellation_handle(&self) -> CancellationHandle {
+        self.cancel.clone()
+    }
+
+    /// Run `f` with the [`WiFi`] on the worker thread, for operations
+    /// without an async wrapper.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `f` panics.
+    pub async fn call<T, F>(&self, f: F) -> T
+    where
+        T: Send + 'static,
+        F: FnOnce(&WiFi) -> T + Send + 'static,
+    {
+        let (result, receiver) = oneshot::channel();
+        let job: Job = Box::new(move |wifi| {
+            let _ = result.send(f(wifi));
+        });
+        if let Some(jobs) = &self.jobs {
+            // The worker only exits once the sender is dropped
+            let _ = jobs.send(job);
+        }
+        receiver.await.expect("WiFi operation panicked on the worker thread")
+    }
+
+    /// Asynchronous [`WiFi::scan`].
+    pub async fn scan_async(&self) -> Vec<NetworkInfo> {
+        self.call(WiFi::scan).await
+    }
+
+    /// Asynchronous [`WiFi::connect`].
+    ///
+    /// # Errors
+    ///
+    /// As for [`WiFi::connect`].
+    pub async fn connect_async(&self, ssid: &str, password: Option<&str>) -> Result<(), WifiError> {
+        let ssid = ssid.to_string();
+        let password = password.map(SecretString::from);
+        self.call(move |wifi| wifi.connect(&ssid, password.as_ref().map(SecretString::expose))).await
+    }
+
+    /// Asynchronous [`WiFi::connect_with`].
+    ///
+    /// # Errors
+    ///
+    /// As for [`WiFi::connect_with`].
+    pub async fn connect_with_async(&self, ssid: &str, options: &ConnectOptions) -> Result<(), WifiError> {
+        let ssid = ssid.to_string();
+        let options = options.clone();
+        self.call(move |wifi| wifi.connect_with(&ssid, &options)).await
+    }
+
+    /// Asynchronous [`WiFi::disconnect`].
+    ///
+    /// # Errors
+    ///
+    /// As for [`WiFi::disconnect`].
+    pub async fn disconnect_async(&self) -> Result<(), WifiError> {
+        self.call(WiFi::disconnect).await
+    }
+
+    /// Asynchronous [`WiFi::get_status`].
+    pub async fn get_status_async(&self) -> ConnectionStatus {
+        self.call(WiFi::get_status).await
+    }
+
+    /// Asynchronous [`WiFi::status_detailed`].
+    pub async fn status_detailed_async(&self) -> StatusDetail {
+        self.call(WiFi::status_detailed).await
+    }
+
+    /// Asynchronous [`WiFi::create_hotspot_with`].
+    ///
+    /// # Errors
+    ///
+    /// As for [`WiFi::create_hotspot_with`].
+    pub async fn create_hotspot_async(&self, config: &HotspotConfig) -> Result<(), WifiError> {
+        let config = config.clone();
+        self.call(move |wifi| wifi.create_hotspot_with(&config)).await
+    }
+
+    /// Asynchronous [`WiFi::stop_hotspot`].
+    ///
+    /// # Errors
+    ///
+    /// As for [`WiFi::stop_hotspot`].
+    pub async fn stop_hotspot_async(&self) -> Result<(), WifiError> {
+        self.call(WiFi::stop_hotspot).await
+    }
+}
+
+impl Default for AsyncWiFi {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for AsyncWiFi {
+    fn drop(&mut self) {
+        // Closing the queue lets the worker finish pending calls and exit
+        self.jobs.take();
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
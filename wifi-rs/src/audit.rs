@@ -0,0 +1,128 @@
+//! Security audit of the surrounding RF environment.
+
+use crate::{NetworkInfo, PmfMode, SecurityType};
+
+/// How serious an audit finding is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Low,
+    Medium,
+    High,
+}
+
+/// A weakness found on an access point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Finding {
+    /// WEP encryption, which can be cracked in minutes.
+    Wep,
+    /// WPA (TKIP) instead of WPA2/WPA3.
+    Wpa1,
+    /// No encryption at all.
+    Open,
+    /// WPA2/WPA3 without Protected Management Frames, allowing
+    /// deauthentication attacks.
+    PmfMissing,
+    /// WPS is enabled, exposing the network to PIN brute-forcing.
+    WpsEnabled,
+    /// The SSID looks like a vendor default, suggesting an unconfigured AP
+    /// that may still use default credentials.
+    DefaultSsid,
+}
+
+impl Finding {
+    /// Severity assigned to the finding.
+    pub fn severity(&self) -> Severity {
+        match self {
+            Finding::Wep | Finding::Wpa1 => Severity::High,
+            Finding::Open | Finding::WpsEnabled => Severity::Medium,
+            Finding::PmfMissing | Finding::DefaultSsid => Severity::Low,
+        }
+    }
+}
+
+/// A finding tied to the access point it was observed on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditIssue {
+    pub ssid: String,
+    pub bssid: String,
+    pub finding: Finding,
+}
+
+impl AuditIssue {
+    /// Severity of the underlying finding.
+    pub fn severity(&self) -> Severity {
+        self.finding.severity()
+    }
+}
+
+/// Structured result of [`WiFi::security_audit`](crate::WiFi::security_audit).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SecurityAudit {
+    /// Number of networks examined.
+    pub networks_scanned: usize,
+    /// Issues found, most severe first.
+    pub issues: Vec<AuditIssue>,
+}
+
+// SSIDs (or SSID prefixes) shipped as factory defaults by common vendors
+const DEFAULT_SSID_PREFIXES: &[&str] = &[
+    "linksys", "netgear", "dlink", "d-link", "tp-link_", "tplink_", "asus", "belkin",
+    "huawei-", "tenda_", "xiaomi_", "zte_", "arris-", "motorola", "cisco", "2wire",
+    "default",
+];
+
+fn is_default_ssid(ssid: &str) -> bool {
+    let lower = ssid.to_ascii_lowercase();
+    DEFAULT_SSID_PREFIXES.iter().any(|prefix| lower.starts_with(prefix))
+}
+
+impl SecurityAudit {
+    /// Audit a set of scan results.
+    pub fn from_networks(networks: &[NetworkInfo]) -> Self {
+        let mut issues = Vec::new();
+        for network in networks {
+            let mut push = |finding| {
+                issues.push(AuditIssue {
+                    ssid: network.ssid.clone(),
+                    bssid: network.bssid.clone(),
+                    finding,
+                })
+            };
+            match network.security_type {
+                SecurityType::Wep => push(Finding::Wep),
+                SecurityType::Wpa => push(Finding::Wpa1),
+                SecurityType::None => push(Finding::Open),
+                SecurityType::Wpa2 | SecurityType::Wpa3 if network.pmf == PmfMode::Disabled => {
+                    push(Finding::PmfMissing)
+                }
+                _ => {}
+            }
+            if network.wps_enabled {
+                push(Finding::WpsEnabled);
+            }
+            if is_default_ssid(&network.ssid) {
+                push(Finding::DefaultSsid);
+            }
+        }
+        issues.sort_by_key(|issue| std::cmp::Reverse(issue.severity()));
+        SecurityAudit {
+            networks_scanned: networks.len(),
+            issues,
+        }
+    }
+
+    /// Returns `true` if no issues were found.
+    pub fn is_clean(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Issues at or above `severity`.
+    pub fn at_least(&self, severity: Severity) -> impl Iterator<Item = &AuditIssue> {
+        self.issues.iter().filter(move |issue| issue.severity() >= severity)
+    }
+
+    /// Issues of a specific kind.
+    pub fn with_finding(&self, finding: Finding) -> impl Iterator<Item = &AuditIssue> {
+        self.issues.iter().filter(move |issue| issue.finding == finding)
+    }
+}
@@ -0,0 +1,140 @@
+//! A [`ConnectionPolicy`] maintained on a background thread.
+//!
+//! [`ConnectionPolicy::spawn`](crate::ConnectionPolicy::spawn) hands the
+//! policy to a thread that checks it on an interval and, between checks,
+//! watches the link so a lost connection is replaced within seconds rather
+//! than at the next check.
+//!
+//! ```no_run
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//! use wifi_rs::{Band, ConnectOptions, ConnectionPolicy, PolicyNetwork, PolicyRules, WiFi};
+//!
+//! let wifi = Arc::new(WiFi::new());
+//! let rules = PolicyRules { preferred_band: Some(Band::Ghz5), avoid_metered: true, ..PolicyRules::default() };
+//! let auto = ConnectionPolicy::new(rules)
+//!     .network(PolicyNetwork::new("Office", ConnectOptions::password("secret")).priority(1))
+//!     .network(PolicyNetwork::new("Phone", ConnectOptions::password("secret")).metered(true))
+//!     .spawn(Arc::clone(&wifi), Duration::from_secs(60));
+//! for event in auto.subscribe() {
+//!     println!("{:?}", event);
+//! }
+//! ```
+
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use crate::{ConnectionPolicy, ConnectionStatus, PolicyEvent, WifiBackend};
+
+/// How often the link is checked between two policy checks.
+const LINK_POLL: Duration = Duration::from_secs(2);
+
+#[derive(Default)]
+struct State {
+    subscribers: Vec<Sender<PolicyEvent>>,
+    refresh: bool,
+    stopped: bool,
+}
+
+#[derive(Default)]
+struct Shared {
+    state: Mutex<State>,
+    wake: Condvar,
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Handle of a policy running on a background thread, created by
+/// [`ConnectionPolicy::spawn`](crate::ConnectionPolicy::spawn).
+///
+/// Dropping the handle stops the thread, waiting for a check in progress
+/// to finish. The connection is left as it is.
+pub struct AutoConnect {
+    shared: Arc<Shared>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl AutoConnect {
+    pub(crate) fn start<B>(policy: ConnectionPolicy, backend: Arc<B>, interval: Duration) -> Self
+    where
+        B: WifiBackend + Send + Sync + 'static,
+    {
+        let shared = Arc::new(Shared::default());
+        let worker = {
+            let shared = Arc::clone(&shared);
+            thread::Builder::new()
+                .name("wifi-rs-autoconnect".to_string())
+                .spawn(move || policy_loop(policy, backend.as_ref(), interval, &shared))
+                .expect("failed to spawn the auto-connect thread")
+        };
+        AutoConnect { shared, worker: Some(worker) }
+    }
+
+    /// Receive every event of the policy from now on. The receiver may be
+    /// dropped at any time.
+    pub fn subscribe(&self) -> Receiver<PolicyEvent> {
+        let (sender, receiver) = mpsc::channel();
+        self.shared.lock().subscribers.push(sender);
+        receiver
+    }
+
+    /// Check the policy as soon as possible instead of waiting for the next
+    /// interval, e.g. after the application learned that a network came
+    /// into range.
+    pub fn check_now(&self) {
+        self.shared.lock().refresh = true;
+        self.shared.wake.notify_all();
+    }
+}
+
+impl Drop for AutoConnect {
+    fn drop(&mut self) {
+        self.shared.lock().stopped = true;
+        self.shared.wake.notify_all();
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+fn policy_loop(mut policy: ConnectionPolicy, backend: &impl WifiBackend, interval: Duration, shared: &Shared) {
+    let mut current = backend.link_info().map(|link| link.ssid);
+    let mut next_check = Instant::now();
+    let mut forced = false;
+    loop {
+        if current.is_some() && backend.get_status() == ConnectionStatus::Disconnected {
+            publish(shared, PolicyEvent::LinkLost { ssid: current.take().unwrap_or_default() });
+            forced = true;
+        }
+        if forced || Instant::now() >= next_check {
+            let event = policy.check(backend);
+            // Track the new link before subscribers can react to the event
+            current = backend.link_info().map(|link| link.ssid);
+            if let Some(event) = event {
+                publish(shared, event);
+            }
+            next_check = Instant::now() + interval;
+        }
+
+        let wait = next_check.saturating_duration_since(Instant::now()).min(LINK_POLL);
+        let state = shared.lock();
+        let (mut state, _) = shared
+            .wake
+            .wait_timeout_while(state, wait, |state| !state.stopped && !state.refresh)
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if state.stopped {
+            return;
+        }
+        forced = std::mem::take(&mut state.refresh);
+    }
+}
+
+fn publish(shared: &Shared, event: PolicyEvent) {
+    shared.lock().subscribers.retain(|subscriber| subscriber.send(event.clone()).is_ok());
+}
@@ -0,0 +1,149 @@
+//! Common interface of real and simulated WiFi backends.
+//!
+//! Application code written against [`WifiBackend`] runs unchanged on a
+//! [`WiFi`] and, with the `backend-mock` feature, on a scriptable
+//! [`MockBackend`](crate::mock::MockBackend) in unit tests and CI. The
+//! trait is object safe, and boxed or shared backends implement it too, so
+//! the backend can also be chosen at runtime:
+//!
+//! ```no_run
+//! use wifi_rs::{ConnectionPolicy, PolicyRules, WiFi, WifiBackend};
+//!
+//! fn open_backend(simulate: bool) -> Box<dyn WifiBackend> {
+//!     # #[cfg(feature = "backend-mock")]
+//!     if simulate {
+//!         return Box::new(wifi_rs::mock::MockBackend::new());
+//!     }
+//!     Box::new(WiFi::new())
+//! }
+//!
+//! let backend = open_backend(std::env::var_os("WIFI_SIMULATE").is_some());
+//! ConnectionPolicy::new(PolicyRules::default()).check(&backend);
+//! ```
+
+use std::sync::Arc;
+
+use crate::{
+    ConnectOptions, ConnectionStatus, EventKind, HotspotConfig, LinkInfo, NetworkInfo, WiFi, WifiError, WifiEvent,
+};
+
+/// The operations application code typically needs from a WiFi adapter.
+pub trait WifiBackend {
+    /// Visible networks, one entry per access point.
+    fn scan(&self) -> Vec<NetworkInfo>;
+
+    /// Connect to `ssid` with the given credentials and settings.
+    fn connect_with(&self, ssid: &str, options: &ConnectOptions) -> Result<(), WifiError>;
+
+    /// Drop the current connection.
+    fn disconnect(&self) -> Result<(), WifiError>;
+
+    /// Current connection state.
+    fn get_status(&self) -> ConnectionStatus;
+
+    /// Details of the current association, `None` while not connected.
+    fn link_info(&self) -> Option<LinkInfo>;
+
+    /// Recent events, oldest first.
+    fn event_log(&self) -> Vec<WifiEvent>;
+
+    /// Add an event to the log, for components such as the
+    /// [`ConnectionWatchdog`](crate::ConnectionWatchdog) that act on the
+    /// backend's behalf.
+    fn record_event(&self, kind: EventKind);
+
+    /// Start a hotspot. Backends without hotspot support return
+    /// [`WifiError::Unsupported`], the default.
+    fn create_hotspot_with(&self, config: &HotspotConfig) -> Result<(), WifiError> {
+        let _ = config;
+        Err(WifiError::Unsupported)
+    }
+
+    /// Stop the hotspot. Backends without hotspot support return
+    /// [`WifiError::Unsupported`], the default.
+    fn stop_hotspot(&self) -> Result<(), WifiError> {
+        Err(WifiError::Unsupported)
+    }
+}
+
+macro_rules! forward_backend {
+    ($($wrapper:ty),*) => {$(
+        impl<B: WifiBackend + ?Sized> WifiBackend for $wrapper {
+            fn scan(&self) -> Vec<NetworkInfo> {
+                (**self).scan()
+            }
+
+            fn connect_with(&self, ssid: &str, options: &ConnectOptions) -> Result<(), WifiError> {
+                (**self).connect_with(ssid, options)
+            }
+
+            fn disconnect(&self) -> Result<(), WifiError> {
+                (**self).disconnect()
+            }
+
+            fn get_status(&self) -> ConnectionStatus {
+                (**self).get_status()
+            }
+
+            fn link_info(&self) -> Option<LinkInfo> {
+                (**self).link_info()
+            }
+
+            fn event_log(&self) -> Vec<WifiEvent> {
+                (**self).event_log()
+            }
+
+            fn record_event(&self, kind: EventKind) {
+                (**self).record_event(kind)
+            }
+
+            fn create_hotspot_with(&self, config: &HotspotConfig) -> Result<(), WifiError> {
+                (**self).create_hotspot_with(config)
+            }
+
+            fn stop_hotspot(&self) -> Result<(), WifiError> {
+                (**self).stop_hotspot()
+            }
+        }
+    )*};
+}
+
+forward_backend!(&B, Box<B>, Arc<B>);
+
+impl WifiBackend for WiFi {
+    fn scan(&self) -> Vec<NetworkInfo> {
+        WiFi::scan(self)
+    }
+
+    fn connect_with(&self, ssid: &str, options: &ConnectOptions) -> Result<(), WifiError> {
+        WiFi::connect_with(self, ssid, options)
+    }
+
+    fn disconnect(&self) -> Result<(), WifiError> {
+        WiFi::disconnect(self)
+    }
+
+    fn get_status(&self) -> ConnectionStatus {
+        WiFi::get_status(self)
+    }
+
+    fn link_info(&self) -> Option<LinkInfo> {
+        WiFi::link_info(self)
+    }
+
+    fn event_log(&self) -> Vec<WifiEvent> {
+        WiFi::event_log(self)
+    }
+
+    fn record_event(&self, kind: EventKind) {
+        self.events.record(kind);
+    }
+
+    fn create_hotspot_with(&self, config: &HotspotConfig) -> Result<(), WifiError> {
+        WiFi::create_hotspot_with(self, config)
+    }
+
+    fn stop_hotspot(&self) -> Result<(), WifiError> {
+        WiFi::stop_hotspot(self)
+    }
+}
@@ -0,0 +1,114 @@
+//! Band steering for networks broadcast on several bands.
+
+use crate::channel::Band;
+use crate::NetworkInfo;
+
+/// Which band to join when a network is available on 2.4, 5 and 6 GHz.
+///
+/// The default prefers 6 GHz when its strongest access point is at least
+/// -70 dBm, then 5 GHz, and uses 2.4 GHz only when no 5 or 6 GHz access
+/// point is usable (at least -82 dBm). Attach a policy to a network with
+/// [`ConnectOptions::band_policy`](crate::ConnectOptions::band_policy); the
+/// connection and the platform's own roaming then stay on the chosen band,
+/// where the platform supports restricting frequencies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BandPolicy {
+    pub(crate) prefer_6ghz_dbm: Option<i32>,
+    pub(crate) usable_dbm: i32,
+    pub(crate) allow_2ghz: bool,
+}
+
+impl Default for BandPolicy {
+    fn default() -> Self {
+        BandPolicy {
+            prefer_6ghz_dbm: Some(-70),
+            usable_dbm: -82,
+            allow_2ghz: true,
+        }
+    }
+}
+
+impl BandPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Prefer 6 GHz over 5 GHz when its signal is at least `dbm`.
+    pub fn prefer_6ghz_above(mut self, dbm: i32) -> Self {
+        self.prefer_6ghz_dbm = Some(dbm);
+        self
+    }
+
+    /// Only use 6 GHz when no 5 GHz access point is usable.
+    pub fn avoid_6ghz(mut self) -> Self {
+        self.prefer_6ghz_dbm = None;
+        self
+    }
+
+    /// Weakest signal at which an access point is considered usable.
+    pub fn usable_above(mut self, dbm: i32) -> Self {
+        self.usable_dbm = dbm;
+        self
+    }
+
+    /// Whether 2.4 GHz may be used as a last resort.
+    pub fn allow_2ghz(mut self, allow: bool) -> Self {
+        self.allow_2ghz = allow;
+        self
+    }
+
+    /// Band to join for `ssid` given scan results.
+    ///
+    /// # Returns
+    ///
+    /// `None` if no access point of `ssid` is visible on a band the policy
+    /// allows.
+    pub fn choose(&self, ssid: &str, networks: &[NetworkInfo]) -> Option<Band> {
+        let best = |band: Band| {
+            networks
+                .iter()
+                .filter(|n| n.ssid == ssid && Band::from_frequency(n.frequency) == Some(band))
+                .map(|n| n.signal_strength)
+                .max()
+        };
+        let ghz6 = best(Band::Ghz6);
+        let ghz5 = best(Band::Ghz5);
+        let ghz2 = best(Band::Ghz2);
+        let usable = |signal: Option<i32>| signal.is_some_and(|dbm| dbm >= self.usable_dbm);
+
+        if let (Some(threshold), Some(dbm)) = (self.prefer_6ghz_dbm, ghz6) {
+            if dbm >= threshold {
+                return Some(Band::Ghz6);
+            }
+        }
+        if usable(ghz5) {
+            return Some(Band::Ghz5);
+        }
+        if usable(ghz6) {
+            return Some(Band::Ghz6);
+        }
+        if self.allow_2ghz && ghz2.is_some() {
+            return Some(Band::Ghz2);
+        }
+        // Nothing is usable: take the strongest allowed band rather than none
+        [(Band::Ghz6, ghz6), (Band::Ghz5, ghz5)]
+            .into_iter()
+            .filter_map(|(band, dbm)| dbm.map(|dbm| (band, dbm)))
+            .max_by_key(|&(_, dbm)| dbm)
+            .map(|(band, _)| band)
+    }
+
+    /// Frequencies of the access points of `ssid` on the chosen band.
+    pub(crate) fn frequencies(&self, ssid: &str, networks: &[NetworkInfo]) -> Option<Vec<i32>> {
+        let band = self.choose(ssid, networks)?;
+        let mut frequencies: Vec<i32> = networks
+            .iter()
+            .filter(|n| n.ssid == ssid && Band::from_frequency(n.frequency) == Some(band))
+            .map(|n| n.frequency)
+            .collect();
+        frequencies.sort_unstable();
+        frequencies.dedup();
+        Some(frequencies)
+    }
+}
@@ -0,0 +1,212 @@
+//! Configuration of a [`WiFi`] handle before it is opened.
+
+use std::time::Duration;
+
+use crate::logging::{self, LogLevel, LogSink};
+use crate::{LinuxBackend, WiFi, WifiError};
+
+/// How long the blocking native operations may take, set with
+/// [`WiFiBuilder::timeouts`].
+///
+/// The default waits the platform's default time for an association, the
+/// 120 second WPS walk time for a WPS exchange, 10 seconds for a scan and
+/// 90 seconds for a hotspot to start.
+///
+/// When a scan or hotspot start runs out of time the native layer stops
+/// it rather than leaving the calling thread blocked: a scan is aborted and
+/// returns what it found so far, and a hotspot is torn down and fails with
+/// [`WifiError::Timeout`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Timeouts {
+    pub(crate) connect: Option<Duration>,
+    pub(crate) wps: Duration,
+    pub(crate) scan: Option<Duration>,
+    pub(crate) hotspot: Option<Duration>,
+}
+
+impl Default for Timeouts {
+    fn default() -> Self {
+        Timeouts {
+            connect: None,
+            wps: Duration::from_secs(120),
+            scan: None,
+            hotspot: None,
+        }
+    }
+}
+
+impl Timeouts {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Give up on a connect that has not joined the network within
+    /// `timeout`, unless [`ConnectOptions::timeout`](crate::ConnectOptions::timeout)
+    /// sets another. Platforms that connect asynchronously ignore it.
+    pub fn connect(mut self, timeout: Duration) -> Self {
+        self.connect = Some(timeout);
+        self
+    }
+
+    /// Wait up to `timeout` for an access point to complete a WPS exchange.
+    pub fn wps(mut self, timeout: Duration) -> Self {
+        self.wps = timeout;
+        self
+    }
+
+    /// Wait up to `timeout` for a scan to complete, unless
+    /// [`ScanOptions::timeout`](crate::ScanOptions::timeout) sets another.
+    pub fn scan(mut self, timeout: Duration) -> Self {
+        self.scan = Some(timeout);
+        self
+    }
+
+    /// Wait up to `timeout` for a hotspot's access point to start, unless
+    /// [`HotspotConfig::timeout`](crate::HotspotConfig::timeout) sets
+    /// another. Channels that need a radar check (DFS) take over a minute.
+    pub fn hotspot(mut self, timeout: Duration) -> Self {
+        self.hotspot = Some(timeout);
+        self
+    }
+
+    /// WPS timeout in whole seconds for the native layer.
+    pub(crate) fn wps_seconds(&self) -> libc::c_int {
+        self.wps.as_secs().clamp(1, libc::c_int::MAX as u64) as libc::c_int
+    }
+
+    /// A timeout in milliseconds for the native layer, 0 for its default.
+    pub(crate) fn millis(timeout: Option<Duration>) -> i32 {
+        timeout.map_or(0, |timeout| timeout.as_millis().clamp(1, i32::MAX as u128) as i32)
+    }
+}
+
+/// Settings for opening a [`WiFi`], created by [`WiFi::builder`].
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use wifi_rs::{LogLevel, Timeouts, WiFi};
+///
+/// let wifi = WiFi::builder()
+///     .interface("wlan1")
+///     .scan_cache_ttl(Duration::from_secs(10))
+///     .timeouts(Timeouts::new().connect(Duration::from_secs(20)).scan(Duration::from_secs(5)))
+///     .log_sink(|level, message| eprintln!("{:?}: {}", level, message))
+///     .log_level(LogLevel::Warning)
+///     .require_admin(true)
+///     .build()?;
+/// # Ok::<(), wifi_rs::WifiError>(())
+/// ```
+#[derive(Default)]
+pub struct WiFiBuilder {
+    interface: Option<String>,
+    scan_cache_ttl: Option<Duration>,
+    timeouts: Timeouts,
+    log_sink: Option<LogSink>,
+    log_level: Option<LogLevel>,
+    require_admin: bool,
+    linux_backend: Option<LinuxBackend>,
+}
+
+impl WiFiBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Operate on the adapter named `name`, e.g. `"wlan1"`, instead of the
+    /// platform's default.
+    pub fn interface(mut self, name: &str) -> Self {
+        self.interface = Some(name.to_string());
+        self
+    }
+
+    /// Answer [`WiFi::scan`] from the previous scan while it is younger than
+    /// `ttl`, so components that scan independently share one radio scan.
+    /// Scans that found nothing are not reused.
+    pub fn scan_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.scan_cache_ttl = Some(ttl);
+        self
+    }
+
+    pub fn timeouts(mut self, timeouts: Timeouts) -> Self {
+        self.timeouts = timeouts;
+        self
+    }
+
+    /// Receive the native library's log messages instead of having them
+    /// printed to standard output.
+    ///
+    /// The native logger is shared by the whole process, so the sink also
+    /// receives messages of other [`WiFi`] instances and replaces any sink
+    /// set before. It may be called from any thread.
+    pub fn log_sink(mut self, sink: impl Fn(LogLevel, &str) + Send + Sync + 'static) -> Self {
+        self.log_sink = Some(Box::new(sink));
+        self
+    }
+
+    /// Emit the native library's log messages as `tracing` events with
+    /// target `wifi_rs::native` instead of printing them, replacing any
+    /// [`log_sink`](Self::log_sink).
+    ///
+    /// Native code logs on the calling thread, so its messages land inside
+    /// the `wifi_op` span of the operation that produced them.
+    #[cfg(feature = "tracing")]
+    pub fn log_to_tracing(self) -> Self {
+        self.log_sink(crate::trace::native_log)
+    }
+
+    /// Drop native log messages below `level`, [`LogLevel::Info`] by
+    /// default. Like the sink, this applies to the whole process.
+    pub fn log_level(mut self, level: LogLevel) -> Self {
+        self.log_level = Some(level);
+        self
+    }
+
+    /// Refuse to open the adapter unless the process has the privileges to
+    /// manage it (root or `CAP_NET_ADMIN` on Linux, an elevated process on
+    /// Windows), instead of failing on the first connect or hotspot call.
+    pub fn require_admin(mut self, require: bool) -> Self {
+        self.require_admin = require;
+        self
+    }
+
+    /// Make connections on Linux with `backend` instead of picking one
+    /// automatically. See [`WiFi::set_linux_backend`].
+    pub fn linux_backend(mut self, backend: LinuxBackend) -> Self {
+        self.linux_backend = Some(backend);
+        self
+    }
+
+    /// Open the adapter.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WifiError::AdapterNotFound`] if there is no usable wireless
+    /// adapter (with that name), or [`WifiError::PermissionDenied`] if
+    /// [`require_admin`](Self::require_admin) is set and the process lacks
+    /// the privileges, or the error of
+    /// [`WiFi::set_linux_backend`] if a backend was selected.
+    pub fn build(self) -> Result<WiFi, WifiError> {
+        // Before the manager exists, so messages about opening it reach the sink
+        if let Some(sink) = self.log_sink {
+            logging::set_sink(Some(sink));
+        }
+        if let Some(level) = self.log_level {
+            logging::set_level(level);
+        }
+
+        let mut wifi = match &self.interface {
+            Some(name) => WiFi::with_interface(name)?,
+            None => WiFi::try_new()?,
+        };
+        if self.require_admin && !wifi.has_admin_privileges() {
+            return Err(WifiError::PermissionDenied);
+        }
+        if let Some(backend) = self.linux_backend {
+            wifi.set_linux_backend(backend)?;
+        }
+        wifi.set_timeouts(self.timeouts);
+        wifi.scan_cache_ttl = self.scan_cache_ttl;
+        Ok(wifi)
+    }
+}
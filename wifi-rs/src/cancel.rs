@@ -0,0 +1,114 @@
+//! Aborting a scan or connect from another thread.
+//!
+//! A [`CancellationHandle`] lets a UI thread give up on an operation that
+//! a worker thread is blocked in, e.g. when the user picks another network
+//! while the first is still being joined:
+//!
+//! ```no_run
+//! use std::sync::Arc;
+//! use wifi_rs::{WiFi, WifiError};
+//!
+//! let wifi = Arc::new(WiFi::new());
+//! let cancel = wifi.cancellation_handle();
+//! let worker = {
+//!     let wifi = Arc::clone(&wifi);
+//!     std::thread::spawn(move || wifi.connect("Office", Some("secret")))
+//! };
+//! // The user changed their mind
+//! cancel.cancel();
+//! assert!(matches!(worker.join().unwrap(), Ok(()) | Err(WifiError::Cancelled)));
+//! ```
+
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::{Arc, Mutex};
+
+use crate::WifiManager;
+
+const IDLE: u8 = 0;
+const RUNNING: u8 = 1;
+const CANCELLED: u8 = 2;
+
+/// Cancels the scan or connect in progress on a [`WiFi`](crate::WiFi),
+/// created by [`WiFi::cancellation_handle`](crate::WiFi::cancellation_handle).
+///
+/// The operation stops the platform's work (the scan is aborted, the
+/// association attempt dropped) and fails with
+/// [`WifiError::Cancelled`](crate::WifiError::Cancelled). Operations that
+/// start after the call are not affected. On macOS the native calls cannot
+/// be interrupted.
+///
+/// The handle can be cloned and sent to other threads, and does nothing
+/// once the `WiFi` is dropped.
+#[derive(Debug, Clone)]
+pub struct CancellationHandle {
+    target: Arc<CancelTarget>,
+}
+
+impl CancellationHandle {
+    /// Cancel the operation in progress. Returns whether there was one.
+    pub fn cancel(&self) -> bool {
+        self.target.cancel()
+    }
+}
+
+/// What a [`CancellationHandle`] cancels, owned by a `WiFi`.
+#[derive(Debug)]
+pub(crate) struct CancelTarget {
+    /// The native manager, null once the `WiFi` is dropped.
+    manager: Mutex<ManagerPtr>,
+    /// A wait in Rust around native calls, e.g. in `connect_and_wait`.
+    wait: AtomicU8,
+}
+
+#[derive(Debug)]
+struct ManagerPtr(*mut WifiManager);
+
+// SAFETY: only used to call wifi_manager_cancel, which may be called from
+// any thread, while the mutex keeps the manager from being freed.
+unsafe impl Send for ManagerPtr {}
+
+impl CancelTarget {
+    pub(crate) fn new(manager: *mut WifiManager) -> Arc<Self> {
+        Arc::new(CancelTarget { manager: Mutex::new(ManagerPtr(manager)), wait: AtomicU8::new(IDLE) })
+    }
+
+    pub(crate) fn handle(self: &Arc<Self>) -> CancellationHandle {
+        CancellationHandle { target: Arc::clone(self) }
+    }
+
+    pub(crate) fn cancel(&self) -> bool {
+        let native = {
+            let manager = self.manager.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            !manager.0.is_null() && unsafe { crate::wifi_manager_cancel(manager.0) }
+        };
+        let waiting = self.wait.compare_exchange(RUNNING, CANCELLED, Ordering::SeqCst, Ordering::SeqCst).is_ok();
+        native || waiting
+    }
+
+    /// Forget the manager before it is freed, waiting for a cancel using it.
+    pub(crate) fn detach(&self) {
+        self.manager.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).0 = std::ptr::null_mut();
+    }
+
+    /// Make a wait in Rust cancellable until the guard is dropped.
+    pub(crate) fn begin_wait(&self) -> WaitGuard<'_> {
+        self.wait.store(RUNNING, Ordering::SeqCst);
+        WaitGuard { target: self }
+    }
+}
+
+pub(crate) struct WaitGuard<'a> {
+    target: &'a CancelTarget,
+}
+
+impl WaitGuard<'_> {
+    pub(crate) fn cancelled(&self) -> bool {
+        self.target.wait.load(Ordering::SeqCst) == CANCELLED
+    }
+}
+
+impl Drop for WaitGuard<'_> {
+    fn drop(&mut self) {
+        self.target.wait.store(IDLE, Ordering::SeqCst);
+    }
+}
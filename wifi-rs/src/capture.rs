@@ -0,0 +1,343 @@
+//! Monitor mode capture of raw 802.11 frames.
+//!
+//! [`WiFi::start_capture`](crate::WiFi::start_capture) adds a monitor-mode
+//! interface next to the managed one and streams every frame it receives,
+//! together with its radiotap metadata. Frames can be consumed as an
+//! iterator or written straight to a pcapng file with
+//! [`PcapngWriter::write_packet`](crate::pcap::PcapngWriter::write_packet).
+//! A capture either stays on one channel or hops across channels following a
+//! [`HopSchedule`], so surveys cover the whole spectrum.
+//!
+//! Capturing requires root (or `CAP_NET_ADMIN` and `CAP_NET_RAW`) and a driver
+//! that supports monitor mode. Only Linux is supported at the moment.
+
+use crate::pcap::RadiotapInfo;
+use crate::{WiFi, WifiError};
+use std::ffi::CString;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender, TryRecvError};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Settings for a monitor mode capture.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CaptureConfig {
+    pub(crate) channel: Option<u32>,
+    pub(crate) management: bool,
+    pub(crate) control: bool,
+    pub(crate) data: bool,
+    pub(crate) monitor_interface: Option<String>,
+    pub(crate) hop: Option<HopSchedule>,
+}
+
+/// 2.4 GHz channels used for hopping (1-13).
+pub const CHANNELS_2GHZ: &[u32] = &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13];
+/// 5 GHz channels used for hopping (UNII-1 to UNII-3, 20 MHz).
+pub const CHANNELS_5GHZ: &[u32] = &[
+    36, 40, 44, 48, 52, 56, 60, 64, 100, 104, 108, 112, 116, 120, 124, 128, 132, 136, 140, 144,
+    149, 153, 157, 161, 165,
+];
+
+/// Channel-hop schedule followed by the capture thread.
+///
+/// Channels the radio or regulatory domain does not allow are skipped by the
+/// driver; the capture simply sees no frames during their dwell time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HopSchedule {
+    pub(crate) channels: Vec<u32>,
+    pub(crate) dwell: Duration,
+}
+
+impl Default for HopSchedule {
+    fn default() -> Self {
+        HopSchedule {
+            channels: Vec::new(),
+            dwell: Duration::from_millis(250),
+        }
+    }
+}
+
+impl HopSchedule {
+    /// An empty schedule with a 250 ms dwell time.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cover every 2.4 GHz and 5 GHz channel.
+    pub fn all_bands() -> Self {
+        Self::new().band_2ghz().band_5ghz()
+    }
+
+    /// Add the 2.4 GHz channels.
+    pub fn band_2ghz(self) -> Self {
+        self.channels(CHANNELS_2GHZ)
+    }
+
+    /// Add the 5 GHz channels.
+    pub fn band_5ghz(self) -> Self {
+        self.channels(CHANNELS_5GHZ)
+    }
+
+    /// Add specific channels, in the order they should be visited.
+    pub fn channels(mut self, channels: &[u32]) -> Self {
+        for &channel in channels {
+            if !self.channels.contains(&channel) {
+                self.channels.push(channel);
+            }
+        }
+        self
+    }
+
+    /// Time spent on each channel before moving on.
+    pub fn dwell(mut self, dwell: Duration) -> Self {
+        self.dwell = dwell;
+        self
+    }
+
+    /// Time taken to visit every channel once.
+    pub fn cycle_time(&self) -> Duration {
+        self.dwell * self.channels.len() as u32
+    }
+}
+
+impl Default for CaptureConfig {
+    fn default() -> Self {
+        CaptureConfig {
+            channel: None,
+            management: true,
+            control: true,
+            data: true,
+            monitor_interface: None,
+            hop: None,
+        }
+    }
+}
+
+impl CaptureConfig {
+    /// Capture all frame types on the current channel.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Tune the monitor interface to `channel` before capturing.
+    pub fn channel(mut self, channel: u32) -> Self {
+        self.channel = Some(channel);
+        self
+    }
+
+    /// Hop across channels following `schedule` instead of staying on one.
+    pub fn hop(mut self, schedule: HopSchedule) -> Self {
+        self.hop = Some(schedule);
+        self
+    }
+
+    /// Select which frame types are delivered.
+    pub fn frames(mut self, management: bool, control: bool, data: bool) -> Self {
+        self.management = management;
+        self.control = control;
+        self.data = data;
+        self
+    }
+
+    /// Name of the monitor interface to create (defaults to `<interface>mon`).
+    pub fn monitor_interface(mut self, name: &str) -> Self {
+        self.monitor_interface = Some(name.to_string());
+        self
+    }
+}
+
+#[repr(C)]
+pub(crate) struct RawCaptureConfig {
+    channel: libc::c_int,
+    include_management: libc::c_int,
+    include_control: libc::c_int,
+    include_data: libc::c_int,
+    monitor_interface: *const libc::c_char,
+    hop_channels: *const i32,
+    hop_channel_count: libc::c_int,
+    dwell_ms: libc::c_int,
+}
+
+pub(crate) type RawFrameCallback =
+    extern "C" fn(data: *const u8, length: libc::c_int, timestamp_us: u64, user_data: *mut libc::c_void);
+
+/// 802.11 frame type from the frame control field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameType {
+    Management,
+    Control,
+    Data,
+    Extension,
+}
+
+/// Decoded fixed part of an 802.11 MAC header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameHeader {
+    pub frame_type: FrameType,
+    /// Subtype within the frame type (e.g. 8 for a beacon).
+    pub subtype: u8,
+    /// Frame control flags (to/from DS, retry, protected, ...).
+    pub flags: u8,
+    /// Receiver address.
+    pub addr1: [u8; 6],
+    /// Transmitter address, absent in some control frames.
+    pub addr2: Option<[u8; 6]>,
+    /// BSSID or third address, absent in control frames.
+    pub addr3: Option<[u8; 6]>,
+}
+
+impl FrameHeader {
+    /// Decode the MAC header at the start of `frame`.
+    pub fn parse(frame: &[u8]) -> Option<FrameHeader> {
+        if frame.len() < 10 {
+            return None;
+        }
+        let frame_type = match (frame[0] >> 2) & 0x3 {
+            0 => FrameType::Management,
+            1 => FrameType::Control,
+            2 => FrameType::Data,
+            _ => FrameType::Extension,
+        };
+        let address = |offset: usize| -> Option<[u8; 6]> { frame.get(offset..offset + 6)?.try_into().ok() };
+        Some(FrameHeader {
+            frame_type,
+            subtype: frame[0] >> 4,
+            flags: frame[1],
+            addr1: address(4)?,
+            addr2: address(10),
+            addr3: if frame_type == FrameType::Control { None } else { address(16) },
+        })
+    }
+}
+
+/// A frame received in monitor mode.
+#[derive(Debug, Clone)]
+pub struct CapturedFrame {
+    /// Time the frame was read from the monitor interface.
+    pub timestamp: SystemTime,
+    /// Decoded radiotap metadata.
+    pub radiotap: RadiotapInfo,
+    packet: Vec<u8>,
+    frame_offset: usize,
+}
+
+impl CapturedFrame {
+    fn new(timestamp: SystemTime, packet: Vec<u8>) -> Option<Self> {
+        let (radiotap, frame_offset) = RadiotapInfo::parse(&packet)?;
+        Some(CapturedFrame {
+            timestamp,
+            radiotap,
+            packet,
+            frame_offset,
+        })
+    }
+
+    /// The raw packet including the radiotap header, as written to pcap files.
+    pub fn raw(&self) -> &[u8] {
+        &self.packet
+    }
+
+    /// The 802.11 frame without the radiotap header.
+    pub fn frame(&self) -> &[u8] {
+        &self.packet[self.frame_offset..]
+    }
+
+    /// Decode the 802.11 MAC header.
+    pub fn header(&self) -> Option<FrameHeader> {
+        FrameHeader::parse(self.frame())
+    }
+}
+
+extern "C" fn on_frame(data: *const u8, length: libc::c_int, timestamp_us: u64, user_data: *mut libc::c_void) {
+    if data.is_null() || length <= 0 || user_data.is_null() {
+        return;
+    }
+    // SAFETY: user_data is the boxed sender owned by the `Capture`, which
+    // stops the capture (and therefore all callbacks) before freeing it.
+    let sender = unsafe { &*(user_data as *const Sender<CapturedFrame>) };
+    let packet = unsafe { std::slice::from_raw_parts(data, length as usize) }.to_vec();
+    let timestamp = UNIX_EPOCH + Duration::from_micros(timestamp_us);
+    if let Some(frame) = CapturedFrame::new(timestamp, packet) {
+        let _ = sender.send(frame);
+    }
+}
+
+/// A running monitor mode capture; stops when dropped.
+///
+/// Iterating blocks until the next frame arrives.
+pub struct Capture<'a> {
+    wifi: &'a WiFi,
+    frames: Receiver<CapturedFrame>,
+    sender: *mut Sender<CapturedFrame>,
+}
+
+impl<'a> Capture<'a> {
+    pub(crate) fn start(wifi: &'a WiFi, config: &CaptureConfig) -> Result<Self, WifiError> {
+        let monitor_interface = config
+            .monitor_interface
+            .as_deref()
+            .map(CString::new)
+            .transpose()?;
+        let hop_channels: Vec<i32> = config
+            .hop
+            .as_ref()
+            .map(|hop| hop.channels.iter().map(|&c| c as i32).collect())
+            .unwrap_or_default();
+        let dwell_ms = config.hop.as_ref().map_or(0, |hop| hop.dwell.as_millis() as libc::c_int);
+        let raw = RawCaptureConfig {
+            channel: config.channel.unwrap_or(0) as libc::c_int,
+            include_management: config.management as libc::c_int,
+            include_control: config.control as libc::c_int,
+            include_data: config.data as libc::c_int,
+            monitor_interface: monitor_interface.as_ref().map_or(std::ptr::null(), |m| m.as_ptr()),
+            hop_channels: if hop_channels.is_empty() { std::ptr::null() } else { hop_channels.as_ptr() },
+            hop_channel_count: hop_channels.len() as libc::c_int,
+            dwell_ms,
+        };
+
+        let (sender, frames) = mpsc::channel();
+        let sender = Box::into_raw(Box::new(sender));
+        let started = unsafe {
+            crate::wifi_manager_start_capture(wifi.manager, &raw, on_frame, sender as *mut libc::c_void)
+        };
+        if !started {
+            drop(unsafe { Box::from_raw(sender) });
+            return Err(wifi.native_error());
+        }
+        Ok(Capture { wifi, frames, sender })
+    }
+
+    /// Return the next frame if one is already queued.
+    pub fn try_next(&self) -> Option<CapturedFrame> {
+        match self.frames.try_recv() {
+            Ok(frame) => Some(frame),
+            Err(TryRecvError::Empty) | Err(TryRecvError::Disconnected) => None,
+        }
+    }
+
+    /// Wait up to `timeout` for the next frame.
+    pub fn next_timeout(&self, timeout: Duration) -> Option<CapturedFrame> {
+        match self.frames.recv_timeout(timeout) {
+            Ok(frame) => Some(frame),
+            Err(RecvTimeoutError::Timeout) | Err(RecvTimeoutError::Disconnected) => None,
+        }
+    }
+
+    /// Stop the capture and remove the monitor interface.
+    pub fn stop(self) {}
+}
+
+impl Iterator for Capture<'_> {
+    type Item = CapturedFrame;
+
+    fn next(&mut self) -> Option<CapturedFrame> {
+        self.frames.recv().ok()
+    }
+}
+
+impl Drop for Capture<'_> {
+    fn drop(&mut self) {
+        unsafe {
+            crate::wifi_manager_stop_capture(self.wifi.manager);
+            drop(Box::from_raw(self.sender));
+        }
+    }
+}
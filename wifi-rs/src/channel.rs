@@ -0,0 +1,369 @@
+//! Channel selection from scan results and survey data.
+
+use std::collections::HashMap;
+
+use crate::scan_cache::key;
+use crate::{ChannelSurvey, NetworkInfo};
+
+/// Frequency band.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Band {
+    /// 2.4 GHz
+    Ghz2,
+    /// 5 GHz
+    Ghz5,
+    /// 6 GHz (Wi-Fi 6E and later)
+    Ghz6,
+}
+
+impl Band {
+    /// Band a center frequency in MHz belongs to.
+    pub fn from_frequency(frequency: i32) -> Option<Band> {
+        match frequency {
+            2400..=2500 => Some(Band::Ghz2),
+            5000..=5900 => Some(Band::Ghz5),
+            5925..=7125 => Some(Band::Ghz6),
+            _ => None,
+        }
+    }
+
+    /// Channels considered when recommending a hotspot channel: the
+    /// non-overlapping 2.4 GHz channels, the 5 GHz channels that do not
+    /// require radar detection (DFS) and the 6 GHz preferred scanning
+    /// channels.
+    pub fn hotspot_channels(&self) -> &'static [i32] {
+        match self {
+            Band::Ghz2 => &[1, 6, 11],
+            Band::Ghz5 => &[36, 40, 44, 48, 149, 153, 157, 161, 165],
+            Band::Ghz6 => &[5, 21, 37, 53, 69, 85, 101, 117, 133, 149, 165, 181, 197, 213, 229],
+        }
+    }
+}
+
+/// Width of the channel an access point operates on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ChannelWidth {
+    Mhz20,
+    Mhz40,
+    Mhz80,
+    /// 160 MHz, contiguous or as two 80 MHz segments.
+    Mhz160,
+    /// 320 MHz (Wi-Fi 7 on 6 GHz).
+    Mhz320,
+}
+
+impl ChannelWidth {
+    /// Width in MHz.
+    pub fn mhz(&self) -> i32 {
+        match self {
+            ChannelWidth::Mhz20 => 20,
+            ChannelWidth::Mhz40 => 40,
+            ChannelWidth::Mhz80 => 80,
+            ChannelWidth::Mhz160 => 160,
+            ChannelWidth::Mhz320 => 320,
+        }
+    }
+}
+
+impl NetworkInfo {
+    /// Band of the access point, `None` for bands the crate does not model.
+    ///
+    /// Prefer this over [`channel`](Self::channel) to tell bands apart:
+    /// 6 GHz channel numbers repeat those of 2.4 and 5 GHz.
+    pub fn band(&self) -> Option<Band> {
+        Band::from_frequency(self.frequency)
+    }
+
+    /// Width of the channel the access point operates on, from the EHT,
+    /// HE, VHT and HT operation elements of its beacon. `None` if the
+    /// platform does not report information elements.
+    pub fn channel_width(&self) -> Option<ChannelWidth> {
+        const HT_OPERATION: u8 = 61;
+        const VHT_OPERATION: u8 = 192;
+        const EXTENSION: u8 = 255;
+        const HE_OPERATION: u8 = 36;
+        const EHT_OPERATION: u8 = 106;
+
+        let mut width = None;
+        for element in self.elements() {
+            let data = element.data;
+            let reported = match (element.id, element.extension_id) {
+                // Secondary channel offset
+                (HT_OPERATION, _) => data.get(1).map(|info| match info & 0x3 {
+                    0 => ChannelWidth::Mhz20,
+                    _ => ChannelWidth::Mhz40,
+                }),
+                // 0 defers to HT; 1 with a second center frequency is 160 MHz
+                (VHT_OPERATION, _) => match (data.first(), data.get(2)) {
+                    (Some(1), Some(0)) => Some(ChannelWidth::Mhz80),
+                    (Some(1..=3), _) => Some(ChannelWidth::Mhz160),
+                    _ => None,
+                },
+                // The 6 GHz operation information follows optional fields
+                (EXTENSION, Some(HE_OPERATION)) if data.len() >= 3 && data[2] & 0x02 != 0 => {
+                    let params = u32::from_le_bytes([data[0], data[1], data[2], 0]);
+                    let vht_info = if params & (1 << 14) != 0 { 3 } else { 0 };
+                    let offset = 6 + vht_info + usize::from(params & (1 << 15) != 0);
+                    data.get(offset + 1).map(|control| match control & 0x3 {
+                        0 => ChannelWidth::Mhz20,
+                        1 => ChannelWidth::Mhz40,
+                        2 => ChannelWidth::Mhz80,
+                        _ => ChannelWidth::Mhz160,
+                    })
+                }
+                (EXTENSION, Some(EHT_OPERATION)) if data.first().is_some_and(|params| params & 0x01 != 0) => {
+                    data.get(5).and_then(|control| match control & 0x7 {
+                        0 => Some(ChannelWidth::Mhz20),
+                        1 => Some(ChannelWidth::Mhz40),
+                        2 => Some(ChannelWidth::Mhz80),
+                        3 => Some(ChannelWidth::Mhz160),
+                        4 => Some(ChannelWidth::Mhz320),
+                        _ => None,
+                    })
+                }
+                _ => None,
+            };
+            width = width.max(reported);
+        }
+        width
+    }
+}
+
+/// Channel number of a center frequency in MHz, 0 if unknown.
+pub(crate) fn frequency_to_channel(frequency: i32) -> i32 {
+    match frequency {
+        2484 => 14,
+        2412..=2472 => (frequency - 2407) / 5,
+        5000..=5900 => (frequency - 5000) / 5,
+        5935 => 2,
+        5955..=7115 => (frequency - 5950) / 5,
+        _ => 0,
+    }
+}
+
+/// Center frequency in MHz of `channel` in `band`, `None` if the band has
+/// no such channel.
+pub(crate) fn channel_to_frequency(band: Band, channel: i32) -> Option<i32> {
+    match (band, channel) {
+        (Band::Ghz2, 14) => Some(2484),
+        (Band::Ghz2, 1..=13) => Some(2407 + channel * 5),
+        (Band::Ghz5, 32..=177) => Some(5000 + channel * 5),
+        (Band::Ghz6, 2) => Some(5935),
+        (Band::Ghz6, 1..=233) if channel % 4 == 1 => Some(5950 + channel * 5),
+        _ => None,
+    }
+}
+
+#[repr(C)]
+pub(crate) struct RawChannelInfo {
+    frequency: i32,
+    max_power_mbm: i32,
+    dfs: i32,
+    no_ir: i32,
+}
+
+/// A channel the regulatory domain permits, from
+/// [`WiFi::allowed_channels`](crate::WiFi::allowed_channels).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChannelInfo {
+    pub number: i32,
+    /// Center frequency in MHz.
+    pub frequency: i32,
+    /// Maximum transmit power in dBm, `None` if not reported.
+    pub max_power_dbm: Option<f64>,
+    /// Radar detection (DFS) is required before transmitting, which delays
+    /// a hotspot by a minute or more and can force it off the channel.
+    pub dfs: bool,
+    /// No initiating radiation: the device may not start a network or
+    /// probe actively here, only join one that is already running.
+    pub no_ir: bool,
+}
+
+impl ChannelInfo {
+    pub(crate) fn from_raw(raw: &RawChannelInfo) -> Self {
+        ChannelInfo {
+            number: frequency_to_channel(raw.frequency),
+            frequency: raw.frequency,
+            max_power_dbm: (raw.max_power_mbm > 0).then(|| raw.max_power_mbm as f64 / 100.0),
+            dfs: raw.dfs != 0,
+            no_ir: raw.no_ir != 0,
+        }
+    }
+
+    /// Whether a hotspot can start here without radar detection.
+    pub fn hotspot_usable(&self) -> bool {
+        !self.dfs && !self.no_ir
+    }
+}
+
+/// Result of [`WiFi::recommend_channel`](crate::WiFi::recommend_channel).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChannelRecommendation {
+    pub channel: i32,
+    pub band: Band,
+    /// Congestion score of the channel; lower is better.
+    pub score: f64,
+}
+
+// Weight of measured airtime utilization relative to one strong neighbor
+const SURVEY_WEIGHT: f64 = 3.0;
+
+/// Interference a network on `other` causes on `channel`.
+///
+/// 2.4 GHz channels are 5 MHz apart but 20 MHz wide, so networks up to four
+/// channels away still overlap, with decreasing impact.
+fn overlap(band: Band, channel: i32, other: i32) -> f64 {
+    let distance = (channel - other).abs();
+    match band {
+        Band::Ghz2 if distance < 5 => (5 - distance) as f64 / 5.0,
+        Band::Ghz5 | Band::Ghz6 if distance == 0 => 1.0,
+        _ => 0.0,
+    }
+}
+
+/// Congestion of one channel, from [`ChannelAnalyzer::channels`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChannelStats {
+    pub channel: i32,
+    pub band: Band,
+    /// Access points whose primary channel this is.
+    pub access_points: usize,
+    /// Combined received power of those access points in dBm, `None`
+    /// without any.
+    pub cumulative_signal_dbm: Option<f64>,
+    /// Access points on nearby channels whose transmissions reach into this
+    /// one. Only 2.4 GHz channels overlap.
+    pub overlapping: usize,
+    /// Fraction of airtime the medium was busy, where the driver reports it.
+    pub utilization: Option<f64>,
+    /// Congestion score; lower is better.
+    pub score: f64,
+}
+
+/// Per-channel statistics aggregated from scan results and, where the
+/// driver provides it, survey data.
+///
+/// Several scans can be added to catch access points a single scan missed;
+/// each BSSID counts once, with the strongest signal seen.
+///
+/// ```no_run
+/// use wifi_rs::{Band, WiFi};
+///
+/// let wifi = WiFi::new();
+/// let analyzer = wifi.analyze_channels();
+/// for stats in analyzer.channels(Band::Ghz2) {
+///     println!("channel {}: {} access points, score {:.2}", stats.channel, stats.access_points, stats.score);
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ChannelAnalyzer {
+    networks: HashMap<String, NetworkInfo>,
+    surveys: Vec<ChannelSurvey>,
+}
+
+impl ChannelAnalyzer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the results of a scan.
+    pub fn add_scan(&mut self, networks: &[NetworkInfo]) -> &mut Self {
+        for network in networks {
+            match self.networks.get(key(network)) {
+                Some(kept) if kept.signal_strength >= network.signal_strength => {}
+                _ => {
+                    self.networks.insert(key(network).to_string(), network.clone());
+                }
+            }
+        }
+        self
+    }
+
+    /// Use measured airtime utilization, replacing earlier survey data.
+    pub fn set_surveys(&mut self, surveys: &[ChannelSurvey]) -> &mut Self {
+        self.surveys = surveys.to_vec();
+        self
+    }
+
+    /// Statistics of the hotspot channels of `band` and of every other
+    /// channel of it with access points, ordered by channel number.
+    pub fn channels(&self, band: Band) -> Vec<ChannelStats> {
+        let mut numbers: Vec<i32> = band.hotspot_channels().to_vec();
+        numbers.extend(self.in_band(band).map(|n| n.channel).filter(|&channel| channel > 0));
+        numbers.sort_unstable();
+        numbers.dedup();
+        numbers.into_iter().map(|channel| self.stats(band, channel)).collect()
+    }
+
+    /// The least congested hotspot channel of `band`, `None` if it has no
+    /// candidate channels. Unlike
+    /// [`WiFi::recommend_channel`](crate::WiFi::recommend_channel) this does
+    /// not check the regulatory rules.
+    pub fn recommend(&self, band: Band) -> Option<ChannelRecommendation> {
+        self.recommend_where(band, |_| true)
+    }
+
+    pub(crate) fn recommend_where(&self, band: Band, usable: impl Fn(i32) -> bool) -> Option<ChannelRecommendation> {
+        band.hotspot_channels()
+            .iter()
+            .filter(|&&channel| usable(channel))
+            .map(|&channel| {
+                let stats = self.stats(band, channel);
+                ChannelRecommendation {
+                    channel,
+                    band,
+                    score: stats.score,
+                }
+            })
+            .min_by(|a, b| a.score.total_cmp(&b.score))
+    }
+
+    fn in_band(&self, band: Band) -> impl Iterator<Item = &NetworkInfo> {
+        self.networks.values().filter(move |n| Band::from_frequency(n.frequency) == Some(band))
+    }
+
+    /// Each visible network adds to the score of the channels it overlaps,
+    /// weighted by its signal strength; measured busy time adds on top where
+    /// survey data is available.
+    fn stats(&self, band: Band, channel: i32) -> ChannelStats {
+        let mut access_points = 0;
+        let mut power_mw = 0.0;
+        let mut overlapping = 0;
+        let mut neighbors = 0.0;
+        for network in self.in_band(band) {
+            let weight = overlap(band, channel, network.channel);
+            if weight == 0.0 {
+                continue;
+            }
+            if network.channel == channel {
+                access_points += 1;
+                power_mw += 10f64.powf(network.signal_strength as f64 / 10.0);
+            } else {
+                overlapping += 1;
+            }
+            // -100 dBm contributes nothing, -40 dBm and above counts fully
+            let strength = ((network.signal_strength + 100) as f64 / 60.0).clamp(0.0, 1.0);
+            neighbors += strength * weight;
+        }
+        let frequency = channel_to_frequency(band, channel);
+        let utilization = self
+            .surveys
+            .iter()
+            .find(|s| Some(s.frequency) == frequency)
+            .and_then(ChannelSurvey::utilization);
+        ChannelStats {
+            channel,
+            band,
+            access_points,
+            cumulative_signal_dbm: (access_points > 0).then(|| 10.0 * power_mw.log10()),
+            overlapping,
+            utilization,
+            score: neighbors + utilization.unwrap_or(0.0) * SURVEY_WEIGHT,
+        }
+    }
+}
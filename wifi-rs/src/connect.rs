@@ -0,0 +1,316 @@
+//! Options controlling how a connection is established, and its outcome.
+
+use std::ffi::{CStr, CString};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::time::Duration;
+
+use crate::band::BandPolicy;
+use crate::eap::EapConfig;
+use crate::secret::SecretString;
+
+/// Settings for [`WiFi::connect_with`](crate::WiFi::connect_with).
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConnectOptions {
+    pub(crate) password: Option<SecretString>,
+    pub(crate) eap: Option<EapConfig>,
+    pub(crate) ephemeral: bool,
+    pub(crate) verify_network: bool,
+    pub(crate) band_policy: Option<BandPolicy>,
+    pub(crate) hidden: bool,
+    pub(crate) bssid: Option<String>,
+    pub(crate) timeout: Option<Duration>,
+    pub(crate) ipv4: Option<StaticAddress<Ipv4Addr>>,
+    pub(crate) ipv6: Option<StaticAddress<Ipv6Addr>>,
+    pub(crate) dns_servers: Vec<IpAddr>,
+}
+
+/// A manually assigned address with its subnet and default route.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) struct StaticAddress<A> {
+    address: A,
+    prefix_length: u8,
+    gateway: Option<A>,
+}
+
+#[repr(C)]
+pub(crate) struct RawConnectOptions {
+    ephemeral: libc::c_int,
+    frequencies: *const i32,
+    frequency_count: i32,
+    hidden: i32,
+    bssid: *const libc::c_char,
+    timeout_seconds: i32,
+    ipv4_address: *const libc::c_char,
+    ipv4_gateway: *const libc::c_char,
+    ipv6_address: *const libc::c_char,
+    ipv6_gateway: *const libc::c_char,
+    dns_servers: *const libc::c_char,
+}
+
+/// Owned C strings backing the static addressing of a `RawConnectOptions`;
+/// must outlive the FFI call.
+#[derive(Default)]
+pub(crate) struct StaticIpStrings {
+    ipv4_address: Option<CString>,
+    ipv4_gateway: Option<CString>,
+    ipv6_address: Option<CString>,
+    ipv6_gateway: Option<CString>,
+    dns_servers: Option<CString>,
+}
+
+fn c_string(text: String) -> Option<CString> {
+    CString::new(text).ok()
+}
+
+fn ptr(s: &Option<CString>) -> *const libc::c_char {
+    s.as_ref().map_or(std::ptr::null(), |s| s.as_ptr())
+}
+
+/// Whether `bssid` is a MAC address in `aa:bb:cc:dd:ee:ff` form.
+pub(crate) fn is_bssid(bssid: &str) -> bool {
+    bssid.len() == 17
+        && bssid.split(':').count() == 6
+        && bssid.split(':').all(|octet| octet.len() == 2 && octet.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+impl ConnectOptions {
+    /// Options for an open network.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Options for a personal (PSK) network secured with `password`.
+    pub fn password(password: impl Into<SecretString>) -> Self {
+        ConnectOptions {
+            password: Some(password.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Options for a WPA2/WPA3-Enterprise network using 802.1X credentials.
+    ///
+    /// Accepts either a bare [`Eap`](crate::Eap) (default server validation)
+    /// or an [`EapConfig`] with pinning/name matching. Only supported on
+    /// Linux.
+    pub fn enterprise(eap: impl Into<EapConfig>) -> Self {
+        ConnectOptions {
+            eap: Some(eap.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Join without storing a profile in the operating system.
+    ///
+    /// The network is forgotten on disconnect and when the `WiFi` instance is
+    /// dropped, so no credentials are left behind (e.g. after joining a
+    /// customer's network during a service visit). Platforms that cannot
+    /// guarantee this fail the connection instead of persisting a profile.
+    /// Only applies to personal and open networks.
+    pub fn ephemeral(mut self, ephemeral: bool) -> Self {
+        self.ephemeral = ephemeral;
+        self
+    }
+
+    /// After connecting, check that DNS resolves and the gateway answers.
+    ///
+    /// Without this only a missing DHCP lease is detected. The checks add up
+    /// to a few seconds to a successful connect.
+    pub fn verify_network(mut self, verify: bool) -> Self {
+        self.verify_network = verify;
+        self
+    }
+
+    /// Pick the band with `policy` when the network is on several bands.
+    ///
+    /// The connection takes a scan first and is then restricted to the
+    /// access points of the chosen band. Only applies to personal and open
+    /// networks; platforms that cannot restrict frequencies treat it as a
+    /// hint.
+    pub fn band_policy(mut self, policy: BandPolicy) -> Self {
+        self.band_policy = Some(policy);
+        self
+    }
+
+    /// The network does not broadcast its SSID.
+    ///
+    /// Hidden networks never appear in a passive scan, so the platform has to
+    /// probe for the SSID by name. Only applies to personal and open
+    /// networks.
+    pub fn hidden(mut self, hidden: bool) -> Self {
+        self.hidden = hidden;
+        self
+    }
+
+    /// Join only the access point with this BSSID, e.g. `"aa:bb:cc:dd:ee:ff"`.
+    ///
+    /// Only applies to personal and open networks; platforms that cannot
+    /// select an access point fail the connection with
+    /// [`WifiError::Unsupported`](crate::WifiError::Unsupported).
+    pub fn bssid(mut self, bssid: &str) -> Self {
+        self.bssid = Some(bssid.to_ascii_lowercase());
+        self
+    }
+
+    /// Give up if the network has not been joined within `timeout`, instead
+    /// of after the platform's default. Platforms that connect
+    /// asynchronously ignore it.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Assign `address` with a `prefix_length` bit subnet mask instead of
+    /// asking DHCP for one, with `gateway` as the default route.
+    ///
+    /// Only applies to personal and open networks; platforms that cannot
+    /// configure addressing fail the connection with
+    /// [`WifiError::Unsupported`](crate::WifiError::Unsupported). The
+    /// addressing stays on the adapter after disconnecting.
+    ///
+    /// ```no_run
+    /// use std::net::Ipv4Addr;
+    /// use wifi_rs::{ConnectOptions, WiFi};
+    ///
+    /// let options = ConnectOptions::password("secret")
+    ///     .static_ipv4(Ipv4Addr::new(192, 168, 1, 20), 24, Some(Ipv4Addr::new(192, 168, 1, 1)))
+    ///     .dns_servers(&[Ipv4Addr::new(192, 168, 1, 1).into()]);
+    /// WiFi::new().connect_with("Lab", &options)?;
+    /// # Ok::<(), wifi_rs::WifiError>(())
+    /// ```
+    pub fn static_ipv4(mut self, address: Ipv4Addr, prefix_length: u8, gateway: Option<Ipv4Addr>) -> Self {
+        self.ipv4 = Some(StaticAddress { address, prefix_length, gateway });
+        self
+    }
+
+    /// Add `address` with a `prefix_length` bit prefix next to the
+    /// autoconfigured IPv6 addresses, with `gateway` as the default route.
+    ///
+    /// Same restrictions as [`static_ipv4`](Self::static_ipv4).
+    pub fn static_ipv6(mut self, address: Ipv6Addr, prefix_length: u8, gateway: Option<Ipv6Addr>) -> Self {
+        self.ipv6 = Some(StaticAddress { address, prefix_length, gateway });
+        self
+    }
+
+    /// Use these DNS servers instead of the ones DHCP hands out.
+    ///
+    /// Same restrictions as [`static_ipv4`](Self::static_ipv4).
+    pub fn dns_servers(mut self, servers: &[IpAddr]) -> Self {
+        self.dns_servers = servers.to_vec();
+        self
+    }
+
+    /// Whether manual addressing was requested.
+    pub(crate) fn has_static_ip(&self) -> bool {
+        self.ipv4.is_some() || self.ipv6.is_some() || !self.dns_servers.is_empty()
+    }
+
+    /// Whether the static prefix lengths, if any, fit their address family.
+    pub(crate) fn has_valid_static_ip(&self) -> bool {
+        self.ipv4.is_none_or(|ipv4| ipv4.prefix_length <= 32) && self.ipv6.is_none_or(|ipv6| ipv6.prefix_length <= 128)
+    }
+
+    pub(crate) fn static_ip_strings(&self) -> StaticIpStrings {
+        let servers: Vec<String> = self.dns_servers.iter().map(IpAddr::to_string).collect();
+        StaticIpStrings {
+            ipv4_address: self.ipv4.and_then(|ipv4| c_string(format!("{}/{}", ipv4.address, ipv4.prefix_length))),
+            ipv4_gateway: self.ipv4.and_then(|ipv4| ipv4.gateway).and_then(|gateway| c_string(gateway.to_string())),
+            ipv6_address: self.ipv6.and_then(|ipv6| c_string(format!("{}/{}", ipv6.address, ipv6.prefix_length))),
+            ipv6_gateway: self.ipv6.and_then(|ipv6| ipv6.gateway).and_then(|gateway| c_string(gateway.to_string())),
+            dns_servers: (!servers.is_empty()).then(|| servers.join(",")).and_then(c_string),
+        }
+    }
+
+    /// Whether the BSSID, if any, is a well-formed MAC address.
+    pub(crate) fn has_valid_bssid(&self) -> bool {
+        self.bssid.as_deref().is_none_or(is_bssid)
+    }
+
+    /// `frequencies`, `bssid` and `addressing` must outlive the returned
+    /// struct.
+    pub(crate) fn raw(&self, frequencies: &[i32], bssid: Option<&CStr>, addressing: &StaticIpStrings) -> RawConnectOptions {
+        RawConnectOptions {
+            ephemeral: self.ephemeral as libc::c_int,
+            frequencies: if frequencies.is_empty() { std::ptr::null() } else { frequencies.as_ptr() },
+            frequency_count: frequencies.len() as i32,
+            hidden: self.hidden as i32,
+            bssid: bssid.map_or(std::ptr::null(), CStr::as_ptr),
+            // Round up so a sub-second timeout does not mean the platform default
+            timeout_seconds: self.timeout.map_or(0, |timeout| timeout.as_secs_f64().ceil().clamp(1.0, i32::MAX as f64) as i32),
+            ipv4_address: ptr(&addressing.ipv4_address),
+            ipv4_gateway: ptr(&addressing.ipv4_gateway),
+            ipv6_address: ptr(&addressing.ipv6_address),
+            ipv6_gateway: ptr(&addressing.ipv6_gateway),
+            dns_servers: ptr(&addressing.dns_servers),
+        }
+    }
+}
+
+/// Outcome of [`WiFi::connect_and_wait`](crate::WiFi::connect_and_wait).
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConnectionResult {
+    /// Joined the network and obtained an IP address.
+    Connected {
+        /// Access point associated with, where the platform reports it.
+        bssid: Option<String>,
+        /// Time from the start of the attempt until the address was assigned.
+        elapsed: Duration,
+    },
+    /// The network rejected the password.
+    AuthFailed,
+    /// Associated and authenticated, but no DHCP lease was obtained.
+    DhcpFailed,
+    /// No access point with the SSID is in range.
+    NetworkNotFound,
+    /// The attempt did not complete in time for another reason.
+    Timeout,
+    /// The platform reported another error.
+    Failed(crate::WifiError),
+}
+
+impl ConnectionResult {
+    pub fn is_connected(&self) -> bool {
+        matches!(self, ConnectionResult::Connected { .. })
+    }
+}
+
+/// How connections are made on Linux, selected with
+/// [`WiFi::set_linux_backend`](crate::WiFi::set_linux_backend).
+///
+/// Scans, link information and events always go through nl80211; the
+/// backend only decides who associates and authenticates.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LinuxBackend {
+    /// NetworkManager when it manages the interface, so the library does
+    /// not fight it over the adapter, and wpa_supplicant otherwise.
+    #[default]
+    Auto,
+    /// The driver associates on its own through nl80211. Without a
+    /// supplicant there is no key exchange, so only open networks can be
+    /// joined.
+    Nl80211,
+    /// NetworkManager through `nmcli`, which saves every network joined as
+    /// a connection profile. Ephemeral connections, band policies and
+    /// static addressing are not supported.
+    NetworkManager,
+    /// A wpa_supplicant started by the library and controlled with
+    /// `wpa_cli`. Supports every connect option.
+    WpaCli,
+}
+
+impl LinuxBackend {
+    pub(crate) fn raw(self) -> libc::c_int {
+        self as libc::c_int
+    }
+
+    pub(crate) fn from_raw(backend: libc::c_int) -> Option<Self> {
+        match backend {
+            1 => Some(LinuxBackend::Nl80211),
+            2 => Some(LinuxBackend::NetworkManager),
+            3 => Some(LinuxBackend::WpaCli),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,175 @@
+//! Network credentials in the `WIFI:` QR code format.
+//!
+//! The format is what phone cameras recognize when scanning a network's QR
+//! code: `WIFI:T:WPA;S:Office;P:secret;;`. Fields are separated by `;`, and
+//! `\`, `;`, `,`, `:` and `"` inside values are escaped with a backslash.
+
+use crate::secret::SecretString;
+use crate::{ConnectOptions, SecurityType, WifiError};
+
+/// An SSID and the secret needed to join it, as encoded in a WiFi QR code.
+///
+/// ```
+/// use wifi_rs::{NetworkCredentials, SecurityType};
+///
+/// let credentials = NetworkCredentials::from_wifi_qr_string(r"WIFI:T:WPA;S:Office;P:s3cret\;;;")?;
+/// assert_eq!(credentials.ssid, "Office");
+/// assert_eq!(credentials.security, SecurityType::Wpa2);
+/// assert_eq!(credentials.password.as_ref().map(|p| p.expose()), Some("s3cret;"));
+/// assert_eq!(credentials.to_wifi_qr_string(), r"WIFI:T:WPA;S:Office;P:s3cret\;;;");
+/// # Ok::<(), wifi_rs::WifiError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetworkCredentials {
+    pub ssid: String,
+    /// [`SecurityType::None`] for an open network, [`SecurityType::Wep`],
+    /// [`SecurityType::Wpa2`] for WPA/WPA2/WPA3 personal or
+    /// [`SecurityType::Wpa3`] for a network that disabled the WPA2
+    /// transition.
+    pub security: SecurityType,
+    pub password: Option<SecretString>,
+    /// The network does not broadcast its SSID.
+    pub hidden: bool,
+}
+
+impl NetworkCredentials {
+    /// Credentials for a network secured with `password`.
+    pub fn new(ssid: &str, password: Option<&str>) -> Self {
+        NetworkCredentials {
+            ssid: ssid.to_string(),
+            security: if password.is_some() { SecurityType::Wpa2 } else { SecurityType::None },
+            password: password.map(SecretString::from),
+            hidden: false,
+        }
+    }
+
+    /// Parse a `WIFI:` QR code payload.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WifiError::InvalidArgument`] if `qr` is not a `WIFI:`
+    /// payload, has no SSID, or uses an authentication type other than
+    /// `WPA`, `SAE`, `WEP` or `nopass`, e.g. an enterprise network.
+    pub fn from_wifi_qr_string(qr: &str) -> Result<Self, WifiError> {
+        let body = qr.trim().strip_prefix("WIFI:").ok_or(WifiError::InvalidArgument)?;
+        let mut ssid = None;
+        let mut auth = None;
+        let mut password = None;
+        let mut hidden = false;
+        let mut transition_disable = false;
+        for field in split_fields(body) {
+            let Some((key, value)) = field.split_once(':') else {
+                if field.is_empty() {
+                    continue;
+                }
+                return Err(WifiError::InvalidArgument);
+            };
+            let value = unescape(value);
+            match key {
+                "S" => ssid = Some(value),
+                "T" => auth = Some(value),
+                "P" => password = Some(SecretString::new(value)),
+                "H" => hidden = value.eq_ignore_ascii_case("true"),
+                // Bit 0 of the transition disable field turns off WPA2 fallback
+                "R" => transition_disable = u32::from_str_radix(&value, 16).is_ok_and(|bits| bits & 1 != 0),
+                _ => {}
+            }
+        }
+
+        let ssid = ssid.filter(|ssid| !ssid.is_empty()).ok_or(WifiError::InvalidArgument)?;
+        let password = password.filter(|password| !password.is_empty());
+        let security = match auth.as_deref() {
+            None | Some("") | Some("nopass") => SecurityType::None,
+            Some("WEP") => SecurityType::Wep,
+            Some("WPA") if transition_disable => SecurityType::Wpa3,
+            Some("WPA") => SecurityType::Wpa2,
+            Some("SAE") => SecurityType::Wpa3,
+            Some(_) => return Err(WifiError::InvalidArgument),
+        };
+        Ok(NetworkCredentials {
+            ssid,
+            password: if security == SecurityType::None { None } else { password },
+            security,
+            hidden,
+        })
+    }
+
+    /// Encode as a `WIFI:` QR code payload, e.g. to render with a QR code
+    /// library for guests to scan.
+    pub fn to_wifi_qr_string(&self) -> String {
+        let secured = self.password.is_some();
+        let (auth, transition_disable) = match self.security {
+            SecurityType::None => ("nopass", false),
+            SecurityType::Wep => ("WEP", false),
+            SecurityType::Wpa3 => ("WPA", true),
+            SecurityType::Wpa | SecurityType::Wpa2 => ("WPA", false),
+            SecurityType::Unknown if secured => ("WPA", false),
+            SecurityType::Unknown => ("nopass", false),
+        };
+        let mut qr = format!("WIFI:T:{};S:{};", auth, escape(&self.ssid));
+        if let Some(password) = self.password.as_ref().filter(|_| auth != "nopass") {
+            qr.push_str(&format!("P:{};", escape(password.expose())));
+        }
+        if self.hidden {
+            qr.push_str("H:true;");
+        }
+        if transition_disable {
+            qr.push_str("R:1;");
+        }
+        qr.push(';');
+        qr
+    }
+
+    /// Options for [`WiFi::connect_with`](crate::WiFi::connect_with) that
+    /// join the network.
+    pub fn connect_options(&self) -> ConnectOptions {
+        let options = match &self.password {
+            Some(password) => ConnectOptions::password(password.clone()),
+            None => ConnectOptions::new(),
+        };
+        options.hidden(self.hidden)
+    }
+}
+
+/// Split at semicolons that are not escaped.
+fn split_fields(body: &str) -> Vec<&str> {
+    let mut fields = Vec::new();
+    let mut start = 0;
+    let mut escaped = false;
+    for (index, c) in body.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            ';' => {
+                fields.push(&body[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    fields.push(&body[start..]);
+    fields
+}
+
+fn unescape(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => unescaped.extend(chars.next()),
+            c => unescaped.push(c),
+        }
+    }
+    unescaped
+}
+
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '\\' | ';' | ',' | ':' | '"') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
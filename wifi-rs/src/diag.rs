@@ -0,0 +1,387 @@
+//! One-shot connection diagnostics.
+//!
+//! Walks the path from the adapter to the internet and reports which stage
+//! first broke, so "WiFi doesn't work" becomes "DHCP did not hand out an
+//! address" or "DNS servers are unreachable".
+
+use std::ffi::CStr;
+use std::fmt;
+use std::io::{ErrorKind, Read, Write};
+use std::net::{IpAddr, SocketAddr, TcpStream, ToSocketAddrs};
+use std::time::{Duration, Instant};
+
+use crate::NetworkInfo;
+
+/// Host used for the DNS and internet checks.
+const PROBE_HOST: &str = "connectivitycheck.gstatic.com";
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[repr(C)]
+pub(crate) struct RawLinkDiagnostics {
+    adapter_present: i32,
+    radio_enabled: i32,
+    associated: i32,
+    authenticated: i32,
+    ssid: [libc::c_char; 33],
+    bssid: [libc::c_char; 18],
+    ip_address: [libc::c_char; 46],
+    gateway: [libc::c_char; 46],
+    dns_servers: [[libc::c_char; 46]; 4],
+    dns_server_count: i32,
+}
+
+impl RawLinkDiagnostics {
+    pub(crate) fn zeroed() -> Self {
+        // SAFETY: the struct is plain integers and character arrays.
+        unsafe { std::mem::zeroed() }
+    }
+}
+
+pub(crate) fn text(buffer: &[libc::c_char]) -> String {
+    // SAFETY: the C API always NUL-terminates the fixed-size buffers.
+    unsafe { CStr::from_ptr(buffer.as_ptr()) }.to_string_lossy().into_owned()
+}
+
+/// Link state reported by the native library.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct LinkState {
+    pub adapter_present: bool,
+    pub radio_enabled: bool,
+    pub associated: bool,
+    pub authenticated: bool,
+    pub ssid: String,
+    pub bssid: String,
+    pub ip_address: Option<IpAddr>,
+    pub gateway: Option<IpAddr>,
+    pub dns_servers: Vec<IpAddr>,
+}
+
+impl LinkState {
+    pub(crate) fn from_raw(raw: &RawLinkDiagnostics) -> Self {
+        let count = raw.dns_server_count.clamp(0, 4) as usize;
+        LinkState {
+            adapter_present: raw.adapter_present != 0,
+            radio_enabled: raw.radio_enabled != 0,
+            associated: raw.associated != 0,
+            authenticated: raw.authenticated != 0,
+            ssid: text(&raw.ssid),
+            bssid: text(&raw.bssid),
+            ip_address: text(&raw.ip_address).parse().ok(),
+            gateway: text(&raw.gateway).parse().ok(),
+            dns_servers: raw.dns_servers[..count]
+                .iter()
+                .filter_map(|server| text(server).parse().ok())
+                .collect(),
+        }
+    }
+}
+
+/// A step on the path from the adapter to the internet, in check order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Stage {
+    /// A WiFi adapter is present.
+    Adapter,
+    /// The radio is not disabled by rfkill or airplane mode.
+    Radio,
+    /// A scan returns at least one network.
+    Scan,
+    /// The adapter is associated with an access point.
+    Association,
+    /// The key handshake or 802.1X authentication completed.
+    Authentication,
+    /// An IP address was obtained.
+    Dhcp,
+    /// Names resolve through the configured DNS servers.
+    Dns,
+    /// The default gateway answers.
+    Gateway,
+    /// An HTTP probe reaches the internet without being intercepted.
+    Internet,
+}
+
+impl Stage {
+    /// All stages in the order they are checked.
+    pub const ALL: [Stage; 9] = [
+        Stage::Adapter,
+        Stage::Radio,
+        Stage::Scan,
+        Stage::Association,
+        Stage::Authentication,
+        Stage::Dhcp,
+        Stage::Dns,
+        Stage::Gateway,
+        Stage::Internet,
+    ];
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Stage::Adapter => "adapter",
+            Stage::Radio => "radio",
+            Stage::Scan => "scan",
+            Stage::Association => "association",
+            Stage::Authentication => "authentication",
+            Stage::Dhcp => "DHCP",
+            Stage::Dns => "DNS",
+            Stage::Gateway => "gateway",
+            Stage::Internet => "internet",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Why a network is unusable although the adapter associated with it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NetworkFailure {
+    /// No IP address was obtained.
+    DhcpTimeout,
+    /// The configured DNS servers do not resolve names.
+    DnsUnresolvable,
+    /// The default gateway is missing or does not respond.
+    GatewayUnreachable,
+}
+
+impl NetworkFailure {
+    /// The diagnostic stage the failure corresponds to.
+    pub fn stage(&self) -> Stage {
+        match self {
+            NetworkFailure::DhcpTimeout => Stage::Dhcp,
+            NetworkFailure::DnsUnresolvable => Stage::Dns,
+            NetworkFailure::GatewayUnreachable => Stage::Gateway,
+        }
+    }
+}
+
+impl fmt::Display for NetworkFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NetworkFailure::DhcpTimeout => write!(f, "DHCP timed out"),
+            NetworkFailure::DnsUnresolvable => write!(f, "DNS is unresolvable"),
+            NetworkFailure::GatewayUnreachable => write!(f, "gateway is unreachable"),
+        }
+    }
+}
+
+/// Attribute an unusable connection to DHCP, DNS or the gateway.
+///
+/// Returns `None` when the adapter is not authenticated, since the failure
+/// then lies below IP. DNS and the gateway are only probed when `probe` is
+/// set, as those checks go out on the network.
+pub(crate) fn classify(link: &LinkState, probe: bool) -> Option<NetworkFailure> {
+    if !link.authenticated {
+        return None;
+    }
+    if link.ip_address.is_none() {
+        return Some(NetworkFailure::DhcpTimeout);
+    }
+    if !probe {
+        return None;
+    }
+    if check_dns(&link.dns_servers).is_err() {
+        return Some(NetworkFailure::DnsUnresolvable);
+    }
+    if check_gateway(link.gateway).is_err() {
+        return Some(NetworkFailure::GatewayUnreachable);
+    }
+    None
+}
+
+/// Outcome of a single stage.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StageStatus {
+    /// The check succeeded; the string describes what was found.
+    Passed(String),
+    /// The check failed; the string describes why.
+    Failed(String),
+    /// The check was not run because an earlier stage failed.
+    Skipped,
+}
+
+/// Result of checking one stage.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StageResult {
+    pub stage: Stage,
+    pub status: StageStatus,
+    /// Time the check took.
+    pub duration: Duration,
+}
+
+/// Report produced by [`WiFi::diagnose`](crate::WiFi::diagnose).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiagnosticReport {
+    /// One entry per [`Stage`], in check order.
+    pub stages: Vec<StageResult>,
+}
+
+impl DiagnosticReport {
+    /// The first stage that failed, which is where troubleshooting should start.
+    pub fn first_failure(&self) -> Option<&StageResult> {
+        self.stages.iter().find(|result| matches!(result.status, StageStatus::Failed(_)))
+    }
+
+    /// Whether every stage passed.
+    pub fn is_healthy(&self) -> bool {
+        self.first_failure().is_none()
+    }
+
+    /// The result for `stage`.
+    pub fn stage(&self, stage: Stage) -> Option<&StageResult> {
+        self.stages.iter().find(|result| result.stage == stage)
+    }
+}
+
+impl fmt::Display for DiagnosticReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for result in &self.stages {
+            match &result.status {
+                StageStatus::Passed(detail) => writeln!(f, "[ OK ] {}: {}", result.stage, detail)?,
+                StageStatus::Failed(reason) => writeln!(f, "[FAIL] {}: {}", result.stage, reason)?,
+                StageStatus::Skipped => writeln!(f, "[SKIP] {}", result.stage)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Run the stage checks in order, skipping everything after the first failure.
+pub(crate) fn run(link: Option<LinkState>, scan: impl FnOnce() -> Vec<NetworkInfo>) -> DiagnosticReport {
+    let mut stages = Vec::with_capacity(Stage::ALL.len());
+    let mut failed = false;
+    let mut scan = Some(scan);
+
+    for stage in Stage::ALL {
+        if failed {
+            stages.push(StageResult { stage, status: StageStatus::Skipped, duration: Duration::ZERO });
+            continue;
+        }
+
+        let started = Instant::now();
+        let outcome = match &link {
+            None => Err("link state is not available on this platform".to_string()),
+            Some(link) => match stage {
+                Stage::Scan => check_scan(scan.take().map(|f| f()).unwrap_or_default()),
+                _ => check(stage, link),
+            },
+        };
+        let status = match outcome {
+            Ok(detail) => StageStatus::Passed(detail),
+            Err(reason) => {
+                failed = true;
+                StageStatus::Failed(reason)
+            }
+        };
+        stages.push(StageResult { stage, status, duration: started.elapsed() });
+    }
+
+    DiagnosticReport { stages }
+}
+
+fn check(stage: Stage, link: &LinkState) -> Result<String, String> {
+    match stage {
+        Stage::Adapter if link.adapter_present => Ok("WiFi adapter found".to_string()),
+        Stage::Adapter => Err("no WiFi adapter found".to_string()),
+        Stage::Radio if link.radio_enabled => Ok("radio enabled".to_string()),
+        Stage::Radio => Err("radio is blocked (rfkill or airplane mode)".to_string()),
+        Stage::Association if link.associated => Ok(format!("associated with {} ({})", link.ssid, link.bssid)),
+        Stage::Association => Err("not associated with an access point".to_string()),
+        Stage::Authentication if link.authenticated => Ok("authentication completed".to_string()),
+        Stage::Authentication => Err("association did not complete authentication, check the credentials".to_string()),
+        Stage::Dhcp => match link.ip_address {
+            Some(address) => Ok(format!("address {}", address)),
+            None => Err("no IP address assigned".to_string()),
+        },
+        Stage::Dns => check_dns(&link.dns_servers),
+        Stage::Gateway => check_gateway(link.gateway),
+        Stage::Internet => check_internet(),
+        Stage::Scan => unreachable!("scan is checked separately"),
+    }
+}
+
+fn check_scan(networks: Vec<NetworkInfo>) -> Result<String, String> {
+    if networks.is_empty() {
+        Err("scan found no networks".to_string())
+    } else {
+        Ok(format!("{} networks visible", networks.len()))
+    }
+}
+
+fn check_dns(servers: &[IpAddr]) -> Result<String, String> {
+    if servers.is_empty() {
+        return Err("no DNS servers configured".to_string());
+    }
+    match (PROBE_HOST, 80).to_socket_addrs().map(|mut addresses| addresses.next()) {
+        Ok(Some(address)) => Ok(format!("resolved {} to {}", PROBE_HOST, address.ip())),
+        Ok(None) => Err(format!("{} resolved to no addresses", PROBE_HOST)),
+        Err(e) => Err(format!("cannot resolve {}: {}", PROBE_HOST, e)),
+    }
+}
+
+fn check_gateway(gateway: Option<IpAddr>) -> Result<String, String> {
+    let gateway = gateway.ok_or_else(|| "no default gateway".to_string())?;
+
+    // A refused connection still proves the gateway is reachable.
+    for port in [53, 80] {
+        match TcpStream::connect_timeout(&SocketAddr::new(gateway, port), PROBE_TIMEOUT) {
+            Ok(_) => return Ok(format!("gateway {} reachable", gateway)),
+            Err(e) if e.kind() == ErrorKind::ConnectionRefused => {
+                return Ok(format!("gateway {} reachable", gateway));
+            }
+            Err(_) => continue,
+        }
+    }
+    Err(format!("gateway {} does not respond", gateway))
+}
+
+fn check_internet() -> Result<String, String> {
+    match probe_internet()?.as_str() {
+        "204" => Ok("internet reachable".to_string()),
+        "" => Err("empty response from connectivity probe".to_string()),
+        other => Err(format!("connectivity probe returned HTTP {}, likely a captive portal", other)),
+    }
+}
+
+/// Whether the internet is reachable past the local network.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Connectivity {
+    Internet,
+    /// The probe was answered by something other than the probe host,
+    /// usually a captive portal's login page or redirect.
+    CaptivePortal,
+    /// The probe host could not be resolved or reached.
+    Limited,
+}
+
+/// Probe the internet the way [`Stage::Internet`] does.
+pub(crate) fn connectivity() -> Connectivity {
+    match probe_internet().as_deref() {
+        Ok("204") => Connectivity::Internet,
+        Ok("") | Err(_) => Connectivity::Limited,
+        Ok(_) => Connectivity::CaptivePortal,
+    }
+}
+
+/// Fetch the probe URL and return the HTTP status code of the response.
+fn probe_internet() -> Result<String, String> {
+    let address = (PROBE_HOST, 80)
+        .to_socket_addrs()
+        .ok()
+        .and_then(|mut addresses| addresses.next())
+        .ok_or_else(|| format!("cannot resolve {}", PROBE_HOST))?;
+
+    let mut stream = TcpStream::connect_timeout(&address, PROBE_TIMEOUT)
+        .map_err(|e| format!("cannot connect to {}: {}", PROBE_HOST, e))?;
+    stream.set_read_timeout(Some(PROBE_TIMEOUT)).ok();
+    stream.set_write_timeout(Some(PROBE_TIMEOUT)).ok();
+
+    let request = format!("GET /generate_204 HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n\r\n", PROBE_HOST);
+    stream.write_all(request.as_bytes()).map_err(|e| format!("probe request failed: {}", e))?;
+
+    let mut response = Vec::new();
+    stream.take(1024).read_to_end(&mut response).map_err(|e| format!("probe response failed: {}", e))?;
+    let status_line = String::from_utf8_lossy(&response);
+    let status = status_line.split_whitespace().nth(1).unwrap_or("");
+
+    Ok(status.to_string())
+}
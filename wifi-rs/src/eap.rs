@@ -0,0 +1,403 @@
+//! EAP credentials for WPA2/WPA3-Enterprise (802.1X) networks.
+
+use crate::secret::SecretString;
+use crate::WifiError;
+use std::ffi::CString;
+use std::fmt;
+use std::path::PathBuf;
+use zeroize::Zeroizing;
+
+/// EAP method and credentials used to join an enterprise network.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Eap {
+    /// PEAP with MSCHAPv2 inner authentication.
+    Peap {
+        identity: String,
+        password: SecretString,
+        /// Outer (anonymous) identity sent in the clear, e.g. `anonymous@example.edu`.
+        anonymous_identity: Option<String>,
+        /// CA certificate used to validate the RADIUS server; required
+        /// unless the server certificate is pinned.
+        ca_cert: Option<PathBuf>,
+    },
+    /// EAP-TTLS with a configurable inner method.
+    Ttls {
+        identity: String,
+        password: SecretString,
+        inner: TtlsInner,
+        /// Outer (anonymous) identity sent in the clear.
+        anonymous_identity: Option<String>,
+        /// CA certificate used to validate the RADIUS server; required
+        /// unless the server certificate is pinned.
+        ca_cert: Option<PathBuf>,
+    },
+    /// EAP-TLS with a client certificate.
+    Tls {
+        identity: String,
+        client_cert: ClientCertificate,
+        /// CA certificate used to validate the RADIUS server; required
+        /// unless the server certificate is pinned.
+        ca_cert: Option<PathBuf>,
+    },
+    /// EAP-SIM using the device's SIM card (carrier offload).
+    Sim {
+        /// Identity override; derived from the IMSI when `None`.
+        identity: Option<String>,
+        pin: Option<SecretString>,
+    },
+    /// EAP-AKA, or EAP-AKA' when `prime` is set, using the device's SIM card.
+    Aka {
+        /// Identity override; derived from the IMSI when `None`.
+        identity: Option<String>,
+        pin: Option<SecretString>,
+        prime: bool,
+    },
+}
+
+/// EAP method without credentials, used for capability probing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EapMethod {
+    Peap,
+    Tls,
+    Ttls,
+    Sim,
+    Aka,
+    AkaPrime,
+}
+
+impl EapMethod {
+    pub(crate) fn code(self) -> libc::c_int {
+        match self {
+            EapMethod::Peap => WIFI_EAP_PEAP,
+            EapMethod::Tls => WIFI_EAP_TLS,
+            EapMethod::Ttls => WIFI_EAP_TTLS,
+            EapMethod::Sim => WIFI_EAP_SIM,
+            EapMethod::Aka => WIFI_EAP_AKA,
+            EapMethod::AkaPrime => WIFI_EAP_AKA_PRIME,
+        }
+    }
+}
+
+impl Eap {
+    /// The EAP method these credentials authenticate with.
+    pub fn method(&self) -> EapMethod {
+        match self {
+            Eap::Peap { .. } => EapMethod::Peap,
+            Eap::Ttls { .. } => EapMethod::Ttls,
+            Eap::Tls { .. } => EapMethod::Tls,
+            Eap::Sim { .. } => EapMethod::Sim,
+            Eap::Aka { prime: false, .. } => EapMethod::Aka,
+            Eap::Aka { prime: true, .. } => EapMethod::AkaPrime,
+        }
+    }
+}
+
+/// Enterprise connection settings: EAP credentials plus RADIUS server validation.
+///
+/// The server certificate is validated against the CA given in the [`Eap`]
+/// variant, or against a fingerprint pinned with
+/// [`pin_server_cert_sha256`](Self::pin_server_cert_sha256); the system
+/// trust store is never used, as any public CA could then vouch for a rogue
+/// server. Name matching tightens validation further.
+///
+/// ```no_run
+/// use std::path::PathBuf;
+/// use wifi_rs::{Eap, EnterpriseConfig, WiFi};
+///
+/// let wifi = WiFi::new();
+/// let config = EnterpriseConfig::new(Eap::Peap {
+///     identity: "alice@example.edu".to_string(),
+///     password: "secret".into(),
+///     anonymous_identity: Some("anonymous@example.edu".to_string()),
+///     ca_cert: Some(PathBuf::from("/etc/ssl/certs/example-radius.pem")),
+/// })
+/// .server_domain_suffix("radius.example.edu");
+/// wifi.connect_enterprise("CorpNet", config)?;
+/// # Ok::<(), wifi_rs::WifiError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EapConfig {
+    pub(crate) eap: Eap,
+    pub(crate) server_cert_sha256: Option<String>,
+    pub(crate) domain_match: Option<String>,
+    pub(crate) domain_suffix_match: Option<String>,
+    pub(crate) validate_server: bool,
+}
+
+/// Name under which enterprise settings are passed to
+/// [`WiFi::connect_enterprise`](crate::WiFi::connect_enterprise).
+pub type EnterpriseConfig = EapConfig;
+
+impl EapConfig {
+    /// Create a configuration with default server validation.
+    pub fn new(eap: Eap) -> Self {
+        EapConfig {
+            eap,
+            server_cert_sha256: None,
+            domain_match: None,
+            domain_suffix_match: None,
+            validate_server: true,
+        }
+    }
+
+    /// Pin the RADIUS server certificate by its SHA-256 fingerprint (hex, colons allowed).
+    pub fn pin_server_cert_sha256(mut self, fingerprint: &str) -> Self {
+        let hex: String = fingerprint
+            .chars()
+            .filter(|c| *c != ':')
+            .collect::<String>()
+            .to_ascii_lowercase();
+        self.server_cert_sha256 = Some(hex);
+        self
+    }
+
+    /// Require the server certificate to be issued for exactly `name`.
+    pub fn server_name(mut self, name: &str) -> Self {
+        self.domain_match = Some(name.to_string());
+        self
+    }
+
+    /// Require the server certificate name to end with `suffix` (e.g. `radius.example.edu`).
+    pub fn server_domain_suffix(mut self, suffix: &str) -> Self {
+        self.domain_suffix_match = Some(suffix.to_string());
+        self
+    }
+
+    /// Whether the RADIUS server can be validated: a CA or pinned certificate
+    /// is given, validation is disabled, or the method has no server
+    /// certificate (EAP-SIM and EAP-AKA).
+    pub(crate) fn has_trust_anchor(&self) -> bool {
+        let ca_cert = match &self.eap {
+            Eap::Peap { ca_cert, .. } | Eap::Ttls { ca_cert, .. } | Eap::Tls { ca_cert, .. } => ca_cert,
+            Eap::Sim { .. } | Eap::Aka { .. } => return true,
+        };
+        !self.validate_server || ca_cert.is_some() || self.server_cert_sha256.is_some()
+    }
+
+    /// Disable RADIUS server certificate validation entirely.
+    ///
+    /// # Warning
+    ///
+    /// This makes the connection vulnerable to rogue access points that
+    /// impersonate the network and harvest credentials. Only use it for
+    /// testing against lab infrastructure.
+    pub fn danger_disable_server_validation(mut self) -> Self {
+        self.validate_server = false;
+        self
+    }
+}
+
+impl From<Eap> for EapConfig {
+    fn from(eap: Eap) -> Self {
+        EapConfig::new(eap)
+    }
+}
+
+/// Inner authentication carried inside the EAP-TTLS tunnel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TtlsInner {
+    Pap,
+    Mschapv2,
+}
+
+/// Source of the client certificate and private key for EAP-TLS.
+///
+/// The `Debug` output leaves out the PKCS#12 data and passwords.
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ClientCertificate {
+    /// PEM/DER certificate and private key files.
+    Files {
+        cert: PathBuf,
+        key: PathBuf,
+        key_password: Option<SecretString>,
+    },
+    /// A PKCS#12 bundle containing certificate and key; installed by the
+    /// backend. The data is wiped from memory when dropped.
+    Pkcs12 {
+        data: Zeroizing<Vec<u8>>,
+        password: Option<SecretString>,
+    },
+    /// A reference into the OS certificate store (e.g. a thumbprint on Windows
+    /// or a keychain label on macOS).
+    Store(String),
+}
+
+impl fmt::Debug for ClientCertificate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientCertificate::Files { cert, key, key_password } => f
+                .debug_struct("Files")
+                .field("cert", cert)
+                .field("key", key)
+                .field("key_password", key_password)
+                .finish(),
+            ClientCertificate::Pkcs12 { data, password } => f
+                .debug_struct("Pkcs12")
+                .field("data", &format_args!("[REDACTED; {} bytes]", data.len()))
+                .field("password", password)
+                .finish(),
+            ClientCertificate::Store(reference) => f.debug_tuple("Store").field(reference).finish(),
+        }
+    }
+}
+
+// Method codes shared with wifi_c_api.h
+const WIFI_EAP_PEAP: libc::c_int = 0;
+const WIFI_EAP_TLS: libc::c_int = 1;
+const WIFI_EAP_TTLS: libc::c_int = 2;
+const WIFI_EAP_SIM: libc::c_int = 3;
+const WIFI_EAP_AKA: libc::c_int = 4;
+const WIFI_EAP_AKA_PRIME: libc::c_int = 5;
+const WIFI_PHASE2_MSCHAPV2: libc::c_int = 0;
+const WIFI_PHASE2_PAP: libc::c_int = 1;
+
+#[repr(C)]
+pub(crate) struct RawEapConfig {
+    method: libc::c_int,
+    phase2: libc::c_int,
+    identity: *const libc::c_char,
+    anonymous_identity: *const libc::c_char,
+    password: *const libc::c_char,
+    ca_cert: *const libc::c_char,
+    disable_server_validation: libc::c_int,
+    server_cert_hash: *const libc::c_char,
+    domain_match: *const libc::c_char,
+    domain_suffix_match: *const libc::c_char,
+    client_cert: *const libc::c_char,
+    private_key: *const libc::c_char,
+    private_key_password: *const libc::c_char,
+    pkcs12_data: *const u8,
+    pkcs12_len: libc::c_int,
+    cert_store_ref: *const libc::c_char,
+}
+
+/// Owned C strings backing a `RawEapConfig`; must outlive the FFI call.
+#[derive(Default)]
+pub(crate) struct EapStrings<'a> {
+    method: libc::c_int,
+    phase2: libc::c_int,
+    identity: CString,
+    anonymous_identity: Option<CString>,
+    password: Option<Zeroizing<CString>>,
+    ca_cert: Option<CString>,
+    disable_server_validation: libc::c_int,
+    server_cert_hash: Option<CString>,
+    domain_match: Option<CString>,
+    domain_suffix_match: Option<CString>,
+    client_cert: Option<CString>,
+    private_key: Option<CString>,
+    private_key_password: Option<Zeroizing<CString>>,
+    pkcs12: Option<&'a [u8]>,
+    cert_store_ref: Option<CString>,
+}
+
+fn c_string(s: &str) -> Result<CString, WifiError> {
+    Ok(CString::new(s)?)
+}
+
+fn c_path(p: &std::path::Path) -> Result<CString, WifiError> {
+    Ok(CString::new(p.to_string_lossy().into_owned())?)
+}
+
+fn ptr(s: &Option<CString>) -> *const libc::c_char {
+    s.as_ref().map_or(std::ptr::null(), |s| s.as_ptr())
+}
+
+fn secret_ptr(s: &Option<Zeroizing<CString>>) -> *const libc::c_char {
+    s.as_ref().map_or(std::ptr::null(), |s| s.as_ptr())
+}
+
+impl<'a> EapStrings<'a> {
+    /// # Errors
+    ///
+    /// Returns [`WifiError::InvalidArgument`] if a string or path contains a
+    /// NUL byte.
+    pub(crate) fn new(config: &'a EapConfig) -> Result<Self, WifiError> {
+        let mut strings = Self::credentials(&config.eap)?;
+        strings.disable_server_validation = !config.validate_server as libc::c_int;
+        strings.server_cert_hash = config.server_cert_sha256.as_deref().map(c_string).transpose()?;
+        strings.domain_match = config.domain_match.as_deref().map(c_string).transpose()?;
+        strings.domain_suffix_match = config.domain_suffix_match.as_deref().map(c_string).transpose()?;
+        Ok(strings)
+    }
+
+    fn credentials(eap: &'a Eap) -> Result<Self, WifiError> {
+        Ok(match eap {
+            Eap::Peap { identity, password, anonymous_identity, ca_cert } => EapStrings {
+                method: WIFI_EAP_PEAP,
+                identity: c_string(identity)?,
+                anonymous_identity: anonymous_identity.as_deref().map(c_string).transpose()?,
+                password: Some(password.to_c_string()?),
+                ca_cert: ca_cert.as_deref().map(c_path).transpose()?,
+                ..Default::default()
+            },
+            Eap::Ttls { identity, password, inner, anonymous_identity, ca_cert } => EapStrings {
+                method: WIFI_EAP_TTLS,
+                phase2: match inner {
+                    TtlsInner::Mschapv2 => WIFI_PHASE2_MSCHAPV2,
+                    TtlsInner::Pap => WIFI_PHASE2_PAP,
+                },
+                identity: c_string(identity)?,
+                anonymous_identity: anonymous_identity.as_deref().map(c_string).transpose()?,
+                password: Some(password.to_c_string()?),
+                ca_cert: ca_cert.as_deref().map(c_path).transpose()?,
+                ..Default::default()
+            },
+            Eap::Sim { identity, pin } | Eap::Aka { identity, pin, .. } => EapStrings {
+                method: eap.method().code(),
+                identity: c_string(identity.as_deref().unwrap_or(""))?,
+                password: pin.as_ref().map(SecretString::to_c_string).transpose()?,
+                ..Default::default()
+            },
+            Eap::Tls { identity, client_cert, ca_cert } => {
+                let mut strings = EapStrings {
+                    method: WIFI_EAP_TLS,
+                    identity: c_string(identity)?,
+                    ca_cert: ca_cert.as_deref().map(c_path).transpose()?,
+                    ..Default::default()
+                };
+                match client_cert {
+                    ClientCertificate::Files { cert, key, key_password } => {
+                        strings.client_cert = Some(c_path(cert)?);
+                        strings.private_key = Some(c_path(key)?);
+                        strings.private_key_password = key_password.as_ref().map(SecretString::to_c_string).transpose()?;
+                    }
+                    ClientCertificate::Pkcs12 { data, password } => {
+                        strings.pkcs12 = Some(data);
+                        strings.private_key_password = password.as_ref().map(SecretString::to_c_string).transpose()?;
+                    }
+                    ClientCertificate::Store(reference) => {
+                        strings.cert_store_ref = Some(c_string(reference)?);
+                    }
+                }
+                strings
+            }
+        })
+    }
+
+    pub(crate) fn raw(&self) -> RawEapConfig {
+        RawEapConfig {
+            method: self.method,
+            phase2: self.phase2,
+            identity: self.identity.as_ptr(),
+            anonymous_identity: ptr(&self.anonymous_identity),
+            password: secret_ptr(&self.password),
+            ca_cert: ptr(&self.ca_cert),
+            disable_server_validation: self.disable_server_validation,
+            server_cert_hash: ptr(&self.server_cert_hash),
+            domain_match: ptr(&self.domain_match),
+            domain_suffix_match: ptr(&self.domain_suffix_match),
+            client_cert: ptr(&self.client_cert),
+            private_key: ptr(&self.private_key),
+            private_key_password: secret_ptr(&self.private_key_password),
+            pkcs12_data: self.pkcs12.map_or(std::ptr::null(), |d| d.as_ptr()),
+            pkcs12_len: self.pkcs12.map_or(0, |d| d.len() as libc::c_int),
+            cert_store_ref: ptr(&self.cert_store_ref),
+        }
+    }
+}
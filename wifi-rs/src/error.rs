@@ -0,0 +1,82 @@
+//! Error type for fallible WiFi operations.
+
+use std::fmt;
+
+use crate::NetworkFailure;
+
+/// Reason a WiFi operation failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WifiError {
+    /// The platform or adapter does not support the requested operation.
+    Unsupported,
+    /// The native layer reported a failure.
+    OperationFailed,
+    /// The adapter associated but the network is not usable.
+    NetworkUnusable(NetworkFailure),
+    /// No saved profile with the requested name exists.
+    ProfileNotFound,
+    /// The network rejected the password, certificate or other credentials.
+    InvalidCredentials,
+    /// No WiFi adapter is present.
+    AdapterNotFound,
+    /// The process lacks the privileges to manage the adapter, usually
+    /// root or `CAP_NET_ADMIN` on Linux.
+    PermissionDenied,
+    /// The operation did not complete in time, e.g. the network was out of
+    /// range or DHCP never answered.
+    Timeout,
+    /// The driver or a system service such as `wpa_supplicant` or
+    /// `hostapd` reported an error.
+    DriverError,
+    /// An argument is malformed, e.g. a string contains a NUL byte and
+    /// cannot be passed to the native library.
+    InvalidArgument,
+    /// The operation was aborted with [`WiFi::cancel`](crate::WiFi::cancel)
+    /// or a [`CancellationHandle`](crate::CancellationHandle).
+    Cancelled,
+}
+
+impl WifiError {
+    /// Map a `WifiErrorCode` from `wifi_manager_get_last_error`, `None` for
+    /// `WIFI_ERROR_NONE`.
+    pub(crate) fn from_code(code: libc::c_int) -> Option<WifiError> {
+        match code {
+            0 => None,
+            2 => Some(WifiError::Unsupported),
+            3 => Some(WifiError::InvalidCredentials),
+            4 => Some(WifiError::AdapterNotFound),
+            5 => Some(WifiError::PermissionDenied),
+            6 => Some(WifiError::Timeout),
+            7 => Some(WifiError::DriverError),
+            8 => Some(WifiError::Cancelled),
+            _ => Some(WifiError::OperationFailed),
+        }
+    }
+}
+
+impl fmt::Display for WifiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WifiError::Unsupported => write!(f, "operation not supported on this platform"),
+            WifiError::OperationFailed => write!(f, "operation failed"),
+            WifiError::NetworkUnusable(failure) => write!(f, "connected but the network is unusable: {}", failure),
+            WifiError::ProfileNotFound => write!(f, "no saved profile with that name"),
+            WifiError::InvalidCredentials => write!(f, "the network rejected the credentials"),
+            WifiError::AdapterNotFound => write!(f, "no WiFi adapter found"),
+            WifiError::PermissionDenied => write!(f, "permission denied"),
+            WifiError::Timeout => write!(f, "operation timed out"),
+            WifiError::DriverError => write!(f, "driver or system service error"),
+            WifiError::InvalidArgument => write!(f, "invalid argument"),
+            WifiError::Cancelled => write!(f, "operation cancelled"),
+        }
+    }
+}
+
+impl std::error::Error for WifiError {}
+
+impl From<std::ffi::NulError> for WifiError {
+    fn from(_: std::ffi::NulError) -> Self {
+        WifiError::InvalidArgument
+    }
+}
@@ -0,0 +1,206 @@
+//! Bounded in-memory history of recent WiFi events.
+//!
+//! Every [`WiFi`](crate::WiFi) keeps the last few events so crash reports and
+//! bug tickets can include recent wireless history without any logging setup.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+use crate::{ConnectionStatus, NetworkFailure, WifiError};
+
+/// Number of events kept unless changed with
+/// [`WiFi::set_event_log_capacity`](crate::WiFi::set_event_log_capacity).
+pub const DEFAULT_EVENT_LOG_CAPACITY: usize = 64;
+
+/// Why the connection went down.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DisconnectReason {
+    /// [`WiFi::disconnect`](crate::WiFi::disconnect) was called.
+    Requested,
+    /// The link dropped without a request, noticed when polling the status.
+    LinkLost,
+}
+
+/// What happened.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EventKind {
+    /// A connection attempt succeeded, or a connection made by other
+    /// software was noticed when polling the status.
+    Connected { ssid: String },
+    /// A connection attempt failed.
+    ConnectFailed { ssid: String },
+    /// The adapter associated but the network was not usable.
+    NetworkUnusable { ssid: String, failure: NetworkFailure },
+    /// A watchdog moved the connection to a better network.
+    NetworkSwitched { from: Option<String>, to: String },
+    /// The connection went down.
+    Disconnected { reason: DisconnectReason },
+    /// The adapter moved to another access point of the same network.
+    Roamed { ssid: String, from_bssid: String, to_bssid: String },
+    /// A [`FlapDetector`](crate::FlapDetector) saw the link drop `flaps`
+    /// times within `window`.
+    LinkFlapping { flaps: usize, window: Duration },
+    /// A scan failed, usually an adapter or permission problem. A scan that
+    /// finds no networks is not a failure.
+    ScanFailed,
+    /// A hotspot was started.
+    HotspotStarted { ssid: String },
+    /// A hotspot could not be started.
+    HotspotFailed { ssid: String },
+    /// The hotspot was stopped.
+    HotspotStopped,
+}
+
+/// A timestamped entry of the event log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WifiEvent {
+    pub timestamp: SystemTime,
+    pub kind: EventKind,
+}
+
+impl fmt::Display for WifiEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let since_epoch = self
+            .timestamp
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default();
+        write!(f, "{}.{:03} ", since_epoch.as_secs(), since_epoch.subsec_millis())?;
+        match &self.kind {
+            EventKind::Connected { ssid } => write!(f, "connected to {}", ssid),
+            EventKind::ConnectFailed { ssid } => write!(f, "failed to connect to {}", ssid),
+            EventKind::NetworkUnusable { ssid, failure } => write!(f, "joined {} but {}", ssid, failure),
+            EventKind::NetworkSwitched { from: Some(from), to } => write!(f, "switched from {} to {}", from, to),
+            EventKind::NetworkSwitched { from: None, to } => write!(f, "switched to {}", to),
+            EventKind::Disconnected { reason: DisconnectReason::Requested } => write!(f, "disconnected"),
+            EventKind::Disconnected { reason: DisconnectReason::LinkLost } => write!(f, "link lost"),
+            EventKind::Roamed { ssid, from_bssid, to_bssid } => {
+                write!(f, "roamed on {} from {} to {}", ssid, from_bssid, to_bssid)
+            }
+            EventKind::LinkFlapping { flaps, window } => {
+                write!(f, "link flapping: {} drops in {}s", flaps, window.as_secs())
+            }
+            EventKind::ScanFailed => write!(f, "scan failed"),
+            EventKind::HotspotStarted { ssid } => write!(f, "hotspot {} started", ssid),
+            EventKind::HotspotFailed { ssid } => write!(f, "failed to start hotspot {}", ssid),
+            EventKind::HotspotStopped => write!(f, "hotspot stopped"),
+        }
+    }
+}
+
+struct Inner {
+    events: VecDeque<WifiEvent>,
+    capacity: usize,
+    last_status: Option<ConnectionStatus>,
+    /// Network and error of the latest failed connection attempt, cleared
+    /// by the next successful one.
+    last_error: Option<(String, WifiError)>,
+}
+
+/// Ring buffer of the most recent events.
+pub(crate) struct EventLog {
+    inner: Mutex<Inner>,
+}
+
+impl EventLog {
+    pub(crate) fn new() -> Self {
+        EventLog {
+            inner: Mutex::new(Inner {
+                events: VecDeque::with_capacity(DEFAULT_EVENT_LOG_CAPACITY),
+                capacity: DEFAULT_EVENT_LOG_CAPACITY,
+                last_status: None,
+                last_error: None,
+            }),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Inner> {
+        self.inner.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    pub(crate) fn record(&self, kind: EventKind) {
+        crate::trace::event(&kind);
+        let mut inner = self.lock();
+        match &kind {
+            EventKind::Connected { .. } => inner.last_error = None,
+            EventKind::ConnectFailed { ssid } => inner.last_error = Some((ssid.clone(), WifiError::OperationFailed)),
+            EventKind::NetworkUnusable { ssid, failure } => {
+                inner.last_error = Some((ssid.clone(), WifiError::NetworkUnusable(*failure)))
+            }
+            _ => {}
+        }
+        if inner.capacity == 0 {
+            return;
+        }
+        while inner.events.len() >= inner.capacity {
+            inner.events.pop_front();
+        }
+        inner.events.push_back(WifiEvent { timestamp: SystemTime::now(), kind });
+    }
+
+    /// Note a polled status, recording a lost link when a connection drops
+    /// without a requested disconnect.
+    ///
+    /// Returns whether a connection came up without a connect call, e.g.
+    /// made by other software, so the caller can record it with its SSID.
+    pub(crate) fn observe_status(&self, status: ConnectionStatus) -> bool {
+        let previous = self.lock().last_status.replace(status);
+        if previous == Some(ConnectionStatus::Connected) && status == ConnectionStatus::Disconnected {
+            self.record(EventKind::Disconnected { reason: DisconnectReason::LinkLost });
+        }
+        previous.is_some_and(|previous| previous != ConnectionStatus::Connected) && status == ConnectionStatus::Connected
+    }
+
+    /// Note a status reached through a requested operation, so it is not
+    /// mistaken for a lost link on the next poll.
+    pub(crate) fn set_status(&self, status: ConnectionStatus) {
+        self.lock().last_status = Some(status);
+    }
+
+    /// Record a failed connection attempt along with the reason.
+    pub(crate) fn record_failure(&self, ssid: String, error: WifiError) {
+        match error {
+            WifiError::NetworkUnusable(failure) => self.record(EventKind::NetworkUnusable { ssid, failure }),
+            _ => {
+                self.record(EventKind::ConnectFailed { ssid: ssid.clone() });
+                self.lock().last_error = Some((ssid, error));
+            }
+        }
+    }
+
+    /// Record the outcome of a connection attempt made by a simulated or
+    /// replayed backend.
+    pub(crate) fn record_connect(&self, ssid: &str, outcome: &Result<(), WifiError>) {
+        let ssid = ssid.to_string();
+        match outcome {
+            Ok(()) => {
+                self.set_status(ConnectionStatus::Connected);
+                self.record(EventKind::Connected { ssid });
+            }
+            Err(error) => self.record_failure(ssid, *error),
+        }
+    }
+
+    /// SSID and error of the latest failed connection attempt, unless a
+    /// connection has succeeded since.
+    pub(crate) fn last_error(&self) -> Option<(String, WifiError)> {
+        self.lock().last_error.clone()
+    }
+
+    pub(crate) fn snapshot(&self) -> Vec<WifiEvent> {
+        self.lock().events.iter().cloned().collect()
+    }
+
+    pub(crate) fn set_capacity(&self, capacity: usize) {
+        let mut inner = self.lock();
+        inner.capacity = capacity;
+        while inner.events.len() > capacity {
+            inner.events.pop_front();
+        }
+    }
+
+    pub(crate) fn clear(&self) {
+        self.lock().events.clear();
+    }
+}
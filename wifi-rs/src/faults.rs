@@ -0,0 +1,269 @@
+//! Deterministic fault injection for any backend.
+//!
+//! A [`FaultInjector`] wraps a [`WifiBackend`] and makes some of its calls
+//! fail the way real adapters do: scans that time out, authentication that
+//! fails every few attempts, or a native handle that stops working. Random
+//! faults come from a generator seeded by the caller, so a failing run can
+//! be reproduced exactly by reusing its seed.
+//!
+//! ```
+//! # #[cfg(feature = "backend-mock")] {
+//! use wifi_rs::mock::{self, MockBackend};
+//! use wifi_rs::{ConnectOptions, FaultInjector, WifiBackend};
+//!
+//! let inner = MockBackend::new().with_networks(vec![mock::network("Office", 2437, -55)]);
+//! let wifi = FaultInjector::new(inner, 42).auth_failure_every(2);
+//!
+//! let options = ConnectOptions::password("secret");
+//! assert!(wifi.connect_with("Office", &options).is_ok());
+//! assert!(wifi.connect_with("Office", &options).is_err());
+//! assert!(wifi.connect_with("Office", &options).is_ok());
+//! # }
+//! ```
+
+use std::sync::{Mutex, MutexGuard};
+use std::time::Duration;
+
+use crate::{
+    ConnectOptions, ConnectionStatus, EventKind, HotspotConfig, LinkInfo, NetworkInfo, WifiBackend, WifiError, WifiEvent,
+};
+
+/// A fault a [`FaultInjector`] produced.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InjectedFault {
+    /// A scan timed out and returned no networks.
+    ScanTimeout,
+    /// Authentication to `ssid` failed.
+    AuthFailure { ssid: String },
+    /// The native handle became invalid; every call fails until
+    /// [`FaultInjector::restore_handle`] is called.
+    HandleInvalidated,
+}
+
+struct State {
+    rng: u64,
+    calls: u64,
+    connect_attempts: u64,
+    handle_valid: bool,
+    expired: bool,
+    injected: Vec<InjectedFault>,
+}
+
+/// [`WifiBackend`] decorator that injects configurable failures.
+///
+/// Injected failures look like the real ones: a timed-out scan returns no
+/// networks after the configured delay, a failed authentication returns
+/// [`WifiError::InvalidCredentials`], and both are recorded in the wrapped
+/// backend's event log. While the handle is invalid, scans return nothing,
+/// connecting fails, the status is [`ConnectionStatus::Error`] and there is
+/// no link.
+pub struct FaultInjector<B: WifiBackend> {
+    inner: B,
+    scan_timeout_rate: f64,
+    scan_timeout: Duration,
+    auth_failure_every: Option<u64>,
+    auth_failure_rate: f64,
+    invalidate_after: Option<u64>,
+    handle_invalidation_rate: f64,
+    state: Mutex<State>,
+}
+
+impl<B: WifiBackend> FaultInjector<B> {
+    /// Wrap `inner` without any faults configured. `seed` determines the
+    /// sequence of random faults.
+    pub fn new(inner: B, seed: u64) -> Self {
+        FaultInjector {
+            inner,
+            scan_timeout_rate: 0.0,
+            scan_timeout: Duration::ZERO,
+            auth_failure_every: None,
+            auth_failure_rate: 0.0,
+            invalidate_after: None,
+            handle_invalidation_rate: 0.0,
+            state: Mutex::new(State {
+                // Xorshift needs a non-zero state
+                rng: (seed ^ 0x9e37_79b9_7f4a_7c15).max(1),
+                calls: 0,
+                connect_attempts: 0,
+                handle_valid: true,
+                expired: false,
+                injected: Vec::new(),
+            }),
+        }
+    }
+
+    /// Let scans time out with `probability` (0.0 to 1.0), returning no
+    /// networks after blocking for `delay`.
+    pub fn scan_timeouts(mut self, probability: f64, delay: Duration) -> Self {
+        self.scan_timeout_rate = probability.clamp(0.0, 1.0);
+        self.scan_timeout = delay;
+        self
+    }
+
+    /// Fail every `n`th connection attempt as if the credentials were
+    /// rejected. `0` disables this fault.
+    pub fn auth_failure_every(mut self, n: u64) -> Self {
+        self.auth_failure_every = (n > 0).then_some(n);
+        self
+    }
+
+    /// Fail connection attempts with `probability` (0.0 to 1.0).
+    pub fn auth_failure_rate(mut self, probability: f64) -> Self {
+        self.auth_failure_rate = probability.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Invalidate the handle once `calls` backend calls have been made.
+    pub fn invalidate_handle_after(mut self, calls: u64) -> Self {
+        self.invalidate_after = Some(calls);
+        self
+    }
+
+    /// Invalidate the handle on any call with `probability` (0.0 to 1.0).
+    pub fn handle_invalidation_rate(mut self, probability: f64) -> Self {
+        self.handle_invalidation_rate = probability.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Make the handle usable again, as after recreating it.
+    pub fn restore_handle(&self) {
+        self.lock().handle_valid = true;
+    }
+
+    /// Faults injected so far, oldest first.
+    pub fn injected(&self) -> Vec<InjectedFault> {
+        self.lock().injected.clone()
+    }
+
+    /// The wrapped backend.
+    pub fn inner(&self) -> &B {
+        &self.inner
+    }
+
+    /// Return the wrapped backend.
+    pub fn into_inner(self) -> B {
+        self.inner
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Count a call and decide whether the handle is still valid.
+    fn handle_valid(&self) -> bool {
+        let mut state = self.lock();
+        state.calls += 1;
+        if !state.handle_valid {
+            return false;
+        }
+        // A fixed call count invalidates only once
+        let expired = !state.expired && self.invalidate_after.is_some_and(|after| state.calls > after);
+        if expired || state.chance(self.handle_invalidation_rate) {
+            state.expired |= expired;
+            state.handle_valid = false;
+            state.injected.push(InjectedFault::HandleInvalidated);
+            return false;
+        }
+        true
+    }
+}
+
+impl State {
+    /// Whether an event with `probability` happens, from a xorshift64
+    /// sequence.
+    fn chance(&mut self, probability: f64) -> bool {
+        if probability <= 0.0 {
+            return false;
+        }
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 7;
+        self.rng ^= self.rng << 17;
+        ((self.rng >> 11) as f64 / (1u64 << 53) as f64) < probability
+    }
+}
+
+impl<B: WifiBackend> WifiBackend for FaultInjector<B> {
+    fn scan(&self) -> Vec<NetworkInfo> {
+        if !self.handle_valid() {
+            return Vec::new();
+        }
+        let timeout = {
+            let mut state = self.lock();
+            let timeout = state.chance(self.scan_timeout_rate);
+            if timeout {
+                state.injected.push(InjectedFault::ScanTimeout);
+            }
+            timeout
+        };
+        if timeout {
+            std::thread::sleep(self.scan_timeout);
+            self.inner.record_event(EventKind::ScanFailed);
+            return Vec::new();
+        }
+        self.inner.scan()
+    }
+
+    fn connect_with(&self, ssid: &str, options: &ConnectOptions) -> Result<(), WifiError> {
+        if !self.handle_valid() {
+            return Err(WifiError::OperationFailed);
+        }
+        let fail = {
+            let mut state = self.lock();
+            state.connect_attempts += 1;
+            let nth = self.auth_failure_every.is_some_and(|n| state.connect_attempts.is_multiple_of(n));
+            let fail = nth || state.chance(self.auth_failure_rate);
+            if fail {
+                state.injected.push(InjectedFault::AuthFailure { ssid: ssid.to_string() });
+            }
+            fail
+        };
+        if fail {
+            self.inner.record_event(EventKind::ConnectFailed { ssid: ssid.to_string() });
+            return Err(WifiError::InvalidCredentials);
+        }
+        self.inner.connect_with(ssid, options)
+    }
+
+    fn disconnect(&self) -> Result<(), WifiError> {
+        if !self.handle_valid() {
+            return Err(WifiError::OperationFailed);
+        }
+        self.inner.disconnect()
+    }
+
+    fn get_status(&self) -> ConnectionStatus {
+        if !self.handle_valid() {
+            return ConnectionStatus::Error;
+        }
+        self.inner.get_status()
+    }
+
+    fn link_info(&self) -> Option<LinkInfo> {
+        if !self.handle_valid() {
+            return None;
+        }
+        self.inner.link_info()
+    }
+
+    fn event_log(&self) -> Vec<WifiEvent> {
+        self.inner.event_log()
+    }
+
+    fn record_event(&self, kind: EventKind) {
+        self.inner.record_event(kind);
+    }
+
+    fn create_hotspot_with(&self, config: &HotspotConfig) -> Result<(), WifiError> {
+        if !self.handle_valid() {
+            return Err(WifiError::OperationFailed);
+        }
+        self.inner.create_hotspot_with(config)
+    }
+
+    fn stop_hotspot(&self) -> Result<(), WifiError> {
+        if !self.handle_valid() {
+            return Err(WifiError::OperationFailed);
+        }
+        self.inner.stop_hotspot()
+    }
+}
@@ -0,0 +1,186 @@
+//! Detection of unstable links that keep dropping and reconnecting.
+//!
+//! Feed the detector connection status samples, either directly with
+//! [`FlapDetector::observe`] or by polling a [`WiFi`] with
+//! [`FlapDetector::poll`]. It counts disconnects inside a sliding window and
+//! raises a [`FlapAlert`] once when the count reaches the threshold, so
+//! supervising software can switch networks or alert an operator.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use crate::{ConnectionStatus, EventKind, WiFi};
+
+/// Raised when the number of disconnects inside the window reaches the
+/// threshold.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlapAlert {
+    /// Disconnects counted inside the window.
+    pub flaps: usize,
+    pub window: Duration,
+}
+
+/// Link stability statistics.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlapStats {
+    /// Disconnects inside the current window.
+    pub flaps_in_window: usize,
+    /// Disconnects since the detector was created.
+    pub total_disconnects: usize,
+    /// Mean time the link stayed up before dropping.
+    pub mean_time_between_failures: Option<Duration>,
+    /// Mean time from a drop until the link came back.
+    pub mean_time_to_recover: Option<Duration>,
+    /// Whether the link is currently considered flapping.
+    pub flapping: bool,
+}
+
+/// Sliding-window link flap detector.
+#[derive(Debug, Clone)]
+pub struct FlapDetector {
+    window: Duration,
+    threshold: usize,
+    connected: Option<bool>,
+    last_change: Option<Instant>,
+    disconnects: VecDeque<Instant>,
+    total_disconnects: usize,
+    uptime: Duration,
+    uptime_samples: u32,
+    downtime: Duration,
+    downtime_samples: u32,
+    flapping: bool,
+}
+
+impl FlapDetector {
+    /// Create a detector that alerts after `threshold` disconnects within
+    /// `window`.
+    ///
+    /// # Arguments
+    ///
+    /// * `window` - Length of the sliding window
+    /// * `threshold` - Number of disconnects inside the window that counts as flapping
+    pub fn new(window: Duration, threshold: usize) -> Self {
+        FlapDetector {
+            window,
+            threshold: threshold.max(1),
+            connected: None,
+            last_change: None,
+            disconnects: VecDeque::new(),
+            total_disconnects: 0,
+            uptime: Duration::ZERO,
+            uptime_samples: 0,
+            downtime: Duration::ZERO,
+            downtime_samples: 0,
+            flapping: false,
+        }
+    }
+
+    /// Record a status sample taken now.
+    ///
+    /// # Returns
+    ///
+    /// A [`FlapAlert`] when the link starts flapping. The alert is raised
+    /// once; it can fire again after the window has drained below the
+    /// threshold.
+    pub fn observe(&mut self, status: ConnectionStatus) -> Option<FlapAlert> {
+        self.observe_at(status, Instant::now())
+    }
+
+    /// Record a status sample taken at `at`.
+    ///
+    /// Samples must be passed in chronological order.
+    pub fn observe_at(&mut self, status: ConnectionStatus, at: Instant) -> Option<FlapAlert> {
+        // Connecting is a transient state and does not end an outage.
+        let connected = match status {
+            ConnectionStatus::Connected => true,
+            ConnectionStatus::Disconnected | ConnectionStatus::Error => false,
+            ConnectionStatus::Connecting => return self.evaluate(at),
+        };
+
+        if self.connected != Some(connected) {
+            if let (Some(previous), Some(since)) = (self.connected, self.last_change) {
+                let elapsed = at.saturating_duration_since(since);
+                if previous {
+                    self.uptime += elapsed;
+                    self.uptime_samples += 1;
+                } else {
+                    self.downtime += elapsed;
+                    self.downtime_samples += 1;
+                }
+            }
+            if self.connected == Some(true) && !connected {
+                self.disconnects.push_back(at);
+                self.total_disconnects += 1;
+            }
+            self.connected = Some(connected);
+            self.last_change = Some(at);
+        }
+
+        self.evaluate(at)
+    }
+
+    /// Poll the status of `wifi` and record it.
+    ///
+    /// A raised alert is also added to the event log of `wifi`.
+    pub fn poll(&mut self, wifi: &WiFi) -> Option<FlapAlert> {
+        let alert = self.observe(wifi.get_status());
+        if let Some(alert) = &alert {
+            wifi.events.record(EventKind::LinkFlapping { flaps: alert.flaps, window: alert.window });
+        }
+        alert
+    }
+
+    fn evaluate(&mut self, now: Instant) -> Option<FlapAlert> {
+        self.expire(now);
+        let flaps = self.disconnects.len();
+        if flaps >= self.threshold {
+            if !self.flapping {
+                self.flapping = true;
+                return Some(FlapAlert { flaps, window: self.window });
+            }
+        } else {
+            self.flapping = false;
+        }
+        None
+    }
+
+    fn expire(&mut self, now: Instant) {
+        while let Some(&oldest) = self.disconnects.front() {
+            if now.saturating_duration_since(oldest) > self.window {
+                self.disconnects.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Statistics as of now.
+    pub fn stats(&mut self) -> FlapStats {
+        self.stats_at(Instant::now())
+    }
+
+    /// Statistics as of `now`.
+    pub fn stats_at(&mut self, now: Instant) -> FlapStats {
+        self.expire(now);
+        FlapStats {
+            flaps_in_window: self.disconnects.len(),
+            total_disconnects: self.total_disconnects,
+            mean_time_between_failures: mean(self.uptime, self.uptime_samples),
+            mean_time_to_recover: mean(self.downtime, self.downtime_samples),
+            flapping: self.flapping,
+        }
+    }
+
+    /// Forget all history.
+    pub fn reset(&mut self) {
+        *self = FlapDetector::new(self.window, self.threshold);
+    }
+}
+
+fn mean(total: Duration, samples: u32) -> Option<Duration> {
+    if samples == 0 {
+        None
+    } else {
+        Some(total / samples)
+    }
+}
@@ -0,0 +1,213 @@
+//! Position estimates from visible access points.
+//!
+//! Enabled with the `geolocation` feature. The BSSIDs and signal strengths
+//! of a scan are submitted to a location service speaking the Mozilla
+//! Location Service geolocate API, which Google's Geolocation API and
+//! self-hosted services such as Ichnaea share, and the estimated position
+//! comes back. Useful for devices without GPS.
+//!
+//! Access points whose SSID ends in `_nomap` opted out of location
+//! services and hidden networks are never submitted.
+//!
+//! [`LocationService`] is the HTTP client for such services; implement
+//! [`LocationProvider`] to resolve positions some other way, e.g. from an
+//! offline database of access points.
+//!
+//! ```no_run
+//! use wifi_rs::geolocation::LocationService;
+//! use wifi_rs::WiFi;
+//!
+//! let service = LocationService::google("API_KEY");
+//! let location = WiFi::new().locate(&service)?;
+//! println!("{}, {} (±{} m)", location.latitude, location.longitude, location.accuracy_m);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use std::fmt;
+use std::time::Duration;
+
+use serde_json::{json, Value};
+
+use crate::{NetworkInfo, ScanSnapshot};
+
+/// Geolocate endpoint of Google's Geolocation API.
+const GOOGLE_URL: &str = "https://www.googleapis.com/geolocation/v1/geolocate";
+
+/// Fewest access points location services accept, to protect the privacy
+/// of a single access point's owner.
+pub const MIN_ACCESS_POINTS: usize = 2;
+
+/// Why no position could be determined.
+#[derive(Debug)]
+pub enum GeolocationError {
+    /// Fewer than [`MIN_ACCESS_POINTS`] usable access points are visible.
+    TooFewAccessPoints,
+    /// The service does not know enough of the access points.
+    NotFound,
+    /// The service answered with an HTTP error status, e.g. 403 for a
+    /// missing or invalid API key.
+    Http(u16),
+    /// The request did not reach the service or the response was cut off.
+    Transport(String),
+    /// The response is not a geolocate response.
+    InvalidResponse,
+}
+
+impl fmt::Display for GeolocationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GeolocationError::TooFewAccessPoints => write!(f, "too few access points visible"),
+            GeolocationError::NotFound => write!(f, "location service could not locate the access points"),
+            GeolocationError::Http(status) => write!(f, "location service returned HTTP {}", status),
+            GeolocationError::Transport(error) => write!(f, "location service unreachable: {}", error),
+            GeolocationError::InvalidResponse => write!(f, "invalid location service response"),
+        }
+    }
+}
+
+impl std::error::Error for GeolocationError {}
+
+/// Estimated position of the device.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Location {
+    /// Latitude in degrees (WGS 84).
+    pub latitude: f64,
+    /// Longitude in degrees (WGS 84).
+    pub longitude: f64,
+    /// Radius in meters around the position that contains the device with
+    /// high probability.
+    pub accuracy_m: f64,
+}
+
+/// Something that estimates a position from access points, used by
+/// [`WiFi::locate`](crate::WiFi::locate).
+pub trait LocationProvider {
+    /// Estimate the position from `networks`, seen `age` before the call.
+    ///
+    /// # Errors
+    ///
+    /// See [`GeolocationError`].
+    fn estimate(&self, networks: &[NetworkInfo], age: Duration) -> Result<Location, GeolocationError>;
+
+    /// Estimate the position from a background scan of a
+    /// [`ScanCache`](crate::ScanCache), as old as the snapshot.
+    ///
+    /// # Errors
+    ///
+    /// See [`GeolocationError`].
+    fn estimate_snapshot(&self, snapshot: &ScanSnapshot) -> Result<Location, GeolocationError> {
+        let networks: Vec<NetworkInfo> = snapshot.networks.iter().map(|cached| cached.network.clone()).collect();
+        self.estimate(&networks, snapshot.taken_at.elapsed().unwrap_or_default())
+    }
+}
+
+/// A location service endpoint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocationService {
+    url: String,
+    api_key: Option<String>,
+    timeout: Duration,
+}
+
+impl LocationService {
+    /// Service at `url`, the full geolocate endpoint such as
+    /// `https://location.example.com/v1/geolocate`.
+    pub fn new(url: &str) -> Self {
+        LocationService {
+            url: url.to_string(),
+            api_key: None,
+            timeout: Duration::from_secs(10),
+        }
+    }
+
+    /// Google's Geolocation API with the key `api_key`.
+    pub fn google(api_key: &str) -> Self {
+        LocationService::new(GOOGLE_URL).api_key(api_key)
+    }
+
+    /// API key sent as the `key` query parameter.
+    pub fn api_key(mut self, key: &str) -> Self {
+        self.api_key = Some(key.to_string());
+        self
+    }
+
+    /// Time limit for the whole request.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Estimate the position from scan results.
+    ///
+    /// # Errors
+    ///
+    /// See [`GeolocationError`].
+    pub fn locate(&self, networks: &[NetworkInfo]) -> Result<Location, GeolocationError> {
+        self.estimate(networks, Duration::ZERO)
+    }
+}
+
+impl LocationProvider for LocationService {
+    fn estimate(&self, networks: &[NetworkInfo], age: Duration) -> Result<Location, GeolocationError> {
+        let body = request_body(networks, age)?;
+
+        let mut request = ureq::post(&self.url).timeout(self.timeout);
+        if let Some(key) = &self.api_key {
+            request = request.query("key", key);
+        }
+        let response = match request.set("Content-Type", "application/json").send_string(&body.to_string()) {
+            Ok(response) => response,
+            Err(ureq::Error::Status(404, _)) => return Err(GeolocationError::NotFound),
+            Err(ureq::Error::Status(status, _)) => return Err(GeolocationError::Http(status)),
+            Err(error) => return Err(GeolocationError::Transport(error.to_string())),
+        };
+        let text = response
+            .into_string()
+            .map_err(|error| GeolocationError::Transport(error.to_string()))?;
+        parse_response(&text)
+    }
+}
+
+/// Whether the access point may be submitted to a location service.
+fn submittable(network: &NetworkInfo) -> bool {
+    network.bssid.len() == 17
+        && !network.ssid.is_empty()
+        && network.ssid != "[Hidden Network]"
+        && !network.ssid.ends_with("_nomap")
+}
+
+fn request_body(networks: &[NetworkInfo], age: Duration) -> Result<Value, GeolocationError> {
+    let mut access_points: Vec<Value> = Vec::new();
+    for network in networks.iter().filter(|n| submittable(n)) {
+        let mac = network.bssid.to_ascii_lowercase();
+        if access_points.iter().any(|ap| ap["macAddress"] == mac.as_str()) {
+            continue;
+        }
+        let mut access_point = json!({
+            "macAddress": mac,
+            "signalStrength": network.signal_strength,
+            "frequency": network.frequency,
+        });
+        if !age.is_zero() {
+            // Milliseconds since the access point was seen
+            access_point["age"] = json!(age.as_millis() as u64);
+        }
+        access_points.push(access_point);
+    }
+    if access_points.len() < MIN_ACCESS_POINTS {
+        return Err(GeolocationError::TooFewAccessPoints);
+    }
+    Ok(json!({
+        "considerIp": false,
+        "wifiAccessPoints": access_points,
+    }))
+}
+
+fn parse_response(text: &str) -> Result<Location, GeolocationError> {
+    let value: Value = serde_json::from_str(text).map_err(|_| GeolocationError::InvalidResponse)?;
+    let location = &value["location"];
+    match (location["lat"].as_f64(), location["lng"].as_f64(), value["accuracy"].as_f64()) {
+        (Some(latitude), Some(longitude), Some(accuracy_m)) => Ok(Location { latitude, longitude, accuracy_m }),
+        _ => Err(GeolocationError::InvalidResponse),
+    }
+}
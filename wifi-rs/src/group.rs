@@ -0,0 +1,58 @@
+//! Scan results grouped into one entry per network.
+//!
+//! Scans list every access point separately, so an office or mesh network
+//! appears once per BSSID. [`NetworkGroup::from_scan`] folds those entries
+//! into one row per network for display.
+
+use crate::{NetworkInfo, SecurityType};
+
+/// The access points of one network, created by
+/// [`WiFi::scan_grouped`](crate::WiFi::scan_grouped).
+///
+/// Access points belong to the same network when they advertise the same
+/// SSID with the same security, so an open network impersonating a
+/// secured one is a separate group.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NetworkGroup {
+    pub ssid: String,
+    pub security: SecurityType,
+    /// BSSID of the strongest access point, the one a connect would most
+    /// likely join.
+    pub best_bssid: String,
+    /// The access points, strongest first.
+    pub aps: Vec<NetworkInfo>,
+}
+
+impl NetworkGroup {
+    /// Group scan results by network, strongest network first.
+    pub fn from_scan(networks: Vec<NetworkInfo>) -> Vec<NetworkGroup> {
+        let mut groups: Vec<NetworkGroup> = Vec::new();
+        for network in networks {
+            match groups
+                .iter_mut()
+                .find(|group| group.ssid == network.ssid && group.security == network.security_type)
+            {
+                Some(group) => group.aps.push(network),
+                None => groups.push(NetworkGroup {
+                    ssid: network.ssid.clone(),
+                    security: network.security_type,
+                    best_bssid: String::new(),
+                    aps: vec![network],
+                }),
+            }
+        }
+
+        for group in &mut groups {
+            group.aps.sort_by_key(|ap| std::cmp::Reverse(ap.signal_strength));
+            group.best_bssid = group.aps[0].bssid.clone();
+        }
+        groups.sort_by_key(|group| std::cmp::Reverse(group.signal_strength()));
+        groups
+    }
+
+    /// Signal strength of the strongest access point.
+    pub fn signal_strength(&self) -> i32 {
+        self.aps.first().map_or(i32::MIN, |ap| ap.signal_strength)
+    }
+}
@@ -0,0 +1,199 @@
+//! Site survey data collection for coverage heatmaps.
+//!
+//! The caller supplies where the device is, from a grid cell, GPS fix or a
+//! manual "I am here" mark, and each recorded point pairs that position with
+//! a scan and the state of the current link.
+
+use std::fmt::Write as _;
+use std::time::SystemTime;
+
+use crate::signal_log::csv_field;
+use crate::{NetworkInfo, WiFi};
+
+/// Where a survey point was taken.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Position {
+    /// Cell of a floor plan grid.
+    Grid { x: i32, y: i32 },
+    /// GPS or other geographic fix.
+    Geo { latitude: f64, longitude: f64, accuracy_m: Option<f64> },
+    /// A named spot marked by hand, e.g. "conference room B".
+    Mark(String),
+}
+
+impl Position {
+    fn csv_fields(&self) -> String {
+        match self {
+            Position::Grid { x, y } => format!("grid,{},{},", x, y),
+            Position::Geo { latitude, longitude, accuracy_m } => format!(
+                "geo,{},{},{}",
+                latitude,
+                longitude,
+                accuracy_m.map(|a| a.to_string()).unwrap_or_default()
+            ),
+            Position::Mark(label) => format!("mark,{},,", csv_field(label)),
+        }
+    }
+}
+
+/// Supplies the current position while surveying.
+///
+/// Implemented for closures returning `Option<Position>`; return `None`
+/// while no position is known and the point is skipped.
+pub trait PositionProvider {
+    fn position(&mut self) -> Option<Position>;
+}
+
+impl<F: FnMut() -> Option<Position>> PositionProvider for F {
+    fn position(&mut self) -> Option<Position> {
+        self()
+    }
+}
+
+/// The current association when a point was recorded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkReading {
+    pub ssid: String,
+    pub bssid: String,
+    /// Frequency in MHz.
+    pub frequency: i32,
+    /// Signal strength in dBm.
+    pub signal_strength: i32,
+}
+
+/// Scan and link readings taken at one position.
+#[derive(Debug, Clone)]
+pub struct SurveyPoint {
+    pub position: Position,
+    pub timestamp: SystemTime,
+    /// `None` while not connected.
+    pub link: Option<LinkReading>,
+    pub networks: Vec<NetworkInfo>,
+}
+
+/// Collects survey points from a [`WiFi`] and a [`PositionProvider`].
+///
+/// ```no_run
+/// use wifi_rs::{HeatmapSurvey, Position, WiFi};
+///
+/// let wifi = WiFi::new();
+/// let mut survey = HeatmapSurvey::new(|| Some(Position::Mark("lobby".into())));
+/// survey.record(&wifi);
+/// std::fs::write("survey.csv", survey.to_csv()).unwrap();
+/// ```
+pub struct HeatmapSurvey<P> {
+    provider: P,
+    points: Vec<SurveyPoint>,
+}
+
+impl<P: PositionProvider> HeatmapSurvey<P> {
+    pub fn new(provider: P) -> Self {
+        HeatmapSurvey { provider, points: Vec::new() }
+    }
+
+    /// Record a point at the provider's current position.
+    ///
+    /// # Returns
+    ///
+    /// The recorded point, or `None` if the provider has no position.
+    pub fn record(&mut self, wifi: &WiFi) -> Option<&SurveyPoint> {
+        let position = self.provider.position()?;
+        Some(self.record_at(wifi, position))
+    }
+
+    /// Record a point at an explicit position, bypassing the provider.
+    pub fn record_at(&mut self, wifi: &WiFi, position: Position) -> &SurveyPoint {
+        let networks = wifi.scan();
+        let link = wifi.sample_connection().map(|sample| LinkReading {
+            ssid: sample.ssid,
+            bssid: sample.bssid,
+            frequency: sample.frequency,
+            signal_strength: sample.signal_strength,
+        });
+        self.points.push(SurveyPoint {
+            position,
+            timestamp: SystemTime::now(),
+            link,
+            networks,
+        });
+        self.points.last().expect("point was just recorded")
+    }
+
+    /// All recorded points in order.
+    pub fn points(&self) -> &[SurveyPoint] {
+        &self.points
+    }
+
+    /// Signal strength of `bssid` at every point where it was seen.
+    pub fn signal_of(&self, bssid: &str) -> Vec<(&Position, i32)> {
+        self.points
+            .iter()
+            .filter_map(|point| {
+                point
+                    .networks
+                    .iter()
+                    .find(|n| n.bssid.eq_ignore_ascii_case(bssid))
+                    .map(|n| (&point.position, n.signal_strength))
+            })
+            .collect()
+    }
+
+    /// Strongest signal of any access point of `ssid` at every point.
+    pub fn best_signal_of(&self, ssid: &str) -> Vec<(&Position, Option<i32>)> {
+        self.points
+            .iter()
+            .map(|point| {
+                let best = point
+                    .networks
+                    .iter()
+                    .filter(|n| n.ssid == ssid)
+                    .map(|n| n.signal_strength)
+                    .max();
+                (&point.position, best)
+            })
+            .collect()
+    }
+
+    /// Export one row per network per point, plus a row for the link.
+    ///
+    /// Columns: `timestamp,kind,a,b,c,source,ssid,bssid,channel,signal_dbm`
+    /// where `kind,a,b,c` describe the position (`grid,x,y,`,
+    /// `geo,lat,lon,accuracy` or `mark,label,,`) and `source` is `link` for
+    /// the current association or `scan` for scan results.
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from("timestamp,kind,a,b,c,source,ssid,bssid,channel,signal_dbm\n");
+        for point in &self.points {
+            let timestamp = point
+                .timestamp
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            let position = point.position.csv_fields();
+            if let Some(link) = &point.link {
+                let _ = writeln!(
+                    out,
+                    "{},{},link,{},{},{},{}",
+                    timestamp,
+                    position,
+                    csv_field(&link.ssid),
+                    link.bssid,
+                    crate::channel::frequency_to_channel(link.frequency),
+                    link.signal_strength
+                );
+            }
+            for network in &point.networks {
+                let _ = writeln!(
+                    out,
+                    "{},{},scan,{},{},{},{}",
+                    timestamp,
+                    position,
+                    csv_field(&network.ssid),
+                    network.bssid,
+                    network.channel,
+                    network.signal_strength
+                );
+            }
+        }
+        out
+    }
+}
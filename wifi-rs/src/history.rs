@@ -0,0 +1,235 @@
+//! Scan and link history persisted in an embedded SQLite database.
+//!
+//! Enabled with the `history` feature. Each scan is stored as a snapshot of
+//! the visible networks; link samples record the current association. The
+//! query helpers cover the common questions (what was seen recently, how did
+//! an access point's signal develop) so analysis tools don't need their own
+//! storage.
+
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+use rusqlite::{params, Connection};
+
+use crate::{NetworkInfo, WiFi};
+
+pub use rusqlite::Error;
+
+/// Result type of history operations.
+pub type Result<T> = std::result::Result<T, Error>;
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS scans (
+        id INTEGER PRIMARY KEY,
+        taken_at INTEGER NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS observations (
+        scan_id INTEGER NOT NULL REFERENCES scans(id) ON DELETE CASCADE,
+        ssid TEXT NOT NULL,
+        bssid TEXT NOT NULL,
+        channel INTEGER NOT NULL,
+        frequency INTEGER NOT NULL,
+        signal INTEGER NOT NULL,
+        security TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS observations_bssid ON observations(bssid);
+    CREATE TABLE IF NOT EXISTS link_samples (
+        taken_at INTEGER NOT NULL,
+        ssid TEXT NOT NULL,
+        bssid TEXT NOT NULL,
+        frequency INTEGER NOT NULL,
+        signal INTEGER NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS link_samples_bssid ON link_samples(bssid);
+";
+
+fn to_millis(time: SystemTime) -> i64 {
+    time.duration_since(SystemTime::UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis() as i64)
+}
+
+fn from_millis(millis: i64) -> SystemTime {
+    SystemTime::UNIX_EPOCH + Duration::from_millis(millis.max(0) as u64)
+}
+
+/// A network seen in one or more stored scans.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeenNetwork {
+    pub ssid: String,
+    pub bssid: String,
+    pub channel: i32,
+    pub first_seen: SystemTime,
+    pub last_seen: SystemTime,
+    /// Number of scans the network appeared in.
+    pub sightings: u32,
+    /// Strongest signal in dBm across those scans.
+    pub best_signal: i32,
+}
+
+/// Where a signal reading came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SampleSource {
+    Scan,
+    Link,
+}
+
+/// One signal reading of an access point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RssiPoint {
+    pub timestamp: SystemTime,
+    /// Signal strength in dBm.
+    pub signal: i32,
+    pub source: SampleSource,
+}
+
+/// SQLite-backed store of scan snapshots and link samples.
+pub struct ScanHistory {
+    conn: Connection,
+}
+
+impl ScanHistory {
+    /// Open or create the database at `path`.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Self::init(Connection::open(path)?)
+    }
+
+    /// Open a database that lives only in memory.
+    pub fn open_in_memory() -> Result<Self> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(conn: Connection) -> Result<Self> {
+        conn.execute_batch("PRAGMA foreign_keys = ON;")?;
+        conn.execute_batch(SCHEMA)?;
+        Ok(ScanHistory { conn })
+    }
+
+    /// Store a scan snapshot taken now.
+    ///
+    /// # Returns
+    ///
+    /// The id of the stored scan.
+    pub fn record_scan(&mut self, networks: &[NetworkInfo]) -> Result<i64> {
+        self.record_scan_at(networks, SystemTime::now())
+    }
+
+    /// Store a scan snapshot taken at `taken_at`.
+    pub fn record_scan_at(&mut self, networks: &[NetworkInfo], taken_at: SystemTime) -> Result<i64> {
+        let tx = self.conn.transaction()?;
+        tx.execute("INSERT INTO scans (taken_at) VALUES (?1)", params![to_millis(taken_at)])?;
+        let scan_id = tx.last_insert_rowid();
+        {
+            let mut insert = tx.prepare(
+                "INSERT INTO observations (scan_id, ssid, bssid, channel, frequency, signal, security)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            )?;
+            for network in networks {
+                insert.execute(params![
+                    scan_id,
+                    network.ssid,
+                    network.bssid.to_ascii_lowercase(),
+                    network.channel,
+                    network.frequency,
+                    network.signal_strength,
+                    format!("{:?}", network.security_type),
+                ])?;
+            }
+        }
+        tx.commit()?;
+        Ok(scan_id)
+    }
+
+    /// Scan with `wifi` and store the result.
+    pub fn scan_and_record(&mut self, wifi: &WiFi) -> Result<Vec<NetworkInfo>> {
+        let networks = wifi.scan();
+        self.record_scan(&networks)?;
+        Ok(networks)
+    }
+
+    /// Store a sample of the current association of `wifi`.
+    ///
+    /// # Returns
+    ///
+    /// `false` if not connected, in which case nothing is stored.
+    pub fn record_link(&mut self, wifi: &WiFi) -> Result<bool> {
+        let sample = match wifi.sample_connection() {
+            Some(sample) => sample,
+            None => return Ok(false),
+        };
+        self.conn.execute(
+            "INSERT INTO link_samples (taken_at, ssid, bssid, frequency, signal) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                to_millis(sample.taken),
+                sample.ssid,
+                sample.bssid.to_ascii_lowercase(),
+                sample.frequency,
+                sample.signal_strength,
+            ],
+        )?;
+        Ok(true)
+    }
+
+    /// Networks that appeared in scans since `since`, most recently seen first.
+    pub fn networks_seen_since(&self, since: SystemTime) -> Result<Vec<SeenNetwork>> {
+        let mut query = self.conn.prepare(
+            "SELECT o.ssid, o.bssid, MAX(o.channel), MIN(s.taken_at), MAX(s.taken_at), COUNT(*), MAX(o.signal)
+             FROM observations o JOIN scans s ON s.id = o.scan_id
+             WHERE s.taken_at >= ?1
+             GROUP BY o.bssid, o.ssid
+             ORDER BY MAX(s.taken_at) DESC",
+        )?;
+        let rows = query.query_map(params![to_millis(since)], |row| {
+            Ok(SeenNetwork {
+                ssid: row.get(0)?,
+                bssid: row.get(1)?,
+                channel: row.get(2)?,
+                first_seen: from_millis(row.get(3)?),
+                last_seen: from_millis(row.get(4)?),
+                sightings: row.get(5)?,
+                best_signal: row.get(6)?,
+            })
+        })?;
+        rows.collect()
+    }
+
+    /// Networks that appeared in scans during the last week.
+    pub fn networks_seen_last_week(&self) -> Result<Vec<SeenNetwork>> {
+        let week = Duration::from_secs(7 * 24 * 60 * 60);
+        self.networks_seen_since(SystemTime::now() - week)
+    }
+
+    /// Signal readings of `bssid` since `since` from scans and link
+    /// samples, oldest first.
+    pub fn rssi_trend(&self, bssid: &str, since: SystemTime) -> Result<Vec<RssiPoint>> {
+        let mut query = self.conn.prepare(
+            "SELECT s.taken_at, o.signal, 0 FROM observations o JOIN scans s ON s.id = o.scan_id
+             WHERE o.bssid = ?1 AND s.taken_at >= ?2
+             UNION ALL
+             SELECT taken_at, signal, 1 FROM link_samples
+             WHERE bssid = ?1 AND taken_at >= ?2
+             ORDER BY 1",
+        )?;
+        let rows = query.query_map(params![bssid.to_ascii_lowercase(), to_millis(since)], |row| {
+            Ok(RssiPoint {
+                timestamp: from_millis(row.get(0)?),
+                signal: row.get(1)?,
+                source: if row.get::<_, i32>(2)? == 0 { SampleSource::Scan } else { SampleSource::Link },
+            })
+        })?;
+        rows.collect()
+    }
+
+    /// Delete scans and link samples older than `before`.
+    ///
+    /// # Returns
+    ///
+    /// The number of scans and link samples removed.
+    pub fn prune_before(&mut self, before: SystemTime) -> Result<usize> {
+        let cutoff = to_millis(before);
+        let tx = self.conn.transaction()?;
+        let scans = tx.execute("DELETE FROM scans WHERE taken_at < ?1", params![cutoff])?;
+        let samples = tx.execute("DELETE FROM link_samples WHERE taken_at < ?1", params![cutoff])?;
+        tx.commit()?;
+        Ok(scans + samples)
+    }
+}
@@ -0,0 +1,176 @@
+//! Home Assistant MQTT discovery integration.
+//!
+//! Announces the device's WiFi signal, SSID, connection and hotspot state as
+//! Home Assistant entities using the MQTT discovery convention
+//! (`<discovery_prefix>/<component>/<node_id>/<object_id>/config`).
+//!
+//! The crate does not ship an MQTT client; implement [`MqttPublisher`] for the
+//! client your application already uses.
+
+use crate::{ConnectionStatus, WiFi};
+use serde_json::{json, Value};
+
+/// Minimal publishing interface required by the discovery integration.
+pub trait MqttPublisher {
+    type Error;
+
+    /// Publish `payload` to `topic`, optionally as a retained message.
+    fn publish(&mut self, topic: &str, payload: &str, retain: bool) -> Result<(), Self::Error>;
+}
+
+/// A single MQTT message produced by the integration.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MqttMessage {
+    pub topic: String,
+    pub payload: String,
+    pub retain: bool,
+}
+
+/// Snapshot of the values reported to Home Assistant.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WifiState {
+    pub ssid: Option<String>,
+    pub signal_strength: Option<i32>,
+    pub connected: bool,
+    pub hotspot_active: bool,
+}
+
+impl WifiState {
+    /// Collect the current state from a `WiFi` instance, with the SSID and
+    /// signal strength in dBm of the access point it is connected to.
+    pub fn from_wifi(wifi: &WiFi) -> Self {
+        let connection = wifi.current_connection();
+        WifiState {
+            ssid: connection.as_ref().map(|link| link.ssid.clone()),
+            signal_strength: connection.as_ref().map(|link| link.signal_dbm),
+            connected: wifi.get_status() == ConnectionStatus::Connected,
+            hotspot_active: wifi.is_hotspot_active(),
+        }
+    }
+}
+
+/// Home Assistant discovery announcer for one device.
+#[derive(Debug, Clone)]
+pub struct HomeAssistant {
+    discovery_prefix: String,
+    node_id: String,
+    device_name: String,
+}
+
+impl HomeAssistant {
+    /// Create an announcer for the device identified by `node_id`.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - Unique, topic-safe identifier of the device (e.g. `kitchen_sensor`)
+    /// * `device_name` - Human readable device name shown in Home Assistant
+    pub fn new(node_id: &str, device_name: &str) -> Self {
+        HomeAssistant {
+            discovery_prefix: "homeassistant".to_string(),
+            node_id: node_id.to_string(),
+            device_name: device_name.to_string(),
+        }
+    }
+
+    /// Override the discovery prefix (defaults to `homeassistant`).
+    pub fn discovery_prefix(mut self, prefix: &str) -> Self {
+        self.discovery_prefix = prefix.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Topic the JSON state document is published to.
+    pub fn state_topic(&self) -> String {
+        format!("wifi-rs/{}/state", self.node_id)
+    }
+
+    /// Build the retained discovery configuration messages for all entities.
+    pub fn discovery_messages(&self) -> Vec<MqttMessage> {
+        let device = json!({
+            "identifiers": [format!("wifi_rs_{}", self.node_id)],
+            "name": self.device_name,
+            "sw_version": env!("CARGO_PKG_VERSION"),
+        });
+
+        let entities = [
+            ("sensor", "wifi_signal", "WiFi Signal", json!({
+                "device_class": "signal_strength",
+                "unit_of_measurement": "dBm",
+                "state_class": "measurement",
+                "value_template": "{{ value_json.signal_strength }}",
+            })),
+            ("sensor", "wifi_ssid", "WiFi SSID", json!({
+                "icon": "mdi:wifi",
+                "value_template": "{{ value_json.ssid }}",
+            })),
+            ("binary_sensor", "wifi_connected", "WiFi Connected", json!({
+                "device_class": "connectivity",
+                "payload_on": "ON",
+                "payload_off": "OFF",
+                "value_template": "{{ value_json.connected }}",
+            })),
+            ("binary_sensor", "wifi_hotspot", "WiFi Hotspot", json!({
+                "icon": "mdi:access-point",
+                "payload_on": "ON",
+                "payload_off": "OFF",
+                "value_template": "{{ value_json.hotspot }}",
+            })),
+        ];
+
+        entities
+            .into_iter()
+            .map(|(component, object_id, name, mut config)| {
+                let unique_id = format!("{}_{}", self.node_id, object_id);
+                if let Value::Object(map) = &mut config {
+                    map.insert("name".into(), json!(name));
+                    map.insert("unique_id".into(), json!(unique_id));
+                    map.insert("object_id".into(), json!(unique_id));
+                    map.insert("state_topic".into(), json!(self.state_topic()));
+                    map.insert("device".into(), device.clone());
+                }
+                MqttMessage {
+                    topic: format!("{}/{}/{}/{}/config", self.discovery_prefix, component, self.node_id, object_id),
+                    payload: config.to_string(),
+                    retain: true,
+                }
+            })
+            .collect()
+    }
+
+    /// Build the state message for `state`.
+    pub fn state_message(&self, state: &WifiState) -> MqttMessage {
+        let on_off = |v: bool| if v { "ON" } else { "OFF" };
+        let payload = json!({
+            "ssid": state.ssid,
+            "signal_strength": state.signal_strength,
+            "connected": on_off(state.connected),
+            "hotspot": on_off(state.hotspot_active),
+        });
+        MqttMessage {
+            topic: self.state_topic(),
+            payload: payload.to_string(),
+            retain: false,
+        }
+    }
+
+    /// Publish the discovery configuration so Home Assistant creates the entities.
+    pub fn announce<P: MqttPublisher>(&self, publisher: &mut P) -> Result<(), P::Error> {
+        for message in self.discovery_messages() {
+            publisher.publish(&message.topic, &message.payload, message.retain)?;
+        }
+        Ok(())
+    }
+
+    /// Remove the entities from Home Assistant by publishing empty retained configs.
+    pub fn withdraw<P: MqttPublisher>(&self, publisher: &mut P) -> Result<(), P::Error> {
+        for message in self.discovery_messages() {
+            publisher.publish(&message.topic, "", true)?;
+        }
+        Ok(())
+    }
+
+    /// Read the current state from `wifi` and publish it.
+    pub fn publish_state<P: MqttPublisher>(&self, wifi: &WiFi, publisher: &mut P) -> Result<(), P::Error> {
+        let message = self.state_message(&WifiState::from_wifi(wifi));
+        publisher.publish(&message.topic, &message.payload, message.retain)
+    }
+}
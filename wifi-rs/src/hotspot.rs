@@ -0,0 +1,221 @@
+//! Hotspot settings and clients.
+
+use std::net::Ipv4Addr;
+use std::ops::RangeInclusive;
+use std::time::{Duration, SystemTime};
+
+use crate::channel::Band;
+use crate::diag;
+use crate::secret::SecretString;
+
+/// Channel selection for a hotspot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HotspotChannel {
+    /// Let the platform choose.
+    Default,
+    /// Pick the least congested channel of the configured band with
+    /// [`WiFi::recommend_channel`](crate::WiFi::recommend_channel).
+    Auto,
+    /// A specific channel.
+    Fixed(u32),
+}
+
+/// Settings for [`WiFi::create_hotspot_with`](crate::WiFi::create_hotspot_with).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HotspotConfig {
+    pub(crate) ssid: String,
+    pub(crate) password: Option<SecretString>,
+    pub(crate) band: Band,
+    pub(crate) channel: HotspotChannel,
+    pub(crate) dhcp: Option<HotspotDhcp>,
+    pub(crate) share_from: Option<String>,
+    pub(crate) captive_portal: bool,
+    pub(crate) timeout: Option<Duration>,
+}
+
+/// Addressing handed out to hotspot clients.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) struct HotspotDhcp {
+    pub(crate) range: RangeInclusive<Ipv4Addr>,
+    pub(crate) gateway: Ipv4Addr,
+    pub(crate) dns: Vec<Ipv4Addr>,
+}
+
+impl HotspotDhcp {
+    /// Length of the subnet prefix: 24, or shorter if the gateway and the
+    /// range do not fit a /24. `None` if the gateway lies inside the range
+    /// or an address is the network or broadcast address.
+    pub(crate) fn prefix_length(&self) -> Option<u32> {
+        let gateway = u32::from(self.gateway);
+        let (start, end) = (u32::from(*self.range.start()), u32::from(*self.range.end()));
+        if start > end || (start..=end).contains(&gateway) {
+            return None;
+        }
+        let differing = (gateway ^ start) | (gateway ^ end);
+        let prefix = differing.leading_zeros().min(24);
+        // Neither the network nor the broadcast address may be handed out
+        let host_mask = u32::MAX >> prefix;
+        let usable = |address: u32| address & host_mask != 0 && address & host_mask != host_mask;
+        (prefix >= 8 && usable(gateway) && usable(start) && usable(end)).then_some(prefix)
+    }
+}
+
+impl HotspotConfig {
+    /// An open 2.4 GHz hotspot named `ssid` on the platform's default channel.
+    pub fn new(ssid: &str) -> Self {
+        HotspotConfig {
+            ssid: ssid.to_string(),
+            password: None,
+            band: Band::Ghz2,
+            channel: HotspotChannel::Default,
+            dhcp: None,
+            share_from: None,
+            captive_portal: false,
+            timeout: None,
+        }
+    }
+
+    /// Secure the hotspot with WPA2-Personal.
+    pub fn password(mut self, password: impl Into<SecretString>) -> Self {
+        self.password = Some(password.into());
+        self
+    }
+
+    /// Band used when the channel is chosen automatically.
+    pub fn band(mut self, band: Band) -> Self {
+        self.band = band;
+        self
+    }
+
+    /// Channel selection.
+    pub fn channel(mut self, channel: HotspotChannel) -> Self {
+        self.channel = channel;
+        self
+    }
+
+    /// Addresses the hotspot hands out to clients over DHCP.
+    ///
+    /// Without this Linux serves 192.168.4.2 to 192.168.4.20 with the
+    /// hotspot at 192.168.4.1. The subnet is a /24, or larger if the
+    /// gateway and the range do not fit in one.
+    ///
+    /// # Arguments
+    ///
+    /// * `range` - First and last client address
+    /// * `gateway` - Address of the hotspot itself, outside `range`
+    /// * `dns` - DNS servers announced to clients, empty for the hotspot's
+    ///   own forwarder
+    ///
+    /// ```no_run
+    /// use std::net::Ipv4Addr;
+    /// use wifi_rs::{HotspotConfig, WiFi};
+    ///
+    /// let config = HotspotConfig::new("Workshop").password("secret123").dhcp(
+    ///     Ipv4Addr::new(10, 42, 0, 10)..=Ipv4Addr::new(10, 42, 0, 99),
+    ///     Ipv4Addr::new(10, 42, 0, 1),
+    ///     &[Ipv4Addr::new(1, 1, 1, 1)],
+    /// );
+    /// WiFi::new().create_hotspot_with(&config)?;
+    /// # Ok::<(), wifi_rs::WifiError>(())
+    /// ```
+    pub fn dhcp(mut self, range: RangeInclusive<Ipv4Addr>, gateway: Ipv4Addr, dns: &[Ipv4Addr]) -> Self {
+        self.dhcp = Some(HotspotDhcp { range, gateway, dns: dns.to_vec() });
+        self
+    }
+
+    /// Give clients internet access through the connection of `interface`,
+    /// e.g. `"eth0"`, by masquerading their traffic (NAT). The rules are
+    /// removed again by [`WiFi::stop_hotspot`](crate::WiFi::stop_hotspot).
+    ///
+    /// Without this the hotspot only reaches the device itself, which is
+    /// enough for provisioning. Supported on Linux, with iptables or
+    /// nftables.
+    ///
+    /// ```no_run
+    /// use wifi_rs::{HotspotConfig, WiFi};
+    ///
+    /// WiFi::new().create_hotspot_with(&HotspotConfig::new("Workshop").password("secret123").share_from("eth0"))?;
+    /// # Ok::<(), wifi_rs::WifiError>(())
+    /// ```
+    pub fn share_from(mut self, interface: &str) -> Self {
+        self.share_from = Some(interface.to_string());
+        self
+    }
+
+    /// Answer every DNS query of the clients with the hotspot's own
+    /// address, so whatever they open reaches a web server running on the
+    /// device and phones show it as a captive portal login page. DNS
+    /// servers set with [`dhcp`](Self::dhcp) are not announced. Supported
+    /// on Linux.
+    ///
+    /// [`ProvisioningPortal`](crate::ProvisioningPortal) sets this and
+    /// serves the page.
+    pub fn captive_portal(mut self) -> Self {
+        self.captive_portal = true;
+        self
+    }
+
+    /// Give up if the access point has not started within `timeout`,
+    /// instead of after the hotspot timeout of
+    /// [`Timeouts`](crate::Timeouts). Supported on Linux.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+}
+
+#[repr(C)]
+pub(crate) struct RawHotspotConfig {
+    pub(crate) ssid: *const libc::c_char,
+    pub(crate) password: *const libc::c_char,
+    pub(crate) channel: libc::c_int,
+    pub(crate) dhcp_range_start: *const libc::c_char,
+    pub(crate) dhcp_range_end: *const libc::c_char,
+    pub(crate) gateway: *const libc::c_char,
+    pub(crate) prefix_length: i32,
+    pub(crate) dns_servers: *const libc::c_char,
+}
+
+#[repr(C)]
+pub(crate) struct RawHotspotClient {
+    mac_address: [libc::c_char; 18],
+    ip_address: [libc::c_char; 46],
+    hostname: [libc::c_char; 64],
+    signal_strength: i32,
+    connected_seconds: i32,
+}
+
+/// A device associated with the hotspot, from
+/// [`WiFi::hotspot_clients`](crate::WiFi::hotspot_clients).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HotspotClient {
+    pub mac: String,
+    /// Address handed out by the hotspot's DHCP server, `None` until the
+    /// client requested one.
+    pub ip: Option<String>,
+    /// Name the client sent with its DHCP request, if any.
+    pub hostname: Option<String>,
+    /// Signal strength of the client in dBm, if reported.
+    pub rssi: Option<i32>,
+    /// When the client associated, if reported.
+    pub connected_since: Option<SystemTime>,
+}
+
+impl HotspotClient {
+    pub(crate) fn from_raw(raw: &RawHotspotClient, now: SystemTime) -> Self {
+        let non_empty = |buffer: &[libc::c_char]| Some(diag::text(buffer)).filter(|value| !value.is_empty());
+        HotspotClient {
+            mac: diag::text(&raw.mac_address),
+            ip: non_empty(&raw.ip_address),
+            hostname: non_empty(&raw.hostname),
+            rssi: (raw.signal_strength != 0).then_some(raw.signal_strength),
+            connected_since: u64::try_from(raw.connected_seconds)
+                .ok()
+                .and_then(|seconds| now.checked_sub(Duration::from_secs(seconds))),
+        }
+    }
+}
@@ -0,0 +1,301 @@
+//! End-to-end test harness on virtual radios (Linux).
+//!
+//! Enabled with the `hwsim` feature. A [`HwsimLab`] loads the kernel's
+//! `mac80211_hwsim` module, which creates simulated radios that hear each
+//! other as if they were in the same room, and can run a hostapd access
+//! point on one of them. [`WiFi`] instances opened on the other radios then
+//! exercise the real scan, connect and hotspot paths without any hardware,
+//! e.g. in a privileged CI container.
+//!
+//! Needs root, the `mac80211_hwsim` module, and `hostapd`, `dnsmasq`,
+//! `wpa_supplicant` and `iw` on the `PATH`. Run the harness's own tests
+//! with `cargo test --features hwsim`; they are skipped when the
+//! environment lacks any of these.
+//!
+//! ```no_run
+//! use wifi_rs::hwsim::{HwsimAp, HwsimLab};
+//! use wifi_rs::ConnectOptions;
+//!
+//! let lab = HwsimLab::start(2)?;
+//! lab.start_ap(0, &HwsimAp::new("lab-net").password("labsecret"))?;
+//! let wifi = lab.wifi(1);
+//! assert!(wifi.connect_with("lab-net", &ConnectOptions::password("labsecret")).is_ok());
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::WiFi;
+
+/// Environment variable the native layer reads to pick its interface.
+const INTERFACE_VAR: &str = "WIFICPP_INTERFACE";
+
+/// Only one lab can exist at a time because the module is global.
+static LAB: Mutex<()> = Mutex::new(());
+
+fn run(program: &str, args: &[&str]) -> io::Result<()> {
+    let status = Command::new(program)
+        .args(args)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!("{} {} failed with {}", program, args.join(" "), status)))
+    }
+}
+
+fn on_path(program: &str) -> bool {
+    std::env::var_os("PATH")
+        .is_some_and(|path| std::env::split_paths(&path).any(|dir| dir.join(program).is_file()))
+}
+
+/// Network interfaces backed by `mac80211_hwsim`, in name order.
+fn hwsim_interfaces() -> Vec<String> {
+    let mut interfaces: Vec<String> = fs::read_dir("/sys/class/net")
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| {
+            fs::read_link(entry.path().join("device"))
+                .is_ok_and(|device| device.to_string_lossy().contains("mac80211_hwsim"))
+        })
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .collect();
+    interfaces.sort();
+    interfaces
+}
+
+/// Access point run by hostapd on a virtual radio.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HwsimAp {
+    ssid: String,
+    password: Option<String>,
+    channel: u32,
+}
+
+impl HwsimAp {
+    /// An open access point named `ssid` on channel 6.
+    pub fn new(ssid: &str) -> Self {
+        HwsimAp { ssid: ssid.to_string(), password: None, channel: 6 }
+    }
+
+    /// Secure the access point with WPA2-Personal.
+    pub fn password(mut self, password: &str) -> Self {
+        self.password = Some(password.to_string());
+        self
+    }
+
+    pub fn channel(mut self, channel: u32) -> Self {
+        self.channel = channel;
+        self
+    }
+
+    fn hostapd_config(&self, interface: &str) -> String {
+        let mut config = format!(
+            "interface={}\ndriver=nl80211\nssid={}\nhw_mode={}\nchannel={}\nieee80211n=1\n",
+            interface,
+            self.ssid,
+            if self.channel > 14 { "a" } else { "g" },
+            self.channel
+        );
+        if let Some(password) = &self.password {
+            config.push_str(&format!(
+                "wpa=2\nwpa_passphrase={}\nwpa_key_mgmt=WPA-PSK\nrsn_pairwise=CCMP\n",
+                password
+            ));
+        }
+        config
+    }
+}
+
+/// Loaded `mac80211_hwsim` radios and the daemons started on them.
+///
+/// Dropping the lab stops the daemons and unloads the module.
+pub struct HwsimLab {
+    interfaces: Vec<String>,
+    dir: PathBuf,
+    pid_files: Mutex<Vec<PathBuf>>,
+    _guard: std::sync::MutexGuard<'static, ()>,
+}
+
+impl HwsimLab {
+    /// Whether the environment can run a lab: root, the kernel module and
+    /// the required tools.
+    pub fn available() -> bool {
+        // SAFETY: geteuid has no preconditions.
+        let root = unsafe { libc::geteuid() } == 0;
+        root && ["hostapd", "dnsmasq", "wpa_supplicant", "iw", "modprobe"].iter().all(|p| on_path(p))
+            && run("modprobe", &["-n", "mac80211_hwsim"]).is_ok()
+    }
+
+    /// Load `mac80211_hwsim` with `radios` virtual radios.
+    ///
+    /// Blocks while another lab in the process is alive.
+    ///
+    /// # Errors
+    ///
+    /// If the module cannot be loaded or its interfaces do not appear.
+    pub fn start(radios: usize) -> io::Result<Self> {
+        let guard = LAB.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        // Start from a clean module so interface names are predictable
+        let _ = run("modprobe", &["-r", "mac80211_hwsim"]);
+        run("modprobe", &["mac80211_hwsim", &format!("radios={}", radios)])?;
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let interfaces = loop {
+            let interfaces = hwsim_interfaces();
+            if interfaces.len() >= radios {
+                break interfaces;
+            }
+            if Instant::now() >= deadline {
+                let _ = run("modprobe", &["-r", "mac80211_hwsim"]);
+                return Err(io::Error::new(io::ErrorKind::TimedOut, "hwsim interfaces did not appear"));
+            }
+            std::thread::sleep(Duration::from_millis(100));
+        };
+
+        let dir = std::env::temp_dir().join(format!("wifi-rs-hwsim-{}", std::process::id()));
+        fs::create_dir_all(&dir)?;
+        Ok(HwsimLab { interfaces, dir, pid_files: Mutex::new(Vec::new()), _guard: guard })
+    }
+
+    /// Names of the virtual radios' interfaces.
+    pub fn interfaces(&self) -> &[String] {
+        &self.interfaces
+    }
+
+    /// Run `ap` on radio `radio` with a DHCP server handing out
+    /// `10.77.<radio>.0/24` addresses.
+    ///
+    /// # Panics
+    ///
+    /// If `radio` is not a radio of the lab.
+    pub fn start_ap(&self, radio: usize, ap: &HwsimAp) -> io::Result<()> {
+        let interface = &self.interfaces[radio];
+        let config = self.dir.join(format!("hostapd-{}.conf", interface));
+        fs::write(&config, ap.hostapd_config(interface))?;
+
+        let hostapd_pid = self.dir.join(format!("hostapd-{}.pid", interface));
+        run("hostapd", &["-B", "-P", &hostapd_pid.to_string_lossy(), &config.to_string_lossy()])?;
+        self.track(hostapd_pid);
+
+        let address = format!("10.77.{}.1/24", radio);
+        run("ip", &["addr", "flush", "dev", interface])?;
+        run("ip", &["addr", "add", &address, "dev", interface])?;
+        let dnsmasq_pid = self.dir.join(format!("dnsmasq-{}.pid", interface));
+        run(
+            "dnsmasq",
+            &[
+                &format!("--interface={}", interface),
+                "--bind-interfaces",
+                "--no-resolv",
+                "--port=0",
+                &format!("--dhcp-range=10.77.{0}.10,10.77.{0}.100,1h", radio),
+                &format!("--pid-file={}", dnsmasq_pid.to_string_lossy()),
+            ],
+        )?;
+        self.track(dnsmasq_pid);
+        Ok(())
+    }
+
+    /// A [`WiFi`] operating on radio `radio`.
+    ///
+    /// # Panics
+    ///
+    /// If `radio` is not a radio of the lab.
+    pub fn wifi(&self, radio: usize) -> WiFi {
+        // The native layer reads the variable once while constructing
+        std::env::set_var(INTERFACE_VAR, &self.interfaces[radio]);
+        let wifi = WiFi::new();
+        std::env::remove_var(INTERFACE_VAR);
+        wifi
+    }
+
+    fn track(&self, pid_file: PathBuf) {
+        self.pid_files.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).push(pid_file);
+    }
+}
+
+impl Drop for HwsimLab {
+    fn drop(&mut self) {
+        let pid_files = self.pid_files.get_mut().unwrap_or_else(|poisoned| poisoned.into_inner());
+        for pid_file in pid_files.drain(..) {
+            let pid = fs::read_to_string(&pid_file).ok().and_then(|pid| pid.trim().parse::<libc::pid_t>().ok());
+            if let Some(pid) = pid {
+                // SAFETY: plain signal delivery to a daemon the lab started.
+                unsafe { libc::kill(pid, libc::SIGTERM) };
+            }
+        }
+        let _ = fs::remove_dir_all(&self.dir);
+        let _ = run("modprobe", &["-r", "mac80211_hwsim"]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConnectOptions, HotspotChannel, HotspotConfig};
+
+    fn lab(radios: usize) -> Option<HwsimLab> {
+        if !HwsimLab::available() {
+            eprintln!("skipping: mac80211_hwsim environment not available");
+            return None;
+        }
+        Some(HwsimLab::start(radios).expect("failed to start hwsim lab"))
+    }
+
+    #[test]
+    fn scan_finds_ap() {
+        let Some(lab) = lab(2) else { return };
+        lab.start_ap(0, &HwsimAp::new("hwsim-scan").channel(11)).unwrap();
+        let wifi = lab.wifi(1);
+
+        let network = (0..5)
+            .find_map(|_| wifi.scan().into_iter().find(|n| n.ssid == "hwsim-scan"))
+            .expect("access point not found");
+        assert_eq!(network.channel, 11);
+    }
+
+    #[test]
+    fn connect_with_password() {
+        let Some(lab) = lab(2) else { return };
+        lab.start_ap(0, &HwsimAp::new("hwsim-wpa2").password("hwsim-secret")).unwrap();
+        let wifi = lab.wifi(1);
+
+        wifi.connect_with("hwsim-wpa2", &ConnectOptions::password("hwsim-secret")).unwrap();
+        assert_eq!(wifi.link_info().map(|link| link.ssid).as_deref(), Some("hwsim-wpa2"));
+        assert!(wifi.disconnect().is_ok());
+    }
+
+    #[test]
+    fn connect_with_wrong_password_fails() {
+        let Some(lab) = lab(2) else { return };
+        lab.start_ap(0, &HwsimAp::new("hwsim-reject").password("hwsim-secret")).unwrap();
+        let wifi = lab.wifi(1);
+
+        assert!(wifi.connect_with("hwsim-reject", &ConnectOptions::password("wrong-secret")).is_err());
+    }
+
+    #[test]
+    fn hotspot_is_joinable() {
+        let Some(lab) = lab(2) else { return };
+        let ap = lab.wifi(0);
+        let client = lab.wifi(1);
+
+        let config = HotspotConfig::new("hwsim-hotspot")
+            .password("hwsim-secret")
+            .channel(HotspotChannel::Fixed(1));
+        ap.create_hotspot_with(&config).unwrap();
+        let joined = client.connect_with("hwsim-hotspot", &ConnectOptions::password("hwsim-secret"));
+        ap.stop_hotspot().unwrap();
+        joined.unwrap();
+    }
+}
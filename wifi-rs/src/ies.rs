@@ -0,0 +1,224 @@
+//! Information elements advertised in beacons and probe responses.
+//!
+//! [`NetworkInfo::information_elements`] holds the elements exactly as the
+//! access point sent them. The accessors here decode the common ones; use
+//! [`NetworkInfo::elements`] or [`NetworkInfo::vendor_elements`] for
+//! anything else.
+//!
+//! ```
+//! use wifi_rs::{NetworkInfo, PhyMode, PmfMode, SecurityType};
+//!
+//! let network = NetworkInfo {
+//!     information_elements: vec![
+//!         0x07, 0x06, b'D', b'E', b' ', 36, 4, 23, // Country: DE, channels 36-48
+//!         0xdd, 0x05, 0x00, 0x50, 0xf2, 0x04, 0x10, // WPS vendor element
+//!     ],
+//! #   ssid: "Office".into(), bssid: "02:00:00:00:00:01".into(), signal_strength: -50,
+//! #   security_type: SecurityType::Wpa2, channel: 36, frequency: 5180, pmf: PmfMode::Capable,
+//! #   wps_enabled: true, wifi7: false, mlo: false, phy_mode: PhyMode::Unknown, max_rate_mbps: 0,
+//! };
+//! assert_eq!(network.country().as_deref(), Some("DE"));
+//! let (oui, data) = network.vendor_elements().next().unwrap();
+//! assert_eq!((oui, data), ([0x00, 0x50, 0xf2], &[0x04, 0x10][..]));
+//! ```
+
+use crate::NetworkInfo;
+
+const COUNTRY: u8 = 7;
+const HT_CAPABILITIES: u8 = 45;
+const RSN: u8 = 48;
+const VHT_CAPABILITIES: u8 = 191;
+const VENDOR_SPECIFIC: u8 = 221;
+const EXTENSION: u8 = 255;
+const HE_CAPABILITIES: u8 = 35;
+
+/// One information element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InformationElement<'a> {
+    pub id: u8,
+    /// Element ID extension, the first body byte of elements with ID 255.
+    pub extension_id: Option<u8>,
+    /// The body, without the extension ID.
+    pub data: &'a [u8],
+}
+
+/// Iterator over the information elements of a network, created by
+/// [`NetworkInfo::elements`]. Stops at a truncated element.
+#[derive(Debug, Clone)]
+pub struct InformationElements<'a> {
+    rest: &'a [u8],
+}
+
+impl<'a> Iterator for InformationElements<'a> {
+    type Item = InformationElement<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (&id, rest) = self.rest.split_first()?;
+        let (&len, rest) = rest.split_first()?;
+        if rest.len() < len as usize {
+            self.rest = &[];
+            return None;
+        }
+        let (body, rest) = rest.split_at(len as usize);
+        self.rest = rest;
+        Some(match (id, body.split_first()) {
+            (EXTENSION, Some((&extension_id, data))) => InformationElement { id, extension_id: Some(extension_id), data },
+            _ => InformationElement { id, extension_id: None, data: body },
+        })
+    }
+}
+
+/// HT (802.11n) capabilities of an access point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HtCapabilities {
+    /// The HT capability information field.
+    pub info: u16,
+    /// Spatial streams in the supported receive MCS set.
+    pub max_spatial_streams: u8,
+}
+
+impl HtCapabilities {
+    /// Whether 40 MHz channels are supported.
+    pub fn supports_40mhz(&self) -> bool {
+        self.info & 0x0002 != 0
+    }
+}
+
+/// VHT (802.11ac) capabilities of an access point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VhtCapabilities {
+    /// The VHT capability information field.
+    pub info: u32,
+    /// Spatial streams in the receive MCS map.
+    pub max_spatial_streams: u8,
+}
+
+impl VhtCapabilities {
+    /// Whether 160 MHz channels are supported.
+    pub fn supports_160mhz(&self) -> bool {
+        (self.info >> 2) & 0x3 != 0
+    }
+}
+
+/// HE (802.11ax) capabilities of an access point, undecoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HeCapabilities {
+    pub mac: [u8; 6],
+    pub phy: [u8; 11],
+    /// Spatial streams in the receive MCS map for up to 80 MHz.
+    pub max_spatial_streams: u8,
+}
+
+/// The RSN element, listing the ciphers and key management an access point
+/// accepts. Suites are 32-bit selectors: the OUI followed by the type, e.g.
+/// `0x000fac04` for CCMP and `0x000fac08` for SAE.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RsnInfo {
+    pub group_cipher: u32,
+    pub pairwise_ciphers: Vec<u32>,
+    pub akm_suites: Vec<u32>,
+    /// The RSN capabilities field; bit 6 requires and bit 7 allows
+    /// management frame protection.
+    pub capabilities: u16,
+}
+
+impl NetworkInfo {
+    /// Iterate over the information elements of the access point.
+    pub fn elements(&self) -> InformationElements<'_> {
+        InformationElements { rest: &self.information_elements }
+    }
+
+    /// Iterate over the vendor-specific elements as the vendor's OUI and
+    /// the data following it.
+    pub fn vendor_elements(&self) -> impl Iterator<Item = ([u8; 3], &[u8])> + '_ {
+        self.elements()
+            .filter(|element| element.id == VENDOR_SPECIFIC && element.data.len() >= 3)
+            .map(|element| ([element.data[0], element.data[1], element.data[2]], &element.data[3..]))
+    }
+
+    /// The two-letter country code the access point operates under.
+    pub fn country(&self) -> Option<String> {
+        let data = self.element(COUNTRY)?.data;
+        let code = data.get(..2)?;
+        code.iter().all(u8::is_ascii_alphabetic).then(|| String::from_utf8_lossy(code).into_owned())
+    }
+
+    /// HT capabilities, if the access point supports 802.11n.
+    pub fn ht_capabilities(&self) -> Option<HtCapabilities> {
+        let data = self.element(HT_CAPABILITIES)?.data;
+        let mcs = data.get(3..7)?;
+        Some(HtCapabilities {
+            info: u16::from_le_bytes([data[0], data[1]]),
+            max_spatial_streams: mcs.iter().filter(|set| **set != 0).count() as u8,
+        })
+    }
+
+    /// VHT capabilities, if the access point supports 802.11ac.
+    pub fn vht_capabilities(&self) -> Option<VhtCapabilities> {
+        let data = self.element(VHT_CAPABILITIES)?.data;
+        let rx_map = u16::from_le_bytes(data.get(4..6)?.try_into().ok()?);
+        Some(VhtCapabilities {
+            info: u32::from_le_bytes(data[..4].try_into().ok()?),
+            max_spatial_streams: mcs_map_streams(rx_map),
+        })
+    }
+
+    /// HE capabilities, if the access point supports 802.11ax.
+    pub fn he_capabilities(&self) -> Option<HeCapabilities> {
+        let data = self
+            .elements()
+            .find(|element| element.id == EXTENSION && element.extension_id == Some(HE_CAPABILITIES))?
+            .data;
+        let rx_map = u16::from_le_bytes(data.get(17..19)?.try_into().ok()?);
+        Some(HeCapabilities {
+            mac: data[..6].try_into().ok()?,
+            phy: data[6..17].try_into().ok()?,
+            max_spatial_streams: mcs_map_streams(rx_map),
+        })
+    }
+
+    /// The RSN element, absent on open, WEP and WPA1-only networks.
+    pub fn rsn(&self) -> Option<RsnInfo> {
+        let mut data = self.element(RSN)?.data;
+        if read_u16(&mut data)? != 1 {
+            return None;
+        }
+        let group_cipher = read_u32(&mut data)?;
+        let pairwise_ciphers = read_suites(&mut data)?;
+        let akm_suites = read_suites(&mut data)?;
+        let capabilities = read_u16(&mut data).unwrap_or(0);
+        Some(RsnInfo { group_cipher, pairwise_ciphers, akm_suites, capabilities })
+    }
+
+    fn element(&self, id: u8) -> Option<InformationElement<'_>> {
+        self.elements().find(|element| element.id == id)
+    }
+}
+
+/// Streams in a VHT or HE MCS map: two bits per stream, 3 meaning
+/// unsupported.
+fn mcs_map_streams(map: u16) -> u8 {
+    (0..8).filter(|stream| (map >> (stream * 2)) & 0x3 != 0x3).count() as u8
+}
+
+fn read_u16(data: &mut &[u8]) -> Option<u16> {
+    let value = u16::from_le_bytes(data.get(..2)?.try_into().ok()?);
+    *data = &data[2..];
+    Some(value)
+}
+
+/// Suite selectors are sent OUI first, so they read big-endian.
+fn read_u32(data: &mut &[u8]) -> Option<u32> {
+    let value = u32::from_be_bytes(data.get(..4)?.try_into().ok()?);
+    *data = &data[4..];
+    Some(value)
+}
+
+fn read_suites(data: &mut &[u8]) -> Option<Vec<u32>> {
+    let count = read_u16(data)?;
+    (0..count).map(|_| read_u32(data)).collect()
+}
@@ -0,0 +1,94 @@
+//! Wireless adapter enumeration.
+
+use crate::diag;
+use crate::Band;
+
+#[repr(C)]
+pub(crate) struct RawInterfaceInfo {
+    name: [libc::c_char; 64],
+    mac_address: [libc::c_char; 18],
+    driver: [libc::c_char; 64],
+    selected: i32,
+    supports_ap: i32,
+    supports_monitor: i32,
+    supports_mesh: i32,
+    supports_p2p: i32,
+    band_2ghz: i32,
+    band_5ghz: i32,
+    band_6ghz: i32,
+}
+
+/// Modes and bands a wireless adapter supports.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InterfaceCapabilities {
+    /// Can run a hotspot.
+    pub access_point: bool,
+    pub monitor: bool,
+    pub mesh: bool,
+    /// Can act as a Wi-Fi Direct group owner.
+    pub p2p: bool,
+    pub bands: Vec<Band>,
+}
+
+/// What the process can do with its adapter, from
+/// [`WiFi::capabilities`](crate::WiFi::capabilities).
+///
+/// Unlike [`InterfaceCapabilities`], which describes the hardware, this
+/// also accounts for the privileges of the process.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Capabilities {
+    /// A wireless adapter is present. Unprivileged processes on Linux may
+    /// only see the results of scans started by others.
+    pub can_scan: bool,
+    /// Connections can be made: with admin rights, through NetworkManager
+    /// on Linux, or by any process on other platforms.
+    pub can_connect: bool,
+    /// The adapter can run an access point and the process has the admin
+    /// rights to start one.
+    pub can_create_hotspot: bool,
+    /// See [`WiFi::has_admin_privileges`](crate::WiFi::has_admin_privileges).
+    pub has_admin: bool,
+    /// The adapter can capture frames in monitor mode.
+    pub monitor_mode_supported: bool,
+    pub bands_supported: Vec<Band>,
+}
+
+/// A wireless adapter, from [`WiFi::list_interfaces`](crate::WiFi::list_interfaces).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InterfaceInfo {
+    /// Name to pass to [`WiFi::with_interface`](crate::WiFi::with_interface),
+    /// e.g. `"wlan1"`. On Windows this is the adapter description.
+    pub name: String,
+    /// Hardware address, if the platform reports it.
+    pub mac_address: Option<String>,
+    /// Kernel driver or adapter description, if known.
+    pub driver: Option<String>,
+    /// Whether [`WiFi::new`](crate::WiFi::new) picks this adapter by default.
+    pub default: bool,
+    pub capabilities: InterfaceCapabilities,
+}
+
+impl InterfaceInfo {
+    pub(crate) fn from_raw(raw: &RawInterfaceInfo) -> Self {
+        let non_empty = |buffer: &[libc::c_char]| Some(diag::text(buffer)).filter(|value| !value.is_empty());
+        let bands = [(raw.band_2ghz, Band::Ghz2), (raw.band_5ghz, Band::Ghz5), (raw.band_6ghz, Band::Ghz6)]
+            .into_iter()
+            .filter(|(supported, _)| *supported != 0)
+            .map(|(_, band)| band)
+            .collect();
+        InterfaceInfo {
+            name: diag::text(&raw.name),
+            mac_address: non_empty(&raw.mac_address),
+            driver: non_empty(&raw.driver),
+            default: raw.selected != 0,
+            capabilities: InterfaceCapabilities {
+                access_point: raw.supports_ap != 0,
+                monitor: raw.supports_monitor != 0,
+                mesh: raw.supports_mesh != 0,
+                p2p: raw.supports_p2p != 0,
+                bands,
+            },
+        }
+    }
+}
@@ -0,0 +1,116 @@
+//! IP configuration of the wireless interface.
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::diag::text;
+
+#[repr(C)]
+pub(crate) struct RawIpInfo {
+    ipv4_addresses: [[libc::c_char; 50]; 4],
+    ipv4_address_count: i32,
+    ipv6_addresses: [[libc::c_char; 50]; 8],
+    ipv6_address_count: i32,
+    gateway: [libc::c_char; 46],
+    ipv6_gateway: [libc::c_char; 46],
+    dns_servers: [[libc::c_char; 46]; 4],
+    dns_server_count: i32,
+    dhcp: i32,
+    dhcp_server: [libc::c_char; 46],
+    lease_obtained: i64,
+    lease_expires: i64,
+}
+
+impl RawIpInfo {
+    pub(crate) fn zeroed() -> Self {
+        // SAFETY: the struct is plain integers and character arrays.
+        unsafe { std::mem::zeroed() }
+    }
+}
+
+/// An address assigned to the interface with the prefix length of its
+/// subnet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InterfaceAddress {
+    pub address: IpAddr,
+    pub prefix_length: u8,
+}
+
+impl InterfaceAddress {
+    /// Parse CIDR notation, e.g. `192.168.1.20/24`.
+    fn parse(cidr: &str) -> Option<Self> {
+        let (address, prefix) = cidr.split_once('/')?;
+        let address: IpAddr = address.parse().ok()?;
+        let prefix_length: u8 = prefix.parse().ok()?;
+        let max = if address.is_ipv4() { 32 } else { 128 };
+        (prefix_length <= max).then_some(InterfaceAddress { address, prefix_length })
+    }
+}
+
+/// The DHCP lease the IPv4 address was obtained with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DhcpLease {
+    pub server: Option<Ipv4Addr>,
+    pub obtained: Option<SystemTime>,
+    pub expires: Option<SystemTime>,
+}
+
+impl DhcpLease {
+    /// Time left until the lease expires, zero once it has, `None` if the
+    /// expiry is unknown.
+    pub fn remaining(&self) -> Option<Duration> {
+        self.expires
+            .map(|expires| expires.duration_since(SystemTime::now()).unwrap_or(Duration::ZERO))
+    }
+}
+
+/// Addresses, routes and name servers of the wireless interface, returned
+/// by [`WiFi::ip_info`](crate::WiFi::ip_info).
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IpInfo {
+    pub ipv4_addresses: Vec<InterfaceAddress>,
+    /// Includes link-local addresses.
+    pub ipv6_addresses: Vec<InterfaceAddress>,
+    pub gateway: Option<Ipv4Addr>,
+    pub ipv6_gateway: Option<Ipv6Addr>,
+    /// Name servers in the order they are queried; on Linux these are the
+    /// system's resolvers, which may be a local stub such as systemd-resolved.
+    pub dns_servers: Vec<IpAddr>,
+    /// `None` for a static address, or if the platform does not say how the
+    /// address was obtained.
+    pub dhcp: Option<DhcpLease>,
+}
+
+fn unix_time(seconds: i64) -> Option<SystemTime> {
+    (seconds > 0).then(|| UNIX_EPOCH + Duration::from_secs(seconds as u64))
+}
+
+impl IpInfo {
+    pub(crate) fn from_raw(raw: &RawIpInfo) -> Self {
+        let addresses = |buffers: &[[libc::c_char; 50]], count: i32| -> Vec<InterfaceAddress> {
+            buffers[..count.clamp(0, buffers.len() as i32) as usize]
+                .iter()
+                .filter_map(|buffer| InterfaceAddress::parse(&text(buffer)))
+                .collect()
+        };
+        let dns_count = raw.dns_server_count.clamp(0, 4) as usize;
+        IpInfo {
+            ipv4_addresses: addresses(&raw.ipv4_addresses, raw.ipv4_address_count),
+            ipv6_addresses: addresses(&raw.ipv6_addresses, raw.ipv6_address_count),
+            gateway: text(&raw.gateway).parse().ok(),
+            ipv6_gateway: text(&raw.ipv6_gateway).parse().ok(),
+            dns_servers: raw.dns_servers[..dns_count]
+                .iter()
+                .filter_map(|server| text(server).parse().ok())
+                .collect(),
+            dhcp: (raw.dhcp != 0).then(|| DhcpLease {
+                server: text(&raw.dhcp_server).parse().ok(),
+                obtained: unix_time(raw.lease_obtained),
+                expires: unix_time(raw.lease_expires),
+            }),
+        }
+    }
+}
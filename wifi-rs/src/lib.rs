@@ -1,3 +1,126 @@
+mod adhoc;
+#[cfg(feature = "tokio")]
+mod async_wifi;
+mod audit;
+mod autoconnect;
+mod backend;
+mod band;
+mod builder;
+mod cancel;
+#[cfg(feature = "capture")]
+pub mod capture;
+mod channel;
+mod connect;
+mod credentials;
+mod diag;
+mod eap;
+mod error;
+mod events;
+mod faults;
+mod flap;
+mod group;
+#[cfg(feature = "geolocation")]
+pub mod geolocation;
+mod heatmap;
+#[cfg(feature = "history")]
+pub mod history;
+mod hotspot;
+mod ies;
+mod interfaces;
+mod ip;
+#[cfg(all(feature = "hwsim", target_os = "linux"))]
+pub mod hwsim;
+mod link;
+mod listener;
+mod logging;
+pub mod mesh;
+#[cfg(feature = "backend-mock")]
+pub mod mock;
+pub mod nan;
+mod observer;
+pub mod p2p;
+mod passpoint;
+mod phy;
+pub mod pcap;
+mod policy;
+mod profiles;
+mod provisioning;
+mod replay;
+mod roam;
+mod rogue;
+mod rtt;
+mod scan;
+mod scan_cache;
+mod scan_diff;
+mod schedule;
+mod secret;
+mod signal;
+mod signal_log;
+#[cfg(feature = "backend-mock")]
+pub mod sim;
+mod status;
+mod survey;
+mod tdls;
+mod trace;
+mod twt;
+mod watchdog;
+mod wowlan;
+pub mod wps;
+
+#[cfg(feature = "homeassistant")]
+pub mod homeassistant;
+
+pub use adhoc::AdHocConfig;
+#[cfg(feature = "tokio")]
+pub use async_wifi::AsyncWiFi;
+pub use audit::{AuditIssue, Finding, SecurityAudit, Severity};
+pub use autoconnect::AutoConnect;
+pub use backend::WifiBackend;
+pub use band::BandPolicy;
+pub use builder::{Timeouts, WiFiBuilder};
+pub use cancel::CancellationHandle;
+pub use channel::{Band, ChannelAnalyzer, ChannelInfo, ChannelRecommendation, ChannelStats, ChannelWidth};
+pub use connect::{ConnectOptions, ConnectionResult, LinuxBackend};
+pub use credentials::NetworkCredentials;
+pub use diag::{DiagnosticReport, NetworkFailure, Stage, StageResult, StageStatus};
+pub use eap::{ClientCertificate, Eap, EapConfig, EapMethod, EnterpriseConfig, TtlsInner};
+pub use error::WifiError;
+pub use events::{DisconnectReason, EventKind, WifiEvent, DEFAULT_EVENT_LOG_CAPACITY};
+pub use faults::{FaultInjector, InjectedFault};
+pub use flap::{FlapAlert, FlapDetector, FlapStats};
+pub use group::NetworkGroup;
+pub use heatmap::{HeatmapSurvey, LinkReading, Position, PositionProvider, SurveyPoint};
+pub use hotspot::{HotspotChannel, HotspotClient, HotspotConfig};
+pub use interfaces::{Capabilities, InterfaceCapabilities, InterfaceInfo};
+pub use ies::{HeCapabilities, HtCapabilities, InformationElement, InformationElements, RsnInfo, VhtCapabilities};
+pub use ip::{DhcpLease, InterfaceAddress, IpInfo};
+pub use link::{FeatureSupport, LinkInfo, LinkStats, MloLink, RoamingFeatures};
+pub use logging::LogLevel;
+pub use listener::{ListenerRegistration, WifiEventListener, WifiNotification};
+pub use observer::NetworkObserver;
+pub use passpoint::{PasspointCredential, PasspointProfile};
+pub use phy::PhyMode;
+pub use policy::{Candidate, ConnectionPolicy, PolicyEvent, PolicyNetwork, PolicyRules, PRIORITY_STEP_DB};
+pub use profiles::SavedProfile;
+pub use provisioning::ProvisioningPortal;
+pub use replay::{SessionRecorder, SessionReplay};
+pub use roam::{Roam, RoamingPolicy, ROAM_HISTORY_CAPACITY};
+pub use rogue::{RogueApDetector, SecurityAlert};
+pub use rtt::RangingResult;
+pub use scan::ScanOptions;
+pub use scan_cache::{CachedNetwork, ScanCache, ScanSnapshot};
+pub use scan_diff::{ScanDiff, SignalChange};
+pub use schedule::{on_ac_power, ParseScheduleError, ScanSchedule, ScanScheduler, ScanSink, TimeOfDay};
+pub use secret::SecretString;
+pub use signal::{SignalMonitor, SignalSample};
+pub use signal_log::{LogFormat, Rotation, SignalLogger};
+pub use status::{StatusDetail, SubState};
+pub use survey::ChannelSurvey;
+pub use tdls::TdlsPeer;
+pub use twt::{TwtConfig, TwtStatus, MAX_TWT_WAKE_DURATION};
+pub use watchdog::{ConnectionWatchdog, WatchdogEvent, WatchdogPolicy};
+pub use wowlan::{WowlanCapabilities, WowlanConfig, WowlanPattern, WowlanTriggers};
+
 #[repr(C)]
 pub struct RawNetworkInfo {
     ssid: *const libc::c_char,
@@ -6,20 +129,49 @@ pub struct RawNetworkInfo {
     security_type: libc::c_int,
     channel: libc::c_int,
     frequency: libc::c_int,
+    pmf: libc::c_int,
+    wps_enabled: libc::c_int,
+    eht: libc::c_int,
+    mlo: libc::c_int,
+    ie_data: *const u8,
+    ie_len: libc::c_int,
 }
 
 // Safe wrapper for NetworkInfo
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NetworkInfo {
     pub ssid: String,
     pub bssid: String,
     pub signal_strength: i32,
     pub security_type: SecurityType,
+    /// Channel number within the band, see [`band`](Self::band).
     pub channel: i32,
+    /// Center frequency in MHz.
     pub frequency: i32,
+    /// Protected Management Frames (802.11w) advertised by the AP.
+    pub pmf: PmfMode,
+    /// Whether the AP advertises WPS.
+    pub wps_enabled: bool,
+    /// Whether the AP supports Wi-Fi 7 (802.11be EHT).
+    pub wifi7: bool,
+    /// Whether the AP belongs to a multi-link device, so a Wi-Fi 7 client
+    /// can use several bands at once.
+    pub mlo: bool,
+    /// Information elements from the beacon or probe response, empty if
+    /// the platform does not report them. See [`elements`](Self::elements).
+    pub information_elements: Vec<u8>,
+    /// The newest 802.11 standard the AP advertises, from its HT, VHT, HE
+    /// and EHT capabilities.
+    pub phy_mode: PhyMode,
+    /// Highest PHY rate the AP advertises in Mbit/s, with every spatial
+    /// stream and the widest channel it supports; 0 if unknown. Clients
+    /// with fewer streams or a narrower channel see less.
+    pub max_rate_mbps: u32,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SecurityType {
     None,
     Wep,
@@ -29,7 +181,32 @@ pub enum SecurityType {
     Unknown,
 }
 
+impl SecurityType {
+    pub(crate) fn from_raw(code: libc::c_int) -> Self {
+        match code {
+            0 => SecurityType::None,
+            1 => SecurityType::Wep,
+            2 => SecurityType::Wpa,
+            3 => SecurityType::Wpa2,
+            4 => SecurityType::Wpa3,
+            _ => SecurityType::Unknown,
+        }
+    }
+}
+
+/// Protected Management Frames (802.11w) support of an access point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PmfMode {
+    /// Not reported by the platform.
+    Unknown,
+    Disabled,
+    Capable,
+    Required,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ConnectionStatus {
     Connected,
     Disconnected,
@@ -43,182 +220,2391 @@ pub struct WifiManager {
 }
 
 extern "C" {
+    fn wifi_api_version() -> u32;
+    fn wifi_set_log_callback(callback: Option<logging::RawLogCallback>, user_data: *mut libc::c_void);
+    fn wifi_set_log_level(level: libc::c_int);
+    fn wifi_manager_set_linux_backend(manager: *mut WifiManager, backend: libc::c_int) -> bool;
+    fn wifi_manager_get_linux_backend(manager: *mut WifiManager) -> libc::c_int;
+    fn wifi_manager_is_location_authorized(manager: *mut WifiManager) -> bool;
+    fn wifi_manager_request_location_authorization(manager: *mut WifiManager) -> bool;
+    fn wifi_manager_has_admin_privileges(manager: *mut WifiManager) -> bool;
     fn wifi_manager_new() -> *mut WifiManager;
+    fn wifi_manager_new_with_interface(interface_name: *const libc::c_char) -> *mut WifiManager;
     fn wifi_manager_delete(manager: *mut WifiManager);
     fn wifi_manager_scan(manager: *mut WifiManager, count: *mut libc::c_int) -> *mut RawNetworkInfo;
+    fn wifi_manager_scan_fresh(manager: *mut WifiManager, networks: *mut *mut RawNetworkInfo, count: *mut libc::c_int) -> bool;
+    fn wifi_manager_scan_with_timeout(manager: *mut WifiManager, options: *const scan::RawScanOptions, timeout_ms: i32, count: *mut libc::c_int) -> *mut RawNetworkInfo;
+    fn wifi_manager_cancel(manager: *mut WifiManager) -> bool;
+    fn wifi_manager_set_timeouts(manager: *mut WifiManager, scan_timeout_ms: i32, hotspot_timeout_ms: i32) -> bool;
     fn wifi_manager_connect(manager: *mut WifiManager, ssid: *const libc::c_char, password: *const libc::c_char) -> bool;
+    fn wifi_manager_connect_with_options(manager: *mut WifiManager, ssid: *const libc::c_char, password: *const libc::c_char, options: *const connect::RawConnectOptions) -> bool;
+    fn wifi_manager_connect_enterprise(manager: *mut WifiManager, ssid: *const libc::c_char, eap: *const eap::RawEapConfig) -> bool;
+    fn wifi_manager_connect_wps_pbc_with_progress(manager: *mut WifiManager, timeout_seconds: libc::c_int, callback: wps::RawWpsProgressCallback, user_data: *mut libc::c_void) -> bool;
+    fn wifi_manager_connect_wps_pin_with_progress(manager: *mut WifiManager, bssid: *const libc::c_char, pin: *const libc::c_char, timeout_seconds: libc::c_int, callback: wps::RawWpsProgressCallback, user_data: *mut libc::c_void) -> bool;
+    fn wifi_manager_is_eap_method_supported(manager: *mut WifiManager, method: libc::c_int) -> bool;
+    fn wifi_manager_install_passpoint(manager: *mut WifiManager, profile: *const passpoint::RawPasspointProfile) -> bool;
+    fn wifi_manager_remove_passpoint(manager: *mut WifiManager, domain: *const libc::c_char) -> bool;
+    fn wifi_manager_set_probe_privacy(manager: *mut WifiManager, enabled: bool) -> bool;
+    fn wifi_manager_is_probe_privacy_enabled(manager: *mut WifiManager) -> bool;
+    #[cfg(feature = "capture")]
+    fn wifi_manager_start_capture(manager: *mut WifiManager, config: *const capture::RawCaptureConfig, callback: capture::RawFrameCallback, user_data: *mut libc::c_void) -> bool;
+    #[cfg(feature = "capture")]
+    fn wifi_manager_stop_capture(manager: *mut WifiManager) -> bool;
+    fn wifi_manager_is_rtt_supported(manager: *mut WifiManager) -> bool;
+    fn wifi_manager_measure_distance(manager: *mut WifiManager, bssid: *const libc::c_char, frequency: libc::c_int, result: *mut rtt::RawRangingResult) -> bool;
+    fn wifi_manager_is_tdls_supported(manager: *mut WifiManager) -> bool;
+    fn wifi_manager_tdls_discover(manager: *mut WifiManager, timeout_seconds: i32, count: *mut libc::c_int) -> *mut tdls::RawTdlsPeer;
+    fn wifi_free_tdls_peers(peers: *mut tdls::RawTdlsPeer);
+    fn wifi_manager_tdls_setup(manager: *mut WifiManager, peer_address: *const libc::c_char) -> bool;
+    fn wifi_manager_tdls_teardown(manager: *mut WifiManager, peer_address: *const libc::c_char) -> bool;
+    fn wifi_manager_is_adhoc_supported(manager: *mut WifiManager) -> bool;
+    fn wifi_manager_join_adhoc(manager: *mut WifiManager, config: *const adhoc::RawAdHocConfig) -> bool;
+    fn wifi_manager_is_mesh_supported(manager: *mut WifiManager) -> bool;
+    fn wifi_manager_mesh_join(manager: *mut WifiManager, config: *const mesh::RawMeshConfig) -> bool;
+    fn wifi_manager_mesh_leave(manager: *mut WifiManager) -> bool;
+    fn wifi_manager_mesh_peers(manager: *mut WifiManager, count: *mut libc::c_int) -> *mut mesh::RawMeshPeer;
+    fn wifi_free_mesh_peers(peers: *mut mesh::RawMeshPeer);
+    fn wifi_manager_is_nan_supported(manager: *mut WifiManager) -> bool;
+    fn wifi_manager_nan_publish(manager: *mut WifiManager, config: *const nan::RawNanPublishConfig) -> i32;
+    fn wifi_manager_nan_subscribe(manager: *mut WifiManager, config: *const nan::RawNanSubscribeConfig) -> i32;
+    fn wifi_manager_nan_cancel(manager: *mut WifiManager, handle: i32) -> bool;
+    fn wifi_manager_nan_transmit(manager: *mut WifiManager, handle: i32, peer_instance_id: i32, peer_address: *const libc::c_char, data: *const u8, data_len: i32) -> bool;
+    fn wifi_manager_nan_next_event(manager: *mut WifiManager, timeout_ms: i32, event: *mut nan::RawNanEvent) -> bool;
+    fn wifi_manager_is_p2p_supported(manager: *mut WifiManager) -> bool;
+    fn wifi_manager_p2p_discover(manager: *mut WifiManager, timeout_seconds: i32, count: *mut libc::c_int) -> *mut p2p::RawP2pPeer;
+    fn wifi_free_p2p_peers(peers: *mut p2p::RawP2pPeer);
+    fn wifi_manager_p2p_connect(manager: *mut WifiManager, config: *const p2p::RawP2pConnectConfig, timeout_seconds: i32, group: *mut p2p::RawP2pGroup) -> bool;
+    fn wifi_manager_p2p_disconnect(manager: *mut WifiManager) -> bool;
+    fn wifi_manager_get_connection_info(manager: *mut WifiManager, info: *mut roam::RawConnectionInfo) -> bool;
+    fn wifi_manager_get_link_stats(manager: *mut WifiManager, stats: *mut link::RawLinkStats) -> bool;
+    fn wifi_manager_is_radio_enabled(manager: *mut WifiManager) -> bool;
+    fn wifi_manager_set_radio_enabled(manager: *mut WifiManager, enabled: bool) -> bool;
+    fn wifi_manager_get_twt_status(manager: *mut WifiManager, status: *mut twt::RawTwtStatus) -> bool;
+    fn wifi_manager_twt_setup(manager: *mut WifiManager, config: *const twt::RawTwtConfig) -> bool;
+    fn wifi_manager_twt_teardown(manager: *mut WifiManager) -> bool;
+    fn wifi_manager_get_wowlan_capabilities(manager: *mut WifiManager, capabilities: *mut wowlan::RawWowlanCapabilities) -> bool;
+    fn wifi_manager_get_wowlan(manager: *mut WifiManager, status: *mut wowlan::RawWowlanStatus) -> bool;
+    fn wifi_manager_set_wowlan(manager: *mut WifiManager, config: *const wowlan::RawWowlanConfig) -> bool;
+    fn wifi_manager_get_mlo_links(manager: *mut WifiManager, count: *mut libc::c_int) -> *mut link::RawMloLink;
+    fn wifi_free_mlo_links(links: *mut link::RawMloLink);
+    fn wifi_manager_get_allowed_channels(manager: *mut WifiManager, count: *mut libc::c_int) -> *mut channel::RawChannelInfo;
+    fn wifi_free_channel_info(channels: *mut channel::RawChannelInfo);
+    fn wifi_manager_get_regulatory_domain(manager: *mut WifiManager, country: *mut libc::c_char, country_size: libc::c_int) -> bool;
+    fn wifi_manager_can_set_regulatory_domain(manager: *mut WifiManager) -> bool;
+    fn wifi_manager_set_regulatory_domain(manager: *mut WifiManager, country: *const libc::c_char) -> bool;
+    fn wifi_manager_get_roaming_features(manager: *mut WifiManager, features: *mut link::RawRoamingFeatures) -> bool;
+    fn wifi_manager_roam_to(manager: *mut WifiManager, bssid: *const libc::c_char) -> bool;
+    fn wifi_manager_set_roaming_policy(manager: *mut WifiManager, policy: *const roam::RawRoamingPolicy) -> bool;
+    fn wifi_manager_get_link_diagnostics(manager: *mut WifiManager, diagnostics: *mut diag::RawLinkDiagnostics) -> bool;
+    fn wifi_manager_get_ip_info(manager: *mut WifiManager, info: *mut ip::RawIpInfo) -> bool;
+    fn wifi_manager_channel_survey(manager: *mut WifiManager, count: *mut libc::c_int) -> *mut survey::RawChannelSurvey;
+    fn wifi_free_channel_survey(surveys: *mut survey::RawChannelSurvey);
+    fn wifi_manager_list_interfaces(manager: *mut WifiManager, count: *mut libc::c_int) -> *mut interfaces::RawInterfaceInfo;
+    fn wifi_free_interface_list(interfaces: *mut interfaces::RawInterfaceInfo);
+    fn wifi_manager_get_interface_name(manager: *mut WifiManager, name: *mut libc::c_char, name_size: libc::c_int) -> bool;
+    fn wifi_manager_start_network_observer(manager: *mut WifiManager, callback: observer::RawNetworkChangeCallback, user_data: *mut libc::c_void) -> bool;
+    fn wifi_manager_stop_network_observer(manager: *mut WifiManager) -> bool;
+    fn wifi_manager_start_signal_monitor(manager: *mut WifiManager, interval_ms: i32, callback: signal::RawSignalCallback, user_data: *mut libc::c_void) -> bool;
+    fn wifi_manager_stop_signal_monitor(manager: *mut WifiManager) -> bool;
+    fn wifi_manager_register_callback(manager: *mut WifiManager, callback: listener::RawWifiEventCallback, user_data: *mut libc::c_void, signal_threshold: i32) -> bool;
+    fn wifi_manager_unregister_callback(manager: *mut WifiManager) -> bool;
+    fn wifi_manager_is_profile_store_supported(manager: *mut WifiManager) -> bool;
+    fn wifi_manager_has_profile(manager: *mut WifiManager, name: *const libc::c_char) -> bool;
+    fn wifi_manager_connect_profile(manager: *mut WifiManager, name: *const libc::c_char) -> bool;
+    fn wifi_manager_list_profiles(manager: *mut WifiManager, count: *mut libc::c_int) -> *mut profiles::RawSavedProfile;
+    fn wifi_free_profile_list(profiles: *mut profiles::RawSavedProfile);
+    fn wifi_manager_save_profile(manager: *mut WifiManager, ssid: *const libc::c_char, password: *const libc::c_char, auto_connect: bool) -> bool;
+    fn wifi_manager_forget_profile(manager: *mut WifiManager, name: *const libc::c_char) -> bool;
+    fn wifi_manager_set_profile_auto_connect(manager: *mut WifiManager, name: *const libc::c_char, enabled: bool) -> bool;
     fn wifi_manager_disconnect(manager: *mut WifiManager) -> bool;
     fn wifi_manager_get_status(manager: *mut WifiManager) -> libc::c_int;
+    fn wifi_manager_get_last_error(manager: *mut WifiManager) -> libc::c_int;
     fn wifi_free_network_info(networks: *mut RawNetworkInfo, count: libc::c_int);
     
     // Hotspot functions
     fn wifi_manager_create_hotspot(manager: *mut WifiManager, ssid: *const libc::c_char) -> bool;
+    fn wifi_manager_create_hotspot_with_timeout(
+        manager: *mut WifiManager,
+        config: *const hotspot::RawHotspotConfig,
+        share_from: *const libc::c_char,
+        captive_portal: bool,
+        timeout_ms: i32,
+    ) -> bool;
     fn wifi_manager_stop_hotspot(manager: *mut WifiManager) -> bool;
     fn wifi_manager_is_hotspot_active(manager: *mut WifiManager) -> bool;
     fn wifi_manager_is_hotspot_supported(manager: *mut WifiManager) -> bool;
+    fn wifi_manager_hotspot_clients(manager: *mut WifiManager, count: *mut libc::c_int) -> *mut hotspot::RawHotspotClient;
+    fn wifi_free_hotspot_clients(clients: *mut hotspot::RawHotspotClient);
 }
 
+/// Major version of the native C API these bindings are written against,
+/// `WIFI_API_VERSION_MAJOR` in `wifi_c_api.h`.
+pub const NATIVE_API_VERSION_MAJOR: u32 = 2;
+
+/// Handle to a wireless adapter.
+///
+/// A `WiFi` can be shared between threads, e.g. in an `Arc`. Calls are
+/// serialized by the native layer, so a scan on one thread delays a status
+/// query on another until it completes.
 pub struct WiFi {
     manager: *mut WifiManager,
+    interface: Option<String>,
+    events: events::EventLog,
+    roams: roam::RoamTracker,
+    timeouts: Timeouts,
+    scan_cache_ttl: Option<std::time::Duration>,
+    last_scan: std::sync::Mutex<Option<(std::time::Instant, Vec<NetworkInfo>)>>,
+    cancel: std::sync::Arc<cancel::CancelTarget>,
 }
 
+// SAFETY: the native manager is not tied to the thread that created it, and
+// is only freed on drop.
+unsafe impl Send for WiFi {}
+
+// SAFETY: every native call locks the manager for its duration, and the
+// Rust-side state is behind mutexes.
+unsafe impl Sync for WiFi {}
+
 impl WiFi {
+    /// Version of the C API implemented by the loaded native library, as
+    /// `(major, minor)`. The bindings need the major version to be
+    /// [`NATIVE_API_VERSION_MAJOR`].
+    pub fn native_api_version() -> (u32, u32) {
+        let version = unsafe { wifi_api_version() };
+        (version >> 16, version & 0xffff)
+    }
+
+    /// Open the platform's default adapter.
+    ///
+    /// Never fails: without a usable adapter the handle is detached and
+    /// every call fails with [`WifiError::AdapterNotFound`] or returns
+    /// nothing. Use [`try_new`](Self::try_new) or [`builder`](Self::builder)
+    /// to find out at construction instead.
     pub fn new() -> Self {
-        unsafe {
-            WiFi {
-                manager: wifi_manager_new(),
-            }
+        Self::from_manager(unsafe { wifi_manager_new() })
+    }
+
+    /// Open the platform's default adapter.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WifiError::AdapterNotFound`] if there is no usable wireless
+    /// adapter or the native manager could not be created.
+    pub fn try_new() -> Result<Self, WifiError> {
+        let manager = unsafe { wifi_manager_new() };
+        if manager.is_null() {
+            return Err(WifiError::AdapterNotFound);
         }
+        Ok(Self::from_manager(manager))
     }
 
-    pub fn scan(&self) -> Vec<NetworkInfo> {
-        unsafe {
+    /// Operate on a specific adapter instead of the platform's default, so
+    /// every scan, connect and hotspot call targets it.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Adapter name as reported by
+    ///   [`list_interfaces`](Self::list_interfaces), e.g. `"wlan1"`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WifiError::InvalidArgument`] if `name` contains a NUL byte,
+    /// or [`WifiError::AdapterNotFound`] if no usable wireless adapter has
+    /// that name, or the platform cannot select adapters.
+    pub fn with_interface(name: &str) -> Result<Self, WifiError> {
+        let name = std::ffi::CString::new(name)?;
+        let manager = unsafe { wifi_manager_new_with_interface(name.as_ptr()) };
+        if manager.is_null() {
+            return Err(WifiError::AdapterNotFound);
+        }
+        Ok(Self::from_manager(manager))
+    }
+
+    /// List the wireless adapters present on the system with their
+    /// capabilities.
+    ///
+    /// # Returns
+    ///
+    /// One [`InterfaceInfo`] per adapter, empty if there are none or the
+    /// platform cannot enumerate them.
+    pub fn list_interfaces() -> Vec<InterfaceInfo> {
+        Self::new().interfaces()
+    }
+
+    fn interfaces(&self) -> Vec<InterfaceInfo> {
+        trace::op(self.interface.as_deref(), "list_interfaces", || unsafe {
             let mut count: libc::c_int = 0;
-            let raw_networks = wifi_manager_scan(self.manager, &mut count);
-            
-            if raw_networks.is_null() || count <= 0 {
+            let raw = wifi_manager_list_interfaces(self.manager, &mut count);
+            if raw.is_null() || count <= 0 {
                 return Vec::new();
             }
-            
-            let raw_slice = std::slice::from_raw_parts(raw_networks, count as usize);
-            let result = raw_slice
-                .iter()                .map(|raw| {
-                    let ssid = if raw.ssid.is_null() {
-                        "[Hidden Network]".to_string()
-                    } else {
-                        let s = std::ffi::CStr::from_ptr(raw.ssid)
-                            .to_string_lossy()
-                            .into_owned();
-                        if s.is_empty() { 
-                            "[Hidden Network]".to_string() 
-                        } else if s.contains("Enable Location Services") {
-                            "[Hidden Network]".to_string()
-                        } else { 
-                            s 
-                        }
-                    };
-                    
-                    let bssid = if raw.bssid.is_null() {
-                        "[No Access]".to_string()
-                    } else {
-                        let s = std::ffi::CStr::from_ptr(raw.bssid)
-                            .to_string_lossy()
-                            .into_owned();
-                        if s.is_empty() { 
-                            "[No Access]".to_string() 
-                        } else if s.contains("Enable Location Services") {
-                            "[No Access]".to_string()
-                        } else { 
-                            s 
-                        }
-                    };
-                    
-                    NetworkInfo {
-                        ssid,
-                        bssid,
-                        signal_strength: raw.signal_strength,
-                        security_type: match raw.security_type {
-                            0 => SecurityType::None,
-                            1 => SecurityType::Wep,
-                            2 => SecurityType::Wpa,
-                            3 => SecurityType::Wpa2,
-                            4 => SecurityType::Wpa3,
-                            _ => SecurityType::Unknown,
-                        },
-                        channel: raw.channel,
-                        frequency: raw.frequency,
-                    }
-                })
+            let interfaces = std::slice::from_raw_parts(raw, count as usize)
+                .iter()
+                .map(InterfaceInfo::from_raw)
                 .collect();
-            
-            wifi_free_network_info(raw_networks, count);
-            result
+            wifi_free_interface_list(raw);
+            interfaces
+        })
+    }
+
+    fn from_manager(manager: *mut WifiManager) -> Self {
+        let mut name: [libc::c_char; 32] = [0; 32];
+        // A null manager is safe to pass, but has no name to ask for
+        let named = !manager.is_null()
+            && unsafe { wifi_manager_get_interface_name(manager, name.as_mut_ptr(), name.len() as libc::c_int) };
+        let interface = named.then(|| diag::text(&name));
+        WiFi {
+            manager,
+            interface,
+            events: events::EventLog::new(),
+            roams: roam::RoamTracker::new(),
+            timeouts: Timeouts::default(),
+            scan_cache_ttl: None,
+            last_scan: std::sync::Mutex::new(None),
+            cancel: cancel::CancelTarget::new(manager),
         }
     }
 
-    pub fn connect(&self, ssid: &str, password: Option<&str>) -> bool {
+    /// Configure the interface, timeouts, logging and privilege checks
+    /// before opening the adapter. See [`WiFiBuilder`].
+    pub fn builder() -> WiFiBuilder {
+        WiFiBuilder::new()
+    }
+
+    /// Abort the scan or connect another thread is running on this handle,
+    /// which then fails with [`WifiError::Cancelled`]. Returns whether one
+    /// was in progress. See [`CancellationHandle`].
+    pub fn cancel(&self) -> bool {
+        self.cancel.cancel()
+    }
+
+    /// A handle that cancels operations on this `WiFi` from anywhere, e.g.
+    /// a UI thread that does not own it.
+    pub fn cancellation_handle(&self) -> CancellationHandle {
+        self.cancel.handle()
+    }
+
+    /// Change how long the blocking operations may take. See [`Timeouts`].
+    pub fn set_timeouts(&mut self, timeouts: Timeouts) {
         unsafe {
-            let ssid = std::ffi::CString::new(ssid).unwrap();
-            let password = password.map(|p| std::ffi::CString::new(p).unwrap());
-            
-            wifi_manager_connect(
-                self.manager,
-                ssid.as_ptr(),
-                password.map_or(std::ptr::null(), |p| p.as_ptr())
-            )
+            wifi_manager_set_timeouts(self.manager, Timeouts::millis(timeouts.scan), Timeouts::millis(timeouts.hotspot));
         }
+        self.timeouts = timeouts;
     }
 
-    pub fn disconnect(&self) -> bool {
-        unsafe {
-            wifi_manager_disconnect(self.manager)
+    /// The timeouts in effect, see [`set_timeouts`](Self::set_timeouts).
+    pub fn timeouts(&self) -> Timeouts {
+        self.timeouts
+    }
+
+    /// Select how connections are made on Linux. See [`LinuxBackend`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WifiError::Unsupported`] on other platforms, or for
+    /// [`LinuxBackend::NetworkManager`] when NetworkManager does not manage
+    /// the interface.
+    pub fn set_linux_backend(&self, backend: LinuxBackend) -> Result<(), WifiError> {
+        self.native_op("set_linux_backend", || unsafe {
+            wifi_manager_set_linux_backend(self.manager, backend.raw())
+        })
+    }
+
+    /// The backend connections are made with, with [`LinuxBackend::Auto`]
+    /// resolved, or `None` on platforms other than Linux.
+    pub fn linux_backend(&self) -> Option<LinuxBackend> {
+        LinuxBackend::from_raw(unsafe { wifi_manager_get_linux_backend(self.manager) })
+    }
+
+    /// Whether the process may see network names and BSSIDs. macOS hides
+    /// them from scans and the current connection until the user grants
+    /// Location Services access; always `true` on other platforms.
+    pub fn location_authorized(&self) -> bool {
+        unsafe { wifi_manager_is_location_authorized(self.manager) }
+    }
+
+    /// Ask the user for Location Services access on macOS, blocking until
+    /// they answer, at most 30 seconds. Scans request it themselves the
+    /// first time; this lets an application ask at a moment of its choosing.
+    ///
+    /// macOS only shows the prompt for applications whose `Info.plist` has
+    /// an `NSLocationUsageDescription`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WifiError::PermissionDenied`] if access was not granted.
+    pub fn request_location_authorization(&self) -> Result<(), WifiError> {
+        let granted = trace::op(self.interface.as_deref(), "request_location_authorization", || unsafe {
+            wifi_manager_request_location_authorization(self.manager)
+        });
+        if granted {
+            Ok(())
+        } else {
+            Err(WifiError::PermissionDenied)
         }
-    }    pub fn get_status(&self) -> ConnectionStatus {
-        unsafe {            match wifi_manager_get_status(self.manager) {
-                0 => ConnectionStatus::Connected,
-                1 => ConnectionStatus::Disconnected,
-                2 => ConnectionStatus::Connecting,
-                _ => ConnectionStatus::Error,
+    }
+
+    /// Whether the process has the privileges to manage the adapter: root
+    /// or `CAP_NET_ADMIN` on Linux, an elevated process on Windows, root on
+    /// macOS.
+    pub fn has_admin_privileges(&self) -> bool {
+        unsafe { wifi_manager_has_admin_privileges(self.manager) }
+    }
+
+    /// Check which operations can work before attempting them, so an
+    /// application can hide a hotspot button instead of failing with
+    /// [`WifiError::PermissionDenied`].
+    ///
+    /// The answer is gathered from the platform on every call; call it once
+    /// at startup, and again after the adapter changed.
+    ///
+    /// ```no_run
+    /// use wifi_rs::WiFi;
+    ///
+    /// let wifi = WiFi::new();
+    /// let capabilities = wifi.capabilities();
+    /// if !capabilities.can_create_hotspot {
+    ///     println!("Hotspots need an adapter with AP mode and admin rights");
+    /// }
+    /// ```
+    pub fn capabilities(&self) -> Capabilities {
+        let interfaces = self.interfaces();
+        let adapter = interfaces
+            .iter()
+            .find(|info| self.interface.as_deref() == Some(info.name.as_str()))
+            .or_else(|| interfaces.iter().find(|info| info.default));
+        let has_admin = self.has_admin_privileges();
+        let can_scan = adapter.is_some() && !self.manager.is_null();
+        // NetworkManager connects on behalf of unprivileged users
+        let unprivileged_connect =
+            !cfg!(target_os = "linux") || self.linux_backend() == Some(LinuxBackend::NetworkManager);
+        Capabilities {
+            can_scan,
+            can_connect: can_scan && (has_admin || unprivileged_connect),
+            can_create_hotspot: can_scan && has_admin && adapter.is_some_and(|info| info.capabilities.access_point),
+            has_admin,
+            monitor_mode_supported: adapter.is_some_and(|info| info.capabilities.monitor),
+            bands_supported: adapter.map(|info| info.capabilities.bands.clone()).unwrap_or_default(),
+        }
+    }
+
+    /// Name of the station interface the instance operates on, e.g.
+    /// `"wlan0"`, or `None` if the platform does not name its interfaces.
+    pub fn interface_name(&self) -> Option<&str> {
+        self.interface.as_deref()
+    }
+
+    /// Scan for networks, one entry per access point (BSSID), so a network
+    /// served by several access points is listed several times. See
+    /// [`scan_grouped`](Self::scan_grouped) for one entry per network.
+    ///
+    /// With [`WiFiBuilder::scan_cache_ttl`] the results of a recent scan are
+    /// returned without scanning again.
+    pub fn scan(&self) -> Vec<NetworkInfo> {
+        self.scan_taken().0
+    }
+
+    /// [`scan`](Self::scan) with the time the results were taken, earlier
+    /// than now when they come from the scan cache.
+    fn scan_taken(&self) -> (Vec<NetworkInfo>, std::time::Instant) {
+        let mut last_scan = self.last_scan.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let (Some(ttl), Some((taken, networks))) = (self.scan_cache_ttl, last_scan.as_ref()) {
+            if taken.elapsed() < ttl {
+                return (networks.clone(), *taken);
+            }
+        }
+        let networks = self.scan_networks();
+        let taken = std::time::Instant::now();
+        if networks.is_empty() {
+            // An empty result is also what a scan finding nothing returns
+            if self.native_failed() {
+                self.events.record(EventKind::ScanFailed);
             }
+        } else if self.scan_cache_ttl.is_some() {
+            *last_scan = Some((taken, networks.clone()));
         }
+        (networks, taken)
     }
-    
-    /// Check if the hardware supports hotspot functionality.
+
+    /// Scan for networks, one entry per network with its access points, so a
+    /// UI can show one row per network.
+    ///
+    /// ```no_run
+    /// use wifi_rs::WiFi;
+    ///
+    /// let wifi = WiFi::new();
+    /// for network in wifi.scan_grouped() {
+    ///     println!("{} ({} access points, best {})", network.ssid, network.aps.len(), network.best_bssid);
+    /// }
+    /// ```
+    pub fn scan_grouped(&self) -> Vec<NetworkGroup> {
+        NetworkGroup::from_scan(self.scan())
+    }
+
+    /// Scan for networks and wait for the platform to report that the scan
+    /// completed, so the results never come from an earlier scan.
+    ///
+    /// [`scan`](Self::scan) falls back to whatever the platform has cached
+    /// when a scan does not finish in time; on Windows, where the WLAN
+    /// service keeps its last list, that can be results several minutes
+    /// old. Bypasses [`WiFiBuilder::scan_cache_ttl`] but refreshes the cache.
     ///
     /// # Returns
     ///
-    /// `true` if the hardware supports creating hotspots, `false` otherwise.
-    pub fn is_hotspot_supported(&self) -> bool {
-        unsafe {
-            wifi_manager_is_hotspot_supported(self.manager)
+    /// The networks seen by this scan.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WifiError::Timeout`] if the scan did not complete,
+    /// [`WifiError::DriverError`] if the driver aborted it, or
+    /// [`WifiError::Unsupported`] if the platform cannot detect the end of
+    /// a scan.
+    pub fn scan_fresh(&self) -> Result<Vec<NetworkInfo>, WifiError> {
+        let mut last_scan = self.last_scan.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let mut raw_networks = std::ptr::null_mut();
+        let mut count: libc::c_int = 0;
+        let scanned = trace::op(self.interface.as_deref(), "scan_fresh", || unsafe {
+            wifi_manager_scan_fresh(self.manager, &mut raw_networks, &mut count)
+        });
+        if !scanned {
+            self.events.record(EventKind::ScanFailed);
+            return Err(self.native_error());
+        }
+        let networks = unsafe { take_networks(raw_networks, count) };
+        if self.scan_cache_ttl.is_some() {
+            *last_scan = Some((std::time::Instant::now(), networks.clone()));
         }
+        Ok(networks)
     }
-    
-    /// Check if a hotspot is currently active.
+
+    /// Scan only some networks, channels or a band, which finishes much
+    /// faster than a full [`scan`](Self::scan) and also finds hidden
+    /// networks given by SSID.
+    ///
+    /// Platforms that cannot direct a scan (currently all but Linux) run a
+    /// full scan and filter the results.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - Networks, channels and band to scan
     ///
     /// # Returns
     ///
-    /// `true` if a hotspot is active, `false` otherwise.
-    pub fn is_hotspot_active(&self) -> bool {
-        unsafe {
-            wifi_manager_is_hotspot_active(self.manager)
+    /// The matching networks, one entry per access point.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WifiError::InvalidArgument`] if a channel does not exist in
+    /// its band or an SSID is empty or longer than 32 bytes, or
+    /// [`WifiError::Cancelled`] if the scan was [cancelled](Self::cancel).
+    pub fn scan_with_options(&self, options: &ScanOptions) -> Result<Vec<NetworkInfo>, WifiError> {
+        let mut frequencies = options.channel_frequencies().ok_or(WifiError::InvalidArgument)?;
+        if let (true, Some(band)) = (frequencies.is_empty(), options.band) {
+            frequencies = self.allowed_channels(band).iter().map(|channel| channel.frequency).collect();
+        }
+        let ssids = options
+            .ssids
+            .iter()
+            .map(|ssid| (1..=32).contains(&ssid.len()).then(|| std::ffi::CString::new(ssid.as_str()).ok()).flatten())
+            .collect::<Option<Vec<_>>>()
+            .ok_or(WifiError::InvalidArgument)?;
+        let ssid_pointers: Vec<*const libc::c_char> = ssids.iter().map(|ssid| ssid.as_ptr()).collect();
+        let raw = scan::RawScanOptions {
+            ssids: ssid_pointers.as_ptr(),
+            ssid_count: ssid_pointers.len() as i32,
+            frequencies: frequencies.as_ptr(),
+            frequency_count: frequencies.len() as i32,
+            passive: options.passive as i32,
+        };
+
+        let mut networks = trace::op(self.interface.as_deref(), "scan_with_options", || unsafe {
+            let mut count: libc::c_int = 0;
+            let raw_networks = wifi_manager_scan_with_timeout(self.manager, &raw, Timeouts::millis(options.timeout), &mut count);
+            take_networks(raw_networks, count)
+        });
+        if networks.is_empty() && self.native_error() == WifiError::Cancelled {
+            return Err(WifiError::Cancelled);
         }
+        // Also covers platforms that ignored the frequencies
+        if let Some(band) = options.band {
+            networks.retain(|network| Band::from_frequency(network.frequency) == Some(band));
+        }
+        Ok(networks)
     }
-    
-    /// Create an unsecured WiFi hotspot with the given SSID.
+
+    fn scan_networks(&self) -> Vec<NetworkInfo> {
+        trace::op(self.interface.as_deref(), "scan", || unsafe {
+            let mut count: libc::c_int = 0;
+            let raw_networks = wifi_manager_scan(self.manager, &mut count);
+            take_networks(raw_networks, count)
+        })
+    }
+
+    /// Read per-channel airtime and noise statistics from the radio.
     ///
-    /// # Arguments
+    /// Measures congestion directly rather than inferring it from the number
+    /// of visible networks. Many drivers only report channels visited during
+    /// the last scan, so call [`scan`](Self::scan) first for full coverage.
     ///
-    /// * `ssid` - The SSID (network name) for the hotspot
+    /// # Returns
+    ///
+    /// One [`ChannelSurvey`] per channel reported by the driver, empty if the
+    /// platform does not provide survey data.
+    pub fn channel_survey(&self) -> Vec<ChannelSurvey> {
+        trace::op(self.interface.as_deref(), "channel_survey", || unsafe {
+            let mut count: libc::c_int = 0;
+            let raw = wifi_manager_channel_survey(self.manager, &mut count);
+            if raw.is_null() || count <= 0 {
+                return Vec::new();
+            }
+            let surveys = std::slice::from_raw_parts(raw, count as usize)
+                .iter()
+                .map(ChannelSurvey::from_raw)
+                .collect();
+            wifi_free_channel_survey(raw);
+            surveys
+        })
+    }
+
+    /// Check every stage from the adapter to the internet and report the
+    /// first one that fails.
+    ///
+    /// Stages are checked in the order of [`Stage::ALL`]: adapter, radio,
+    /// scan, association, authentication, DHCP, DNS, gateway and finally an
+    /// HTTP probe to `connectivitycheck.gstatic.com`. Once a stage fails the
+    /// remaining stages are reported as skipped. The network checks block
+    /// for up to a few seconds each.
     ///
     /// # Returns
     ///
-    /// `true` if the hotspot was created successfully, `false` otherwise.
+    /// A [`DiagnosticReport`]; use [`DiagnosticReport::first_failure`] to
+    /// find where the connection breaks.
+    pub fn diagnose(&self) -> DiagnosticReport {
+        diag::run(self.link_state(), || self.scan())
+    }
+
+    fn link_state(&self) -> Option<diag::LinkState> {
+        trace::op(self.interface.as_deref(), "link_diagnostics", || unsafe {
+            let mut raw = diag::RawLinkDiagnostics::zeroed();
+            if wifi_manager_get_link_diagnostics(self.manager, &mut raw) {
+                Some(diag::LinkState::from_raw(&raw))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// IP configuration of the wireless interface: its addresses, default
+    /// gateways, DNS servers and, where the address was leased over DHCP,
+    /// the lease.
     ///
-    /// # Note
+    /// # Returns
     ///
-    /// This operation typically requires administrative privileges.
-    pub fn create_hotspot(&self, ssid: &str) -> bool {
-        unsafe {
-            let ssid = std::ffi::CString::new(ssid).unwrap();
-            wifi_manager_create_hotspot(self.manager, ssid.as_ptr())
+    /// `None` if there is no wireless interface or the platform cannot read
+    /// its configuration. The lists are empty while no address is assigned.
+    ///
+    /// ```no_run
+    /// use wifi_rs::WiFi;
+    ///
+    /// let wifi = WiFi::new();
+    /// if let Some(lease) = wifi.ip_info().and_then(|info| info.dhcp) {
+    ///     println!("lease expires in {:?}", lease.remaining());
+    /// }
+    /// ```
+    pub fn ip_info(&self) -> Option<IpInfo> {
+        trace::op(self.interface.as_deref(), "ip_info", || unsafe {
+            let mut raw = ip::RawIpInfo::zeroed();
+            if wifi_manager_get_ip_info(self.manager, &mut raw) {
+                Some(IpInfo::from_raw(&raw))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Recommend the least congested channel of `band` for a hotspot.
+    ///
+    /// Scans, then scores each candidate channel by the signal strength of
+    /// the networks overlapping it (2.4 GHz networks up to four channels away
+    /// count partially) and, where the driver reports it, the measured
+    /// airtime utilization from [`channel_survey`](Self::channel_survey).
+    /// Where the platform reports the regulatory rules, channels that are
+    /// not permitted or require radar detection are skipped.
+    ///
+    /// # Returns
+    ///
+    /// The best channel, or `None` if `band` has no candidate channels.
+    pub fn recommend_channel(&self, band: Band) -> Option<ChannelRecommendation> {
+        let allowed = self.allowed_channels(band);
+        let usable = |number: i32| {
+            allowed.is_empty() || allowed.iter().any(|c| c.number == number && c.hotspot_usable())
+        };
+        self.analyze_channels().recommend_where(band, usable)
+    }
+
+    /// Scan and read the channel survey, and aggregate both into
+    /// per-channel statistics.
+    ///
+    /// Add further scans with [`ChannelAnalyzer::add_scan`] for a more
+    /// complete picture.
+    pub fn analyze_channels(&self) -> ChannelAnalyzer {
+        let mut analyzer = ChannelAnalyzer::new();
+        analyzer.add_scan(&self.scan()).set_surveys(&self.channel_survey());
+        analyzer
+    }
+
+    /// Estimate the device's position from the access points around it.
+    ///
+    /// Scans and hands the visible BSSIDs with their signal strengths to
+    /// `provider`, usually a [`LocationService`](geolocation::LocationService),
+    /// which leaves out hidden networks and networks whose SSID ends in
+    /// `_nomap`. Results from the [scan cache](WiFiBuilder::scan_cache_ttl)
+    /// are passed with their age.
+    ///
+    /// # Errors
+    ///
+    /// Returns the provider's error; for a `LocationService`,
+    /// [`GeolocationError::TooFewAccessPoints`](geolocation::GeolocationError::TooFewAccessPoints)
+    /// if fewer than two usable access points are visible, otherwise the
+    /// service's or the transport's error.
+    #[cfg(feature = "geolocation")]
+    pub fn locate(
+        &self,
+        provider: &impl geolocation::LocationProvider,
+    ) -> Result<geolocation::Location, geolocation::GeolocationError> {
+        let (networks, taken) = self.scan_taken();
+        provider.estimate(&networks, taken.elapsed())
+    }
+
+    /// Channels of `band` the current regulatory domain permits on this
+    /// adapter, with their power limit and restrictions.
+    ///
+    /// # Returns
+    ///
+    /// The channels in ascending order, empty if the platform does not
+    /// report them.
+    pub fn allowed_channels(&self, band: Band) -> Vec<ChannelInfo> {
+        trace::op(self.interface.as_deref(), "allowed_channels", || unsafe {
+            let mut count: libc::c_int = 0;
+            let raw = wifi_manager_get_allowed_channels(self.manager, &mut count);
+            if raw.is_null() || count <= 0 {
+                return Vec::new();
+            }
+            let mut channels: Vec<ChannelInfo> = std::slice::from_raw_parts(raw, count as usize)
+                .iter()
+                .map(ChannelInfo::from_raw)
+                .filter(|c| Band::from_frequency(c.frequency) == Some(band))
+                .collect();
+            wifi_free_channel_info(raw);
+            channels.sort_by_key(|c| c.frequency);
+            channels
+        })
+    }
+
+    /// Scan and audit the security of the surrounding networks.
+    ///
+    /// Reports WEP/WPA1 and open networks, WPA2/WPA3 access points without
+    /// Protected Management Frames, WPS-enabled access points and SSIDs that
+    /// look like vendor defaults. PMF and WPS are only checked on platforms
+    /// that report them in scan results (currently Linux).
+    ///
+    /// # Returns
+    ///
+    /// A [`SecurityAudit`] with issues ordered by severity.
+    pub fn security_audit(&self) -> SecurityAudit {
+        SecurityAudit::from_networks(&self.scan())
+    }
+
+    /// Connect to a WPA2-Personal or open network.
+    ///
+    /// # Arguments
+    ///
+    /// * `ssid` - The SSID of the network to join
+    /// * `password` - The passphrase, or `None` for an open network
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WifiError::InvalidCredentials`] if the network rejected the
+    /// password, [`WifiError::Timeout`] if the network could not be joined in
+    /// time, [`WifiError::PermissionDenied`] if the process may not manage
+    /// the adapter, [`WifiError::NetworkUnusable`] if the adapter joined but
+    /// DHCP failed, or another [`WifiError`] the platform reported.
+    pub fn connect(&self, ssid: &str, password: Option<&str>) -> Result<(), WifiError> {
+        if let Some(timeout) = self.timeouts.connect {
+            let options = password.map_or_else(ConnectOptions::new, ConnectOptions::password);
+            return self.connect_with(ssid, &options.timeout(timeout));
         }
+        let connected = self.connect_psk(ssid, password.map(SecretString::from).as_ref())?;
+        self.finish_connect(ssid, connected, false)
     }
-    
-    /// Stop the active hotspot.
+
+    /// Why the last native call that returned false failed, as reported by
+    /// the native layer. The native layer keeps it per thread, so this
+    /// must run on the thread that made the call.
+    fn native_error(&self) -> WifiError {
+        if self.manager.is_null() {
+            return WifiError::AdapterNotFound;
+        }
+        let code = unsafe { wifi_manager_get_last_error(self.manager) };
+        WifiError::from_code(code).unwrap_or(WifiError::OperationFailed)
+    }
+
+    /// Whether the native layer reported an error for the last call, for
+    /// calls like scans whose result cannot tell failure apart.
+    fn native_failed(&self) -> bool {
+        self.manager.is_null() || unsafe { wifi_manager_get_last_error(self.manager) } != 0
+    }
+
+    /// Turn the result of a native call into a `Result`, fetching the
+    /// reason for a failure.
+    fn native_result(&self, ok: bool) -> Result<(), WifiError> {
+        if ok {
+            Ok(())
+        } else {
+            Err(self.native_error())
+        }
+    }
+
+    /// Run a native call that reports failures through the last error in a
+    /// `wifi_op` span, so the span records the error code as well.
+    fn native_op(&self, op: &'static str, f: impl FnOnce() -> bool) -> Result<(), WifiError> {
+        trace::op(self.interface.as_deref(), op, || self.native_result(f()))
+    }
+
+    /// Attribute the outcome of a connect attempt and record it in the event log.
+    fn finish_connect(&self, ssid: &str, connected: bool, verify_network: bool) -> Result<(), WifiError> {
+        let failure = if connected && !verify_network {
+            None
+        } else {
+            self.link_state().and_then(|link| diag::classify(&link, connected))
+        };
+
+        let ssid = ssid.to_string();
+        let error = match failure {
+            Some(failure) => WifiError::NetworkUnusable(failure),
+            None if connected => {
+                self.events.set_status(ConnectionStatus::Connected);
+                self.events.record(EventKind::Connected { ssid });
+                return Ok(());
+            }
+            None => self.native_error(),
+        };
+        self.events.record_failure(ssid, error);
+        Err(error)
+    }
+
+    fn connect_psk(&self, ssid: &str, password: Option<&SecretString>) -> Result<bool, WifiError> {
+        let c_ssid = std::ffi::CString::new(ssid)?;
+        let password = password.map(SecretString::to_c_string).transpose()?;
+        Ok(trace::op(self.interface.as_deref(), "connect", || unsafe {
+            trace::ssid(ssid);
+            wifi_manager_connect(
+                self.manager,
+                c_ssid.as_ptr(),
+                password.as_ref().map_or(std::ptr::null(), |p| p.as_ptr())
+            )
+        }))
+    }
+
+    fn connect_psk_with(&self, ssid: &str, password: Option<&SecretString>, options: &ConnectOptions, frequencies: &[i32]) -> Result<bool, WifiError> {
+        let c_ssid = std::ffi::CString::new(ssid)?;
+        let password = password.map(SecretString::to_c_string).transpose()?;
+        let bssid = options.bssid.as_deref().map(std::ffi::CString::new).transpose()?;
+        let addressing = options.static_ip_strings();
+        let raw = options.raw(frequencies, bssid.as_deref(), &addressing);
+        Ok(trace::op(self.interface.as_deref(), "connect", || unsafe {
+            trace::ssid(ssid);
+            wifi_manager_connect_with_options(
+                self.manager,
+                c_ssid.as_ptr(),
+                password.as_ref().map_or(std::ptr::null(), |p| p.as_ptr()),
+                &raw
+            )
+        }))
+    }
+
+    /// Join the network encoded in a WiFi QR code, e.g.
+    /// `WIFI:T:WPA;S:Office;P:secret;;`. See [`NetworkCredentials`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WifiError::InvalidArgument`] if `qr` is not a WiFi QR code
+    /// payload, or an error as for [`connect_with`](Self::connect_with).
+    pub fn connect_from_qr(&self, qr: &str) -> Result<(), WifiError> {
+        let credentials = NetworkCredentials::from_wifi_qr_string(qr)?;
+        self.connect_with(&credentials.ssid, &credentials.connect_options())
+    }
+
+    /// Connect to a network using the given options.
+    ///
+    /// # Arguments
+    ///
+    /// * `ssid` - The SSID of the network to join
+    /// * `options` - Credentials and connection settings
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WifiError::Unsupported`] if the platform cannot authenticate
+    /// with the requested EAP method, an ephemeral enterprise connection was
+    /// requested, or static addressing was requested for an enterprise
+    /// network or on a platform that cannot apply it,
+    /// [`WifiError::NetworkUnusable`] if the adapter joined but
+    /// DHCP failed (or, with [`ConnectOptions::verify_network`], DNS or the
+    /// gateway did not work), [`WifiError::Timeout`] if the network was not
+    /// joined within the [`ConnectOptions::timeout`],
+    /// [`WifiError::InvalidArgument`] if the [`ConnectOptions::bssid`] is not
+    /// a MAC address, a static prefix length is too long for its address, or
+    /// [enterprise](ConnectOptions::enterprise) settings that validate the
+    /// server have neither a CA certificate nor a pinned server certificate,
+    /// or [`WifiError::OperationFailed`] if the connection could not be
+    /// initiated or a [`BandPolicy`] that excludes 2.4 GHz found no 5 or
+    /// 6 GHz access point.
+    pub fn connect_with(&self, ssid: &str, options: &ConnectOptions) -> Result<(), WifiError> {
+        if !options.has_valid_bssid() || !options.has_valid_static_ip() {
+            return Err(WifiError::InvalidArgument);
+        }
+        let with_timeout;
+        let options = match self.timeouts.connect {
+            Some(timeout) if options.timeout.is_none() => {
+                with_timeout = options.clone().timeout(timeout);
+                &with_timeout
+            }
+            _ => options,
+        };
+        let initiated = match &options.eap {
+            Some(_) if options.ephemeral || options.has_static_ip() => return Err(WifiError::Unsupported),
+            Some(config) => {
+                if !config.has_trust_anchor() {
+                    return Err(WifiError::InvalidArgument);
+                }
+                if !self.is_eap_method_supported(config.eap.method()) {
+                    return Err(WifiError::Unsupported);
+                }
+                let c_ssid = std::ffi::CString::new(ssid)?;
+                let strings = eap::EapStrings::new(config)?;
+                let raw = strings.raw();
+                trace::op(self.interface.as_deref(), "connect_enterprise", || unsafe {
+                    trace::ssid(ssid);
+                    wifi_manager_connect_enterprise(self.manager, c_ssid.as_ptr(), &raw)
+                })
+            }
+            None => {
+                let frequencies = match options.band_policy {
+                    Some(policy) => match policy.frequencies(ssid, &self.scan()) {
+                        Some(frequencies) => frequencies,
+                        None if policy.allow_2ghz => Vec::new(),
+                        None => {
+                            self.events.record_failure(ssid.to_string(), WifiError::OperationFailed);
+                            return Err(WifiError::OperationFailed);
+                        }
+                    },
+                    None => Vec::new(),
+                };
+                self.connect_psk_with(ssid, options.password.as_ref(), options, &frequencies)?
+            }
+        };
+        self.finish_connect(ssid, initiated, options.verify_network)
+    }
+
+    /// Connect and block until the network is usable or the attempt has
+    /// failed.
+    ///
+    /// Unlike [`connect`](Self::connect), which returns once the platform
+    /// has accepted the request, this waits for an IP address and reports
+    /// why the attempt failed.
+    ///
+    /// # Arguments
+    ///
+    /// * `ssid` - The SSID of the network to join
+    /// * `password` - Optional password for secured networks, wiped from
+    ///   memory once the attempt ends
+    /// * `timeout` - How long to wait for the connection to complete
     ///
     /// # Returns
     ///
-    /// `true` if the hotspot was stopped successfully or if no hotspot was active, `false` otherwise.
-    pub fn stop_hotspot(&self) -> bool {
-        unsafe {
-            wifi_manager_stop_hotspot(self.manager)
+    /// A [`ConnectionResult`] with the associated access point, or the
+    /// failure reason. A failed attempt is followed by a scan to tell an out
+    /// of range network from other timeouts.
+    pub fn connect_and_wait(&self, ssid: &str, password: Option<impl Into<SecretString>>, timeout: std::time::Duration) -> ConnectionResult {
+        let start = std::time::Instant::now();
+        let wait = self.cancel.begin_wait();
+        let options = match password {
+            Some(password) => ConnectOptions::password(password),
+            None => ConnectOptions::new(),
+        }
+        .timeout(timeout);
+
+        let error = match self.connect_with(ssid, &options) {
+            // Platforms that connect asynchronously return before the link is up
+            Ok(()) => loop {
+                let detail = self.status_detailed();
+                if detail.has_ip() {
+                    return ConnectionResult::Connected { bssid: detail.bssid, elapsed: start.elapsed() };
+                }
+                if detail.status == ConnectionStatus::Error {
+                    break self.native_error();
+                }
+                if wait.cancelled() {
+                    let _ = self.disconnect();
+                    break WifiError::Cancelled;
+                }
+                if start.elapsed() >= timeout {
+                    break match detail.sub_state {
+                        SubState::ObtainingIp => WifiError::NetworkUnusable(NetworkFailure::DhcpTimeout),
+                        SubState::Authenticating => WifiError::InvalidCredentials,
+                        _ => WifiError::Timeout,
+                    };
+                }
+                std::thread::sleep(std::time::Duration::from_millis(250));
+            },
+            Err(error) => error,
+        };
+
+        match error {
+            WifiError::InvalidCredentials => ConnectionResult::AuthFailed,
+            WifiError::NetworkUnusable(NetworkFailure::DhcpTimeout) => ConnectionResult::DhcpFailed,
+            WifiError::Timeout | WifiError::OperationFailed if !self.scan().iter().any(|n| n.ssid == ssid) => {
+                ConnectionResult::NetworkNotFound
+            }
+            WifiError::Timeout => ConnectionResult::Timeout,
+            error => ConnectionResult::Failed(error),
         }
     }
+
+    /// Join a WPA2/WPA3-Enterprise (802.1X) network, such as a corporate
+    /// PEAP or EAP-TLS network. Shorthand for [`connect_with`](Self::connect_with)
+    /// and [`ConnectOptions::enterprise`].
+    ///
+    /// # Arguments
+    ///
+    /// * `ssid` - The SSID of the network to join
+    /// * `config` - EAP method, credentials and server validation, or a bare
+    ///   [`Eap`] for default validation
+    ///
+    /// Only supported on Linux, where wpa_supplicant performs the 802.1X
+    /// exchange.
+    ///
+    /// # Errors
+    ///
+    /// As for [`connect_with`](Self::connect_with), and
+    /// [`WifiError::Unsupported`] on Windows and macOS.
+    pub fn connect_enterprise(&self, ssid: &str, config: impl Into<EnterpriseConfig>) -> Result<(), WifiError> {
+        self.connect_with(ssid, &ConnectOptions::enterprise(config))
+    }
+
+    /// Check whether the platform has a profile store to connect from:
+    /// WLAN profiles on Windows, NetworkManager connections on Linux.
+    pub fn is_profile_store_supported(&self) -> bool {
+        unsafe { wifi_manager_is_profile_store_supported(self.manager) }
+    }
+
+    /// Connect with the credentials and settings of a saved profile, as
+    /// `netsh wlan connect name=...` or `nmcli connection up id ...` would.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the profile, often but not necessarily the SSID
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WifiError::Unsupported`] if the platform has no profile
+    /// store, [`WifiError::ProfileNotFound`] if no WiFi profile is called
+    /// `name`, or [`WifiError::OperationFailed`] if the connection could not
+    /// be established.
+    pub fn connect_profile(&self, name: &str) -> Result<(), WifiError> {
+        let c_name = self.existing_profile(name)?;
+        let connected = trace::op(self.interface.as_deref(), "connect_profile", || unsafe {
+            wifi_manager_connect_profile(self.manager, c_name.as_ptr())
+        });
+        // Record the network under its SSID where the link reports it
+        let ssid = connected
+            .then(|| self.sample_connection())
+            .flatten()
+            .map_or_else(|| name.to_string(), |sample| sample.ssid);
+        self.finish_connect(&ssid, connected, false)
+    }
+
+    /// List the WiFi profiles in the platform's profile store.
+    ///
+    /// # Returns
+    ///
+    /// One [`SavedProfile`] per stored network, empty if there are none or
+    /// the platform has no profile store.
+    pub fn list_profiles(&self) -> Vec<SavedProfile> {
+        trace::op(self.interface.as_deref(), "list_profiles", || unsafe {
+            let mut count: libc::c_int = 0;
+            let raw = wifi_manager_list_profiles(self.manager, &mut count);
+            if raw.is_null() || count <= 0 {
+                return Vec::new();
+            }
+            let profiles = std::slice::from_raw_parts(raw, count as usize)
+                .iter()
+                .map(SavedProfile::from_raw)
+                .collect();
+            wifi_free_profile_list(raw);
+            profiles
+        })
+    }
+
+    /// Store credentials for a network in the platform's profile store
+    /// without connecting, in a profile named after the SSID. A profile of
+    /// the same name is replaced.
+    ///
+    /// # Arguments
+    ///
+    /// * `ssid` - Network to save
+    /// * `password` - WPA2 passphrase, or `None` for an open network. It is
+    ///   wiped from memory once handed to the platform.
+    /// * `auto_connect` - Whether the platform joins the network
+    ///   automatically when in range
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WifiError::Unsupported`] if the platform has no profile
+    /// store, or the reason the profile could not be stored.
+    pub fn save_profile(&self, ssid: &str, password: Option<impl Into<SecretString>>, auto_connect: bool) -> Result<(), WifiError> {
+        if !self.is_profile_store_supported() {
+            return Err(WifiError::Unsupported);
+        }
+        let c_ssid = std::ffi::CString::new(ssid)?;
+        let c_password = password.map(|password| password.into().to_c_string()).transpose()?;
+        self.native_op("save_profile", || unsafe {
+            trace::ssid(ssid);
+            wifi_manager_save_profile(
+                self.manager,
+                c_ssid.as_ptr(),
+                c_password.as_ref().map_or(std::ptr::null(), |p| p.as_ptr()),
+                auto_connect,
+            )
+        })
+    }
+
+    /// Remove a profile from the platform's profile store, so the network
+    /// is no longer joined automatically and its credentials are discarded.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WifiError::Unsupported`] if the platform has no profile
+    /// store, [`WifiError::ProfileNotFound`] if no WiFi profile is called
+    /// `name`, or the reason it could not be removed.
+    pub fn forget_network(&self, name: &str) -> Result<(), WifiError> {
+        let c_name = self.existing_profile(name)?;
+        self.native_op("forget_network", || unsafe {
+            wifi_manager_forget_profile(self.manager, c_name.as_ptr())
+        })
+    }
+
+    /// Enable or disable automatic connection for a saved profile.
+    ///
+    /// # Errors
+    ///
+    /// As for [`forget_network`](Self::forget_network).
+    pub fn set_auto_connect(&self, name: &str, enabled: bool) -> Result<(), WifiError> {
+        let c_name = self.existing_profile(name)?;
+        self.native_op("set_auto_connect", || unsafe {
+            wifi_manager_set_profile_auto_connect(self.manager, c_name.as_ptr(), enabled)
+        })
+    }
+
+    /// Name of a profile that exists in the profile store, as a C string.
+    fn existing_profile(&self, name: &str) -> Result<std::ffi::CString, WifiError> {
+        if !self.is_profile_store_supported() {
+            return Err(WifiError::Unsupported);
+        }
+        let c_name = std::ffi::CString::new(name).map_err(|_| WifiError::ProfileNotFound)?;
+        if !unsafe { wifi_manager_has_profile(self.manager, c_name.as_ptr()) } {
+            return Err(WifiError::ProfileNotFound);
+        }
+        Ok(c_name)
+    }
+
+    /// Connect using WPS push-button configuration (PBC).
+    ///
+    /// Press the WPS button on the router before or shortly after calling
+    /// this. The call blocks until the credentials have been received and the
+    /// connection is established, or the two-minute WPS walk time expires.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WifiError::Timeout`] if no registrar responded in time, or
+    /// [`WifiError::OperationFailed`] if the exchange failed or the platform
+    /// has no WPS support. Use [`connect_wps_pbc_with`](Self::connect_wps_pbc_with)
+    /// to learn why an exchange failed.
+    pub fn connect_wps_pbc(&self) -> Result<(), WifiError> {
+        self.connect_wps_pbc_with(|_| {})
+    }
+
+    /// Connect using WPS push-button configuration, reporting each stage of
+    /// the exchange to `on_progress`.
+    ///
+    /// `on_progress` runs on the calling thread while this call blocks and
+    /// must not call back into this `WiFi`. Only Linux follows the exchange;
+    /// elsewhere it is never called.
+    ///
+    /// ```no_run
+    /// use wifi_rs::wps::{WpsFailure, WpsProgress};
+    /// use wifi_rs::WiFi;
+    ///
+    /// let wifi = WiFi::new();
+    /// let result = wifi.connect_wps_pbc_with(|progress| match progress {
+    ///     WpsProgress::Searching => println!("press the WPS button on the router"),
+    ///     WpsProgress::Failed(WpsFailure::SessionOverlap) => println!("several routers are in WPS mode"),
+    ///     other => println!("{:?}", other),
+    /// });
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// As [`connect_wps_pbc`](Self::connect_wps_pbc).
+    pub fn connect_wps_pbc_with(&self, mut on_progress: impl FnMut(wps::WpsProgress)) -> Result<(), WifiError> {
+        let mut callback: &mut dyn FnMut(wps::WpsProgress) = &mut on_progress;
+        self.native_op("connect_wps_pbc", || unsafe {
+            wifi_manager_connect_wps_pbc_with_progress(
+                self.manager,
+                self.timeouts.wps_seconds(),
+                wps::on_progress,
+                &mut callback as *mut &mut dyn FnMut(wps::WpsProgress) as *mut libc::c_void,
+            )
+        })
+    }
+
+    /// Connect using a WPS PIN.
+    ///
+    /// Enter `pin` on the access point's registrar (usually its web interface),
+    /// or pass the PIN printed on the access point label. The call blocks until
+    /// the connection is established or the two-minute WPS walk time expires.
+    ///
+    /// WPS PIN authentication is weak: the eight-digit PIN is verified in two
+    /// halves and can be brute-forced in hours against access points without
+    /// lockout. Prefer a freshly generated PIN from [`wps::generate_pin`] over a
+    /// static label PIN, and keep WPS disabled on the access point otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `bssid` - BSSID of the access point, or `None` to accept any AP in a PIN session
+    /// * `pin` - Four or eight digit WPS PIN
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WifiError::InvalidCredentials`] if the registrar rejected
+    /// the PIN, [`WifiError::Timeout`] if no registrar responded in time,
+    /// [`WifiError::InvalidArgument`] if the PIN is malformed, or
+    /// [`WifiError::OperationFailed`] if the exchange failed otherwise or the
+    /// platform has no WPS support.
+    pub fn connect_wps_pin(&self, bssid: Option<&str>, pin: &str) -> Result<(), WifiError> {
+        self.connect_wps_pin_with(bssid, pin, |_| {})
+    }
+
+    /// Connect using a WPS PIN, reporting each stage of the exchange to
+    /// `on_progress`. See [`connect_wps_pbc_with`](Self::connect_wps_pbc_with)
+    /// for the callback's restrictions.
+    ///
+    /// # Errors
+    ///
+    /// As [`connect_wps_pin`](Self::connect_wps_pin).
+    pub fn connect_wps_pin_with(&self, bssid: Option<&str>, pin: &str, mut on_progress: impl FnMut(wps::WpsProgress)) -> Result<(), WifiError> {
+        if !wps::is_valid_pin(pin) {
+            return Err(WifiError::InvalidArgument);
+        }
+        let bssid = bssid.map(std::ffi::CString::new).transpose()?;
+        let pin = std::ffi::CString::new(pin)?;
+        let mut callback: &mut dyn FnMut(wps::WpsProgress) = &mut on_progress;
+        self.native_op("connect_wps_pin", || unsafe {
+            wifi_manager_connect_wps_pin_with_progress(
+                self.manager,
+                bssid.as_ref().map_or(std::ptr::null(), |b| b.as_ptr()),
+                pin.as_ptr(),
+                self.timeouts.wps_seconds(),
+                wps::on_progress,
+                &mut callback as *mut &mut dyn FnMut(wps::WpsProgress) as *mut libc::c_void,
+            )
+        })
+    }
+
+    /// Enable or disable probe-request privacy.
+    ///
+    /// While enabled, scanning and connecting use randomized MAC addresses and
+    /// never send directed probe requests for saved SSIDs, so a device running
+    /// continuous scans does not broadcast its identity or network history.
+    /// Takes effect from the next connection.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WifiError::Unsupported`] if the platform cannot control
+    /// probe behaviour, or another [`WifiError`] the platform reported if
+    /// the setting could not be applied.
+    pub fn set_probe_privacy(&self, enabled: bool) -> Result<(), WifiError> {
+        self.native_op("set_probe_privacy", || unsafe { wifi_manager_set_probe_privacy(self.manager, enabled) })
+    }
+
+    /// Check whether probe-request privacy is enabled.
+    pub fn is_probe_privacy_enabled(&self) -> bool {
+        unsafe { wifi_manager_is_probe_privacy_enabled(self.manager) }
+    }
+
+    /// Start capturing raw 802.11 frames in monitor mode.
+    ///
+    /// A monitor interface is created on the same radio and removed again
+    /// when the returned [`Capture`](capture::Capture) is dropped. Only one
+    /// capture can run at a time. Requires root and a driver with monitor
+    /// mode support; Linux only for now.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - Channel, frame types and monitor interface name
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WifiError::Unsupported`] if the platform or the adapter's
+    /// driver cannot capture in monitor mode,
+    /// [`WifiError::PermissionDenied`] without the required privileges,
+    /// [`WifiError::DriverError`] if the monitor interface could not be
+    /// created, or [`WifiError::OperationFailed`] if a capture is already
+    /// running.
+    #[cfg(feature = "capture")]
+    pub fn start_capture(&self, config: &capture::CaptureConfig) -> Result<capture::Capture<'_>, WifiError> {
+        capture::Capture::start(self, config)
+    }
+
+    /// Check whether the platform can authenticate with an EAP method.
+    ///
+    /// SIM-based methods additionally require SIM access (e.g. a PC/SC
+    /// reader on Linux), so this should be probed before offering them.
+    pub fn is_eap_method_supported(&self, method: EapMethod) -> bool {
+        unsafe {
+            wifi_manager_is_eap_method_supported(self.manager, method.code())
+        }
+    }
+
+    /// Install a Passpoint (Hotspot 2.0) profile.
+    ///
+    /// Once installed, the platform automatically joins hotspots operated by
+    /// the home provider or its roaming partners.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WifiError::Unsupported`] if the platform has no Passpoint
+    /// support, [`WifiError::InvalidArgument`] if a string of the profile
+    /// contains a NUL byte, or the reason the profile could not be stored.
+    pub fn install_passpoint_profile(&self, profile: &PasspointProfile) -> Result<(), WifiError> {
+        passpoint::with_raw_profile(profile, |raw| {
+            self.native_op("install_passpoint_profile", || unsafe { wifi_manager_install_passpoint(self.manager, raw) })
+        })?
+    }
+
+    /// Remove the Passpoint profile of the provider identified by `domain`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WifiError::Unsupported`] if the platform has no Passpoint
+    /// support, [`WifiError::OperationFailed`] if no profile is installed
+    /// for `domain`, or another reason it could not be removed.
+    pub fn remove_passpoint_profile(&self, domain: &str) -> Result<(), WifiError> {
+        let domain = std::ffi::CString::new(domain)?;
+        self.native_op("remove_passpoint_profile", || unsafe { wifi_manager_remove_passpoint(self.manager, domain.as_ptr()) })
+    }
+
+    /// Check whether Wi-Fi Aware (NAN) service discovery is available.
+    pub fn is_nan_supported(&self) -> bool {
+        unsafe { wifi_manager_is_nan_supported(self.manager) }
+    }
+
+    /// Start advertising a Wi-Fi Aware service.
+    ///
+    /// Subscribers that find it show up as [`nan::NanEvent::Discovered`]
+    /// from [`nan_next_event`](Self::nan_next_event).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WifiError::Unsupported`] if Wi-Fi Aware is not available, or
+    /// [`WifiError::OperationFailed`] if the publication was rejected.
+    pub fn nan_publish(&self, publish: &nan::NanPublish) -> Result<nan::NanHandle, WifiError> {
+        if !self.is_nan_supported() {
+            return Err(WifiError::Unsupported);
+        }
+        let raw = publish.raw();
+        trace::op(self.interface.as_deref(), "nan_publish", || match unsafe { wifi_manager_nan_publish(self.manager, &raw) } {
+            id if id > 0 => Ok(nan::NanHandle(id)),
+            _ => Err(WifiError::OperationFailed),
+        })
+    }
+
+    /// Start looking for a Wi-Fi Aware service.
+    ///
+    /// Matching publishers show up as [`nan::NanEvent::Discovered`] from
+    /// [`nan_next_event`](Self::nan_next_event).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WifiError::Unsupported`] if Wi-Fi Aware is not available, or
+    /// [`WifiError::OperationFailed`] if the subscription was rejected.
+    pub fn nan_subscribe(&self, subscribe: &nan::NanSubscribe) -> Result<nan::NanHandle, WifiError> {
+        if !self.is_nan_supported() {
+            return Err(WifiError::Unsupported);
+        }
+        let raw = subscribe.raw();
+        trace::op(self.interface.as_deref(), "nan_subscribe", || match unsafe { wifi_manager_nan_subscribe(self.manager, &raw) } {
+            id if id > 0 => Ok(nan::NanHandle(id)),
+            _ => Err(WifiError::OperationFailed),
+        })
+    }
+
+    /// Stop a publication or subscription.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WifiError::Unsupported`] without Wi-Fi Aware support,
+    /// [`WifiError::OperationFailed`] if the handle is unknown, or another
+    /// [`WifiError`] the platform reported if the session could not be
+    /// cancelled.
+    pub fn nan_cancel(&self, handle: nan::NanHandle) -> Result<(), WifiError> {
+        self.native_op("nan_cancel", || unsafe { wifi_manager_nan_cancel(self.manager, handle.0) })
+    }
+
+    /// Send a follow-up message to a discovered peer.
+    ///
+    /// # Arguments
+    ///
+    /// * `handle` - The publication or subscription the peer was discovered through
+    /// * `peer` - The peer from the discovery event
+    /// * `data` - Payload of at most [`nan::MAX_SERVICE_INFO`] bytes
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WifiError::OperationFailed`] if the payload is too large,
+    /// [`WifiError::Unsupported`] without Wi-Fi Aware support, or another
+    /// [`WifiError`] the platform reported if the message could not be sent.
+    pub fn nan_send(&self, handle: nan::NanHandle, peer: &nan::NanPeer, data: &[u8]) -> Result<(), WifiError> {
+        if data.len() > nan::MAX_SERVICE_INFO {
+            return Err(WifiError::OperationFailed);
+        }
+        let address = std::ffi::CString::new(peer.address.as_str())?;
+        self.native_op("nan_send", || unsafe {
+            wifi_manager_nan_transmit(
+                self.manager,
+                handle.0,
+                peer.instance_id,
+                address.as_ptr(),
+                data.as_ptr(),
+                data.len() as i32,
+            )
+        })
+    }
+
+    /// Wait up to `timeout` for the next Wi-Fi Aware event.
+    ///
+    /// # Returns
+    ///
+    /// The event, or `None` on timeout or when no session is active.
+    pub fn nan_next_event(&self, timeout: std::time::Duration) -> Option<nan::NanEvent> {
+        let timeout_ms = timeout.as_millis().min(i32::MAX as u128) as i32;
+        trace::op(self.interface.as_deref(), "nan_next_event", || unsafe {
+            let mut raw = nan::RawNanEvent::zeroed();
+            if wifi_manager_nan_next_event(self.manager, timeout_ms, &mut raw) {
+                nan::NanEvent::from_raw(&raw)
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Check whether the adapter can range with 802.11mc Fine Timing
+    /// Measurement.
+    pub fn is_rtt_supported(&self) -> bool {
+        unsafe { wifi_manager_is_rtt_supported(self.manager) }
+    }
+
+    /// Measure the distance to an access point with Fine Timing Measurement.
+    ///
+    /// The access point must act as an FTM responder. Its channel is taken
+    /// from a fresh scan, and the measurement itself takes up to a few
+    /// seconds. No connection to the access point is needed.
+    ///
+    /// # Arguments
+    ///
+    /// * `bssid` - MAC address of the access point
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WifiError::Unsupported`] if the adapter cannot range,
+    /// [`WifiError::OperationFailed`] if the access point was not found or
+    /// did not answer, or another [`WifiError`] the platform reported.
+    pub fn measure_distance(&self, bssid: &str) -> Result<RangingResult, WifiError> {
+        if !self.is_rtt_supported() {
+            return Err(WifiError::Unsupported);
+        }
+        let c_bssid = std::ffi::CString::new(bssid)?;
+        trace::op(self.interface.as_deref(), "measure_distance", || unsafe {
+            let mut raw = rtt::RawRangingResult::zeroed();
+            if wifi_manager_measure_distance(self.manager, c_bssid.as_ptr(), 0, &mut raw) {
+                Ok(RangingResult::from_raw(bssid, &raw))
+            } else {
+                Err(self.native_error())
+            }
+        })
+    }
+
+    /// Check whether the adapter supports TDLS direct links.
+    pub fn is_tdls_supported(&self) -> bool {
+        unsafe { wifi_manager_is_tdls_supported(self.manager) }
+    }
+
+    /// List stations on the current network and send them TDLS discovery
+    /// requests.
+    ///
+    /// Candidates come from the neighbor table, so only stations this
+    /// device has exchanged traffic with are found; the access point itself
+    /// is left out. Blocks for `wait` (rounded up to whole seconds) to give
+    /// peers time to answer.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WifiError::Unsupported`] if the adapter has no TDLS support.
+    pub fn tdls_discover(&self, wait: std::time::Duration) -> Result<Vec<TdlsPeer>, WifiError> {
+        if !self.is_tdls_supported() {
+            return Err(WifiError::Unsupported);
+        }
+        let seconds = wait.as_secs_f64().ceil().clamp(1.0, i32::MAX as f64) as i32;
+        trace::op(self.interface.as_deref(), "tdls_discover", || unsafe {
+            let mut count: libc::c_int = 0;
+            let raw = wifi_manager_tdls_discover(self.manager, seconds, &mut count);
+            if raw.is_null() || count <= 0 {
+                return Ok(Vec::new());
+            }
+            let peers = std::slice::from_raw_parts(raw, count as usize)
+                .iter()
+                .map(TdlsPeer::from_raw)
+                .collect();
+            wifi_free_tdls_peers(raw);
+            Ok(peers)
+        })
+    }
+
+    /// Set up a direct link to a station on the same network, so traffic to
+    /// it no longer passes through the access point.
+    ///
+    /// # Arguments
+    ///
+    /// * `peer_address` - MAC address of the station
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WifiError::InvalidArgument`] if `peer_address` is not a MAC
+    /// address, [`WifiError::Unsupported`] if the adapter has no TDLS
+    /// support, [`WifiError::OperationFailed`] if the peer did not complete
+    /// the setup, e.g. because it lacks TDLS or the access point prohibits
+    /// it, or another [`WifiError`] the platform reported.
+    pub fn tdls_setup(&self, peer_address: &str) -> Result<(), WifiError> {
+        if !connect::is_bssid(peer_address) {
+            return Err(WifiError::InvalidArgument);
+        }
+        if !self.is_tdls_supported() {
+            return Err(WifiError::Unsupported);
+        }
+        let peer = std::ffi::CString::new(peer_address)?;
+        self.native_op("tdls_setup", || unsafe { wifi_manager_tdls_setup(self.manager, peer.as_ptr()) })
+    }
+
+    /// Tear down the direct link to a station.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WifiError::InvalidArgument`] if `peer_address` is not a MAC
+    /// address, [`WifiError::OperationFailed`] if no link to the station
+    /// exists, or another [`WifiError`] the platform reported.
+    pub fn tdls_teardown(&self, peer_address: &str) -> Result<(), WifiError> {
+        if !connect::is_bssid(peer_address) {
+            return Err(WifiError::InvalidArgument);
+        }
+        let peer = std::ffi::CString::new(peer_address)?;
+        self.native_op("tdls_teardown", || unsafe { wifi_manager_tdls_teardown(self.manager, peer.as_ptr()) })
+    }
+
+    /// Check whether the adapter can operate in ad-hoc (IBSS) mode.
+    pub fn is_adhoc_supported(&self) -> bool {
+        unsafe { wifi_manager_is_adhoc_supported(self.manager) }
+    }
+
+    /// Join an ad-hoc network, creating it if no other station is around.
+    ///
+    /// Replaces the current connection; leave the network with
+    /// [`disconnect`](Self::disconnect). No address is configured since
+    /// ad-hoc peers rarely run a DHCP server.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WifiError::Unsupported`] if the adapter has no ad-hoc
+    /// support, or [`WifiError::OperationFailed`] if the channel or
+    /// passphrase is invalid or the network could not be joined.
+    pub fn join_adhoc(&self, config: &AdHocConfig) -> Result<(), WifiError> {
+        if !self.is_adhoc_supported() {
+            return Err(WifiError::Unsupported);
+        }
+        let ssid = std::ffi::CString::new(config.ssid.as_str())?;
+        let password = config.password.as_ref().map(SecretString::to_c_string).transpose()?;
+        let raw = adhoc::RawAdHocConfig {
+            ssid: ssid.as_ptr(),
+            channel: config.channel.min(i32::MAX as u32) as libc::c_int,
+            password: password.as_ref().map_or(std::ptr::null(), |p| p.as_ptr()),
+        };
+        let joined = trace::op(self.interface.as_deref(), "join_adhoc", || unsafe { wifi_manager_join_adhoc(self.manager, &raw) });
+        self.finish_connect(&config.ssid, joined, false)
+    }
+
+    /// Check whether the adapter can operate as an 802.11s mesh point.
+    pub fn is_mesh_supported(&self) -> bool {
+        unsafe { wifi_manager_is_mesh_supported(self.manager) }
+    }
+
+    /// Join the 802.11s mesh `mesh_id`, starting it if no other node is
+    /// around yet.
+    ///
+    /// A separate mesh interface is created for the mesh; the station
+    /// connection is not affected. Addresses on the mesh interface are up to
+    /// the application.
+    ///
+    /// # Arguments
+    ///
+    /// * `mesh_id` - Mesh ID shared by all nodes, 1 to 32 bytes
+    /// * `config` - Channel, SAE password and interface name
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WifiError::Unsupported`] if the adapter cannot operate as a
+    /// mesh point, [`WifiError::OperationFailed`] if the mesh ID is invalid
+    /// or a mesh is already active, or another [`WifiError`] the platform
+    /// reported if the driver refused to join.
+    pub fn mesh_join(&self, mesh_id: &str, config: &mesh::MeshConfig) -> Result<(), WifiError> {
+        if !self.is_mesh_supported() {
+            return Err(WifiError::Unsupported);
+        }
+        let strings = mesh::MeshStrings::new(mesh_id, config).ok_or(WifiError::OperationFailed)?;
+        let raw = strings.raw();
+        self.native_op("mesh_join", || unsafe { wifi_manager_mesh_join(self.manager, &raw) })
+    }
+
+    /// Start a new 802.11s mesh `mesh_id` for other nodes to join.
+    ///
+    /// Like [`mesh_join`](Self::mesh_join), but requires an explicit channel
+    /// so that joining nodes know where to find the mesh.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WifiError::OperationFailed`] if `config` has no channel, and
+    /// the errors of [`mesh_join`](Self::mesh_join) otherwise.
+    pub fn mesh_create(&self, mesh_id: &str, config: &mesh::MeshConfig) -> Result<(), WifiError> {
+        if config.channel.is_none() {
+            return Err(WifiError::OperationFailed);
+        }
+        self.mesh_join(mesh_id, config)
+    }
+
+    /// Leave the mesh and remove the mesh interface. Succeeds if no mesh is
+    /// active.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WifiError::Unsupported`] without mesh support, or another
+    /// [`WifiError`] the platform reported if the mesh interface could not be
+    /// removed.
+    pub fn mesh_leave(&self) -> Result<(), WifiError> {
+        self.native_op("mesh_leave", || unsafe { wifi_manager_mesh_leave(self.manager) })
+    }
+
+    /// Neighbors of this node in the mesh with their peer link state.
+    ///
+    /// # Returns
+    ///
+    /// An empty list if no mesh is active.
+    pub fn mesh_peers(&self) -> Vec<mesh::MeshPeer> {
+        trace::op(self.interface.as_deref(), "mesh_peers", || unsafe {
+            let mut count: libc::c_int = 0;
+            let raw = wifi_manager_mesh_peers(self.manager, &mut count);
+            if raw.is_null() || count <= 0 {
+                return Vec::new();
+            }
+            let peers = std::slice::from_raw_parts(raw, count as usize)
+                .iter()
+                .map(mesh::MeshPeer::from_raw)
+                .collect();
+            wifi_free_mesh_peers(raw);
+            peers
+        })
+    }
+
+    /// Check whether the adapter supports Wi-Fi Direct.
+    pub fn is_p2p_supported(&self) -> bool {
+        unsafe { wifi_manager_is_p2p_supported(self.manager) }
+    }
+
+    /// Search for Wi-Fi Direct devices.
+    ///
+    /// Blocks for `duration` (rounded up to whole seconds) while searching.
+    /// Peers only show up while they are discoverable themselves, e.g. with
+    /// the Wi-Fi Direct settings screen open on a phone.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WifiError::Unsupported`] if the adapter has no Wi-Fi Direct
+    /// support.
+    pub fn p2p_discover(&self, duration: std::time::Duration) -> Result<Vec<p2p::P2pPeer>, WifiError> {
+        if !self.is_p2p_supported() {
+            return Err(WifiError::Unsupported);
+        }
+        let seconds = duration.as_secs_f64().ceil().clamp(1.0, i32::MAX as f64) as i32;
+        trace::op(self.interface.as_deref(), "p2p_discover", || unsafe {
+            let mut count: libc::c_int = 0;
+            let raw = wifi_manager_p2p_discover(self.manager, seconds, &mut count);
+            if raw.is_null() || count <= 0 {
+                return Ok(Vec::new());
+            }
+            let peers = std::slice::from_raw_parts(raw, count as usize)
+                .iter()
+                .map(p2p::P2pPeer::from_raw)
+                .collect();
+            wifi_free_p2p_peers(raw);
+            Ok(peers)
+        })
+    }
+
+    /// Negotiate a Wi-Fi Direct group with a peer and join it.
+    ///
+    /// Blocks until the group is up or the configured timeout expires. As a
+    /// client an address is requested over DHCP; as group owner the
+    /// application must serve addresses itself.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - Peer, authorization method and group owner intent
+    ///
+    /// # Returns
+    ///
+    /// The group that was formed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WifiError::InvalidArgument`] if the peer is not a MAC
+    /// address or the PIN is not all digits, [`WifiError::Unsupported`] if
+    /// the adapter has no Wi-Fi Direct support, [`WifiError::OperationFailed`]
+    /// if negotiation failed or timed out, or another [`WifiError`] the
+    /// platform reported.
+    pub fn p2p_connect(&self, config: &p2p::P2pConnectConfig) -> Result<p2p::P2pGroup, WifiError> {
+        let strings = p2p::P2pConnectStrings::new(config).ok_or(WifiError::InvalidArgument)?;
+        if !self.is_p2p_supported() {
+            return Err(WifiError::Unsupported);
+        }
+        let raw = strings.raw();
+        let timeout = config.timeout.as_secs().clamp(1, i32::MAX as u64) as i32;
+        trace::op(self.interface.as_deref(), "p2p_connect", || unsafe {
+            let mut group = p2p::RawP2pGroup::zeroed();
+            if wifi_manager_p2p_connect(self.manager, &raw, timeout, &mut group) {
+                Ok(p2p::P2pGroup::from_raw(&group))
+            } else {
+                Err(self.native_error())
+            }
+        })
+    }
+
+    /// Leave the current Wi-Fi Direct group.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WifiError::Unsupported`] without Wi-Fi Direct support, or
+    /// another [`WifiError`] the platform reported if the group could not be
+    /// removed.
+    pub fn p2p_disconnect(&self) -> Result<(), WifiError> {
+        self.native_op("p2p_disconnect", || unsafe { wifi_manager_p2p_disconnect(self.manager) })
+    }
+
+    /// Drop the current connection.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WifiError::AdapterNotFound`], [`WifiError::PermissionDenied`]
+    /// or another [`WifiError`] the platform reported if the connection could
+    /// not be dropped.
+    pub fn disconnect(&self) -> Result<(), WifiError> {
+        let disconnected = self.native_op("disconnect", || unsafe { wifi_manager_disconnect(self.manager) });
+        if disconnected.is_ok() {
+            self.events.set_status(ConnectionStatus::Disconnected);
+            self.events.record(EventKind::Disconnected { reason: DisconnectReason::Requested });
+        }
+        disconnected
+    }    pub fn get_status(&self) -> ConnectionStatus {
+        let status = trace::op(self.interface.as_deref(), "get_status", || unsafe {            match wifi_manager_get_status(self.manager) {
+                0 => ConnectionStatus::Connected,
+                1 => ConnectionStatus::Disconnected,
+                2 => ConnectionStatus::Connecting,
+                _ => ConnectionStatus::Error,
+            }
+        });
+        let came_up = self.events.observe_status(status);
+        if status == ConnectionStatus::Connected {
+            let sample = self.sample_connection();
+            if let (true, Some(sample)) = (came_up, sample) {
+                self.events.record(EventKind::Connected { ssid: sample.ssid });
+            }
+        }
+        status
+    }
+
+    /// Connection status with the step of the connection lifecycle, the
+    /// network involved and the last connection error.
+    ///
+    /// Once the adapter has an address this sends the same HTTP probe as
+    /// [`diagnose`](Self::diagnose) to tell working internet access from a
+    /// captive portal or a network without upstream connectivity, so the
+    /// call can block for a few seconds.
+    ///
+    /// ```no_run
+    /// use wifi_rs::{SubState, WiFi};
+    ///
+    /// let wifi = WiFi::new();
+    /// let detail = wifi.status_detailed();
+    /// match (detail.sub_state, detail.ssid, detail.last_error) {
+    ///     (SubState::Disconnected, Some(ssid), Some(error)) => println!("joining {} failed: {}", ssid, error),
+    ///     (state, Some(ssid), _) => println!("{}: {}", ssid, state),
+    ///     (state, None, _) => println!("{}", state),
+    /// }
+    /// ```
+    pub fn status_detailed(&self) -> StatusDetail {
+        let status = self.get_status();
+        StatusDetail::new(status, self.link_state(), self.events.last_error())
+    }
+
+    /// Subscribe to the operating system's connectivity notifications, so
+    /// transitions caused by other software or the user reach the event log
+    /// even between polls. See [`NetworkObserver`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WifiError::Unsupported`] if the platform has no
+    /// notifications or an observer is already active.
+    pub fn observe_network_changes(&self) -> Result<NetworkObserver<'_>, WifiError> {
+        NetworkObserver::start(self)
+    }
+
+    /// Have `listener` called whenever the platform reports a connection
+    /// state change, instead of polling. See [`WifiEventListener`].
+    ///
+    /// # Arguments
+    ///
+    /// * `listener` - Receiver of the notifications, called on a platform
+    ///   thread
+    /// * `signal_threshold_dbm` - Signal level whose crossing is reported as
+    ///   [`WifiNotification::SignalChanged`]
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WifiError::Unsupported`] if the platform cannot push events
+    /// or a listener is already registered.
+    pub fn register_listener(
+        &self,
+        listener: impl WifiEventListener + 'static,
+        signal_threshold_dbm: i32,
+    ) -> Result<ListenerRegistration<'_>, WifiError> {
+        ListenerRegistration::start(self, listener, signal_threshold_dbm)
+    }
+
+    /// Sample the current association, recording a roam if the BSSID changed.
+    fn sample_connection(&self) -> Option<roam::LinkSample> {
+        let sample = trace::op(self.interface.as_deref(), "connection_info", || unsafe {
+            let mut raw = roam::RawConnectionInfo::zeroed();
+            if wifi_manager_get_connection_info(self.manager, &mut raw) {
+                Some(roam::LinkSample::from_raw(&raw))
+            } else {
+                None
+            }
+        });
+        if let Some(roam) = self.roams.observe(sample.clone()) {
+            self.events.record(EventKind::Roamed {
+                ssid: roam.ssid,
+                from_bssid: roam.from_bssid,
+                to_bssid: roam.to_bssid,
+            });
+        }
+        sample
+    }
+
+    /// Live signal strength, noise floor and link speed of the access
+    /// point the device is associated with.
+    ///
+    /// # Returns
+    ///
+    /// `None` while not connected.
+    pub fn current_connection(&self) -> Option<SignalSample> {
+        self.sample_connection().map(SignalSample::from_sample)
+    }
+
+    /// Check whether the radio is on, i.e. neither switched off in software
+    /// nor by a hardware switch or airplane mode key.
+    ///
+    /// Platforms that cannot report the radio state report it as on.
+    pub fn is_radio_enabled(&self) -> bool {
+        unsafe { wifi_manager_is_radio_enabled(self.manager) }
+    }
+
+    /// Switch the radio on or off in software, through rfkill on Linux and
+    /// the WLAN service on Windows.
+    ///
+    /// Switching it off drops the connection. On Linux only the adapter's
+    /// own radio is switched where the kernel reports it, otherwise every
+    /// wireless LAN radio.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WifiError::PermissionDenied`] without the privileges to
+    /// switch the radio, or [`WifiError::OperationFailed`] if a hardware
+    /// switch keeps it off.
+    pub fn set_radio_enabled(&self, enabled: bool) -> Result<(), WifiError> {
+        self.native_op("set_radio_enabled", || unsafe {
+            wifi_manager_set_radio_enabled(self.manager, enabled)
+        })
+    }
+
+    /// Read the current association every `interval` on a background
+    /// thread. See [`SignalMonitor`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WifiError::OperationFailed`] if a monitor is already
+    /// running on this instance.
+    pub fn monitor_signal(&self, interval: std::time::Duration) -> Result<SignalMonitor<'_>, WifiError> {
+        SignalMonitor::start(self, interval)
+    }
+
+    /// Details of the current association.
+    ///
+    /// # Returns
+    ///
+    /// `None` while not connected.
+    pub fn link_info(&self) -> Option<LinkInfo> {
+        let sample = self.sample_connection()?;
+        let roaming = unsafe {
+            let mut raw = link::RawRoamingFeatures::zeroed();
+            if wifi_manager_get_roaming_features(self.manager, &mut raw) {
+                RoamingFeatures::from_raw(&raw)
+            } else {
+                RoamingFeatures::default()
+            }
+        };
+        let links = unsafe {
+            let mut count: libc::c_int = 0;
+            let raw = wifi_manager_get_mlo_links(self.manager, &mut count);
+            if raw.is_null() || count <= 0 {
+                Vec::new()
+            } else {
+                let links = std::slice::from_raw_parts(raw, count as usize)
+                    .iter()
+                    .map(MloLink::from_raw)
+                    .collect();
+                wifi_free_mlo_links(raw);
+                links
+            }
+        };
+        Some(LinkInfo::new(sample, roaming, links))
+    }
+
+    /// Transmit and receive rates, retries and dropped frames of the
+    /// current association, read from the driver on every call.
+    ///
+    /// # Returns
+    ///
+    /// `None` while not connected or if the platform cannot report them.
+    pub fn link_stats(&self) -> Option<LinkStats> {
+        trace::op(self.interface.as_deref(), "link_stats", || unsafe {
+            let mut raw = link::RawLinkStats::zeroed();
+            if wifi_manager_get_link_stats(self.manager, &mut raw) {
+                Some(LinkStats::from_raw(&raw))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Target Wake Time support of the current association.
+    ///
+    /// # Returns
+    ///
+    /// `None` while not connected or if the platform cannot report TWT.
+    pub fn twt_status(&self) -> Option<TwtStatus> {
+        trace::op(self.interface.as_deref(), "twt_status", || unsafe {
+            let mut raw = twt::RawTwtStatus::zeroed();
+            if wifi_manager_get_twt_status(self.manager, &mut raw) {
+                Some(TwtStatus::from_raw(&raw))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Request an individual Target Wake Time agreement with the connected
+    /// access point.
+    ///
+    /// The request is sent without waiting for the access point's answer,
+    /// and the agreement ends with the association.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WifiError::Unsupported`] if the access point or the adapter
+    /// lacks TWT, [`WifiError::OperationFailed`] if the parameters are out
+    /// of range, or another [`WifiError`] the platform reported if the
+    /// request could not be sent.
+    pub fn twt_request(&self, config: &TwtConfig) -> Result<(), WifiError> {
+        match self.twt_status() {
+            Some(status) if status.supported() => {}
+            _ => return Err(WifiError::Unsupported),
+        }
+        let raw = config.raw();
+        self.native_op("twt_request", || unsafe { wifi_manager_twt_setup(self.manager, &raw) })
+    }
+
+    /// Tear down the Target Wake Time agreement. Succeeds if none was
+    /// requested.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WifiError::Unsupported`] without TWT support, or another
+    /// [`WifiError`] the platform reported if the teardown could not be sent.
+    pub fn twt_teardown(&self) -> Result<(), WifiError> {
+        self.native_op("twt_teardown", || unsafe { wifi_manager_twt_teardown(self.manager) })
+    }
+
+    /// Wake-on-WLAN triggers the adapter supports.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the adapter or platform has no WoWLAN support.
+    pub fn wowlan_capabilities(&self) -> Option<WowlanCapabilities> {
+        unsafe {
+            let mut raw = wowlan::RawWowlanCapabilities::zeroed();
+            if wifi_manager_get_wowlan_capabilities(self.manager, &mut raw) {
+                Some(WowlanCapabilities::from_raw(&raw))
+            } else {
+                None
+            }
+        }
+    }
+
+    /// Wake-on-WLAN triggers applied on the next suspend.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the adapter or platform has no WoWLAN support.
+    pub fn wowlan_triggers(&self) -> Option<WowlanTriggers> {
+        trace::op(self.interface.as_deref(), "wowlan_triggers", || unsafe {
+            let mut raw = wowlan::RawWowlanStatus::zeroed();
+            if wifi_manager_get_wowlan(self.manager, &mut raw) {
+                Some(WowlanTriggers::from_raw(&raw))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Configure the triggers that wake the system from suspend, replacing
+    /// the previous ones. A [`WowlanConfig`] without triggers disables
+    /// WoWLAN. Usually requires root (`CAP_NET_ADMIN` on Linux).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WifiError::Unsupported`] if the adapter has no WoWLAN
+    /// support or cannot apply one of the triggers, e.g. a pattern longer
+    /// than [`WowlanCapabilities::max_pattern_length`], or another
+    /// [`WifiError`] the platform reported if the driver rejected the
+    /// configuration.
+    pub fn set_wowlan(&self, config: &WowlanConfig) -> Result<(), WifiError> {
+        match self.wowlan_capabilities() {
+            Some(capabilities) if capabilities.supports(config) => {}
+            _ => return Err(WifiError::Unsupported),
+        }
+        self.native_op("set_wowlan", || config.with_raw(|raw| unsafe { wifi_manager_set_wowlan(self.manager, raw) }))
+    }
+
+    /// Disable Wake-on-WLAN.
+    ///
+    /// # Errors
+    ///
+    /// See [`set_wowlan`](Self::set_wowlan).
+    pub fn disable_wowlan(&self) -> Result<(), WifiError> {
+        self.set_wowlan(&WowlanConfig::new())
+    }
+
+    /// Recent moves between access points of the connected network, oldest
+    /// first.
+    ///
+    /// Roams are detected by sampling the association each time
+    /// [`get_status`](Self::get_status) reports a connection and when this
+    /// method is called, so poll regularly (e.g. once a second) for accurate
+    /// timing. The last [`ROAM_HISTORY_CAPACITY`] roams are kept.
+    pub fn roam_history(&self) -> Vec<Roam> {
+        self.sample_connection();
+        self.roams.history()
+    }
+
+    /// Move the current association to another access point of the same
+    /// network, e.g. a stronger one found with [`scan`](Self::scan).
+    ///
+    /// On Linux this waits up to 10 seconds for the move to complete; on
+    /// Windows it returns once the re-association has been requested.
+    ///
+    /// # Arguments
+    ///
+    /// * `bssid` - MAC address of the access point, as in [`NetworkInfo::bssid`]
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WifiError::InvalidArgument`] if `bssid` is not a MAC
+    /// address, [`WifiError::OperationFailed`] if there is no connection or
+    /// the access point is not a known member of the connected network,
+    /// [`WifiError::Timeout`] if the move did not complete, or
+    /// [`WifiError::Unsupported`] where the platform cannot pick the access
+    /// point.
+    pub fn roam_to(&self, bssid: &str) -> Result<(), WifiError> {
+        if !connect::is_bssid(bssid) {
+            return Err(WifiError::InvalidArgument);
+        }
+        let c_bssid = std::ffi::CString::new(bssid)?;
+        self.native_op("roam_to", || unsafe {
+            wifi_manager_roam_to(self.manager, c_bssid.as_ptr())
+        })?;
+        self.sample_connection();
+        Ok(())
+    }
+
+    /// Tune when the station looks for a stronger access point of the
+    /// connected network.
+    ///
+    /// The policy applies to the current connection; set it again after
+    /// connecting to another network.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WifiError::OperationFailed`] if a setting is out of range
+    /// or there is no connection, or [`WifiError::Unsupported`] on
+    /// platforms that roam on their own terms (Windows, macOS).
+    pub fn set_roaming_policy(&self, policy: &RoamingPolicy) -> Result<(), WifiError> {
+        let raw = policy.raw().ok_or(WifiError::OperationFailed)?;
+        self.native_op("set_roaming_policy", || unsafe {
+            wifi_manager_set_roaming_policy(self.manager, &raw)
+        })
+    }
+
+    /// Recent connects, disconnects, roams, scan failures and hotspot changes,
+    /// oldest first.
+    ///
+    /// The log holds the last [`DEFAULT_EVENT_LOG_CAPACITY`] events unless
+    /// changed with [`set_event_log_capacity`](Self::set_event_log_capacity).
+    /// Links that drop on their own are noticed when the status is polled
+    /// with [`get_status`](Self::get_status).
+    pub fn event_log(&self) -> Vec<WifiEvent> {
+        self.events.snapshot()
+    }
+
+    /// Change how many events the log keeps, dropping the oldest if needed.
+    /// A capacity of zero disables the log.
+    pub fn set_event_log_capacity(&self, capacity: usize) {
+        self.events.set_capacity(capacity);
+    }
+
+    /// Remove all events from the log.
+    pub fn clear_event_log(&self) {
+        self.events.clear();
+    }
+
+    /// Country whose regulatory rules limit the channels and transmit
+    /// power the adapter uses.
+    ///
+    /// # Returns
+    ///
+    /// An ISO 3166-1 alpha-2 code such as `"DE"`, `"00"` for the
+    /// conservative world domain, or `None` if the platform does not report
+    /// it.
+    pub fn regulatory_domain(&self) -> Option<String> {
+        let mut country: [libc::c_char; 3] = [0; 3];
+        let known = trace::op(self.interface.as_deref(), "regulatory_domain", || unsafe {
+            wifi_manager_get_regulatory_domain(self.manager, country.as_mut_ptr(), country.len() as libc::c_int)
+        });
+        known.then(|| diag::text(&country))
+    }
+
+    /// Whether the application may change the regulatory domain. Some
+    /// drivers take it from firmware instead.
+    pub fn can_set_regulatory_domain(&self) -> bool {
+        unsafe { wifi_manager_can_set_regulatory_domain(self.manager) }
+    }
+
+    /// Switch to the regulatory rules of `country`.
+    ///
+    /// Devices shipped worldwide should set the domain of their location
+    /// before starting a hotspot, since channels such as 12, 13 or the 5 GHz
+    /// radar channels are only available under the matching rules.
+    ///
+    /// # Arguments
+    ///
+    /// * `country` - ISO 3166-1 alpha-2 code in upper case, or `"00"` for
+    ///   the world domain
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WifiError::InvalidArgument`] if the code is invalid,
+    /// [`WifiError::Unsupported`] if the platform or driver does not allow
+    /// changing the domain, or [`WifiError::PermissionDenied`] or another
+    /// [`WifiError`] the platform reported if the change was not applied.
+    pub fn set_regulatory_domain(&self, country: &str) -> Result<(), WifiError> {
+        let valid = country == "00" || (country.len() == 2 && country.bytes().all(|b| b.is_ascii_uppercase()));
+        if !valid {
+            return Err(WifiError::InvalidArgument);
+        }
+        if !self.can_set_regulatory_domain() {
+            return Err(WifiError::Unsupported);
+        }
+        let country = std::ffi::CString::new(country)?;
+        self.native_op("set_regulatory_domain", || unsafe {
+            wifi_manager_set_regulatory_domain(self.manager, country.as_ptr())
+        })
+    }
+    
+    /// Check if the hardware supports hotspot functionality.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the hardware supports creating hotspots, `false` otherwise.
+    pub fn is_hotspot_supported(&self) -> bool {
+        unsafe {
+            wifi_manager_is_hotspot_supported(self.manager)
+        }
+    }
+    
+    /// Check if a hotspot is currently active.
+    ///
+    /// # Returns
+    ///
+    /// `true` if a hotspot is active, `false` otherwise.
+    pub fn is_hotspot_active(&self) -> bool {
+        unsafe {
+            wifi_manager_is_hotspot_active(self.manager)
+        }
+    }
+    
+    /// Create an unsecured WiFi hotspot with the given SSID.
+    ///
+    /// # Arguments
+    ///
+    /// * `ssid` - The SSID (network name) for the hotspot
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WifiError::PermissionDenied`] without administrative
+    /// privileges, [`WifiError::DriverError`] if the access point or DHCP
+    /// service failed to start, [`WifiError::Timeout`] if the access point
+    /// did not start within the hotspot timeout of [`Timeouts`], or another
+    /// [`WifiError`] the platform reported.
+    pub fn create_hotspot(&self, ssid: &str) -> Result<(), WifiError> {
+        let c_ssid = std::ffi::CString::new(ssid)?;
+        let created = self.native_op("create_hotspot", || unsafe {
+            trace::ssid(ssid);
+            wifi_manager_create_hotspot(self.manager, c_ssid.as_ptr())
+        });
+        self.record_hotspot(ssid, created.is_ok());
+        created
+    }
+
+    fn record_hotspot(&self, ssid: &str, created: bool) {
+        let ssid = ssid.to_string();
+        self.events.record(if created {
+            EventKind::HotspotStarted { ssid }
+        } else {
+            EventKind::HotspotFailed { ssid }
+        });
+    }
+    
+    /// Create a WiFi hotspot with explicit settings.
+    ///
+    /// With [`HotspotChannel::Auto`] the channel is picked with
+    /// [`recommend_channel`](Self::recommend_channel) before the hotspot is
+    /// started, which takes a scan.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WifiError::InvalidArgument`] if the gateway lies inside the
+    /// DHCP range or the range includes a network or broadcast address,
+    /// [`WifiError::OperationFailed`] if the platform cannot use the
+    /// requested channel or DHCP settings or the interface to share from
+    /// does not exist, [`WifiError::Unsupported`] if the platform cannot
+    /// share a connection, or the error reported by the platform as for
+    /// [`create_hotspot`](Self::create_hotspot).
+    pub fn create_hotspot_with(&self, config: &HotspotConfig) -> Result<(), WifiError> {
+        let channel = match config.channel {
+            HotspotChannel::Default => 0,
+            HotspotChannel::Fixed(channel) => channel as libc::c_int,
+            HotspotChannel::Auto => self
+                .recommend_channel(config.band)
+                .map_or(0, |recommendation| recommendation.channel),
+        };
+        let ssid = std::ffi::CString::new(config.ssid.as_str())?;
+        let password = config.password.as_ref().map(SecretString::to_c_string).transpose()?;
+        let prefix_length = match &config.dhcp {
+            Some(dhcp) => dhcp.prefix_length().ok_or(WifiError::InvalidArgument)? as i32,
+            None => 0,
+        };
+        let address = |address: std::net::Ipv4Addr| std::ffi::CString::new(address.to_string()).unwrap();
+        let range_start = config.dhcp.as_ref().map(|dhcp| address(*dhcp.range.start()));
+        let range_end = config.dhcp.as_ref().map(|dhcp| address(*dhcp.range.end()));
+        let gateway = config.dhcp.as_ref().map(|dhcp| address(dhcp.gateway));
+        let dns_servers = config.dhcp.as_ref().filter(|dhcp| !dhcp.dns.is_empty()).map(|dhcp| {
+            let servers: Vec<String> = dhcp.dns.iter().map(|server| server.to_string()).collect();
+            std::ffi::CString::new(servers.join(",")).unwrap()
+        });
+        let share_from = config.share_from.as_deref().map(std::ffi::CString::new).transpose()?;
+        let pointer = |value: &Option<std::ffi::CString>| value.as_ref().map_or(std::ptr::null(), |value| value.as_ptr());
+        let raw = hotspot::RawHotspotConfig {
+            ssid: ssid.as_ptr(),
+            password: password.as_ref().map_or(std::ptr::null(), |p| p.as_ptr()),
+            channel,
+            dhcp_range_start: pointer(&range_start),
+            dhcp_range_end: pointer(&range_end),
+            gateway: pointer(&gateway),
+            prefix_length,
+            dns_servers: pointer(&dns_servers),
+        };
+        let created = self.native_op("create_hotspot", || unsafe {
+            trace::ssid(&config.ssid);
+            wifi_manager_create_hotspot_with_timeout(
+                self.manager,
+                &raw,
+                pointer(&share_from),
+                config.captive_portal,
+                Timeouts::millis(config.timeout),
+            )
+        });
+        self.record_hotspot(&config.ssid, created.is_ok());
+        created
+    }
+
+    /// Stop the active hotspot.
+    ///
+    /// Succeeds as well if no hotspot was active.
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`WifiError`] reported by the platform if the hotspot
+    /// could not be stopped.
+    pub fn stop_hotspot(&self) -> Result<(), WifiError> {
+        let stopped = self.native_op("stop_hotspot", || unsafe { wifi_manager_stop_hotspot(self.manager) });
+        if stopped.is_ok() {
+            self.events.record(EventKind::HotspotStopped);
+        }
+        stopped
+    }
+
+    /// Devices currently associated with the hotspot.
+    ///
+    /// Register a [`WifiEventListener`] to be told when clients join or
+    /// leave instead of polling.
+    ///
+    /// # Returns
+    ///
+    /// An empty list if no hotspot is active or the platform cannot list
+    /// its clients (currently only Linux can).
+    pub fn hotspot_clients(&self) -> Vec<HotspotClient> {
+        trace::op(self.interface.as_deref(), "hotspot_clients", || unsafe {
+            let mut count: libc::c_int = 0;
+            let raw = wifi_manager_hotspot_clients(self.manager, &mut count);
+            if raw.is_null() || count <= 0 {
+                return Vec::new();
+            }
+            let now = std::time::SystemTime::now();
+            let clients = std::slice::from_raw_parts(raw, count as usize)
+                .iter()
+                .map(|client| HotspotClient::from_raw(client, now))
+                .collect();
+            wifi_free_hotspot_clients(raw);
+            clients
+        })
+    }
+}
+
+/// Convert and free a network array returned by the native layer.
+///
+/// # Safety
+///
+/// `raw_networks` must be null or an array of `count` entries allocated by
+/// the native layer; it is freed and must not be used afterwards.
+#[allow(clippy::if_same_then_else)]
+unsafe fn take_networks(raw_networks: *mut RawNetworkInfo, count: libc::c_int) -> Vec<NetworkInfo> {
+    if raw_networks.is_null() || count <= 0 {
+        return Vec::new();
+    }
+    
+    let raw_slice = std::slice::from_raw_parts(raw_networks, count as usize);
+    let result = raw_slice
+        .iter()                .map(|raw| {
+            let ssid = if raw.ssid.is_null() {
+                "[Hidden Network]".to_string()
+            } else {
+                let s = std::ffi::CStr::from_ptr(raw.ssid)
+                    .to_string_lossy()
+                    .into_owned();
+                if s.is_empty() { 
+                    "[Hidden Network]".to_string() 
+                } else if s.contains("Enable Location Services") {
+                    "[Hidden Network]".to_string()
+                } else { 
+                    s 
+                }
+            };
+            
+            let bssid = if raw.bssid.is_null() {
+                "[No Access]".to_string()
+            } else {
+                let s = std::ffi::CStr::from_ptr(raw.bssid)
+                    .to_string_lossy()
+                    .into_owned();
+                if s.is_empty() { 
+                    "[No Access]".to_string() 
+                } else if s.contains("Enable Location Services") {
+                    "[No Access]".to_string()
+                } else { 
+                    s 
+                }
+            };
+            
+            NetworkInfo {
+                ssid,
+                bssid,
+                signal_strength: raw.signal_strength,
+                security_type: SecurityType::from_raw(raw.security_type),
+                channel: raw.channel,
+                frequency: raw.frequency,
+                pmf: match raw.pmf {
+                    1 => PmfMode::Disabled,
+                    2 => PmfMode::Capable,
+                    3 => PmfMode::Required,
+                    _ => PmfMode::Unknown,
+                },
+                wps_enabled: raw.wps_enabled != 0,
+                wifi7: raw.eht != 0,
+                mlo: raw.mlo != 0,
+                information_elements: if raw.ie_data.is_null() || raw.ie_len <= 0 {
+                    Vec::new()
+                } else {
+                    std::slice::from_raw_parts(raw.ie_data, raw.ie_len as usize).to_vec()
+                },
+                phy_mode: PhyMode::Unknown,
+                max_rate_mbps: 0,
+            }
+            .with_phy()
+        })
+        .collect();
+    
+    wifi_free_network_info(raw_networks, count);
+    result
 }
 
 impl Drop for WiFi {
     fn drop(&mut self) {
+        self.cancel.detach();
         unsafe {
             wifi_manager_delete(self.manager);
         }
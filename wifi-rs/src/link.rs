@@ -0,0 +1,281 @@
+//! Details of the current association.
+
+use crate::channel::{frequency_to_channel, Band};
+use crate::diag::text;
+use crate::roam::LinkSample;
+
+#[repr(C)]
+pub(crate) struct RawRoamingFeatures {
+    ap_neighbor_report: i32,
+    ap_bss_transition: i32,
+    ap_fast_transition: i32,
+    local_neighbor_report: i32,
+    local_bss_transition: i32,
+    local_fast_transition: i32,
+}
+
+impl RawRoamingFeatures {
+    pub(crate) fn zeroed() -> Self {
+        // SAFETY: the struct is plain integers.
+        unsafe { std::mem::zeroed() }
+    }
+}
+
+#[repr(C)]
+pub(crate) struct RawLinkStats {
+    tx_bitrate_kbps: u32,
+    rx_bitrate_kbps: u32,
+    tx_mcs: i32,
+    rx_mcs: i32,
+    tx_packets: i64,
+    rx_packets: i64,
+    tx_retries: i64,
+    tx_failed: i64,
+    rx_dropped: i64,
+}
+
+impl RawLinkStats {
+    pub(crate) fn zeroed() -> Self {
+        // SAFETY: the struct is plain integers.
+        unsafe { std::mem::zeroed() }
+    }
+}
+
+#[repr(C)]
+pub(crate) struct RawMloLink {
+    link_id: i32,
+    ap_link_address: [libc::c_char; 18],
+    frequency: i32,
+    signal_strength: i32,
+}
+
+/// One link of a Wi-Fi 7 multi-link (MLO) association.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MloLink {
+    pub link_id: i32,
+    /// BSSID of the access point on this link.
+    pub bssid: String,
+    /// Frequency in MHz.
+    pub frequency: i32,
+    pub channel: i32,
+    /// Signal strength in dBm, `None` if unknown.
+    pub signal_strength: Option<i32>,
+}
+
+impl MloLink {
+    pub(crate) fn from_raw(raw: &RawMloLink) -> Self {
+        MloLink {
+            link_id: raw.link_id,
+            bssid: text(&raw.ap_link_address),
+            frequency: raw.frequency,
+            channel: frequency_to_channel(raw.frequency),
+            signal_strength: (raw.signal_strength != 0).then_some(raw.signal_strength),
+        }
+    }
+
+    /// Band of the link, `None` for bands the crate does not model.
+    pub fn band(&self) -> Option<Band> {
+        Band::from_frequency(self.frequency)
+    }
+}
+
+/// Support for one roaming feature by either side of the link.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FeatureSupport {
+    /// The access point advertises the feature.
+    pub ap: bool,
+    /// The local driver and supplicant implement the feature.
+    pub local: bool,
+}
+
+impl FeatureSupport {
+    /// Both sides support the feature, so it can be relied on.
+    pub fn usable(&self) -> bool {
+        self.ap && self.local
+    }
+}
+
+/// 802.11k/v/r support of the current association.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RoamingFeatures {
+    /// 802.11k neighbor reports: the access point lists nearby access
+    /// points so roaming candidates are found without a full scan.
+    pub neighbor_report: FeatureSupport,
+    /// 802.11v BSS transition management: the access point can steer the
+    /// client to another access point.
+    pub bss_transition: FeatureSupport,
+    /// 802.11r fast transition: keys are derived ahead of time so a roam
+    /// skips the full handshake.
+    pub fast_transition: FeatureSupport,
+}
+
+impl RoamingFeatures {
+    pub(crate) fn from_raw(raw: &RawRoamingFeatures) -> Self {
+        RoamingFeatures {
+            neighbor_report: FeatureSupport {
+                ap: raw.ap_neighbor_report != 0,
+                local: raw.local_neighbor_report != 0,
+            },
+            bss_transition: FeatureSupport {
+                ap: raw.ap_bss_transition != 0,
+                local: raw.local_bss_transition != 0,
+            },
+            fast_transition: FeatureSupport {
+                ap: raw.ap_fast_transition != 0,
+                local: raw.local_fast_transition != 0,
+            },
+        }
+    }
+}
+
+/// The current association, from [`WiFi::link_info`](crate::WiFi::link_info).
+///
+/// For a Wi-Fi 7 multi-link association, `bssid`, `frequency` and
+/// `signal_strength` describe the primary link; [`links`](Self::links) lists
+/// all of them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkInfo {
+    pub ssid: String,
+    pub bssid: String,
+    /// Frequency in MHz.
+    pub frequency: i32,
+    /// Signal strength in dBm.
+    pub signal_strength: i32,
+    /// Whether 802.11r fast transition was negotiated for this association.
+    pub fast_transition: bool,
+    /// MLD address of the access point for a multi-link association.
+    pub ap_mld_address: Option<String>,
+    /// Affiliated links of a multi-link association, empty otherwise.
+    pub links: Vec<MloLink>,
+    pub(crate) roaming: RoamingFeatures,
+}
+
+impl LinkInfo {
+    pub(crate) fn new(sample: LinkSample, roaming: RoamingFeatures, links: Vec<MloLink>) -> Self {
+        LinkInfo {
+            ssid: sample.ssid,
+            bssid: sample.bssid,
+            frequency: sample.frequency,
+            signal_strength: sample.signal_strength,
+            fast_transition: sample.fast_transition,
+            ap_mld_address: (!sample.ap_mld_address.is_empty()).then_some(sample.ap_mld_address),
+            links,
+            roaming,
+        }
+    }
+
+    /// Whether the association uses Wi-Fi 7 multi-link operation.
+    pub fn is_mlo(&self) -> bool {
+        self.ap_mld_address.is_some()
+    }
+
+    /// Bands in use, one entry per band even if several links share it.
+    pub fn bands(&self) -> Vec<Band> {
+        let mut bands = Vec::new();
+        let frequencies = if self.links.is_empty() {
+            vec![self.frequency]
+        } else {
+            self.links.iter().map(|link| link.frequency).collect()
+        };
+        for band in frequencies.into_iter().filter_map(Band::from_frequency) {
+            if !bands.contains(&band) {
+                bands.push(band);
+            }
+        }
+        bands
+    }
+
+    /// Which of neighbor reports, BSS transition management and fast
+    /// transition the access point and the local stack support.
+    pub fn roaming_features(&self) -> RoamingFeatures {
+        self.roaming
+    }
+}
+
+/// Rates and frame counters of the current association, from
+/// [`WiFi::link_stats`](crate::WiFi::link_stats). Fields are `None` where
+/// the platform does not report them; macOS only reports the transmit rate.
+///
+/// Counters accumulate from the start of the association on Linux and
+/// from when the adapter was started on Windows. Compare two samples with
+/// [`since`](Self::since) for the rates over an interval.
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use wifi_rs::WiFi;
+///
+/// let wifi = WiFi::new();
+/// let before = wifi.link_stats().expect("not connected");
+/// std::thread::sleep(Duration::from_secs(10));
+/// let after = wifi.link_stats().expect("not connected");
+/// if let Some(retries) = after.since(&before).retry_rate() {
+///     println!("{:.1}% of frames were retransmitted", retries * 100.0);
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LinkStats {
+    /// Rate of the last transmitted frame in kbit/s.
+    pub tx_bitrate: Option<u32>,
+    /// Rate of the last received frame in kbit/s.
+    pub rx_bitrate: Option<u32>,
+    /// HT, VHT, HE or EHT MCS index of the transmit rate, `None` for legacy
+    /// rates.
+    pub tx_mcs: Option<u8>,
+    pub rx_mcs: Option<u8>,
+    pub tx_packets: Option<u64>,
+    pub rx_packets: Option<u64>,
+    /// Retransmissions.
+    pub tx_retries: Option<u64>,
+    /// Frames given up on after all retries.
+    pub tx_failed: Option<u64>,
+    /// Received frames dropped by the driver or the network stack.
+    pub rx_dropped: Option<u64>,
+}
+
+impl LinkStats {
+    pub(crate) fn from_raw(raw: &RawLinkStats) -> Self {
+        let counter = |value: i64| u64::try_from(value).ok();
+        let mcs = |value: i32| u8::try_from(value).ok();
+        LinkStats {
+            tx_bitrate: (raw.tx_bitrate_kbps != 0).then_some(raw.tx_bitrate_kbps),
+            rx_bitrate: (raw.rx_bitrate_kbps != 0).then_some(raw.rx_bitrate_kbps),
+            tx_mcs: mcs(raw.tx_mcs),
+            rx_mcs: mcs(raw.rx_mcs),
+            tx_packets: counter(raw.tx_packets),
+            rx_packets: counter(raw.rx_packets),
+            tx_retries: counter(raw.tx_retries),
+            tx_failed: counter(raw.tx_failed),
+            rx_dropped: counter(raw.rx_dropped),
+        }
+    }
+
+    /// Counters accumulated between `earlier` and this sample, with the
+    /// rates of this sample. A counter that went backwards, e.g. after a
+    /// reconnect, becomes `None`.
+    pub fn since(&self, earlier: &LinkStats) -> LinkStats {
+        let delta = |now: Option<u64>, then: Option<u64>| now?.checked_sub(then?);
+        LinkStats {
+            tx_packets: delta(self.tx_packets, earlier.tx_packets),
+            rx_packets: delta(self.rx_packets, earlier.rx_packets),
+            tx_retries: delta(self.tx_retries, earlier.tx_retries),
+            tx_failed: delta(self.tx_failed, earlier.tx_failed),
+            rx_dropped: delta(self.rx_dropped, earlier.rx_dropped),
+            ..*self
+        }
+    }
+
+    /// Retransmissions per transmitted frame.
+    pub fn retry_rate(&self) -> Option<f64> {
+        ratio(self.tx_retries?, self.tx_packets?)
+    }
+
+    /// Share of frames that could not be delivered despite retries.
+    pub fn loss_rate(&self) -> Option<f64> {
+        ratio(self.tx_failed?, self.tx_packets? + self.tx_failed?)
+    }
+}
+
+fn ratio(count: u64, total: u64) -> Option<f64> {
+    (total > 0).then(|| count as f64 / total as f64)
+}
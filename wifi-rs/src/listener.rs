@@ -0,0 +1,152 @@
+//! Push notifications of connection state changes.
+//!
+//! Instead of polling [`WiFi::get_status`](crate::WiFi::get_status), an
+//! application can register a [`WifiEventListener`] and be called as soon as
+//! the driver reports that the link came up, went down, roamed, crossed a
+//! signal threshold, finished a scan, or that a client joined or left the
+//! hotspot. Listeners run on a platform thread, so they must be `Send +
+//! Sync` and should return quickly.
+
+use std::ffi::CStr;
+
+use crate::{WiFi, WifiError};
+
+#[repr(C)]
+pub(crate) struct RawWifiEventData {
+    kind: i32,
+    ssid: [libc::c_char; 33],
+    bssid: [libc::c_char; 18],
+    signal_strength: i32,
+    signal_low: i32,
+    client_address: [libc::c_char; 18],
+}
+
+pub(crate) type RawWifiEventCallback = extern "C" fn(event: *const RawWifiEventData, user_data: *mut libc::c_void);
+
+/// A state change pushed by the platform.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WifiNotification {
+    /// Associated and authenticated with a network.
+    Connected { ssid: String, bssid: String },
+    /// The association was lost or torn down.
+    ConnectionLost,
+    /// Moved to another access point of the same network.
+    Roamed { bssid: String },
+    /// A scan finished and fresh results are available.
+    ScanComplete,
+    /// The signal crossed the registered threshold.
+    SignalChanged {
+        /// Signal strength in dBm, 0 if the driver does not report it.
+        signal_dbm: i32,
+        /// Whether the signal fell below the threshold rather than rose
+        /// above it.
+        low: bool,
+    },
+    /// A client associated with the hotspot.
+    HotspotClientJoined { address: String },
+    /// A client left the hotspot.
+    HotspotClientLeft { address: String },
+}
+
+fn field(chars: &[libc::c_char]) -> String {
+    // SAFETY: the C layer NUL terminates every field.
+    unsafe { CStr::from_ptr(chars.as_ptr()) }.to_string_lossy().into_owned()
+}
+
+impl WifiNotification {
+    fn from_raw(raw: &RawWifiEventData) -> Option<Self> {
+        Some(match raw.kind {
+            0 => WifiNotification::Connected { ssid: field(&raw.ssid), bssid: field(&raw.bssid) },
+            1 => WifiNotification::ConnectionLost,
+            2 => WifiNotification::Roamed { bssid: field(&raw.bssid) },
+            3 => WifiNotification::ScanComplete,
+            4 => WifiNotification::SignalChanged { signal_dbm: raw.signal_strength, low: raw.signal_low != 0 },
+            5 => WifiNotification::HotspotClientJoined { address: field(&raw.client_address) },
+            6 => WifiNotification::HotspotClientLeft { address: field(&raw.client_address) },
+            _ => return None,
+        })
+    }
+}
+
+/// Receiver of [`WifiNotification`]s, registered with
+/// [`WiFi::register_listener`].
+///
+/// Closures taking a `&WifiNotification` implement it.
+pub trait WifiEventListener: Send + Sync {
+    fn on_event(&self, event: &WifiNotification);
+}
+
+impl<F: Fn(&WifiNotification) + Send + Sync> WifiEventListener for F {
+    fn on_event(&self, event: &WifiNotification) {
+        self(event)
+    }
+}
+
+type BoxedListener = Box<dyn WifiEventListener>;
+
+extern "C" fn on_event(event: *const RawWifiEventData, user_data: *mut libc::c_void) {
+    if event.is_null() || user_data.is_null() {
+        return;
+    }
+    // SAFETY: user_data is the boxed listener owned by the
+    // `ListenerRegistration`, which unregisters the callback before freeing
+    // it, and the event is valid for the duration of the call.
+    let (listener, event) = unsafe { (&*(user_data as *const BoxedListener), &*event) };
+    if let Some(notification) = WifiNotification::from_raw(event) {
+        // A panic must not unwind into the platform thread
+        let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| listener.on_event(&notification)));
+    }
+}
+
+/// A registered [`WifiEventListener`], created by
+/// [`WiFi::register_listener`].
+///
+/// Dropping the registration removes the listener; no calls are made once
+/// the drop returns.
+///
+/// ```no_run
+/// use wifi_rs::{WiFi, WifiNotification};
+///
+/// let wifi = WiFi::new();
+/// let _registration = wifi.register_listener(
+///     |event: &WifiNotification| match event {
+///         WifiNotification::ConnectionLost => eprintln!("connection lost"),
+///         WifiNotification::SignalChanged { low: true, signal_dbm } => eprintln!("weak signal: {} dBm", signal_dbm),
+///         _ => {}
+///     },
+///     -75,
+/// )?;
+/// # Ok::<(), wifi_rs::WifiError>(())
+/// ```
+pub struct ListenerRegistration<'a> {
+    wifi: &'a WiFi,
+    listener: *mut BoxedListener,
+}
+
+impl<'a> ListenerRegistration<'a> {
+    pub(crate) fn start(
+        wifi: &'a WiFi,
+        listener: impl WifiEventListener + 'static,
+        signal_threshold: i32,
+    ) -> Result<Self, WifiError> {
+        let boxed: BoxedListener = Box::new(listener);
+        let listener = Box::into_raw(Box::new(boxed));
+        let registered = unsafe {
+            crate::wifi_manager_register_callback(wifi.manager, on_event, listener as *mut libc::c_void, signal_threshold)
+        };
+        if !registered {
+            drop(unsafe { Box::from_raw(listener) });
+            return Err(WifiError::Unsupported);
+        }
+        Ok(ListenerRegistration { wifi, listener })
+    }
+}
+
+impl Drop for ListenerRegistration<'_> {
+    fn drop(&mut self) {
+        unsafe {
+            crate::wifi_manager_unregister_callback(self.wifi.manager);
+            drop(Box::from_raw(self.listener));
+        }
+    }
+}
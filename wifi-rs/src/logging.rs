@@ -0,0 +1,72 @@
+//! Log messages of the native library.
+//!
+//! The C++ layer logs every step of scans, connects and hotspot changes,
+//! by default to standard output. A sink set with
+//! [`WiFiBuilder::log_sink`](crate::WiFiBuilder::log_sink) receives them
+//! instead. The sink is shared by every [`WiFi`](crate::WiFi) of the
+//! process, as the native logger is.
+
+use std::ffi::CStr;
+use std::sync::Mutex;
+
+/// Severity of a native log message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LogLevel {
+    Debug,
+    Info,
+    Warning,
+    Error,
+}
+
+impl LogLevel {
+    fn from_raw(level: libc::c_int) -> Self {
+        match level {
+            0 => LogLevel::Debug,
+            1 => LogLevel::Info,
+            2 => LogLevel::Warning,
+            _ => LogLevel::Error,
+        }
+    }
+
+    fn raw(self) -> libc::c_int {
+        self as libc::c_int
+    }
+}
+
+pub(crate) type LogSink = Box<dyn Fn(LogLevel, &str) + Send + Sync>;
+
+pub(crate) type RawLogCallback = extern "C" fn(level: libc::c_int, message: *const libc::c_char, user_data: *mut libc::c_void);
+
+static SINK: Mutex<Option<LogSink>> = Mutex::new(None);
+
+extern "C" fn on_message(level: libc::c_int, message: *const libc::c_char, _user_data: *mut libc::c_void) {
+    if message.is_null() {
+        return;
+    }
+    // SAFETY: the native logger passes a NUL-terminated string that lives
+    // for the duration of the call.
+    let message = unsafe { CStr::from_ptr(message) }.to_string_lossy();
+    let sink = SINK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some(sink) = sink.as_ref() {
+        let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| sink(LogLevel::from_raw(level), &message)));
+    }
+}
+
+/// Route native log messages to `sink`, or back to standard output.
+pub(crate) fn set_sink(sink: Option<LogSink>) {
+    let install = sink.is_some();
+    *SINK.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = sink;
+    unsafe {
+        if install {
+            crate::wifi_set_log_callback(Some(on_message), std::ptr::null_mut());
+        } else {
+            crate::wifi_set_log_callback(None, std::ptr::null_mut());
+        }
+    }
+}
+
+/// Drop native log messages below `level`.
+pub(crate) fn set_level(level: LogLevel) {
+    unsafe { crate::wifi_set_log_level(level.raw()) }
+}
@@ -1,4 +1,4 @@
-use wifi_rs::{WiFi, SecurityType};
+use wifi_rs::{WiFi, SecurityType, WifiError};
 
 fn main() {    let wifi = WiFi::new();    println!("Scanning for WiFi networks...");
     #[cfg(target_os = "macos")]
@@ -28,17 +28,18 @@ fn main() {    let wifi = WiFi::new();    println!("Scanning for WiFi networks..
             let network = &open_networks[0];
             println!("Attempting to connect to open network: {}", network.ssid);
             
-            if wifi.connect(&network.ssid, None) {
-                println!("Connection initiated successfully");
-                println!("Connection status: {:?}", wifi.get_status());
-            } else {
-                println!("Failed to connect to the network");
+            match wifi.connect(&network.ssid, None) {
+                Ok(()) => {
+                    println!("Connection initiated successfully");
+                    println!("Connection status: {:?}", wifi.get_status());
+                }
+                Err(e) => println!("Failed to connect to the network: {}", e),
             }
         } else {            println!("No open networks available for automatic connection");
             
             // Example: How to connect to a secured network
             // Replace with actual network credentials for testing
-            // if wifi.connect("YourNetworkSSID", Some("YourPassword")) {
+            // if wifi.connect("YourNetworkSSID", Some("YourPassword")).is_ok() {
             //     println!("Connected to secured network");
             // }
         }
@@ -53,10 +54,9 @@ fn main() {    let wifi = WiFi::new();    println!("Scanning for WiFi networks..
         if wifi.is_hotspot_active() {
             println!("A hotspot is currently active");
             println!("Stopping active hotspot...");
-            if wifi.stop_hotspot() {
-                println!("Hotspot stopped successfully");
-            } else {
-                println!("Failed to stop hotspot");
+            match wifi.stop_hotspot() {
+                Ok(()) => println!("Hotspot stopped successfully"),
+                Err(e) => println!("Failed to stop hotspot: {}", e),
             }
         }
         
@@ -65,22 +65,25 @@ fn main() {    let wifi = WiFi::new();    println!("Scanning for WiFi networks..
         println!("Creating a test hotspot with SSID: {}", hotspot_ssid);
         println!("Note: This requires administrative privileges");
         
-        if wifi.create_hotspot(hotspot_ssid) {
-            println!("Hotspot created successfully");
-            println!("Hotspot active: {}", wifi.is_hotspot_active());
-            
-            println!("Press Enter to stop the hotspot...");
-            let mut input = String::new();
-            std::io::stdin().read_line(&mut input).unwrap();
-            
-            println!("Stopping hotspot...");
-            if wifi.stop_hotspot() {
-                println!("Hotspot stopped successfully");
-            } else {
-                println!("Failed to stop hotspot");
+        match wifi.create_hotspot(hotspot_ssid) {
+            Ok(()) => {
+                println!("Hotspot created successfully");
+                println!("Hotspot active: {}", wifi.is_hotspot_active());
+                
+                println!("Press Enter to stop the hotspot...");
+                let mut input = String::new();
+                std::io::stdin().read_line(&mut input).unwrap();
+                
+                println!("Stopping hotspot...");
+                match wifi.stop_hotspot() {
+                    Ok(()) => println!("Hotspot stopped successfully"),
+                    Err(e) => println!("Failed to stop hotspot: {}", e),
+                }
+            }
+            Err(WifiError::PermissionDenied) => {
+                println!("Failed to create hotspot. Make sure you're running with admin privileges");
             }
-        } else {
-            println!("Failed to create hotspot. Make sure you're running with admin privileges");
+            Err(e) => println!("Failed to create hotspot: {}", e),
         }    } else {
         println!("Hotspot functionality is not supported on this device");
     }
@@ -0,0 +1,156 @@
+//! 802.11s mesh networking.
+//!
+//! Mesh points talk to each other directly and forward frames over several
+//! hops, without an access point, which suits drone swarms and sensor
+//! networks. The adapter gets a separate mesh interface so the regular
+//! station connection stays untouched; the first node to join a mesh ID
+//! effectively creates it.
+//!
+//! Backed by nl80211 on Linux. Secured meshes use SAE and need
+//! wpa_supplicant built with mesh support.
+
+use std::ffi::CString;
+
+use zeroize::Zeroizing;
+
+use crate::diag::text;
+use crate::SecretString;
+
+#[repr(C)]
+pub(crate) struct RawMeshConfig {
+    mesh_id: *const libc::c_char,
+    channel: i32,
+    password: *const libc::c_char,
+    interface_name: *const libc::c_char,
+}
+
+#[repr(C)]
+pub(crate) struct RawMeshPeer {
+    address: [libc::c_char; 18],
+    link_state: i32,
+    signal_strength: i32,
+    inactive_ms: i32,
+}
+
+/// Settings for [`WiFi::mesh_join`](crate::WiFi::mesh_join) and
+/// [`WiFi::mesh_create`](crate::WiFi::mesh_create).
+#[derive(Debug, Clone, Default)]
+pub struct MeshConfig {
+    pub(crate) channel: Option<u32>,
+    pub(crate) password: Option<SecretString>,
+    pub(crate) interface_name: Option<String>,
+}
+
+impl MeshConfig {
+    /// An open mesh on a channel chosen by the driver, on an interface named
+    /// after the adapter with a `mesh` suffix.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Operate on `channel`. All nodes of a mesh must use the same channel.
+    pub fn channel(mut self, channel: u32) -> Self {
+        self.channel = Some(channel);
+        self
+    }
+
+    /// Secure the mesh with SAE using `password`.
+    pub fn password(mut self, password: impl Into<SecretString>) -> Self {
+        self.password = Some(password.into());
+        self
+    }
+
+    /// Name of the mesh interface to create.
+    pub fn interface_name(mut self, name: &str) -> Self {
+        self.interface_name = Some(name.to_string());
+        self
+    }
+}
+
+/// Keeps the strings referenced by a [`RawMeshConfig`] alive.
+pub(crate) struct MeshStrings {
+    mesh_id: CString,
+    password: Option<Zeroizing<CString>>,
+    interface_name: Option<CString>,
+    channel: i32,
+}
+
+impl MeshStrings {
+    pub(crate) fn new(mesh_id: &str, config: &MeshConfig) -> Option<Self> {
+        if mesh_id.is_empty() || mesh_id.len() > 32 {
+            return None;
+        }
+        Some(MeshStrings {
+            mesh_id: CString::new(mesh_id).ok()?,
+            password: match &config.password {
+                Some(password) => Some(password.to_c_string().ok()?),
+                None => None,
+            },
+            interface_name: match &config.interface_name {
+                Some(name) => Some(CString::new(name.as_str()).ok()?),
+                None => None,
+            },
+            channel: config.channel.map_or(0, |c| c.min(i32::MAX as u32) as i32),
+        })
+    }
+
+    pub(crate) fn raw(&self) -> RawMeshConfig {
+        RawMeshConfig {
+            mesh_id: self.mesh_id.as_ptr(),
+            channel: self.channel,
+            password: self.password.as_ref().map_or(std::ptr::null(), |p| p.as_ptr()),
+            interface_name: self.interface_name.as_ref().map_or(std::ptr::null(), |n| n.as_ptr()),
+        }
+    }
+}
+
+/// Mesh Peering Management state of the link to a neighbor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PeerLinkState {
+    Listen,
+    OpenSent,
+    OpenReceived,
+    ConfirmReceived,
+    /// The link is up and carries traffic.
+    Established,
+    Holding,
+    Blocked,
+    Unknown,
+}
+
+impl PeerLinkState {
+    fn from_raw(state: i32) -> Self {
+        match state {
+            0 => PeerLinkState::Listen,
+            1 => PeerLinkState::OpenSent,
+            2 => PeerLinkState::OpenReceived,
+            3 => PeerLinkState::ConfirmReceived,
+            4 => PeerLinkState::Established,
+            5 => PeerLinkState::Holding,
+            6 => PeerLinkState::Blocked,
+            _ => PeerLinkState::Unknown,
+        }
+    }
+}
+
+/// A neighbor reported by [`WiFi::mesh_peers`](crate::WiFi::mesh_peers).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MeshPeer {
+    pub address: String,
+    pub link_state: PeerLinkState,
+    /// Signal strength in dBm, `None` if unknown.
+    pub signal_strength: Option<i32>,
+    /// Time since the last frame from the peer in milliseconds.
+    pub inactive_ms: Option<u32>,
+}
+
+impl MeshPeer {
+    pub(crate) fn from_raw(raw: &RawMeshPeer) -> Self {
+        MeshPeer {
+            address: text(&raw.address),
+            link_state: PeerLinkState::from_raw(raw.link_state),
+            signal_strength: (raw.signal_strength != 0).then_some(raw.signal_strength),
+            inactive_ms: u32::try_from(raw.inactive_ms).ok(),
+        }
+    }
+}
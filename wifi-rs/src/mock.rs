@@ -0,0 +1,297 @@
+//! Scriptable in-memory backend for tests.
+//!
+//! Enabled with the `backend-mock` feature or its alias `mock`. A
+//! [`MockBackend`], also named [`MockWifi`], needs no hardware or
+//! privileges: tests set the networks a scan returns, script the outcome
+//! of connection attempts, add latency and inject events, then run their
+//! WiFi handling against it through [`WifiBackend`].
+//!
+//! ```
+//! use wifi_rs::mock::{self, MockBackend};
+//! use wifi_rs::{ConnectOptions, ConnectionStatus, WifiBackend, WifiError};
+//!
+//! let wifi = MockBackend::new().with_networks(vec![mock::network("Office", 2437, -55)]);
+//! wifi.script_connect("Office", Err(WifiError::OperationFailed));
+//!
+//! assert!(wifi.connect_with("Office", &ConnectOptions::password("secret")).is_err());
+//! assert!(wifi.connect_with("Office", &ConnectOptions::password("secret")).is_ok());
+//! assert_eq!(wifi.get_status(), ConnectionStatus::Connected);
+//! ```
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, SystemTime};
+
+use crate::channel::frequency_to_channel;
+use crate::events::EventLog;
+use crate::roam::LinkSample;
+use crate::{
+    ConnectOptions, ConnectionStatus, DisconnectReason, EventKind, HotspotConfig, LinkInfo, NetworkInfo,
+    PhyMode, PmfMode, RoamingFeatures, SecurityType, WifiBackend, WifiError, WifiEvent,
+};
+
+/// A WPA2 network for scan results, with a BSSID derived from the SSID and
+/// frequency.
+pub fn network(ssid: &str, frequency: i32, signal_dbm: i32) -> NetworkInfo {
+    let hash = ssid
+        .bytes()
+        .fold(frequency as u32, |hash, b| hash.wrapping_mul(31).wrapping_add(b as u32));
+    NetworkInfo {
+        ssid: ssid.to_string(),
+        bssid: format!(
+            "02:00:00:{:02x}:{:02x}:{:02x}",
+            (hash >> 16) as u8,
+            (hash >> 8) as u8,
+            hash as u8
+        ),
+        signal_strength: signal_dbm,
+        security_type: SecurityType::Wpa2,
+        channel: frequency_to_channel(frequency),
+        frequency,
+        pmf: PmfMode::Capable,
+        wps_enabled: false,
+        wifi7: false,
+        mlo: false,
+        information_elements: Vec::new(),
+        phy_mode: PhyMode::Unknown,
+        max_rate_mbps: 0,
+    }
+    .with_phy()
+}
+
+/// Backend operation a latency applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MockOp {
+    Scan,
+    Connect,
+    Disconnect,
+}
+
+/// A call made on a [`MockBackend`], for asserting what the code under test
+/// did.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MockCall {
+    Scan,
+    Connect { ssid: String },
+    Disconnect,
+    CreateHotspot { ssid: String },
+    StopHotspot,
+}
+
+struct State {
+    networks: Vec<NetworkInfo>,
+    scans: VecDeque<Vec<NetworkInfo>>,
+    outcomes: HashMap<String, VecDeque<Result<(), WifiError>>>,
+    latencies: HashMap<MockOp, Duration>,
+    connected: Option<String>,
+    hotspot: Option<String>,
+    calls: Vec<MockCall>,
+}
+
+/// In-memory [`WifiBackend`] with scriptable behavior.
+///
+/// Without a script, scans return the configured networks and connecting
+/// succeeds for any SSID among them.
+pub struct MockBackend {
+    state: Mutex<State>,
+    events: EventLog,
+}
+
+/// Name under which the mock stands in for [`WiFi`](crate::WiFi) in tests.
+pub type MockWifi = MockBackend;
+
+impl Default for MockBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MockBackend {
+    /// A disconnected backend that sees no networks.
+    pub fn new() -> Self {
+        MockBackend {
+            state: Mutex::new(State {
+                networks: Vec::new(),
+                scans: VecDeque::new(),
+                outcomes: HashMap::new(),
+                latencies: HashMap::new(),
+                connected: None,
+                hotspot: None,
+                calls: Vec::new(),
+            }),
+            events: EventLog::new(),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Networks visible to scans.
+    pub fn with_networks(self, networks: Vec<NetworkInfo>) -> Self {
+        self.set_networks(networks);
+        self
+    }
+
+    /// Replace the visible networks, e.g. to change signal strengths
+    /// between steps of a test.
+    pub fn set_networks(&self, networks: Vec<NetworkInfo>) {
+        self.lock().networks = networks;
+    }
+
+    /// Return `networks` from the next scan only, ahead of the visible
+    /// networks. Queued results are used in order.
+    pub fn queue_scan(&self, networks: Vec<NetworkInfo>) {
+        self.lock().scans.push_back(networks);
+    }
+
+    /// Use `outcome` for the next connection attempt to `ssid`. Outcomes
+    /// queued for the same SSID are used in order.
+    pub fn script_connect(&self, ssid: &str, outcome: Result<(), WifiError>) {
+        self.lock().outcomes.entry(ssid.to_string()).or_default().push_back(outcome);
+    }
+
+    /// Delay every call of `op` by `latency`.
+    pub fn set_latency(&self, op: MockOp, latency: Duration) {
+        self.lock().latencies.insert(op, latency);
+    }
+
+    /// Add an event to the event log as if the backend had observed it.
+    pub fn inject_event(&self, kind: EventKind) {
+        self.events.record(kind);
+    }
+
+    /// Drop the connection as if the link was lost.
+    pub fn drop_link(&self) {
+        if self.lock().connected.take().is_some() {
+            self.events.set_status(ConnectionStatus::Disconnected);
+            self.events.record(EventKind::Disconnected { reason: DisconnectReason::LinkLost });
+        }
+    }
+
+    /// SSID of the current connection.
+    pub(crate) fn connected(&self) -> Option<String> {
+        self.lock().connected.clone()
+    }
+
+    /// SSID of the running hotspot.
+    pub fn hotspot(&self) -> Option<String> {
+        self.lock().hotspot.clone()
+    }
+
+    /// Calls made so far, oldest first.
+    pub fn calls(&self) -> Vec<MockCall> {
+        self.lock().calls.clone()
+    }
+
+    fn delay(&self, op: MockOp) {
+        let latency = self.lock().latencies.get(&op).copied();
+        if let Some(latency) = latency {
+            std::thread::sleep(latency);
+        }
+    }
+}
+
+impl WifiBackend for MockBackend {
+    fn scan(&self) -> Vec<NetworkInfo> {
+        self.delay(MockOp::Scan);
+        let mut state = self.lock();
+        state.calls.push(MockCall::Scan);
+        match state.scans.pop_front() {
+            Some(networks) => networks,
+            None => state.networks.clone(),
+        }
+    }
+
+    fn connect_with(&self, ssid: &str, _options: &ConnectOptions) -> Result<(), WifiError> {
+        self.delay(MockOp::Connect);
+        let outcome = {
+            let mut state = self.lock();
+            state.calls.push(MockCall::Connect { ssid: ssid.to_string() });
+            let scripted = state.outcomes.get_mut(ssid).and_then(VecDeque::pop_front);
+            let outcome = scripted.unwrap_or_else(|| {
+                if state.networks.iter().any(|n| n.ssid == ssid) {
+                    Ok(())
+                } else {
+                    Err(WifiError::OperationFailed)
+                }
+            });
+            state.connected = outcome.is_ok().then(|| ssid.to_string());
+            outcome
+        };
+
+        self.events.record_connect(ssid, &outcome);
+        outcome
+    }
+
+    fn disconnect(&self) -> Result<(), WifiError> {
+        self.delay(MockOp::Disconnect);
+        let mut state = self.lock();
+        state.calls.push(MockCall::Disconnect);
+        if state.connected.take().is_some() {
+            drop(state);
+            self.events.set_status(ConnectionStatus::Disconnected);
+            self.events.record(EventKind::Disconnected { reason: DisconnectReason::Requested });
+        }
+        Ok(())
+    }
+
+    fn get_status(&self) -> ConnectionStatus {
+        let status = if self.lock().connected.is_some() {
+            ConnectionStatus::Connected
+        } else {
+            ConnectionStatus::Disconnected
+        };
+        self.events.observe_status(status);
+        status
+    }
+
+    fn link_info(&self) -> Option<LinkInfo> {
+        let state = self.lock();
+        let ssid = state.connected.as_ref()?;
+        let network = state
+            .networks
+            .iter()
+            .filter(|n| &n.ssid == ssid)
+            .max_by_key(|n| n.signal_strength);
+        let sample = LinkSample {
+            ssid: ssid.clone(),
+            bssid: network.map(|n| n.bssid.clone()).unwrap_or_default(),
+            frequency: network.map_or(0, |n| n.frequency),
+            signal_strength: network.map_or(0, |n| n.signal_strength),
+            fast_transition: false,
+            ap_mld_address: String::new(),
+            noise: 0,
+            link_speed_mbps: 0,
+            taken: SystemTime::now(),
+        };
+        Some(LinkInfo::new(sample, RoamingFeatures::default(), Vec::new()))
+    }
+
+    fn event_log(&self) -> Vec<WifiEvent> {
+        self.events.snapshot()
+    }
+
+    fn record_event(&self, kind: EventKind) {
+        self.events.record(kind);
+    }
+
+    fn create_hotspot_with(&self, config: &HotspotConfig) -> Result<(), WifiError> {
+        let mut state = self.lock();
+        state.calls.push(MockCall::CreateHotspot { ssid: config.ssid.clone() });
+        state.hotspot = Some(config.ssid.clone());
+        drop(state);
+        self.events.record(EventKind::HotspotStarted { ssid: config.ssid.clone() });
+        Ok(())
+    }
+
+    fn stop_hotspot(&self) -> Result<(), WifiError> {
+        let mut state = self.lock();
+        state.calls.push(MockCall::StopHotspot);
+        if state.hotspot.take().is_some() {
+            drop(state);
+            self.events.record(EventKind::HotspotStopped);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,233 @@
+//! Wi-Fi Aware (NAN) service discovery.
+//!
+//! Devices publish named services and subscribe to services of others
+//! without being connected to the same network. A matching subscription
+//! produces a [`NanEvent::Discovered`]; the two sides can then exchange short
+//! follow-up messages, e.g. to agree on a Wi-Fi Direct connection or an
+//! address on a shared network.
+//!
+//! Backed by wpa_supplicant's unsynchronized service discovery (USD) on
+//! Linux, which needs wpa_supplicant 2.11 or later built with NAN USD.
+//! Dedicated NAN data paths are not set up.
+
+use std::ffi::CString;
+use std::time::Duration;
+
+use crate::diag::text;
+
+/// Largest service info or follow-up payload in bytes.
+pub const MAX_SERVICE_INFO: usize = 255;
+
+#[repr(C)]
+pub(crate) struct RawNanPublishConfig {
+    service_name: *const libc::c_char,
+    service_info: *const u8,
+    service_info_len: i32,
+    unsolicited: i32,
+    solicited: i32,
+    ttl_seconds: i32,
+}
+
+#[repr(C)]
+pub(crate) struct RawNanSubscribeConfig {
+    service_name: *const libc::c_char,
+    service_info: *const u8,
+    service_info_len: i32,
+    active: i32,
+    ttl_seconds: i32,
+}
+
+#[repr(C)]
+pub(crate) struct RawNanEvent {
+    kind: i32,
+    handle: i32,
+    peer_instance_id: i32,
+    peer_address: [libc::c_char; 18],
+    service_info: [u8; MAX_SERVICE_INFO],
+    service_info_len: i32,
+}
+
+impl RawNanEvent {
+    pub(crate) fn zeroed() -> Self {
+        // SAFETY: the struct is plain integers and arrays.
+        unsafe { std::mem::zeroed() }
+    }
+}
+
+fn ttl_seconds(ttl: Option<Duration>) -> i32 {
+    ttl.map_or(0, |ttl| ttl.as_secs().clamp(1, i32::MAX as u64) as i32)
+}
+
+/// A service to advertise with [`WiFi::nan_publish`](crate::WiFi::nan_publish).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NanPublish {
+    pub(crate) service_name: CString,
+    pub(crate) service_info: Vec<u8>,
+    pub(crate) unsolicited: bool,
+    pub(crate) solicited: bool,
+    pub(crate) ttl: Option<Duration>,
+}
+
+impl NanPublish {
+    /// Publish `service_name`, announcing it unsolicited and answering
+    /// subscribers until cancelled.
+    ///
+    /// Service names are limited to letters, digits, `.`, `-` and `_`.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the name is empty or contains other characters.
+    pub fn new(service_name: &str) -> Option<Self> {
+        Some(NanPublish {
+            service_name: service_name_c(service_name)?,
+            service_info: Vec::new(),
+            unsolicited: true,
+            solicited: true,
+            ttl: None,
+        })
+    }
+
+    /// Service specific information sent with the announcement, truncated to
+    /// [`MAX_SERVICE_INFO`] bytes.
+    pub fn service_info(mut self, info: &[u8]) -> Self {
+        self.service_info = info[..info.len().min(MAX_SERVICE_INFO)].to_vec();
+        self
+    }
+
+    /// Whether to announce the service without being asked.
+    pub fn unsolicited(mut self, unsolicited: bool) -> Self {
+        self.unsolicited = unsolicited;
+        self
+    }
+
+    /// Whether to answer active subscribers.
+    pub fn solicited(mut self, solicited: bool) -> Self {
+        self.solicited = solicited;
+        self
+    }
+
+    /// Stop publishing after `ttl`.
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    pub(crate) fn raw(&self) -> RawNanPublishConfig {
+        RawNanPublishConfig {
+            service_name: self.service_name.as_ptr(),
+            service_info: self.service_info.as_ptr(),
+            service_info_len: self.service_info.len() as i32,
+            unsolicited: self.unsolicited as i32,
+            solicited: self.solicited as i32,
+            ttl_seconds: ttl_seconds(self.ttl),
+        }
+    }
+}
+
+/// A service to look for with [`WiFi::nan_subscribe`](crate::WiFi::nan_subscribe).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NanSubscribe {
+    pub(crate) service_name: CString,
+    pub(crate) service_info: Vec<u8>,
+    pub(crate) active: bool,
+    pub(crate) ttl: Option<Duration>,
+}
+
+impl NanSubscribe {
+    /// Actively look for `service_name` until cancelled.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the name is empty or contains characters other than
+    /// letters, digits, `.`, `-` and `_`.
+    pub fn new(service_name: &str) -> Option<Self> {
+        Some(NanSubscribe {
+            service_name: service_name_c(service_name)?,
+            service_info: Vec::new(),
+            active: true,
+            ttl: None,
+        })
+    }
+
+    /// Service specific information sent with queries, truncated to
+    /// [`MAX_SERVICE_INFO`] bytes.
+    pub fn service_info(mut self, info: &[u8]) -> Self {
+        self.service_info = info[..info.len().min(MAX_SERVICE_INFO)].to_vec();
+        self
+    }
+
+    /// Send queries (active) or only listen for unsolicited announcements
+    /// (passive).
+    pub fn active(mut self, active: bool) -> Self {
+        self.active = active;
+        self
+    }
+
+    /// Stop subscribing after `ttl`.
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    pub(crate) fn raw(&self) -> RawNanSubscribeConfig {
+        RawNanSubscribeConfig {
+            service_name: self.service_name.as_ptr(),
+            service_info: self.service_info.as_ptr(),
+            service_info_len: self.service_info.len() as i32,
+            active: self.active as i32,
+            ttl_seconds: ttl_seconds(self.ttl),
+        }
+    }
+}
+
+fn service_name_c(name: &str) -> Option<CString> {
+    let valid = !name.is_empty()
+        && name.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'.' || b == b'-' || b == b'_');
+    if valid {
+        CString::new(name).ok()
+    } else {
+        None
+    }
+}
+
+/// Identifies an active publication or subscription.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NanHandle(pub(crate) i32);
+
+/// The other side of a discovery.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NanPeer {
+    /// The peer's publish or subscribe id.
+    pub instance_id: i32,
+    pub address: String,
+}
+
+/// Notification from an active publication or subscription.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NanEvent {
+    /// A subscription found a publisher, or a subscriber answered a
+    /// publication.
+    Discovered { handle: NanHandle, peer: NanPeer, service_info: Vec<u8> },
+    /// A follow-up message arrived.
+    Received { handle: NanHandle, peer: NanPeer, data: Vec<u8> },
+    /// The publication or subscription ended, e.g. because its TTL expired.
+    Terminated { handle: NanHandle },
+}
+
+impl NanEvent {
+    pub(crate) fn from_raw(raw: &RawNanEvent) -> Option<Self> {
+        let handle = NanHandle(raw.handle);
+        let peer = NanPeer {
+            instance_id: raw.peer_instance_id,
+            address: text(&raw.peer_address),
+        };
+        let len = (raw.service_info_len.max(0) as usize).min(MAX_SERVICE_INFO);
+        let data = raw.service_info[..len].to_vec();
+        match raw.kind {
+            0 => Some(NanEvent::Discovered { handle, peer, service_info: data }),
+            1 => Some(NanEvent::Received { handle, peer, data }),
+            2 => Some(NanEvent::Terminated { handle }),
+            _ => None,
+        }
+    }
+}
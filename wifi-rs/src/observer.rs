@@ -0,0 +1,101 @@
+//! Connectivity changes reported by the operating system.
+//!
+//! The event log normally only learns about transitions when the status is
+//! polled, so a connection that another program or the user drops and
+//! re-establishes between two polls goes unnoticed. A [`NetworkObserver`]
+//! subscribes to the platform's own notifications (rtnetlink link and
+//! address changes on Linux, the Network List Manager on Windows) and
+//! re-reads the status whenever one arrives, which records the transition
+//! in the event log like a poll would.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::time::Duration;
+
+use crate::{ConnectionStatus, WiFi, WifiError};
+
+pub(crate) type RawNetworkChangeCallback = extern "C" fn(user_data: *mut libc::c_void);
+
+extern "C" fn on_change(user_data: *mut libc::c_void) {
+    if user_data.is_null() {
+        return;
+    }
+    // SAFETY: user_data is the boxed sender owned by the `NetworkObserver`,
+    // which stops the observer (and therefore all callbacks) before freeing
+    // it.
+    let sender = unsafe { &*(user_data as *const Sender<()>) };
+    let _ = sender.send(());
+}
+
+/// Subscription to the operating system's connectivity notifications,
+/// created by [`WiFi::observe_network_changes`].
+///
+/// Notifications are queued until [`wait`](Self::wait) is called. Dropping
+/// the observer ends the subscription.
+///
+/// ```no_run
+/// use std::sync::atomic::AtomicBool;
+/// use wifi_rs::WiFi;
+///
+/// let wifi = WiFi::new();
+/// let stop = AtomicBool::new(false);
+/// let observer = wifi.observe_network_changes()?;
+/// observer.run(&stop, |status| println!("now {:?}", status));
+/// # Ok::<(), wifi_rs::WifiError>(())
+/// ```
+pub struct NetworkObserver<'a> {
+    wifi: &'a WiFi,
+    changes: Receiver<()>,
+    sender: *mut Sender<()>,
+}
+
+impl<'a> NetworkObserver<'a> {
+    pub(crate) fn start(wifi: &'a WiFi) -> Result<Self, WifiError> {
+        let (sender, changes) = mpsc::channel();
+        let sender = Box::into_raw(Box::new(sender));
+        let started = unsafe {
+            crate::wifi_manager_start_network_observer(wifi.manager, on_change, sender as *mut libc::c_void)
+        };
+        if !started {
+            drop(unsafe { Box::from_raw(sender) });
+            return Err(WifiError::Unsupported);
+        }
+        Ok(NetworkObserver { wifi, changes, sender })
+    }
+
+    /// Wait up to `timeout` for the operating system to report a change,
+    /// then read the status, recording any transition in the event log.
+    ///
+    /// # Returns
+    ///
+    /// The status after the change, or `None` if nothing was reported in
+    /// time.
+    pub fn wait(&self, timeout: Duration) -> Option<ConnectionStatus> {
+        match self.changes.recv_timeout(timeout) {
+            Ok(()) => {}
+            Err(RecvTimeoutError::Timeout) | Err(RecvTimeoutError::Disconnected) => return None,
+        }
+        // A burst of notifications describes one transition
+        while self.changes.try_recv().is_ok() {}
+        Some(self.wifi.get_status())
+    }
+
+    /// Wait for changes until `stop` is set, passing the status after each
+    /// to `on_change`.
+    pub fn run(&self, stop: &AtomicBool, mut on_change: impl FnMut(ConnectionStatus)) {
+        while !stop.load(Ordering::Relaxed) {
+            if let Some(status) = self.wait(Duration::from_millis(250)) {
+                on_change(status);
+            }
+        }
+    }
+}
+
+impl Drop for NetworkObserver<'_> {
+    fn drop(&mut self) {
+        unsafe {
+            crate::wifi_manager_stop_network_observer(self.wifi.manager);
+            drop(Box::from_raw(self.sender));
+        }
+    }
+}
@@ -0,0 +1,211 @@
+//! Wi-Fi Direct (P2P) device discovery and group formation.
+//!
+//! Lets two devices connect directly without an access point, e.g. to
+//! transfer files from a phone. Discovery finds nearby P2P devices, group
+//! owner negotiation decides which side acts as the access point, and the
+//! resulting [`P2pGroup`] names the network interface to use for traffic.
+//!
+//! Backed by wpa_supplicant's P2P support on Linux. Windows exposes Wi-Fi
+//! Direct only through the WinRT device APIs, which this crate does not
+//! bind, so there and on other platforms [`WiFi::is_p2p_supported`]
+//! reports `false` and the operations fail with
+//! [`WifiError::Unsupported`](crate::WifiError::Unsupported).
+//!
+//! [`WiFi::is_p2p_supported`]: crate::WiFi::is_p2p_supported
+
+use std::ffi::CString;
+use std::time::Duration;
+
+use crate::diag::text;
+use crate::wps;
+
+#[repr(C)]
+pub(crate) struct RawP2pPeer {
+    device_address: [libc::c_char; 18],
+    device_name: [libc::c_char; 64],
+    device_type: [libc::c_char; 32],
+    group_owner: i32,
+}
+
+#[repr(C)]
+pub(crate) struct RawP2pConnectConfig {
+    peer_address: *const libc::c_char,
+    wps_method: i32,
+    pin: *const libc::c_char,
+    go_intent: i32,
+}
+
+#[repr(C)]
+pub(crate) struct RawP2pGroup {
+    interface_name: [libc::c_char; 32],
+    ssid: [libc::c_char; 33],
+    group_owner: i32,
+    go_device_address: [libc::c_char; 18],
+    frequency: i32,
+}
+
+impl RawP2pGroup {
+    pub(crate) fn zeroed() -> Self {
+        // SAFETY: the struct is plain integers and character arrays.
+        unsafe { std::mem::zeroed() }
+    }
+}
+
+/// A Wi-Fi Direct device found by [`WiFi::p2p_discover`](crate::WiFi::p2p_discover).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct P2pPeer {
+    /// P2P device address, used to connect.
+    pub device_address: String,
+    pub device_name: String,
+    /// Primary device type, e.g. `10-0050F204-5` for a smartphone.
+    pub device_type: String,
+    /// Whether the peer currently owns a group.
+    pub group_owner: bool,
+}
+
+impl P2pPeer {
+    pub(crate) fn from_raw(raw: &RawP2pPeer) -> Self {
+        P2pPeer {
+            device_address: text(&raw.device_address),
+            device_name: text(&raw.device_name),
+            device_type: text(&raw.device_type),
+            group_owner: raw.group_owner != 0,
+        }
+    }
+}
+
+/// How the connection to a peer is authorized.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum P2pWps {
+    /// Push button on both devices.
+    PushButton,
+    /// This device shows the PIN, the user enters it on the peer.
+    DisplayPin(String),
+    /// The peer shows a PIN, the user enters it here.
+    EnterPin(String),
+}
+
+/// Settings for [`WiFi::p2p_connect`](crate::WiFi::p2p_connect).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct P2pConnectConfig {
+    pub(crate) peer_address: String,
+    pub(crate) wps: P2pWps,
+    pub(crate) go_intent: u8,
+    pub(crate) timeout: Duration,
+}
+
+impl P2pConnectConfig {
+    /// Connect to the peer with the given device address using push button
+    /// authorization, a neutral group owner intent and a two-minute timeout.
+    pub fn new(peer_address: &str) -> Self {
+        P2pConnectConfig {
+            peer_address: peer_address.to_string(),
+            wps: P2pWps::PushButton,
+            go_intent: 7,
+            timeout: Duration::from_secs(120),
+        }
+    }
+
+    pub fn wps(mut self, wps: P2pWps) -> Self {
+        self.wps = wps;
+        self
+    }
+
+    /// Show a freshly generated PIN on this device; read it with
+    /// [`pin`](Self::pin) to display it.
+    pub fn display_generated_pin(self) -> Self {
+        self.wps(P2pWps::DisplayPin(wps::generate_pin()))
+    }
+
+    /// Willingness to become group owner, from 0 (client) to 15 (must be
+    /// owner). Values above 15 are clamped.
+    pub fn go_intent(mut self, intent: u8) -> Self {
+        self.go_intent = intent.min(15);
+        self
+    }
+
+    /// How long to wait for negotiation and group formation.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// The PIN used for authorization, if any.
+    pub fn pin(&self) -> Option<&str> {
+        match &self.wps {
+            P2pWps::PushButton => None,
+            P2pWps::DisplayPin(pin) | P2pWps::EnterPin(pin) => Some(pin),
+        }
+    }
+}
+
+/// Keeps the strings referenced by a [`RawP2pConnectConfig`] alive.
+pub(crate) struct P2pConnectStrings {
+    peer_address: CString,
+    pin: Option<CString>,
+    wps_method: i32,
+    go_intent: i32,
+}
+
+impl P2pConnectStrings {
+    /// `None` if the peer is not a MAC address or the PIN is not all digits.
+    pub(crate) fn new(config: &P2pConnectConfig) -> Option<Self> {
+        if !crate::connect::is_bssid(&config.peer_address) {
+            return None;
+        }
+        if let Some(pin) = config.pin() {
+            if pin.is_empty() || !pin.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+        }
+        let wps_method = match config.wps {
+            P2pWps::PushButton => 0,
+            P2pWps::DisplayPin(_) => 1,
+            P2pWps::EnterPin(_) => 2,
+        };
+        Some(P2pConnectStrings {
+            peer_address: CString::new(config.peer_address.as_str()).ok()?,
+            pin: match config.pin() {
+                Some(pin) => Some(CString::new(pin).ok()?),
+                None => None,
+            },
+            wps_method,
+            go_intent: config.go_intent as i32,
+        })
+    }
+
+    pub(crate) fn raw(&self) -> RawP2pConnectConfig {
+        RawP2pConnectConfig {
+            peer_address: self.peer_address.as_ptr(),
+            wps_method: self.wps_method,
+            pin: self.pin.as_ref().map_or(std::ptr::null(), |p| p.as_ptr()),
+            go_intent: self.go_intent,
+        }
+    }
+}
+
+/// A Wi-Fi Direct group this device belongs to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct P2pGroup {
+    /// Network interface carrying the group's traffic.
+    pub interface_name: String,
+    pub ssid: String,
+    /// Whether this device is the group owner. As group owner, the
+    /// application is responsible for assigning addresses to clients.
+    pub group_owner: bool,
+    pub go_device_address: String,
+    /// Operating frequency in MHz.
+    pub frequency: i32,
+}
+
+impl P2pGroup {
+    pub(crate) fn from_raw(raw: &RawP2pGroup) -> Self {
+        P2pGroup {
+            interface_name: text(&raw.interface_name),
+            ssid: text(&raw.ssid),
+            group_owner: raw.group_owner != 0,
+            go_device_address: text(&raw.go_device_address),
+            frequency: raw.frequency,
+        }
+    }
+}
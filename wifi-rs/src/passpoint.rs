@@ -0,0 +1,88 @@
+//! Passpoint (Hotspot 2.0) credential provisioning.
+//!
+//! Installed credentials let the platform join partner hotspots of the home
+//! service provider automatically, using the enterprise (EAP) plumbing.
+
+use crate::eap::{ClientCertificate, Eap, EapConfig, EapStrings, RawEapConfig, TtlsInner};
+use crate::secret::SecretString;
+use crate::WifiError;
+use std::ffi::CString;
+use std::path::PathBuf;
+
+/// Credential presented to Passpoint hotspots.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PasspointCredential {
+    /// Username/password, authenticated with EAP-TTLS/MSCHAPv2.
+    Password { username: String, password: SecretString },
+    /// Client certificate, authenticated with EAP-TLS.
+    Certificate { identity: String, client_cert: ClientCertificate },
+}
+
+/// A Passpoint profile issued by a home service provider.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PasspointProfile {
+    /// Home service provider FQDN, also used to identify the profile.
+    pub domain: String,
+    /// NAI realm of the credential.
+    pub realm: String,
+    /// Roaming consortium OIs as hex strings (e.g. `"5a03ba0000"`).
+    pub home_ois: Vec<String>,
+    /// Human readable provider name.
+    pub friendly_name: Option<String>,
+    pub credential: PasspointCredential,
+    /// CA certificate used to validate the AAA server; the system store is used when absent.
+    pub ca_cert: Option<PathBuf>,
+}
+
+impl PasspointProfile {
+    fn eap_config(&self) -> EapConfig {
+        let eap = match &self.credential {
+            PasspointCredential::Password { username, password } => Eap::Ttls {
+                identity: username.clone(),
+                password: password.clone(),
+                inner: TtlsInner::Mschapv2,
+                anonymous_identity: None,
+                ca_cert: self.ca_cert.clone(),
+            },
+            PasspointCredential::Certificate { identity, client_cert } => Eap::Tls {
+                identity: identity.clone(),
+                client_cert: client_cert.clone(),
+                ca_cert: self.ca_cert.clone(),
+            },
+        };
+        EapConfig::new(eap).server_domain_suffix(&self.domain)
+    }
+}
+
+#[repr(C)]
+pub(crate) struct RawPasspointProfile {
+    domain: *const libc::c_char,
+    realm: *const libc::c_char,
+    home_ois: *const libc::c_char,
+    friendly_name: *const libc::c_char,
+    credentials: RawEapConfig,
+}
+
+/// Invoke `f` with a raw profile whose pointers stay valid for the call.
+///
+/// # Errors
+///
+/// Returns [`WifiError::InvalidArgument`] without calling `f` if a string
+/// of the profile contains a NUL byte.
+pub(crate) fn with_raw_profile<T>(profile: &PasspointProfile, f: impl FnOnce(&RawPasspointProfile) -> T) -> Result<T, WifiError> {
+    let domain = CString::new(profile.domain.as_str())?;
+    let realm = CString::new(profile.realm.as_str())?;
+    let home_ois = CString::new(profile.home_ois.join(","))?;
+    let friendly_name = profile.friendly_name.as_deref().map(CString::new).transpose()?;
+    let eap = profile.eap_config();
+    let strings = EapStrings::new(&eap)?;
+
+    let raw = RawPasspointProfile {
+        domain: domain.as_ptr(),
+        realm: realm.as_ptr(),
+        home_ois: if profile.home_ois.is_empty() { std::ptr::null() } else { home_ois.as_ptr() },
+        friendly_name: friendly_name.as_ref().map_or(std::ptr::null(), |n| n.as_ptr()),
+        credentials: strings.raw(),
+    };
+    Ok(f(&raw))
+}
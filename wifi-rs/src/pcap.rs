@@ -0,0 +1,306 @@
+//! pcapng export of captured 802.11 frames.
+//!
+//! Frames are written with a radiotap header (link type
+//! `LINKTYPE_IEEE802_11_RADIOTAP`) so the resulting files open directly in
+//! Wireshark and tcpdump.
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const BLOCK_SECTION_HEADER: u32 = 0x0A0D_0D0A;
+const BLOCK_INTERFACE_DESCRIPTION: u32 = 0x0000_0001;
+const BLOCK_ENHANCED_PACKET: u32 = 0x0000_0006;
+const BYTE_ORDER_MAGIC: u32 = 0x1A2B_3C4D;
+
+const LINKTYPE_IEEE802_11_RADIOTAP: u16 = 127;
+
+const OPT_END_OF_OPT: u16 = 0;
+const OPT_IF_NAME: u16 = 2;
+
+// Radiotap field presence bits
+const RADIOTAP_TSFT: u32 = 0;
+const RADIOTAP_FLAGS: u32 = 1;
+const RADIOTAP_RATE: u32 = 2;
+const RADIOTAP_CHANNEL: u32 = 3;
+const RADIOTAP_DBM_ANTSIGNAL: u32 = 5;
+const RADIOTAP_DBM_ANTNOISE: u32 = 6;
+const RADIOTAP_ANTENNA: u32 = 11;
+
+/// Radiotap channel flag: CCK channel.
+pub const CHANNEL_CCK: u16 = 0x0020;
+/// Radiotap channel flag: OFDM channel.
+pub const CHANNEL_OFDM: u16 = 0x0040;
+/// Radiotap channel flag: 2 GHz spectrum channel.
+pub const CHANNEL_2GHZ: u16 = 0x0080;
+/// Radiotap channel flag: 5 GHz spectrum channel.
+pub const CHANNEL_5GHZ: u16 = 0x0100;
+
+const BAND_2GHZ: std::ops::Range<u16> = 2400..2500;
+const BAND_5GHZ: std::ops::Range<u16> = 4900..5925;
+const BAND_6GHZ: std::ops::RangeInclusive<u16> = 5925..=7125;
+
+/// Per-frame radio metadata encoded into the radiotap header.
+///
+/// Only the fields that are `Some` are emitted.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RadiotapInfo {
+    /// Value of the TSF timer when the first bit of the frame arrived, in microseconds.
+    pub tsft: Option<u64>,
+    /// Radiotap frame flags (FCS present, short preamble, ...).
+    pub flags: Option<u8>,
+    /// Legacy data rate in units of 500 kbps.
+    pub rate: Option<u8>,
+    /// Channel center frequency in MHz.
+    pub channel_frequency: Option<u16>,
+    /// Radiotap channel flags; see [`channel_flags_for_frequency`].
+    pub channel_flags: u16,
+    /// Antenna signal in dBm.
+    pub signal_dbm: Option<i8>,
+    /// Antenna noise in dBm.
+    pub noise_dbm: Option<i8>,
+    /// Antenna index the frame was received on.
+    pub antenna: Option<u8>,
+}
+
+impl RadiotapInfo {
+    /// Encode the metadata as a little-endian radiotap header.
+    pub fn to_header(&self) -> Vec<u8> {
+        let mut present: u32 = 0;
+        let mut fields: Vec<u8> = Vec::new();
+        // Field alignment is relative to the start of the header, which is
+        // the 8-byte fixed part (version, pad, length, present word).
+        const FIXED_LEN: usize = 8;
+        let align = |fields: &mut Vec<u8>, to: usize| {
+            while !(FIXED_LEN + fields.len()).is_multiple_of(to) {
+                fields.push(0);
+            }
+        };
+
+        if let Some(tsft) = self.tsft {
+            present |= 1 << RADIOTAP_TSFT;
+            align(&mut fields, 8);
+            fields.extend_from_slice(&tsft.to_le_bytes());
+        }
+        if let Some(flags) = self.flags {
+            present |= 1 << RADIOTAP_FLAGS;
+            fields.push(flags);
+        }
+        if let Some(rate) = self.rate {
+            present |= 1 << RADIOTAP_RATE;
+            fields.push(rate);
+        }
+        if let Some(frequency) = self.channel_frequency {
+            present |= 1 << RADIOTAP_CHANNEL;
+            align(&mut fields, 2);
+            fields.extend_from_slice(&frequency.to_le_bytes());
+            fields.extend_from_slice(&self.channel_flags.to_le_bytes());
+        }
+        if let Some(signal) = self.signal_dbm {
+            present |= 1 << RADIOTAP_DBM_ANTSIGNAL;
+            fields.push(signal as u8);
+        }
+        if let Some(noise) = self.noise_dbm {
+            present |= 1 << RADIOTAP_DBM_ANTNOISE;
+            fields.push(noise as u8);
+        }
+        if let Some(antenna) = self.antenna {
+            present |= 1 << RADIOTAP_ANTENNA;
+            fields.push(antenna);
+        }
+
+        let length = (FIXED_LEN + fields.len()) as u16;
+        let mut header = Vec::with_capacity(length as usize);
+        header.push(0); // version
+        header.push(0); // pad
+        header.extend_from_slice(&length.to_le_bytes());
+        header.extend_from_slice(&present.to_le_bytes());
+        header.extend_from_slice(&fields);
+        header
+    }
+
+    /// Decode a little-endian radiotap header at the start of `packet`.
+    ///
+    /// Returns the decoded fields and the header length, i.e. the offset of
+    /// the 802.11 frame. Fields not represented in `RadiotapInfo` are skipped.
+    pub fn parse(packet: &[u8]) -> Option<(RadiotapInfo, usize)> {
+        if packet.len() < 8 || packet[0] != 0 {
+            return None;
+        }
+        let length = u16::from_le_bytes([packet[2], packet[3]]) as usize;
+        if length < 8 || length > packet.len() {
+            return None;
+        }
+        let header = &packet[..length];
+
+        // Skip extended presence bitmaps; only the first one is decoded
+        let present = u32::from_le_bytes([header[4], header[5], header[6], header[7]]);
+        let mut offset = 8;
+        let mut word = present;
+        while word & (1 << 31) != 0 {
+            if offset + 4 > length {
+                return None;
+            }
+            word = u32::from_le_bytes([header[offset], header[offset + 1], header[offset + 2], header[offset + 3]]);
+            offset += 4;
+        }
+
+        // (bit, alignment, size) of the fields up to and including the antenna
+        const FIELDS: [(u32, usize, usize); 12] = [
+            (RADIOTAP_TSFT, 8, 8),
+            (RADIOTAP_FLAGS, 1, 1),
+            (RADIOTAP_RATE, 1, 1),
+            (RADIOTAP_CHANNEL, 2, 4),
+            (4, 1, 2), // FHSS
+            (RADIOTAP_DBM_ANTSIGNAL, 1, 1),
+            (RADIOTAP_DBM_ANTNOISE, 1, 1),
+            (7, 2, 2),  // lock quality
+            (8, 2, 2),  // TX attenuation
+            (9, 2, 2),  // dB TX attenuation
+            (10, 1, 1), // dBm TX power
+            (RADIOTAP_ANTENNA, 1, 1),
+        ];
+
+        let mut info = RadiotapInfo::default();
+        for (bit, alignment, size) in FIELDS {
+            if present & (1 << bit) == 0 {
+                continue;
+            }
+            offset = offset.next_multiple_of(alignment);
+            let field = header.get(offset..offset + size)?;
+            match bit {
+                RADIOTAP_TSFT => info.tsft = Some(u64::from_le_bytes(field.try_into().ok()?)),
+                RADIOTAP_FLAGS => info.flags = Some(field[0]),
+                RADIOTAP_RATE => info.rate = Some(field[0]),
+                RADIOTAP_CHANNEL => {
+                    info.channel_frequency = Some(u16::from_le_bytes([field[0], field[1]]));
+                    info.channel_flags = u16::from_le_bytes([field[2], field[3]]);
+                }
+                RADIOTAP_DBM_ANTSIGNAL => info.signal_dbm = Some(field[0] as i8),
+                RADIOTAP_DBM_ANTNOISE => info.noise_dbm = Some(field[0] as i8),
+                RADIOTAP_ANTENNA => info.antenna = Some(field[0]),
+                _ => {}
+            }
+            offset += size;
+        }
+        Some((info, length))
+    }
+}
+
+/// Derive the radiotap channel flags for a center frequency in MHz.
+///
+/// 2.4 GHz frames may be CCK or OFDM, so no modulation flag is set for
+/// them; add [`CHANNEL_CCK`] or [`CHANNEL_OFDM`] when the rate is known.
+/// Radiotap has no 6 GHz band flag, so 6 GHz channels are only marked as
+/// OFDM. Frequencies outside the WiFi bands get no flags.
+pub fn channel_flags_for_frequency(frequency: u16) -> u16 {
+    if BAND_2GHZ.contains(&frequency) {
+        CHANNEL_2GHZ
+    } else if BAND_5GHZ.contains(&frequency) {
+        CHANNEL_5GHZ | CHANNEL_OFDM
+    } else if BAND_6GHZ.contains(&frequency) {
+        CHANNEL_OFDM
+    } else {
+        0
+    }
+}
+
+/// Writer producing a pcapng stream with a single radiotap interface.
+pub struct PcapngWriter<W: Write> {
+    out: W,
+}
+
+impl PcapngWriter<BufWriter<File>> {
+    /// Create (or truncate) a pcapng file at `path`.
+    pub fn create<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::new(BufWriter::new(File::create(path)?), None)
+    }
+}
+
+impl<W: Write> PcapngWriter<W> {
+    /// Start a new pcapng stream, writing the section header and interface description.
+    ///
+    /// # Arguments
+    ///
+    /// * `out` - Destination of the pcapng data
+    /// * `interface_name` - Optional capture interface name recorded in the file
+    pub fn new(mut out: W, interface_name: Option<&str>) -> io::Result<Self> {
+        // Section Header Block
+        let mut shb = Vec::new();
+        shb.extend_from_slice(&BYTE_ORDER_MAGIC.to_le_bytes());
+        shb.extend_from_slice(&1u16.to_le_bytes()); // major version
+        shb.extend_from_slice(&0u16.to_le_bytes()); // minor version
+        shb.extend_from_slice(&(-1i64).to_le_bytes()); // section length unspecified
+        write_block(&mut out, BLOCK_SECTION_HEADER, &shb)?;
+
+        // Interface Description Block
+        let mut idb = Vec::new();
+        idb.extend_from_slice(&LINKTYPE_IEEE802_11_RADIOTAP.to_le_bytes());
+        idb.extend_from_slice(&0u16.to_le_bytes()); // reserved
+        idb.extend_from_slice(&0u32.to_le_bytes()); // snap length: unlimited
+        if let Some(name) = interface_name {
+            push_option(&mut idb, OPT_IF_NAME, name.as_bytes());
+            push_option(&mut idb, OPT_END_OF_OPT, &[]);
+        }
+        write_block(&mut out, BLOCK_INTERFACE_DESCRIPTION, &idb)?;
+
+        Ok(PcapngWriter { out })
+    }
+
+    /// Write an 802.11 frame, prefixing it with a radiotap header built from `radiotap`.
+    pub fn write_frame(&mut self, timestamp: SystemTime, radiotap: &RadiotapInfo, frame: &[u8]) -> io::Result<()> {
+        let mut packet = radiotap.to_header();
+        packet.extend_from_slice(frame);
+        self.write_packet(timestamp, &packet)
+    }
+
+    /// Write a packet that already starts with a radiotap header.
+    pub fn write_packet(&mut self, timestamp: SystemTime, packet: &[u8]) -> io::Result<()> {
+        let micros = timestamp
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_micros() as u64)
+            .unwrap_or(0);
+
+        let mut epb = Vec::with_capacity(20 + packet.len() + 3);
+        epb.extend_from_slice(&0u32.to_le_bytes()); // interface id
+        epb.extend_from_slice(&((micros >> 32) as u32).to_le_bytes());
+        epb.extend_from_slice(&(micros as u32).to_le_bytes());
+        epb.extend_from_slice(&(packet.len() as u32).to_le_bytes()); // captured length
+        epb.extend_from_slice(&(packet.len() as u32).to_le_bytes()); // original length
+        epb.extend_from_slice(packet);
+        pad_to_32(&mut epb);
+        write_block(&mut self.out, BLOCK_ENHANCED_PACKET, &epb)
+    }
+
+    /// Flush buffered output.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.out.flush()
+    }
+
+    /// Consume the writer and return the underlying output.
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+}
+
+fn push_option(body: &mut Vec<u8>, code: u16, value: &[u8]) {
+    body.extend_from_slice(&code.to_le_bytes());
+    body.extend_from_slice(&(value.len() as u16).to_le_bytes());
+    body.extend_from_slice(value);
+    pad_to_32(body);
+}
+
+fn pad_to_32(body: &mut Vec<u8>) {
+    while !body.len().is_multiple_of(4) {
+        body.push(0);
+    }
+}
+
+fn write_block<W: Write>(out: &mut W, block_type: u32, body: &[u8]) -> io::Result<()> {
+    let total_length = (12 + body.len()) as u32;
+    out.write_all(&block_type.to_le_bytes())?;
+    out.write_all(&total_length.to_le_bytes())?;
+    out.write_all(body)?;
+    out.write_all(&total_length.to_le_bytes())
+}
@@ -0,0 +1,95 @@
+//! The 802.11 generation of an access point, from its information elements.
+
+use crate::channel::Band;
+use crate::NetworkInfo;
+
+const SUPPORTED_RATES: u8 = 1;
+const EXTENDED_SUPPORTED_RATES: u8 = 50;
+const EXTENSION: u8 = 255;
+const EHT_CAPABILITIES: u8 = 108;
+
+/// The newest 802.11 standard an access point advertises.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PhyMode {
+    /// The platform did not report the information elements.
+    Unknown,
+    /// 802.11b, DSSS rates up to 11 Mbit/s.
+    B,
+    /// 802.11a, OFDM on 5 GHz.
+    A,
+    /// 802.11g, OFDM on 2.4 GHz.
+    G,
+    /// 802.11n (Wi-Fi 4).
+    N,
+    /// 802.11ac (Wi-Fi 5).
+    Ac,
+    /// 802.11ax (Wi-Fi 6 and 6E).
+    Ax,
+    /// 802.11be (Wi-Fi 7).
+    Be,
+}
+
+impl NetworkInfo {
+    /// Fill in [`phy_mode`](Self::phy_mode) and
+    /// [`max_rate_mbps`](Self::max_rate_mbps) from the other fields.
+    pub(crate) fn with_phy(mut self) -> Self {
+        (self.phy_mode, self.max_rate_mbps) = PhyMode::detect(&self);
+        self
+    }
+}
+
+impl PhyMode {
+    /// Detect the standard and the highest PHY rate in Mbit/s from the
+    /// capabilities in `network`'s information elements.
+    fn detect(network: &NetworkInfo) -> (PhyMode, u32) {
+        let eht = network.wifi7
+            || network
+                .elements()
+                .any(|element| element.id == EXTENSION && element.extension_id == Some(EHT_CAPABILITIES));
+        if let Some(he) = network.he_capabilities() {
+            let width = match he.phy[0] {
+                bits if bits & 0x08 != 0 => 160,
+                bits if bits & 0x04 != 0 => 80,
+                bits if bits & 0x02 != 0 => 40,
+                _ => 20,
+            };
+            // MCS 11 with 0.8 µs guard interval per stream
+            let per_stream = match width {
+                160 => 1201,
+                80 => 600,
+                40 => 287,
+                _ => 143,
+            };
+            let rate = per_stream * u32::from(he.max_spatial_streams.max(1));
+            // MCS 13 carries 12 bits per symbol instead of 10
+            return if eht { (PhyMode::Be, rate * 6 / 5) } else { (PhyMode::Ax, rate) };
+        }
+        if eht {
+            return (PhyMode::Be, 0);
+        }
+        if let Some(vht) = network.vht_capabilities() {
+            // MCS 9 with short guard interval per stream
+            let per_stream = if vht.supports_160mhz() { 867 } else { 433 };
+            return (PhyMode::Ac, per_stream * u32::from(vht.max_spatial_streams.max(1)));
+        }
+        if let Some(ht) = network.ht_capabilities() {
+            // MCS 7 with short guard interval per stream
+            let per_stream = if ht.supports_40mhz() { 150 } else { 72 };
+            return (PhyMode::N, per_stream * u32::from(ht.max_spatial_streams.max(1)));
+        }
+
+        // Rates are in units of 500 kbit/s, the top bit marking basic rates
+        let legacy = network
+            .elements()
+            .filter(|element| element.id == SUPPORTED_RATES || element.id == EXTENDED_SUPPORTED_RATES)
+            .flat_map(|element| element.data.iter().map(|rate| u32::from(rate & 0x7f) / 2))
+            .max();
+        match legacy {
+            None => (PhyMode::Unknown, 0),
+            Some(rate) if rate <= 11 => (PhyMode::B, rate),
+            Some(rate) if Band::from_frequency(network.frequency) == Some(Band::Ghz2) => (PhyMode::G, rate),
+            Some(rate) => (PhyMode::A, rate),
+        }
+    }
+}
@@ -0,0 +1,327 @@
+//! Rule-based selection of the best known network.
+//!
+//! A [`ConnectionPolicy`] holds the networks a device may join and the
+//! rules for choosing among them: a minimum signal, a preferred band,
+//! blocked SSIDs and access points, and whether metered networks are a last
+//! resort. Each check ranks the visible known networks, connects when the
+//! device is offline and moves it when a clearly better candidate appears.
+//!
+//! ```
+//! # #[cfg(feature = "backend-mock")] {
+//! use wifi_rs::sim::SimEnv;
+//! use wifi_rs::{ConnectOptions, ConnectionPolicy, PolicyEvent, PolicyNetwork, PolicyRules, WifiBackend};
+//!
+//! let env = SimEnv::new().ap("Phone", 6, -45).ap("Office", 36, -60);
+//! let mut policy = ConnectionPolicy::new(PolicyRules { avoid_metered: true, ..PolicyRules::default() })
+//!     .network(PolicyNetwork::new("Phone", ConnectOptions::password("secret")).metered(true))
+//!     .known_network("Office", ConnectOptions::password("secret"));
+//!
+//! let event = policy.check(&env);
+//! assert!(matches!(event, Some(PolicyEvent::Connected { ref ssid, .. }) if ssid == "Office"));
+//! assert_eq!(env.link_info().unwrap().ssid, "Office");
+//! # }
+//! ```
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::{AutoConnect, Band, ConnectOptions, EventKind, NetworkInfo, WifiBackend, WifiError};
+
+/// How many dB of signal one step of [`PolicyNetwork::priority`] is worth.
+pub const PRIORITY_STEP_DB: i32 = 10;
+
+/// Rules a [`ConnectionPolicy`] applies to every candidate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolicyRules {
+    /// Signal strength in dBm below which an access point is ignored.
+    pub min_signal_dbm: i32,
+    /// Band whose access points get [`band_bonus_db`](Self::band_bonus_db)
+    /// added to their signal when ranking.
+    pub preferred_band: Option<Band>,
+    pub band_bonus_db: i32,
+    /// SSIDs and BSSIDs never to join, compared case-insensitively.
+    pub blocklist: Vec<String>,
+    /// Only join a metered network when no unmetered one is usable.
+    pub avoid_metered: bool,
+    /// How many dB a candidate's score must exceed the current network's
+    /// before switching.
+    pub hysteresis_db: i32,
+    /// Minimum time between two switches.
+    pub cooldown: Duration,
+    /// How long to skip a network after connecting to it failed.
+    pub retry_after: Duration,
+}
+
+impl Default for PolicyRules {
+    fn default() -> Self {
+        PolicyRules {
+            min_signal_dbm: -80,
+            preferred_band: None,
+            band_bonus_db: 10,
+            blocklist: Vec::new(),
+            avoid_metered: false,
+            hysteresis_db: 8,
+            cooldown: Duration::from_secs(60),
+            retry_after: Duration::from_secs(300),
+        }
+    }
+}
+
+/// A network the policy may join.
+#[derive(Debug, Clone)]
+pub struct PolicyNetwork {
+    ssid: String,
+    options: ConnectOptions,
+    priority: i32,
+    metered: bool,
+}
+
+impl PolicyNetwork {
+    pub fn new(ssid: &str, options: ConnectOptions) -> Self {
+        PolicyNetwork { ssid: ssid.to_string(), options, priority: 0, metered: false }
+    }
+
+    /// Rank the network higher (or lower, if negative) than its signal
+    /// alone would, by [`PRIORITY_STEP_DB`] per step.
+    pub fn priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Mark the network as metered, e.g. a phone's hotspot.
+    pub fn metered(mut self, metered: bool) -> Self {
+        self.metered = metered;
+        self
+    }
+}
+
+/// A visible known network that passes the rules.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Candidate {
+    pub ssid: String,
+    /// Strongest usable access point of the network.
+    pub bssid: String,
+    pub frequency: i32,
+    pub signal_dbm: i32,
+    pub metered: bool,
+    /// Signal plus band bonus and priority; higher is better.
+    pub score: i32,
+}
+
+/// What the policy did during a check.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PolicyEvent {
+    /// Joined `ssid` while not connected.
+    Connected { ssid: String, signal_dbm: i32 },
+    /// Moved from `from` to the better candidate `to`.
+    Switched { from: String, to: String, signal_dbm: i32 },
+    /// Connecting to `ssid` failed; it is skipped for
+    /// [`PolicyRules::retry_after`].
+    ConnectFailed { ssid: String, error: WifiError },
+    /// The connection to `ssid` dropped. Only reported by [`AutoConnect`],
+    /// which then checks the policy right away.
+    LinkLost { ssid: String },
+}
+
+/// Policy engine that keeps the device on the best known network.
+///
+/// The device is moved off a network that is unknown, blocked or, with
+/// [`PolicyRules::avoid_metered`], metered while a better alternative is
+/// visible. Otherwise it only switches when a candidate beats the current
+/// network by the hysteresis margin and the cooldown has passed.
+///
+/// ```no_run
+/// use std::sync::atomic::AtomicBool;
+/// use std::time::Duration;
+/// use wifi_rs::{Band, ConnectOptions, ConnectionPolicy, PolicyRules, WiFi};
+///
+/// let wifi = WiFi::new();
+/// let stop = AtomicBool::new(false);
+/// let rules = PolicyRules {
+///     preferred_band: Some(Band::Ghz5),
+///     blocklist: vec!["Guest".to_string()],
+///     ..PolicyRules::default()
+/// };
+/// let mut policy = ConnectionPolicy::new(rules)
+///     .known_network("Office", ConnectOptions::password("secret"))
+///     .known_network("Lab", ConnectOptions::password("secret"));
+/// policy.run(&wifi, Duration::from_secs(30), &stop, |event| println!("{:?}", event));
+/// ```
+pub struct ConnectionPolicy {
+    rules: PolicyRules,
+    networks: Vec<PolicyNetwork>,
+    last_switch: Option<Instant>,
+    failed: Vec<(String, Instant)>,
+}
+
+impl ConnectionPolicy {
+    pub fn new(rules: PolicyRules) -> Self {
+        ConnectionPolicy { rules, networks: Vec::new(), last_switch: None, failed: Vec::new() }
+    }
+
+    /// Add a network with default priority that is not metered.
+    pub fn known_network(self, ssid: &str, options: ConnectOptions) -> Self {
+        self.network(PolicyNetwork::new(ssid, options))
+    }
+
+    /// Add a network the policy may join.
+    pub fn network(mut self, network: PolicyNetwork) -> Self {
+        self.networks.push(network);
+        self
+    }
+
+    pub fn rules(&self) -> &PolicyRules {
+        &self.rules
+    }
+
+    fn blocked(&self, value: &str) -> bool {
+        self.rules.blocklist.iter().any(|entry| entry.eq_ignore_ascii_case(value))
+    }
+
+    fn score(&self, network: &PolicyNetwork, frequency: i32, signal_dbm: i32) -> i32 {
+        let bonus = match self.rules.preferred_band {
+            Some(band) if Band::from_frequency(frequency) == Some(band) => self.rules.band_bonus_db,
+            _ => 0,
+        };
+        signal_dbm + bonus + network.priority * PRIORITY_STEP_DB
+    }
+
+    /// Whether `a` should be preferred over `b`.
+    fn better(&self, a: &Candidate, b: &Candidate) -> bool {
+        if self.rules.avoid_metered && a.metered != b.metered {
+            return !a.metered;
+        }
+        a.score > b.score
+    }
+
+    /// Known networks in `networks` that pass the rules, best first.
+    ///
+    /// Networks that recently failed to connect are included; only
+    /// [`check`](Self::check) skips them.
+    pub fn rank(&self, networks: &[NetworkInfo]) -> Vec<Candidate> {
+        let mut candidates: Vec<Candidate> = self
+            .networks
+            .iter()
+            .filter(|known| !self.blocked(&known.ssid))
+            .filter_map(|known| {
+                // Only consider the band the network's own policy would join
+                let band = known.options.band_policy.map(|policy| policy.choose(&known.ssid, networks));
+                networks
+                    .iter()
+                    .filter(|n| n.ssid == known.ssid && !self.blocked(&n.bssid))
+                    .filter(|n| n.signal_strength >= self.rules.min_signal_dbm)
+                    .filter(|n| band.is_none_or(|band| band.is_some() && Band::from_frequency(n.frequency) == band))
+                    .map(|n| Candidate {
+                        ssid: n.ssid.clone(),
+                        bssid: n.bssid.clone(),
+                        frequency: n.frequency,
+                        signal_dbm: n.signal_strength,
+                        metered: known.metered,
+                        score: self.score(known, n.frequency, n.signal_strength),
+                    })
+                    .max_by_key(|candidate| candidate.score)
+            })
+            .collect();
+        candidates.sort_by(|a, b| {
+            let metered = |c: &Candidate| self.rules.avoid_metered && c.metered;
+            metered(a).cmp(&metered(b)).then(b.score.cmp(&a.score))
+        });
+        candidates
+    }
+
+    /// Scan once and connect or switch networks if the rules say so.
+    ///
+    /// # Returns
+    ///
+    /// The event, if a connection was attempted.
+    pub fn check(&mut self, wifi: &impl WifiBackend) -> Option<PolicyEvent> {
+        let now = Instant::now();
+        let retry_after = self.rules.retry_after;
+        self.failed.retain(|(_, at)| now.duration_since(*at) < retry_after);
+
+        let visible = wifi.scan();
+        let best = self
+            .rank(&visible)
+            .into_iter()
+            .find(|candidate| !self.failed.iter().any(|(ssid, _)| *ssid == candidate.ssid))?;
+
+        let link = wifi.link_info();
+        if let Some(link) = &link {
+            if link.ssid == best.ssid {
+                return None;
+            }
+            let current = self
+                .networks
+                .iter()
+                .find(|known| known.ssid == link.ssid)
+                .filter(|_| !self.blocked(&link.ssid) && !self.blocked(&link.bssid))
+                .map(|known| Candidate {
+                    ssid: link.ssid.clone(),
+                    bssid: link.bssid.clone(),
+                    frequency: link.frequency,
+                    signal_dbm: link.signal_strength,
+                    metered: known.metered,
+                    score: self.score(known, link.frequency, link.signal_strength),
+                });
+            // Unknown and blocked networks are left as soon as possible
+            if let Some(current) = current {
+                if self.last_switch.is_some_and(|last| now.duration_since(last) < self.rules.cooldown) {
+                    return None;
+                }
+                let metered_upgrade = self.rules.avoid_metered && current.metered && !best.metered;
+                let clearly_better = self.better(&best, &current)
+                    && best.score >= current.score + self.rules.hysteresis_db;
+                if !metered_upgrade && !clearly_better {
+                    return None;
+                }
+            }
+        }
+
+        let target = self.networks.iter().find(|known| known.ssid == best.ssid)?;
+        match wifi.connect_with(&target.ssid, &target.options) {
+            Ok(()) => match link {
+                Some(link) => {
+                    self.last_switch = Some(now);
+                    wifi.record_event(EventKind::NetworkSwitched {
+                        from: Some(link.ssid.clone()),
+                        to: best.ssid.clone(),
+                    });
+                    Some(PolicyEvent::Switched { from: link.ssid, to: best.ssid, signal_dbm: best.signal_dbm })
+                }
+                None => Some(PolicyEvent::Connected { ssid: best.ssid, signal_dbm: best.signal_dbm }),
+            },
+            Err(error) => {
+                self.failed.push((best.ssid.clone(), now));
+                Some(PolicyEvent::ConnectFailed { ssid: best.ssid, error })
+            }
+        }
+    }
+
+    /// Check every `interval` on a background thread, which also reconnects
+    /// within seconds when the link drops. Events are delivered to
+    /// [`AutoConnect::subscribe`] receivers.
+    ///
+    /// Calls go through `backend`, which other threads may keep using; a
+    /// [`WiFi`](crate::WiFi) serializes concurrent calls.
+    pub fn spawn<B>(self, backend: Arc<B>, interval: Duration) -> AutoConnect
+    where
+        B: WifiBackend + Send + Sync + 'static,
+    {
+        AutoConnect::start(self, backend, interval)
+    }
+
+    /// Check every `interval` until `stop` is set, passing events to `on_event`.
+    pub fn run(&mut self, wifi: &impl WifiBackend, interval: Duration, stop: &AtomicBool, mut on_event: impl FnMut(&PolicyEvent)) {
+        while !stop.load(Ordering::Relaxed) {
+            if let Some(event) = self.check(wifi) {
+                on_event(&event);
+            }
+
+            let started = Instant::now();
+            while !stop.load(Ordering::Relaxed) && started.elapsed() < interval {
+                std::thread::sleep((interval - started.elapsed()).min(Duration::from_millis(250)));
+            }
+        }
+    }
+}
@@ -0,0 +1,36 @@
+//! Saved network profiles.
+
+use crate::diag;
+use crate::SecurityType;
+
+#[repr(C)]
+pub(crate) struct RawSavedProfile {
+    name: [libc::c_char; 256],
+    ssid: [libc::c_char; 33],
+    security_type: i32,
+    auto_connect: i32,
+}
+
+/// A network stored in the platform's profile store, from
+/// [`WiFi::list_profiles`](crate::WiFi::list_profiles).
+#[derive(Debug, Clone, PartialEq)]
+pub struct SavedProfile {
+    /// Name to pass to [`WiFi::connect_profile`](crate::WiFi::connect_profile),
+    /// often but not necessarily the SSID.
+    pub name: String,
+    pub ssid: String,
+    pub security_type: SecurityType,
+    /// Whether the platform joins the network automatically when in range.
+    pub auto_connect: bool,
+}
+
+impl SavedProfile {
+    pub(crate) fn from_raw(raw: &RawSavedProfile) -> Self {
+        SavedProfile {
+            name: diag::text(&raw.name),
+            ssid: diag::text(&raw.ssid),
+            security_type: SecurityType::from_raw(raw.security_type),
+            auto_connect: raw.auto_connect != 0,
+        }
+    }
+}
@@ -0,0 +1,302 @@
+//! Captive portal provisioning for devices without a screen.
+//!
+//! A [`ProvisioningPortal`] opens a hotspot whose DNS answers every name
+//! with the device's own address and serves a small web page on it. Phones
+//! that join the hotspot show the page as a login screen; the user picks
+//! the home network, enters its password, and the credentials are handed
+//! back to the caller, which typically stops there and connects.
+//!
+//! ```no_run
+//! use std::time::Duration;
+//! use wifi_rs::{HotspotConfig, ProvisioningPortal, WiFi};
+//!
+//! let wifi = WiFi::new();
+//! let portal = ProvisioningPortal::new(HotspotConfig::new("Sensor-Setup"));
+//! let credentials = portal.run(&wifi, Duration::from_secs(600))?;
+//! wifi.connect_with(&credentials.ssid, &credentials.connect_options())?;
+//! # Ok::<(), wifi_rs::WifiError>(())
+//! ```
+
+use std::io::{self, Read, Write};
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+use std::time::{Duration, Instant};
+
+use crate::{HotspotConfig, NetworkCredentials, WifiBackend, WifiError};
+
+/// Address of the hotspot when its DHCP settings are left to the platform.
+const DEFAULT_GATEWAY: Ipv4Addr = Ipv4Addr::new(192, 168, 4, 1);
+
+/// Largest request the portal reads.
+const MAX_REQUEST: usize = 16 * 1024;
+
+/// Hotspot with a captive portal that asks for network credentials.
+///
+/// The page posts the fields `ssid`, `password` and optionally `hidden`
+/// to any path; [`page`](Self::page) replaces the built-in one with a
+/// branded page that does the same.
+#[derive(Debug, Clone)]
+pub struct ProvisioningPortal {
+    hotspot: HotspotConfig,
+    page: Option<String>,
+}
+
+impl ProvisioningPortal {
+    /// A portal on the hotspot `hotspot`, which is switched to
+    /// [`captive_portal`](HotspotConfig::captive_portal) mode.
+    pub fn new(hotspot: HotspotConfig) -> Self {
+        ProvisioningPortal { hotspot: hotspot.captive_portal(), page: None }
+    }
+
+    /// Serve `html` instead of the built-in page.
+    pub fn page(mut self, html: &str) -> Self {
+        self.page = Some(html.to_string());
+        self
+    }
+
+    /// Scan for nearby networks to suggest, start the hotspot, and serve
+    /// the portal until a client submits credentials. The hotspot is
+    /// stopped before returning.
+    ///
+    /// The portal listens on port 80 of every address, which needs the
+    /// same privileges as the hotspot itself.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WifiError::Timeout`] if nothing was submitted within
+    /// `timeout`, [`WifiError::PermissionDenied`] if port 80 cannot be
+    /// opened, [`WifiError::OperationFailed`] if it is in use, or the error
+    /// of [`WifiBackend::create_hotspot_with`].
+    pub fn run(&self, wifi: &impl WifiBackend, timeout: Duration) -> Result<NetworkCredentials, WifiError> {
+        let listener = TcpListener::bind((Ipv4Addr::UNSPECIFIED, 80)).map_err(io_error)?;
+        listener.set_nonblocking(true).map_err(io_error)?;
+
+        // Most adapters cannot scan once they run an access point
+        let mut nearby: Vec<String> = Vec::new();
+        for network in wifi.scan() {
+            if !network.ssid.is_empty() && !network.ssid.starts_with('[') && !nearby.contains(&network.ssid) {
+                nearby.push(network.ssid);
+            }
+        }
+        let page = self.page.clone().unwrap_or_else(|| default_page(&self.hotspot.ssid, &nearby));
+
+        wifi.create_hotspot_with(&self.hotspot)?;
+        let gateway = self.hotspot.dhcp.as_ref().map_or(DEFAULT_GATEWAY, |dhcp| dhcp.gateway);
+        let submitted = serve(&listener, &page, gateway, Instant::now() + timeout);
+        let stopped = wifi.stop_hotspot();
+        let credentials = submitted.ok_or(WifiError::Timeout)?;
+        stopped?;
+        Ok(credentials)
+    }
+}
+
+fn io_error(error: io::Error) -> WifiError {
+    match error.kind() {
+        io::ErrorKind::PermissionDenied => WifiError::PermissionDenied,
+        _ => WifiError::OperationFailed,
+    }
+}
+
+/// Answer requests until credentials arrive or `deadline` passes.
+fn serve(listener: &TcpListener, page: &str, gateway: Ipv4Addr, deadline: Instant) -> Option<NetworkCredentials> {
+    while Instant::now() < deadline {
+        match listener.accept() {
+            Ok((stream, _)) => {
+                if let Some(credentials) = handle(stream, page, gateway) {
+                    return Some(credentials);
+                }
+            }
+            // Nothing pending, or a client that gave up before being accepted
+            Err(_) => std::thread::sleep(Duration::from_millis(100)),
+        }
+    }
+    None
+}
+
+/// Serve one request; a client's own connectivity check gets a redirect,
+/// which is what makes it open the portal.
+fn handle(mut stream: TcpStream, page: &str, gateway: Ipv4Addr) -> Option<NetworkCredentials> {
+    stream.set_nonblocking(false).ok()?;
+    stream.set_read_timeout(Some(Duration::from_secs(5))).ok()?;
+    let request = read_request(&mut stream)?;
+    let host = request.header("host").map(|host| host.split(':').next().unwrap_or(host));
+
+    if request.method == "POST" {
+        let credentials = parse_form(&request.body);
+        let reply = match &credentials {
+            Some(credentials) => format!(
+                "<p>Joining <b>{}</b>. You can close this page.</p>",
+                escape_html(&credentials.ssid)
+            ),
+            None => "<p>Please enter the network name.</p>".to_string(),
+        };
+        respond(&mut stream, "200 OK", &[], &wrap_page("Saved", &reply));
+        return credentials;
+    }
+    if request.path == "/" && host.is_none_or(|host| host == gateway.to_string()) {
+        respond(&mut stream, "200 OK", &[], page);
+    } else {
+        let location = format!("Location: http://{}/", gateway);
+        respond(&mut stream, "302 Found", &[&location], "");
+    }
+    None
+}
+
+struct Request {
+    method: String,
+    path: String,
+    headers: Vec<(String, String)>,
+    body: String,
+}
+
+impl Request {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+fn read_request(stream: &mut TcpStream) -> Option<Request> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 2048];
+    let header_end = loop {
+        if let Some(end) = buffer.windows(4).position(|window| window == b"\r\n\r\n") {
+            break end;
+        }
+        let read = stream.read(&mut chunk).ok().filter(|read| *read > 0)?;
+        buffer.extend_from_slice(&chunk[..read]);
+        if buffer.len() > MAX_REQUEST {
+            return None;
+        }
+    };
+
+    let head = String::from_utf8_lossy(&buffer[..header_end]).into_owned();
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next()?.split(' ');
+    let method = request_line.next()?.to_string();
+    let path = request_line.next()?.split('?').next()?.to_string();
+    let headers: Vec<(String, String)> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+        .collect();
+    let mut request = Request { method, path, headers, body: String::new() };
+
+    let length: usize = request.header("content-length").and_then(|length| length.parse().ok()).unwrap_or(0);
+    if length > MAX_REQUEST {
+        return None;
+    }
+    let mut body = buffer.split_off(header_end + 4);
+    while body.len() < length {
+        let read = stream.read(&mut chunk).ok().filter(|read| *read > 0)?;
+        body.extend_from_slice(&chunk[..read]);
+    }
+    body.truncate(length);
+    request.body = String::from_utf8_lossy(&body).into_owned();
+    Some(request)
+}
+
+fn respond(stream: &mut TcpStream, status: &str, headers: &[&str], body: &str) {
+    let mut response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n",
+        status,
+        body.len()
+    );
+    for header in headers {
+        response.push_str(header);
+        response.push_str("\r\n");
+    }
+    response.push_str("\r\n");
+    response.push_str(body);
+    let _ = stream.write_all(response.as_bytes());
+}
+
+/// Credentials from an `application/x-www-form-urlencoded` body, `None`
+/// without an SSID.
+fn parse_form(body: &str) -> Option<NetworkCredentials> {
+    let mut ssid = None;
+    let mut password = None;
+    let mut hidden = false;
+    for field in body.split('&') {
+        let (key, value) = field.split_once('=').unwrap_or((field, ""));
+        let value = decode_form_value(value);
+        match key {
+            "ssid" => ssid = Some(value),
+            "password" => password = Some(value).filter(|password| !password.is_empty()),
+            "hidden" => hidden = !value.is_empty(),
+            _ => {}
+        }
+    }
+    let ssid = ssid.filter(|ssid| !ssid.is_empty() && ssid.len() <= 32)?;
+    let mut credentials = NetworkCredentials::new(&ssid, password.as_deref());
+    credentials.hidden = hidden;
+    Some(credentials)
+}
+
+fn decode_form_value(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            b'%' => match (hex_digit(bytes.get(i + 1)), hex_digit(bytes.get(i + 2))) {
+                (Some(high), Some(low)) => {
+                    decoded.push(high << 4 | low);
+                    i += 2;
+                }
+                _ => decoded.push(b'%'),
+            },
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn hex_digit(byte: Option<&u8>) -> Option<u8> {
+    (*byte? as char).to_digit(16).map(|digit| digit as u8)
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn wrap_page(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html><html><head><meta charset=\"utf-8\">\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\
+         <title>{}</title></head><body style=\"font-family:sans-serif;max-width:24em;margin:2em auto\">{}</body></html>",
+        escape_html(title),
+        body
+    )
+}
+
+fn default_page(title: &str, nearby: &[String]) -> String {
+    let options: String = nearby
+        .iter()
+        .map(|ssid| format!("<option value=\"{}\">", escape_html(ssid)))
+        .collect();
+    let form = format!(
+        "<h2>{}</h2><form method=\"post\" action=\"/\">\
+         <p><label>Network<br><input name=\"ssid\" list=\"nearby\" required maxlength=\"32\"></label></p>\
+         <datalist id=\"nearby\">{}</datalist>\
+         <p><label>Password<br><input name=\"password\" type=\"password\"></label></p>\
+         <p><label><input name=\"hidden\" type=\"checkbox\"> Hidden network</label></p>\
+         <p><button type=\"submit\">Connect</button></p></form>",
+        escape_html(title),
+        options
+    );
+    wrap_page(title, &form)
+}
@@ -0,0 +1,611 @@
+//! Recording and deterministic replay of backend sessions.
+//!
+//! Wrap the backend of a field deployment in a [`SessionRecorder`] to write
+//! every call and its result to a file. On a developer machine, a
+//! [`SessionReplay`] of that file answers the same calls with the same
+//! results, so a reported bug can be reproduced under a debugger without
+//! the original hardware or radio environment.
+//!
+//! ```no_run
+//! use wifi_rs::{SessionRecorder, SessionReplay, WiFi, WifiBackend};
+//!
+//! // In the field
+//! let wifi = SessionRecorder::create(WiFi::new(), "/var/log/wifi-session.log")?;
+//! let networks = wifi.scan();
+//!
+//! // On the developer machine, running the same application code
+//! let wifi = SessionReplay::open("wifi-session.log")?;
+//! assert_eq!(wifi.scan().len(), networks.len());
+//! # Ok::<(), std::io::Error>(())
+//! ```
+//!
+//! The file is line-based text, one call per line, with tab-separated
+//! fields. Credentials passed to connect are never written.
+
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard};
+use std::time::SystemTime;
+
+use crate::channel::frequency_to_channel;
+use crate::events::EventLog;
+use crate::roam::LinkSample;
+use crate::{
+    ConnectOptions, ConnectionStatus, DisconnectReason, EventKind, FeatureSupport, HotspotConfig, LinkInfo,
+    MloLink, NetworkFailure, NetworkInfo, PhyMode, PmfMode, RoamingFeatures, SecurityType, WifiBackend, WifiError,
+    WifiEvent,
+};
+
+const HEADER: &str = "# wifi-rs session v1";
+
+/// One recorded call and its result.
+#[derive(Debug, Clone)]
+enum Entry {
+    Scan(Vec<NetworkInfo>),
+    Connect { ssid: String, outcome: Result<(), WifiError> },
+    Disconnect(Result<(), WifiError>),
+    Status(ConnectionStatus),
+    Link(Option<LinkInfo>),
+}
+
+impl Entry {
+    fn name(&self) -> &'static str {
+        match self {
+            Entry::Scan(_) => "scan",
+            Entry::Connect { .. } => "connect",
+            Entry::Disconnect(_) => "disconnect",
+            Entry::Status(_) => "status",
+            Entry::Link(_) => "link",
+        }
+    }
+}
+
+fn escape(field: &str) -> String {
+    let mut escaped = String::with_capacity(field.len());
+    for c in field.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn unescape(field: &str) -> String {
+    let mut unescaped = String::with_capacity(field.len());
+    let mut chars = field.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => unescaped.push('\t'),
+            Some('n') => unescaped.push('\n'),
+            Some('r') => unescaped.push('\r'),
+            Some(other) => unescaped.push(other),
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
+
+fn line(fields: &[String]) -> String {
+    let mut line = fields.iter().map(|f| escape(f)).collect::<Vec<_>>().join("\t");
+    line.push('\n');
+    line
+}
+
+fn flag(value: bool) -> String {
+    if value { "1" } else { "0" }.to_string()
+}
+
+fn security_name(security: SecurityType) -> &'static str {
+    match security {
+        SecurityType::None => "none",
+        SecurityType::Wep => "wep",
+        SecurityType::Wpa => "wpa",
+        SecurityType::Wpa2 => "wpa2",
+        SecurityType::Wpa3 => "wpa3",
+        SecurityType::Unknown => "unknown",
+    }
+}
+
+fn pmf_name(pmf: PmfMode) -> &'static str {
+    match pmf {
+        PmfMode::Unknown => "unknown",
+        PmfMode::Disabled => "disabled",
+        PmfMode::Capable => "capable",
+        PmfMode::Required => "required",
+    }
+}
+
+fn outcome_name(outcome: &Result<(), WifiError>) -> &'static str {
+    match outcome {
+        Ok(()) => "ok",
+        Err(WifiError::Unsupported) => "unsupported",
+        Err(WifiError::OperationFailed) => "failed",
+        Err(WifiError::NetworkUnusable(NetworkFailure::DhcpTimeout)) => "unusable-dhcp",
+        Err(WifiError::NetworkUnusable(NetworkFailure::GatewayUnreachable)) => "unusable-gateway",
+        Err(WifiError::NetworkUnusable(NetworkFailure::DnsUnresolvable)) => "unusable-dns",
+        Err(WifiError::ProfileNotFound) => "no-profile",
+        Err(WifiError::InvalidCredentials) => "bad-credentials",
+        Err(WifiError::AdapterNotFound) => "no-adapter",
+        Err(WifiError::PermissionDenied) => "permission-denied",
+        Err(WifiError::Timeout) => "timeout",
+        Err(WifiError::DriverError) => "driver-error",
+        Err(WifiError::InvalidArgument) => "invalid-argument",
+        Err(WifiError::Cancelled) => "cancelled",
+    }
+}
+
+fn status_name(status: ConnectionStatus) -> &'static str {
+    match status {
+        ConnectionStatus::Connected => "connected",
+        ConnectionStatus::Disconnected => "disconnected",
+        ConnectionStatus::Connecting => "connecting",
+        ConnectionStatus::Error => "error",
+    }
+}
+
+fn encode(entry: &Entry) -> String {
+    let name = entry.name().to_string();
+    match entry {
+        Entry::Scan(networks) => {
+            let mut out = line(&[name, networks.len().to_string()]);
+            for n in networks {
+                out.push_str(&line(&[
+                    "net".to_string(),
+                    n.ssid.clone(),
+                    n.bssid.clone(),
+                    n.signal_strength.to_string(),
+                    security_name(n.security_type).to_string(),
+                    n.frequency.to_string(),
+                    pmf_name(n.pmf).to_string(),
+                    flag(n.wps_enabled),
+                    flag(n.wifi7),
+                    flag(n.mlo),
+                ]));
+            }
+            out
+        }
+        Entry::Connect { ssid, outcome } => line(&[name, ssid.clone(), outcome_name(outcome).to_string()]),
+        Entry::Disconnect(outcome) => line(&[name, outcome_name(outcome).to_string()]),
+        Entry::Status(status) => line(&[name, status_name(*status).to_string()]),
+        Entry::Link(None) => line(&[name, "none".to_string()]),
+        Entry::Link(Some(link)) => {
+            let roaming = link.roaming;
+            let mut out = line(&[
+                name,
+                "up".to_string(),
+                link.ssid.clone(),
+                link.bssid.clone(),
+                link.frequency.to_string(),
+                link.signal_strength.to_string(),
+                flag(link.fast_transition),
+                link.ap_mld_address.clone().unwrap_or_default(),
+                [roaming.neighbor_report, roaming.bss_transition, roaming.fast_transition]
+                    .iter()
+                    .map(|f| format!("{}{}", flag(f.ap), flag(f.local)))
+                    .collect(),
+                link.links.len().to_string(),
+            ]);
+            for l in &link.links {
+                out.push_str(&line(&[
+                    "mlo".to_string(),
+                    l.link_id.to_string(),
+                    l.bssid.clone(),
+                    l.frequency.to_string(),
+                    l.signal_strength.map_or_else(String::new, |dbm| dbm.to_string()),
+                ]));
+            }
+            out
+        }
+    }
+}
+
+/// Reads entries back from the lines of a session file.
+struct Parser<R> {
+    lines: io::Lines<R>,
+    number: usize,
+}
+
+fn invalid(number: usize, message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("session line {}: {}", number, message))
+}
+
+impl<R: BufRead> Parser<R> {
+    fn next_fields(&mut self) -> io::Result<Option<Vec<String>>> {
+        loop {
+            let text = match self.lines.next() {
+                Some(text) => text?,
+                None => return Ok(None),
+            };
+            self.number += 1;
+            if text.is_empty() || text.starts_with('#') {
+                continue;
+            }
+            return Ok(Some(text.split('\t').map(unescape).collect()));
+        }
+    }
+
+    fn expect(&mut self, kind: &str, count: usize) -> io::Result<Vec<String>> {
+        match self.next_fields()? {
+            Some(fields) if fields[0] == kind && fields.len() == count => Ok(fields),
+            Some(_) => Err(self.error(&format!("expected a {} line", kind))),
+            None => Err(self.error(&format!("missing {} line", kind))),
+        }
+    }
+
+    fn error(&self, message: &str) -> io::Error {
+        invalid(self.number, message)
+    }
+
+    fn number<T: std::str::FromStr>(&self, field: &str) -> io::Result<T> {
+        field.parse().map_err(|_| self.error(&format!("invalid number {:?}", field)))
+    }
+
+    fn flag(&self, field: &str) -> io::Result<bool> {
+        match field {
+            "0" => Ok(false),
+            "1" => Ok(true),
+            _ => Err(self.error(&format!("invalid flag {:?}", field))),
+        }
+    }
+
+    fn outcome(&self, field: &str) -> io::Result<Result<(), WifiError>> {
+        Ok(match field {
+            "ok" => Ok(()),
+            "unsupported" => Err(WifiError::Unsupported),
+            "failed" => Err(WifiError::OperationFailed),
+            "unusable-dhcp" => Err(WifiError::NetworkUnusable(NetworkFailure::DhcpTimeout)),
+            "unusable-gateway" => Err(WifiError::NetworkUnusable(NetworkFailure::GatewayUnreachable)),
+            "unusable-dns" => Err(WifiError::NetworkUnusable(NetworkFailure::DnsUnresolvable)),
+            "no-profile" => Err(WifiError::ProfileNotFound),
+            "bad-credentials" => Err(WifiError::InvalidCredentials),
+            "no-adapter" => Err(WifiError::AdapterNotFound),
+            "permission-denied" => Err(WifiError::PermissionDenied),
+            "timeout" => Err(WifiError::Timeout),
+            "driver-error" => Err(WifiError::DriverError),
+            "invalid-argument" => Err(WifiError::InvalidArgument),
+            "cancelled" => Err(WifiError::Cancelled),
+            other => return Err(self.error(&format!("unknown outcome {:?}", other))),
+        })
+    }
+
+    fn entry(&mut self) -> io::Result<Option<Entry>> {
+        let fields = match self.next_fields()? {
+            Some(fields) => fields,
+            None => return Ok(None),
+        };
+        let entry = match (fields[0].as_str(), fields.len()) {
+            ("scan", 2) => {
+                let count: usize = self.number(&fields[1])?;
+                let mut networks = Vec::with_capacity(count);
+                for _ in 0..count {
+                    networks.push(self.network()?);
+                }
+                Entry::Scan(networks)
+            }
+            ("connect", 3) => Entry::Connect { ssid: fields[1].clone(), outcome: self.outcome(&fields[2])? },
+            // Older recordings store the disconnect result as a flag
+            ("disconnect", 2) => Entry::Disconnect(match fields[1].as_str() {
+                "1" => Ok(()),
+                "0" => Err(WifiError::OperationFailed),
+                other => self.outcome(other)?,
+            }),
+            ("status", 2) => Entry::Status(match fields[1].as_str() {
+                "connected" => ConnectionStatus::Connected,
+                "disconnected" => ConnectionStatus::Disconnected,
+                "connecting" => ConnectionStatus::Connecting,
+                "error" => ConnectionStatus::Error,
+                other => return Err(self.error(&format!("unknown status {:?}", other))),
+            }),
+            ("link", 2) if fields[1] == "none" => Entry::Link(None),
+            ("link", 10) if fields[1] == "up" => Entry::Link(Some(self.link(&fields)?)),
+            (other, _) => return Err(self.error(&format!("unexpected {:?} line", other))),
+        };
+        Ok(Some(entry))
+    }
+
+    fn network(&mut self) -> io::Result<NetworkInfo> {
+        let f = self.expect("net", 10)?;
+        let frequency = self.number(&f[5])?;
+        Ok(NetworkInfo {
+            ssid: f[1].clone(),
+            bssid: f[2].clone(),
+            signal_strength: self.number(&f[3])?,
+            security_type: match f[4].as_str() {
+                "none" => SecurityType::None,
+                "wep" => SecurityType::Wep,
+                "wpa" => SecurityType::Wpa,
+                "wpa2" => SecurityType::Wpa2,
+                "wpa3" => SecurityType::Wpa3,
+                _ => SecurityType::Unknown,
+            },
+            channel: frequency_to_channel(frequency),
+            frequency,
+            pmf: match f[6].as_str() {
+                "disabled" => PmfMode::Disabled,
+                "capable" => PmfMode::Capable,
+                "required" => PmfMode::Required,
+                _ => PmfMode::Unknown,
+            },
+            wps_enabled: self.flag(&f[7])?,
+            wifi7: self.flag(&f[8])?,
+            mlo: self.flag(&f[9])?,
+            information_elements: Vec::new(),
+            phy_mode: PhyMode::Unknown,
+            max_rate_mbps: 0,
+        }
+        .with_phy())
+    }
+
+    fn link(&mut self, f: &[String]) -> io::Result<LinkInfo> {
+        let roaming = f[8].as_bytes();
+        if roaming.len() != 6 || !roaming.iter().all(|b| *b == b'0' || *b == b'1') {
+            return Err(self.error("invalid roaming features"));
+        }
+        let support = |i: usize| FeatureSupport { ap: roaming[i] == b'1', local: roaming[i + 1] == b'1' };
+        let roaming = RoamingFeatures {
+            neighbor_report: support(0),
+            bss_transition: support(2),
+            fast_transition: support(4),
+        };
+        let sample = LinkSample {
+            ssid: f[2].clone(),
+            bssid: f[3].clone(),
+            frequency: self.number(&f[4])?,
+            signal_strength: self.number(&f[5])?,
+            fast_transition: self.flag(&f[6])?,
+            ap_mld_address: f[7].clone(),
+            noise: 0,
+            link_speed_mbps: 0,
+            taken: SystemTime::now(),
+        };
+        let count: usize = self.number(&f[9])?;
+        let mut links = Vec::with_capacity(count);
+        for _ in 0..count {
+            let m = self.expect("mlo", 5)?;
+            let frequency = self.number(&m[3])?;
+            links.push(MloLink {
+                link_id: self.number(&m[1])?,
+                bssid: m[2].clone(),
+                frequency,
+                channel: frequency_to_channel(frequency),
+                signal_strength: if m[4].is_empty() { None } else { Some(self.number(&m[4])?) },
+            });
+        }
+        Ok(LinkInfo::new(sample, roaming, links))
+    }
+}
+
+/// Records every call on a backend and its result to a session file.
+///
+/// Each call is flushed before returning, so the file is complete up to the
+/// last call even if the process crashes. Writing is best effort: a failed
+/// write never changes the result seen by the caller, and is reported by
+/// [`finish`](Self::finish).
+pub struct SessionRecorder<B: WifiBackend> {
+    inner: B,
+    out: Mutex<Output>,
+}
+
+struct Output {
+    writer: BufWriter<File>,
+    error: Option<io::Error>,
+}
+
+impl<B: WifiBackend> SessionRecorder<B> {
+    /// Record the calls on `inner` to a new file at `path`, replacing any
+    /// existing file.
+    pub fn create(inner: B, path: impl AsRef<Path>) -> io::Result<Self> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        writeln!(writer, "{}", HEADER)?;
+        writer.flush()?;
+        Ok(SessionRecorder { inner, out: Mutex::new(Output { writer, error: None }) })
+    }
+
+    /// The wrapped backend.
+    pub fn inner(&self) -> &B {
+        &self.inner
+    }
+
+    /// Stop recording and return the wrapped backend.
+    ///
+    /// # Errors
+    ///
+    /// The first error that occurred while writing the session file.
+    pub fn finish(self) -> io::Result<B> {
+        let out = self.out.into_inner().unwrap_or_else(|poisoned| poisoned.into_inner());
+        match out.error {
+            Some(error) => Err(error),
+            None => Ok(self.inner),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Output> {
+        self.out.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn write(&self, entry: &Entry) {
+        let mut out = self.lock();
+        if out.error.is_some() {
+            return;
+        }
+        let text = encode(entry);
+        if let Err(error) = out.writer.write_all(text.as_bytes()).and_then(|_| out.writer.flush()) {
+            out.error = Some(error);
+        }
+    }
+}
+
+impl<B: WifiBackend> WifiBackend for SessionRecorder<B> {
+    fn scan(&self) -> Vec<NetworkInfo> {
+        let networks = self.inner.scan();
+        self.write(&Entry::Scan(networks.clone()));
+        networks
+    }
+
+    fn connect_with(&self, ssid: &str, options: &ConnectOptions) -> Result<(), WifiError> {
+        let outcome = self.inner.connect_with(ssid, options);
+        self.write(&Entry::Connect { ssid: ssid.to_string(), outcome });
+        outcome
+    }
+
+    fn disconnect(&self) -> Result<(), WifiError> {
+        let outcome = self.inner.disconnect();
+        self.write(&Entry::Disconnect(outcome));
+        outcome
+    }
+
+    fn get_status(&self) -> ConnectionStatus {
+        let status = self.inner.get_status();
+        self.write(&Entry::Status(status));
+        status
+    }
+
+    fn link_info(&self) -> Option<LinkInfo> {
+        let link = self.inner.link_info();
+        self.write(&Entry::Link(link.clone()));
+        link
+    }
+
+    fn event_log(&self) -> Vec<WifiEvent> {
+        self.inner.event_log()
+    }
+
+    fn record_event(&self, kind: EventKind) {
+        self.inner.record_event(kind);
+    }
+
+    // Hotspot calls pass through unrecorded; replays report them unsupported
+    fn create_hotspot_with(&self, config: &HotspotConfig) -> Result<(), WifiError> {
+        self.inner.create_hotspot_with(config)
+    }
+
+    fn stop_hotspot(&self) -> Result<(), WifiError> {
+        self.inner.stop_hotspot()
+    }
+}
+
+/// Answers backend calls from a session file written by a
+/// [`SessionRecorder`].
+///
+/// Calls must come in the recorded order. The event log is rebuilt from the
+/// replayed results, so timestamps differ from the original session.
+///
+/// # Panics
+///
+/// Every [`WifiBackend`] method panics when the call does not match the
+/// next recorded one, or when the session is exhausted, pointing at where
+/// the code under test diverged from the recording.
+pub struct SessionReplay {
+    entries: Mutex<VecDeque<Entry>>,
+    events: EventLog,
+}
+
+impl SessionReplay {
+    /// Load the session file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// An I/O error, or [`io::ErrorKind::InvalidData`] naming the offending
+    /// line if the file is not a session file.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let mut reader = BufReader::new(File::open(path)?);
+        let mut header = String::new();
+        reader.read_line(&mut header)?;
+        if header.trim_end() != HEADER {
+            return Err(invalid(1, "not a wifi-rs session file"));
+        }
+
+        let mut parser = Parser { lines: reader.lines(), number: 1 };
+        let mut entries = VecDeque::new();
+        while let Some(entry) = parser.entry()? {
+            entries.push_back(entry);
+        }
+        Ok(SessionReplay { entries: Mutex::new(entries), events: EventLog::new() })
+    }
+
+    /// Number of recorded calls not replayed yet.
+    pub fn remaining(&self) -> usize {
+        self.lock().len()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, VecDeque<Entry>> {
+        self.entries.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn next(&self, call: &str) -> Entry {
+        let entry = self.lock().pop_front();
+        match entry {
+            Some(entry) if entry.name() == call => entry,
+            Some(entry) => panic!("session replay diverged: called {} but the recording has {}", call, entry.name()),
+            None => panic!("session replay exhausted: called {} after the last recorded call", call),
+        }
+    }
+}
+
+impl WifiBackend for SessionReplay {
+    fn scan(&self) -> Vec<NetworkInfo> {
+        match self.next("scan") {
+            Entry::Scan(networks) => networks,
+            _ => unreachable!(),
+        }
+    }
+
+    fn connect_with(&self, ssid: &str, _options: &ConnectOptions) -> Result<(), WifiError> {
+        match self.next("connect") {
+            Entry::Connect { ssid: recorded, outcome } => {
+                assert_eq!(ssid, recorded, "session replay diverged: connect to a different network");
+                self.events.record_connect(ssid, &outcome);
+                outcome
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    fn disconnect(&self) -> Result<(), WifiError> {
+        match self.next("disconnect") {
+            Entry::Disconnect(outcome) => {
+                if outcome.is_ok() {
+                    self.events.set_status(ConnectionStatus::Disconnected);
+                    self.events.record(EventKind::Disconnected { reason: DisconnectReason::Requested });
+                }
+                outcome
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    fn get_status(&self) -> ConnectionStatus {
+        match self.next("status") {
+            Entry::Status(status) => {
+                self.events.observe_status(status);
+                status
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    fn link_info(&self) -> Option<LinkInfo> {
+        match self.next("link") {
+            Entry::Link(link) => link,
+            _ => unreachable!(),
+        }
+    }
+
+    fn event_log(&self) -> Vec<WifiEvent> {
+        self.events.snapshot()
+    }
+
+    fn record_event(&self, kind: EventKind) {
+        self.events.record(kind);
+    }
+}
@@ -0,0 +1,205 @@
+//! History of BSSID transitions within a network.
+//!
+//! Roams are detected by comparing consecutive samples of the current
+//! association, so the measured duration is bounded by how often the
+//! connection is sampled.
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+use crate::diag::text;
+
+#[repr(C)]
+pub(crate) struct RawRoamingPolicy {
+    rssi_threshold: i32,
+    scan_interval_seconds: i32,
+}
+
+/// When the station scans for a stronger access point of the network it is
+/// connected to, set with
+/// [`WiFi::set_roaming_policy`](crate::WiFi::set_roaming_policy).
+///
+/// While the signal is below the threshold the station scans every scan
+/// interval and moves to a stronger access point of the same SSID; a higher
+/// threshold roams more eagerly. The default is -70 dBm every 30 seconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RoamingPolicy {
+    pub(crate) threshold_dbm: i32,
+    pub(crate) scan_interval: Duration,
+}
+
+impl Default for RoamingPolicy {
+    fn default() -> Self {
+        RoamingPolicy {
+            threshold_dbm: -70,
+            scan_interval: Duration::from_secs(30),
+        }
+    }
+}
+
+impl RoamingPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Look for a stronger access point while the signal is below `dbm`,
+    /// between -100 and 0.
+    pub fn threshold(mut self, dbm: i32) -> Self {
+        self.threshold_dbm = dbm;
+        self
+    }
+
+    /// How often to scan while below the threshold, at least one second.
+    pub fn scan_interval(mut self, interval: Duration) -> Self {
+        self.scan_interval = interval;
+        self
+    }
+
+    /// `None` if a setting is out of range.
+    pub(crate) fn raw(&self) -> Option<RawRoamingPolicy> {
+        let seconds = i32::try_from(self.scan_interval.as_secs()).ok().filter(|&seconds| seconds > 0)?;
+        (-100..=0).contains(&self.threshold_dbm).then_some(RawRoamingPolicy {
+            rssi_threshold: self.threshold_dbm,
+            scan_interval_seconds: seconds,
+        })
+    }
+}
+
+/// Number of roams kept by [`WiFi::roam_history`](crate::WiFi::roam_history).
+pub const ROAM_HISTORY_CAPACITY: usize = 32;
+
+#[repr(C)]
+pub(crate) struct RawConnectionInfo {
+    ssid: [libc::c_char; 33],
+    bssid: [libc::c_char; 18],
+    frequency: i32,
+    signal_strength: i32,
+    fast_transition: i32,
+    ap_mld_address: [libc::c_char; 18],
+    noise: i32,
+    link_speed_mbps: i32,
+}
+
+impl RawConnectionInfo {
+    pub(crate) fn zeroed() -> Self {
+        // SAFETY: the struct is plain integers and character arrays.
+        unsafe { std::mem::zeroed() }
+    }
+}
+
+/// One sample of the current association.
+#[derive(Debug, Clone)]
+pub(crate) struct LinkSample {
+    pub ssid: String,
+    pub bssid: String,
+    pub frequency: i32,
+    pub signal_strength: i32,
+    pub fast_transition: bool,
+    pub ap_mld_address: String,
+    pub noise: i32,
+    pub link_speed_mbps: i32,
+    pub taken: SystemTime,
+}
+
+impl LinkSample {
+    pub(crate) fn from_raw(raw: &RawConnectionInfo) -> Self {
+        LinkSample {
+            ssid: text(&raw.ssid),
+            bssid: text(&raw.bssid),
+            frequency: raw.frequency,
+            signal_strength: raw.signal_strength,
+            fast_transition: raw.fast_transition != 0,
+            ap_mld_address: text(&raw.ap_mld_address),
+            noise: raw.noise,
+            link_speed_mbps: raw.link_speed_mbps,
+            taken: SystemTime::now(),
+        }
+    }
+}
+
+/// A move from one access point to another of the same network.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Roam {
+    pub ssid: String,
+    pub from_bssid: String,
+    pub to_bssid: String,
+    /// Frequencies in MHz before and after the roam.
+    pub from_frequency: i32,
+    pub to_frequency: i32,
+    /// Signal strength in dBm of the last sample on the old access point.
+    pub rssi_before: i32,
+    /// Signal strength in dBm of the first sample on the new access point.
+    pub rssi_after: i32,
+    /// Whether 802.11r fast transition was negotiated.
+    pub fast_transition: bool,
+    /// Time of the last sample on the old access point.
+    pub left_at: SystemTime,
+    /// Time of the first sample on the new access point.
+    pub joined_at: SystemTime,
+}
+
+impl Roam {
+    /// Upper bound on how long the roam took, the gap between the samples
+    /// either side of it.
+    pub fn duration(&self) -> Duration {
+        self.joined_at.duration_since(self.left_at).unwrap_or_default()
+    }
+}
+
+struct Inner {
+    last: Option<LinkSample>,
+    roams: VecDeque<Roam>,
+}
+
+/// Compares association samples and remembers the roams between them.
+pub(crate) struct RoamTracker {
+    inner: Mutex<Inner>,
+}
+
+impl RoamTracker {
+    pub(crate) fn new() -> Self {
+        RoamTracker {
+            inner: Mutex::new(Inner { last: None, roams: VecDeque::new() }),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Inner> {
+        self.inner.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Record a sample, `None` when not associated.
+    ///
+    /// Returns the roam if the BSSID changed within the same network.
+    pub(crate) fn observe(&self, sample: Option<LinkSample>) -> Option<Roam> {
+        let mut inner = self.lock();
+        let previous = std::mem::replace(&mut inner.last, sample.clone());
+        let (previous, current) = (previous?, sample?);
+        if previous.ssid != current.ssid || previous.bssid.eq_ignore_ascii_case(&current.bssid) {
+            return None;
+        }
+
+        let roam = Roam {
+            ssid: current.ssid,
+            from_bssid: previous.bssid,
+            to_bssid: current.bssid,
+            from_frequency: previous.frequency,
+            to_frequency: current.frequency,
+            rssi_before: previous.signal_strength,
+            rssi_after: current.signal_strength,
+            fast_transition: current.fast_transition,
+            left_at: previous.taken,
+            joined_at: current.taken,
+        };
+        if inner.roams.len() >= ROAM_HISTORY_CAPACITY {
+            inner.roams.pop_front();
+        }
+        inner.roams.push_back(roam.clone());
+        Some(roam)
+    }
+
+    pub(crate) fn history(&self) -> Vec<Roam> {
+        self.lock().roams.iter().cloned().collect()
+    }
+}
@@ -0,0 +1,223 @@
+//! Evil-twin and rogue access point detection.
+//!
+//! [`RogueApDetector`] is fed successive scan results and compares them with
+//! what it knows about a set of watched SSIDs. It reports suspicious patterns
+//! as [`SecurityAlert`]s; it never blocks connections by itself.
+
+use crate::{NetworkInfo, SecurityType};
+use std::collections::{HashMap, HashSet};
+
+/// A suspicious observation for a watched SSID.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SecurityAlert {
+    /// A BSSID advertises the SSID with different security than expected.
+    SecurityMismatch {
+        ssid: String,
+        bssid: String,
+        expected: SecurityType,
+        observed: SecurityType,
+    },
+    /// An open network appeared under the name of a secured SSID.
+    OpenTwin { ssid: String, bssid: String },
+    /// A BSSID not seen before advertises the SSID (only reported when
+    /// learning of new BSSIDs is disabled).
+    UnknownBssid { ssid: String, bssid: String, channel: i32 },
+    /// The same BSSID was seen on several channels in a single scan, which
+    /// points to a cloned access point.
+    DuplicateBssid { ssid: String, bssid: String, channels: Vec<i32> },
+    /// A known BSSID moved to a different channel.
+    ChannelChanged { ssid: String, bssid: String, previous: i32, current: i32 },
+}
+
+impl SecurityAlert {
+    /// SSID the alert refers to.
+    pub fn ssid(&self) -> &str {
+        match self {
+            SecurityAlert::SecurityMismatch { ssid, .. }
+            | SecurityAlert::OpenTwin { ssid, .. }
+            | SecurityAlert::UnknownBssid { ssid, .. }
+            | SecurityAlert::DuplicateBssid { ssid, .. }
+            | SecurityAlert::ChannelChanged { ssid, .. } => ssid,
+        }
+    }
+
+    /// BSSID the alert refers to.
+    pub fn bssid(&self) -> &str {
+        match self {
+            SecurityAlert::SecurityMismatch { bssid, .. }
+            | SecurityAlert::OpenTwin { bssid, .. }
+            | SecurityAlert::UnknownBssid { bssid, .. }
+            | SecurityAlert::DuplicateBssid { bssid, .. }
+            | SecurityAlert::ChannelChanged { bssid, .. } => bssid,
+        }
+    }
+
+    fn key(&self) -> String {
+        let kind = match self {
+            SecurityAlert::SecurityMismatch { .. } => "security",
+            SecurityAlert::OpenTwin { .. } => "open",
+            SecurityAlert::UnknownBssid { .. } => "unknown",
+            SecurityAlert::DuplicateBssid { .. } => "duplicate",
+            SecurityAlert::ChannelChanged { .. } => "channel",
+        };
+        format!("{}/{}/{}", kind, self.ssid(), self.bssid())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct WatchedNetwork {
+    security: SecurityType,
+    /// Known BSSIDs and the channel they were last seen on.
+    bssids: HashMap<String, i32>,
+}
+
+/// Opt-in analyzer flagging evil-twin patterns across continuous scans.
+///
+/// ```no_run
+/// use wifi_rs::{RogueApDetector, SecurityType, WiFi};
+///
+/// let wifi = WiFi::new();
+/// let mut detector = RogueApDetector::new();
+/// detector.watch("CorpNet", SecurityType::Wpa2);
+/// loop {
+///     for alert in detector.analyze(&wifi.scan()) {
+///         eprintln!("possible rogue AP: {:?}", alert);
+///     }
+///     std::thread::sleep(std::time::Duration::from_secs(30));
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct RogueApDetector {
+    watched: HashMap<String, WatchedNetwork>,
+    learn_new_bssids: bool,
+    reported: HashSet<String>,
+}
+
+impl Default for RogueApDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RogueApDetector {
+    /// Create a detector with no watched SSIDs.
+    pub fn new() -> Self {
+        RogueApDetector {
+            watched: HashMap::new(),
+            learn_new_bssids: true,
+            reported: HashSet::new(),
+        }
+    }
+
+    /// Watch `ssid`, expecting every access point to use `security`.
+    pub fn watch(&mut self, ssid: &str, security: SecurityType) -> &mut Self {
+        self.watched.insert(
+            ssid.to_string(),
+            WatchedNetwork {
+                security,
+                bssids: HashMap::new(),
+            },
+        );
+        self
+    }
+
+    /// Mark `bssid` as a legitimate access point of a watched SSID.
+    pub fn trust_bssid(&mut self, ssid: &str, bssid: &str, channel: i32) -> &mut Self {
+        if let Some(network) = self.watched.get_mut(ssid) {
+            network.bssids.insert(bssid.to_ascii_uppercase(), channel);
+        }
+        self
+    }
+
+    /// Control whether unseen BSSIDs with the expected security are learned
+    /// silently (the default) or reported as [`SecurityAlert::UnknownBssid`].
+    ///
+    /// Disable learning after seeding the detector with [`trust_bssid`](Self::trust_bssid)
+    /// on sites with a fixed set of access points.
+    pub fn learn_new_bssids(&mut self, learn: bool) -> &mut Self {
+        self.learn_new_bssids = learn;
+        self
+    }
+
+    /// Analyze one scan and return the alerts that have not been reported before.
+    pub fn analyze(&mut self, networks: &[NetworkInfo]) -> Vec<SecurityAlert> {
+        let mut alerts = Vec::new();
+
+        // Channels each BSSID of a watched SSID was seen on in this scan
+        let mut seen: HashMap<(&str, String), Vec<i32>> = HashMap::new();
+        for network in networks {
+            if self.watched.contains_key(&network.ssid) {
+                let channels = seen
+                    .entry((network.ssid.as_str(), network.bssid.to_ascii_uppercase()))
+                    .or_default();
+                if !channels.contains(&network.channel) {
+                    channels.push(network.channel);
+                }
+            }
+        }
+
+        for network in networks {
+            let watched = match self.watched.get_mut(&network.ssid) {
+                Some(watched) => watched,
+                None => continue,
+            };
+            let bssid = network.bssid.to_ascii_uppercase();
+
+            if network.security_type != watched.security {
+                if network.security_type == SecurityType::None {
+                    alerts.push(SecurityAlert::OpenTwin {
+                        ssid: network.ssid.clone(),
+                        bssid,
+                    });
+                } else {
+                    alerts.push(SecurityAlert::SecurityMismatch {
+                        ssid: network.ssid.clone(),
+                        bssid,
+                        expected: watched.security,
+                        observed: network.security_type,
+                    });
+                }
+                continue;
+            }
+
+            let channels = &seen[&(network.ssid.as_str(), bssid.clone())];
+            if channels.len() > 1 {
+                alerts.push(SecurityAlert::DuplicateBssid {
+                    ssid: network.ssid.clone(),
+                    bssid,
+                    channels: channels.clone(),
+                });
+                continue;
+            }
+
+            match watched.bssids.get(&bssid).copied() {
+                Some(previous) if previous != network.channel => {
+                    watched.bssids.insert(bssid.clone(), network.channel);
+                    alerts.push(SecurityAlert::ChannelChanged {
+                        ssid: network.ssid.clone(),
+                        bssid,
+                        previous,
+                        current: network.channel,
+                    });
+                }
+                Some(_) => {}
+                None if self.learn_new_bssids => {
+                    watched.bssids.insert(bssid, network.channel);
+                }
+                None => alerts.push(SecurityAlert::UnknownBssid {
+                    ssid: network.ssid.clone(),
+                    bssid,
+                    channel: network.channel,
+                }),
+            }
+        }
+
+        alerts.retain(|alert| self.reported.insert(alert.key()));
+        alerts
+    }
+
+    /// Forget previously reported alerts so they are raised again if seen.
+    pub fn reset_alerts(&mut self) {
+        self.reported.clear();
+    }
+}
@@ -0,0 +1,57 @@
+//! 802.11mc Fine Timing Measurement (round trip time) ranging.
+
+use std::time::Duration;
+
+#[repr(C)]
+pub(crate) struct RawRangingResult {
+    distance_mm: i64,
+    distance_variance_mm2: i64,
+    rtt_ps: i64,
+    attempts: i32,
+    successes: i32,
+}
+
+impl RawRangingResult {
+    pub(crate) fn zeroed() -> Self {
+        // SAFETY: the struct is plain integers.
+        unsafe { std::mem::zeroed() }
+    }
+}
+
+/// Distance estimate from [`WiFi::measure_distance`](crate::WiFi::measure_distance).
+///
+/// Typical accuracy is one to two meters with line of sight; walls and
+/// reflections bias the estimate upwards. Combine measurements against
+/// three or more access points at known positions to locate the device.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RangingResult {
+    pub bssid: String,
+    /// Estimated distance in meters.
+    pub distance_m: f64,
+    /// Variance of the estimate in square meters.
+    pub variance_m2: f64,
+    /// Average round trip time of the timing frames.
+    pub rtt: Duration,
+    /// Timing measurement requests sent.
+    pub attempts: u32,
+    /// Requests the access point answered.
+    pub successes: u32,
+}
+
+impl RangingResult {
+    pub(crate) fn from_raw(bssid: &str, raw: &RawRangingResult) -> Self {
+        RangingResult {
+            bssid: bssid.to_string(),
+            distance_m: raw.distance_mm.max(0) as f64 / 1000.0,
+            variance_m2: raw.distance_variance_mm2.max(0) as f64 / 1_000_000.0,
+            rtt: Duration::from_nanos(raw.rtt_ps.max(0) as u64 / 1000),
+            attempts: raw.attempts.max(0) as u32,
+            successes: raw.successes.max(0) as u32,
+        }
+    }
+
+    /// Standard deviation of the estimate in meters.
+    pub fn std_dev_m(&self) -> f64 {
+        self.variance_m2.sqrt()
+    }
+}
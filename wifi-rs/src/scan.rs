@@ -0,0 +1,95 @@
+//! Directed scans of selected networks, channels or bands.
+
+use std::time::Duration;
+
+use crate::channel::channel_to_frequency;
+use crate::Band;
+
+#[repr(C)]
+pub(crate) struct RawScanOptions {
+    pub(crate) ssids: *const *const libc::c_char,
+    pub(crate) ssid_count: i32,
+    pub(crate) frequencies: *const i32,
+    pub(crate) frequency_count: i32,
+    pub(crate) passive: i32,
+}
+
+/// Settings for [`WiFi::scan_with_options`](crate::WiFi::scan_with_options).
+///
+/// A full scan visits every channel and takes 4 to 8 seconds; limiting it to
+/// a few channels or networks finishes in a fraction of that.
+///
+/// ```no_run
+/// use wifi_rs::{Band, ScanOptions, WiFi};
+///
+/// let wifi = WiFi::new();
+/// let office = wifi.scan_with_options(&ScanOptions::new().ssid("Office").band(Band::Ghz5))?;
+/// # Ok::<(), wifi_rs::WifiError>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScanOptions {
+    pub(crate) ssids: Vec<String>,
+    pub(crate) channels: Vec<u32>,
+    pub(crate) band: Option<Band>,
+    pub(crate) passive: bool,
+    pub(crate) timeout: Option<Duration>,
+}
+
+impl ScanOptions {
+    /// Options for a scan of all networks on all channels.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Probe for `ssid`, which also finds it when it is hidden. May be
+    /// given several times; results are limited to the given networks.
+    pub fn ssid(mut self, ssid: &str) -> Self {
+        self.ssids.push(ssid.to_string());
+        self
+    }
+
+    /// Only scan these channels. Channel numbers are taken from the band
+    /// set with [`band`](Self::band), or from 2.4 and 5 GHz without one.
+    pub fn channels(mut self, channels: &[u32]) -> Self {
+        self.channels = channels.to_vec();
+        self
+    }
+
+    /// Only scan channels of `band`.
+    pub fn band(mut self, band: Band) -> Self {
+        self.band = Some(band);
+        self
+    }
+
+    /// Only listen for beacons instead of sending probe requests. Slower,
+    /// but does not reveal the device, and is required on channels where
+    /// the regulatory domain forbids transmitting first.
+    pub fn passive(mut self, passive: bool) -> Self {
+        self.passive = passive;
+        self
+    }
+
+    /// Wait at most `timeout` for the scan to complete, instead of the scan
+    /// timeout of [`Timeouts`](crate::Timeouts). A scan still running then
+    /// is aborted and returns what it found so far.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Frequencies in MHz of the selected channels, `None` if one of them
+    /// does not exist in its band.
+    pub(crate) fn channel_frequencies(&self) -> Option<Vec<i32>> {
+        self.channels
+            .iter()
+            .map(|&channel| {
+                let channel = i32::try_from(channel).ok()?;
+                match self.band {
+                    Some(band) => channel_to_frequency(band, channel),
+                    None => channel_to_frequency(Band::Ghz2, channel).or_else(|| channel_to_frequency(Band::Ghz5, channel)),
+                }
+            })
+            .collect()
+    }
+}
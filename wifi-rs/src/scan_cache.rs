@@ -0,0 +1,231 @@
+//! Scan results shared between consumers.
+//!
+//! A full scan takes seconds and briefly takes the radio off channel, so
+//! components that each scan on their own waste time and disturb the link.
+//! [`ScanScheduler::spawn`](crate::ScanScheduler::spawn) instead scans on a
+//! background thread and keeps the latest results in a [`ScanCache`], which
+//! any number of consumers read or subscribe to.
+//!
+//! ```no_run
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//! use wifi_rs::{ScanSchedule, ScanScheduler, WiFi};
+//!
+//! let wifi = Arc::new(WiFi::new());
+//! let cache = ScanScheduler::new(ScanSchedule::every(Duration::from_secs(30))).spawn(Arc::clone(&wifi));
+//! for snapshot in cache.subscribe() {
+//!     println!("{} access points", snapshot.networks.len());
+//! }
+//! ```
+
+use std::collections::HashMap;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, SystemTime};
+
+use crate::{NetworkInfo, ScanSchedule, WifiBackend};
+
+/// An access point in a [`ScanSnapshot`].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CachedNetwork {
+    pub network: NetworkInfo,
+    /// When the access point first appeared in the cache's scans.
+    pub first_seen: SystemTime,
+    /// When it was last seen, i.e. the time of the snapshot.
+    pub last_seen: SystemTime,
+}
+
+/// The result of one background scan, one entry per BSSID, strongest
+/// first.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScanSnapshot {
+    pub networks: Vec<CachedNetwork>,
+    pub taken_at: SystemTime,
+}
+
+impl ScanSnapshot {
+    /// Entries whose SSID is `ssid`, strongest first.
+    pub fn by_ssid<'a>(&'a self, ssid: &'a str) -> impl Iterator<Item = &'a CachedNetwork> + 'a {
+        self.networks.iter().filter(move |cached| cached.network.ssid == ssid)
+    }
+}
+
+/// Access points are keyed by BSSID, or by SSID where the platform hides
+/// the BSSID.
+pub(crate) fn key(network: &NetworkInfo) -> &str {
+    if network.bssid == "[No Access]" {
+        &network.ssid
+    } else {
+        &network.bssid
+    }
+}
+
+fn build_snapshot(networks: Vec<NetworkInfo>, previous: Option<&ScanSnapshot>, taken_at: SystemTime) -> ScanSnapshot {
+    let mut strongest: HashMap<String, NetworkInfo> = HashMap::new();
+    for network in networks {
+        match strongest.get(key(&network)) {
+            Some(kept) if kept.signal_strength >= network.signal_strength => {}
+            _ => {
+                strongest.insert(key(&network).to_string(), network);
+            }
+        }
+    }
+
+    let first_seen: HashMap<&str, SystemTime> = previous
+        .map(|snapshot| snapshot.networks.iter().map(|cached| (key(&cached.network), cached.first_seen)).collect())
+        .unwrap_or_default();
+    let mut networks: Vec<CachedNetwork> = strongest
+        .into_values()
+        .map(|network| CachedNetwork {
+            first_seen: first_seen.get(key(&network)).copied().unwrap_or(taken_at),
+            last_seen: taken_at,
+            network,
+        })
+        .collect();
+    networks.sort_by_key(|cached| std::cmp::Reverse(cached.network.signal_strength));
+    ScanSnapshot { networks, taken_at }
+}
+
+/// Whether the set of access points differs; signal fluctuations alone do
+/// not count as a change.
+fn changed(previous: Option<&ScanSnapshot>, current: &ScanSnapshot) -> bool {
+    let Some(previous) = previous else { return true };
+    let mut before: Vec<&str> = previous.networks.iter().map(|cached| key(&cached.network)).collect();
+    let mut after: Vec<&str> = current.networks.iter().map(|cached| key(&cached.network)).collect();
+    before.sort_unstable();
+    after.sort_unstable();
+    before != after
+}
+
+#[derive(Default)]
+struct State {
+    latest: Option<Arc<ScanSnapshot>>,
+    subscribers: Vec<Sender<Arc<ScanSnapshot>>>,
+    refresh: bool,
+    stopped: bool,
+}
+
+#[derive(Default)]
+struct Shared {
+    state: Mutex<State>,
+    wake: Condvar,
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Latest results of a background scan loop, created by
+/// [`ScanScheduler::spawn`](crate::ScanScheduler::spawn).
+///
+/// Dropping the cache stops the scan thread, waiting for a scan in
+/// progress to finish.
+pub struct ScanCache {
+    shared: Arc<Shared>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl ScanCache {
+    pub(crate) fn start<B>(schedule: ScanSchedule, backend: Arc<B>) -> Self
+    where
+        B: WifiBackend + Send + Sync + 'static,
+    {
+        let shared = Arc::new(Shared::default());
+        let worker = {
+            let shared = Arc::clone(&shared);
+            thread::Builder::new()
+                .name("wifi-rs-scan".to_string())
+                .spawn(move || scan_loop(&schedule, backend.as_ref(), &shared))
+                .expect("failed to spawn the scan thread")
+        };
+        ScanCache { shared, worker: Some(worker) }
+    }
+
+    /// The most recent scan, `None` until the first one completed.
+    pub fn latest(&self) -> Option<Arc<ScanSnapshot>> {
+        self.shared.lock().latest.clone()
+    }
+
+    /// The networks of the most recent scan, empty until the first one
+    /// completed.
+    pub fn networks(&self) -> Vec<NetworkInfo> {
+        self.latest()
+            .map(|snapshot| snapshot.networks.iter().map(|cached| cached.network.clone()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Receive a snapshot whenever access points appear or disappear.
+    ///
+    /// The current snapshot, if any, is delivered immediately. The receiver
+    /// may be dropped at any time.
+    pub fn subscribe(&self) -> Receiver<Arc<ScanSnapshot>> {
+        let (sender, receiver) = mpsc::channel();
+        let mut state = self.shared.lock();
+        if let Some(latest) = &state.latest {
+            let _ = sender.send(Arc::clone(latest));
+        }
+        state.subscribers.push(sender);
+        receiver
+    }
+
+    /// Scan as soon as possible instead of waiting for the next interval.
+    /// Ignores the schedule's time and power conditions.
+    pub fn refresh(&self) {
+        self.shared.lock().refresh = true;
+        self.shared.wake.notify_all();
+    }
+}
+
+impl Drop for ScanCache {
+    fn drop(&mut self) {
+        self.shared.lock().stopped = true;
+        self.shared.wake.notify_all();
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+fn scan_loop(schedule: &ScanSchedule, backend: &impl WifiBackend, shared: &Shared) {
+    // Conditions are re-checked every few seconds while they forbid a scan
+    let recheck = schedule.interval().min(Duration::from_secs(5));
+    let mut forced = false;
+    loop {
+        let wait = if forced || schedule.allows_now() {
+            let networks = backend.scan();
+            publish(shared, networks);
+            schedule.interval()
+        } else {
+            recheck
+        };
+
+        let state = shared.lock();
+        let (mut state, _) = shared
+            .wake
+            .wait_timeout_while(state, wait, |state| !state.stopped && !state.refresh)
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if state.stopped {
+            return;
+        }
+        forced = std::mem::take(&mut state.refresh);
+    }
+}
+
+fn publish(shared: &Shared, networks: Vec<NetworkInfo>) {
+    let mut state = shared.lock();
+    // Platforms report a failed scan as an empty one; keep the last result
+    if networks.is_empty() && state.latest.is_some() {
+        return;
+    }
+    let snapshot = Arc::new(build_snapshot(networks, state.latest.as_deref(), SystemTime::now()));
+    let notify = changed(state.latest.as_deref(), &snapshot);
+    state.latest = Some(Arc::clone(&snapshot));
+    if notify {
+        state.subscribers.retain(|subscriber| subscriber.send(Arc::clone(&snapshot)).is_ok());
+    }
+}
@@ -0,0 +1,121 @@
+//! Changes between two scans.
+//!
+//! Access points are matched by BSSID, or by SSID where the platform hides
+//! BSSIDs, and an access point listed more than once in a scan counts with
+//! its strongest entry.
+//!
+//! ```no_run
+//! use std::time::Duration;
+//! use wifi_rs::{ScanDiff, WiFi};
+//!
+//! let wifi = WiFi::new();
+//! let mut before = wifi.scan();
+//! loop {
+//!     std::thread::sleep(Duration::from_secs(30));
+//!     let after = wifi.scan();
+//!     let diff = ScanDiff::between(&before, &after, 10);
+//!     for network in &diff.appeared {
+//!         println!("+ {} ({})", network.ssid, network.bssid);
+//!     }
+//!     for network in &diff.disappeared {
+//!         println!("- {} ({})", network.ssid, network.bssid);
+//!     }
+//!     before = after;
+//! }
+//! ```
+
+use std::collections::HashMap;
+
+use crate::scan_cache::key;
+use crate::{NetworkInfo, ScanSnapshot};
+
+/// An access point whose signal changed by at least the threshold.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SignalChange {
+    /// The access point as seen in the newer scan.
+    pub network: NetworkInfo,
+    /// Signal strength in the older scan, in dBm.
+    pub previous_dbm: i32,
+}
+
+impl SignalChange {
+    /// Change in dB, positive when the signal got stronger.
+    pub fn delta_db(&self) -> i32 {
+        self.network.signal_strength - self.previous_dbm
+    }
+}
+
+/// What changed from one scan to the next, each list strongest first.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScanDiff {
+    /// Access points only in the newer scan.
+    pub appeared: Vec<NetworkInfo>,
+    /// Access points only in the older scan, as last seen.
+    pub disappeared: Vec<NetworkInfo>,
+    /// Access points in both scans whose signal moved by at least the
+    /// threshold.
+    pub changed: Vec<SignalChange>,
+}
+
+impl ScanDiff {
+    /// Compare the scans `before` and `after`, reporting signal changes of
+    /// at least `threshold_db`.
+    pub fn between(before: &[NetworkInfo], after: &[NetworkInfo], threshold_db: i32) -> ScanDiff {
+        let before = strongest(before);
+        let after = strongest(after);
+        let mut diff = ScanDiff::default();
+        for (bssid, network) in &after {
+            match before.get(bssid) {
+                None => diff.appeared.push((*network).clone()),
+                Some(previous) if (network.signal_strength - previous.signal_strength).abs() >= threshold_db => {
+                    diff.changed.push(SignalChange {
+                        network: (*network).clone(),
+                        previous_dbm: previous.signal_strength,
+                    })
+                }
+                Some(_) => {}
+            }
+        }
+        diff.disappeared = before
+            .iter()
+            .filter(|(bssid, _)| !after.contains_key(*bssid))
+            .map(|(_, network)| (*network).clone())
+            .collect();
+
+        diff.appeared.sort_by_key(|network| std::cmp::Reverse(network.signal_strength));
+        diff.disappeared.sort_by_key(|network| std::cmp::Reverse(network.signal_strength));
+        diff.changed.sort_by_key(|change| std::cmp::Reverse(change.network.signal_strength));
+        diff
+    }
+
+    /// Whether nothing changed.
+    pub fn is_empty(&self) -> bool {
+        self.appeared.is_empty() && self.disappeared.is_empty() && self.changed.is_empty()
+    }
+}
+
+impl ScanSnapshot {
+    /// Changes from this snapshot to the newer `snapshot`, e.g. between two
+    /// results received from [`ScanCache::subscribe`](crate::ScanCache::subscribe).
+    pub fn diff(&self, snapshot: &ScanSnapshot, threshold_db: i32) -> ScanDiff {
+        let networks = |snapshot: &ScanSnapshot| -> Vec<NetworkInfo> {
+            snapshot.networks.iter().map(|cached| cached.network.clone()).collect()
+        };
+        ScanDiff::between(&networks(self), &networks(snapshot), threshold_db)
+    }
+}
+
+fn strongest(networks: &[NetworkInfo]) -> HashMap<&str, &NetworkInfo> {
+    let mut strongest: HashMap<&str, &NetworkInfo> = HashMap::new();
+    for network in networks {
+        match strongest.get(key(network)) {
+            Some(kept) if kept.signal_strength >= network.signal_strength => {}
+            _ => {
+                strongest.insert(key(network), network);
+            }
+        }
+    }
+    strongest
+}
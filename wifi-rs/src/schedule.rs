@@ -0,0 +1,383 @@
+//! Periodic scans on a schedule, stored through a sink.
+//!
+//! For monitoring deployments that record the radio environment over
+//! weeks: a [`ScanSchedule`] says how often to scan and under which
+//! conditions (time of day, mains power), and a [`ScanScheduler`] runs the
+//! scans and hands each result to a [`ScanSink`], such as a
+//! [`ScanHistory`](crate::history::ScanHistory) with the `history`
+//! feature, without an external cron wrapper.
+//!
+//! ```no_run
+//! use std::sync::atomic::AtomicBool;
+//! use wifi_rs::{ScanSchedule, ScanScheduler, WiFi};
+//!
+//! let schedule: ScanSchedule = "every 5 minutes between 08:00-18:00 on AC power only".parse().unwrap();
+//! let wifi = WiFi::new();
+//! let stop = AtomicBool::new(false);
+//! let mut sink = |networks: &[wifi_rs::NetworkInfo], _taken_at| {
+//!     println!("{} networks", networks.len());
+//!     Ok::<(), std::io::Error>(())
+//! };
+//! ScanScheduler::new(schedule).run(&wifi, &mut sink, &stop).unwrap();
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::{NetworkInfo, ScanCache, WifiBackend};
+
+/// A wall-clock time of day in the device's local time zone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TimeOfDay {
+    hour: u8,
+    minute: u8,
+}
+
+impl TimeOfDay {
+    /// `None` unless `hour` is below 24 and `minute` below 60.
+    pub fn new(hour: u8, minute: u8) -> Option<Self> {
+        (hour < 24 && minute < 60).then_some(TimeOfDay { hour, minute })
+    }
+
+    pub fn hour(&self) -> u8 {
+        self.hour
+    }
+
+    pub fn minute(&self) -> u8 {
+        self.minute
+    }
+
+    /// The current local time of day, `None` if the platform cannot tell.
+    pub fn now() -> Option<Self> {
+        let (hour, minute) = local_hour_minute()?;
+        TimeOfDay::new(hour, minute)
+    }
+}
+
+impl fmt::Display for TimeOfDay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}:{:02}", self.hour, self.minute)
+    }
+}
+
+impl FromStr for TimeOfDay {
+    type Err = ParseScheduleError;
+
+    /// Parse `HH:MM` in 24-hour notation.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseScheduleError(format!("invalid time of day {:?}", s));
+        let (hour, minute) = s.split_once(':').ok_or_else(error)?;
+        TimeOfDay::new(hour.parse().map_err(|_| error())?, minute.parse().map_err(|_| error())?).ok_or_else(error)
+    }
+}
+
+#[cfg(unix)]
+fn local_hour_minute() -> Option<(u8, u8)> {
+    // SAFETY: localtime_r only writes to the provided struct.
+    unsafe {
+        let now = libc::time(std::ptr::null_mut());
+        let mut tm: libc::tm = std::mem::zeroed();
+        if libc::localtime_r(&now, &mut tm).is_null() {
+            return None;
+        }
+        Some((tm.tm_hour as u8, tm.tm_min as u8))
+    }
+}
+
+#[cfg(windows)]
+fn local_hour_minute() -> Option<(u8, u8)> {
+    // SAFETY: localtime_s only writes to the provided struct.
+    unsafe {
+        let now = libc::time(std::ptr::null_mut());
+        let mut tm: libc::tm = std::mem::zeroed();
+        if libc::localtime_s(&mut tm, &now) != 0 {
+            return None;
+        }
+        Some((tm.tm_hour as u8, tm.tm_min as u8))
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+fn local_hour_minute() -> Option<(u8, u8)> {
+    None
+}
+
+/// Whether the device runs on mains power. Devices without a battery
+/// count as on mains power.
+pub fn on_ac_power() -> bool {
+    power::on_ac_power()
+}
+
+#[cfg(target_os = "linux")]
+mod power {
+    use std::fs;
+
+    pub(super) fn on_ac_power() -> bool {
+        let mut battery = false;
+        for entry in fs::read_dir("/sys/class/power_supply").into_iter().flatten().flatten() {
+            let path = entry.path();
+            let kind = fs::read_to_string(path.join("type")).unwrap_or_default();
+            let online = fs::read_to_string(path.join("online")).is_ok_and(|v| v.trim() == "1");
+            match kind.trim() {
+                "Mains" | "USB" if online => return true,
+                "Battery" => battery = true,
+                _ => {}
+            }
+        }
+        !battery
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod power {
+    pub(super) fn on_ac_power() -> bool {
+        std::process::Command::new("pmset")
+            .args(["-g", "batt"])
+            .output()
+            .map_or(true, |output| String::from_utf8_lossy(&output.stdout).contains("'AC Power'"))
+    }
+}
+
+#[cfg(windows)]
+mod power {
+    #[repr(C)]
+    struct SystemPowerStatus {
+        ac_line_status: u8,
+        battery_flag: u8,
+        battery_life_percent: u8,
+        system_status_flag: u8,
+        battery_life_time: u32,
+        battery_full_life_time: u32,
+    }
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn GetSystemPowerStatus(status: *mut SystemPowerStatus) -> i32;
+    }
+
+    pub(super) fn on_ac_power() -> bool {
+        // SAFETY: the struct matches SYSTEM_POWER_STATUS and is only written.
+        let mut status: SystemPowerStatus = unsafe { std::mem::zeroed() };
+        if unsafe { GetSystemPowerStatus(&mut status) } == 0 {
+            return true;
+        }
+        // 0 is offline, 1 online, 255 unknown; 128 in the flags means no battery
+        status.ac_line_status != 0 || status.battery_flag == 128
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+mod power {
+    pub(super) fn on_ac_power() -> bool {
+        true
+    }
+}
+
+/// Error parsing a [`ScanSchedule`] or [`TimeOfDay`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseScheduleError(String);
+
+impl fmt::Display for ParseScheduleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for ParseScheduleError {}
+
+/// When scheduled scans run.
+///
+/// Parses from rules such as `every 5 minutes`,
+/// `every 30 seconds between 22:00-06:00` or
+/// `every 1 hour on AC power only`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScanSchedule {
+    interval: Duration,
+    window: Option<(TimeOfDay, TimeOfDay)>,
+    ac_power_only: bool,
+}
+
+impl ScanSchedule {
+    /// Scan every `interval`, at any time.
+    pub fn every(interval: Duration) -> Self {
+        ScanSchedule { interval, window: None, ac_power_only: false }
+    }
+
+    /// Only scan from `start` until `end` local time. A window whose end is
+    /// before its start spans midnight.
+    pub fn between(mut self, start: TimeOfDay, end: TimeOfDay) -> Self {
+        self.window = Some((start, end));
+        self
+    }
+
+    /// Only scan while the device runs on mains power.
+    pub fn ac_power_only(mut self) -> Self {
+        self.ac_power_only = true;
+        self
+    }
+
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Whether the time and power conditions allow a scan now.
+    pub fn allows_now(&self) -> bool {
+        let in_window = match (self.window, TimeOfDay::now()) {
+            (None, _) => true,
+            (Some((start, end)), Some(now)) => in_window(start, end, now),
+            // Without a clock, honoring the window is impossible; scan anyway
+            (Some(_), None) => true,
+        };
+        in_window && (!self.ac_power_only || on_ac_power())
+    }
+}
+
+fn in_window(start: TimeOfDay, end: TimeOfDay, now: TimeOfDay) -> bool {
+    if start <= end {
+        start <= now && now < end
+    } else {
+        now >= start || now < end
+    }
+}
+
+impl FromStr for ScanSchedule {
+    type Err = ParseScheduleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = |message: &str| ParseScheduleError(format!("{} in schedule {:?}", message, s));
+        // Accept the en dash people paste from documents
+        let normalized = s.replace('\u{2013}', "-").to_ascii_lowercase();
+        let words: Vec<&str> = normalized.split_whitespace().collect();
+
+        let (count, unit) = match words.as_slice() {
+            ["every", count, unit, ..] => (*count, *unit),
+            _ => return Err(error("expected \"every <n> <unit>\"")),
+        };
+        let count: u64 = count.parse().map_err(|_| error("invalid count"))?;
+        let seconds = match unit.trim_end_matches('s') {
+            "second" | "sec" => 1,
+            "minute" | "min" => 60,
+            "hour" => 3600,
+            _ => return Err(error("unknown unit")),
+        };
+        if count == 0 {
+            return Err(error("interval must not be zero"));
+        }
+        let mut schedule = ScanSchedule::every(Duration::from_secs(count * seconds));
+
+        let mut rest = &words[3..];
+        while !rest.is_empty() {
+            match rest {
+                ["between", range, tail @ ..] => {
+                    let (start, end) = range.split_once('-').ok_or_else(|| error("expected HH:MM-HH:MM"))?;
+                    schedule = schedule.between(start.parse()?, end.parse()?);
+                    rest = tail;
+                }
+                ["on", "ac", "power", "only", tail @ ..] | ["on", "ac", "power", tail @ ..] => {
+                    schedule = schedule.ac_power_only();
+                    rest = tail;
+                }
+                _ => return Err(error("unexpected rule")),
+            }
+        }
+        Ok(schedule)
+    }
+}
+
+/// Destination for scheduled scan results.
+///
+/// Implemented for closures taking the networks and the time of the scan,
+/// and for [`ScanHistory`](crate::history::ScanHistory) with the `history`
+/// feature.
+pub trait ScanSink {
+    type Error;
+
+    /// Store one scan result.
+    fn store(&mut self, networks: &[NetworkInfo], taken_at: SystemTime) -> Result<(), Self::Error>;
+}
+
+impl<F, E> ScanSink for F
+where
+    F: FnMut(&[NetworkInfo], SystemTime) -> Result<(), E>,
+{
+    type Error = E;
+
+    fn store(&mut self, networks: &[NetworkInfo], taken_at: SystemTime) -> Result<(), E> {
+        self(networks, taken_at)
+    }
+}
+
+#[cfg(feature = "history")]
+impl ScanSink for crate::history::ScanHistory {
+    type Error = crate::history::Error;
+
+    fn store(&mut self, networks: &[NetworkInfo], taken_at: SystemTime) -> Result<(), Self::Error> {
+        self.record_scan_at(networks, taken_at).map(|_| ())
+    }
+}
+
+/// Runs scans according to a [`ScanSchedule`].
+pub struct ScanScheduler {
+    schedule: ScanSchedule,
+    last_scan: Option<Instant>,
+}
+
+impl ScanScheduler {
+    pub fn new(schedule: ScanSchedule) -> Self {
+        ScanScheduler { schedule, last_scan: None }
+    }
+
+    /// Scan and store the result if the schedule says a scan is due.
+    ///
+    /// # Returns
+    ///
+    /// The number of networks stored, `None` if no scan was due.
+    ///
+    /// # Errors
+    ///
+    /// Returns the sink's error if storing failed.
+    pub fn tick<S: ScanSink>(&mut self, wifi: &impl WifiBackend, sink: &mut S) -> Result<Option<usize>, S::Error> {
+        let due = self.last_scan.is_none_or(|last| last.elapsed() >= self.schedule.interval);
+        if !due || !self.schedule.allows_now() {
+            return Ok(None);
+        }
+        self.last_scan = Some(Instant::now());
+        let networks = wifi.scan();
+        sink.store(&networks, SystemTime::now())?;
+        Ok(Some(networks.len()))
+    }
+
+    /// Scan on schedule until `stop` is set.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error of the sink.
+    pub fn run<S: ScanSink>(&mut self, wifi: &impl WifiBackend, sink: &mut S, stop: &AtomicBool) -> Result<(), S::Error> {
+        while !stop.load(Ordering::Relaxed) {
+            self.tick(wifi, sink)?;
+            // Conditions are re-checked every few seconds so a window
+            // opening or mains power returning is noticed promptly.
+            let step = self.schedule.interval.min(Duration::from_secs(5));
+            let started = Instant::now();
+            while !stop.load(Ordering::Relaxed) && started.elapsed() < step {
+                std::thread::sleep(Duration::from_millis(250));
+            }
+        }
+        Ok(())
+    }
+
+    /// Scan on schedule on a background thread, keeping the results in a
+    /// [`ScanCache`] that consumers share instead of scanning themselves.
+    ///
+    /// Scans are made through `backend`, which other threads may keep
+    /// using; a [`WiFi`](crate::WiFi) serializes concurrent calls.
+    pub fn spawn<B>(self, backend: Arc<B>) -> ScanCache
+    where
+        B: WifiBackend + Send + Sync + 'static,
+    {
+        ScanCache::start(self.schedule, backend)
+    }
+}
@@ -0,0 +1,81 @@
+//! Secret string type for passwords and passphrases.
+
+use std::ffi::CString;
+use std::fmt;
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::WifiError;
+
+/// A password or passphrase that is redacted in `Debug` output and wiped
+/// from memory when dropped.
+///
+/// The plaintext is only reachable through [`SecretString::expose`], which
+/// makes every place that reads the secret easy to audit.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct SecretString(String);
+
+impl SecretString {
+    /// Wrap `secret`, taking ownership of its buffer.
+    pub fn new(secret: impl Into<String>) -> Self {
+        SecretString(secret.into())
+    }
+
+    /// Access the plaintext secret.
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns `true` if the secret is empty.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Copy the secret into a C string that is wiped when dropped.
+    pub(crate) fn to_c_string(&self) -> Result<Zeroizing<CString>, WifiError> {
+        // Checked up front: the failed copy inside a `NulError` is not wiped
+        if self.0.contains('\0') {
+            return Err(WifiError::InvalidArgument);
+        }
+        Ok(Zeroizing::new(CString::new(self.0.as_bytes())?))
+    }
+}
+
+impl Drop for SecretString {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl fmt::Debug for SecretString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SecretString([REDACTED])")
+    }
+}
+
+impl From<String> for SecretString {
+    fn from(secret: String) -> Self {
+        SecretString(secret)
+    }
+}
+
+impl From<&str> for SecretString {
+    fn from(secret: &str) -> Self {
+        SecretString(secret.to_string())
+    }
+}
+
+/// Serializes the plaintext, so serialized settings need the same
+/// protection as the operating system's stored profiles.
+#[cfg(feature = "serde")]
+impl serde::Serialize for SecretString {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for SecretString {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(SecretString)
+    }
+}
@@ -0,0 +1,135 @@
+//! Live signal readings of the current connection.
+//!
+//! [`WiFi::current_connection`](crate::WiFi::current_connection) reads the
+//! association once; a [`SignalMonitor`] has the native layer poll it on a
+//! background thread and delivers each reading, which is what a signal meter
+//! needs.
+
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::time::{Duration, SystemTime};
+
+use crate::roam::{LinkSample, RawConnectionInfo};
+use crate::{WiFi, WifiError};
+
+pub(crate) type RawSignalCallback = extern "C" fn(info: *const RawConnectionInfo, user_data: *mut libc::c_void);
+
+/// One reading of the current association.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SignalSample {
+    pub ssid: String,
+    pub bssid: String,
+    /// Frequency in MHz.
+    pub frequency: i32,
+    /// Received signal strength in dBm.
+    pub signal_dbm: i32,
+    /// Noise floor in dBm, if the driver reports it.
+    pub noise_dbm: Option<i32>,
+    /// Current transmit rate in Mbit/s, if reported.
+    pub link_speed_mbps: Option<u32>,
+    pub taken: SystemTime,
+}
+
+impl SignalSample {
+    pub(crate) fn from_sample(sample: LinkSample) -> Self {
+        SignalSample {
+            ssid: sample.ssid,
+            bssid: sample.bssid,
+            frequency: sample.frequency,
+            signal_dbm: sample.signal_strength,
+            noise_dbm: (sample.noise != 0).then_some(sample.noise),
+            link_speed_mbps: u32::try_from(sample.link_speed_mbps).ok().filter(|&speed| speed > 0),
+            taken: sample.taken,
+        }
+    }
+
+    /// Signal-to-noise ratio in dB, if the noise floor is known.
+    pub fn snr_db(&self) -> Option<i32> {
+        self.noise_dbm.map(|noise| self.signal_dbm - noise)
+    }
+}
+
+extern "C" fn on_sample(info: *const RawConnectionInfo, user_data: *mut libc::c_void) {
+    if info.is_null() || user_data.is_null() {
+        return;
+    }
+    // SAFETY: user_data is the boxed sender owned by the `SignalMonitor`,
+    // which stops the monitor (and therefore all callbacks) before freeing
+    // it, and the reading is valid for the duration of the call.
+    let (sender, info) = unsafe { (&*(user_data as *const Sender<SignalSample>), &*info) };
+    let _ = sender.send(SignalSample::from_sample(LinkSample::from_raw(info)));
+}
+
+/// Periodic signal readings, created by [`WiFi::monitor_signal`].
+///
+/// Readings are queued until taken, and are only produced while associated.
+/// Iterating blocks for the next reading; dropping the monitor stops the
+/// polling thread.
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use wifi_rs::WiFi;
+///
+/// let wifi = WiFi::new();
+/// for sample in wifi.monitor_signal(Duration::from_millis(500))?.take(20) {
+///     println!("{} dBm on {}", sample.signal_dbm, sample.bssid);
+/// }
+/// # Ok::<(), wifi_rs::WifiError>(())
+/// ```
+pub struct SignalMonitor<'a> {
+    wifi: &'a WiFi,
+    samples: Receiver<SignalSample>,
+    sender: *mut Sender<SignalSample>,
+}
+
+impl<'a> SignalMonitor<'a> {
+    pub(crate) fn start(wifi: &'a WiFi, interval: Duration) -> Result<Self, WifiError> {
+        let interval_ms = interval.as_millis().clamp(1, i32::MAX as u128) as i32;
+        let (sender, samples) = mpsc::channel();
+        let sender = Box::into_raw(Box::new(sender));
+        let started = unsafe {
+            crate::wifi_manager_start_signal_monitor(wifi.manager, interval_ms, on_sample, sender as *mut libc::c_void)
+        };
+        if !started {
+            drop(unsafe { Box::from_raw(sender) });
+            return Err(WifiError::OperationFailed);
+        }
+        Ok(SignalMonitor { wifi, samples, sender })
+    }
+
+    /// Wait up to `timeout` for the next reading.
+    ///
+    /// # Returns
+    ///
+    /// The reading, or `None` if none arrived in time, e.g. because the
+    /// device is not associated.
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<SignalSample> {
+        match self.samples.recv_timeout(timeout) {
+            Ok(sample) => Some(sample),
+            Err(RecvTimeoutError::Timeout) | Err(RecvTimeoutError::Disconnected) => None,
+        }
+    }
+
+    /// The most recent queued reading, discarding older ones, without
+    /// waiting.
+    pub fn latest(&self) -> Option<SignalSample> {
+        self.samples.try_iter().last()
+    }
+}
+
+impl Iterator for SignalMonitor<'_> {
+    type Item = SignalSample;
+
+    fn next(&mut self) -> Option<SignalSample> {
+        self.samples.recv().ok()
+    }
+}
+
+impl Drop for SignalMonitor<'_> {
+    fn drop(&mut self) {
+        unsafe {
+            crate::wifi_manager_stop_signal_monitor(self.wifi.manager);
+            drop(Box::from_raw(self.sender));
+        }
+    }
+}
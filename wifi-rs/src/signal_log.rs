@@ -0,0 +1,260 @@
+//! Long-running logging of link metrics to rotated files.
+//!
+//! Meant to run for weeks on unattended hardware: each sample is appended
+//! and flushed immediately, files are rotated by size or age, and only a
+//! fixed number of old files is kept.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::roam::LinkSample;
+use crate::WiFi;
+
+/// Output format of the log files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    /// Comma separated values with a header line per file.
+    Csv,
+    /// One JSON object per line.
+    JsonLines,
+}
+
+/// When the current file is moved aside and a new one started.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rotation {
+    /// Never rotate.
+    Never,
+    /// Rotate once the file reaches the given number of bytes.
+    Size(u64),
+    /// Rotate once the file has been written to for the given time.
+    Interval(Duration),
+}
+
+/// Periodically samples the current connection and appends it to a file.
+///
+/// Rotated files are renamed `<path>.1`, `<path>.2`, ... with `.1` the most
+/// recent; files beyond [`keep`](Self::keep) are deleted.
+///
+/// ```no_run
+/// use std::sync::atomic::AtomicBool;
+/// use std::time::Duration;
+/// use wifi_rs::{LogFormat, Rotation, SignalLogger, WiFi};
+///
+/// let wifi = WiFi::new();
+/// let stop = AtomicBool::new(false);
+/// let mut logger = SignalLogger::new("/var/log/wifi-signal.csv")
+///     .format(LogFormat::Csv)
+///     .interval(Duration::from_secs(10))
+///     .rotate(Rotation::Size(10 * 1024 * 1024))
+///     .keep(5);
+/// logger.run(&wifi, &stop).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct SignalLogger {
+    path: PathBuf,
+    format: LogFormat,
+    interval: Duration,
+    rotation: Rotation,
+    keep: usize,
+    file: Option<File>,
+    written: u64,
+    opened: Instant,
+}
+
+impl SignalLogger {
+    /// Log to `path` as CSV every 5 seconds, rotating at 10 MiB and keeping
+    /// 5 old files.
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        SignalLogger {
+            path: path.as_ref().to_path_buf(),
+            format: LogFormat::Csv,
+            interval: Duration::from_secs(5),
+            rotation: Rotation::Size(10 * 1024 * 1024),
+            keep: 5,
+            file: None,
+            written: 0,
+            opened: Instant::now(),
+        }
+    }
+
+    pub fn format(mut self, format: LogFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Time between samples taken by [`run`](Self::run).
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    pub fn rotate(mut self, rotation: Rotation) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Number of rotated files to keep.
+    pub fn keep(mut self, keep: usize) -> Self {
+        self.keep = keep;
+        self
+    }
+
+    /// Sample `wifi` every interval until `stop` is set.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first I/O error writing or rotating the log.
+    pub fn run(&mut self, wifi: &WiFi, stop: &AtomicBool) -> io::Result<()> {
+        while !stop.load(Ordering::Relaxed) {
+            let started = Instant::now();
+            self.log_sample(wifi)?;
+
+            // Sleep in short steps so a stop request is noticed promptly.
+            while !stop.load(Ordering::Relaxed) {
+                let elapsed = started.elapsed();
+                if elapsed >= self.interval {
+                    break;
+                }
+                std::thread::sleep((self.interval - elapsed).min(Duration::from_millis(250)));
+            }
+        }
+        Ok(())
+    }
+
+    /// Take one sample of `wifi` and append it.
+    ///
+    /// Samples taken while disconnected are logged with empty link fields,
+    /// so coverage gaps show up in the log.
+    pub fn log_sample(&mut self, wifi: &WiFi) -> io::Result<()> {
+        let sample = wifi.sample_connection();
+        let line = match self.format {
+            LogFormat::Csv => csv_line(sample.as_ref()),
+            LogFormat::JsonLines => json_line(sample.as_ref()),
+        };
+        self.append(&line)
+    }
+
+    fn append(&mut self, line: &str) -> io::Result<()> {
+        if self.due_for_rotation() {
+            self.rotate_files()?;
+        }
+        if self.file.is_none() {
+            self.open()?;
+        }
+        let file = self.file.as_mut().expect("log file was just opened");
+        file.write_all(line.as_bytes())?;
+        file.flush()?;
+        self.written += line.len() as u64;
+        Ok(())
+    }
+
+    fn due_for_rotation(&self) -> bool {
+        if self.file.is_none() {
+            return false;
+        }
+        match self.rotation {
+            Rotation::Never => false,
+            Rotation::Size(limit) => self.written >= limit,
+            Rotation::Interval(age) => self.opened.elapsed() >= age,
+        }
+    }
+
+    fn open(&mut self) -> io::Result<()> {
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        self.written = file.metadata()?.len();
+        self.opened = Instant::now();
+        if self.written == 0 && self.format == LogFormat::Csv {
+            let header = "timestamp,connected,ssid,bssid,frequency_mhz,signal_dbm\n";
+            file.write_all(header.as_bytes())?;
+            self.written = header.len() as u64;
+        }
+        self.file = Some(file);
+        Ok(())
+    }
+
+    fn rotated_path(&self, index: usize) -> PathBuf {
+        let mut name = self.path.clone().into_os_string();
+        name.push(format!(".{}", index));
+        PathBuf::from(name)
+    }
+
+    fn rotate_files(&mut self) -> io::Result<()> {
+        self.file = None;
+        if self.keep == 0 {
+            return fs::remove_file(&self.path);
+        }
+
+        let oldest = self.rotated_path(self.keep);
+        if oldest.exists() {
+            fs::remove_file(&oldest)?;
+        }
+        for index in (1..self.keep).rev() {
+            let from = self.rotated_path(index);
+            if from.exists() {
+                fs::rename(&from, self.rotated_path(index + 1))?;
+            }
+        }
+        fs::rename(&self.path, self.rotated_path(1))
+    }
+}
+
+fn timestamp() -> String {
+    let now = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default();
+    format!("{}.{:03}", now.as_secs(), now.subsec_millis())
+}
+
+pub(crate) fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn csv_line(sample: Option<&LinkSample>) -> String {
+    match sample {
+        Some(s) => format!(
+            "{},true,{},{},{},{}\n",
+            timestamp(),
+            csv_field(&s.ssid),
+            s.bssid,
+            s.frequency,
+            s.signal_strength
+        ),
+        None => format!("{},false,,,,\n", timestamp()),
+    }
+}
+
+fn json_string(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn json_line(sample: Option<&LinkSample>) -> String {
+    match sample {
+        Some(s) => format!(
+            "{{\"timestamp\":{},\"connected\":true,\"ssid\":{},\"bssid\":{},\"frequency_mhz\":{},\"signal_dbm\":{}}}\n",
+            timestamp(),
+            json_string(&s.ssid),
+            json_string(&s.bssid),
+            s.frequency,
+            s.signal_strength
+        ),
+        None => format!("{{\"timestamp\":{},\"connected\":false}}\n", timestamp()),
+    }
+}
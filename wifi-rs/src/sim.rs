@@ -0,0 +1,222 @@
+//! Simulated radio environment for scenario tests.
+//!
+//! Enabled with the `backend-mock` feature. A [`SimEnv`] describes access
+//! points whose signal changes over time and links that drop at scripted
+//! moments, and answers [`WifiBackend`] calls from that description. Like a
+//! real supplicant, it keeps the connection on the strongest access point
+//! of the joined network, so roaming, the
+//! [`ConnectionWatchdog`](crate::ConnectionWatchdog) and reconnect logic
+//! can be exercised in CI.
+//!
+//! Time runs with the wall clock from the moment the environment is built;
+//! [`advance`](SimEnv::advance) skips ahead so long scenarios finish
+//! quickly.
+//!
+//! ```
+//! use std::time::Duration;
+//! use wifi_rs::sim::SimEnv;
+//! use wifi_rs::{ConnectOptions, WifiBackend};
+//!
+//! let env = SimEnv::new()
+//!     .ap("Office", 6, -55)
+//!     .moving_ap("Office", 36, -85, -45, Duration::from_secs(60));
+//!
+//! env.connect_with("Office", &ConnectOptions::password("secret")).unwrap();
+//! assert_eq!(env.link_info().unwrap().frequency, 2437);
+//!
+//! env.advance(Duration::from_secs(60));
+//! assert_eq!(env.link_info().unwrap().frequency, 5180);
+//! ```
+
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use crate::mock::{self, MockBackend};
+use crate::{
+    ConnectOptions, ConnectionStatus, EventKind, HotspotConfig, LinkInfo, NetworkInfo, WifiBackend, WifiError, WifiEvent,
+};
+
+/// Signal strength in dBm below which a simulated access point is out of
+/// range: it no longer shows up in scans and a link to it drops.
+pub const SIM_NOISE_FLOOR_DBM: i32 = -95;
+
+/// Center frequency in MHz of a 2.4 or 5 GHz channel.
+fn channel_to_frequency(channel: i32) -> i32 {
+    match channel {
+        14 => 2484,
+        1..=13 => 2407 + channel * 5,
+        _ => 5000 + channel * 5,
+    }
+}
+
+struct SimAp {
+    ssid: String,
+    frequency: i32,
+    from_dbm: i32,
+    to_dbm: i32,
+    over: Duration,
+}
+
+impl SimAp {
+    fn signal_at(&self, elapsed: Duration) -> i32 {
+        if elapsed >= self.over || self.over.is_zero() {
+            return self.to_dbm;
+        }
+        let progress = elapsed.as_secs_f64() / self.over.as_secs_f64();
+        self.from_dbm + ((self.to_dbm - self.from_dbm) as f64 * progress).round() as i32
+    }
+}
+
+struct Clock {
+    skipped: Duration,
+    disconnects: Vec<Duration>,
+}
+
+/// Simulated access points and scripted link failures, usable as a
+/// [`WifiBackend`].
+///
+/// Channels 1 to 14 are in the 2.4 GHz band, higher channels in the 5 GHz
+/// band.
+pub struct SimEnv {
+    mock: MockBackend,
+    aps: Vec<SimAp>,
+    started: Instant,
+    clock: Mutex<Clock>,
+}
+
+impl Default for SimEnv {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SimEnv {
+    /// An environment without access points.
+    pub fn new() -> Self {
+        SimEnv {
+            mock: MockBackend::new(),
+            aps: Vec::new(),
+            started: Instant::now(),
+            clock: Mutex::new(Clock { skipped: Duration::ZERO, disconnects: Vec::new() }),
+        }
+    }
+
+    /// Add an access point of `ssid` on `channel` with a constant signal.
+    pub fn ap(self, ssid: &str, channel: i32, signal_dbm: i32) -> Self {
+        self.moving_ap(ssid, channel, signal_dbm, signal_dbm, Duration::ZERO)
+    }
+
+    /// Add an access point of `ssid` on `channel` whose signal changes
+    /// linearly from `from_dbm` to `to_dbm` over `over`, as seen by a
+    /// device walking towards or away from it, and then stays at `to_dbm`.
+    pub fn moving_ap(mut self, ssid: &str, channel: i32, from_dbm: i32, to_dbm: i32, over: Duration) -> Self {
+        self.aps.push(SimAp {
+            ssid: ssid.to_string(),
+            frequency: channel_to_frequency(channel),
+            from_dbm,
+            to_dbm,
+            over,
+        });
+        self
+    }
+
+    /// Drop the connection, if any, once `at` has passed.
+    pub fn disconnect_at(self, at: Duration) -> Self {
+        self.lock().disconnects.push(at);
+        self
+    }
+
+    /// The underlying mock, e.g. to script connection failures or inspect
+    /// the calls made.
+    pub fn mock(&self) -> &MockBackend {
+        &self.mock
+    }
+
+    /// Simulated time since the environment was built.
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed() + self.lock().skipped
+    }
+
+    /// Move simulated time forward by `step` without waiting.
+    pub fn advance(&self, step: Duration) {
+        self.lock().skipped += step;
+        self.sync();
+    }
+
+    /// Visible networks at the current simulated time.
+    pub fn networks(&self) -> Vec<NetworkInfo> {
+        let elapsed = self.elapsed();
+        self.aps
+            .iter()
+            .map(|ap| mock::network(&ap.ssid, ap.frequency, ap.signal_at(elapsed)))
+            .filter(|n| n.signal_strength >= SIM_NOISE_FLOOR_DBM)
+            .collect()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Clock> {
+        self.clock.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Bring the mock up to the current simulated time.
+    fn sync(&self) {
+        let elapsed = self.elapsed();
+        let networks = self.networks();
+        let due = {
+            let mut clock = self.lock();
+            let before = clock.disconnects.len();
+            clock.disconnects.retain(|at| *at > elapsed);
+            clock.disconnects.len() < before
+        };
+        let out_of_range = self
+            .mock
+            .connected()
+            .is_some_and(|ssid| !networks.iter().any(|n| n.ssid == ssid));
+        self.mock.set_networks(networks);
+        if due || out_of_range {
+            self.mock.drop_link();
+        }
+    }
+}
+
+impl WifiBackend for SimEnv {
+    fn scan(&self) -> Vec<NetworkInfo> {
+        self.sync();
+        self.mock.scan()
+    }
+
+    fn connect_with(&self, ssid: &str, options: &ConnectOptions) -> Result<(), WifiError> {
+        self.sync();
+        self.mock.connect_with(ssid, options)
+    }
+
+    fn disconnect(&self) -> Result<(), WifiError> {
+        self.sync();
+        self.mock.disconnect()
+    }
+
+    fn get_status(&self) -> ConnectionStatus {
+        self.sync();
+        self.mock.get_status()
+    }
+
+    fn link_info(&self) -> Option<LinkInfo> {
+        self.sync();
+        self.mock.link_info()
+    }
+
+    fn event_log(&self) -> Vec<WifiEvent> {
+        self.mock.event_log()
+    }
+
+    fn record_event(&self, kind: EventKind) {
+        self.mock.record_event(kind);
+    }
+
+    fn create_hotspot_with(&self, config: &HotspotConfig) -> Result<(), WifiError> {
+        self.mock.create_hotspot_with(config)
+    }
+
+    fn stop_hotspot(&self) -> Result<(), WifiError> {
+        self.mock.stop_hotspot()
+    }
+}
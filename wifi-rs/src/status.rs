@@ -0,0 +1,106 @@
+//! Detailed connection status.
+//!
+//! [`ConnectionStatus`] only says whether the device is connected. A
+//! [`StatusDetail`] also reports which step of joining a network the stack is
+//! in, whether the network actually reaches the internet, and why the last
+//! attempt failed, so a UI can show "Obtaining IP address on Office" or
+//! "Sign-in required" instead of a spinner.
+
+use std::fmt;
+
+use crate::diag::{self, Connectivity, LinkState};
+use crate::{ConnectionStatus, WifiError};
+
+/// Step of the connection lifecycle the stack is in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SubState {
+    /// Not associated with any network.
+    Disconnected,
+    /// Looking for or associating with the access point.
+    Associating,
+    /// Associated; the key exchange or 802.1X authentication is running.
+    Authenticating,
+    /// Authenticated and waiting for DHCP to assign an address.
+    ObtainingIp,
+    /// Has an address, but a captive portal intercepts traffic until the
+    /// user signs in.
+    CaptivePortalPending,
+    /// Has an address, but the internet is not reachable.
+    LimitedConnectivity,
+    /// Connected with working internet access.
+    Connected,
+}
+
+impl fmt::Display for SubState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            SubState::Disconnected => "disconnected",
+            SubState::Associating => "associating",
+            SubState::Authenticating => "authenticating",
+            SubState::ObtainingIp => "obtaining IP address",
+            SubState::CaptivePortalPending => "sign-in required",
+            SubState::LimitedConnectivity => "limited connectivity",
+            SubState::Connected => "connected",
+        })
+    }
+}
+
+/// Connection status with context, from
+/// [`WiFi::status_detailed`](crate::WiFi::status_detailed).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StatusDetail {
+    /// The coarse status [`WiFi::get_status`](crate::WiFi::get_status)
+    /// reports.
+    pub status: ConnectionStatus,
+    pub sub_state: SubState,
+    /// Network being joined or connected to, or else the network of the
+    /// last failed attempt.
+    pub ssid: Option<String>,
+    /// Access point associated with, where the platform reports it.
+    pub bssid: Option<String>,
+    /// Why the last connection attempt failed, unless one has succeeded
+    /// since.
+    pub last_error: Option<WifiError>,
+}
+
+impl StatusDetail {
+    /// Combine the coarse status with the link state, probing the internet
+    /// once the link has an address.
+    pub(crate) fn new(status: ConnectionStatus, link: Option<LinkState>, last_error: Option<(String, WifiError)>) -> Self {
+        let non_empty = |value: &str| (!value.is_empty()).then(|| value.to_string());
+        let ssid = link.as_ref().and_then(|link| non_empty(&link.ssid));
+        let bssid = link.as_ref().and_then(|link| non_empty(&link.bssid));
+
+        let sub_state = match &link {
+            Some(link) if link.authenticated && link.ip_address.is_none() => SubState::ObtainingIp,
+            Some(link) if link.associated && !link.authenticated => SubState::Authenticating,
+            _ => match status {
+                ConnectionStatus::Connected => Self::probe(),
+                ConnectionStatus::Connecting => SubState::Associating,
+                ConnectionStatus::Disconnected | ConnectionStatus::Error => SubState::Disconnected,
+            },
+        };
+
+        let (failed_ssid, last_error) = last_error.unzip();
+        StatusDetail { status, sub_state, ssid: ssid.or(failed_ssid), bssid, last_error }
+    }
+
+    fn probe() -> SubState {
+        match diag::connectivity() {
+            Connectivity::Internet => SubState::Connected,
+            Connectivity::CaptivePortal => SubState::CaptivePortalPending,
+            Connectivity::Limited => SubState::LimitedConnectivity,
+        }
+    }
+
+    /// Whether the device has an address on the network, with or without
+    /// internet access.
+    pub fn has_ip(&self) -> bool {
+        matches!(
+            self.sub_state,
+            SubState::Connected | SubState::CaptivePortalPending | SubState::LimitedConnectivity
+        )
+    }
+}
@@ -0,0 +1,69 @@
+//! Per-channel airtime and noise survey.
+
+use std::time::Duration;
+
+#[repr(C)]
+pub(crate) struct RawChannelSurvey {
+    frequency: libc::c_int,
+    channel: libc::c_int,
+    in_use: libc::c_int,
+    noise: libc::c_int,
+    active_time_ms: i64,
+    busy_time_ms: i64,
+    receive_time_ms: i64,
+    transmit_time_ms: i64,
+}
+
+/// Channel occupancy measured by the radio.
+///
+/// Times are cumulative since the driver started counting, so utilization
+/// over an interval is the difference between two surveys. Fields the driver
+/// does not report are `None`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChannelSurvey {
+    /// Center frequency in MHz.
+    pub frequency: i32,
+    pub channel: i32,
+    /// Whether the interface is currently operating on this channel.
+    pub in_use: bool,
+    /// Noise floor in dBm.
+    pub noise_dbm: Option<i32>,
+    /// Time the radio spent on the channel.
+    pub active_time: Option<Duration>,
+    /// Time the medium was sensed busy.
+    pub busy_time: Option<Duration>,
+    /// Time spent receiving frames.
+    pub receive_time: Option<Duration>,
+    /// Time spent transmitting frames.
+    pub transmit_time: Option<Duration>,
+}
+
+fn millis(value: i64) -> Option<Duration> {
+    u64::try_from(value).ok().map(Duration::from_millis)
+}
+
+impl ChannelSurvey {
+    pub(crate) fn from_raw(raw: &RawChannelSurvey) -> Self {
+        ChannelSurvey {
+            frequency: raw.frequency,
+            channel: raw.channel,
+            in_use: raw.in_use != 0,
+            noise_dbm: if raw.noise == 0 { None } else { Some(raw.noise) },
+            active_time: millis(raw.active_time_ms),
+            busy_time: millis(raw.busy_time_ms),
+            receive_time: millis(raw.receive_time_ms),
+            transmit_time: millis(raw.transmit_time_ms),
+        }
+    }
+
+    /// Fraction of the active time the medium was busy, from 0.0 to 1.0.
+    pub fn utilization(&self) -> Option<f64> {
+        let active = self.active_time?.as_secs_f64();
+        let busy = self.busy_time?.as_secs_f64();
+        if active > 0.0 {
+            Some((busy / active).min(1.0))
+        } else {
+            None
+        }
+    }
+}
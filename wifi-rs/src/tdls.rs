@@ -0,0 +1,35 @@
+//! TDLS (Tunneled Direct Link Setup) peers.
+
+use crate::diag::text;
+
+#[repr(C)]
+pub(crate) struct RawTdlsPeer {
+    address: [libc::c_char; 18],
+    ip_address: [libc::c_char; 46],
+    linked: i32,
+}
+
+/// A station on the current network found by
+/// [`WiFi::tdls_discover`](crate::WiFi::tdls_discover).
+///
+/// Whether the peer supports TDLS is only known once
+/// [`WiFi::tdls_setup`](crate::WiFi::tdls_setup) succeeds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TdlsPeer {
+    pub address: String,
+    /// IP address of the station, if known.
+    pub ip_address: Option<String>,
+    /// Whether a direct link to the station is up.
+    pub linked: bool,
+}
+
+impl TdlsPeer {
+    pub(crate) fn from_raw(raw: &RawTdlsPeer) -> Self {
+        let ip_address = text(&raw.ip_address);
+        TdlsPeer {
+            address: text(&raw.address),
+            ip_address: (!ip_address.is_empty()).then_some(ip_address),
+            linked: raw.linked != 0,
+        }
+    }
+}
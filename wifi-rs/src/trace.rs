@@ -0,0 +1,169 @@
+//! Optional `tracing` instrumentation.
+//!
+//! Every call into the C++ layer is wrapped in a `wifi_op` span recording
+//! the operation name, interface, duration and outcome, and every entry of
+//! the event log is also emitted as a `wifi_event` event with target
+//! `wifi_rs::events`. SSIDs never appear in clear text: operations and
+//! events on a network carry an `ssid_hash` field instead, a stable 64-bit
+//! FNV-1a hash that lets log pipelines correlate records for the same
+//! network. `WiFiBuilder::log_to_tracing` adds the native library's own log
+//! messages. With the `tracing` feature disabled the wrappers compile down
+//! to a plain call.
+
+#[cfg(feature = "tracing")]
+use crate::{ConnectionStatus, EventKind, LogLevel, WifiError};
+
+/// Outcome of a native operation as recorded on its span.
+#[cfg(feature = "tracing")]
+pub(crate) trait Traced {
+    fn record(&self, span: &tracing::Span);
+}
+
+#[cfg(feature = "tracing")]
+impl Traced for () {
+    fn record(&self, span: &tracing::Span) {
+        span.record("result", "ok");
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl Traced for bool {
+    fn record(&self, span: &tracing::Span) {
+        span.record("result", if *self { "ok" } else { "failed" });
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl<T> Traced for Vec<T> {
+    fn record(&self, span: &tracing::Span) {
+        span.record("result", "ok");
+        span.record("count", self.len());
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl<T> Traced for Option<T> {
+    fn record(&self, span: &tracing::Span) {
+        span.record("result", if self.is_some() { "ok" } else { "none" });
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl Traced for ConnectionStatus {
+    fn record(&self, span: &tracing::Span) {
+        span.record("result", tracing::field::debug(self));
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl<T> Traced for Result<T, WifiError> {
+    fn record(&self, span: &tracing::Span) {
+        match self {
+            Ok(_) => span.record("result", "ok"),
+            Err(e) => span.record("result", "failed").record("error_code", tracing::field::debug(e)),
+        };
+    }
+}
+
+/// Stable pseudonym of an SSID for log records.
+#[cfg(feature = "tracing")]
+pub(crate) fn ssid_hash(ssid: &str) -> String {
+    let hash = ssid.bytes().fold(0xcbf2_9ce4_8422_2325u64, |hash, b| {
+        (hash ^ b as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    });
+    format!("{:016x}", hash)
+}
+
+/// Run `f` inside a `wifi_op` span named after `op` on `interface`.
+#[cfg(feature = "tracing")]
+pub(crate) fn op<T: Traced>(interface: Option<&str>, op: &'static str, f: impl FnOnce() -> T) -> T {
+    let span = tracing::info_span!(
+        "wifi_op",
+        op,
+        interface = tracing::field::Empty,
+        ssid_hash = tracing::field::Empty,
+        duration_ms = tracing::field::Empty,
+        result = tracing::field::Empty,
+        error_code = tracing::field::Empty,
+        count = tracing::field::Empty,
+    );
+    if let Some(interface) = interface {
+        span.record("interface", interface);
+    }
+    let _guard = span.enter();
+    let start = std::time::Instant::now();
+    let outcome = f();
+    span.record("duration_ms", start.elapsed().as_secs_f64() * 1000.0);
+    outcome.record(&span);
+    outcome
+}
+
+/// Record the network an operation acts on, from inside [`op`].
+#[cfg(feature = "tracing")]
+pub(crate) fn ssid(ssid: &str) {
+    tracing::Span::current().record("ssid_hash", ssid_hash(ssid));
+}
+
+/// Emit an event log entry.
+#[cfg(feature = "tracing")]
+pub(crate) fn event(kind: &EventKind) {
+    let (name, ssid) = match kind {
+        EventKind::Connected { ssid } => ("connected", Some(ssid)),
+        EventKind::ConnectFailed { ssid } => ("connect_failed", Some(ssid)),
+        EventKind::NetworkUnusable { ssid, .. } => ("network_unusable", Some(ssid)),
+        EventKind::NetworkSwitched { to, .. } => ("network_switched", Some(to)),
+        EventKind::Disconnected { .. } => ("disconnected", None),
+        EventKind::Roamed { ssid, .. } => ("roamed", Some(ssid)),
+        EventKind::LinkFlapping { .. } => ("link_flapping", None),
+        EventKind::ScanFailed => ("scan_failed", None),
+        EventKind::HotspotStarted { ssid } => ("hotspot_started", Some(ssid)),
+        EventKind::HotspotFailed { ssid } => ("hotspot_failed", Some(ssid)),
+        EventKind::HotspotStopped => ("hotspot_stopped", None),
+    };
+    let ssid_hash = ssid.map(|ssid| ssid_hash(ssid));
+    match kind {
+        EventKind::ConnectFailed { .. } | EventKind::HotspotFailed { .. } | EventKind::ScanFailed => {
+            tracing::warn!(target: "wifi_rs::events", event = name, ssid_hash, "wifi_event")
+        }
+        EventKind::NetworkUnusable { failure, .. } => {
+            tracing::warn!(target: "wifi_rs::events", event = name, ssid_hash, failure = ?failure, "wifi_event")
+        }
+        EventKind::Disconnected { reason } => {
+            tracing::info!(target: "wifi_rs::events", event = name, reason = ?reason, "wifi_event")
+        }
+        EventKind::LinkFlapping { flaps, window } => tracing::warn!(
+            target: "wifi_rs::events",
+            event = name,
+            flaps,
+            window_s = window.as_secs(),
+            "wifi_event"
+        ),
+        _ => tracing::info!(target: "wifi_rs::events", event = name, ssid_hash, "wifi_event"),
+    }
+}
+
+/// Emit a native log message.
+#[cfg(feature = "tracing")]
+pub(crate) fn native_log(level: LogLevel, message: &str) {
+    match level {
+        LogLevel::Debug => tracing::debug!(target: "wifi_rs::native", "{}", message),
+        LogLevel::Info => tracing::info!(target: "wifi_rs::native", "{}", message),
+        LogLevel::Warning => tracing::warn!(target: "wifi_rs::native", "{}", message),
+        LogLevel::Error => tracing::error!(target: "wifi_rs::native", "{}", message),
+    }
+}
+
+/// Run `f` without instrumentation.
+#[cfg(not(feature = "tracing"))]
+#[inline(always)]
+pub(crate) fn op<T>(_interface: Option<&str>, _op: &'static str, f: impl FnOnce() -> T) -> T {
+    f()
+}
+
+#[cfg(not(feature = "tracing"))]
+#[inline(always)]
+pub(crate) fn ssid(_ssid: &str) {}
+
+#[cfg(not(feature = "tracing"))]
+#[inline(always)]
+pub(crate) fn event(_kind: &crate::EventKind) {}
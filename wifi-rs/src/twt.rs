@@ -0,0 +1,128 @@
+//! Target Wake Time (802.11ax) power saving.
+//!
+//! With an individual TWT agreement the station sleeps between negotiated
+//! service periods instead of waking for every beacon, which cuts idle power
+//! of battery powered devices considerably. Both the access point and the
+//! adapter must support it.
+
+use std::time::Duration;
+
+/// Longest wake duration a TWT agreement can express.
+pub const MAX_TWT_WAKE_DURATION: Duration = Duration::from_micros(255 * 256);
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub(crate) struct RawTwtConfig {
+    wake_interval_us: u64,
+    wake_duration_us: u32,
+    trigger_enabled: i32,
+    announced: i32,
+}
+
+#[repr(C)]
+pub(crate) struct RawTwtStatus {
+    ap_responder: i32,
+    local_requester: i32,
+    agreement_requested: i32,
+    config: RawTwtConfig,
+}
+
+impl RawTwtStatus {
+    pub(crate) fn zeroed() -> Self {
+        // SAFETY: the struct is plain integers.
+        unsafe { std::mem::zeroed() }
+    }
+}
+
+/// Parameters of a TWT agreement for
+/// [`WiFi::twt_request`](crate::WiFi::twt_request).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TwtConfig {
+    pub(crate) wake_interval: Duration,
+    pub(crate) wake_duration: Duration,
+    pub(crate) trigger_enabled: bool,
+    pub(crate) announced: bool,
+}
+
+impl TwtConfig {
+    /// Wake for at least `wake_duration` every `wake_interval`, using
+    /// unannounced service periods without trigger frames.
+    ///
+    /// The access point may round the values; the duration is limited to
+    /// [`MAX_TWT_WAKE_DURATION`].
+    pub fn new(wake_interval: Duration, wake_duration: Duration) -> Self {
+        TwtConfig {
+            wake_interval,
+            wake_duration,
+            trigger_enabled: false,
+            announced: false,
+        }
+    }
+
+    /// Let the access point start each service period with a trigger frame.
+    pub fn trigger_enabled(mut self, trigger_enabled: bool) -> Self {
+        self.trigger_enabled = trigger_enabled;
+        self
+    }
+
+    /// Have the station announce that it is awake before the access point
+    /// sends buffered frames.
+    pub fn announced(mut self, announced: bool) -> Self {
+        self.announced = announced;
+        self
+    }
+
+    pub fn wake_interval(&self) -> Duration {
+        self.wake_interval
+    }
+
+    pub fn wake_duration(&self) -> Duration {
+        self.wake_duration
+    }
+
+    pub(crate) fn raw(&self) -> RawTwtConfig {
+        RawTwtConfig {
+            wake_interval_us: self.wake_interval.as_micros().min(u64::MAX as u128) as u64,
+            wake_duration_us: self.wake_duration.as_micros().min(u32::MAX as u128) as u32,
+            trigger_enabled: self.trigger_enabled as i32,
+            announced: self.announced as i32,
+        }
+    }
+
+    fn from_raw(raw: &RawTwtConfig) -> Self {
+        TwtConfig {
+            wake_interval: Duration::from_micros(raw.wake_interval_us),
+            wake_duration: Duration::from_micros(raw.wake_duration_us as u64),
+            trigger_enabled: raw.trigger_enabled != 0,
+            announced: raw.announced != 0,
+        }
+    }
+}
+
+/// TWT support of the current association, from
+/// [`WiFi::twt_status`](crate::WiFi::twt_status).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TwtStatus {
+    /// The access point accepts TWT requests.
+    pub ap_responder: bool,
+    /// The adapter can request TWT agreements.
+    pub local_requester: bool,
+    /// The agreement requested on this association, as encoded in the
+    /// request. The access point may have adjusted or declined it.
+    pub requested: Option<TwtConfig>,
+}
+
+impl TwtStatus {
+    pub(crate) fn from_raw(raw: &RawTwtStatus) -> Self {
+        TwtStatus {
+            ap_responder: raw.ap_responder != 0,
+            local_requester: raw.local_requester != 0,
+            requested: (raw.agreement_requested != 0).then(|| TwtConfig::from_raw(&raw.config)),
+        }
+    }
+
+    /// Both sides support TWT.
+    pub fn supported(&self) -> bool {
+        self.ap_responder && self.local_requester
+    }
+}
@@ -0,0 +1,184 @@
+//! Watchdog that moves a poor connection to a better known network.
+//!
+//! For devices that must stay online unattended: when the signal of the
+//! active link stays below a threshold for a grace period and a configured
+//! network is visible with a clearly stronger signal, the watchdog connects
+//! to it. Hysteresis and a cooldown keep it from bouncing between networks.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::{Band, ConnectOptions, EventKind, WifiBackend, WifiError};
+
+/// Thresholds controlling when the watchdog switches networks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WatchdogPolicy {
+    /// Signal strength in dBm below which the link counts as degraded.
+    pub min_signal_dbm: i32,
+    /// How long the link must stay degraded before switching.
+    pub grace: Duration,
+    /// How many dB stronger a candidate must be than the current link.
+    pub hysteresis_db: i32,
+    /// Minimum time between two switches.
+    pub cooldown: Duration,
+}
+
+impl Default for WatchdogPolicy {
+    fn default() -> Self {
+        WatchdogPolicy {
+            min_signal_dbm: -75,
+            grace: Duration::from_secs(30),
+            hysteresis_db: 8,
+            cooldown: Duration::from_secs(120),
+        }
+    }
+}
+
+/// What the watchdog did during a check.
+#[derive(Debug, Clone, PartialEq)]
+pub enum WatchdogEvent {
+    /// The link fell below the threshold; `signal_dbm` is `None` when
+    /// disconnected.
+    Degraded { ssid: Option<String>, signal_dbm: Option<i32> },
+    /// The link is above the threshold again without a switch.
+    Recovered { ssid: String, signal_dbm: i32 },
+    /// Connected to a better network.
+    Switched { from: Option<String>, to: String, signal_dbm: i32 },
+    /// Connecting to the better network failed.
+    SwitchFailed { to: String, error: WifiError },
+}
+
+struct KnownNetwork {
+    ssid: String,
+    options: ConnectOptions,
+}
+
+/// Policy-driven connection watchdog.
+///
+/// ```no_run
+/// use std::sync::atomic::AtomicBool;
+/// use std::time::Duration;
+/// use wifi_rs::{ConnectOptions, ConnectionWatchdog, WatchdogPolicy, WiFi};
+///
+/// let wifi = WiFi::new();
+/// let stop = AtomicBool::new(false);
+/// let mut watchdog = ConnectionWatchdog::new(WatchdogPolicy::default())
+///     .known_network("Office", ConnectOptions::password("secret"))
+///     .known_network("Office-Backup", ConnectOptions::password("secret"));
+/// watchdog.run(&wifi, Duration::from_secs(5), &stop, |event| println!("{:?}", event));
+/// ```
+pub struct ConnectionWatchdog {
+    policy: WatchdogPolicy,
+    networks: Vec<KnownNetwork>,
+    degraded_since: Option<Instant>,
+    last_switch: Option<Instant>,
+}
+
+impl ConnectionWatchdog {
+    pub fn new(policy: WatchdogPolicy) -> Self {
+        ConnectionWatchdog {
+            policy,
+            networks: Vec::new(),
+            degraded_since: None,
+            last_switch: None,
+        }
+    }
+
+    /// Add a network the watchdog may switch to.
+    pub fn known_network(mut self, ssid: &str, options: ConnectOptions) -> Self {
+        self.networks.push(KnownNetwork { ssid: ssid.to_string(), options });
+        self
+    }
+
+    /// Sample the link once and switch networks if the policy says so.
+    ///
+    /// # Returns
+    ///
+    /// The event, if the state changed or a switch was attempted.
+    pub fn check(&mut self, wifi: &impl WifiBackend) -> Option<WatchdogEvent> {
+        let now = Instant::now();
+        let link = wifi.link_info();
+        let signal = link.as_ref().map(|l| l.signal_strength);
+        let ssid = link.map(|l| l.ssid);
+
+        if let (Some(current), Some(dbm)) = (&ssid, signal) {
+            if dbm >= self.policy.min_signal_dbm {
+                return self.degraded_since.take().map(|_| WatchdogEvent::Recovered {
+                    ssid: current.clone(),
+                    signal_dbm: dbm,
+                });
+            }
+        }
+
+        let since = match self.degraded_since {
+            Some(since) => since,
+            None => {
+                self.degraded_since = Some(now);
+                return Some(WatchdogEvent::Degraded { ssid, signal_dbm: signal });
+            }
+        };
+        if now.duration_since(since) < self.policy.grace {
+            return None;
+        }
+        if let Some(last) = self.last_switch {
+            if now.duration_since(last) < self.policy.cooldown {
+                return None;
+            }
+        }
+
+        let (index, candidate_dbm) = self.best_candidate(wifi, ssid.as_deref(), signal)?;
+        let target = &self.networks[index];
+        self.last_switch = Some(now);
+        self.degraded_since = None;
+
+        match wifi.connect_with(&target.ssid, &target.options) {
+            Ok(()) => {
+                wifi.record_event(EventKind::NetworkSwitched {
+                    from: ssid.clone(),
+                    to: target.ssid.clone(),
+                });
+                Some(WatchdogEvent::Switched { from: ssid, to: target.ssid.clone(), signal_dbm: candidate_dbm })
+            }
+            Err(error) => Some(WatchdogEvent::SwitchFailed { to: target.ssid.clone(), error }),
+        }
+    }
+
+    /// Strongest visible known network that beats the current link by the
+    /// hysteresis margin.
+    fn best_candidate(&self, wifi: &impl WifiBackend, current: Option<&str>, signal: Option<i32>) -> Option<(usize, i32)> {
+        let required = signal.map_or(i32::MIN, |dbm| dbm + self.policy.hysteresis_db);
+        let visible = wifi.scan();
+
+        self.networks
+            .iter()
+            .enumerate()
+            .filter(|(_, known)| Some(known.ssid.as_str()) != current)
+            .filter_map(|(index, known)| {
+                // Only consider the band the network's policy would join
+                let band = known.options.band_policy.map(|policy| policy.choose(&known.ssid, &visible));
+                visible
+                    .iter()
+                    .filter(|n| n.ssid == known.ssid)
+                    .filter(|n| band.is_none_or(|band| band.is_some() && Band::from_frequency(n.frequency) == band))
+                    .map(|n| n.signal_strength)
+                    .max()
+                    .map(|dbm| (index, dbm))
+            })
+            .filter(|&(_, dbm)| dbm >= required)
+            .max_by_key(|&(_, dbm)| dbm)
+    }
+
+    /// Check every `interval` until `stop` is set, passing events to `on_event`.
+    pub fn run(&mut self, wifi: &impl WifiBackend, interval: Duration, stop: &AtomicBool, mut on_event: impl FnMut(&WatchdogEvent)) {
+        while !stop.load(Ordering::Relaxed) {
+            if let Some(event) = self.check(wifi) {
+                on_event(&event);
+            }
+
+            let started = Instant::now();
+            while !stop.load(Ordering::Relaxed) && started.elapsed() < interval {
+                std::thread::sleep((interval - started.elapsed()).min(Duration::from_millis(250)));
+            }
+        }
+    }
+}
@@ -0,0 +1,223 @@
+//! Wake-on-Wireless-LAN.
+//!
+//! With WoWLAN configured the adapter stays associated while the system
+//! sleeps and wakes it when a trigger fires: a magic packet, a frame
+//! matching a pattern, or the loss of the link. The triggers apply to the
+//! next suspend; the driver and, on most systems, the firmware must support
+//! them.
+
+#[repr(C)]
+pub(crate) struct RawWowlanPattern {
+    pattern: *const u8,
+    mask: *const u8,
+    length: i32,
+    offset: i32,
+}
+
+#[repr(C)]
+pub(crate) struct RawWowlanConfig {
+    magic_packet: i32,
+    disconnect: i32,
+    patterns: *const RawWowlanPattern,
+    pattern_count: i32,
+}
+
+#[repr(C)]
+pub(crate) struct RawWowlanStatus {
+    magic_packet: i32,
+    disconnect: i32,
+    pattern_count: i32,
+}
+
+#[repr(C)]
+pub(crate) struct RawWowlanCapabilities {
+    magic_packet: i32,
+    disconnect: i32,
+    max_patterns: i32,
+    min_pattern_length: i32,
+    max_pattern_length: i32,
+    max_pattern_offset: i32,
+}
+
+impl RawWowlanStatus {
+    pub(crate) fn zeroed() -> Self {
+        // SAFETY: the struct is plain integers.
+        unsafe { std::mem::zeroed() }
+    }
+}
+
+impl RawWowlanCapabilities {
+    pub(crate) fn zeroed() -> Self {
+        // SAFETY: the struct is plain integers.
+        unsafe { std::mem::zeroed() }
+    }
+}
+
+/// Wake-on-WLAN triggers the adapter supports, from
+/// [`WiFi::wowlan_capabilities`](crate::WiFi::wowlan_capabilities).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WowlanCapabilities {
+    pub magic_packet: bool,
+    pub disconnect: bool,
+    /// Most patterns that can be active at once, 0 without pattern support.
+    pub max_patterns: usize,
+    pub min_pattern_length: usize,
+    pub max_pattern_length: usize,
+    /// Largest [`WowlanPattern::offset`] the adapter accepts.
+    pub max_pattern_offset: usize,
+}
+
+impl WowlanCapabilities {
+    pub(crate) fn from_raw(raw: &RawWowlanCapabilities) -> Self {
+        WowlanCapabilities {
+            magic_packet: raw.magic_packet != 0,
+            disconnect: raw.disconnect != 0,
+            max_patterns: raw.max_patterns.max(0) as usize,
+            min_pattern_length: raw.min_pattern_length.max(0) as usize,
+            max_pattern_length: raw.max_pattern_length.max(0) as usize,
+            max_pattern_offset: raw.max_pattern_offset.max(0) as usize,
+        }
+    }
+
+    /// Whether the adapter can apply every trigger of `config`.
+    pub fn supports(&self, config: &WowlanConfig) -> bool {
+        (!config.magic_packet || self.magic_packet)
+            && (!config.disconnect || self.disconnect)
+            && config.patterns.len() <= self.max_patterns
+            && config.patterns.iter().all(|p| {
+                (self.min_pattern_length..=self.max_pattern_length).contains(&p.bytes.len())
+                    && p.offset <= self.max_pattern_offset
+            })
+    }
+}
+
+/// Triggers currently configured, from
+/// [`WiFi::wowlan_triggers`](crate::WiFi::wowlan_triggers).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WowlanTriggers {
+    pub magic_packet: bool,
+    pub disconnect: bool,
+    /// Number of active packet patterns.
+    pub patterns: usize,
+}
+
+impl WowlanTriggers {
+    pub(crate) fn from_raw(raw: &RawWowlanStatus) -> Self {
+        WowlanTriggers {
+            magic_packet: raw.magic_packet != 0,
+            disconnect: raw.disconnect != 0,
+            patterns: raw.pattern_count.max(0) as usize,
+        }
+    }
+
+    /// Whether any trigger is set, i.e. WoWLAN is enabled.
+    pub fn enabled(&self) -> bool {
+        self.magic_packet || self.disconnect || self.patterns > 0
+    }
+}
+
+/// A frame pattern that wakes the system.
+///
+/// Bytes are compared against received frames as delivered by the driver,
+/// usually starting at the 802.3 destination address.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WowlanPattern {
+    bytes: Vec<u8>,
+    mask: Vec<u8>,
+    offset: usize,
+}
+
+impl WowlanPattern {
+    /// Match `bytes` exactly.
+    pub fn new(bytes: &[u8]) -> Self {
+        Self::masked(&bytes.iter().copied().map(Some).collect::<Vec<_>>())
+    }
+
+    /// Match the given bytes, with `None` matching any byte.
+    pub fn masked(bytes: &[Option<u8>]) -> Self {
+        let mut mask = vec![0u8; bytes.len().div_ceil(8)];
+        for (i, byte) in bytes.iter().enumerate() {
+            if byte.is_some() {
+                mask[i / 8] |= 1 << (i % 8);
+            }
+        }
+        WowlanPattern {
+            bytes: bytes.iter().map(|byte| byte.unwrap_or(0)).collect(),
+            mask,
+            offset: 0,
+        }
+    }
+
+    /// Start matching `offset` bytes into the frame.
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    fn raw(&self) -> RawWowlanPattern {
+        RawWowlanPattern {
+            pattern: self.bytes.as_ptr(),
+            mask: self.mask.as_ptr(),
+            length: self.bytes.len() as i32,
+            offset: self.offset as i32,
+        }
+    }
+}
+
+/// Triggers for [`WiFi::set_wowlan`](crate::WiFi::set_wowlan).
+///
+/// ```no_run
+/// use wifi_rs::{WiFi, WowlanConfig, WowlanPattern};
+///
+/// let wifi = WiFi::new();
+/// // Wake on a magic packet or any IPv4 frame to UDP port 9
+/// let mut pattern = vec![None; 36];
+/// pattern[12..14].copy_from_slice(&[Some(0x08), Some(0x00)]);
+/// pattern[34..36].copy_from_slice(&[Some(0x00), Some(0x09)]);
+/// let config = WowlanConfig::new().magic_packet().pattern(WowlanPattern::masked(&pattern));
+/// wifi.set_wowlan(&config)?;
+/// # Ok::<(), wifi_rs::WifiError>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WowlanConfig {
+    pub(crate) magic_packet: bool,
+    pub(crate) disconnect: bool,
+    pub(crate) patterns: Vec<WowlanPattern>,
+}
+
+impl WowlanConfig {
+    /// No triggers; applying it disables WoWLAN.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Wake on a magic packet addressed to the adapter.
+    pub fn magic_packet(mut self) -> Self {
+        self.magic_packet = true;
+        self
+    }
+
+    /// Wake when the connection to the access point is lost.
+    pub fn disconnect(mut self) -> Self {
+        self.disconnect = true;
+        self
+    }
+
+    /// Wake on a frame matching `pattern`.
+    pub fn pattern(mut self, pattern: WowlanPattern) -> Self {
+        self.patterns.push(pattern);
+        self
+    }
+
+    /// Call `f` with the C representation, valid for the duration of the call.
+    pub(crate) fn with_raw<T>(&self, f: impl FnOnce(&RawWowlanConfig) -> T) -> T {
+        let patterns: Vec<RawWowlanPattern> = self.patterns.iter().map(WowlanPattern::raw).collect();
+        let raw = RawWowlanConfig {
+            magic_packet: self.magic_packet as i32,
+            disconnect: self.disconnect as i32,
+            patterns: if patterns.is_empty() { std::ptr::null() } else { patterns.as_ptr() },
+            pattern_count: patterns.len() as i32,
+        };
+        f(&raw)
+    }
+}
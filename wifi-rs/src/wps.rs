@@ -0,0 +1,122 @@
+//! WPS PIN helpers and progress of a WPS enrollment.
+//!
+//! WPS PINs are eight decimal digits where the last digit is a checksum over
+//! the first seven. Access points may also use four-digit PINs, which carry no
+//! checksum.
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Compute the WPS checksum digit for a seven digit PIN prefix.
+pub fn checksum(prefix: u32) -> u32 {
+    let mut pin = prefix;
+    let mut accum = 0;
+    while pin > 0 {
+        accum += 3 * (pin % 10);
+        pin /= 10;
+        accum += pin % 10;
+        pin /= 10;
+    }
+    (10 - accum % 10) % 10
+}
+
+/// Check that `pin` is a well-formed WPS PIN.
+///
+/// Accepts four-digit PINs and eight-digit PINs with a valid checksum digit.
+pub fn is_valid_pin(pin: &str) -> bool {
+    if !pin.bytes().all(|b| b.is_ascii_digit()) {
+        return false;
+    }
+    match pin.len() {
+        4 => true,
+        8 => {
+            let value: u32 = pin.parse().unwrap_or(0);
+            checksum(value / 10) == value % 10
+        }
+        _ => false,
+    }
+}
+
+/// Generate a random eight-digit WPS PIN with a valid checksum.
+///
+/// Use this when the device acts as the enrollee and the user types the PIN
+/// into the access point's registrar. Generate a fresh PIN for every session.
+pub fn generate_pin() -> String {
+    let mut hasher = RandomState::new().build_hasher();
+    if let Ok(now) = SystemTime::now().duration_since(UNIX_EPOCH) {
+        hasher.write_u128(now.as_nanos());
+    }
+    let prefix = (hasher.finish() % 10_000_000) as u32;
+    format!("{:07}{}", prefix, checksum(prefix))
+}
+
+#[repr(C)]
+pub(crate) struct RawWpsProgress {
+    stage: i32,
+    failure: i32,
+    config_error: i32,
+}
+
+pub(crate) type RawWpsProgressCallback = extern "C" fn(progress: *const RawWpsProgress, user_data: *mut libc::c_void);
+
+/// Why a WPS enrollment failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WpsFailure {
+    /// No access point completed the exchange within the walk time, or
+    /// the network gave out no address afterwards.
+    Timeout,
+    /// More than one access point is in push-button mode; wait two minutes
+    /// or use a PIN.
+    SessionOverlap,
+    /// The registrar rejected the PIN.
+    WrongPin,
+    /// The access point locked WPS after repeated failures.
+    SetupLocked,
+    /// The exchange failed for another reason, with the WPS configuration
+    /// error the registrar reported (0 if none).
+    Rejected { config_error: u16 },
+}
+
+/// Stage of a WPS enrollment, reported to the callback of
+/// [`WiFi::connect_wps_pbc_with`](crate::WiFi::connect_wps_pbc_with) and
+/// [`WiFi::connect_wps_pin_with`](crate::WiFi::connect_wps_pin_with).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WpsProgress {
+    /// Looking for an access point with an active WPS session.
+    Searching,
+    /// The registration protocol is running with an access point.
+    Exchanging,
+    /// The network credentials arrived, joining the network.
+    CredentialsReceived,
+    Connected,
+    Failed(WpsFailure),
+}
+
+impl WpsProgress {
+    fn from_raw(raw: &RawWpsProgress) -> Self {
+        match raw.stage {
+            0 => WpsProgress::Searching,
+            1 => WpsProgress::Exchanging,
+            2 => WpsProgress::CredentialsReceived,
+            3 => WpsProgress::Connected,
+            _ => WpsProgress::Failed(match raw.failure {
+                2 => WpsFailure::SessionOverlap,
+                3 => WpsFailure::WrongPin,
+                4 => WpsFailure::SetupLocked,
+                5 => WpsFailure::Rejected { config_error: u16::try_from(raw.config_error).unwrap_or(0) },
+                _ => WpsFailure::Timeout,
+            }),
+        }
+    }
+}
+
+pub(crate) extern "C" fn on_progress(progress: *const RawWpsProgress, user_data: *mut libc::c_void) {
+    if progress.is_null() || user_data.is_null() {
+        return;
+    }
+    // SAFETY: user_data is the callback borrowed for the duration of the
+    // blocking connect call, which is the only time progress is reported.
+    let (callback, progress) = unsafe { (&mut *(user_data as *mut &mut dyn FnMut(WpsProgress)), &*progress) };
+    callback(WpsProgress::from_raw(progress));
+}
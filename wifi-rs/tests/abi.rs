@@ -0,0 +1,11 @@
+//! The native library must implement the C API the bindings are written
+//! against. Layouts of the shared structs are checked on the C side by
+//! `test/test_abi.c`.
+
+use wifi_rs::{WiFi, NATIVE_API_VERSION_MAJOR};
+
+#[test]
+fn native_api_major_version_matches() {
+    let (major, _) = WiFi::native_api_version();
+    assert_eq!(major, NATIVE_API_VERSION_MAJOR);
+}
@@ -0,0 +1,112 @@
+//! Arguments that cannot cross the C API are rejected before any native
+//! call, with or without an adapter.
+
+use std::net::Ipv4Addr;
+use wifi_rs::p2p::{P2pConnectConfig, P2pWps};
+use wifi_rs::{ClientCertificate, ConnectOptions, Eap, EapConfig, HotspotConfig, ScanOptions, WiFi, WifiError};
+
+#[test]
+fn nul_in_ssid_is_rejected() {
+    let wifi = WiFi::new();
+    assert_eq!(wifi.connect("bad\0ssid", None), Err(WifiError::InvalidArgument));
+    assert_eq!(wifi.create_hotspot("bad\0ssid"), Err(WifiError::InvalidArgument));
+}
+
+#[test]
+fn nul_in_password_is_rejected() {
+    let wifi = WiFi::new();
+    assert_eq!(wifi.connect("ssid", Some("bad\0password")), Err(WifiError::InvalidArgument));
+    assert_eq!(
+        wifi.connect_with("ssid", &ConnectOptions::password("bad\0password")),
+        Err(WifiError::InvalidArgument)
+    );
+}
+
+#[test]
+fn tdls_peer_must_be_a_mac_address() {
+    let wifi = WiFi::new();
+    assert_eq!(wifi.tdls_setup("aa; rm -rf ~"), Err(WifiError::InvalidArgument));
+    assert_eq!(wifi.tdls_teardown("02:00:00:00:00"), Err(WifiError::InvalidArgument));
+}
+
+#[test]
+fn nul_in_interface_name_is_rejected() {
+    assert!(matches!(WiFi::with_interface("wlan\0"), Err(WifiError::InvalidArgument)));
+}
+
+#[test]
+fn malformed_connect_options_are_rejected() {
+    let wifi = WiFi::new();
+    assert_eq!(wifi.connect_with("ssid", &ConnectOptions::new().bssid("not-a-mac")), Err(WifiError::InvalidArgument));
+    let too_long = ConnectOptions::new().static_ipv4(Ipv4Addr::new(192, 168, 1, 20), 33, None);
+    assert_eq!(wifi.connect_with("ssid", &too_long), Err(WifiError::InvalidArgument));
+}
+
+#[test]
+fn malformed_scan_options_are_rejected() {
+    let wifi = WiFi::new();
+    assert!(matches!(wifi.scan_with_options(&ScanOptions::new().ssid("")), Err(WifiError::InvalidArgument)));
+    assert!(matches!(wifi.scan_with_options(&ScanOptions::new().ssid(&"x".repeat(33))), Err(WifiError::InvalidArgument)));
+    assert!(matches!(wifi.scan_with_options(&ScanOptions::new().channels(&[200])), Err(WifiError::InvalidArgument)));
+}
+
+#[test]
+fn malformed_wps_pin_is_rejected() {
+    let wifi = WiFi::new();
+    assert_eq!(wifi.connect_wps_pin(None, "12ab5678"), Err(WifiError::InvalidArgument));
+    assert_eq!(wifi.connect_wps_pin(None, "12345678"), Err(WifiError::InvalidArgument));
+}
+
+#[test]
+fn roam_target_must_be_a_mac_address() {
+    let wifi = WiFi::new();
+    assert_eq!(wifi.roam_to("aa:bb:cc"), Err(WifiError::InvalidArgument));
+}
+
+#[test]
+fn malformed_country_code_is_rejected() {
+    let wifi = WiFi::new();
+    assert_eq!(wifi.set_regulatory_domain("us"), Err(WifiError::InvalidArgument));
+    assert_eq!(wifi.set_regulatory_domain("USA"), Err(WifiError::InvalidArgument));
+}
+
+#[test]
+fn gateway_inside_the_dhcp_range_is_rejected() {
+    let wifi = WiFi::new();
+    let config = HotspotConfig::new("hotspot").dhcp(
+        Ipv4Addr::new(10, 0, 0, 1)..=Ipv4Addr::new(10, 0, 0, 100),
+        Ipv4Addr::new(10, 0, 0, 50),
+        &[],
+    );
+    assert_eq!(wifi.create_hotspot_with(&config), Err(WifiError::InvalidArgument));
+}
+
+#[test]
+fn malformed_p2p_peer_and_pin_are_rejected() {
+    let wifi = WiFi::new();
+    let peer = P2pConnectConfig::new("aa; reboot");
+    assert!(matches!(wifi.p2p_connect(&peer), Err(WifiError::InvalidArgument)));
+    let pin = P2pConnectConfig::new("02:00:00:00:01:00").wps(P2pWps::EnterPin("12; ls".to_string()));
+    assert!(matches!(wifi.p2p_connect(&pin), Err(WifiError::InvalidArgument)));
+}
+
+#[test]
+fn enterprise_settings_need_a_ca_or_pinned_server() {
+    let wifi = WiFi::new();
+    let peap = || Eap::Peap {
+        identity: "alice@example.edu".to_string(),
+        password: "secret".into(),
+        anonymous_identity: None,
+        ca_cert: None,
+    };
+    assert_eq!(wifi.connect_enterprise("CorpNet", peap()), Err(WifiError::InvalidArgument));
+    let pinned = EapConfig::new(peap()).pin_server_cert_sha256(&"ab".repeat(32));
+    assert_ne!(wifi.connect_enterprise("CorpNet", pinned), Err(WifiError::InvalidArgument));
+}
+
+#[test]
+fn pkcs12_data_is_left_out_of_debug_output() {
+    let certificate = ClientCertificate::Pkcs12 { data: b"PRIVATE KEY".to_vec().into(), password: Some("secret".into()) };
+    let debug = format!("{certificate:?}");
+    assert!(!debug.contains("80, 82") && !debug.contains("secret"), "{debug}");
+}
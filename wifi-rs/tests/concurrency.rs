@@ -0,0 +1,58 @@
+//! Sharing one `WiFi` between threads.
+//!
+//! These run against whatever adapter the machine has, or none: without
+//! hardware every call fails cleanly, which still exercises the locking.
+
+use std::sync::{Arc, Barrier};
+use std::thread;
+
+use wifi_rs::WiFi;
+
+const THREADS: usize = 8;
+const ROUNDS: usize = 4;
+
+#[test]
+fn wifi_is_send_and_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<WiFi>();
+}
+
+#[test]
+fn concurrent_scan_and_status() {
+    let wifi = Arc::new(WiFi::new());
+    let start = Arc::new(Barrier::new(THREADS));
+
+    let workers: Vec<_> = (0..THREADS)
+        .map(|index| {
+            let wifi = Arc::clone(&wifi);
+            let start = Arc::clone(&start);
+            thread::spawn(move || {
+                start.wait();
+                for _ in 0..ROUNDS {
+                    if index % 2 == 0 {
+                        let _ = wifi.scan();
+                    } else {
+                        let _ = wifi.get_status();
+                        let _ = wifi.status_detailed();
+                        let _ = wifi.current_connection();
+                    }
+                }
+            })
+        })
+        .collect();
+
+    for worker in workers {
+        worker.join().expect("worker thread panicked");
+    }
+}
+
+#[test]
+fn handle_moves_to_another_thread() {
+    let wifi = WiFi::new();
+    thread::spawn(move || {
+        let _ = wifi.get_status();
+        drop(wifi);
+    })
+    .join()
+    .expect("worker thread panicked");
+}
@@ -0,0 +1,31 @@
+//! State documents published to Home Assistant. Run with
+//! `--features homeassistant`.
+
+#![cfg(feature = "homeassistant")]
+
+use serde_json::{json, Value};
+use wifi_rs::homeassistant::{HomeAssistant, WifiState};
+
+#[test]
+fn state_payload_carries_ssid_and_signal() {
+    let ha = HomeAssistant::new("kitchen", "Kitchen sensor");
+    let state = WifiState {
+        ssid: Some("Office".to_string()),
+        signal_strength: Some(-52),
+        connected: true,
+        hotspot_active: false,
+    };
+    let message = ha.state_message(&state);
+    assert_eq!(message.topic, "wifi-rs/kitchen/state");
+    assert!(!message.retain);
+    let payload: Value = serde_json::from_str(&message.payload).unwrap();
+    assert_eq!(payload, json!({ "ssid": "Office", "signal_strength": -52, "connected": "ON", "hotspot": "OFF" }));
+}
+
+#[test]
+fn disconnected_state_has_no_ssid_or_signal() {
+    let ha = HomeAssistant::new("kitchen", "Kitchen sensor");
+    let message = ha.state_message(&WifiState::default());
+    let payload: Value = serde_json::from_str(&message.payload).unwrap();
+    assert_eq!(payload, json!({ "ssid": null, "signal_strength": null, "connected": "OFF", "hotspot": "OFF" }));
+}
@@ -0,0 +1,117 @@
+//! Byte layout of the pcapng blocks and radiotap headers.
+
+use std::time::{Duration, UNIX_EPOCH};
+use wifi_rs::pcap::{
+    channel_flags_for_frequency, PcapngWriter, RadiotapInfo, CHANNEL_2GHZ, CHANNEL_5GHZ, CHANNEL_CCK, CHANNEL_OFDM,
+};
+
+fn beacon_radiotap() -> RadiotapInfo {
+    RadiotapInfo {
+        flags: Some(0x10),
+        channel_frequency: Some(2437),
+        channel_flags: CHANNEL_2GHZ | CHANNEL_CCK,
+        signal_dbm: Some(-42),
+        ..RadiotapInfo::default()
+    }
+}
+
+#[test]
+fn radiotap_header_aligns_fields() {
+    #[rustfmt::skip]
+    let expected = [
+        0x00, 0x00, 0x0f, 0x00,  // version, pad, length 15
+        0x2a, 0x00, 0x00, 0x00,  // flags, channel and antenna signal present
+        0x10,                    // flags: FCS at end
+        0x00,                    // pad to align the channel to 2 bytes
+        0x85, 0x09, 0xa0, 0x00,  // 2437 MHz, 2 GHz CCK
+        0xd6,                    // -42 dBm
+    ];
+    assert_eq!(beacon_radiotap().to_header(), expected);
+
+    let timed = RadiotapInfo { tsft: Some(1), antenna: Some(2), ..RadiotapInfo::default() };
+    #[rustfmt::skip]
+    let expected = [
+        0x00, 0x00, 0x11, 0x00,
+        0x01, 0x08, 0x00, 0x00,  // TSFT and antenna present
+        0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x02,
+    ];
+    assert_eq!(timed.to_header(), expected);
+}
+
+#[test]
+fn radiotap_header_round_trips() {
+    let info = RadiotapInfo { tsft: Some(0x0102_0304), rate: Some(12), noise_dbm: Some(-95), ..beacon_radiotap() };
+    let mut packet = info.to_header();
+    let header_len = packet.len();
+    packet.extend_from_slice(&[0x80, 0x00]);
+    assert_eq!(RadiotapInfo::parse(&packet), Some((info, header_len)));
+}
+
+#[test]
+fn radiotap_header_shorter_than_its_fixed_part_is_rejected() {
+    let mut packet = beacon_radiotap().to_header();
+    for length in 0..8 {
+        packet[2] = length;
+        assert_eq!(RadiotapInfo::parse(&packet), None);
+    }
+}
+
+#[test]
+fn channel_flags_follow_the_band() {
+    assert_eq!(channel_flags_for_frequency(2412), CHANNEL_2GHZ);
+    assert_eq!(channel_flags_for_frequency(5180), CHANNEL_5GHZ | CHANNEL_OFDM);
+    assert_eq!(channel_flags_for_frequency(5955), CHANNEL_OFDM);
+    assert_eq!(channel_flags_for_frequency(7115), CHANNEL_OFDM);
+    assert_eq!(channel_flags_for_frequency(900), 0);
+    assert_eq!(channel_flags_for_frequency(60480), 0);
+}
+
+#[test]
+fn writes_section_interface_and_packet_blocks() {
+    let mut writer = PcapngWriter::new(Vec::new(), Some("wlan0mon")).unwrap();
+    let timestamp = UNIX_EPOCH + Duration::from_micros(0x1_0000_0002);
+    writer.write_frame(timestamp, &beacon_radiotap(), &[0x80, 0x00, 0x00]).unwrap();
+    let out = writer.into_inner();
+
+    #[rustfmt::skip]
+    let section = [
+        0x0a, 0x0d, 0x0d, 0x0a,  // section header block
+        0x1c, 0x00, 0x00, 0x00,  // 28 bytes
+        0x4d, 0x3c, 0x2b, 0x1a,  // byte-order magic
+        0x01, 0x00, 0x00, 0x00,  // version 1.0
+        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,  // section length unknown
+        0x1c, 0x00, 0x00, 0x00,
+    ];
+    #[rustfmt::skip]
+    let interface = [
+        0x01, 0x00, 0x00, 0x00,  // interface description block
+        0x24, 0x00, 0x00, 0x00,  // 36 bytes
+        0x7f, 0x00, 0x00, 0x00,  // LINKTYPE_IEEE802_11_RADIOTAP
+        0x00, 0x00, 0x00, 0x00,  // no snap length
+        0x02, 0x00, 0x08, 0x00, b'w', b'l', b'a', b'n', b'0', b'm', b'o', b'n',  // if_name
+        0x00, 0x00, 0x00, 0x00,  // end of options
+        0x24, 0x00, 0x00, 0x00,
+    ];
+    #[rustfmt::skip]
+    let packet_header = [
+        0x06, 0x00, 0x00, 0x00,  // enhanced packet block
+        0x34, 0x00, 0x00, 0x00,  // 52 bytes
+        0x00, 0x00, 0x00, 0x00,  // interface 0
+        0x01, 0x00, 0x00, 0x00,  // timestamp, high word
+        0x02, 0x00, 0x00, 0x00,  // timestamp, low word
+        0x12, 0x00, 0x00, 0x00,  // 18 bytes captured
+        0x12, 0x00, 0x00, 0x00,  // of 18
+    ];
+
+    let (head, rest) = out.split_at(section.len());
+    assert_eq!(head, section);
+    let (head, rest) = rest.split_at(interface.len());
+    assert_eq!(head, interface);
+    let (head, rest) = rest.split_at(packet_header.len());
+    assert_eq!(head, packet_header);
+    let (data, trailer) = rest.split_at(20);
+    assert_eq!(&data[..15], beacon_radiotap().to_header());
+    assert_eq!(&data[15..], [0x80, 0x00, 0x00, 0x00, 0x00]);
+    assert_eq!(trailer, [0x34, 0x00, 0x00, 0x00]);
+}
@@ -0,0 +1,56 @@
+//! Connection policies driven against the mock backend, so their decisions
+//! are covered without an adapter. Run with `--features backend-mock`.
+
+#![cfg(feature = "backend-mock")]
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use wifi_rs::mock::{self, MockBackend};
+use wifi_rs::{ConnectOptions, ConnectionPolicy, PolicyEvent, PolicyNetwork, PolicyRules, WifiBackend, WifiError};
+
+fn policy() -> ConnectionPolicy {
+    ConnectionPolicy::new(PolicyRules::default())
+        .network(PolicyNetwork::new("Office", ConnectOptions::password("secret")).priority(1))
+        .network(PolicyNetwork::new("Phone", ConnectOptions::password("secret")))
+}
+
+#[test]
+fn priority_outweighs_a_slightly_stronger_signal() {
+    let wifi = MockBackend::new().with_networks(vec![
+        mock::network("Phone", 2437, -55),
+        mock::network("Office", 5180, -60),
+        mock::network("Unknown", 2412, -30),
+    ]);
+    let event = policy().check(&wifi);
+    assert!(matches!(event, Some(PolicyEvent::Connected { ref ssid, .. }) if ssid == "Office"), "{:?}", event);
+    assert_eq!(wifi.link_info().map(|link| link.ssid).as_deref(), Some("Office"));
+}
+
+#[test]
+fn skips_a_network_that_failed() {
+    let wifi = MockBackend::new().with_networks(vec![mock::network("Office", 5180, -60)]);
+    wifi.script_connect("Office", Err(WifiError::InvalidCredentials));
+    let mut policy = policy();
+    assert_eq!(
+        policy.check(&wifi),
+        Some(PolicyEvent::ConnectFailed { ssid: "Office".to_string(), error: WifiError::InvalidCredentials })
+    );
+    assert_eq!(policy.check(&wifi), None);
+}
+
+#[test]
+fn auto_connect_reports_a_lost_link_and_reconnects() {
+    // Nothing to join until the test has subscribed
+    let wifi = Arc::new(MockBackend::new());
+    let auto = policy().spawn(Arc::clone(&wifi), Duration::from_secs(3600));
+    let events = auto.subscribe();
+    wifi.set_networks(vec![mock::network("Office", 5180, -60)]);
+    auto.check_now();
+    let timeout = Duration::from_secs(10);
+    assert!(matches!(events.recv_timeout(timeout), Ok(PolicyEvent::Connected { .. })));
+
+    wifi.drop_link();
+    assert_eq!(events.recv_timeout(timeout), Ok(PolicyEvent::LinkLost { ssid: "Office".to_string() }));
+    assert!(matches!(events.recv_timeout(timeout), Ok(PolicyEvent::Connected { .. })));
+}
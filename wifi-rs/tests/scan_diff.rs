@@ -0,0 +1,30 @@
+//! Diffing scans with the mock backend's networks. Run with
+//! `--features backend-mock`.
+
+#![cfg(feature = "backend-mock")]
+
+use wifi_rs::mock;
+use wifi_rs::ScanDiff;
+
+#[test]
+fn reports_appeared_disappeared_and_changed_access_points() {
+    let before = vec![mock::network("Office", 2437, -60), mock::network("Cafe", 2412, -80)];
+    let after = vec![
+        mock::network("Office", 2437, -45),
+        mock::network("Phone", 5180, -50),
+        // A second entry of the same access point does not count twice
+        mock::network("Office", 2437, -70),
+    ];
+    let diff = ScanDiff::between(&before, &after, 10);
+    assert_eq!(diff.appeared.iter().map(|n| n.ssid.as_str()).collect::<Vec<_>>(), ["Phone"]);
+    assert_eq!(diff.disappeared.iter().map(|n| n.ssid.as_str()).collect::<Vec<_>>(), ["Cafe"]);
+    assert_eq!(diff.changed.len(), 1);
+    assert_eq!(diff.changed[0].delta_db(), 15);
+}
+
+#[test]
+fn ignores_changes_below_the_threshold() {
+    let before = vec![mock::network("Office", 2437, -60)];
+    let after = vec![mock::network("Office", 2437, -55)];
+    assert!(ScanDiff::between(&before, &after, 10).is_empty());
+}